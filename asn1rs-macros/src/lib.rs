@@ -1,16 +1,206 @@
+use asn1rs_model::asn::{OctetStringType, StringType, Substitutions};
+use asn1rs_model::generate::rust::RustCodeGenerator;
 use proc_macro::TokenStream;
+use syn::parse::{Parse, ParseStream};
 use syn::parse_macro_input;
+use syn::punctuated::Punctuated;
 use syn::DeriveInput;
+use syn::LitBool;
 use syn::LitStr;
+use syn::Token;
 
 mod derive_protobuf_eq;
 
+/// Generates the Rust types for the given ASN.1 module. If multiple modules are given - separated
+/// by commas - they are resolved together, so that `IMPORTS` from each other can be resolved, and
+/// each module is emitted as `pub mod` named like the file the CLI would generate for it.
+///
+/// Global type substitutions can be passed before the modules as
+/// `substitutions = "OCTET STRING (SIZE(16)) => uuid::Uuid via crate::UuidBytes"`, either
+/// inline or as path of the configuration relative to the directory of the crate manifest.
+/// The Rust type of all string fields is chosen by `string_type = "Arc<str>"`, one of `String`,
+/// `Box<str>`, `Arc<str>` and `SmolStr`, and of all `OCTET STRING` fields by
+/// `octet_string_type = "Bytes"`, one of `Vec<u8>` and `Bytes`. With `heapless = true`, bounded
+/// strings and lists are generated as `heapless` types (requires the `heapless` feature). With
+/// `footprint = true`, each module gets a `footprint()` fn, see `asn1rs::footprint`. With
+/// `encoding_docs = true`, the doc comments of the fields and `CHOICE` variants state the layout
+/// of their UPER encoding. With `borrowed_views = true`, the types with `OCTET STRING` and
+/// `UTF8String` fields get views that borrow them when decoding UPER, like `FooRef<'a>`. Each module gets a `self_test()` fn, which is renamed by
+/// `self_test = "other_name"` - so that the fns of invocations in the same scope do not
+/// collide - or omitted with `self_test = false`.
 #[proc_macro]
 pub fn asn_to_rust(item: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(item as LitStr).value();
-    asn1rs_model::proc_macro::asn_to_rust(&input)
-        .parse()
-        .unwrap()
+    let input = parse_macro_input!(item as AsnToRustInput);
+    let substitutions = match input.substitutions() {
+        Ok(substitutions) => substitutions,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let heapless = input.heapless.as_ref().is_some_and(LitBool::value);
+    let footprint = input.footprint.as_ref().is_some_and(LitBool::value);
+    let encoding_docs = input.encoding_docs.as_ref().is_some_and(LitBool::value);
+    let borrowed_views = input.borrowed_views.as_ref().is_some_and(LitBool::value);
+    let self_test = input.self_test.as_ref().is_none_or(LitBool::value);
+    let self_test_name = input.self_test_name.as_ref().map(LitStr::value);
+    let adjust = |generator: &mut RustCodeGenerator| {
+        generator.set_heapless_types(heapless);
+        generator.set_footprint(footprint);
+        generator.set_encoding_docs(encoding_docs);
+        generator.set_borrowed_views(borrowed_views);
+        generator.set_self_test(self_test);
+        if let Some(name) = &self_test_name {
+            generator.set_self_test_name(name.as_str());
+        }
+    };
+    let modules = input.modules.iter().map(LitStr::value).collect::<Vec<_>>();
+    let output = if let [module] = &modules[..] {
+        asn1rs_model::proc_macro::asn_to_rust_substituted(module, &substitutions, adjust)
+    } else {
+        let modules = modules.iter().map(String::as_str).collect::<Vec<_>>();
+        asn1rs_model::proc_macro::asn_to_rust_modules(&modules, &substitutions, adjust)
+    };
+    output.parse().unwrap()
+}
+
+struct AsnToRustInput {
+    substitutions: Option<LitStr>,
+    string_type: Option<LitStr>,
+    octet_string_type: Option<LitStr>,
+    heapless: Option<LitBool>,
+    footprint: Option<LitBool>,
+    encoding_docs: Option<LitBool>,
+    borrowed_views: Option<LitBool>,
+    self_test: Option<LitBool>,
+    self_test_name: Option<LitStr>,
+    modules: Vec<LitStr>,
+}
+
+impl AsnToRustInput {
+    fn substitutions(&self) -> syn::Result<Substitutions> {
+        let mut substitutions = self.configured_substitutions()?;
+        if let Some(string_type) = &self.string_type {
+            substitutions.set_string_type(Self::named(
+                string_type,
+                &StringType::ALL,
+                StringType::from_name,
+            )?);
+        }
+        if let Some(octet_string_type) = &self.octet_string_type {
+            substitutions.set_octet_string_type(Self::named(
+                octet_string_type,
+                &OctetStringType::ALL,
+                OctetStringType::from_name,
+            )?);
+        }
+        Ok(substitutions)
+    }
+
+    fn named<T: std::fmt::Display>(
+        literal: &LitStr,
+        all: &[T],
+        from_name: fn(&str) -> Option<T>,
+    ) -> syn::Result<T> {
+        from_name(&literal.value()).ok_or_else(|| {
+            let names = all.iter().map(|t| format!("`{t}`")).collect::<Vec<_>>();
+            syn::Error::new(
+                literal.span(),
+                format!("Expected one of {}", names.join(", ")),
+            )
+        })
+    }
+
+    fn configured_substitutions(&self) -> syn::Result<Substitutions> {
+        let Some(config) = &self.substitutions else {
+            return Ok(Substitutions::default());
+        };
+        let mut text = config.value();
+        if !text.contains("=>") {
+            let mut path = std::env::var("CARGO_MANIFEST_DIR")
+                .map(std::path::PathBuf::from)
+                .unwrap_or_default();
+            path.push(&text);
+            text = std::fs::read_to_string(&path).map_err(|e| {
+                syn::Error::new(
+                    config.span(),
+                    format!("Failed to read substitutions {}: {e}", path.display()),
+                )
+            })?;
+        }
+        Substitutions::parse(&text).map_err(|e| syn::Error::new(config.span(), e.to_string()))
+    }
+}
+
+impl Parse for AsnToRustInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut substitutions = None;
+        let mut string_type = None;
+        let mut octet_string_type = None;
+        let mut heapless = None;
+        let mut footprint = None;
+        let mut encoding_docs = None;
+        let mut borrowed_views = None;
+        let mut self_test = None;
+        let mut self_test_name = None;
+        while input.peek(syn::Ident) {
+            let key = input.parse::<syn::Ident>()?;
+            if key == "self_test" {
+                input.parse::<Token![=]>()?;
+                if input.peek(LitStr) {
+                    self_test_name = Some(input.parse::<LitStr>()?);
+                } else {
+                    self_test = Some(input.parse::<LitBool>()?);
+                }
+                input.parse::<Token![,]>()?;
+                continue;
+            }
+            let flag = if key == "heapless" {
+                Some(&mut heapless)
+            } else if key == "footprint" {
+                Some(&mut footprint)
+            } else if key == "encoding_docs" {
+                Some(&mut encoding_docs)
+            } else if key == "borrowed_views" {
+                Some(&mut borrowed_views)
+            } else {
+                None
+            };
+            if let Some(flag) = flag {
+                input.parse::<Token![=]>()?;
+                *flag = Some(input.parse::<LitBool>()?);
+                input.parse::<Token![,]>()?;
+                continue;
+            }
+            let value = if key == "substitutions" {
+                &mut substitutions
+            } else if key == "string_type" {
+                &mut string_type
+            } else if key == "octet_string_type" {
+                &mut octet_string_type
+            } else {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "Expected `substitutions`, `string_type`, `octet_string_type`, `heapless`, `footprint`, `encoding_docs`, `borrowed_views` or `self_test`",
+                ));
+            };
+            input.parse::<Token![=]>()?;
+            *value = Some(input.parse::<LitStr>()?);
+            input.parse::<Token![,]>()?;
+        }
+        let modules = Punctuated::<LitStr, Token![,]>::parse_terminated(input)?
+            .into_iter()
+            .collect();
+        Ok(Self {
+            substitutions,
+            string_type,
+            octet_string_type,
+            heapless,
+            footprint,
+            encoding_docs,
+            borrowed_views,
+            self_test,
+            self_test_name,
+            modules,
+        })
+    }
 }
 
 #[proc_macro_attribute]
@@ -18,6 +208,15 @@ pub fn asn(attr: TokenStream, item: TokenStream) -> TokenStream {
     TokenStream::from(asn1rs_model::proc_macro::parse(attr.into(), item.into()))
 }
 
+/// Verifies at compile time that a hand-written type structurally matches - names, types and
+/// optionality of its fields or variants - the type generated for an ASN.1 definition:
+/// `#[asn_verify(schema = "schema/vehicle.asn1", type = "Vehicle")]`. The schema is either the
+/// ASN.1 module itself or a path relative to the directory of the crate manifest.
+#[proc_macro_attribute]
+pub fn asn_verify(attr: TokenStream, item: TokenStream) -> TokenStream {
+    TokenStream::from(asn1rs_model::proc_macro::verify(attr.into(), item.into()))
+}
+
 #[proc_macro_derive(ProtobufEq)]
 pub fn protobuf_eq(input: TokenStream) -> TokenStream {
     let output = derive_protobuf_eq::expand(parse_macro_input!(input as DeriveInput));
@@ -0,0 +1,358 @@
+use crate::asn::{Asn, ChoiceVariant, Type};
+use crate::model::{Definition, Model};
+use crate::parse::{Location, Token};
+use crate::resolve::Unresolved;
+use std::fmt::{Display, Formatter};
+
+/// Annotations are single-line comments starting with `@`. They apply to the component - or
+/// `CHOICE` alternative - whose name is on the same line or - if the comment is on a line of its
+/// own - to the component in the line below. Annotations that are unknown or do not apply to any
+/// component are reported in [`Model::ignored_annotations`]. Plain comments can be turned into
+/// annotations with an [`AnnotationProfile`].
+///
+/// ```text
+/// Vehicle ::= SEQUENCE {
+///     speed INTEGER (0..255),
+///     vin   IA5String, -- @sensitive
+///     -- @sensitive
+///     owner UTF8String,
+///     fuel  INTEGER (0..1000) -- @unit 0.1 l
+/// }
+/// ```
+#[derive(Debug, Clone, PartialOrd, PartialEq, Eq)]
+pub enum Annotation {
+    /// The value of the component must not show up in logs and is therefore masked by the
+    /// generated `RedactedDebug` implementation
+    Sensitive,
+    /// The physical unit of the value of the component, such as `0.01 degree`
+    Unit(String),
+}
+
+impl Annotation {
+    pub fn from_text(text: &str) -> Option<Self> {
+        match text.split_once(char::is_whitespace) {
+            _ if text == "sensitive" => Some(Self::Sensitive),
+            Some(("unit", unit)) if !unit.trim().is_empty() => {
+                Some(Self::Unit(unit.trim().to_string()))
+            }
+            _ => None,
+        }
+    }
+
+    fn apply_to(self, component: Annotatable) -> Result<(), IgnoredAnnotationKind> {
+        match (self, component) {
+            (Annotation::Sensitive, Annotatable::Field(asn)) => asn.make_sensitive(),
+            (Annotation::Sensitive, Annotatable::Variant(variant)) => variant.sensitive = true,
+            (Annotation::Unit(unit), Annotatable::Field(asn)) => asn.set_unit(unit),
+            (Annotation::Unit(_), Annotatable::Variant(_)) => {
+                return Err(IgnoredAnnotationKind::NotApplicable)
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The text of the annotation as understood by [`Annotation::from_text`]
+impl Display for Annotation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Annotation::Sensitive => write!(f, "sensitive"),
+            Annotation::Unit(unit) => write!(f, "unit {}", unit),
+        }
+    }
+}
+
+/// Splits a unit like `0.1 microdegree` (or `0,1 microdegree`) into its scale factor and the
+/// remaining unit. Units without scale factor have the factor `1.0`.
+///
+/// ```rust
+/// use asn1rs_model::asn::split_unit;
+/// assert_eq!((0.1, "microdegree"), split_unit("0,1 microdegree"));
+/// assert_eq!((1.0, "cm"), split_unit("cm"));
+/// ```
+pub fn split_unit(unit: &str) -> (f64, &str) {
+    unit.split_once(char::is_whitespace)
+        .and_then(|(factor, unit)| {
+            let factor = factor.replace(',', ".").parse::<f64>().ok()?;
+            Some((factor, unit.trim()))
+        })
+        .unwrap_or((1.0, unit))
+}
+
+/// Extracts annotations from plain comments, so that specifications that document units in
+/// prose (such as `-- Unit: 0.01 degree`) do not need to be rewritten to use `-- @unit`.
+///
+/// A pattern is matched case-insensitively anywhere in the comment, `{}` captures the unit.
+/// Without text after `{}`, the unit extends to the end of the comment, trailing `.` and `)`
+/// excluded.
+///
+/// ```rust
+/// use asn1rs_model::asn::{Annotation, AnnotationProfile};
+///
+/// let profile = AnnotationProfile::default().with_unit_pattern("in units of {}");
+/// assert_eq!(
+///     Some(Annotation::Unit("0.1 m/s".to_string())),
+///     profile.extract("Speed in units of 0.1 m/s.")
+/// );
+/// assert_eq!(None, AnnotationProfile::default().extract("Unit: cm"));
+/// assert_eq!(
+///     Some(Annotation::Unit("cm".to_string())),
+///     AnnotationProfile::etsi().extract("Unit: cm")
+/// );
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AnnotationProfile {
+    unit_patterns: Vec<String>,
+}
+
+impl AnnotationProfile {
+    /// The patterns used in ETSI ITS and SAE J2735 specifications
+    pub const ETSI_UNIT_PATTERNS: &'static [&'static str] =
+        &["@unit: {}", "unit: {}", "units: {}", "units of {}"];
+
+    pub fn etsi() -> Self {
+        Self::ETSI_UNIT_PATTERNS
+            .iter()
+            .fold(Self::default(), |profile, pattern| {
+                profile.with_unit_pattern(*pattern)
+            })
+    }
+
+    pub fn with_unit_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.add_unit_pattern(pattern);
+        self
+    }
+
+    pub fn add_unit_pattern(&mut self, pattern: impl Into<String>) {
+        self.unit_patterns.push(pattern.into());
+    }
+
+    pub fn unit_patterns(&self) -> impl Iterator<Item = &str> {
+        self.unit_patterns.iter().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.unit_patterns.is_empty()
+    }
+
+    /// The annotation described by the text of the given comment, if any pattern matches
+    pub fn extract(&self, comment: &str) -> Option<Annotation> {
+        self.unit_patterns
+            .iter()
+            .find_map(|pattern| Self::capture(pattern, comment))
+            .map(Annotation::Unit)
+    }
+
+    fn capture(pattern: &str, comment: &str) -> Option<String> {
+        let (prefix, suffix) = pattern.split_once("{}")?;
+        let lowercase = comment.to_lowercase();
+        if lowercase.len() != comment.len() {
+            // byte offsets would not match, such comments are not worth the effort
+            return None;
+        }
+        let start = lowercase.find(&prefix.to_lowercase())? + prefix.len();
+        let captured = &comment[start..];
+        let captured = if suffix.is_empty() {
+            captured.trim_end().trim_end_matches(['.', ')'])
+        } else {
+            &captured[..captured.to_lowercase().find(&suffix.to_lowercase())?]
+        };
+        Some(captured.trim())
+            .filter(|unit| !unit.is_empty())
+            .map(str::to_string)
+    }
+}
+
+/// An annotation that is not applied, see [`Model::ignored_annotations`]
+#[derive(Debug, Clone, PartialOrd, PartialEq, Eq)]
+pub struct IgnoredAnnotation {
+    pub location: Location,
+    /// The text of the annotation, without the leading `@`
+    pub text: String,
+    pub kind: IgnoredAnnotationKind,
+}
+
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq, Eq)]
+pub enum IgnoredAnnotationKind {
+    /// The text is not understood by [`Annotation::from_text`], such as a misspelled `@sensitve`
+    Unknown,
+    /// There is no component on the line of the annotation or - if the annotation is on a line
+    /// of its own - on the line below
+    NoComponent,
+    /// The annotation cannot be applied to the component, such as `@unit` to a `CHOICE`
+    /// alternative
+    NotApplicable,
+}
+
+impl Display for IgnoredAnnotation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: ",
+            self.location.line(),
+            self.location.column()
+        )?;
+        match self.kind {
+            IgnoredAnnotationKind::Unknown => write!(f, "Unknown annotation `@{}`", self.text),
+            IgnoredAnnotationKind::NoComponent => {
+                write!(f, "The annotation `@{}` belongs to no component", self.text)
+            }
+            IgnoredAnnotationKind::NotApplicable => write!(
+                f,
+                "The annotation `@{}` cannot be applied to the component",
+                self.text
+            ),
+        }
+    }
+}
+
+/// A component of a `SEQUENCE`, `SET` or `CHOICE`, identified by the name of the definition and
+/// the names of the nested components
+struct Component {
+    location: Location,
+    path: Vec<String>,
+}
+
+/// The part of the model an [`Annotation`] is applied to
+enum Annotatable<'a> {
+    Field(&'a mut Asn<Unresolved>),
+    Variant(&'a mut ChoiceVariant<Unresolved>),
+}
+
+impl Model<Asn<Unresolved>> {
+    pub(crate) fn apply_annotations(&mut self, tokens: &[Token], annotations: &[Token]) {
+        let components = Self::components(tokens);
+        for token in annotations {
+            let text = token.annotation().unwrap_or_default();
+            let applied = match Annotation::from_text(text) {
+                None => Err(IgnoredAnnotationKind::Unknown),
+                Some(annotation) => {
+                    Self::annotated_component(token.location(), tokens, &components)
+                        .and_then(|component| self.component_mut(&component.path))
+                        .ok_or(IgnoredAnnotationKind::NoComponent)
+                        .and_then(|component| annotation.apply_to(component))
+                }
+            };
+            if let Err(kind) = applied {
+                self.ignored_annotations.push(IgnoredAnnotation {
+                    location: token.location(),
+                    text: text.to_string(),
+                    kind,
+                });
+            }
+        }
+    }
+
+    fn components(tokens: &[Token]) -> Vec<Component> {
+        let mut components = Vec::new();
+        let mut owners: Vec<Option<String>> = Vec::new(); // one entry for each open '{'
+        let mut current = None;
+        let mut expect_name = false;
+        let mut parentheses = 0_usize;
+
+        for (index, token) in tokens.iter().enumerate() {
+            if parentheses > 0 {
+                match token.separator() {
+                    Some('(') => parentheses += 1,
+                    Some(')') => parentheses -= 1,
+                    _ => {}
+                }
+                continue;
+            }
+
+            match token {
+                Token::Separator(_, '(') => parentheses += 1,
+                Token::Separator(_, '{') => {
+                    owners.push(current.take());
+                    expect_name = true;
+                    continue;
+                }
+                Token::Separator(_, '}') => {
+                    owners.pop();
+                    current = None;
+                }
+                Token::Separator(_, ',') if !owners.is_empty() => {
+                    expect_name = true;
+                    continue;
+                }
+                Token::Text(location, name) if expect_name => {
+                    if let Some(mut path) = owners.iter().cloned().collect::<Option<Vec<_>>>() {
+                        path.push(name.clone());
+                        components.push(Component {
+                            location: *location,
+                            path,
+                        });
+                    }
+                    current = Some(name.clone());
+                }
+                Token::Text(_, name)
+                    if owners.is_empty()
+                        && tokens.get(index + 1).is_some_and(|t| t.eq_separator(':')) =>
+                {
+                    current = Some(name.clone());
+                }
+                _ => {}
+            }
+            expect_name = false;
+        }
+
+        components
+    }
+
+    fn annotated_component<'a>(
+        annotation: Location,
+        tokens: &[Token],
+        components: &'a [Component],
+    ) -> Option<&'a Component> {
+        if tokens
+            .iter()
+            .any(|t| t.location().line() == annotation.line())
+        {
+            components.iter().find(|c| {
+                c.location.line() == annotation.line() && c.location.column() < annotation.column()
+            })
+        } else {
+            let next = tokens.iter().find(|t| t.location() > annotation)?;
+            components.iter().find(|c| c.location == next.location())
+        }
+    }
+
+    fn component_mut(&mut self, path: &[String]) -> Option<Annotatable<'_>> {
+        let (definition, path) = path.split_first()?;
+        self.definitions
+            .iter_mut()
+            .find(|Definition(name, _)| name == definition)
+            .and_then(|Definition(_, asn)| Self::nested_component_mut(&mut asn.r#type, path))
+    }
+
+    fn nested_component_mut<'a>(
+        r#type: &'a mut Type<Unresolved>,
+        path: &[String],
+    ) -> Option<Annotatable<'a>> {
+        let (name, remaining) = path.split_first()?;
+        match r#type {
+            Type::Optional(inner)
+            | Type::Default(inner, _)
+            | Type::Containing(inner, _)
+            | Type::SequenceOf(inner, _)
+            | Type::SetOf(inner, _) => Self::nested_component_mut(inner, path),
+            Type::Sequence(components) | Type::Set(components) => {
+                let field = components.fields.iter_mut().find(|f| f.name == *name)?;
+                if remaining.is_empty() {
+                    Some(Annotatable::Field(&mut field.role))
+                } else {
+                    Self::nested_component_mut(&mut field.role.r#type, remaining)
+                }
+            }
+            Type::Choice(choice) => {
+                let variant = choice.variants_mut().find(|v| v.name == *name)?;
+                if remaining.is_empty() {
+                    Some(Annotatable::Variant(variant))
+                } else {
+                    Self::nested_component_mut(&mut variant.r#type, remaining)
+                }
+            }
+            _ => None,
+        }
+    }
+}
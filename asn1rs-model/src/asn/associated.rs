@@ -0,0 +1,130 @@
+//! The types of `EMBEDDED PDV`, ITU-T X.680 | ISO/IEC 8824-1, 36, and of `EXTERNAL`, 37, are
+//! represented by definitions of their associated types, which are added to every module that
+//! refers to them.
+
+use crate::asn::Asn;
+use crate::model::{Definition, Model};
+use crate::parse::{Error, Token, Tokenizer};
+use crate::resolve::Unresolved;
+
+/// The name of the definition of the associated type of `EMBEDDED PDV`
+pub const EMBEDDED_PDV: &str = "EmbeddedPdv";
+
+/// The name of the definition of the associated type of `EXTERNAL`
+pub const EXTERNAL: &str = "External";
+
+/// The `data-value-descriptor` of `EMBEDDED PDV` is constrained to be absent and therefore
+/// omitted. The alternatives of the `identification` of `EXTERNAL` are constrained to `syntax`,
+/// `presentation-context-id` and `context-negotiation` and therefore limited to these. The
+/// `ObjectDescriptor` (a `GraphicString`) is represented by a `UTF8String`.
+const ASSOCIATED_TYPES: &str = r"AssociatedTypes DEFINITIONS AUTOMATIC TAGS ::=
+BEGIN
+
+EmbeddedPdv ::= [UNIVERSAL 11] SEQUENCE {
+    identification CHOICE {
+        syntaxes SEQUENCE {
+            abstract OBJECT IDENTIFIER,
+            transfer OBJECT IDENTIFIER
+        },
+        syntax OBJECT IDENTIFIER,
+        presentation-context-id INTEGER,
+        context-negotiation SEQUENCE {
+            presentation-context-id INTEGER,
+            transfer-syntax OBJECT IDENTIFIER
+        },
+        transfer-syntax OBJECT IDENTIFIER,
+        fixed NULL
+    },
+    data-value OCTET STRING
+}
+
+External ::= [UNIVERSAL 8] SEQUENCE {
+    identification CHOICE {
+        syntax OBJECT IDENTIFIER,
+        presentation-context-id INTEGER,
+        context-negotiation SEQUENCE {
+            presentation-context-id INTEGER,
+            transfer-syntax OBJECT IDENTIFIER
+        }
+    },
+    data-value-descriptor UTF8String OPTIONAL,
+    data-value OCTET STRING
+}
+
+END";
+
+/// Whether the tokens refer to `EMBEDDED PDV` and to `EXTERNAL`. Like all reserved words, these
+/// are case-sensitive, so that a definition named `External` does not refer to `EXTERNAL`.
+pub(crate) fn referenced_in(tokens: &[Token]) -> (bool, bool) {
+    let embedded_pdv = tokens
+        .windows(2)
+        .any(|pair| pair[0].eq_text("EMBEDDED") && pair[1].eq_text("PDV"));
+    let external = tokens.iter().any(|token| token.eq_text("EXTERNAL"));
+    (embedded_pdv, external)
+}
+
+/// The definitions of the associated types of `EMBEDDED PDV` and of `EXTERNAL`, as selected
+pub(crate) fn definitions(
+    embedded_pdv: bool,
+    external: bool,
+) -> Result<Vec<Definition<Asn<Unresolved>>>, Error> {
+    if !embedded_pdv && !external {
+        // the associated types do not refer to them, which ends the recursion
+        return Ok(Vec::new());
+    }
+    Ok(Model::try_from(Tokenizer.parse(ASSOCIATED_TYPES))?
+        .definitions
+        .into_iter()
+        .filter(|Definition(name, _)| {
+            (embedded_pdv && name == EMBEDDED_PDV) || (external && name == EXTERNAL)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asn::Type;
+
+    fn definitions_of(asn: &str) -> Vec<Definition<Asn<Unresolved>>> {
+        Model::try_from(Tokenizer.parse(asn)).unwrap().definitions
+    }
+
+    #[test]
+    fn test_associated_types_are_added_once_if_referenced() {
+        let definitions = definitions_of(
+            r"Legacy DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Envelope ::= SEQUENCE {
+                content EMBEDDED PDV,
+                other EMBEDDED PDV
+            }
+            END",
+        );
+        assert_eq!(
+            vec!["Envelope", EMBEDDED_PDV],
+            definitions.iter().map(Definition::name).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Type::TypeReference(EMBEDDED_PDV.to_string(), None),
+            match &definitions[0].value().r#type {
+                Type::Sequence(sequence) => sequence.fields[1].role.r#type.clone(),
+                _ => unreachable!(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_reserved_words_are_case_sensitive() {
+        let definitions = definitions_of(
+            r"Legacy DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            External ::= SEQUENCE { embedded BOOLEAN }
+            Wrapper ::= SEQUENCE { inner External, old EXTERNAL }
+            END",
+        );
+        // the own definition is not replaced by the associated type
+        assert_eq!(
+            vec![EXTERNAL, "Wrapper"],
+            definitions.iter().map(Definition::name).collect::<Vec<_>>()
+        );
+    }
+}
@@ -1,4 +1,6 @@
 use crate::asn::Tag;
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Clone, Copy, PartialOrd, PartialEq, Eq, EnumString)]
 #[strum(serialize_all = "lowercase")]
@@ -8,9 +10,12 @@ pub enum Charset {
     Numeric,
     /// ITU-T X.680 | ISO/IEC 8824-1, 43.3
     Printable,
+    /// ITU-T X.680 | ISO/IEC 8824-1, 41, the characters of the Basic Multilingual Plane
+    Bmp,
+    /// ITU-T X.680 | ISO/IEC 8824-1, 41 (Also T61String), represented by the characters
+    /// U+0000..=U+00FF, which are encoded as single octets (ISO/IEC 8859-1)
+    Teletex,
 
-    // /// (Also T61String)
-    // Teletext,
     // Videotext,
     /// Encoding as in ISO/IEC 646 (??)
     Ia5,
@@ -72,11 +77,41 @@ impl Charset {
             Charset::Utf8 => Tag::DEFAULT_UTF8_STRING,
             Charset::Numeric => Tag::DEFAULT_NUMERIC_STRING,
             Charset::Printable => Tag::DEFAULT_PRINTABLE_STRING,
+            Charset::Bmp => Tag::DEFAULT_BMP_STRING,
+            Charset::Teletex => Tag::DEFAULT_TELETEXT_STRING,
             Charset::Ia5 => Tag::DEFAULT_IA5_STRING,
             Charset::Visible => Tag::DEFAULT_VISIBLE_STRING,
         }
     }
 
+    /// Replaces each character that is not valid for the charset with the substitute, without
+    /// allocating if all characters are valid. The substitute is not checked against
+    /// [`Charset::is_valid`].
+    /// ```rust
+    /// use asn1rs_model::asn::Charset;
+    /// assert_eq!("Gr?n", Charset::Ia5.substitute_invalid("Grün", '?'));
+    /// assert_eq!("Grün", Charset::Utf8.substitute_invalid("Grün", '?'));
+    /// ```
+    pub fn substitute_invalid(self, str: &str, substitute: char) -> Cow<'_, str> {
+        if self.find_invalid(str).is_none() {
+            Cow::Borrowed(str)
+        } else {
+            Cow::Owned(
+                str.chars()
+                    .map(|char| {
+                        if self.is_valid(char) {
+                            char
+                        } else {
+                            substitute
+                        }
+                    })
+                    .collect(),
+            )
+        }
+    }
+
+    /// The index of the first character - not byte - that is not valid for the charset, and
+    /// the character
     pub fn find_invalid(self, str: &str) -> Option<(usize, char)> {
         str.chars()
             .enumerate()
@@ -90,8 +125,88 @@ impl Charset {
             Charset::Printable => {
                 matches!(char, ' ' | '\'' ..= ')' | '+' ..= ':' | '=' | '?' | 'A'..='Z' | 'a'..='z'  )
             }
+            Charset::Bmp => matches!(char as u32, 0_u32..=0xFFFF),
+            Charset::Teletex => matches!(char as u32, 0_u32..=0xFF),
             Charset::Ia5 => matches!(char as u32, 0_u32..=127),
             Charset::Visible => matches!(char as u32, 32_u32..=126),
         }
     }
+
+    /// The number of octets of each character in the octet-oriented encodings (BER, OER), `None`
+    /// for the variable number of octets of the UTF-8 encoding of `UTF8String`s
+    pub const fn octets_per_char(self) -> Option<usize> {
+        match self {
+            Charset::Utf8 => None,
+            Charset::Bmp => Some(2),
+            Charset::Numeric
+            | Charset::Printable
+            | Charset::Teletex
+            | Charset::Ia5
+            | Charset::Visible => Some(1),
+        }
+    }
+
+    /// The maximum number of octets of the UTF-8 representation of each valid character, which
+    /// is how the Rust `String`s hold them
+    /// ```rust
+    /// use asn1rs_model::asn::Charset;
+    /// assert!(Charset::IA5_STRING_CHARACTERS.chars().all(|c| c.len_utf8() <= Charset::Ia5.max_utf8_len()));
+    /// assert_eq!("é".len(), Charset::Teletex.max_utf8_len());
+    /// ```
+    pub const fn max_utf8_len(self) -> usize {
+        match self {
+            Charset::Utf8 => 4,
+            Charset::Bmp => 3,
+            Charset::Teletex => 2,
+            Charset::Numeric | Charset::Printable | Charset::Ia5 | Charset::Visible => 1,
+        }
+    }
+
+    /// The octets of the valid characters of the string in the octet-oriented encodings (BER,
+    /// OER): UTF-16BE for `BMPString`s, a single octet per character for `TeletexString`s and
+    /// UTF-8 otherwise
+    /// ```rust
+    /// use asn1rs_model::asn::Charset;
+    /// assert_eq!(vec![0x00, 0x41, 0x20, 0xAC], Charset::Bmp.encode("A€"));
+    /// assert_eq!(vec![0x41, 0xE9], Charset::Teletex.encode("Aé"));
+    /// assert_eq!(Some("A€".to_string()), Charset::Bmp.decode(&[0x00, 0x41, 0x20, 0xAC]));
+    /// assert_eq!(None, Charset::Bmp.decode(&[0x00, 0x41, 0x20]));
+    /// ```
+    pub fn encode(self, str: &str) -> Vec<u8> {
+        match self {
+            Charset::Bmp => str.encode_utf16().flat_map(u16::to_be_bytes).collect(),
+            Charset::Teletex => str.chars().map(|char| char as u8).collect(),
+            _ => str.as_bytes().to_vec(),
+        }
+    }
+
+    /// The string of the octets in the octet-oriented encodings (BER, OER), `None` if the octets
+    /// are not a valid encoding, see [`Charset::encode`]. The characters are not checked
+    /// against [`Charset::is_valid`].
+    pub fn decode(self, octets: &[u8]) -> Option<String> {
+        match self {
+            Charset::Bmp if octets.len().is_multiple_of(2) => octets
+                .chunks_exact(2)
+                .map(|pair| char::from_u32(u32::from(u16::from_be_bytes([pair[0], pair[1]]))))
+                .collect(),
+            Charset::Bmp => None,
+            Charset::Teletex => Some(octets.iter().map(|octet| char::from(*octet)).collect()),
+            _ => String::from_utf8(octets.to_vec()).ok(),
+        }
+    }
+}
+
+/// The name of the ASN.1 string type of the charset, such as `IA5String`
+impl Display for Charset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Charset::Utf8 => "UTF8String",
+            Charset::Numeric => "NumericString",
+            Charset::Printable => "PrintableString",
+            Charset::Bmp => "BMPString",
+            Charset::Teletex => "TeletexString",
+            Charset::Ia5 => "IA5String",
+            Charset::Visible => "VisibleString",
+        })
+    }
 }
@@ -1,5 +1,7 @@
 use crate::parse::Token;
-use crate::resolve::{Error as ResolveError, ResolveState, Resolved, Resolver, Unresolved};
+use crate::resolve::{
+    Error as ResolveError, ResolveState, Resolved, Resolver, Unresolved, ValueResolver,
+};
 use std::convert::TryFrom;
 
 use crate::asn::peekable::PeekableTokens;
@@ -53,6 +55,10 @@ impl<RS: ResolveState> Choice<RS> {
         self.variants.iter()
     }
 
+    pub(crate) fn variants_mut(&mut self) -> impl Iterator<Item = &mut ChoiceVariant<RS>> {
+        self.variants.iter_mut()
+    }
+
     pub fn is_extensible(&self) -> bool {
         self.extension_after.is_some()
     }
@@ -60,6 +66,13 @@ impl<RS: ResolveState> Choice<RS> {
     pub fn extension_after_index(&self) -> Option<usize> {
         self.extension_after
     }
+
+    /// Appends the extension marker for `EXTENSIBILITY IMPLIED`, see [`Type::imply_extensibility`]
+    pub(crate) fn imply_extension_marker(&mut self) {
+        if self.extension_after.is_none() && !self.variants.is_empty() {
+            self.extension_after = Some(self.variants.len() - 1);
+        }
+    }
 }
 
 impl<T: Iterator<Item = Token>> TryFrom<&mut Peekable<T>> for Choice<Unresolved> {
@@ -90,7 +103,12 @@ impl<T: Iterator<Item = Token>> TryFrom<&mut Peekable<T>> for Choice<Unresolved>
                     iter,
                     token.into_text_or_else(Error::no_text)?,
                 )?;
-                choice.variants.push(ChoiceVariant { name, tag, r#type });
+                choice.variants.push(ChoiceVariant {
+                    name,
+                    tag,
+                    r#type,
+                    sensitive: false,
+                });
             }
 
             loop_ctrl_separator!(iter.next_or_err()?);
@@ -105,7 +123,8 @@ impl Choice<Unresolved> {
         R: Resolver<<Resolved as ResolveState>::SizeType>
             + Resolver<<Resolved as ResolveState>::RangeType>
             + Resolver<<Resolved as ResolveState>::ConstType>
-            + Resolver<Type<Unresolved>>,
+            + Resolver<Type<Unresolved>>
+            + ValueResolver,
     >(
         &self,
         resolver: &R,
@@ -126,6 +145,10 @@ pub struct ChoiceVariant<RS: ResolveState = Resolved> {
     pub name: String,
     pub tag: Option<Tag>,
     pub r#type: Type<RS>,
+    /// Whether the value must not show up in logs, see [`Annotation::Sensitive`]
+    ///
+    /// [`Annotation::Sensitive`]: crate::asn::Annotation::Sensitive
+    pub sensitive: bool,
 }
 
 impl<RS: ResolveState> ChoiceVariant<RS> {
@@ -135,6 +158,7 @@ impl<RS: ResolveState> ChoiceVariant<RS> {
             name: name.to_string(),
             tag: None,
             r#type,
+            sensitive: false,
         }
     }
 
@@ -166,7 +190,8 @@ impl ChoiceVariant<Unresolved> {
         R: Resolver<<Resolved as ResolveState>::SizeType>
             + Resolver<<Resolved as ResolveState>::RangeType>
             + Resolver<<Resolved as ResolveState>::ConstType>
-            + Resolver<Type<Unresolved>>,
+            + Resolver<Type<Unresolved>>
+            + ValueResolver,
     >(
         &self,
         resolver: &R,
@@ -175,6 +200,7 @@ impl ChoiceVariant<Unresolved> {
             name: self.name.clone(),
             tag: self.tag,
             r#type: self.r#type.try_resolve(resolver)?,
+            sensitive: self.sensitive,
         })
     }
 }
@@ -4,7 +4,7 @@ use crate::model::{Field, Model};
 use crate::parse::Error;
 use crate::parse::Token;
 use crate::resolve::{Error as ResolveError, Resolved, Resolver};
-use crate::resolve::{ResolveState, Unresolved};
+use crate::resolve::{ResolveState, Unresolved, ValueResolver};
 use std::convert::TryFrom;
 use std::iter::Peekable;
 
@@ -54,12 +54,22 @@ impl<T: Iterator<Item = Token>> TryFrom<&mut Peekable<T>> for ComponentTypeList<
     }
 }
 
+impl<RS: ResolveState> ComponentTypeList<RS> {
+    /// Appends the extension marker for `EXTENSIBILITY IMPLIED`, see [`Type::imply_extensibility`]
+    pub(crate) fn imply_extension_marker(&mut self) {
+        if self.extension_after.is_none() {
+            self.extension_after = Some(self.fields.len().saturating_sub(1));
+        }
+    }
+}
+
 impl ComponentTypeList<Unresolved> {
     pub fn try_resolve<
         R: Resolver<<Resolved as ResolveState>::SizeType>
             + Resolver<<Resolved as ResolveState>::RangeType>
             + Resolver<<Resolved as ResolveState>::ConstType>
-            + Resolver<Type<Unresolved>>,
+            + Resolver<Type<Unresolved>>
+            + ValueResolver,
     >(
         &self,
         resolver: &R,
@@ -63,6 +63,13 @@ impl Enumerated {
     pub fn extension_after_index(&self) -> Option<usize> {
         self.extension_after
     }
+
+    /// Appends the extension marker for `EXTENSIBILITY IMPLIED`, see [`Type::imply_extensibility`]
+    pub(crate) fn imply_extension_marker(&mut self) {
+        if self.extension_after.is_none() && !self.variants.is_empty() {
+            self.extension_after = Some(self.variants.len() - 1);
+        }
+    }
 }
 
 impl<T: Iterator<Item = Token>> TryFrom<&mut Peekable<T>> for Enumerated {
@@ -4,6 +4,8 @@ use crate::parse::Token;
 use std::convert::TryFrom;
 use std::iter::Peekable;
 
+/// The constraints of the components of a `SEQUENCE`, `SET` or `CHOICE` (`WITH COMPONENTS`),
+/// ITU-T X.680 | ISO/IEC 8824-1, 51.8
 #[derive(Debug, Clone, PartialOrd, PartialEq, Eq)]
 pub struct InnerTypeConstraints {
     implicit_all_present: bool,
@@ -67,6 +69,36 @@ impl<T: Iterator<Item = Token>> TryFrom<&mut Peekable<T>> for InnerTypeConstrain
     }
 }
 
+impl InnerTypeConstraints {
+    /// Whether the constraint starts with `...`, so that the components it does not mention are
+    /// not constrained, ITU-T X.680 | ISO/IEC 8824-1, 51.8.5. Otherwise, these must be absent.
+    pub fn is_partial_specification(&self) -> bool {
+        self.implicit_all_present
+    }
+
+    /// The names of the constrained components, in the order of the constraint
+    pub fn components(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(name, ..)| name.as_str())
+    }
+
+    /// The presence constraint of the given component, such as `PRESENT`
+    pub fn presence(&self, component: &str) -> Option<PresenceConstraint> {
+        self.entries
+            .iter()
+            .find(|(name, ..)| name == component)
+            .and_then(|(_, _, presence)| *presence)
+    }
+
+    /// The text of the value constraint of the given component, such as `0..10`
+    pub fn value_constraint(&self, component: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(name, ..)| name == component)
+            .and_then(|(_, value, _)| value.as_ref())
+            .map(|ValueConstraint(value)| value.as_str())
+    }
+}
+
 #[derive(Debug, Clone, PartialOrd, PartialEq, Eq)]
 pub struct ValueConstraint(String);
 
@@ -89,6 +121,7 @@ impl<T: Iterator<Item = Token>> TryFrom<&mut Peekable<T>> for ValueConstraint {
                     }
                     string.push(separator);
                 }
+                Token::Annotation(..) => {}
             }
         }
 
@@ -96,7 +129,7 @@ impl<T: Iterator<Item = Token>> TryFrom<&mut Peekable<T>> for ValueConstraint {
     }
 }
 
-#[derive(Debug, Clone, PartialOrd, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq, Eq)]
 pub enum PresenceConstraint {
     Present,
     Absent,
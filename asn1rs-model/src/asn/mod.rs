@@ -8,6 +8,8 @@ macro_rules! loop_ctrl_separator {
     };
 }
 
+mod annotation;
+mod associated;
 mod bit_string;
 mod charset;
 mod choice;
@@ -17,29 +19,41 @@ mod inner_type_constraints;
 mod integer;
 mod model;
 mod oid;
+mod parameterization;
 mod peekable;
+mod per_visibility;
 mod range;
+mod real;
 mod resolve_scope;
 mod size;
+mod substitution;
 mod tag;
 mod tag_resolver;
 
 pub use crate::asn::bit_string::BitString;
+pub use annotation::split_unit;
+pub use annotation::Annotation;
+pub use annotation::AnnotationProfile;
+pub use annotation::{IgnoredAnnotation, IgnoredAnnotationKind};
 pub use charset::Charset;
 pub use choice::Choice;
 pub use choice::ChoiceVariant;
 pub use components::ComponentTypeList;
 pub use enumerated::Enumerated;
 pub use enumerated::EnumeratedVariant;
-pub use inner_type_constraints::InnerTypeConstraints;
+pub use inner_type_constraints::{InnerTypeConstraints, PresenceConstraint};
 pub use integer::Integer;
 pub use oid::ObjectIdentifier;
 pub use oid::ObjectIdentifierComponent;
 pub use peekable::PeekableTokens;
+pub use per_visibility::{InvisibleConstraint, InvisibleConstraintKind};
 pub use range::Range;
+pub use real::RealPrecision;
 pub use resolve_scope::MultiModuleResolver;
 pub use resolve_scope::ResolveScope;
 pub use size::Size;
+pub use substitution::Error as SubstitutionError;
+pub use substitution::{OctetStringType, StringType, Substitution, Substitutions};
 #[cfg(test)]
 pub(crate) use tag::tests::test_property;
 pub use tag::Tag;
@@ -48,7 +62,7 @@ pub use tag_resolver::TagResolver;
 
 use crate::model::{Field, LiteralValue, Target};
 use crate::resolve::{Error as ResolveError, LitOrRef, TryResolve, Unresolved};
-use crate::resolve::{ResolveState, Resolved, Resolver};
+use crate::resolve::{ResolveState, Resolved, Resolver, ValueResolver};
 use std::fmt::Debug;
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
@@ -56,6 +70,14 @@ pub struct Asn<RS: ResolveState = Resolved> {
     pub tag: Option<Tag>,
     pub r#type: Type<RS>,
     pub default: Option<RS::ConstType>,
+    /// Whether the value must not show up in logs, see [`Annotation::Sensitive`]
+    pub sensitive: bool,
+    /// The physical unit of the value, see [`Annotation::Unit`]
+    pub unit: Option<String>,
+    /// The inner subtype constraint (`WITH COMPONENTS`) of a `SEQUENCE`, `SET`, `CHOICE` or of
+    /// a reference to one of these. It is not PER-visible (ITU-T X.691, 9.3.9) and therefore
+    /// not applied by the encoding rules.
+    pub components: Option<InnerTypeConstraints>,
 }
 
 impl<RS: ResolveState> Target for Asn<RS> {
@@ -73,11 +95,22 @@ impl<RS: ResolveState> Asn<RS> {
         self.default = Some(value);
     }
 
+    pub fn make_sensitive(&mut self) {
+        self.sensitive = true;
+    }
+
+    pub fn set_unit(&mut self, unit: String) {
+        self.unit = Some(unit);
+    }
+
     pub fn opt_tagged(tag: Option<Tag>, r#type: Type<RS>) -> Self {
         Self {
             tag,
             r#type,
             default: None,
+            sensitive: false,
+            unit: None,
+            components: None,
         }
     }
 
@@ -115,7 +148,8 @@ impl Asn<Unresolved> {
         R: Resolver<<Resolved as ResolveState>::SizeType>
             + Resolver<<Resolved as ResolveState>::RangeType>
             + Resolver<<Resolved as ResolveState>::ConstType>
-            + Resolver<Type<Unresolved>>,
+            + Resolver<Type<Unresolved>>
+            + ValueResolver,
     >(
         &self,
         resolver: &R,
@@ -127,33 +161,15 @@ impl Asn<Unresolved> {
                 .default
                 .as_ref()
                 .map(|d| match d {
-                    LitOrRef::Lit(_) => resolver.resolve(d),
+                    LitOrRef::Lit(value) => resolver.resolve_value(value, &self.r#type),
                     LitOrRef::Ref(name) => {
-                        if let Type::TypeReference(referenced_name, _tag) = &r#type {
-                            if let Ok(Type::Enumerated(enumerated)) =
-                                resolver.resolve(&LitOrRef::Ref(referenced_name.to_string()))
-                            {
-                                if let Some(lit) =
-                                    enumerated.variants().find(|v| name.eq(v.name())).map(|v| {
-                                        LiteralValue::EnumeratedVariant(
-                                            referenced_name.to_string(),
-                                            v.name().to_string(),
-                                        )
-                                    })
-                                {
-                                    Ok(lit)
-                                } else {
-                                    resolver.resolve(d)
-                                }
-                            } else {
-                                resolver.resolve(d)
-                            }
-                        } else {
-                            resolver.resolve(d)
-                        }
+                        resolver.resolve_value(&LiteralValue::Reference(name.clone()), &self.r#type)
                     }
                 })
                 .transpose()?,
+            sensitive: self.sensitive,
+            unit: self.unit.clone(),
+            components: self.components.clone(),
             r#type,
         })
     }
@@ -168,10 +184,22 @@ pub enum Type<RS: ResolveState = Resolved> {
     String(Size<RS::SizeType>, Charset),
     /// ITU-T X.680 | ISO/IEC 8824-1, 23
     OctetString(Size<RS::SizeType>),
+    /// ITU-T X.682 | ISO/IEC 8824-3, 11: An `OCTET STRING` containing the encoding of the inner
+    /// type. The optional name (`ENCODED BY`) refers to a transformation that is applied to the
+    /// encoded content before it is wrapped into the `OCTET STRING` (such as compression).
+    Containing(Box<Type<RS>>, Option<String>),
     /// ITU-T X.680 | ISO/IEC 8824-1, 22
     BitString(BitString<RS::SizeType>),
     /// ITU-T X.680 | ISO/IEC 8824-1, 24
     Null,
+    /// ITU-T X.680 | ISO/IEC 8824-1, 21
+    Real(RealPrecision),
+    /// ITU-T X.680 | ISO/IEC 8824-1, 32
+    ObjectIdentifier,
+    /// ITU-T X.680 | ISO/IEC 8824-1, 33
+    RelativeOid,
+    /// ITU-T X.680 | ISO/IEC 8824-1, 34
+    OidIri,
 
     Optional(Box<Type<RS>>),
     Default(Box<Type<RS>>, LiteralValue),
@@ -191,6 +219,9 @@ pub enum Type<RS: ResolveState = Resolved> {
 
     /// ITU-T X.680 | ISO/IEC 8824-1, 16
     TypeReference(String, Option<Tag>),
+
+    /// The inner type, represented by a user provided Rust type, see [`Substitutions`]
+    Substituted(Box<Type<RS>>, Substitution),
 }
 
 impl Type {
@@ -263,6 +294,45 @@ impl<RS: ResolveState> Type<RS> {
             self
         }
     }
+
+    /// ITU-T X.680 | ISO/IEC 8824-1, 13.4: for modules with `EXTENSIBILITY IMPLIED`, every
+    /// `SEQUENCE`, `SET`, `CHOICE` and `ENUMERATED` - including the inlined ones - behaves as if
+    /// it had an extension marker at its end. Explicit extension markers are kept.
+    pub fn imply_extensibility(&mut self) {
+        match self {
+            Type::Sequence(components) | Type::Set(components) => {
+                components
+                    .fields
+                    .iter_mut()
+                    .for_each(|field| field.role.r#type.imply_extensibility());
+                components.imply_extension_marker();
+            }
+            Type::Choice(choice) => {
+                choice
+                    .variants_mut()
+                    .for_each(|variant| variant.r#type.imply_extensibility());
+                choice.imply_extension_marker();
+            }
+            Type::Enumerated(enumerated) => enumerated.imply_extension_marker(),
+            Type::Containing(inner, _)
+            | Type::Optional(inner)
+            | Type::Default(inner, _)
+            | Type::SequenceOf(inner, _)
+            | Type::SetOf(inner, _)
+            | Type::Substituted(inner, _) => inner.imply_extensibility(),
+            Type::Boolean
+            | Type::Integer(_)
+            | Type::String(..)
+            | Type::OctetString(_)
+            | Type::BitString(_)
+            | Type::Null
+            | Type::Real(_)
+            | Type::ObjectIdentifier
+            | Type::RelativeOid
+            | Type::OidIri
+            | Type::TypeReference(..) => {}
+        }
+    }
 }
 
 impl Type<Unresolved> {
@@ -270,7 +340,8 @@ impl Type<Unresolved> {
         R: Resolver<<Resolved as ResolveState>::SizeType>
             + Resolver<<Resolved as ResolveState>::RangeType>
             + Resolver<<Resolved as ResolveState>::ConstType>
-            + Resolver<Type<Unresolved>>,
+            + Resolver<Type<Unresolved>>
+            + ValueResolver,
     >(
         &self,
         resolver: &R,
@@ -280,8 +351,15 @@ impl Type<Unresolved> {
             Type::Integer(integer) => Type::Integer(integer.try_resolve(resolver)?),
             Type::String(size, charset) => Type::String(size.try_resolve(resolver)?, *charset),
             Type::OctetString(size) => Type::OctetString(size.try_resolve(resolver)?),
+            Type::Containing(inner, encoded_by) => {
+                Type::Containing(Box::new(inner.try_resolve(resolver)?), encoded_by.clone())
+            }
             Type::BitString(string) => Type::BitString(string.try_resolve(resolver)?),
             Type::Null => Type::Null,
+            Type::Real(precision) => Type::Real(*precision),
+            Type::ObjectIdentifier => Type::ObjectIdentifier,
+            Type::RelativeOid => Type::RelativeOid,
+            Type::OidIri => Type::OidIri,
             Type::Optional(inner) => Type::Optional(Box::new(inner.try_resolve(resolver)?)),
             Type::Default(inner, default) => {
                 Type::Default(Box::new(inner.try_resolve(resolver)?), default.clone())
@@ -299,6 +377,9 @@ impl Type<Unresolved> {
             Type::Enumerated(e) => Type::Enumerated(e.clone()),
             Type::Choice(c) => Type::Choice(c.try_resolve(resolver)?),
             Type::TypeReference(name, tag) => Type::TypeReference(name.clone(), *tag),
+            Type::Substituted(inner, substitution) => {
+                Type::Substituted(Box::new(inner.try_resolve(resolver)?), substitution.clone())
+            }
         })
     }
 }
@@ -324,20 +405,19 @@ impl LiteralValue {
             {
                 let hex = &slice[1..slice.len() - 2];
                 if hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                    // an odd number of digits is completed by a trailing zero digit
                     let mut vec = Vec::with_capacity((hex.len() + 1) / 2);
-                    let offset = hex.len() % 2;
-
-                    if offset > 0 {
-                        let init = &hex[..offset];
-                        vec.push(u8::from_str_radix(init, 16).ok()?);
+                    for i in (0..hex.len()).step_by(2) {
+                        let digits = &hex[i..hex.len().min(i + 2)];
+                        let byte = u8::from_str_radix(digits, 16).ok()?;
+                        vec.push(if digits.len() == 1 { byte << 4 } else { byte });
                     }
 
-                    for i in 0..hex.len() / 2 {
-                        let position = offset + (2 * i);
-                        vec.push(u8::from_str_radix(&hex[position..position + 2], 16).ok()?);
+                    if hex.len() % 2 == 0 {
+                        LiteralValue::OctetString(vec)
+                    } else {
+                        LiteralValue::BitString(vec, hex.len() as u64 * 4)
                     }
-
-                    LiteralValue::OctetString(vec)
                 } else {
                     return None;
                 }
@@ -348,17 +428,15 @@ impl LiteralValue {
                 let bits = &slice[1..slice.len() - 2];
                 let mut vec = vec![0x00u8; (bits.len() + 7) / 8];
 
-                for (i, bit) in bits.chars().rev().enumerate() {
+                for (i, bit) in bits.chars().enumerate() {
                     if bit == '1' {
-                        let target_index = vec.len() - 1 - (i / 8);
-                        let value = 2u8.pow((i % 8) as u32);
-                        vec[target_index] += value;
+                        vec[i / 8] |= 0x80 >> (i % 8);
                     } else if bit != '0' {
                         return None;
                     }
                 }
 
-                LiteralValue::OctetString(vec)
+                LiteralValue::BitString(vec, bits.len() as u64)
             }
 
             _ => return None,
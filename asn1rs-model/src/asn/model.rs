@@ -1,13 +1,16 @@
+use crate::asn::associated;
 use crate::asn::oid::{ObjectIdentifier, ObjectIdentifierComponent};
+use crate::asn::parameterization;
 use crate::asn::peekable::PeekableTokens;
+use crate::asn::per_visibility;
 use crate::asn::resolve_scope::ResolveScope;
+use crate::asn::TagResolver;
 use crate::asn::{Asn, ComponentTypeList, InnerTypeConstraints, Size, Tag, Type};
-use crate::asn::{BitString, Charset, Choice, Enumerated, Integer};
-use crate::model::{Field, Import, LiteralValue, Model, ValueReference};
-use crate::parse::Location;
+use crate::asn::{BitString, Charset, Choice, Enumerated, Integer, RealPrecision};
+use crate::model::{Definition, Field, Import, LiteralValue, Model, ValueReference};
 use crate::parse::Token;
 use crate::parse::{Error, ErrorKind};
-use crate::resolve::{LitOrRef, ResolveState, Resolved, Resolver, Unresolved};
+use crate::resolve::{LitOrRef, ResolveState, Resolved, Resolver, Unresolved, ValueResolver};
 use crate::rust::Rust;
 use std::convert::TryFrom;
 use std::iter::Peekable;
@@ -15,15 +18,37 @@ use std::vec::IntoIter;
 
 impl Model<Asn<Unresolved>> {
     pub fn try_from(value: Vec<Token>) -> Result<Self, Error> {
-        let mut model = Model::default();
+        let mut model = Self::default();
+        let (annotations, value): (Vec<_>, Vec<_>) =
+            value.into_iter().partition(|t| t.annotation().is_some());
+        let value = parameterization::instantiate_parameterized_types(value)?;
+        let (value, invisible_constraints) = per_visibility::strip_invisible_constraints(value);
+        let (embedded_pdv, external) = associated::referenced_in(&value);
+        model.invisible_constraints = invisible_constraints;
+        let tokens = if annotations.is_empty() {
+            Vec::default()
+        } else {
+            value.clone()
+        };
         let mut iter = value.into_iter().peekable();
 
         model.name = Self::read_name(&mut iter)?;
         model.oid = Self::maybe_read_oid(&mut iter)?;
-        Self::skip_until_after_text_ignore_ascii_case(&mut iter, "BEGIN")?;
+        let extensibility_implied = model.read_module_header(&mut iter)?;
 
         while let Some(token) = iter.next() {
             if token.eq_text_ignore_ascii_case("END") {
+                if extensibility_implied {
+                    for Definition(_name, asn) in &mut model.definitions {
+                        asn.r#type.imply_extensibility();
+                    }
+                }
+                for definition in associated::definitions(embedded_pdv, external)? {
+                    if !model.definitions.iter().any(|d| d.0 == definition.0) {
+                        model.definitions.push(definition);
+                    }
+                }
+                model.apply_annotations(&tokens, &annotations);
                 model.make_names_nice();
                 return Ok(model);
             } else if token.eq_text_ignore_ascii_case("IMPORTS") {
@@ -93,13 +118,25 @@ impl Model<Asn<Unresolved>> {
         Ok(ObjectIdentifier(vec))
     }
 
-    fn skip_until_after_text_ignore_ascii_case(
-        iter: &mut Peekable<IntoIter<Token>>,
-        text: &str,
-    ) -> Result<(), Error> {
-        for t in iter {
-            if t.eq_text_ignore_ascii_case(text) {
-                return Ok(());
+    /// Skips the module header until after `BEGIN`, remembers whether the module is declared
+    /// with `AUTOMATIC TAGS` and returns whether it is declared with `EXTENSIBILITY IMPLIED`
+    fn read_module_header(&mut self, iter: &mut Peekable<IntoIter<Token>>) -> Result<bool, Error> {
+        let mut extensibility_implied = false;
+        while let Some(token) = iter.next() {
+            if token.eq_text_ignore_ascii_case("BEGIN") {
+                return Ok(extensibility_implied);
+            } else if token.eq_text_ignore_ascii_case("AUTOMATIC") {
+                let token = iter.next_or_err()?;
+                if !token.eq_text_ignore_ascii_case("TAGS") {
+                    return Err(Error::unexpected_token(token));
+                }
+                self.automatic_tags = true;
+            } else if token.eq_text_ignore_ascii_case("EXTENSIBILITY") {
+                let token = iter.next_or_err()?;
+                if !token.eq_text_ignore_ascii_case("IMPLIED") {
+                    return Err(Error::unexpected_token(token));
+                }
+                extensibility_implied = true;
             }
         }
         Err(Error::unexpected_end_of_stream())
@@ -136,35 +173,11 @@ impl Model<Asn<Unresolved>> {
         iter.next_separator_eq_or_err('=')?;
 
         let (token, tag) = Self::next_with_opt_tag(iter)?;
-
-        if token.eq_text_ignore_ascii_case("SEQUENCE") {
-            Ok(crate::model::Definition(
-                name,
-                Self::read_sequence_or_sequence_of(iter)?.opt_tagged(tag),
-            ))
-        } else if token.eq_text_ignore_ascii_case("SET") {
-            Ok(crate::model::Definition(
-                name,
-                Self::read_set_or_set_of(iter)?.opt_tagged(tag),
-            ))
-        } else if token.eq_text_ignore_ascii_case("ENUMERATED") {
-            Ok(crate::model::Definition(
-                name,
-                Type::Enumerated(Enumerated::try_from(iter)?).opt_tagged(tag),
-            ))
-        } else if token.eq_text_ignore_ascii_case("CHOICE") {
-            Ok(crate::model::Definition(
-                name,
-                Type::Choice(Choice::try_from(iter)?).opt_tagged(tag),
-            ))
-        } else if let Some(text) = token.text() {
-            Ok(crate::model::Definition(
-                name,
-                Self::read_role_given_text(iter, text.to_string())?.opt_tagged(tag),
-            ))
-        } else {
-            Err(Error::unexpected_token(token))
-        }
+        let text = token.into_text_or_else(Error::unexpected_token)?;
+        let (r#type, components) = Self::read_constrained_role_given_text(iter, text)?;
+        let mut asn = r#type.opt_tagged(tag);
+        asn.components = components;
+        Ok(crate::model::Definition(name, asn))
     }
 
     fn read_value_reference<T: Iterator<Item = Token>>(
@@ -178,12 +191,15 @@ impl Model<Asn<Unresolved>> {
                 iter.next_separator_eq_or_err(':')?;
                 iter.next_separator_eq_or_err(':')?;
                 iter.next_separator_eq_or_err('=')?;
-                Self::read_literal(iter)?
+                Self::read_value(iter)?
             },
             role: Asn {
                 tag: None,
                 r#type,
                 default: None,
+                sensitive: false,
+                unit: None,
+                components: None,
             },
         })
     }
@@ -206,7 +222,13 @@ impl Model<Asn<Unresolved>> {
             {
                 iter.next_text_or_err()?
             } else if iter.peek_is_separator_eq('"') {
-                Self::read_string_literal(iter, '"')?
+                // adjacent cstrings are concatenated
+                let mut string = String::from('"');
+                while iter.peek_is_separator_eq('"') {
+                    string.push_str(&Self::read_string_literal(iter)?);
+                }
+                string.push('"');
+                string
             } else if iter.peek_is_separator_eq('\'') {
                 Self::read_hex_or_bit_string_literal(iter)?
             } else {
@@ -217,50 +239,102 @@ impl Model<Asn<Unresolved>> {
             .ok_or(ErrorKind::InvalidLiteral(Token::Text(location, string)))
     }
 
-    fn read_string_literal<T: Iterator<Item = Token>>(
+    /// Like [`Model::read_literal`], but also reads identifiers - which name the variant of an
+    /// `ENUMERATED` or refer to another value -, the values of `CHOICE` types (`name : value`)
+    /// and the structured values of `SEQUENCE`, `SET`, `SEQUENCE OF` and `SET OF` types
+    /// (`{ name value, ... }` and `{ value, ... }`)
+    fn read_value<T: Iterator<Item = Token>>(
         iter: &mut Peekable<T>,
-        delimiter: char,
-    ) -> Result<String, ErrorKind> {
-        iter.next_separator_eq_or_err(delimiter)?;
-        let token = iter.next_or_err()?;
-
-        let first_text = token.text().unwrap_or_default();
-        let mut string = String::from(delimiter);
-        string.push_str(first_text);
-        let mut prev_loc = Location::at(
-            token.location().line(),
-            token.location().column() + first_text.chars().count(),
-        );
+    ) -> Result<LiteralValue, ErrorKind> {
+        if iter.peek_is_separator_eq('{') {
+            Self::read_structured_value(iter)
+        } else if iter.peek_is_text_and_satisfies(Self::is_identifier) {
+            let name = iter.next_text_or_err()?;
+            if iter.next_is_separator_and_eq(':') {
+                Ok(LiteralValue::Choice(
+                    name,
+                    Box::new(Self::read_value(iter)?),
+                ))
+            } else {
+                Ok(LiteralValue::Reference(name))
+            }
+        } else {
+            Self::read_literal(iter)
+        }
+    }
 
-        loop {
-            match iter.next_or_err()? {
-                t if t.eq_separator(delimiter) => break,
-                Token::Text(loc, str) => {
-                    for _ in prev_loc.column()..loc.column() {
-                        string.push(' ');
-                    }
-                    string.push_str(&str);
-                    prev_loc = Location::at(loc.line(), loc.column() + str.chars().count())
-                }
-                Token::Separator(loc, char) => {
-                    for _ in prev_loc.column()..loc.column() {
-                        string.push(' ');
-                    }
-                    string.push(char);
-                    prev_loc = Location::at(loc.line(), loc.column() + 1)
+    fn read_structured_value<T: Iterator<Item = Token>>(
+        iter: &mut Peekable<T>,
+    ) -> Result<LiteralValue, ErrorKind> {
+        let open = iter.next_if_separator_and_eq('{')?;
+        let mut components = Vec::new();
+        let mut values = Vec::new();
+
+        while !iter.peek_is_separator_eq('}') {
+            if iter.peek_is_text_and_satisfies(Self::is_identifier) {
+                let name = iter.next_text_or_err()?;
+                if iter.peek_is_separator_eq(',') || iter.peek_is_separator_eq('}') {
+                    values.push(LiteralValue::Reference(name));
+                } else if iter.next_is_separator_and_eq(':') {
+                    values.push(LiteralValue::Choice(
+                        name,
+                        Box::new(Self::read_value(iter)?),
+                    ));
+                } else {
+                    components.push((name, Self::read_value(iter)?));
                 }
+            } else {
+                values.push(Self::read_value(iter)?);
+            }
+
+            if !iter.next_is_separator_and_eq(',') {
+                break;
             }
         }
+        iter.next_separator_eq_or_err('}')?;
 
-        string.push(delimiter);
+        // an empty value is resolved to either kind by its type
+        match (components.is_empty(), values.is_empty()) {
+            (_, true) => Ok(LiteralValue::Sequence(components)),
+            (true, false) => Ok(LiteralValue::SequenceOf(values)),
+            (false, false) => Err(ErrorKind::InvalidLiteral(open)),
+        }
+    }
 
-        Ok(string)
+    /// Whether the text is a value reference or an identifier, ITU-T X.680, 12.3 and 12.4
+    fn is_identifier(text: &str) -> bool {
+        text.starts_with(|c: char| c.is_ascii_lowercase())
+            && !text.eq_ignore_ascii_case("true")
+            && !text.eq_ignore_ascii_case("false")
+    }
+
+    /// The content of a cstring, which the [`crate::parse::Tokenizer`] keeps as a single text
+    /// between the quotes
+    fn read_string_literal<T: Iterator<Item = Token>>(
+        iter: &mut Peekable<T>,
+    ) -> Result<String, ErrorKind> {
+        iter.next_separator_eq_or_err('"')?;
+        let content = iter.next_text_or_err()?;
+        iter.next_separator_eq_or_err('"')?;
+        Ok(content)
     }
 
+    /// A bstring or an hstring, which ignore whitespace and line breaks, ITU-T X.680, 12.10 and
+    /// 12.12
     fn read_hex_or_bit_string_literal<T: Iterator<Item = Token>>(
         iter: &mut Peekable<T>,
     ) -> Result<String, ErrorKind> {
-        let mut string = Self::read_string_literal(iter, '\'')?;
+        iter.next_separator_eq_or_err('\'')?;
+        let mut string = String::from('\'');
+        loop {
+            match iter.next_or_err()? {
+                t if t.eq_separator('\'') => break,
+                Token::Text(_, text) => string.push_str(&text),
+                Token::Separator(_, separator) => string.push(separator),
+                Token::Annotation(..) => {}
+            }
+        }
+        string.push('\'');
         match iter.next_text_eq_any_ignore_case_or_err(&["H", "B"])? {
             Token::Text(_, suffix) => string.push_str(&suffix),
             t => return Err(ErrorKind::UnexpectedToken(t)),
@@ -293,35 +367,67 @@ impl Model<Asn<Unresolved>> {
         iter: &mut Peekable<T>,
         text: String,
     ) -> Result<Type<Unresolved>, Error> {
-        Ok(match text.to_ascii_lowercase().as_ref() {
-            "integer" => Type::Integer(Integer::try_from(iter)?),
-            "boolean" => Type::Boolean,
-            "null" => Type::Null,
-            "utf8string" => Type::String(Self::maybe_read_size(iter)?, Charset::Utf8),
-            "ia5string" => Type::String(Self::maybe_read_size(iter)?, Charset::Ia5),
-            "numericstring" => Type::String(Self::maybe_read_size(iter)?, Charset::Numeric),
-            "printablestring" => Type::String(Self::maybe_read_size(iter)?, Charset::Printable),
-            "visiblestring" => Type::String(Self::maybe_read_size(iter)?, Charset::Visible),
-            "octet" => {
-                iter.next_text_eq_ignore_case_or_err("STRING")?;
-                Type::OctetString(Self::maybe_read_size(iter)?)
-            }
-            "bit" => {
-                iter.next_text_eq_ignore_case_or_err("STRING")?;
-                Type::BitString(BitString::try_from(iter)?)
+        // the inner subtype constraints of nested types are not kept
+        Ok(Self::read_constrained_role_given_text(iter, text)?.0)
+    }
+
+    /// Like [`Model::read_role_given_text`], but also returns the inner subtype constraint
+    /// (`WITH COMPONENTS`) that follows a constructed type or a type reference
+    fn read_constrained_role_given_text<T: Iterator<Item = Token>>(
+        iter: &mut Peekable<T>,
+        text: String,
+    ) -> Result<(Type<Unresolved>, Option<InnerTypeConstraints>), Error> {
+        let r#type = {
+            let iter = &mut *iter;
+            match text.to_ascii_lowercase().as_ref() {
+                "integer" => Type::Integer(Integer::try_from(iter)?),
+                "boolean" => Type::Boolean,
+                "null" => Type::Null,
+                "real" => Type::Real(RealPrecision::try_from(iter)?),
+                "utf8string" => Type::String(Self::maybe_read_size(iter)?, Charset::Utf8),
+                "ia5string" => Type::String(Self::maybe_read_size(iter)?, Charset::Ia5),
+                "numericstring" => Type::String(Self::maybe_read_size(iter)?, Charset::Numeric),
+                "printablestring" => Type::String(Self::maybe_read_size(iter)?, Charset::Printable),
+                "visiblestring" => Type::String(Self::maybe_read_size(iter)?, Charset::Visible),
+                "bmpstring" => Type::String(Self::maybe_read_size(iter)?, Charset::Bmp),
+                "teletexstring" | "t61string" => {
+                    Type::String(Self::maybe_read_size(iter)?, Charset::Teletex)
+                }
+                "octet" => {
+                    iter.next_text_eq_ignore_case_or_err("STRING")?;
+                    Self::read_octet_string_constraints(iter)?
+                }
+                "object" => {
+                    iter.next_text_eq_ignore_case_or_err("IDENTIFIER")?;
+                    Type::ObjectIdentifier
+                }
+                "relative-oid" => Type::RelativeOid,
+                "oid-iri" => Type::OidIri,
+                "embedded" if text == "EMBEDDED" && iter.peek_is_text_eq("PDV") => {
+                    iter.next_text_eq_ignore_case_or_err("PDV")?;
+                    Type::TypeReference(associated::EMBEDDED_PDV.to_string(), None)
+                }
+                "external" if text == "EXTERNAL" => {
+                    Type::TypeReference(associated::EXTERNAL.to_string(), None)
+                }
+                "bit" => {
+                    iter.next_text_eq_ignore_case_or_err("STRING")?;
+                    Type::BitString(BitString::try_from(iter)?)
+                }
+                "enumerated" => Type::Enumerated(Enumerated::try_from(iter)?),
+                "choice" => Type::Choice(Choice::try_from(iter)?),
+                "sequence" => Self::read_sequence_or_sequence_of(iter)?,
+                "set" => Self::read_set_or_set_of(iter)?,
+                _ => Type::TypeReference(text, None),
             }
-            "enumerated" => Type::Enumerated(Enumerated::try_from(iter)?),
-            "choice" => Type::Choice(Choice::try_from(iter)?),
-            "sequence" => Self::read_sequence_or_sequence_of(iter)?,
-            "set" => Self::read_set_or_set_of(iter)?,
-            _ => {
-                // TODO use InnerTypeConstraints to flatten TypeReference to an actual type and
-                //      prevent tuple-type nesting in the generated rust and other code by copying
-                //      over the fields and adding these additional constraints
-                let _ = Self::maybe_read_with_components_constraint(iter)?;
-                Type::TypeReference(text, None)
+        };
+        let components = match r#type {
+            Type::Sequence(_) | Type::Set(_) | Type::Choice(_) | Type::TypeReference(..) => {
+                Self::maybe_read_with_components_constraint(iter)?
             }
-        })
+            _ => None,
+        };
+        Ok((r#type, components))
     }
 
     fn maybe_read_with_components_constraint<T: Iterator<Item = Token>>(
@@ -336,6 +442,39 @@ impl Model<Asn<Unresolved>> {
         }
     }
 
+    /// ITU-T X.682 | ISO/IEC 8824-3, 11 for the contents constraint (`CONTAINING`)
+    fn read_octet_string_constraints<T: Iterator<Item = Token>>(
+        iter: &mut Peekable<T>,
+    ) -> Result<Type<Unresolved>, Error> {
+        if iter.next_is_separator_and_eq('(') {
+            let result = if iter.next_is_text_and_eq_ignore_case("CONTAINING") {
+                let inner = Self::read_role(iter)?;
+                let encoded_by = if iter.next_is_text_and_eq_ignore_case("ENCODED") {
+                    iter.next_text_eq_ignore_case_or_err("BY")?;
+                    if iter.next_is_separator_and_eq('{') {
+                        // the object identifier of encoding rules, which are not supported
+                        // and therefore ignored
+                        while !iter.next_or_err()?.eq_separator('}') {}
+                        None
+                    } else {
+                        Some(iter.next_text_or_err()?)
+                    }
+                } else {
+                    None
+                };
+                Type::Containing(Box::new(inner), encoded_by)
+            } else {
+                Type::OctetString(Size::try_from(&mut *iter)?)
+            };
+            iter.next_separator_eq_or_err(')')?;
+            Ok(result)
+        } else if iter.peek_is_text_eq_ignore_case("SIZE") {
+            Ok(Type::OctetString(Size::try_from(iter)?))
+        } else {
+            Ok(Type::OctetString(Size::Any))
+        }
+    }
+
     pub(crate) fn maybe_read_size<T: Iterator<Item = Token>>(
         iter: &mut Peekable<T>,
     ) -> Result<Size<<Unresolved as ResolveState>::SizeType>, Error> {
@@ -379,11 +518,13 @@ impl Model<Asn<Unresolved>> {
     ) -> Result<(Field<Asn<Unresolved>>, bool), Error> {
         let name = iter.next_text_or_err()?;
         let (token, tag) = Self::next_with_opt_tag(iter)?;
+        let (r#type, components) =
+            Self::read_constrained_role_given_text(iter, token.into_text_or_else(Error::no_text)?)?;
         let mut field = Field {
             name,
-            role: Self::read_role_given_text(iter, token.into_text_or_else(Error::no_text)?)?
-                .opt_tagged(tag),
+            role: r#type.opt_tagged(tag),
         };
+        field.role.components = components;
 
         let token = {
             let token = iter.next_or_err()?;
@@ -394,7 +535,8 @@ impl Model<Asn<Unresolved>> {
                 if cfg!(feature = "debug-proc-macro") {
                     println!("TOKEN:::: {:?}", token);
                 }
-                field.role.set_default(match Self::read_literal(iter) {
+                field.role.set_default(match Self::read_value(iter) {
+                    Ok(LiteralValue::Reference(name)) => LitOrRef::Ref(name),
                     Ok(value) => LitOrRef::Lit(value),
                     Err(ErrorKind::UnsupportedLiteral(token, ..)) if token.is_text() => {
                         LitOrRef::Ref(iter.next_text_or_err()?)
@@ -501,9 +643,13 @@ impl<RS: ResolveState> Model<Asn<RS>> {
 }
 
 impl Model<Asn<Unresolved>> {
+    /// Resolves the model and fails if two components of a `SET` have the same tag, see
+    /// [`TagResolver::try_check_set_tags`]
     #[inline]
     pub fn try_resolve(&self) -> Result<Model<Asn<Resolved>>, crate::resolve::Error> {
-        ResolveScope::from(self).try_resolve()
+        let model = ResolveScope::from(self).try_resolve()?;
+        TagResolver::new(&model, &[]).try_check_set_tags()?;
+        Ok(model)
     }
 }
 
@@ -513,7 +659,8 @@ impl Field<Asn<Unresolved>> {
         R: Resolver<<Resolved as ResolveState>::SizeType>
             + Resolver<<Resolved as ResolveState>::RangeType>
             + Resolver<<Resolved as ResolveState>::ConstType>
-            + Resolver<Type<Unresolved>>,
+            + Resolver<Type<Unresolved>>
+            + ValueResolver,
     >(
         &self,
         resolver: &R,
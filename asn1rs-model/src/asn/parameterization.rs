@@ -0,0 +1,476 @@
+//! ITU-T X.683 | ISO/IEC 8824-4: parameterized type definitions like
+//! `Container {Type} ::= SEQUENCE { item Type }`.
+//!
+//! Parameterized definitions are removed from the token stream before the model is built. Each
+//! reference like `Container {Payload}` is replaced by the name of an instance, `Container-Payload`,
+//! which is appended to the module as an ordinary definition: a copy of the parameterized
+//! definition with its parameters replaced by the arguments of the reference. Because of this,
+//! the instances are resolved and generated like any other definition. References with the same
+//! arguments share an instance, an instance whose name is already taken by another definition is
+//! rejected.
+
+use crate::asn::per_visibility::index_of_closing_bracket;
+use crate::parse::{Error, Token};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// The maximum number of nested instantiations, which is only exceeded by a definition that
+/// references itself with ever growing arguments
+const MAX_NESTING: usize = 32;
+
+struct Parameterized {
+    parameters: Vec<String>,
+    body: Vec<Token>,
+}
+
+struct Instance {
+    name: Token,
+    body: Vec<Token>,
+    nesting: usize,
+}
+
+/// The names of the instances by their parameterized definition and arguments, and all names
+/// that are taken by a definition or an instance
+#[derive(Default)]
+struct Instances {
+    by_arguments: HashMap<(String, Vec<Vec<String>>), String>,
+    names: HashSet<String>,
+}
+
+/// Replaces all references to parameterized definitions with the names of their instances and
+/// appends the definitions of the instances to the end of the module
+pub(crate) fn instantiate_parameterized_types(tokens: Vec<Token>) -> Result<Vec<Token>, Error> {
+    let begin = match tokens
+        .iter()
+        .position(|t| t.eq_text_ignore_ascii_case("BEGIN"))
+    {
+        Some(begin) => begin + 1,
+        None => return Ok(tokens),
+    };
+
+    let (mut result, definitions) = take_parameterized_definitions(tokens, begin)?;
+    if definitions.is_empty() {
+        return Ok(result);
+    }
+
+    let end = result
+        .iter()
+        .rposition(|t| t.eq_text_ignore_ascii_case("END"))
+        .unwrap_or(result.len());
+    let trailer = result.split_off(end);
+    let body = result.split_off(begin);
+
+    let mut known = Instances {
+        names: definition_names(&body)
+            .chain(definitions.keys().cloned())
+            .collect(),
+        ..Instances::default()
+    };
+    let mut pending = VecDeque::new();
+    result.extend(replace_references(
+        &body,
+        &definitions,
+        &mut known,
+        &mut pending,
+        0,
+    )?);
+
+    while let Some(instance) = pending.pop_front() {
+        let body = replace_references(
+            &instance.body,
+            &definitions,
+            &mut known,
+            &mut pending,
+            instance.nesting,
+        )?;
+        let location = instance.name.location();
+        result.push(instance.name);
+        result.push(Token::Separator(location, ':'));
+        result.push(Token::Separator(location, ':'));
+        result.push(Token::Separator(location, '='));
+        result.extend(body);
+    }
+
+    result.extend(trailer);
+    Ok(result)
+}
+
+/// Removes the parameterized definitions after the token at `begin` from the tokens
+fn take_parameterized_definitions(
+    tokens: Vec<Token>,
+    begin: usize,
+) -> Result<(Vec<Token>, HashMap<String, Parameterized>), Error> {
+    let mut result = Vec::with_capacity(tokens.len());
+    let mut definitions = HashMap::new();
+    let mut index = 0;
+
+    while let Some(token) = tokens.get(index) {
+        match parameter_list_end(&tokens, index).filter(|_| index >= begin) {
+            Some(end) => {
+                let parameters = split_list(&tokens[index + 2..end])
+                    .into_iter()
+                    .map(|parameter| {
+                        // the name of `Type` or `Governor : name`
+                        parameter
+                            .iter()
+                            .rev()
+                            .find_map(Token::text)
+                            .map(str::to_string)
+                            .ok_or_else(|| Error::unexpected_token(tokens[end].clone()))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let body_start = end + 4;
+                let body_end = end_of_assignment(&tokens, body_start);
+                definitions.insert(
+                    token.text().unwrap_or_default().to_string(),
+                    Parameterized {
+                        parameters,
+                        body: tokens[body_start..body_end].to_vec(),
+                    },
+                );
+                index = body_end;
+            }
+            None => {
+                result.push(token.clone());
+                index += 1;
+            }
+        }
+    }
+
+    Ok((result, definitions))
+}
+
+fn replace_references(
+    tokens: &[Token],
+    definitions: &HashMap<String, Parameterized>,
+    known: &mut Instances,
+    pending: &mut VecDeque<Instance>,
+    nesting: usize,
+) -> Result<Vec<Token>, Error> {
+    let mut result = Vec::with_capacity(tokens.len());
+    let mut in_string = false;
+    let mut index = 0;
+
+    while let Some(token) = tokens.get(index) {
+        let definition = token
+            .text()
+            .and_then(|name| definitions.get(name))
+            .filter(|_| !in_string && tokens.get(index + 1).is_some_and(|t| t.eq_separator('{')));
+
+        if let Some(definition) = definition {
+            let end = index_of_closing_bracket(tokens, index + 2);
+            if end >= tokens.len() {
+                return Err(Error::unexpected_end_of_stream());
+            }
+
+            let arguments = split_list(&tokens[index + 2..end]);
+            if arguments.len() != definition.parameters.len() {
+                return Err(Error::invalid_argument_count(
+                    token.clone(),
+                    definition.parameters.len(),
+                ));
+            }
+
+            let key = (
+                token.text().unwrap_or_default().to_string(),
+                arguments
+                    .iter()
+                    .map(|argument| argument.iter().map(ToString::to_string).collect())
+                    .collect::<Vec<_>>(),
+            );
+            let name = match known.by_arguments.get(&key) {
+                Some(name) => name.clone(),
+                None => {
+                    let name = instance_name(&key.0, &arguments);
+                    if !known.names.insert(name.clone()) {
+                        return Err(Error::instance_name_clash(token.clone(), name));
+                    }
+                    if nesting >= MAX_NESTING {
+                        return Err(Error::parameterization_too_deep(token.clone()));
+                    }
+                    known.by_arguments.insert(key, name.clone());
+                    pending.push_back(Instance {
+                        name: Token::Text(token.location(), name.clone()),
+                        body: substitute(&definition.body, &definition.parameters, &arguments),
+                        nesting: nesting + 1,
+                    });
+                    name
+                }
+            };
+
+            result.push(Token::Text(token.location(), name));
+            index = end + 1;
+        } else {
+            if token.eq_separator('"') {
+                in_string = !in_string;
+            }
+            result.push(token.clone());
+            index += 1;
+        }
+    }
+
+    Ok(result)
+}
+
+/// Replaces each parameter in the body by the tokens of its argument
+fn substitute(body: &[Token], parameters: &[String], arguments: &[Vec<Token>]) -> Vec<Token> {
+    let mut result = Vec::with_capacity(body.len());
+    let mut in_string = false;
+    for token in body {
+        if token.eq_separator('"') {
+            in_string = !in_string;
+        }
+        let argument = token
+            .text()
+            .filter(|_| !in_string)
+            .and_then(|text| parameters.iter().position(|p| p == text))
+            .map(|index| &arguments[index]);
+        match argument {
+            Some(argument) => result.extend(argument.iter().cloned()),
+            None => result.push(token.clone()),
+        }
+    }
+    result
+}
+
+/// The name of the definition followed by the text of the arguments, such as
+/// `Container-Payload` for `Container {Payload}` or `Bounded-minus5-255` for
+/// `Bounded {-5, 255}`
+fn instance_name(name: &str, arguments: &[Vec<Token>]) -> String {
+    let mut instance = name.to_string();
+    for text in arguments.iter().flatten().filter_map(Token::text) {
+        let (sign, text) = match text.strip_prefix('-') {
+            Some(text) => ("minus", text),
+            None => ("", text),
+        };
+        let text = text
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+            .collect::<String>();
+        let text = text.trim_matches('-');
+        if !text.is_empty() {
+            instance.push('-');
+            instance.push_str(sign);
+            instance.push_str(text);
+        }
+    }
+    instance
+}
+
+/// The names of the definitions and values assigned in the tokens
+fn definition_names(tokens: &[Token]) -> impl Iterator<Item = String> + '_ {
+    tokens
+        .iter()
+        .enumerate()
+        .filter(move |(index, _)| is_assignment(tokens, index + 1))
+        .filter_map(|(_, token)| token.text())
+        .map(str::to_string)
+}
+
+/// Splits the tokens at the commas that are not nested in brackets
+fn split_list(tokens: &[Token]) -> Vec<Vec<Token>> {
+    let mut list = Vec::new();
+    let mut current = Vec::new();
+    let mut depth = 0_usize;
+    for token in tokens {
+        match token.separator() {
+            Some('(' | '{' | '[') => depth += 1,
+            Some(')' | '}' | ']') => depth = depth.saturating_sub(1),
+            Some(',') if depth == 0 => {
+                list.push(core::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(token.clone());
+    }
+    if !current.is_empty() || !list.is_empty() {
+        list.push(current);
+    }
+    list
+}
+
+/// Whether the tokens at `index` are `::=`
+fn is_assignment(tokens: &[Token], index: usize) -> bool {
+    tokens
+        .get(index..index + 3)
+        .is_some_and(|t| t[0].eq_separator(':') && t[1].eq_separator(':') && t[2].eq_separator('='))
+}
+
+/// If the tokens at `index` are the head of a parameterized definition, `Name { ... } ::=`, this
+/// returns the index of the `}` closing the parameter list
+fn parameter_list_end(tokens: &[Token], index: usize) -> Option<usize> {
+    let _name = tokens.get(index)?.text()?;
+    if !tokens.get(index + 1)?.eq_separator('{') {
+        return None;
+    }
+    let end = index_of_closing_bracket(tokens, index + 2);
+    Some(end).filter(|end| is_assignment(tokens, end + 1))
+}
+
+/// The index of the first token after the assignment whose body starts at `start`, which is
+/// either the start of the next assignment or the `END` of the module
+fn end_of_assignment(tokens: &[Token], start: usize) -> usize {
+    (start + 1..tokens.len())
+        .find(|index| {
+            let token = &tokens[*index];
+            token.eq_text_ignore_ascii_case("END") && *index + 1 == tokens.len()
+                || token.is_text() && is_assignment(tokens, index + 1)
+                || parameter_list_end(tokens, *index).is_some()
+                || is_value_assignment(tokens, *index)
+        })
+        .unwrap_or(tokens.len())
+}
+
+/// Whether the tokens at `index` are the head of a value assignment like `max INTEGER ::=`
+fn is_value_assignment(tokens: &[Token], index: usize) -> bool {
+    let starts_lowercase = tokens[index]
+        .text()
+        .and_then(|text| text.chars().next())
+        .is_some_and(char::is_lowercase);
+    if !starts_lowercase {
+        return false;
+    }
+    let mut type_end = index + 1;
+    while tokens.get(type_end).is_some_and(Token::is_text) {
+        type_end += 1;
+    }
+    type_end > index + 1 && is_assignment(tokens, type_end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::{ErrorKind, Tokenizer};
+
+    fn instantiate(asn: &str) -> Result<String, Error> {
+        Ok(instantiate_parameterized_types(Tokenizer.parse(asn))?
+            .iter()
+            .map(|t| match t {
+                Token::Text(_, text) => text.clone(),
+                other => other.separator().unwrap_or_default().to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" "))
+    }
+
+    #[test]
+    fn test_reference_is_replaced_by_instance() {
+        assert_eq!(
+            "BEGIN A : : = Container-B B : : = BOOLEAN \
+             Container-B : : = SEQUENCE { item B } END",
+            instantiate(
+                "BEGIN
+                Container {Type} ::= SEQUENCE { item Type }
+                A ::= Container {B}
+                B ::= BOOLEAN
+                END"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_instances_are_shared_and_nested() {
+        assert_eq!(
+            "BEGIN A : : = SEQUENCE { a Outer-B , b Outer-B } \
+             Outer-B : : = SEQUENCE OF Inner-B-10 \
+             Inner-B-10 : : = SEQUENCE ( SIZE ( 1 . . 10 ) ) OF B END",
+            instantiate(
+                "BEGIN
+                A ::= SEQUENCE { a Outer {B}, b Outer {B} }
+                Outer {Type} ::= SEQUENCE OF Inner {Type, 10}
+                Inner {Type, INTEGER:max} ::= SEQUENCE (SIZE(1..max)) OF Type
+                END"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_instances_keep_the_sign_of_their_arguments() {
+        assert_eq!(
+            "BEGIN A : : = SEQUENCE { a Range-minus5-5 , b Range-5-5 , c Range-minus5-5 } \
+             Range-minus5-5 : : = INTEGER ( -5 . . 5 ) \
+             Range-5-5 : : = INTEGER ( 5 . . 5 ) END",
+            instantiate(
+                "BEGIN
+                Range {INTEGER:min, INTEGER:max} ::= INTEGER (min..max)
+                A ::= SEQUENCE { a Range {-5, 5}, b Range {5, 5}, c Range {-5, 5} }
+                END"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_instance_name_clash_is_rejected() {
+        let error = instantiate(
+            "BEGIN
+            Container {Type} ::= SEQUENCE { item Type }
+            Container-Payload ::= BOOLEAN
+            A ::= Container {Payload}
+            Payload ::= INTEGER
+            END",
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ErrorKind::InstanceNameClash(Token::Text(_, reference), name)
+                if reference == "Container" && name == "Container-Payload"
+        ));
+
+        let error = instantiate(
+            "BEGIN
+            Pair {A, B} ::= SEQUENCE { a A, b B }
+            C ::= SEQUENCE { a Pair {X, Y-Z}, b Pair {X-Y, Z} }
+            END",
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ErrorKind::InstanceNameClash(_, name) if name == "Pair-X-Y-Z"
+        ));
+    }
+
+    #[test]
+    fn test_module_without_parameterized_definitions_is_unchanged() {
+        let asn = "Module { iso 1 } DEFINITIONS ::= BEGIN \
+            max INTEGER ::= 5 \
+            A ::= SEQUENCE { a INTEGER (0..max) } \
+            END";
+        assert_eq!(
+            Tokenizer.parse(asn),
+            instantiate_parameterized_types(Tokenizer.parse(asn)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_argument_count() {
+        let error = instantiate(
+            "BEGIN
+            Pair {A, B} ::= SEQUENCE { a A, b B }
+            C ::= Pair {BOOLEAN}
+            END",
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ErrorKind::InvalidArgumentCount(Token::Text(_, name), 2) if name == "Pair"
+        ));
+    }
+
+    #[test]
+    fn test_infinite_instantiation_is_rejected() {
+        let error = instantiate(
+            "BEGIN
+            List {T} ::= SEQUENCE { value T, next List {SEQUENCE OF T} OPTIONAL }
+            A ::= List {BOOLEAN}
+            END",
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ErrorKind::ParameterizationTooDeep(..)
+        ));
+    }
+}
@@ -87,7 +87,7 @@ impl<T: Iterator<Item = Token>> PeekableTokens for Peekable<T> {
             let token = self.next_or_err()?;
             debug_assert!(token.text().is_some());
             match token {
-                Token::Separator(..) => unreachable!(),
+                Token::Separator(..) | Token::Annotation(..) => unreachable!(),
                 Token::Text(_, text) => Ok(text),
             }
         } else {
@@ -0,0 +1,280 @@
+//! ITU-T X.691 | ISO/IEC 8825-2, 9.3: only PER-visible constraints influence the encoding.
+//!
+//! Constraints that are not PER-visible are accepted while parsing, but are removed from the
+//! token stream before the model is built. Because of this, the descriptors of the generated
+//! types never see them and no encoder can honor them by accident. Each of them is reported as
+//! [`InvisibleConstraint`] in [`Model::invisible_constraints`](crate::Model).
+
+use crate::parse::{Location, Token};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialOrd, PartialEq, Eq)]
+pub struct InvisibleConstraint {
+    /// The name of the definition the constraint was found in
+    pub definition: Option<String>,
+    pub location: Location,
+    pub kind: InvisibleConstraintKind,
+}
+
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq, Eq)]
+pub enum InvisibleConstraintKind {
+    /// `PATTERN` constraints, ITU-T X.691 | ISO/IEC 8825-2, 9.3.6
+    Pattern,
+    /// `FROM` on a `UTF8String`, which is not a known-multiplier character string type,
+    /// ITU-T X.691 | ISO/IEC 8825-2, 9.3.11
+    PermittedAlphabet,
+    /// Values or sizes added after the extension marker of a constraint,
+    /// ITU-T X.691 | ISO/IEC 8825-2, 9.3.19
+    ExtensionAdditions,
+    /// `WITH COMPONENTS`, ITU-T X.691 | ISO/IEC 8825-2, 9.3.9
+    InnerType,
+    /// `SIZE` of a `UTF8String`, ITU-T X.691 | ISO/IEC 8825-2, 9.3.11. Other than the rest, it
+    /// is kept in the model and checked when writing or reading values.
+    Utf8StringSize,
+}
+
+impl InvisibleConstraintKind {
+    /// Whether the constraint is dropped entirely, instead of only not changing the encoding
+    pub fn is_ignored(self) -> bool {
+        !matches!(self, Self::Utf8StringSize)
+    }
+}
+
+impl Display for InvisibleConstraint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}",
+            self.location.line(),
+            self.location.column()
+        )?;
+        if let Some(definition) = &self.definition {
+            write!(f, " in {definition}")?;
+        }
+        write!(f, ": ")?;
+        match self.kind {
+            InvisibleConstraintKind::Pattern => write!(f, "PATTERN constraints"),
+            InvisibleConstraintKind::PermittedAlphabet => {
+                write!(f, "FROM constraints on UTF8String")
+            }
+            InvisibleConstraintKind::ExtensionAdditions => {
+                write!(f, "Extension additions of constraints")
+            }
+            InvisibleConstraintKind::InnerType => write!(f, "WITH COMPONENTS constraints"),
+            InvisibleConstraintKind::Utf8StringSize => write!(f, "SIZE constraints on UTF8String"),
+        }?;
+        if self.kind.is_ignored() {
+            write!(f, " are not PER-visible and are ignored")
+        } else {
+            write!(
+                f,
+                " are not PER-visible, they are checked but do not change the encoding"
+            )
+        }
+    }
+}
+
+/// Removes the constraints that are ignored because they are not PER-visible and reports every
+/// constraint that is not PER-visible
+pub(crate) fn strip_invisible_constraints(
+    tokens: Vec<Token>,
+) -> (Vec<Token>, Vec<InvisibleConstraint>) {
+    let mut result = Vec::with_capacity(tokens.len());
+    let mut invisible = Vec::new();
+    let mut definition = None;
+    let mut brackets = Vec::new();
+    let mut in_body = false;
+    let mut in_string = false;
+    let mut index = 0;
+
+    while let Some(token) = tokens.get(index) {
+        let next = tokens.get(index + 1);
+        let mut report = |kind, location| {
+            invisible.push(InvisibleConstraint {
+                definition: definition.clone(),
+                location,
+                kind,
+            })
+        };
+
+        if token.eq_separator('"') {
+            in_string = !in_string;
+        } else if in_string {
+            // the content of string literals is never a constraint
+        } else if token.eq_separator('(') {
+            let dropped = next.and_then(|next| {
+                if next.eq_text_ignore_ascii_case("PATTERN") {
+                    Some((InvisibleConstraintKind::Pattern, next.location()))
+                } else if next.eq_text_ignore_ascii_case("FROM")
+                    && result
+                        .last()
+                        .is_some_and(|t: &Token| t.eq_text_ignore_ascii_case("UTF8String"))
+                {
+                    Some((InvisibleConstraintKind::PermittedAlphabet, next.location()))
+                } else {
+                    None
+                }
+            });
+            if let Some((kind, location)) = dropped {
+                report(kind, location);
+                index = index_of_closing_bracket(&tokens, index + 1) + 1;
+                continue;
+            }
+            brackets.push('(');
+        } else if token.eq_separator('{') {
+            brackets.push('{');
+        } else if token.eq_separator(')') || token.eq_separator('}') {
+            brackets.pop();
+        } else if brackets.is_empty() && token.eq_text_ignore_ascii_case("BEGIN") {
+            in_body = true;
+        } else if in_body
+            && brackets.is_empty()
+            && token.text().is_some()
+            && next.is_some_and(|t| t.eq_separator(':'))
+        {
+            definition = token.text().map(str::to_string);
+        } else if token.eq_text_ignore_ascii_case("WITH")
+            && next.is_some_and(|t| {
+                t.eq_text_ignore_ascii_case("COMPONENTS")
+                    || t.eq_text_ignore_ascii_case("COMPONENT")
+            })
+        {
+            report(InvisibleConstraintKind::InnerType, token.location());
+        } else if token.eq_text_ignore_ascii_case("UTF8String")
+            && next.is_some_and(|t| t.eq_separator('(') || t.eq_text_ignore_ascii_case("SIZE"))
+            && !tokens.get(index + 2).is_some_and(|t| {
+                t.eq_text_ignore_ascii_case("PATTERN") || t.eq_text_ignore_ascii_case("FROM")
+            })
+        {
+            report(InvisibleConstraintKind::Utf8StringSize, token.location());
+        } else if brackets.last() == Some(&'(')
+            && tokens.get(index..index + 4).is_some_and(|t| {
+                t[0].eq_separator('.')
+                    && t[1].eq_separator('.')
+                    && t[2].eq_separator('.')
+                    && t[3].eq_separator(',')
+            })
+        {
+            // keep the extension marker, drop the extension additions that follow it
+            result.extend_from_slice(&tokens[index..index + 3]);
+            if let Some(addition) = tokens.get(index + 4) {
+                report(
+                    InvisibleConstraintKind::ExtensionAdditions,
+                    addition.location(),
+                );
+            }
+            index = index_of_closing_bracket(&tokens, index + 3);
+            continue;
+        }
+
+        result.push(token.clone());
+        index += 1;
+    }
+
+    (result, invisible)
+}
+
+/// Returns the index of the bracket closing the innermost bracket that is open at `index`
+pub(crate) fn index_of_closing_bracket(tokens: &[Token], mut index: usize) -> usize {
+    let mut depth = 0_usize;
+    let mut in_string = false;
+    while let Some(token) = tokens.get(index) {
+        if token.eq_separator('"') {
+            in_string = !in_string;
+        } else if !in_string && (token.eq_separator('(') || token.eq_separator('{')) {
+            depth += 1;
+        } else if !in_string && (token.eq_separator(')') || token.eq_separator('}')) {
+            if depth == 0 {
+                return index;
+            }
+            depth -= 1;
+        }
+        index += 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Tokenizer;
+
+    fn strip(asn: &str) -> (String, Vec<InvisibleConstraintKind>) {
+        let (tokens, invisible) = strip_invisible_constraints(Tokenizer.parse(asn));
+        (
+            tokens
+                .iter()
+                .map(|t| match t {
+                    Token::Text(_, text) => text.clone(),
+                    other => other.separator().unwrap_or_default().to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+            invisible.into_iter().map(|i| i.kind).collect(),
+        )
+    }
+
+    #[test]
+    fn test_extension_additions_are_dropped() {
+        assert_eq!(
+            (
+                "BEGIN A : : = INTEGER ( 0 . . 7 , . . . ) END".to_string(),
+                vec![InvisibleConstraintKind::ExtensionAdditions]
+            ),
+            strip("BEGIN A ::= INTEGER (0..7, ..., 8..15) END")
+        );
+        assert_eq!(
+            (
+                "BEGIN A : : = IA5String ( SIZE ( 1 . . 4 , . . . ) ) END".to_string(),
+                vec![InvisibleConstraintKind::ExtensionAdditions]
+            ),
+            strip("BEGIN A ::= IA5String (SIZE(1..4, ..., 8)) END")
+        );
+    }
+
+    #[test]
+    fn test_pattern_and_permitted_alphabet_are_dropped() {
+        assert_eq!(
+            (
+                "BEGIN A : : = UTF8String B : : = IA5String END".to_string(),
+                vec![
+                    InvisibleConstraintKind::PermittedAlphabet,
+                    InvisibleConstraintKind::Pattern
+                ]
+            ),
+            strip(
+                r#"BEGIN A ::= UTF8String (FROM ("a".."z")) B ::= IA5String (PATTERN "(a|b)") END"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_visible_constraints_are_kept() {
+        let asn = "BEGIN A ::= SEQUENCE { a INTEGER (0..7, ...), ..., b BOOLEAN } END";
+        let (tokens, invisible) = strip_invisible_constraints(Tokenizer.parse(asn));
+        assert_eq!(Tokenizer.parse(asn), tokens);
+        assert!(invisible.is_empty());
+    }
+
+    #[test]
+    fn test_size_of_utf8string_and_inner_type_are_reported() {
+        let (_, invisible) = strip_invisible_constraints(Tokenizer.parse(
+            "BEGIN A ::= UTF8String (SIZE(1..4))\nB ::= A (WITH COMPONENTS {..., a PRESENT}) END",
+        ));
+        assert_eq!(
+            vec![
+                InvisibleConstraint {
+                    definition: Some("A".to_string()),
+                    location: Location::at(1, 13),
+                    kind: InvisibleConstraintKind::Utf8StringSize,
+                },
+                InvisibleConstraint {
+                    definition: Some("B".to_string()),
+                    location: Location::at(2, 10),
+                    kind: InvisibleConstraintKind::InnerType,
+                }
+            ],
+            invisible
+        );
+    }
+}
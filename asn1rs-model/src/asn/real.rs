@@ -0,0 +1,116 @@
+use crate::asn::inner_type_constraints::InnerTypeConstraints;
+use crate::asn::peekable::PeekableTokens;
+use crate::parse::Error;
+use crate::parse::Token;
+use std::convert::TryFrom;
+use std::iter::Peekable;
+
+/// The precision of the floating point type that represents a `REAL`, ITU-T X.680 | ISO/IEC
+/// 8824-1, 21.
+///
+/// A `REAL` is represented as `f64`, unless its components are constrained to values an IEEE 754
+/// binary32 is able to represent, as in
+/// `REAL (WITH COMPONENTS { mantissa (-16777215..16777215), base (2), exponent (-125..128) })`.
+/// None of the constraints of a `REAL` is PER-visible, they only choose the Rust type.
+#[derive(Debug, Default, Copy, Clone, PartialOrd, PartialEq, Eq, Hash)]
+pub enum RealPrecision {
+    /// IEEE 754 binary32, `f32`
+    Single,
+    /// IEEE 754 binary64, `f64`
+    #[default]
+    Double,
+}
+
+impl RealPrecision {
+    const SINGLE_MANTISSA: i64 = (1 << 24) - 1;
+    const SINGLE_EXPONENT: (i64, i64) = (-149, 128);
+
+    fn from_constraints(constraints: &InnerTypeConstraints) -> Self {
+        let range = |component| {
+            let constraint = constraints.value_constraint(component)?;
+            match constraint.split_once("..") {
+                Some((min, max)) => Some((min.parse::<i64>().ok()?, max.parse::<i64>().ok()?)),
+                None => constraint.parse::<i64>().ok().map(|value| (value, value)),
+            }
+        };
+        let single = range("mantissa").is_some_and(|(min, max)| {
+            -Self::SINGLE_MANTISSA <= min && max <= Self::SINGLE_MANTISSA
+        }) && range("base") == Some((2, 2))
+            && range("exponent").is_some_and(|(min, max)| {
+                Self::SINGLE_EXPONENT.0 <= min && max <= Self::SINGLE_EXPONENT.1
+            });
+        if single {
+            RealPrecision::Single
+        } else {
+            RealPrecision::Double
+        }
+    }
+}
+
+impl<T: Iterator<Item = Token>> TryFrom<&mut Peekable<T>> for RealPrecision {
+    type Error = Error;
+
+    fn try_from(iter: &mut Peekable<T>) -> Result<Self, Self::Error> {
+        if !iter.next_is_separator_and_eq('(') {
+            return Ok(RealPrecision::default());
+        }
+        let precision = if iter.peek_is_text_eq_ignore_case("WITH") {
+            Self::from_constraints(&InnerTypeConstraints::try_from(&mut *iter)?)
+        } else {
+            // value constraints like `REAL (0..100)` are not PER-visible and therefore ignored
+            let mut depth = 0_usize;
+            while depth > 0 || !iter.peek_is_separator_eq(')') {
+                match iter.next_or_err()?.separator() {
+                    Some('(') => depth += 1,
+                    Some(')') => depth -= 1,
+                    _ => {}
+                }
+            }
+            RealPrecision::Double
+        };
+        iter.next_separator_eq_or_err(')')?;
+        Ok(precision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Tokenizer;
+
+    fn parse(asn: &str) -> RealPrecision {
+        let mut iter = Tokenizer.parse(asn).into_iter().peekable();
+        let precision = RealPrecision::try_from(&mut iter).unwrap();
+        assert!(iter.next().is_none());
+        precision
+    }
+
+    #[test]
+    fn test_unconstrained_is_double() {
+        assert_eq!(RealPrecision::Double, parse(""));
+        assert_eq!(RealPrecision::Double, parse("(0..100)"));
+        assert_eq!(RealPrecision::Double, parse("((0..100) | (200..300))"));
+    }
+
+    #[test]
+    fn test_binary32_components_are_single() {
+        assert_eq!(
+            RealPrecision::Single,
+            parse(
+                "(WITH COMPONENTS { mantissa (-16777215..16777215), base (2), exponent (-125..128) })"
+            )
+        );
+        assert_eq!(
+            RealPrecision::Double,
+            parse(
+                "(WITH COMPONENTS { mantissa (-9007199254740991..9007199254740991), base (2), exponent (-1022..1023) })"
+            )
+        );
+        assert_eq!(
+            RealPrecision::Double,
+            parse(
+                "(WITH COMPONENTS { mantissa (-16777215..16777215), base (10), exponent (-5..5) })"
+            )
+        );
+    }
+}
@@ -1,6 +1,6 @@
-use crate::asn::{Asn, Type};
-use crate::model::{Definition, LiteralValue, Model, Target, ValueReference};
-use crate::resolve::{Error, LitOrRef, Resolved, Resolver, Unresolved};
+use crate::asn::{Asn, TagResolver, Type};
+use crate::model::{Definition, Import, LiteralValue, Model, Target, ValueReference};
+use crate::resolve::{Error, LitOrRef, Resolved, Resolver, Unresolved, ValueResolver};
 
 #[derive(Default)]
 pub struct MultiModuleResolver {
@@ -12,8 +12,45 @@ impl MultiModuleResolver {
         self.models.push(model);
     }
 
+    pub fn models(&self) -> &[Model<Asn<Unresolved>>] {
+        &self.models
+    }
+
+    /// The loaded model with the given name
+    pub fn model_mut(&mut self, name: &str) -> Option<&mut Model<Asn<Unresolved>>> {
+        self.models.iter_mut().find(|model| model.name == name)
+    }
+
+    /// Checks that each import of each model refers to a loaded model that declares - or
+    /// imports itself - each of the imported items
+    pub fn link(&self) -> Result<(), Error> {
+        for model in &self.models {
+            for import in &model.imports {
+                let from = self
+                    .models
+                    .iter()
+                    .find(|m| is_imported_from(import, m))
+                    .ok_or_else(|| {
+                        Error::UnknownImportModule(model.name.clone(), import.from.clone())
+                    })?;
+                if let Some(item) = import.what.iter().find(|item| !provides(from, item)) {
+                    return Err(Error::UnknownImportItem(
+                        model.name.clone(),
+                        from.name.clone(),
+                        item.clone(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Links and resolves all models and fails if the tag of any definition depends on itself or
+    /// if two components of a `SET` have the same tag
     pub fn try_resolve_all(&self) -> Result<Vec<Model<Asn<Resolved>>>, Error> {
-        self.models
+        self.link()?;
+        let models = self
+            .models
             .iter()
             .map(|model| {
                 ResolveScope {
@@ -22,10 +59,35 @@ impl MultiModuleResolver {
                 }
                 .try_resolve()
             })
-            .collect::<_>()
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let scope = models.iter().collect::<Vec<_>>();
+        for model in &models {
+            let resolver = TagResolver::new(model, &scope);
+            for Definition(name, _asn) in &model.definitions {
+                resolver.try_resolve_tag(name)?;
+            }
+            resolver.try_check_set_tags()?;
+        }
+
+        Ok(models)
     }
 }
 
+fn is_imported_from(import: &Import, model: &Model<Asn<Unresolved>>) -> bool {
+    (model.oid.is_some() && model.oid.eq(&import.from_oid)) || model.name.eq(&import.from)
+}
+
+/// Whether the given model declares or imports an item with the given name
+fn provides(model: &Model<Asn<Unresolved>>, item: &str) -> bool {
+    model.definitions.iter().any(|def| def.name().eq(item))
+        || model.value_references.iter().any(|vr| vr.name.eq(item))
+        || model
+            .imports
+            .iter()
+            .any(|import| import.what.iter().any(|what| what.eq(item)))
+}
+
 pub struct ResolveScope<'a> {
     model: &'a Model<Asn<Unresolved>>,
     scope: &'a [Model<Asn<Unresolved>>],
@@ -45,9 +107,14 @@ impl<'a> ResolveScope<'a> {
         let mut result = Model::<Asn<Resolved>> {
             name: self.model.name.clone(),
             oid: self.model.oid.clone(),
+            automatic_tags: self.model.automatic_tags,
             imports: self.model.imports.clone(),
             definitions: Vec::with_capacity(self.model.definitions.len()),
             value_references: Vec::with_capacity(self.model.value_references.len()),
+            invisible_constraints: self.model.invisible_constraints.clone(),
+            ignored_annotations: self.model.ignored_annotations.clone(),
+            inline_definitions: self.model.inline_definitions.clone(),
+            asn_names: self.model.asn_names.clone(),
         };
 
         // copy over all value references
@@ -55,7 +122,7 @@ impl<'a> ResolveScope<'a> {
             result.value_references.push(ValueReference {
                 name: vr.name.clone(),
                 role: vr.role.try_resolve(self)?,
-                value: vr.value.clone(),
+                value: self.resolve_value(&vr.value, &vr.role.r#type)?,
             })
         }
 
@@ -73,47 +140,144 @@ impl<'a> ResolveScope<'a> {
             .imports
             .iter()
             .find(|i| i.what.iter().any(|what| what.eq(item)))
-            .and_then(|import| {
-                self.scope.iter().find(|m| {
-                    (m.oid.is_some() && m.oid.eq(&import.from_oid)) || m.name.eq(&import.from)
-                })
-            })
+            .and_then(|import| self.scope.iter().find(|m| is_imported_from(import, m)))
     }
 
     fn value_reference(
         &self,
         name: &str,
     ) -> Option<&'a ValueReference<<Asn<Unresolved> as Target>::ValueReferenceType>> {
+        self.value_reference_with_scope(name).map(|(_, vr)| vr)
+    }
+
+    /// The value reference with the given name and the scope of the module it is declared in
+    fn value_reference_with_scope(
+        &self,
+        name: &str,
+    ) -> Option<(
+        ResolveScope<'a>,
+        &'a ValueReference<<Asn<Unresolved> as Target>::ValueReferenceType>,
+    )> {
         self.model
             .value_references
             .iter()
             .find(|vr| vr.name.eq(name))
+            .map(|vr| (self.with_model(self.model), vr))
             .or_else(|| {
-                self.model_with_imported_item(name).and_then(|model| {
-                    ResolveScope {
-                        model,
-                        scope: self.scope,
-                    }
-                    .value_reference(name)
-                })
+                self.model_with_imported_item(name)
+                    .and_then(|model| self.with_model(model).value_reference_with_scope(name))
             })
     }
 
     fn definition(&self, name: &str) -> Option<&'a Definition<Asn<Unresolved>>> {
+        self.definition_with_scope(name).map(|(_, def)| def)
+    }
+
+    /// The definition with the given name and the scope of the module it is declared in
+    fn definition_with_scope(
+        &self,
+        name: &str,
+    ) -> Option<(ResolveScope<'a>, &'a Definition<Asn<Unresolved>>)> {
         self.model
             .definitions
             .iter()
             .find(|def| def.name().eq(name))
+            .map(|def| (self.with_model(self.model), def))
             .or_else(|| {
-                self.model_with_imported_item(name).and_then(|model| {
-                    ResolveScope {
-                        model,
-                        scope: self.scope,
-                    }
-                    .definition(name)
-                })
+                self.model_with_imported_item(name)
+                    .and_then(|model| self.with_model(model).definition_with_scope(name))
             })
     }
+
+    fn with_model(&self, model: &'a Model<Asn<Unresolved>>) -> ResolveScope<'a> {
+        ResolveScope {
+            model,
+            scope: self.scope,
+        }
+    }
+
+    /// The resolved value of the value reference with the given name
+    fn resolve_reference(&self, name: &str) -> Result<LiteralValue, Error> {
+        let (scope, vr) = self
+            .value_reference_with_scope(name)
+            .ok_or_else(|| Error::FailedToResolveReference(name.to_string()))?;
+        scope.resolve_value(&vr.value, &vr.role.r#type)
+    }
+}
+
+impl ValueResolver for ResolveScope<'_> {
+    fn resolve_value(
+        &self,
+        value: &LiteralValue,
+        r#type: &Type<Unresolved>,
+    ) -> Result<LiteralValue, Error> {
+        match (value, r#type) {
+            (_, Type::Optional(inner) | Type::Default(inner, _) | Type::Substituted(inner, _)) => {
+                self.resolve_value(value, inner)
+            }
+            (_, Type::TypeReference(name, _)) => {
+                let (scope, Definition(_, asn)) = self
+                    .definition_with_scope(name)
+                    .ok_or_else(|| Error::FailedToResolveType(name.clone()))?;
+                match (value, &asn.r#type) {
+                    (LiteralValue::Reference(variant), Type::Enumerated(enumerated))
+                        if enumerated.variants().any(|v| v.name() == variant) =>
+                    {
+                        Ok(LiteralValue::EnumeratedVariant(
+                            name.clone(),
+                            variant.clone(),
+                        ))
+                    }
+                    // the referenced value is declared in this scope, not in the one of the type
+                    (LiteralValue::Reference(name), _) => self.resolve_reference(name),
+                    (value, r#type) => scope.resolve_value(value, r#type),
+                }
+            }
+            (LiteralValue::Reference(name), _) => self.resolve_reference(name),
+            (LiteralValue::Sequence(components), Type::Sequence(list) | Type::Set(list)) => {
+                components
+                    .iter()
+                    .map(|(name, value)| {
+                        let field = list
+                            .fields
+                            .iter()
+                            .find(|field| field.name.eq(name))
+                            .ok_or_else(|| Error::FailedToResolveReference(name.clone()))?;
+                        Ok((name.clone(), self.resolve_value(value, &field.role.r#type)?))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(LiteralValue::Sequence)
+            }
+            (LiteralValue::Sequence(components), Type::SequenceOf(..) | Type::SetOf(..))
+                if components.is_empty() =>
+            {
+                Ok(LiteralValue::SequenceOf(Vec::new()))
+            }
+            (
+                LiteralValue::SequenceOf(values),
+                Type::SequenceOf(inner, _) | Type::SetOf(inner, _),
+            ) => values
+                .iter()
+                .map(|value| self.resolve_value(value, inner))
+                .collect::<Result<Vec<_>, _>>()
+                .map(LiteralValue::SequenceOf),
+            (LiteralValue::Choice(name, value), Type::Choice(choice)) => {
+                let variant = choice
+                    .variants()
+                    .find(|variant| variant.name().eq(name))
+                    .ok_or_else(|| Error::FailedToResolveReference(name.clone()))?;
+                Ok(LiteralValue::Choice(
+                    name.clone(),
+                    Box::new(self.resolve_value(value, variant.r#type())?),
+                ))
+            }
+            (
+                LiteralValue::Sequence(_) | LiteralValue::SequenceOf(_) | LiteralValue::Choice(..),
+                _,
+            ) => Err(Error::FailedToParseLiteral(format!("{:?}", value))),
+            (value, _) => Ok(value.clone()),
+        }
+    }
 }
 
 impl Resolver<usize> for ResolveScope<'_> {
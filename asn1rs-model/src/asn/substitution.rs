@@ -0,0 +1,530 @@
+use crate::asn::{Asn, ChoiceVariant, ComponentTypeList, Type};
+use crate::model::{Definition, Model};
+use crate::parse::Tokenizer;
+use std::fmt::{Display, Formatter};
+
+/// A user provided Rust type that represents the values of an ASN.1 type in the generated code,
+/// such as `uuid::Uuid` for `OCTET STRING (SIZE(16))`. The conversion between both is done by
+/// the type `via` - or the Rust type itself - which needs to implement
+/// `asn1rs::descriptor::substituted::Substitute`. Like all field types of the generated code,
+/// the Rust type needs to implement `Default`, `Debug`, `Clone`, `PartialEq` and `Hash`.
+#[derive(Debug, Clone, PartialOrd, PartialEq, Eq, Hash)]
+pub struct Substitution {
+    pub rust_type: String,
+    pub via: Option<String>,
+}
+
+impl Substitution {
+    pub fn new<T: ToString>(rust_type: T) -> Self {
+        Self {
+            rust_type: rust_type.to_string(),
+            via: None,
+        }
+    }
+
+    pub fn with_via<T: ToString>(mut self, via: T) -> Self {
+        self.via = Some(via.to_string());
+        self
+    }
+
+    /// The type implementing the conversion
+    pub fn converter(&self) -> &str {
+        self.via.as_deref().unwrap_or(&self.rust_type)
+    }
+}
+
+/// The Rust type of all string fields in the generated code. Other than [`StringType::String`],
+/// each of them is a [`Substitution`] of the string types that is cheaper to clone - as in
+/// pipelines that share decoded messages across threads. Values of `DEFAULT` fields stay
+/// `String`s.
+#[derive(Debug, Default, Copy, Clone, PartialOrd, PartialEq, Eq, Hash)]
+pub enum StringType {
+    #[default]
+    String,
+    BoxStr,
+    ArcStr,
+    /// Requires the `smol_str` feature of `asn1rs`
+    SmolStr,
+}
+
+impl StringType {
+    pub const ALL: [StringType; 4] = [
+        StringType::String,
+        StringType::BoxStr,
+        StringType::ArcStr,
+        StringType::SmolStr,
+    ];
+
+    /// The name as written in the configuration, such as `Arc<str>`
+    pub const fn name(self) -> &'static str {
+        match self {
+            StringType::String => "String",
+            StringType::BoxStr => "Box<str>",
+            StringType::ArcStr => "Arc<str>",
+            StringType::SmolStr => "SmolStr",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|t| t.name() == name.trim())
+    }
+
+    pub fn substitution(self) -> Option<Substitution> {
+        match self {
+            StringType::String => None,
+            StringType::BoxStr => Some(Substitution::new("Box<str>")),
+            StringType::ArcStr => Some(Substitution::new("::std::sync::Arc<str>")),
+            StringType::SmolStr => Some(Substitution::new("::smol_str::SmolStr")),
+        }
+    }
+}
+
+impl Display for StringType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The Rust type of all `OCTET STRING` fields in the generated code. [`OctetStringType::Bytes`]
+/// is a [`Substitution`] by `bytes::Bytes`, which can be sliced and cloned without copying the
+/// decoded payload. Values of `DEFAULT` fields stay `Vec<u8>`s.
+#[derive(Debug, Default, Copy, Clone, PartialOrd, PartialEq, Eq, Hash)]
+pub enum OctetStringType {
+    #[default]
+    VecU8,
+    /// Requires the `bytes` feature of `asn1rs`
+    Bytes,
+}
+
+impl OctetStringType {
+    pub const ALL: [OctetStringType; 2] = [OctetStringType::VecU8, OctetStringType::Bytes];
+
+    /// The name as written in the configuration, such as `Bytes`
+    pub const fn name(self) -> &'static str {
+        match self {
+            OctetStringType::VecU8 => "Vec<u8>",
+            OctetStringType::Bytes => "Bytes",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|t| t.name() == name.trim())
+    }
+
+    pub fn substitution(self) -> Option<Substitution> {
+        match self {
+            OctetStringType::VecU8 => None,
+            OctetStringType::Bytes => Some(Substitution::new("::bytes::Bytes")),
+        }
+    }
+}
+
+impl Display for OctetStringType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The line of the configuration is not of the form `<ASN.1 type> => <Rust type> [via <Rust type>]`
+    InvalidLine(usize, String),
+    /// The ASN.1 type of the line cannot be parsed or resolved
+    InvalidType(usize, String),
+}
+
+impl std::error::Error for Error {}
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidLine(line, text) => write!(
+                f,
+                "Line {}: expected `<ASN.1 type> => <Rust type> [via <Rust type>]` but got `{}`",
+                line, text
+            ),
+            Error::InvalidType(line, text) => {
+                write!(f, "Line {}: invalid ASN.1 type `{}`", line, text)
+            }
+        }
+    }
+}
+
+/// Global substitutions that replace every occurrence of an ASN.1 type - constraints included -
+/// with a user provided Rust type. They are read from a configuration with one substitution per
+/// line, comments start with `--`:
+///
+/// ```text
+/// -- all 128-bit identifiers
+/// OCTET STRING (SIZE(16))  => uuid::Uuid via crate::UuidBytes
+/// INTEGER (0..4294967295)  => crate::Timestamp
+/// ```
+///
+/// ```rust
+/// use asn1rs_model::asn::{Substitution, Substitutions, Type};
+/// use asn1rs_model::parse::Tokenizer;
+/// use asn1rs_model::Model;
+///
+/// let substitutions = Substitutions::parse("OCTET STRING (SIZE(16)) => crate::Id").unwrap();
+/// let mut model = Model::try_from(Tokenizer.parse(
+///     "Ids DEFINITIONS AUTOMATIC TAGS ::= BEGIN Id ::= OCTET STRING (SIZE(16)) END",
+/// ))
+/// .unwrap()
+/// .try_resolve()
+/// .unwrap();
+///
+/// substitutions.apply(&mut model);
+/// assert!(matches!(
+///     &model.definitions[0].1.r#type,
+///     Type::Substituted(_, substitution) if substitution == &Substitution::new("crate::Id")
+/// ));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Substitutions {
+    entries: Vec<(Type, Substitution)>,
+    string_type: StringType,
+    /// The substitution of the `string_type`
+    strings: Option<Substitution>,
+    octet_string_type: OctetStringType,
+    /// The substitution of the `octet_string_type`
+    octet_strings: Option<Substitution>,
+}
+
+impl Substitutions {
+    pub fn parse(config: &str) -> Result<Self, Error> {
+        let mut substitutions = Self::default();
+        for (index, line) in config.lines().enumerate() {
+            let number = index + 1;
+            let line = line.split("--").next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (pattern, target) = line
+                .split_once("=>")
+                .ok_or_else(|| Error::InvalidLine(number, line.to_string()))?;
+            let target = format!(" {} ", target.trim());
+            let substitution = match target.split_once(" via ") {
+                Some((rust_type, via))
+                    if !rust_type.trim().is_empty() && !via.trim().is_empty() =>
+                {
+                    Substitution::new(rust_type.trim()).with_via(via.trim())
+                }
+                None if !target.trim().is_empty() => Substitution::new(target.trim()),
+                _ => return Err(Error::InvalidLine(number, line.to_string())),
+            };
+
+            let pattern = pattern.trim();
+            let r#type = Self::parse_type(pattern)
+                .ok_or_else(|| Error::InvalidType(number, pattern.to_string()))?;
+            substitutions.entries.push((r#type, substitution));
+        }
+        Ok(substitutions)
+    }
+
+    /// Adds a substitution of the given ASN.1 type, returns `None` if it cannot be parsed
+    pub fn with(mut self, pattern: &str, substitution: Substitution) -> Option<Self> {
+        self.entries
+            .push((Self::parse_type(pattern)?, substitution));
+        Some(self)
+    }
+
+    /// Replaces all string types without a substitution of their own by the given type
+    pub fn with_string_type(mut self, string_type: StringType) -> Self {
+        self.set_string_type(string_type);
+        self
+    }
+
+    pub fn set_string_type(&mut self, string_type: StringType) {
+        self.string_type = string_type;
+        self.strings = string_type.substitution();
+    }
+
+    pub const fn string_type(&self) -> StringType {
+        self.string_type
+    }
+
+    /// Replaces all `OCTET STRING` types without a substitution of their own by the given type
+    pub fn with_octet_string_type(mut self, octet_string_type: OctetStringType) -> Self {
+        self.set_octet_string_type(octet_string_type);
+        self
+    }
+
+    pub fn set_octet_string_type(&mut self, octet_string_type: OctetStringType) {
+        self.octet_string_type = octet_string_type;
+        self.octet_strings = octet_string_type.substitution();
+    }
+
+    pub const fn octet_string_type(&self) -> OctetStringType {
+        self.octet_string_type
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn substitution_for(&self, r#type: &Type) -> Option<&Substitution> {
+        self.entries
+            .iter()
+            .find(|(pattern, _)| pattern == r#type)
+            .map(|(_, substitution)| substitution)
+            .or(match r#type {
+                Type::String(..) => self.strings.as_ref(),
+                Type::OctetString(_) => self.octet_strings.as_ref(),
+                _ => None,
+            })
+    }
+
+    fn parse_type(pattern: &str) -> Option<Type> {
+        let module = format!(
+            "Substitution DEFINITIONS AUTOMATIC TAGS ::= BEGIN Pattern ::= {} END",
+            pattern
+        );
+        let model = Model::try_from(Tokenizer.parse(&module))
+            .ok()?
+            .try_resolve()
+            .ok()?;
+        model
+            .definitions
+            .into_iter()
+            .next()
+            .map(|Definition(_, asn)| asn.r#type)
+    }
+
+    /// Replaces every matching type in the model. Types with a `DEFAULT` value are left as they
+    /// are, because the value could not be represented by the substitute.
+    pub fn apply(&self, model: &mut Model<Asn>) {
+        if self.is_empty() && self.strings.is_none() && self.octet_strings.is_none() {
+            return;
+        }
+        for Definition(_, asn) in &mut model.definitions {
+            self.apply_to_type(&mut asn.r#type);
+        }
+    }
+
+    fn apply_to_type(&self, r#type: &mut Type) {
+        if let Some(substitution) = self.substitution_for(r#type) {
+            let inner = std::mem::replace(r#type, Type::Null);
+            *r#type = Type::Substituted(Box::new(inner), substitution.clone());
+            return;
+        }
+
+        match r#type {
+            Type::Optional(inner)
+            | Type::Containing(inner, _)
+            | Type::SequenceOf(inner, _)
+            | Type::SetOf(inner, _) => self.apply_to_type(inner),
+            Type::Sequence(ComponentTypeList { fields, .. })
+            | Type::Set(ComponentTypeList { fields, .. }) => {
+                for field in fields.iter_mut().filter(|f| f.role.default.is_none()) {
+                    self.apply_to_type(&mut field.role.r#type);
+                }
+            }
+            Type::Choice(choice) => {
+                for ChoiceVariant { r#type, .. } in choice.variants_mut() {
+                    self.apply_to_type(r#type);
+                }
+            }
+            Type::Boolean
+            | Type::Integer(_)
+            | Type::String(..)
+            | Type::OctetString(_)
+            | Type::BitString(_)
+            | Type::Null
+            | Type::Real(_)
+            | Type::ObjectIdentifier
+            | Type::RelativeOid
+            | Type::OidIri
+            | Type::Default(..)
+            | Type::Enumerated(_)
+            | Type::Substituted(..)
+            | Type::TypeReference(..) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asn::{Charset, Size};
+
+    fn model(asn: &str) -> Model<Asn> {
+        Model::try_from(Tokenizer.parse(asn))
+            .unwrap()
+            .try_resolve()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let substitutions = Substitutions::parse(
+            r"-- identifiers
+            OCTET STRING (SIZE(16)) => uuid::Uuid via crate::UuidBytes
+
+            INTEGER (0..4294967295) => crate::Timestamp -- seconds",
+        )
+        .unwrap();
+
+        assert_eq!(2, substitutions.len());
+        assert_eq!(
+            Some(&Substitution::new("uuid::Uuid").with_via("crate::UuidBytes")),
+            substitutions.substitution_for(&Type::OctetString(Size::Fix(16, false)))
+        );
+        assert_eq!(
+            None,
+            substitutions.substitution_for(&Type::OctetString(Size::Fix(15, false)))
+        );
+    }
+
+    #[test]
+    fn test_parse_config_errors() {
+        assert_eq!(
+            Err(Error::InvalidLine(2, "INTEGER".to_string())),
+            Substitutions::parse("\nINTEGER")
+        );
+        assert_eq!(
+            Err(Error::InvalidLine(
+                1,
+                "INTEGER => via crate::Foo".to_string()
+            )),
+            Substitutions::parse("INTEGER => via crate::Foo")
+        );
+        assert_eq!(
+            Err(Error::InvalidType(1, "NOT A TYPE (".to_string())),
+            Substitutions::parse("NOT A TYPE ( => crate::Foo")
+        );
+    }
+
+    #[test]
+    fn test_apply_to_nested_types() {
+        let substitutions = Substitutions::parse("OCTET STRING (SIZE(16)) => crate::Id").unwrap();
+        let mut model = model(
+            r"Nested DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Entry ::= SEQUENCE {
+                id     OCTET STRING (SIZE(16)),
+                others SEQUENCE OF OCTET STRING (SIZE(16)),
+                parent OCTET STRING (SIZE(16)) OPTIONAL,
+                data   OCTET STRING
+            }
+            END",
+        );
+        substitutions.apply(&mut model);
+
+        let substituted = || {
+            Type::Substituted(
+                Box::new(Type::OctetString(Size::Fix(16, false))),
+                Substitution::new("crate::Id"),
+            )
+        };
+        let Type::Sequence(ComponentTypeList { fields, .. }) = &model.definitions[0].1.r#type
+        else {
+            panic!("Expected a SEQUENCE");
+        };
+        assert_eq!(substituted(), fields[0].role.r#type);
+        assert_eq!(
+            Type::SequenceOf(Box::new(substituted()), Size::Any),
+            fields[1].role.r#type
+        );
+        assert_eq!(substituted().optional(), fields[2].role.r#type);
+        assert_eq!(Type::OctetString(Size::Any), fields[3].role.r#type);
+    }
+
+    #[test]
+    fn test_string_type() {
+        let substitutions = Substitutions::parse("IA5String (SIZE(4)) => crate::Code")
+            .unwrap()
+            .with_string_type(StringType::ArcStr);
+        let mut model = model(
+            r#"Strings DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Entry ::= SEQUENCE {
+                name    UTF8String,
+                code    IA5String (SIZE(4)),
+                aliases SEQUENCE OF PrintableString OPTIONAL,
+                label   UTF8String DEFAULT "none"
+            }
+            END"#,
+        );
+        substitutions.apply(&mut model);
+
+        let Type::Sequence(ComponentTypeList { fields, .. }) = &model.definitions[0].1.r#type
+        else {
+            panic!("Expected a SEQUENCE");
+        };
+        let arc = |charset| {
+            Type::Substituted(
+                Box::new(Type::String(Size::Any, charset)),
+                Substitution::new("::std::sync::Arc<str>"),
+            )
+        };
+        assert_eq!(arc(Charset::Utf8), fields[0].role.r#type);
+        assert_eq!(
+            Type::Substituted(
+                Box::new(Type::String(Size::Fix(4, false), Charset::Ia5)),
+                Substitution::new("crate::Code"),
+            ),
+            fields[1].role.r#type
+        );
+        assert_eq!(
+            Type::SequenceOf(Box::new(arc(Charset::Printable)), Size::Any).optional(),
+            fields[2].role.r#type
+        );
+        assert!(matches!(fields[3].role.r#type, Type::String(..)));
+    }
+
+    #[test]
+    fn test_string_type_names() {
+        for string_type in StringType::ALL.iter().copied() {
+            assert_eq!(
+                Some(string_type),
+                StringType::from_name(&string_type.to_string())
+            );
+        }
+        assert_eq!(None, StringType::from_name("Rc<str>"));
+    }
+
+    #[test]
+    fn test_octet_string_type() {
+        let substitutions = Substitutions::parse("OCTET STRING (SIZE(16)) => crate::Id")
+            .unwrap()
+            .with_octet_string_type(OctetStringType::Bytes);
+        let mut model = model(
+            r"Payloads DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Packet ::= SEQUENCE {
+                id      OCTET STRING (SIZE(16)),
+                payload OCTET STRING (SIZE(0..1500)),
+                flags   BIT STRING,
+                trailer OCTET STRING DEFAULT '00'H
+            }
+            END",
+        );
+        substitutions.apply(&mut model);
+
+        let Type::Sequence(ComponentTypeList { fields, .. }) = &model.definitions[0].1.r#type
+        else {
+            panic!("Expected a SEQUENCE");
+        };
+        assert_eq!(
+            Type::Substituted(
+                Box::new(Type::OctetString(Size::Fix(16, false))),
+                Substitution::new("crate::Id"),
+            ),
+            fields[0].role.r#type
+        );
+        assert_eq!(
+            Type::Substituted(
+                Box::new(Type::OctetString(Size::Range(0, 1500, false))),
+                Substitution::new("::bytes::Bytes"),
+            ),
+            fields[1].role.r#type
+        );
+        assert!(matches!(fields[2].role.r#type, Type::BitString(_)));
+        assert_eq!(Type::OctetString(Size::Any), fields[3].role.r#type);
+        assert_eq!(
+            Some(OctetStringType::Bytes),
+            OctetStringType::from_name("Bytes")
+        );
+    }
+}
@@ -46,8 +46,13 @@ impl Tag {
     pub const DEFAULT_BIT_STRING: Tag = Tag::Universal(3);
     pub const DEFAULT_OCTET_STRING: Tag = Tag::Universal(4);
     pub const DEFAULT_NULL: Tag = Tag::Universal(5);
+    pub const DEFAULT_OBJECT_IDENTIFIER: Tag = Tag::Universal(6);
+    pub const DEFAULT_EXTERNAL: Tag = Tag::Universal(8);
+    pub const DEFAULT_REAL: Tag = Tag::Universal(9);
     pub const DEFAULT_ENUMERATED: Tag = Tag::Universal(10);
+    pub const DEFAULT_EMBEDDED_PDV: Tag = Tag::Universal(11);
     pub const DEFAULT_UTF8_STRING: Tag = Tag::Universal(12);
+    pub const DEFAULT_RELATIVE_OID: Tag = Tag::Universal(13);
     pub const DEFAULT_SEQUENCE: Tag = Tag::Universal(16);
     pub const DEFAULT_SEQUENCE_OF: Tag = Tag::Universal(16);
     pub const DEFAULT_SET: Tag = Tag::Universal(17);
@@ -73,6 +78,8 @@ impl Tag {
     pub const DEFAULT_UNIVERSAL_STRING: Tag = Tag::Universal(28);
     /// ITU-T Rec. X.680, 41
     pub const DEFAULT_BMP_STRING: Tag = Tag::Universal(30);
+    /// ITU-T Rec. X.680, 8.6
+    pub const DEFAULT_OID_IRI: Tag = Tag::Universal(35);
 
     #[inline]
     pub fn value(self) -> usize {
@@ -1,47 +1,99 @@
 use crate::asn::Charset;
 use crate::asn::{Asn, Tag, TagProperty, Type};
 use crate::model::{Definition, Model};
+use crate::resolve::Error;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
 
+/// Resolves the tags of types. Once resolved, the tag of a referenced definition is memoized for
+/// the lifetime of the resolver, so that large interlinked schemas resolve each definition only
+/// once.
 pub struct TagResolver<'a> {
     model: &'a Model<Asn>,
     scope: &'a [&'a Model<Asn>],
+    /// The resolved tags by module and definition name
+    cache: RefCell<BTreeMap<(String, String), Option<Tag>>>,
+    /// The module and definition names currently being resolved, to detect cycles
+    resolving: RefCell<Vec<(String, String)>>,
 }
 
 impl TagResolver<'_> {
     pub const fn new<'a>(model: &'a Model<Asn>, scope: &'a [&'a Model<Asn>]) -> TagResolver<'a> {
-        TagResolver { model, scope }
+        TagResolver {
+            model,
+            scope,
+            cache: RefCell::new(BTreeMap::new()),
+            resolving: RefCell::new(Vec::new()),
+        }
     }
 
     pub fn resolve_default(ty: &Type) -> Option<Tag> {
         let model = Model::<Asn>::default();
-        TagResolver {
-            model: &model,
-            scope: &[],
-        }
-        .resolve_type_tag(ty)
+        TagResolver::new(&model, &[]).resolve_type_tag(ty)
     }
 
     /// ITU-T X.680 | ISO/IEC 8824-1, 8.6
     /// ITU-T X.680 | ISO/IEC 8824-1, 41, table 8
+    ///
+    /// Cyclic references resolve to no tag, see [`TagResolver::try_resolve_tag`]
     pub fn resolve_tag(&self, ty: &str) -> Option<Tag> {
-        self.model
+        self.try_resolve_tag(ty).ok().flatten()
+    }
+
+    /// Like [`TagResolver::resolve_tag`], but fails with [`Error::CyclicTagReference`] if the
+    /// tag of the type depends on itself
+    pub fn try_resolve_tag(&self, ty: &str) -> Result<Option<Tag>, Error> {
+        self.try_resolve_tag_in(self.model, ty)
+    }
+
+    fn try_resolve_tag_in(&self, model: &Model<Asn>, ty: &str) -> Result<Option<Tag>, Error> {
+        let key = (model.name.clone(), ty.to_string());
+        if let Some(tag) = self.cache.borrow().get(&key) {
+            return Ok(*tag);
+        }
+
+        if let Some(start) = self.resolving.borrow().iter().position(|r| key.eq(r)) {
+            return Err(Error::CyclicTagReference(
+                self.resolving.borrow()[start..]
+                    .iter()
+                    .map(|(_model, name)| name.clone())
+                    .chain(std::iter::once(key.1))
+                    .collect(),
+            ));
+        }
+
+        self.resolving.borrow_mut().push(key);
+        let result = self.try_resolve_tag_uncached(model, ty);
+        let key = self.resolving.borrow_mut().pop();
+
+        let tag = result?;
+        if let Some(key) = key {
+            self.cache.borrow_mut().insert(key, tag);
+        }
+        Ok(tag)
+    }
+
+    fn try_resolve_tag_uncached(&self, model: &Model<Asn>, ty: &str) -> Result<Option<Tag>, Error> {
+        let imported_from = model
             .imports
             .iter()
             .find(|import| import.what.iter().any(|what| what.eq(ty)))
             .map(|import| &import.from)
-            .and_then(|model_name| self.scope.iter().find(|model| model.name.eq(model_name)))
-            .and_then(|model| {
-                TagResolver {
-                    model,
-                    scope: self.scope,
-                }
-                .resolve_tag(ty)
-            })
-            .or_else(|| {
-                self.model.definitions.iter().find(|d| d.0.eq(ty)).and_then(
-                    |Definition(_name, asn)| asn.tag.or_else(|| self.resolve_type_tag(&asn.r#type)),
-                )
-            })
+            .and_then(|model_name| self.scope.iter().find(|model| model.name.eq(model_name)));
+
+        if let Some(imported_from) = imported_from {
+            if let Some(tag) = self.try_resolve_tag_in(imported_from, ty)? {
+                return Ok(Some(tag));
+            }
+        }
+
+        match model.definitions.iter().find(|d| d.0.eq(ty)) {
+            Some(Definition(_name, asn)) => match asn.tag {
+                Some(tag) => Ok(Some(tag)),
+                None => self.try_resolve_type_tag_in(model, &asn.r#type),
+            },
+            None => Ok(None),
+        }
     }
 
     /// ITU-T X.680 | ISO/IEC 8824-1, 8.6
@@ -54,36 +106,60 @@ impl TagResolver<'_> {
 
     /// ITU-T X.680 | ISO/IEC 8824-1, 8.6
     /// ITU-T X.680 | ISO/IEC 8824-1, 41, table 8
+    ///
+    /// Cyclic references resolve to no tag, see [`TagResolver::try_resolve_type_tag`]
     pub fn resolve_type_tag(&self, ty: &Type) -> Option<Tag> {
-        match ty {
+        self.try_resolve_type_tag(ty).ok().flatten()
+    }
+
+    /// Like [`TagResolver::resolve_type_tag`], but fails with [`Error::CyclicTagReference`] if
+    /// the tag of the type depends on itself
+    pub fn try_resolve_type_tag(&self, ty: &Type) -> Result<Option<Tag>, Error> {
+        self.try_resolve_type_tag_in(self.model, ty)
+    }
+
+    fn try_resolve_type_tag_in(&self, model: &Model<Asn>, ty: &Type) -> Result<Option<Tag>, Error> {
+        Ok(match ty {
             Type::Boolean => Some(Tag::DEFAULT_BOOLEAN),
             Type::Integer(_) => Some(Tag::DEFAULT_INTEGER),
             Type::BitString(_) => Some(Tag::DEFAULT_BIT_STRING),
-            Type::OctetString(_) => Some(Tag::DEFAULT_OCTET_STRING),
+            Type::OctetString(_) | Type::Containing(..) => Some(Tag::DEFAULT_OCTET_STRING),
             Type::Enumerated(_) => Some(Tag::DEFAULT_ENUMERATED),
             Type::String(_, Charset::Numeric) => Some(Tag::DEFAULT_NUMERIC_STRING),
             Type::String(_, Charset::Printable) => Some(Tag::DEFAULT_PRINTABLE_STRING),
             Type::String(_, Charset::Visible) => Some(Tag::DEFAULT_VISIBLE_STRING),
+            Type::String(_, Charset::Bmp) => Some(Tag::DEFAULT_BMP_STRING),
+            Type::String(_, Charset::Teletex) => Some(Tag::DEFAULT_TELETEXT_STRING),
             Type::String(_, Charset::Utf8) => Some(Tag::DEFAULT_UTF8_STRING),
             Type::String(_, Charset::Ia5) => Some(Tag::DEFAULT_IA5_STRING),
             Type::Null => Some(Tag::DEFAULT_NULL),
-            Type::Optional(inner) => self.resolve_type_tag(inner),
-            Type::Default(inner, ..) => self.resolve_type_tag(inner),
+            Type::Real(_) => Some(Tag::DEFAULT_REAL),
+            Type::ObjectIdentifier => Some(Tag::DEFAULT_OBJECT_IDENTIFIER),
+            Type::RelativeOid => Some(Tag::DEFAULT_RELATIVE_OID),
+            Type::OidIri => Some(Tag::DEFAULT_OID_IRI),
+            Type::Optional(inner) => self.try_resolve_type_tag_in(model, inner)?,
+            Type::Default(inner, ..) => self.try_resolve_type_tag_in(model, inner)?,
+            Type::Substituted(inner, _) => self.try_resolve_type_tag_in(model, inner)?,
             Type::Sequence(_) => Some(Tag::DEFAULT_SEQUENCE),
             Type::SequenceOf(_, _) => Some(Tag::DEFAULT_SEQUENCE_OF),
             Type::Set(_) => Some(Tag::DEFAULT_SET),
             Type::SetOf(_, _) => Some(Tag::DEFAULT_SET_OF),
             Type::Choice(choice) => {
-                let mut tags = choice
-                    .variants()
-                    .take(
-                        choice
-                            .extension_after_index()
-                            .map(|extension_after| extension_after + 1)
-                            .unwrap_or_else(|| choice.len()),
-                    )
-                    .map(|v| v.tag().or_else(|| self.resolve_type_tag(v.r#type())))
-                    .collect::<Option<Vec<Tag>>>()?;
+                let mut tags = Vec::with_capacity(choice.len());
+                for variant in choice.variants().take(
+                    choice
+                        .extension_after_index()
+                        .map(|extension_after| extension_after + 1)
+                        .unwrap_or_else(|| choice.len()),
+                ) {
+                    match variant.tag() {
+                        Some(tag) => tags.push(tag),
+                        None => match self.try_resolve_type_tag_in(model, variant.r#type())? {
+                            Some(tag) => tags.push(tag),
+                            None => return Ok(None),
+                        },
+                    }
+                }
                 tags.sort();
                 if cfg!(feature = "debug-proc-macro") {
                     println!("resolved::::{:?}", tags);
@@ -91,12 +167,178 @@ impl TagResolver<'_> {
                 tags.into_iter().next()
             }
             Type::TypeReference(inner, tag) => {
-                let tag = (*tag).or_else(|| self.resolve_tag(inner.as_str()));
+                let tag = match tag {
+                    Some(tag) => Some(*tag),
+                    None => self.try_resolve_tag_in(model, inner.as_str())?,
+                };
                 if cfg!(feature = "debug-proc-macro") {
                     println!("resolved :: {}::Tag = {:?}", inner, tag);
                 }
                 tag
             }
+        })
+    }
+
+    /// ITU-T X.680 | ISO/IEC 8824-1, 27.3
+    ///
+    /// Fails with [`Error::DuplicateSetTag`] if two components of a `SET` of the model - also of
+    /// one declared inline - have the same tag. The components of a `SET` that are tagged
+    /// automatically are distinct by their position, components with an unresolvable tag are
+    /// skipped.
+    pub fn try_check_set_tags(&self) -> Result<(), Error> {
+        for Definition(name, asn) in &self.model.definitions {
+            self.check_set_tags(name, &asn.r#type)?;
+        }
+        Ok(())
+    }
+
+    fn check_set_tags(&self, path: &str, ty: &Type) -> Result<(), Error> {
+        match ty {
+            Type::Set(list) => {
+                let automatic =
+                    self.model.automatic_tags && list.fields.iter().all(|f| f.role.tag.is_none());
+                let mut tags = Vec::<(Tag, &str)>::with_capacity(list.fields.len());
+                for field in list.fields.iter().filter(|_| !automatic) {
+                    let tag = field
+                        .role
+                        .tag
+                        .or_else(|| self.resolve_type_tag(&field.role.r#type));
+                    if let Some(tag) = tag {
+                        if let Some((_, other)) = tags.iter().find(|(t, _)| tag.eq(t)) {
+                            return Err(Error::DuplicateSetTag(
+                                path.to_string(),
+                                other.to_string(),
+                                field.name.clone(),
+                            ));
+                        }
+                        tags.push((tag, &field.name));
+                    }
+                }
+                for field in &list.fields {
+                    self.check_set_tags(&format!("{}.{}", path, field.name), &field.role.r#type)?;
+                }
+            }
+            Type::Sequence(list) => {
+                for field in &list.fields {
+                    self.check_set_tags(&format!("{}.{}", path, field.name), &field.role.r#type)?;
+                }
+            }
+            Type::Choice(choice) => {
+                for variant in choice.variants() {
+                    self.check_set_tags(&format!("{}.{}", path, variant.name()), variant.r#type())?;
+                }
+            }
+            Type::SequenceOf(inner, _)
+            | Type::SetOf(inner, _)
+            | Type::Optional(inner)
+            | Type::Default(inner, ..)
+            | Type::Substituted(inner, _) => self.check_set_tags(path, inner)?,
+            _ => {}
         }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Tokenizer;
+
+    fn resolved(asn: &str) -> Model<Asn> {
+        Model::try_from(Tokenizer.parse(asn))
+            .unwrap()
+            .try_resolve()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_cyclic_references_are_named() {
+        let model = resolved(
+            r"Cycle DEFINITIONS ::= BEGIN
+            A ::= B
+            B ::= CHOICE { c C, d BOOLEAN }
+            C ::= A
+            END",
+        );
+        let resolver = TagResolver::new(&model, &[]);
+        assert_eq!(
+            Err(Error::CyclicTagReference(vec![
+                "A".to_string(),
+                "B".to_string(),
+                "C".to_string(),
+                "A".to_string()
+            ])),
+            resolver.try_resolve_tag("A")
+        );
+        assert_eq!(None, resolver.resolve_tag("C"));
+    }
+
+    #[test]
+    fn test_resolved_tags_are_memoized() {
+        let model = resolved(
+            r"Memo DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            A ::= B
+            B ::= [APPLICATION 7] SEQUENCE { a BOOLEAN }
+            END",
+        );
+        let resolver = TagResolver::new(&model, &[]);
+        assert_eq!(Ok(Some(Tag::Application(7))), resolver.try_resolve_tag("A"));
+        assert_eq!(
+            Some(&Some(Tag::Application(7))),
+            resolver
+                .cache
+                .borrow()
+                .get(&("Memo".to_string(), "B".to_string()))
+        );
+        assert_eq!(Some(Tag::Application(7)), resolver.resolve_tag("B"));
+    }
+
+    #[test]
+    fn test_duplicate_set_tags() {
+        let try_resolve = |asn: &str| Model::try_from(Tokenizer.parse(asn)).unwrap().try_resolve();
+
+        assert_eq!(
+            Some(Error::DuplicateSetTag(
+                "Outer.inner".to_string(),
+                "a".to_string(),
+                "c".to_string()
+            )),
+            try_resolve(
+                r"Duplicates DEFINITIONS ::= BEGIN
+                Outer ::= SEQUENCE {
+                    inner SET { a INTEGER, b BOOLEAN, c Number OPTIONAL } OPTIONAL
+                }
+                Number ::= INTEGER
+                END",
+            )
+            .err()
+        );
+        assert_eq!(
+            Some(Error::DuplicateSetTag(
+                "Set.list.b".to_string(),
+                "a".to_string(),
+                "b".to_string()
+            )),
+            try_resolve(
+                r"Duplicates DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Set ::= SET {
+                    list SEQUENCE OF CHOICE {
+                        a BOOLEAN,
+                        b SET { a [1] INTEGER, b [1] BOOLEAN }
+                    }
+                }
+                END",
+            )
+            .err()
+        );
+
+        // distinct by their position and by their tags
+        assert!(try_resolve(
+            r"Distinct DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Automatic ::= SET { a INTEGER, b INTEGER }
+            Tagged ::= SET { a [0] INTEGER, b [1] INTEGER }
+            END",
+        )
+        .is_ok());
     }
 }
@@ -0,0 +1,428 @@
+//! A machine-readable catalog of the definitions of resolved [`Model`]s.
+//!
+//! Other than the [`schema`](crate::schema), the catalog is not meant to decode values, but to
+//! describe them to humans and tools like monitoring dashboards: every definition is listed with
+//! its (flattened) fields, their constraints, their physical unit (see
+//! [`Annotation::Unit`](crate::asn::Annotation::Unit)) and whether they are sensitive.
+//!
+//! ```rust
+//! use asn1rs_model::catalog::Catalog;
+//! use asn1rs_model::parse::Tokenizer;
+//! use asn1rs_model::Model;
+//!
+//! let model = Model::try_from(Tokenizer::default().parse(
+//!     r"Basic DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+//!     Tank ::= SEQUENCE {
+//!         fuel INTEGER (0..1000) -- @unit 0.1 l
+//!     }
+//!     END",
+//! ))
+//! .unwrap()
+//! .try_resolve()
+//! .unwrap();
+//!
+//! let catalog = Catalog::from(vec![model]);
+//! assert_eq!(Some("0.1 l"), catalog.entries[0].fields[0].unit.as_deref());
+//! assert!(catalog.to_json().contains(r#""path": "fuel""#));
+//! ```
+
+use crate::asn::{Asn, Charset, Range, Size, Type};
+use crate::model::{Definition, Model};
+use std::fmt::Write;
+
+/// The version of the JSON layout, incremented on incompatible changes
+pub const VERSION: u8 = 1;
+
+/// The file extension used for catalogs written by the converter
+pub const FILE_EXTENSION: &str = "catalog.json";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Catalog {
+    pub entries: Vec<Entry>,
+}
+
+/// A definition of an ASN.1 module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub module: String,
+    pub name: String,
+    pub r#type: String,
+    pub constraint: Option<String>,
+    pub unit: Option<String>,
+    pub sensitive: bool,
+    /// The names of the variants of an `ENUMERATED`
+    pub values: Vec<String>,
+    /// The components of the definition, nested components included
+    pub fields: Vec<CatalogField>,
+}
+
+/// A component of a definition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogField {
+    /// The names of the enclosing components and of the component itself, separated by `.`.
+    /// The element of a `SEQUENCE OF` or `SET OF` is named `[]`.
+    pub path: String,
+    pub r#type: String,
+    /// Whether the component might be absent, this is also the case for `CHOICE` variants
+    pub optional: bool,
+    pub constraint: Option<String>,
+    pub unit: Option<String>,
+    pub sensitive: bool,
+    /// The names of the variants of an `ENUMERATED`
+    pub values: Vec<String>,
+}
+
+impl From<Vec<Model<Asn>>> for Catalog {
+    fn from(models: Vec<Model<Asn>>) -> Self {
+        Self::from(&models[..])
+    }
+}
+
+impl From<&[Model<Asn>]> for Catalog {
+    fn from(models: &[Model<Asn>]) -> Self {
+        let entries = models
+            .iter()
+            .flat_map(|model| {
+                model
+                    .definitions
+                    .iter()
+                    .map(move |Definition(name, asn)| Entry::new(&model.name, name, asn))
+            })
+            .collect();
+        Self { entries }
+    }
+}
+
+impl Catalog {
+    pub fn entry(&self, module: &str, name: &str) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .find(|e| e.module == module && e.name == name)
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        let _ = writeln!(json, "{{");
+        let _ = writeln!(json, "  \"version\": {},", VERSION);
+        let _ = writeln!(json, "  \"entries\": [");
+        for (index, entry) in self.entries.iter().enumerate() {
+            let _ = writeln!(json, "    {{");
+            let _ = writeln!(json, "      \"module\": {},", string(&entry.module));
+            let _ = writeln!(json, "      \"name\": {},", string(&entry.name));
+            let _ = writeln!(json, "      \"type\": {},", string(&entry.r#type));
+            let _ = writeln!(
+                json,
+                "      \"constraint\": {},",
+                optional_string(entry.constraint.as_deref())
+            );
+            let _ = writeln!(
+                json,
+                "      \"unit\": {},",
+                optional_string(entry.unit.as_deref())
+            );
+            let _ = writeln!(json, "      \"sensitive\": {},", entry.sensitive);
+            let _ = writeln!(json, "      \"values\": {},", strings(&entry.values));
+            if entry.fields.is_empty() {
+                let _ = writeln!(json, "      \"fields\": []");
+            } else {
+                let _ = writeln!(json, "      \"fields\": [");
+                for (index, field) in entry.fields.iter().enumerate() {
+                    let _ = writeln!(
+                        json,
+                        "        {{\"path\": {}, \"type\": {}, \"optional\": {}, \"constraint\": {}, \"unit\": {}, \"sensitive\": {}, \"values\": {}}}{}",
+                        string(&field.path),
+                        string(&field.r#type),
+                        field.optional,
+                        optional_string(field.constraint.as_deref()),
+                        optional_string(field.unit.as_deref()),
+                        field.sensitive,
+                        strings(&field.values),
+                        separator(index, entry.fields.len()),
+                    );
+                }
+                let _ = writeln!(json, "      ]");
+            }
+            let _ = writeln!(json, "    }}{}", separator(index, self.entries.len()));
+        }
+        let _ = writeln!(json, "  ]");
+        let _ = writeln!(json, "}}");
+        json
+    }
+}
+
+impl Entry {
+    fn new(module: &str, name: &str, asn: &Asn) -> Self {
+        let mut fields = Vec::new();
+        collect_fields(&mut fields, None, &asn.r#type);
+        Self {
+            module: module.to_string(),
+            name: name.to_string(),
+            r#type: type_name(&asn.r#type),
+            constraint: constraint(&asn.r#type),
+            unit: asn.unit.clone(),
+            sensitive: asn.sensitive,
+            values: values(&asn.r#type),
+            fields,
+        }
+    }
+}
+
+fn collect_fields(fields: &mut Vec<CatalogField>, prefix: Option<&str>, ty: &Type) {
+    let path = |name: &str| match prefix {
+        Some(prefix) => format!("{}.{}", prefix, name),
+        None => name.to_string(),
+    };
+    match unwrap(ty) {
+        Type::Sequence(list) | Type::Set(list) => {
+            for field in &list.fields {
+                let path = path(&field.name);
+                fields.push(CatalogField::new(
+                    path.clone(),
+                    &field.role.r#type,
+                    matches!(field.role.r#type, Type::Optional(_) | Type::Default(..)),
+                    field.role.unit.clone(),
+                    field.role.sensitive,
+                ));
+                collect_fields(fields, Some(&path), &field.role.r#type);
+            }
+        }
+        Type::Choice(choice) => {
+            for variant in choice.variants() {
+                let path = path(variant.name());
+                fields.push(CatalogField::new(
+                    path.clone(),
+                    variant.r#type(),
+                    true,
+                    None,
+                    variant.sensitive,
+                ));
+                collect_fields(fields, Some(&path), variant.r#type());
+            }
+        }
+        Type::SequenceOf(inner, _) | Type::SetOf(inner, _) => {
+            let path = path("[]");
+            fields.push(CatalogField::new(path.clone(), inner, false, None, false));
+            collect_fields(fields, Some(&path), inner);
+        }
+        _ => {}
+    }
+}
+
+impl CatalogField {
+    fn new(path: String, ty: &Type, optional: bool, unit: Option<String>, sensitive: bool) -> Self {
+        Self {
+            path,
+            r#type: type_name(ty),
+            optional,
+            constraint: constraint(ty),
+            unit,
+            sensitive,
+            values: values(ty),
+        }
+    }
+}
+
+/// Removes the wrappers that do not change what the value is
+fn unwrap(ty: &Type) -> &Type {
+    match ty {
+        Type::Optional(inner) | Type::Default(inner, _) | Type::Substituted(inner, _) => {
+            unwrap(inner)
+        }
+        ty => ty,
+    }
+}
+
+fn type_name(ty: &Type) -> String {
+    match unwrap(ty) {
+        Type::Boolean => "BOOLEAN",
+        Type::Integer(_) => "INTEGER",
+        Type::String(_, Charset::Utf8) => "UTF8String",
+        Type::String(_, Charset::Numeric) => "NumericString",
+        Type::String(_, Charset::Printable) => "PrintableString",
+        Type::String(_, Charset::Ia5) => "IA5String",
+        Type::String(_, Charset::Visible) => "VisibleString",
+        Type::String(_, Charset::Bmp) => "BMPString",
+        Type::String(_, Charset::Teletex) => "TeletexString",
+        Type::OctetString(_) | Type::Containing(..) => "OCTET STRING",
+        Type::BitString(_) => "BIT STRING",
+        Type::Null => "NULL",
+        Type::Real(_) => "REAL",
+        Type::ObjectIdentifier => "OBJECT IDENTIFIER",
+        Type::RelativeOid => "RELATIVE-OID",
+        Type::OidIri => "OID-IRI",
+        Type::Sequence(_) => "SEQUENCE",
+        Type::SequenceOf(..) => "SEQUENCE OF",
+        Type::Set(_) => "SET",
+        Type::SetOf(..) => "SET OF",
+        Type::Enumerated(_) => "ENUMERATED",
+        Type::Choice(_) => "CHOICE",
+        Type::TypeReference(name, _) => return name.clone(),
+        Type::Optional(_) | Type::Default(..) | Type::Substituted(..) => unreachable!(),
+    }
+    .to_string()
+}
+
+fn constraint(ty: &Type) -> Option<String> {
+    match unwrap(ty) {
+        Type::Integer(integer) => range_constraint(&integer.range),
+        Type::String(size, _)
+        | Type::OctetString(size)
+        | Type::SequenceOf(_, size)
+        | Type::SetOf(_, size) => size_constraint(size),
+        Type::BitString(bit_string) => size_constraint(&bit_string.size),
+        _ => None,
+    }
+}
+
+fn range_constraint(range: &Range<Option<i64>>) -> Option<String> {
+    let Range(min, max, extensible) = range;
+    if min.is_none() && max.is_none() {
+        return None;
+    }
+    Some(format!(
+        "{}..{}{}",
+        min.map(|min| min.to_string())
+            .unwrap_or_else(|| "MIN".to_string()),
+        max.map(|max| max.to_string())
+            .unwrap_or_else(|| "MAX".to_string()),
+        if *extensible { ",..." } else { "" }
+    ))
+}
+
+fn size_constraint(size: &Size<usize>) -> Option<String> {
+    size.to_constraint_string()
+        .map(|constraint| constraint.replacen("size", "SIZE", 1))
+}
+
+fn values(ty: &Type) -> Vec<String> {
+    match unwrap(ty) {
+        Type::Enumerated(enumerated) => enumerated
+            .variants()
+            .map(|variant| variant.name().to_string())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn separator(index: usize, len: usize) -> &'static str {
+    if index + 1 < len {
+        ","
+    } else {
+        ""
+    }
+}
+
+fn optional_string(value: Option<&str>) -> String {
+    value.map(string).unwrap_or_else(|| "null".to_string())
+}
+
+fn strings(values: &[String]) -> String {
+    format!(
+        "[{}]",
+        values
+            .iter()
+            .map(|value| string(value))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+fn string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Tokenizer;
+
+    fn catalog(asn: &str) -> Catalog {
+        Catalog::from(vec![Model::try_from(Tokenizer.parse(asn))
+            .unwrap()
+            .try_resolve()
+            .unwrap()])
+    }
+
+    #[test]
+    fn test_nested_fields_are_flattened() {
+        let catalog = catalog(
+            r"Dashboard DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Report ::= SEQUENCE {
+                position SEQUENCE {
+                    lat INTEGER (-900000000..900000001), -- @unit 0.1 microdegree
+                    lon INTEGER (-1800000000..1800000001) -- @unit 0.1 microdegree
+                },
+                readings SEQUENCE (SIZE(1..8)) OF INTEGER (0..4095),
+                state ENUMERATED { idle, driving, ... },
+                driver UTF8String OPTIONAL, -- @sensitive
+                source Source
+            }
+            Source ::= CHOICE { gps NULL, manual IA5String (SIZE(0..32)) }
+            END",
+        );
+
+        let report = catalog.entry("Dashboard", "Report").unwrap();
+        assert_eq!("SEQUENCE", report.r#type);
+        assert_eq!(
+            vec![
+                "position",
+                "position.lat",
+                "position.lon",
+                "readings",
+                "readings.[]",
+                "state",
+                "driver",
+                "source"
+            ],
+            report
+                .fields
+                .iter()
+                .map(|f| f.path.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some("-900000000..900000001"),
+            report.fields[1].constraint.as_deref()
+        );
+        assert_eq!(Some("0.1 microdegree"), report.fields[2].unit.as_deref());
+        assert_eq!(Some("SIZE(1..8)"), report.fields[3].constraint.as_deref());
+        assert_eq!(Some("0..4095"), report.fields[4].constraint.as_deref());
+        assert_eq!(vec!["idle", "driving"], report.fields[5].values);
+        assert!(report.fields[6].optional && report.fields[6].sensitive);
+        assert_eq!("UTF8String", report.fields[6].r#type);
+        assert_eq!("Source", report.fields[7].r#type);
+
+        let source = catalog.entry("Dashboard", "Source").unwrap();
+        assert!(source.fields.iter().all(|f| f.optional));
+        assert_eq!(Some("SIZE(0..32)"), source.fields[1].constraint.as_deref());
+    }
+
+    #[test]
+    fn test_json_is_escaped() {
+        assert_eq!(r#""a\"b\\c\nd\u0001""#, string("a\"b\\c\nd\u{1}"));
+        let json = catalog(
+            r"Basic DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Small ::= INTEGER (0..255, ...)
+            END",
+        )
+        .to_json();
+        assert!(json.contains(r#""constraint": "0..255,...""#));
+        assert!(json.contains(r#""fields": []"#));
+    }
+}
@@ -0,0 +1,592 @@
+//! The memory footprint of the generated types and the sizes of their UPER encodings, derived
+//! from their constraints - to budget the RAM and the link-time size per PDU before the code is
+//! compiled for the target. Generated into the `footprint()` fn of each module by
+//! [`RustCodeGenerator::set_footprint`](crate::generate::rust::RustCodeGenerator::set_footprint),
+//! which adds the `size_of` of the types.
+//!
+//! ```rust
+//! use asn1rs_model::footprint::{heap_expr, uper_bits, Bits};
+//! use asn1rs_model::parse::Tokenizer;
+//! use asn1rs_model::Model;
+//!
+//! let model = Model::try_from(Tokenizer::default().parse(
+//!     r"Budget DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+//!     Report ::= SEQUENCE {
+//!         id      INTEGER (0..255),
+//!         name    IA5String (SIZE(1..8)),
+//!         comment UTF8String OPTIONAL
+//!     }
+//!     Reports ::= SEQUENCE (SIZE(0..4)) OF INTEGER (0..255)
+//!     END",
+//! ))
+//! .unwrap()
+//! .try_resolve()
+//! .unwrap()
+//! .to_rust();
+//!
+//! assert_eq!(Bits { min: 1 + 8 + 3 + 7, max: None }, uper_bits(&model, "Report"));
+//! assert_eq!(None, heap_expr(&model, "Report"));
+//! assert_eq!(Bits { min: 3, max: Some(3 + 4 * 8) }, uper_bits(&model, "Reports"));
+//! assert_eq!(Some("4 * ::core::mem::size_of::<u8>()".to_string()), heap_expr(&model, "Reports"));
+//! ```
+
+use crate::asn::{Charset, Size};
+use crate::model::Model;
+use crate::rust::{DataEnum, PlainEnum, Rust, RustType};
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+/// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 11.9.3.7: lengths from 16K on are fragmented
+const LENGTH_16K: u64 = 16384;
+
+/// Extension indexes below this limit are encoded as normally small numbers of 7 bits
+const SMALL_NON_NEGATIVE_NUMBER: u64 = 64;
+
+/// The most octets of the contents of a `REAL` - the header, two octets of the exponent and
+/// seven of the mantissa of a `f64`
+const REAL_MAX_OCTETS: u64 = 10;
+
+/// The number of bits of the UPER encodings of the values of a type. The maximum is `None` for
+/// unbounded types, such as lists without an upper bound of their `SIZE`, extensible
+/// constraints or recursive and unknown types.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Bits {
+    pub min: u64,
+    pub max: Option<u64>,
+}
+
+impl Bits {
+    pub const fn exactly(bits: u64) -> Self {
+        Self {
+            min: bits,
+            max: Some(bits),
+        }
+    }
+
+    pub const fn unbounded(min: u64) -> Self {
+        Self { min, max: None }
+    }
+
+    fn then(self, other: Bits) -> Self {
+        Self {
+            min: self.min.saturating_add(other.min),
+            max: self.max.zip(other.max).and_then(|(a, b)| a.checked_add(b)),
+        }
+    }
+
+    fn or(self, other: Bits) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.zip(other.max).map(|(a, b)| a.max(b)),
+        }
+    }
+
+    fn optional(self) -> Self {
+        Self {
+            min: 0,
+            max: self.max,
+        }
+    }
+
+    /// The encoding as open type: an unconstrained length determinant of the number of octets
+    /// followed by the octets
+    fn open_type(self) -> Self {
+        let min = self.min.div_ceil(8);
+        Self {
+            min: length_bits(min).unwrap_or(16) + min * 8,
+            max: self.max.and_then(|max| {
+                let octets = max.div_ceil(8);
+                Some(length_bits(octets)? + octets * 8)
+            }),
+        }
+    }
+}
+
+/// The [`Bits`] of the UPER encodings of the values of the definition with the given name
+pub fn uper_bits(model: &Model<Rust>, name: &str) -> Bits {
+    definition_bits(model, name, &mut Vec::new())
+}
+
+/// The maximum number of bytes that a value of the definition with the given name owns on the
+/// heap, as const expression that refers to the sizes of the generated types - such as
+/// `8 + 4 * ::core::mem::size_of::<Entry>()`. It estimates the encoded contents, not the spare
+/// capacity of the allocations. `None` for the types without an upper bound: lists and strings
+/// without an upper bound of their `SIZE`, recursive, unknown and substituted types - except
+/// for the fixed-capacity `heapless` types, which own no heap.
+pub fn heap_expr(model: &Model<Rust>, name: &str) -> Option<String> {
+    definition_heap(model, name, &mut Vec::new()).map(|heap| heap.to_string())
+}
+
+fn definition_bits<'a>(model: &'a Model<Rust>, name: &'a str, stack: &mut Vec<&'a str>) -> Bits {
+    let definition = match model.definition(name) {
+        Some(definition) if !stack.contains(&name) => definition,
+        _ => return Bits::unbounded(0),
+    };
+    stack.push(name);
+    let bits = match definition.value() {
+        Rust::Struct {
+            fields,
+            extension_after,
+            ..
+        } => {
+            let root = extension_after.map_or(fields.len(), |index| index + 1);
+            let mut bits = Bits::exactly(u64::from(extension_after.is_some()));
+            for field in &fields[..root] {
+                if field.r#type().is_optional() {
+                    bits = bits.then(Bits::exactly(1));
+                }
+                bits = bits.then(type_bits(model, field.r#type(), stack));
+            }
+            if root < fields.len() {
+                // the extension bitmap is encoded if any extension field is present
+                let extensions = &fields[root..];
+                let bitmap = Bits::exactly(
+                    normally_small_bits(extensions.len() as u64 - 1) + extensions.len() as u64,
+                );
+                let mut present = bitmap;
+                for field in extensions {
+                    let field_bits = type_bits(model, field.r#type().as_no_option(), stack);
+                    let open_type = field_bits.open_type();
+                    present = present.then(if field.r#type().is_optional() {
+                        open_type.optional()
+                    } else {
+                        open_type
+                    });
+                }
+                if extensions.iter().all(|field| field.r#type().is_optional()) {
+                    present = present.or(Bits::exactly(0));
+                }
+                bits = bits.then(present);
+            }
+            bits
+        }
+        Rust::Enum(enumeration) => enumerated_bits(enumeration),
+        Rust::DataEnum(enumeration) => choice_bits(model, enumeration, stack),
+        Rust::TupleStruct { r#type, .. } => type_bits(model, r#type, stack),
+    };
+    stack.pop();
+    bits
+}
+
+fn index_bits(len: usize, extension_after: Option<usize>) -> (u64, u64) {
+    let root = extension_after.map_or(len, |index| index + 1) as u64;
+    (
+        u64::from(extension_after.is_some()) + width(root.saturating_sub(1)),
+        root,
+    )
+}
+
+fn enumerated_bits(enumeration: &PlainEnum) -> Bits {
+    let (bits, root) = index_bits(enumeration.len(), enumeration.extension_after_index());
+    let mut bits = Bits::exactly(bits);
+    for index in root..enumeration.len() as u64 {
+        bits = bits.or(Bits::exactly(1 + normally_small_bits(index - root)));
+    }
+    bits
+}
+
+fn choice_bits<'a>(
+    model: &'a Model<Rust>,
+    enumeration: &'a DataEnum,
+    stack: &mut Vec<&'a str>,
+) -> Bits {
+    let (index, root) = index_bits(enumeration.len(), enumeration.extension_after_index());
+    enumeration
+        .variants()
+        .enumerate()
+        .map(|(position, variant)| {
+            let bits = type_bits(model, variant.r#type(), stack);
+            let position = position as u64;
+            if position < root {
+                Bits::exactly(index).then(bits)
+            } else {
+                Bits::exactly(1 + normally_small_bits(position - root)).then(bits.open_type())
+            }
+        })
+        .reduce(Bits::or)
+        .unwrap_or(Bits::exactly(0))
+}
+
+fn type_bits<'a>(model: &'a Model<Rust>, r#type: &'a RustType, stack: &mut Vec<&'a str>) -> Bits {
+    match r#type {
+        RustType::Bool => Bits::exactly(1),
+        RustType::I8(range) => integer_bits(*range.min(), *range.max(), range.extensible()),
+        RustType::U8(range) => integer_bits(*range.min(), *range.max(), range.extensible()),
+        RustType::I16(range) => integer_bits(*range.min(), *range.max(), range.extensible()),
+        RustType::U16(range) => integer_bits(*range.min(), *range.max(), range.extensible()),
+        RustType::I32(range) => integer_bits(*range.min(), *range.max(), range.extensible()),
+        RustType::U32(range) => integer_bits(*range.min(), *range.max(), range.extensible()),
+        RustType::I64(range) => integer_bits(*range.min(), *range.max(), range.extensible()),
+        RustType::U64(range) => match (range.min(), range.max()) {
+            (None, None) if !range.extensible() => unconstrained_integer_bits(),
+            (min, max) => integer_bits(
+                min.unwrap_or(0),
+                max.unwrap_or(i64::MAX as u64),
+                range.extensible(),
+            ),
+        },
+        RustType::F32 | RustType::F64 => octets_bits(0, Some(REAL_MAX_OCTETS)),
+        RustType::ObjectIdentifier => octets_bits(1, None),
+        RustType::RelativeOid | RustType::OidIri => octets_bits(0, None),
+        RustType::String(size, charset @ (Charset::Utf8 | Charset::Teletex)) => {
+            // not a known-multiplier character string type, the octets are counted instead
+            let (min, max) = bounds(size);
+            octets_bits(
+                min,
+                max.filter(|_| !size.extensible())
+                    .map(|max| max * charset.max_utf8_len() as u64),
+            )
+        }
+        RustType::String(size, charset) => sized_bits(
+            size,
+            Bits::exactly(match charset {
+                Charset::Numeric => 4,
+                Charset::Bmp => 16,
+                _ => 7,
+            }),
+        ),
+        RustType::VecU8(size) => sized_bits(size, Bits::exactly(8)),
+        RustType::BitVec(size) => sized_bits(size, Bits::exactly(1)),
+        RustType::Vec(inner, size, _) => sized_bits(size, type_bits(model, inner, stack)),
+        RustType::Containing(inner, None) => {
+            let bits = type_bits(model, inner, stack);
+            octets_bits(
+                bits.min.div_ceil(8).max(1),
+                bits.max.map(|max| max.div_ceil(8).max(1)),
+            )
+        }
+        RustType::Containing(_, Some(_)) => octets_bits(0, None),
+        RustType::Null => Bits::exactly(0),
+        RustType::Option(inner) | RustType::Default(inner, _) => {
+            type_bits(model, inner, stack).optional()
+        }
+        RustType::Complex(name, _) => definition_bits(model, name, stack),
+        RustType::Substituted(inner, _) => type_bits(model, inner, stack),
+    }
+}
+
+fn integer_bits(min: impl Into<i128>, max: impl Into<i128>, extensible: bool) -> Bits {
+    let (min, max) = (min.into(), max.into());
+    let root = width(u64::try_from(max - min).unwrap_or(u64::MAX));
+    if extensible {
+        // values beyond the root are encoded like unconstrained ones, in at least one octet
+        Bits {
+            min: 1 + root.min(16),
+            max: unconstrained_integer_bits()
+                .max
+                .map(|max| 1 + max.max(root)),
+        }
+    } else {
+        Bits::exactly(root)
+    }
+}
+
+/// A length determinant and up to eight octets
+fn unconstrained_integer_bits() -> Bits {
+    Bits {
+        min: 8 + 8,
+        max: Some(8 + 64),
+    }
+}
+
+/// The octets of an unconstrained octet string - preceded by their length determinant
+fn octets_bits(min: u64, max: Option<u64>) -> Bits {
+    Bits {
+        min: length_bits(min).unwrap_or(16) + min * 8,
+        max: max.and_then(|max| Some(length_bits(max)? + max * 8)),
+    }
+}
+
+/// The elements of a string or list with the given `SIZE`, preceded by the length determinant
+/// if the `SIZE` is not fixed
+fn sized_bits(size: &Size, element: Bits) -> Bits {
+    let (min, max) = bounds(size);
+    let extension = u64::from(size.extensible());
+    let root = if matches!(size, Size::Any) {
+        Bits {
+            min: length_bits(min).unwrap_or(16),
+            max: None,
+        }
+    } else {
+        let length = width(max.unwrap_or(i64::MAX as u64) - min);
+        let elements = Bits {
+            min: element.min.saturating_mul(min),
+            max: max
+                .zip(element.max)
+                .and_then(|(max, element)| max.checked_mul(element)),
+        };
+        Bits::exactly(extension + length).then(elements)
+    };
+    if size.extensible() {
+        // sizes beyond the root are allowed, down to the empty one with an unconstrained
+        // length determinant
+        Bits {
+            min: root.min.min(1 + 8),
+            max: None,
+        }
+    } else {
+        root
+    }
+}
+
+fn bounds(size: &Size) -> (u64, Option<u64>) {
+    (
+        size.min().map_or(0, |min| *min as u64),
+        size.max().map(|max| *max as u64),
+    )
+}
+
+/// The bits of an unconstrained length determinant, `None` for fragmented lengths
+fn length_bits(len: u64) -> Option<u64> {
+    if len <= 127 {
+        Some(8)
+    } else if len < LENGTH_16K {
+        Some(16)
+    } else {
+        None
+    }
+}
+
+fn normally_small_bits(value: u64) -> u64 {
+    if value < SMALL_NON_NEGATIVE_NUMBER {
+        7
+    } else {
+        let octets = (width(value).div_ceil(8)).max(1);
+        1 + 8 + octets * 8
+    }
+}
+
+/// The number of bits of the values `0..=range`
+pub(crate) fn width(range: u64) -> u64 {
+    u64::from(64 - range.leading_zeros())
+}
+
+/// A sum of constant bytes and of the terms that refer to the sizes of generated types
+#[derive(Default)]
+struct Heap {
+    bytes: u64,
+    terms: Vec<String>,
+}
+
+impl Heap {
+    fn bytes(bytes: u64) -> Self {
+        Self {
+            bytes,
+            terms: Vec::new(),
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.bytes == 0 && self.terms.is_empty()
+    }
+
+    fn add(mut self, other: Heap) -> Self {
+        self.bytes += other.bytes;
+        self.terms.extend(other.terms);
+        self
+    }
+
+    /// The larger one of both, only known at compile time if both refer to generated types
+    fn max(self, other: Heap) -> Self {
+        if self.terms.is_empty() && other.terms.is_empty() {
+            Self::bytes(self.bytes.max(other.bytes))
+        } else if self.is_zero() {
+            other
+        } else if other.is_zero() {
+            self
+        } else {
+            Self {
+                bytes: 0,
+                terms: vec![format!("::asn1rs::footprint::max({}, {})", self, other)],
+            }
+        }
+    }
+
+    /// The given number of elements of the given type, each owning the given heap
+    fn elements(count: u64, element: &RustType, heap: Heap) -> Self {
+        let size = format!("::core::mem::size_of::<{}>()", element.to_string());
+        let element = if heap.is_zero() {
+            size
+        } else {
+            format!("({} + {})", size, heap)
+        };
+        match count {
+            0 => Self::default(),
+            1 => Self {
+                bytes: 0,
+                terms: vec![element],
+            },
+            count => Self {
+                bytes: 0,
+                terms: vec![format!("{} * {}", count, element)],
+            },
+        }
+    }
+}
+
+impl Display for Heap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.bytes > 0 || self.terms.is_empty() {
+            write!(f, "{}", self.bytes)?;
+            if !self.terms.is_empty() {
+                write!(f, " + ")?;
+            }
+        }
+        write!(f, "{}", self.terms.join(" + "))
+    }
+}
+
+fn definition_heap<'a>(
+    model: &'a Model<Rust>,
+    name: &'a str,
+    stack: &mut Vec<&'a str>,
+) -> Option<Heap> {
+    let definition = model.definition(name).filter(|_| !stack.contains(&name))?;
+    stack.push(name);
+    let heap = match definition.value() {
+        Rust::Struct { fields, .. } => fields.iter().try_fold(Heap::default(), |heap, field| {
+            Some(heap.add(type_heap(model, field.r#type(), stack)?))
+        }),
+        Rust::Enum(_) => Some(Heap::default()),
+        Rust::DataEnum(enumeration) => enumeration
+            .variants()
+            .try_fold(Heap::default(), |heap, variant| {
+                Some(heap.max(type_heap(model, variant.r#type(), stack)?))
+            }),
+        Rust::TupleStruct { r#type, .. } => type_heap(model, r#type, stack),
+    };
+    stack.pop();
+    heap
+}
+
+fn type_heap<'a>(
+    model: &'a Model<Rust>,
+    r#type: &'a RustType,
+    stack: &mut Vec<&'a str>,
+) -> Option<Heap> {
+    let max = |size: &Size| {
+        size.max()
+            .filter(|_| !size.extensible())
+            .map(|max| *max as u64)
+    };
+    match r#type {
+        RustType::Bool
+        | RustType::I8(_)
+        | RustType::U8(_)
+        | RustType::I16(_)
+        | RustType::U16(_)
+        | RustType::I32(_)
+        | RustType::U32(_)
+        | RustType::I64(_)
+        | RustType::U64(_)
+        | RustType::F32
+        | RustType::F64
+        | RustType::Null => Some(Heap::default()),
+        RustType::ObjectIdentifier | RustType::RelativeOid | RustType::OidIri => None,
+        RustType::String(size, charset) => {
+            Some(Heap::bytes(max(size)? * charset.max_utf8_len() as u64))
+        }
+        RustType::VecU8(size) => Some(Heap::bytes(max(size)?)),
+        RustType::BitVec(size) => Some(Heap::bytes(max(size)?.div_ceil(8))),
+        RustType::Vec(inner, size, _) => {
+            let count = max(size)?;
+            let heap = type_heap(model, inner, stack)?;
+            Some(Heap::elements(count, inner, heap))
+        }
+        RustType::Containing(inner, _) | RustType::Option(inner) | RustType::Default(inner, _) => {
+            type_heap(model, inner, stack)
+        }
+        RustType::Complex(name, _) => definition_heap(model, name, stack),
+        RustType::Substituted(_, substitution) => substitution
+            .rust_type
+            .starts_with("::heapless::")
+            .then(Heap::default),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Tokenizer;
+
+    fn model(asn: &str) -> Model<Rust> {
+        Model::try_from(Tokenizer::default().parse(asn))
+            .unwrap()
+            .try_resolve()
+            .unwrap()
+            .to_rust()
+    }
+
+    #[test]
+    fn test_bits_and_heap() {
+        let model = model(
+            r"Footprint DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Kind ::= ENUMERATED { a, b, c, ... , d }
+            Entry ::= SEQUENCE {
+                kind    Kind,
+                flag    BOOLEAN,
+                payload OCTET STRING (SIZE(4)),
+                ...,
+                extra   INTEGER (0..7) OPTIONAL
+            }
+            Entries ::= SEQUENCE (SIZE(1..2)) OF Entry
+            Shape ::= CHOICE { entries Entries, code NumericString (SIZE(3)) }
+            Tree ::= SEQUENCE { id INTEGER, children SEQUENCE OF Tree }
+            END",
+        );
+
+        assert_eq!(
+            Bits {
+                min: 1 + 2,
+                max: Some(1 + 7)
+            },
+            uper_bits(&model, "Kind")
+        );
+        // the extension bit, the kind, the flag and the payload, optionally the bitmap with its
+        // length and the extra field as open type
+        assert_eq!(
+            Bits {
+                min: 1 + 3 + 1 + 32,
+                max: Some(1 + 8 + 1 + 32 + 7 + 1 + 8 + 8)
+            },
+            uper_bits(&model, "Entry")
+        );
+        assert_eq!(
+            Bits {
+                min: 1 + 37,
+                max: Some(1 + 2 * 66)
+            },
+            uper_bits(&model, "Entries")
+        );
+        assert_eq!(
+            Bits {
+                min: 1 + 12,
+                max: Some(1 + 1 + 2 * 66)
+            },
+            uper_bits(&model, "Shape")
+        );
+        assert_eq!(
+            Bits {
+                min: 16 + 8,
+                max: None
+            },
+            uper_bits(&model, "Tree")
+        );
+        assert_eq!(Bits::unbounded(0), uper_bits(&model, "Unknown"));
+
+        assert_eq!(Some("4".to_string()), heap_expr(&model, "Entry"));
+        assert_eq!(
+            Some("2 * (::core::mem::size_of::<Entry>() + 4)".to_string()),
+            heap_expr(&model, "Entries")
+        );
+        assert_eq!(
+            Some(
+                "::asn1rs::footprint::max(2 * (::core::mem::size_of::<Entry>() + 4), 3)"
+                    .to_string()
+            ),
+            heap_expr(&model, "Shape")
+        );
+        assert_eq!(Some("0".to_string()), heap_expr(&model, "Kind"));
+        assert_eq!(None, heap_expr(&model, "Tree"));
+    }
+}
@@ -1,9 +1,13 @@
+pub mod naming;
+pub mod profile;
 #[cfg(feature = "protobuf")]
 pub mod protobuf;
 pub mod rust;
 pub mod walker;
 
-pub use self::rust::RustCodeGenerator;
+pub use self::naming::NamingStrategy;
+pub use self::profile::TargetProfile;
+pub use self::rust::{CollectionMerge, RustCodeGenerator};
 
 use crate::model::{Model, Target};
 
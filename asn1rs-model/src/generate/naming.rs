@@ -0,0 +1,113 @@
+use std::fmt::{Debug, Formatter};
+
+/// Names the types that are declared inline - such as the `ENUMERATED` type of a `SEQUENCE`
+/// component - see [`RustCodeGenerator::set_naming_strategy`]. Any
+/// `Fn(&str, &str) -> String` is a strategy as well.
+///
+/// [`RustCodeGenerator::set_naming_strategy`]: crate::generate::RustCodeGenerator::set_naming_strategy
+pub trait NamingStrategy {
+    /// The name of the type declared inline for the component or alternative of the given
+    /// parent. The name of the parent is the one given by this strategy if the parent is declared
+    /// inline as well, the name of the component is named like a type, such as `IntervalFddR13`.
+    fn inline_type_name(&self, parent: &str, component: &str) -> String;
+}
+
+impl<F: Fn(&str, &str) -> String> NamingStrategy for F {
+    fn inline_type_name(&self, parent: &str, component: &str) -> String {
+        self(parent, component)
+    }
+}
+
+impl Debug for dyn NamingStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("NamingStrategy")
+    }
+}
+
+/// The name of the parent followed by the name of the component, such as `CellIntervalFdd`,
+/// which is how inline types are named without a strategy
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ParentPrefixed;
+
+impl NamingStrategy for ParentPrefixed {
+    fn inline_type_name(&self, parent: &str, component: &str) -> String {
+        format!("{}{}", parent, component)
+    }
+}
+
+/// Like [`ParentPrefixed`], but a name longer than `max_len` is cut and suffixed by a hash of
+/// the whole name - so that it stays unique and does not change between conversions. This
+/// keeps the names of deeply nested types readable and within limits such as the 63 characters
+/// of a PostgreSQL identifier.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct HashSuffixed {
+    pub max_len: usize,
+}
+
+impl HashSuffixed {
+    /// The number of hexadecimal digits of the hash
+    pub const HASH_LEN: usize = 8;
+}
+
+impl NamingStrategy for HashSuffixed {
+    fn inline_type_name(&self, parent: &str, component: &str) -> String {
+        let name = ParentPrefixed.inline_type_name(parent, component);
+        if name.len() <= self.max_len {
+            return name;
+        }
+
+        // keep the first character, a type name must not start with a digit of the hash
+        let mut end = self.max_len.saturating_sub(Self::HASH_LEN).max(1);
+        while !name.is_char_boundary(end) {
+            end += 1;
+        }
+        format!("{}{:08X}", &name[..end], fnv1a(&name))
+    }
+}
+
+/// The 32-bit FNV-1a hash, which - unlike the hashers of the standard library - is the same for
+/// each build
+fn fnv1a(value: &str) -> u32 {
+    value.bytes().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_suffixed_cuts_long_names() {
+        let strategy = HashSuffixed { max_len: 20 };
+        assert_eq!(
+            "IntervalFddR13",
+            strategy.inline_type_name("Interval", "FddR13")
+        );
+
+        let name = strategy.inline_type_name("SystemInformationBlockType2", "AcBarringInfo");
+        assert_eq!(20, name.len());
+        assert!(name.starts_with("SystemInfor"), "{}", name);
+        assert_eq!(
+            name,
+            strategy.inline_type_name("SystemInformationBlockType2", "AcBarringInfo")
+        );
+        assert_ne!(
+            name,
+            strategy.inline_type_name("SystemInformationBlockType2", "AcBarringInfo2")
+        );
+    }
+
+    #[test]
+    fn test_hash_suffixed_keeps_first_character() {
+        let name = HashSuffixed { max_len: 4 }.inline_type_name("Parent", "Component");
+        assert_eq!(1 + HashSuffixed::HASH_LEN, name.len());
+        assert!(name.starts_with('P'), "{}", name);
+    }
+
+    #[test]
+    fn test_closure_strategy() {
+        let strategy = |_parent: &str, component: &str| format!("Inline{}", component);
+        assert_eq!("InlineMode", strategy.inline_type_name("Vehicle", "Mode"));
+    }
+}
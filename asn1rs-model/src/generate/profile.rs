@@ -0,0 +1,145 @@
+use proc_macro2::{LexError, TokenStream, TokenTree};
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+/// The environment the generated code is compiled for
+#[derive(Debug, Default, Copy, Clone, PartialOrd, PartialEq, Eq, Hash)]
+pub enum TargetProfile {
+    /// The standard library is available
+    #[default]
+    Std,
+    /// `#![no_std]` with the `alloc` crate, heap allocated types are imported from `alloc`
+    Alloc,
+    /// `#![no_std]` without an allocator, only types with a fixed capacity can be used
+    CoreHeapless,
+}
+
+/// Items that are only available with the standard library
+const STD_ITEMS: [&str; 2] = ["std", "Backtrace"];
+
+/// Items that require an allocator and are imported from `alloc` if there is one
+const ALLOC_ITEMS: [&str; 8] = [
+    "String",
+    "Vec",
+    "Box",
+    "ToString",
+    "ToOwned",
+    "format",
+    "to_string",
+    "to_vec",
+];
+
+/// Items of `asn1rs` that allocate
+const ASN1RS_ALLOC_ITEMS: [&str; 2] = ["BitVec", "Bytes"];
+
+impl TargetProfile {
+    pub const ALL: [TargetProfile; 3] = [
+        TargetProfile::Std,
+        TargetProfile::Alloc,
+        TargetProfile::CoreHeapless,
+    ];
+
+    /// The identifiers in the given code that refer to items that are not available with this
+    /// profile, in alphabetical order. Generated code that passes this check for a profile can
+    /// be compiled in such an environment, as long as the used `asn1rs` items are available too.
+    pub fn unavailable_items(self, code: &str) -> Result<Vec<String>, LexError> {
+        let mut idents = BTreeSet::default();
+        collect_idents(TokenStream::from_str(code)?, &mut idents);
+
+        let unavailable = |item: &&String| match self {
+            TargetProfile::Std => false,
+            TargetProfile::Alloc => {
+                STD_ITEMS.contains(&item.as_str())
+                    || (ALLOC_ITEMS.contains(&item.as_str()) && !idents.contains("alloc"))
+            }
+            TargetProfile::CoreHeapless => {
+                item.as_str() == "alloc"
+                    || STD_ITEMS.contains(&item.as_str())
+                    || ALLOC_ITEMS.contains(&item.as_str())
+                    || ASN1RS_ALLOC_ITEMS.contains(&item.as_str())
+            }
+        };
+
+        Ok(idents.iter().filter(unavailable).cloned().collect())
+    }
+}
+
+/// Skips the items of `heapless`, which have a fixed capacity, like `heapless::Vec`
+fn collect_idents(stream: TokenStream, idents: &mut BTreeSet<String>) {
+    // the number of tokens of `heapless ::` preceding the current token
+    let mut heapless_path = 0;
+    for tree in stream {
+        match tree {
+            TokenTree::Ident(ident) => {
+                let ident = ident.to_string();
+                let heapless_item = heapless_path == 3;
+                heapless_path = usize::from(ident == "heapless");
+                if !heapless_item {
+                    idents.insert(ident);
+                }
+            }
+            TokenTree::Punct(punct) if punct.as_char() == ':' && heapless_path > 0 => {
+                heapless_path += 1;
+            }
+            TokenTree::Group(group) => {
+                heapless_path = 0;
+                collect_idents(group.stream(), idents);
+            }
+            TokenTree::Punct(_) | TokenTree::Literal(_) => heapless_path = 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_std_allows_everything() {
+        assert!(TargetProfile::Std
+            .unavailable_items("pub struct A { b: ::std::sync::Arc<str>, c: Vec<u8> }")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_alloc_requires_alloc_imports() {
+        let code = "pub struct A { b: String, c: Vec<u8> }";
+        assert_eq!(
+            vec!["String".to_string(), "Vec".to_string()],
+            TargetProfile::Alloc.unavailable_items(code).unwrap()
+        );
+        let code = format!("use alloc::{{string::String, vec::Vec}};\n{code}");
+        assert!(TargetProfile::Alloc
+            .unavailable_items(&code)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            vec!["std".to_string()],
+            TargetProfile::Alloc
+                .unavailable_items("pub struct A(::std::sync::Arc<str>);")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_core_heapless_rejects_allocations() {
+        let code = "use alloc::vec::Vec; pub struct A { b: Vec<u8>, c: BitVec, d: [u8; 4] }";
+        assert_eq!(
+            vec!["BitVec".to_string(), "Vec".to_string(), "alloc".to_string()],
+            TargetProfile::CoreHeapless.unavailable_items(code).unwrap()
+        );
+        assert!(TargetProfile::CoreHeapless
+            .unavailable_items("pub struct A { b: u8, c: Option<bool> }")
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            vec!["Vec".to_string()],
+            TargetProfile::CoreHeapless
+                .unavailable_items(
+                    "pub struct A(::heapless::Vec<::heapless::String<3>, 2>, Vec<u8>);"
+                )
+                .unwrap()
+        );
+    }
+}
@@ -3,7 +3,7 @@ use crate::generate::Generator;
 use crate::model::Definition;
 use crate::model::Model;
 use crate::protobuf::{Protobuf, ProtobufType};
-use crate::rust::rust_module_name;
+use crate::rust::{rust_module_name, rust_struct_or_enum_name};
 use std::fmt::Error as FmtError;
 use std::fmt::Write;
 
@@ -110,32 +110,67 @@ impl ProtobufDefGenerator {
         role: &ProtobufType,
         tag: usize,
     ) -> Result<(), Error> {
-        writeln!(
-            target,
-            "    {} {}{};",
-            Self::role_to_full_type(role, model),
-            Self::field_name(name),
-            if let ProtobufType::OneOf(variants) = role {
-                let mut inner = String::new();
-                writeln!(&mut inner, " {{")?;
-                for (index, (variant_name, variant_type)) in variants.iter().enumerate() {
-                    writeln!(
-                        &mut inner,
-                        "      {} {} = {};",
-                        Self::role_to_full_type(variant_type, model),
-                        variant_name,
-                        index + 1
-                    )?;
-                }
-                write!(&mut inner, "    }}")?;
-                inner
+        if let ProtobufType::OneOf(variants) = role {
+            Self::append_one_of(target, model, name, variants)
+        } else {
+            writeln!(
+                target,
+                "    {} {} = {};",
+                Self::role_to_full_type(role, model),
+                Self::field_name(name),
+                tag
+            )?;
+            Ok(())
+        }
+    }
+
+    /// A repeated field is not allowed in a `oneof`, therefore such a variant is a message
+    /// that is nested in the enclosing message and has the values in its only field
+    pub fn append_one_of(
+        target: &mut dyn Write,
+        model: &Model<Protobuf>,
+        name: &str,
+        variants: &[(String, ProtobufType)],
+    ) -> Result<(), Error> {
+        writeln!(target, "    oneof {} {{", Self::field_name(name))?;
+        for (index, (variant_name, variant_type)) in variants.iter().enumerate() {
+            let variant_type = if let ProtobufType::Repeated(_) = variant_type {
+                Self::repeated_variant_message_name(variant_name)
             } else {
-                format!(" = {}", tag)
+                Self::role_to_full_type(variant_type, model)
+            };
+            writeln!(
+                target,
+                "      {} {} = {};",
+                variant_type,
+                variant_name,
+                index + 1
+            )?;
+        }
+        writeln!(target, "    }}")?;
+
+        for (variant_name, variant_type) in variants {
+            if let ProtobufType::Repeated(_) = variant_type {
+                writeln!(
+                    target,
+                    "    message {} {{",
+                    Self::repeated_variant_message_name(variant_name)
+                )?;
+                writeln!(
+                    target,
+                    "        {} value = 1;",
+                    Self::role_to_full_type(variant_type, model)
+                )?;
+                writeln!(target, "    }}")?;
             }
-        )?;
+        }
         Ok(())
     }
 
+    pub fn repeated_variant_message_name(variant_name: &str) -> String {
+        format!("{}List", rust_struct_or_enum_name(variant_name))
+    }
+
     pub fn append_variant(
         target: &mut dyn Write,
         base: &str,
@@ -175,6 +210,9 @@ impl ProtobufDefGenerator {
             ProtobufType::Repeated(inner) => {
                 format!("repeated {}", Self::role_to_full_type(inner, model))
             }
+            ProtobufType::Optional(inner) => {
+                format!("optional {}", Self::role_to_full_type(inner, model))
+            }
             r => r.to_string(),
         }
     }
@@ -269,4 +307,90 @@ mod tests {
         assert_eq!("ABC_DEF", ProtobufDefGenerator::variant_name("AbcDef"));
         assert_eq!("ABC_DEF", ProtobufDefGenerator::variant_name("ABcDef"));
     }
+
+    #[test]
+    fn test_optional_fields_have_explicit_presence() {
+        use crate::parse::Tokenizer;
+        use crate::protobuf::ToProtobufModel;
+
+        let model = Model::try_from(Tokenizer::default().parse(
+            r"Presence DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            Reading ::= SEQUENCE {
+                value    INTEGER (0..255),
+                offset   INTEGER (-10..10) OPTIONAL,
+                mode     ENUMERATED { fast, slow } OPTIONAL,
+                history  SEQUENCE OF INTEGER (0..255) OPTIONAL
+            }
+
+            END",
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust()
+        .to_protobuf();
+
+        let (_file, content) = ProtobufDefGenerator::generate_file(&model).unwrap();
+        assert!(content.starts_with("syntax = 'proto3';"), "{}", content);
+        assert!(content.contains("    uint32 value = 1;"), "{}", content);
+        assert!(
+            content.contains("    optional sint32 offset = 2;"),
+            "{}",
+            content
+        );
+        assert!(
+            content.contains("    optional ReadingMode mode = 3;"),
+            "{}",
+            content
+        );
+        assert!(
+            content.contains("    repeated uint32 history = 4;"),
+            "{}",
+            content
+        );
+    }
+
+    #[test]
+    fn test_choice_is_a_valid_oneof() {
+        use crate::parse::Tokenizer;
+        use crate::protobuf::ToProtobufModel;
+
+        let model = Model::try_from(Tokenizer::default().parse(
+            r"OneOf DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            Body ::= CHOICE {
+                empty       NULL,
+                flag-value  BOOLEAN,
+                items       SEQUENCE OF INTEGER (0..255)
+            }
+
+            END",
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust()
+        .to_protobuf();
+
+        let (_file, content) = ProtobufDefGenerator::generate_file(&model).unwrap();
+        assert!(
+            content.contains(concat!(
+                "message Body {\n",
+                "    oneof value {\n",
+                "      bytes empty = 1;\n",
+                "      bool flag_value = 2;\n",
+                "      ItemsList items = 3;\n",
+                "    }\n",
+                "    message ItemsList {\n",
+                "        repeated uint32 value = 1;\n",
+                "    }\n",
+                "}\n",
+            )),
+            "{}",
+            content
+        );
+    }
 }
@@ -1,6 +1,10 @@
-use crate::asn::{Tag, TagProperty, Type as AsnType, Type};
-use crate::generate::Generator;
-use crate::model::{Definition, Model};
+use crate::asn::{split_unit, Charset, RealPrecision, Size, Tag, TagProperty};
+use crate::asn::{Type as AsnType, Type};
+use crate::footprint;
+use crate::generate::walker::AsnDefWriter;
+use crate::generate::{Generator, NamingStrategy, TargetProfile};
+use crate::layout;
+use crate::model::{Definition, LiteralValue, Model, ValueReference};
 use crate::rust::{DataEnum, Field, Rust, RustType};
 use crate::rust::{EncodingOrdering, PlainEnum};
 use codegen::Block;
@@ -9,11 +13,18 @@ use codegen::Impl;
 use codegen::Scope;
 use codegen::Struct;
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::convert::Infallible;
 use std::fmt::Display;
 
-const KEYWORDS: [&str; 9] = [
-    "use", "mod", "const", "type", "pub", "enum", "struct", "impl", "trait",
+const SELF_TEST_PREFIX: &str = "::asn1rs::descriptor::selftest::";
+
+/// How many nested definitions the doc examples construct at most, the deeper ones are assumed
+/// to have a valid default
+const EXAMPLE_DEPTH: usize = 8;
+
+const KEYWORDS: [&str; 10] = [
+    "use", "mod", "const", "type", "pub", "enum", "struct", "impl", "trait", "abstract",
 ];
 
 pub trait GeneratorSupplement<T> {
@@ -38,6 +49,34 @@ pub struct RustCodeGenerator {
     global_derives: Vec<String>,
     direct_field_access: bool,
     getter_and_setter: bool,
+    keep_unused_imports: bool,
+    derive_default: bool,
+    derive_serde: bool,
+    scaled_value_helpers: bool,
+    choice_trait_objects: bool,
+    self_test: bool,
+    self_test_name: String,
+    registry: bool,
+    footprint: bool,
+    encoding_docs: bool,
+    doc_examples: Option<String>,
+    collection_merge: CollectionMerge,
+    target_profile: TargetProfile,
+    deduplicate_inline_types: bool,
+    heapless_types: bool,
+    borrowed_views: bool,
+    naming_strategy: Option<Box<dyn NamingStrategy>>,
+}
+
+/// How the generated `merge_from` fns of the structs treat `SEQUENCE OF` and `SET OF` fields
+#[derive(Debug, Default, Copy, Clone, PartialOrd, PartialEq, Eq, Hash)]
+pub enum CollectionMerge {
+    /// The elements of the other value replace the elements of the merged value
+    #[default]
+    Replace,
+    /// The elements of the other value are appended to the elements of the merged value, which
+    /// might exceed a `SIZE` constraint of the field
+    Append,
 }
 
 impl From<Model<Rust>> for RustCodeGenerator {
@@ -55,6 +94,23 @@ impl Default for RustCodeGenerator {
             global_derives: Vec::default(),
             direct_field_access: true,
             getter_and_setter: false,
+            keep_unused_imports: false,
+            derive_default: true,
+            derive_serde: false,
+            scaled_value_helpers: true,
+            choice_trait_objects: false,
+            self_test: true,
+            self_test_name: "self_test".to_string(),
+            registry: true,
+            footprint: false,
+            encoding_docs: false,
+            doc_examples: None,
+            collection_merge: CollectionMerge::default(),
+            target_profile: TargetProfile::default(),
+            deduplicate_inline_types: true,
+            heapless_types: false,
+            borrowed_views: false,
+            naming_strategy: None,
         }
     }
 }
@@ -106,6 +162,212 @@ impl RustCodeGenerator {
         self.getter_and_setter = allow;
     }
 
+    pub const fn keeps_unused_imports(&self) -> bool {
+        self.keep_unused_imports
+    }
+
+    /// By default, only the imported items that are referenced by the generated definitions are
+    /// emitted. If set, all imported items are emitted with `#[allow(unused_imports)]` instead.
+    pub fn set_keep_unused_imports(&mut self, keep: bool) {
+        self.keep_unused_imports = keep;
+    }
+
+    pub const fn derives_default(&self) -> bool {
+        self.derive_default
+    }
+
+    /// If unset, `Default` is neither derived nor implemented for the generated types, because
+    /// the defaults of mandatory fields and of the first variant of a `CHOICE` are seldom
+    /// meaningful. The `variants()` fn of `CHOICE`s requires `Default` and is omitted too.
+    pub fn set_derive_default(&mut self, derive: bool) {
+        self.derive_default = derive;
+    }
+
+    pub const fn derives_serde(&self) -> bool {
+        self.derive_serde
+    }
+
+    /// If set, `serde::Serialize` and `serde::Deserialize` are derived for the generated types,
+    /// which requires the `serde` feature of `asn1rs` and a dependency on `serde` with its
+    /// `derive` feature. Fields with an ASN.1 `DEFAULT` value - like the extension additions of
+    /// a `SEQUENCE` - take that value if they are missing in the deserialized input.
+    pub fn set_derive_serde(&mut self, derive: bool) {
+        self.derive_serde = derive;
+    }
+
+    pub const fn has_scaled_value_helpers(&self) -> bool {
+        self.scaled_value_helpers
+    }
+
+    /// If set (the default), integer fields with a unit get a fn returning their physical value,
+    /// such as `fn speed_mps(&self) -> f64` for a unit of `0.01 m/s`
+    pub fn set_scaled_value_helpers(&mut self, scaled_value_helpers: bool) {
+        self.scaled_value_helpers = scaled_value_helpers;
+    }
+
+    pub const fn has_choice_trait_objects(&self) -> bool {
+        self.choice_trait_objects
+    }
+
+    /// If set, a trait like `WoahDecisionLike` is generated for each `CHOICE` and implemented by
+    /// the inner type of each of its variants, together with fns converting the `CHOICE` from
+    /// and into a `Box<dyn WoahDecisionLike>`. A `CHOICE` with several variants of the same
+    /// inner type gets no trait, because its implementations would conflict.
+    pub fn set_choice_trait_objects(&mut self, choice_trait_objects: bool) {
+        self.choice_trait_objects = choice_trait_objects;
+    }
+
+    pub const fn has_self_test(&self) -> bool {
+        self.self_test
+    }
+
+    /// If set (the default), each generated type implements
+    /// `asn1rs::descriptor::selftest::Representative` and each module gets a
+    /// `pub fn self_test() -> Result<(), SelfTestError>`, which round-trips the representative
+    /// values of all its types through the enabled encoding rules
+    pub fn set_self_test(&mut self, self_test: bool) {
+        self.self_test = self_test;
+    }
+
+    pub fn self_test_name(&self) -> &str {
+        &self.self_test_name
+    }
+
+    /// The name of the self test fn (default: `self_test`), so that the fns of modules that are
+    /// generated into the same scope do not collide
+    pub fn set_self_test_name(&mut self, name: impl Into<String>) {
+        self.self_test_name = name.into();
+    }
+
+    pub const fn has_registry(&self) -> bool {
+        self.registry
+    }
+
+    /// If set (the default), each module gets a
+    /// `pub fn definitions() -> &'static [asn1rs::registry::DefinitionInfo]`, which decodes the
+    /// values of its definitions by their ASN.1 names
+    pub fn set_registry(&mut self, registry: bool) {
+        self.registry = registry;
+    }
+
+    pub const fn has_footprint(&self) -> bool {
+        self.footprint
+    }
+
+    /// If set, each module gets a `pub fn footprint() -> &'static [asn1rs::footprint::Footprint]`,
+    /// which lists the `size_of` of the types of its definitions, the most bytes their values
+    /// own on the heap and the bounds of the bits of their UPER encodings
+    pub fn set_footprint(&mut self, footprint: bool) {
+        self.footprint = footprint;
+    }
+
+    pub const fn has_borrowed_views(&self) -> bool {
+        self.borrowed_views
+    }
+
+    /// If set, each `SEQUENCE` with `OCTET STRING` or `UTF8String` fields - and each type that
+    /// wraps one - gets a view `FooRef<'a>`, whose `read_uper` fn borrows the octets and
+    /// characters of these fields from the decoded slice instead of copying them, see
+    /// `UperReader::read_octet_string_borrowed`. The views compare equal to their owned types.
+    pub fn set_borrowed_views(&mut self, borrowed_views: bool) {
+        self.borrowed_views = borrowed_views;
+    }
+
+    pub const fn has_encoding_docs(&self) -> bool {
+        self.encoding_docs
+    }
+
+    /// If set, the doc comment of each field and `CHOICE` variant states the layout of its UPER
+    /// encoding, such as `7-bit constrained integer, offset 0..=100`, see
+    /// [`layout`]
+    pub fn set_encoding_docs(&mut self, encoding_docs: bool) {
+        self.encoding_docs = encoding_docs;
+    }
+
+    pub fn doc_examples(&self) -> Option<&str> {
+        self.doc_examples.as_deref()
+    }
+
+    /// If set to the path of the module the generated modules are declared in - like
+    /// `my_crate::generated` for `my_crate::generated::my_module` - each generated type gets a
+    /// doc example, which constructs a value with plausible values of its fields and round-trips
+    /// it through UPER. The examples are compiled and run as doctests of that crate. Without
+    /// public fields, the values of structs are constructed by `Default::default()` instead.
+    pub fn set_doc_examples(&mut self, module_path: Option<String>) {
+        self.doc_examples = module_path;
+    }
+
+    pub const fn collection_merge(&self) -> CollectionMerge {
+        self.collection_merge
+    }
+
+    /// Whether the `merge_from` fn of a struct replaces or appends to its collections, see
+    /// [`CollectionMerge`]
+    pub fn set_collection_merge(&mut self, collection_merge: CollectionMerge) {
+        self.collection_merge = collection_merge;
+    }
+
+    pub const fn target_profile(&self) -> TargetProfile {
+        self.target_profile
+    }
+
+    /// The environment the generated code is compiled for. With [`TargetProfile::Alloc`], the
+    /// heap allocated types are imported from the `alloc` crate. See
+    /// [`RustCodeGenerator::target_profile_violations`] to check the generated code.
+    pub fn set_target_profile(&mut self, target_profile: TargetProfile) {
+        self.target_profile = target_profile;
+    }
+
+    pub const fn deduplicates_inline_types(&self) -> bool {
+        self.deduplicate_inline_types
+    }
+
+    /// If set (the default), identical types that are declared inline - such as the same
+    /// `ENUMERATED` declared for the components of several `SEQUENCE`s - are generated once,
+    /// named like the first of them, see [`Model::deduplicate_inline_definitions`]
+    pub fn set_deduplicate_inline_types(&mut self, deduplicate_inline_types: bool) {
+        self.deduplicate_inline_types = deduplicate_inline_types;
+    }
+
+    pub const fn uses_heapless_types(&self) -> bool {
+        self.heapless_types
+    }
+
+    /// If set, strings, `OCTET STRING`s, `SEQUENCE OF`s and `SET OF`s with an upper `SIZE` bound
+    /// that is not extensible are generated as `heapless::String`s and `heapless::Vec`s of that
+    /// capacity, see [`Model::use_heapless_types`]. The generated types then hold these values
+    /// without heap allocations, which requires the `heapless` feature of `asn1rs` and a
+    /// dependency on `heapless`. Encoding and decoding still use temporary buffers.
+    pub fn set_heapless_types(&mut self, heapless_types: bool) {
+        self.heapless_types = heapless_types;
+    }
+
+    /// Names the types that are declared inline, such as [`HashSuffixed`] to keep the names of
+    /// deeply nested types short. Without a strategy, these are named like [`ParentPrefixed`]
+    /// does. The names are given before identical types are deduplicated.
+    ///
+    /// [`HashSuffixed`]: crate::generate::naming::HashSuffixed
+    /// [`ParentPrefixed`]: crate::generate::naming::ParentPrefixed
+    pub fn set_naming_strategy(&mut self, strategy: impl NamingStrategy + 'static) {
+        self.naming_strategy = Some(Box::new(strategy));
+    }
+
+    /// The names of the generated files that refer to items which are not available with the
+    /// [`TargetProfile`], together with these items. Meant to be asserted empty in a test of the
+    /// crate that uses the generated code.
+    pub fn target_profile_violations(&self) -> Vec<(String, Vec<String>)> {
+        self.to_string_without_generators()
+            .into_iter()
+            .filter_map(|(file, code)| {
+                let items = self
+                    .target_profile
+                    .unavailable_items(&code)
+                    .unwrap_or_else(|e| vec![format!("{:?}", e)]);
+                Some((file, items)).filter(|(_, items)| !items.is_empty())
+            })
+            .collect()
+    }
+
     pub fn to_string_without_generators(&self) -> Vec<(String, String)> {
         self.to_string_with_generators(&[])
     }
@@ -116,10 +378,30 @@ impl RustCodeGenerator {
     ) -> Vec<(String, String)> {
         self.models
             .iter()
-            .map(|model| self.model_to_file(model, generators))
+            .map(|model| self.model_to_file(&self.prepare_model(model), generators))
             .collect()
     }
 
+    /// The model with its inline definitions renamed and deduplicated and with the `heapless`
+    /// types as configured
+    fn prepare_model<'a>(&self, model: &'a Model<Rust>) -> Cow<'a, Model<Rust>> {
+        if self.naming_strategy.is_none() && !self.deduplicate_inline_types && !self.heapless_types
+        {
+            return Cow::Borrowed(model);
+        }
+        let mut model = model.clone();
+        if let Some(strategy) = &self.naming_strategy {
+            model.rename_inline_definitions(strategy.as_ref());
+        }
+        if self.deduplicate_inline_types {
+            model.deduplicate_inline_definitions();
+        }
+        if self.heapless_types {
+            model.use_heapless_types();
+        }
+        Cow::Owned(model)
+    }
+
     pub fn model_to_file(
         &self,
         model: &Model<Rust>,
@@ -135,34 +417,500 @@ impl RustCodeGenerator {
         generators.iter().for_each(|g| g.add_imports(&mut scope));
 
         scope.import("asn1rs::prelude", "*");
+        if self.target_profile == TargetProfile::Alloc {
+            scope.raw("#[allow(unused_imports)]\nuse alloc::{boxed::Box, string::{String, ToString}, vec::Vec};");
+        }
+        let referenced = Self::referenced_type_names(model);
         for import in &model.imports {
             let from = format!("super::{}", &Self::rust_module_name(&import.from));
-            for what in &import.what {
-                scope.import(&from, what);
+            if self.keep_unused_imports {
+                scope.raw(format!(
+                    "#[allow(unused_imports)]\nuse {}::{{{}}};",
+                    from,
+                    import.what.join(", ")
+                ));
+            } else {
+                for what in &import.what {
+                    if referenced.contains(what.as_str()) {
+                        scope.import(&from, what);
+                    }
+                }
             }
         }
 
         for vref in &model.value_references {
-            scope.raw(&Self::fmt_const(
-                &vref.name,
-                &vref.role,
-                &vref.value.as_rust_const_literal(true),
-                0,
-            ));
+            scope.raw(self.fmt_value_reference(vref));
         }
 
         for definition in &model.definitions {
+            let definition = &self.add_default_values(&mut scope, definition);
+            if let Some(path) = &self.doc_examples {
+                scope.raw(self.doc_example(path, model, definition.name()));
+            }
             self.add_definition(&mut scope, definition);
-            Self::impl_definition(&mut scope, definition, generators, self.getter_and_setter);
+            self.impl_definition(&mut scope, definition, generators);
+            if self.borrowed_views {
+                self.add_borrowed_view(&mut scope, definition);
+            }
 
             generators
                 .iter()
                 .for_each(|g| g.impl_supplement(&mut scope, definition));
         }
 
+        self.impl_prototypes(&mut scope, model);
+
+        if self.self_test && !model.definitions.is_empty() {
+            self.add_self_test_fn(&mut scope, model);
+        }
+
+        if self.registry {
+            Self::add_definitions_fn(&mut scope, model);
+        }
+
+        if self.footprint {
+            Self::add_footprint_fn(&mut scope, model);
+        }
+
         (file, scope.to_string())
     }
 
+    /// Generates the code of a single definition like [`RustCodeGenerator::model_to_file`] does,
+    /// but without the imports and other definitions of its module
+    pub fn definition_to_string(&self, definition: &Definition<Rust>) -> String {
+        let mut scope = Scope::new();
+        let definition = &self.add_default_values(&mut scope, definition);
+        self.add_definition(&mut scope, definition);
+        self.impl_definition(&mut scope, definition, &[]);
+        scope.to_string()
+    }
+
+    /// Adds the structured `DEFAULT` values of the fields of a struct - which have no literal -
+    /// as value references named like `VEHICLE_ENGINE_DEFAULT` and returns the definition with
+    /// its fields referring to these instead
+    fn add_default_values<'a>(
+        &self,
+        scope: &mut Scope,
+        definition: &'a Definition<Rust>,
+    ) -> Cow<'a, Definition<Rust>> {
+        let Definition(name, Rust::Struct { fields, .. }) = definition else {
+            return Cow::Borrowed(definition);
+        };
+        if !fields.iter().any(|field| {
+            matches!(
+                field.r#type(),
+                RustType::Default(_, default) if default.is_structured()
+            )
+        }) {
+            return Cow::Borrowed(definition);
+        }
+
+        let mut definition = definition.clone();
+        if let Definition(_, Rust::Struct { fields, .. }) = &mut definition {
+            for field in fields {
+                if let RustType::Default(inner, default) = &mut field.name_type.1 {
+                    if default.is_structured() {
+                        let vref = ValueReference {
+                            name: format!(
+                                "{}_{}_DEFAULT",
+                                Self::rust_module_name(name).to_uppercase(),
+                                field.name_type.0.to_uppercase()
+                            ),
+                            role: *inner.clone(),
+                            value: default.clone(),
+                        };
+                        scope.raw(self.fmt_value_reference(&vref));
+                        *default = LiteralValue::Reference(vref.name);
+                    }
+                }
+            }
+        }
+        Cow::Owned(definition)
+    }
+
+    /// The names of all types that the definitions and value references of the model refer to
+    fn referenced_type_names(model: &Model<Rust>) -> HashSet<&str> {
+        fn collect<'a>(r#type: &'a RustType, names: &mut HashSet<&'a str>) {
+            match r#type {
+                RustType::Complex(name, _) => {
+                    names.insert(name.as_str());
+                }
+                RustType::Containing(inner, _)
+                | RustType::Vec(inner, ..)
+                | RustType::Option(inner)
+                | RustType::Default(inner, _)
+                | RustType::Substituted(inner, _) => collect(inner, names),
+                _ => {}
+            }
+        }
+
+        let mut names = HashSet::default();
+        for Definition(_name, rust) in &model.definitions {
+            match rust {
+                Rust::Struct { fields, .. } => fields
+                    .iter()
+                    .for_each(|field| collect(field.r#type(), &mut names)),
+                Rust::DataEnum(enumeration) => enumeration
+                    .variants()
+                    .for_each(|variant| collect(variant.r#type(), &mut names)),
+                Rust::TupleStruct { r#type, .. } => collect(r#type, &mut names),
+                Rust::Enum(_) => {}
+            }
+        }
+        for vref in &model.value_references {
+            collect(&vref.role, &mut names);
+        }
+        names
+    }
+
+    /// `f32` and `f64` do not implement `Hash`, so neither can a definition that contains a `REAL`,
+    /// directly or through the definitions it refers to
+    fn is_hashable(&self, name: &str) -> bool {
+        fn contains_real<'a>(
+            models: &'a [Model<Rust>],
+            r#type: &'a RustType,
+            visited: &mut HashSet<&'a str>,
+        ) -> bool {
+            match r#type {
+                RustType::F32 | RustType::F64 => true,
+                RustType::Complex(name, _) => definition_contains_real(models, name, visited),
+                RustType::Containing(inner, _)
+                | RustType::Vec(inner, ..)
+                | RustType::Option(inner)
+                | RustType::Default(inner, _)
+                | RustType::Substituted(inner, _) => contains_real(models, inner, visited),
+                _ => false,
+            }
+        }
+
+        fn definition_contains_real<'a>(
+            models: &'a [Model<Rust>],
+            name: &'a str,
+            visited: &mut HashSet<&'a str>,
+        ) -> bool {
+            if !visited.insert(name) {
+                return false;
+            }
+            let definition = models
+                .iter()
+                .flat_map(|model| &model.definitions)
+                .find(|Definition(n, _)| n == name);
+            match definition.map(|Definition(_, rust)| rust) {
+                Some(Rust::Struct { fields, .. }) => fields
+                    .iter()
+                    .any(|field| contains_real(models, field.r#type(), visited)),
+                Some(Rust::DataEnum(enumeration)) => enumeration
+                    .variants()
+                    .any(|variant| contains_real(models, variant.r#type(), visited)),
+                Some(Rust::TupleStruct { r#type, .. }) => contains_real(models, r#type, visited),
+                Some(Rust::Enum(_)) | None => false,
+            }
+        }
+
+        !definition_contains_real(&self.models, name, &mut HashSet::default())
+    }
+
+    /// Byte and bit strings are declared with types that can be initialized in a `const` context,
+    /// structured values are declared as lazily initialized `static` if their construction
+    /// allocates
+    fn fmt_value_reference(&self, vref: &ValueReference<RustType>) -> String {
+        match (&vref.role, &vref.value) {
+            (_, value) if value.is_structured() => {
+                let (value, allocates) = self.fmt_value(&vref.role, &vref.value);
+                if allocates {
+                    format!(
+                        "pub static {}: std::sync::LazyLock<{}> = std::sync::LazyLock::new(|| {});",
+                        vref.name,
+                        vref.role.to_string(),
+                        value
+                    )
+                } else {
+                    format!(
+                        "pub const {}: {} = {};",
+                        vref.name,
+                        vref.role.to_string(),
+                        value
+                    )
+                }
+            }
+            (RustType::Complex(..), LiteralValue::EnumeratedVariant(..)) => format!(
+                "pub const {}: {} = {};",
+                vref.name,
+                vref.role.to_string(),
+                vref.value.as_rust_const_literal(true),
+            ),
+            (
+                RustType::VecU8(_),
+                LiteralValue::OctetString(bytes) | LiteralValue::BitString(bytes, _),
+            ) => format!(
+                "pub const {}: [u8; {}] = {};",
+                vref.name,
+                bytes.len(),
+                vref.value.as_rust_const_literal(true),
+            ),
+            (RustType::BitVec(_), value) if value.as_bits().is_some() => {
+                let (_bytes, bit_len) = value.as_bits().unwrap_or_default();
+                format!(
+                    "pub const {}: BitVecConst = BitVecConst::new(&{}, {});",
+                    vref.name,
+                    value.as_rust_const_literal(true),
+                    bit_len,
+                )
+            }
+            (role, value) => {
+                Self::fmt_const(&vref.name, role, &value.as_rust_const_literal(true), 0)
+            }
+        }
+    }
+
+    /// The expression constructing the given value of the given type and whether it allocates,
+    /// which prevents it from being evaluated in a `const` context
+    fn fmt_value(&self, r#type: &RustType, value: &LiteralValue) -> (String, bool) {
+        match (r#type, value) {
+            (RustType::Option(inner), value) => {
+                let (value, allocates) = self.fmt_value(inner, value);
+                (format!("Some({})", value), allocates)
+            }
+            (RustType::Default(inner, _), value) => self.fmt_value(inner, value),
+            (RustType::Vec(..), LiteralValue::SequenceOf(values)) if values.is_empty() => {
+                ("Vec::new()".to_string(), false)
+            }
+            (RustType::Vec(inner, ..), LiteralValue::SequenceOf(values)) => {
+                let values = values
+                    .iter()
+                    .map(|value| self.fmt_value(inner, value).0)
+                    .collect::<Vec<_>>();
+                (format!("vec![{}]", values.join(", ")), true)
+            }
+            (RustType::String(..), LiteralValue::String(string)) if string.is_empty() => {
+                ("String::new()".to_string(), false)
+            }
+            (RustType::VecU8(_), LiteralValue::OctetString(bytes)) if bytes.is_empty() => {
+                ("Vec::new()".to_string(), false)
+            }
+            (RustType::String(..) | RustType::VecU8(_) | RustType::BitVec(_), value) => {
+                (Self::fmt_default_value(r#type, value), true)
+            }
+            (RustType::Complex(name, _), value) => self.fmt_complex_value(name, value),
+            (_, value) => (value.as_rust_const_literal(true).to_string(), false),
+        }
+    }
+
+    fn fmt_complex_value(&self, name: &str, value: &LiteralValue) -> (String, bool) {
+        let definition = self
+            .models
+            .iter()
+            .flat_map(|model| &model.definitions)
+            .find(|Definition(n, _)| n == name)
+            .map(|Definition(_, rust)| rust);
+
+        match (definition, value) {
+            (Some(Rust::Struct { fields, .. }), LiteralValue::Sequence(components)) => {
+                let mut allocates = false;
+                let fields = fields
+                    .iter()
+                    .map(|field| {
+                        let component = components
+                            .iter()
+                            .find(|(name, _)| name == field.name())
+                            .map(|(_, value)| value);
+                        let value = match (component, field.r#type()) {
+                            (Some(value), r#type) => self.fmt_value(r#type, value),
+                            (None, RustType::Option(_)) => ("None".to_string(), false),
+                            (None, RustType::Default(inner, default)) => {
+                                self.fmt_value(inner, default)
+                            }
+                            (None, _) => panic!("The value of {} has no {}", name, field.name()),
+                        };
+                        allocates |= value.1;
+                        format!("{}: {}", Self::rust_field_name(field.name(), true), value.0)
+                    })
+                    .collect::<Vec<_>>();
+                (format!("{} {{ {} }}", name, fields.join(", ")), allocates)
+            }
+            (Some(Rust::TupleStruct { r#type, .. }), value) => {
+                let (value, allocates) = self.fmt_value(r#type, value);
+                (format!("{}({})", name, value), allocates)
+            }
+            (Some(Rust::DataEnum(enumeration)), LiteralValue::Choice(variant, value)) => {
+                let r#type = enumeration
+                    .variants()
+                    .find(|v| v.name() == variant)
+                    .map(|v| v.r#type())
+                    .unwrap_or_else(|| panic!("{} has no alternative {}", name, variant));
+                let (value, allocates) = self.fmt_value(r#type, value);
+                (
+                    format!("{}::{}({})", name, Self::rust_variant_name(variant), value),
+                    allocates,
+                )
+            }
+            (_, value) => (value.as_rust_const_literal(true).to_string(), false),
+        }
+    }
+
+    /// The doc comment with an example that constructs a value of the definition and round-trips
+    /// it through UPER, see [`RustCodeGenerator::set_doc_examples`]
+    fn doc_example(&self, path: &str, model: &Model<Rust>, name: &str) -> String {
+        let code = format!(
+            r#"```rust
+use asn1rs::prelude::*;
+use {path}::{module}::*;
+
+let value = {value};
+
+let mut writer = UperWriter::default();
+writer.write(&value).unwrap();
+
+let mut reader = writer.as_reader();
+assert_eq!(value, reader.read::<{name}>().unwrap());
+```"#,
+            path = path,
+            module = Self::rust_module_name(&model.name),
+            value = self.fmt_example_complex(model, name, &[], true),
+            name = name,
+        );
+        code.lines()
+            .map(|line| format!("/// {}", line).trim_end().to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// A plausible value of the type as Rust expression: `1` or the bound closest to it for
+    /// integers, the shortest non-empty strings and lists the `SIZE` allows and the first
+    /// variants of enums. Optional values and lists that may be empty are omitted if they would
+    /// contain a definition that is being constructed already - given by the `stack`.
+    fn fmt_example_value(&self, model: &Model<Rust>, r#type: &RustType, stack: &[&str]) -> String {
+        match r#type {
+            RustType::Bool => "true".to_string(),
+            RustType::I8(_)
+            | RustType::U8(_)
+            | RustType::I16(_)
+            | RustType::U16(_)
+            | RustType::I32(_)
+            | RustType::U32(_)
+            | RustType::I64(_)
+            | RustType::U64(_) => r#type
+                .integer_range_str()
+                .and_then(|range| Some((range.min().parse().ok()?, range.max().parse().ok()?)))
+                .map(|(min, max): (i128, i128)| 1.clamp(min, max).to_string())
+                .unwrap_or_else(|| "1".to_string()),
+            RustType::F32 | RustType::F64 => "0.5".to_string(),
+            RustType::ObjectIdentifier => "Oid::new(vec![1, 3, 6, 1])".to_string(),
+            RustType::RelativeOid => "Oid::new(vec![42])".to_string(),
+            RustType::OidIri => "\"/ISO/Registration_Authority\".to_string()".to_string(),
+            RustType::String(size, charset) => {
+                let char = if *charset == Charset::Numeric { '1' } else { 'A' };
+                let string = char.to_string().repeat(Self::example_len(size));
+                self.fmt_value(r#type, &LiteralValue::String(string)).0
+            }
+            RustType::VecU8(size) => {
+                let bytes = vec![0x2A; Self::example_len(size)];
+                self.fmt_value(r#type, &LiteralValue::OctetString(bytes)).0
+            }
+            RustType::BitVec(size) => {
+                let bits = Self::example_len(size);
+                format!("BitVec::from_bytes(vec![0xA5; {}], {})", bits.div_ceil(8), bits)
+            }
+            RustType::Vec(inner, size, _)
+                if size.min().copied().unwrap_or(0) == 0 && Self::is_recursive(inner, stack) =>
+            {
+                "Vec::new()".to_string()
+            }
+            RustType::Vec(inner, size, _) => format!(
+                "vec![{}; {}]",
+                self.fmt_example_value(model, inner, stack),
+                Self::example_len(size)
+            ),
+            RustType::Null => "Null".to_string(),
+            RustType::Option(inner) if Self::is_recursive(inner, stack) => "None".to_string(),
+            RustType::Option(inner) => {
+                format!("Some({})", self.fmt_example_value(model, inner, stack))
+            }
+            RustType::Default(inner, _) | RustType::Containing(inner, _) => {
+                self.fmt_example_value(model, inner, stack)
+            }
+            RustType::Complex(name, _) => self.fmt_example_complex(model, name, stack, false),
+            RustType::Substituted(inner, substitution) => format!(
+                "<{} as ::asn1rs::descriptor::substituted::Substitute<{}, _>>::from_asn({}).unwrap()",
+                substitution.converter(),
+                substitution.rust_type,
+                self.fmt_example_value(model, inner, stack)
+            ),
+        }
+    }
+
+    /// The example value of a definition of the model, with the fields of a struct on lines of
+    /// their own if `pretty`. Definitions of other models are assumed to have a valid default.
+    fn fmt_example_complex(
+        &self,
+        model: &Model<Rust>,
+        name: &str,
+        stack: &[&str],
+        pretty: bool,
+    ) -> String {
+        let definition = model
+            .definition(name)
+            .map(|Definition(_, rust)| rust)
+            .filter(|_| stack.len() < EXAMPLE_DEPTH);
+        let stack = &[stack, &[name]].concat();
+
+        match definition {
+            Some(Rust::Struct { fields, .. }) if self.direct_field_access => {
+                let fields = fields
+                    .iter()
+                    .map(|field| {
+                        format!(
+                            "{}: {}",
+                            Self::rust_field_name(field.name(), true),
+                            self.fmt_example_value(model, field.r#type(), stack)
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                if pretty {
+                    format!("{} {{\n    {},\n}}", name, fields.join(",\n    "))
+                } else {
+                    format!("{} {{ {} }}", name, fields.join(", "))
+                }
+            }
+            Some(Rust::TupleStruct { r#type, .. }) => {
+                format!("{}({})", name, self.fmt_example_value(model, r#type, stack))
+            }
+            Some(Rust::Enum(enumeration)) => match enumeration.variants().next() {
+                Some(variant) => format!("{}::{}", name, Self::rust_variant_name(variant)),
+                None => "Default::default()".to_string(),
+            },
+            Some(Rust::DataEnum(enumeration)) => match enumeration.variants().next() {
+                Some(variant) => format!(
+                    "{}::{}({})",
+                    name,
+                    Self::rust_variant_name(variant.name()),
+                    self.fmt_example_value(model, variant.r#type(), stack)
+                ),
+                None => "Default::default()".to_string(),
+            },
+            Some(Rust::Struct { .. }) | None => "Default::default()".to_string(),
+        }
+    }
+
+    /// Whether the type refers to one of the given definitions
+    fn is_recursive(r#type: &RustType, stack: &[&str]) -> bool {
+        match r#type {
+            RustType::Complex(name, _) => stack.contains(&name.as_str()),
+            RustType::Vec(inner, ..)
+            | RustType::Option(inner)
+            | RustType::Default(inner, _)
+            | RustType::Containing(inner, _)
+            | RustType::Substituted(inner, _) => Self::is_recursive(inner, stack),
+            _ => false,
+        }
+    }
+
+    /// The shortest non-empty length within the `SIZE` constraint
+    fn example_len(size: &Size) -> usize {
+        let max = size.max().copied().unwrap_or(usize::MAX);
+        size.min().copied().unwrap_or(1).max(1).min(max)
+    }
+
     fn fmt_const(name: &str, r#type: &RustType, value: &impl Display, indent: usize) -> String {
         format!(
             "{}pub const {}: {} = {};",
@@ -193,12 +941,20 @@ impl RustCodeGenerator {
                     *tag,
                     extension_after.map(|index| fields[index].name().to_string()),
                     &[],
+                    false,
+                    None,
                 ));
                 Self::add_struct(
-                    self.new_struct(scope, name),
+                    self.new_struct(
+                        scope,
+                        name,
+                        self.derive_default && !Self::has_default_values(fields),
+                    ),
                     name,
                     fields,
                     self.direct_field_access,
+                    self.derive_serde,
+                    self.encoding_docs.then_some(*extension_after),
                 )
             }
             Rust::Enum(plain) => {
@@ -207,12 +963,14 @@ impl RustCodeGenerator {
                     plain.tag(),
                     plain.extension_after_variant().cloned(),
                     &[],
+                    false,
+                    None,
                 ));
-                Self::add_enum(
-                    self.new_enum(scope, name, true).derive("Default"),
-                    name,
-                    plain,
-                )
+                let en_m = self.new_enum(scope, name, true);
+                if self.derive_default {
+                    en_m.derive("Default");
+                }
+                Self::add_enum(en_m, name, plain, self.derive_default)
             }
             Rust::DataEnum(data) => {
                 scope.raw(&Self::asn_attribute(
@@ -220,17 +978,31 @@ impl RustCodeGenerator {
                     data.tag(),
                     data.extension_after_variant().map(|v| v.name().to_string()),
                     &[],
+                    false,
+                    None,
                 ));
-                Self::add_data_enum(self.new_enum(scope, name, false), name, data)
+                Self::add_data_enum(
+                    self.new_enum(scope, name, false),
+                    name,
+                    data,
+                    self.encoding_docs,
+                )
             }
             Rust::TupleStruct {
                 r#type,
                 tag,
                 constants,
             } => {
-                scope.raw(&Self::asn_attribute("transparent", *tag, None, &[]));
+                scope.raw(Self::asn_attribute(
+                    "transparent",
+                    *tag,
+                    None,
+                    &[],
+                    false,
+                    None,
+                ));
                 Self::add_tuple_struct(
-                    self.new_struct(scope, name),
+                    self.new_struct(scope, name, self.derive_default),
                     name,
                     r#type,
                     self.direct_field_access,
@@ -241,29 +1013,61 @@ impl RustCodeGenerator {
         }
     }
 
-    fn add_struct(str_ct: &mut Struct, _name: &str, fields: &[Field], pub_access: bool) {
-        for field in fields {
-            str_ct.field(
-                &format!(
-                    "{} {}{}",
+    fn add_struct(
+        str_ct: &mut Struct,
+        name: &str,
+        fields: &[Field],
+        pub_access: bool,
+        derive_serde: bool,
+        encoding_docs: Option<Option<usize>>,
+    ) {
+        for (position, field) in fields.iter().enumerate() {
+            let serde_default = match field.r#type() {
+                RustType::Default(..) if derive_serde => format!(
+                    "#[serde(default = \"{}::{}\")] ",
+                    name,
+                    Self::serde_default_fn_name(field.name())
+                ),
+                _ => String::new(),
+            };
+            let rust_field = str_ct.new_field(
+                format!(
+                    "{}{} {}{}",
+                    serde_default,
                     Self::asn_attribute(
                         Self::asn_attribute_type(&field.r#type().clone().into_asn()),
                         field.tag(),
                         None,
                         field.constants(),
+                        field.sensitive(),
+                        field.unit(),
                     ),
                     if pub_access { "pub " } else { "" },
                     Self::rust_field_name(field.name(), true),
                 ),
                 field.r#type().to_string(),
             );
+            let docs = field
+                .unit()
+                .map(|unit| format!("Unit: {}", unit))
+                .into_iter()
+                .chain(encoding_docs.map(|extension_after| {
+                    format!(
+                        "UPER: {}",
+                        layout::uper_field_layout(field, position, extension_after)
+                    )
+                }))
+                .collect::<Vec<_>>();
+            if !docs.is_empty() {
+                rust_field.doc(docs.join("\n\n"));
+            }
         }
     }
 
-    fn add_enum(en_m: &mut Enum, _name: &str, rust_enum: &PlainEnum) {
+    fn add_enum(en_m: &mut Enum, _name: &str, rust_enum: &PlainEnum, default_variant: bool) {
         for (index, variant) in rust_enum.variants().enumerate() {
             let name = Self::rust_variant_name(variant);
-            let name = if index == 0 {
+            let name = if index == 0 && default_variant {
                 format!("#[default] {name}")
             } else {
                 name
@@ -272,15 +1076,25 @@ impl RustCodeGenerator {
         }
     }
 
-    fn add_data_enum(en_m: &mut Enum, _name: &str, enumeration: &DataEnum) {
-        for variant in enumeration.variants() {
+    fn add_data_enum(en_m: &mut Enum, _name: &str, enumeration: &DataEnum, encoding_docs: bool) {
+        for (position, variant) in enumeration.variants().enumerate() {
             en_m.new_variant(&format!(
-                "{} {}({})",
+                "{}{} {}({})",
+                if encoding_docs {
+                    format!(
+                        "/// UPER: {}\n",
+                        layout::uper_variant_layout(enumeration, position)
+                    )
+                } else {
+                    String::new()
+                },
                 Self::asn_attribute(
                     Self::asn_attribute_type(&variant.r#type().clone().into_asn()),
                     variant.tag(),
                     None,
                     &[],
+                    variant.sensitive(),
+                    None,
                 ),
                 Self::rust_variant_name(variant.name()),
                 variant.r#type().to_string(),
@@ -303,6 +1117,8 @@ impl RustCodeGenerator {
                 tag,
                 None,
                 constants,
+                false,
+                None,
             ),
             if pub_access { "pub " } else { "" },
             inner.to_string(),
@@ -314,6 +1130,8 @@ impl RustCodeGenerator {
         tag: Option<Tag>,
         extensible_after: Option<String>,
         constants: &[(String, String)],
+        sensitive: bool,
+        unit: Option<&str>,
     ) -> String {
         format!(
             "#[asn({})]",
@@ -332,7 +1150,9 @@ impl RustCodeGenerator {
                             .collect::<Vec<_>>()
                             .join(", ")
                     ))
-                }
+                },
+                Some("sensitive".to_string()).filter(|_| sensitive),
+                unit.map(|unit| format!("unit({:?})", unit)),
             ]
             .into_iter()
             .flatten()
@@ -381,6 +1201,27 @@ impl RustCodeGenerator {
                     .flatten()
                     .collect(),
             ),
+            Type::Containing(inner, encoded_by) => (
+                Cow::Borrowed("containing"),
+                vec![
+                    Some(Self::asn_attribute_type(inner)),
+                    encoded_by.as_ref().map(|e| format!("encoded_by({})", e)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+            ),
+            Type::Substituted(inner, substitution) => (
+                Cow::Borrowed("substituted"),
+                vec![
+                    Some(Self::asn_attribute_type(inner)),
+                    Some(substitution.rust_type.clone()),
+                    substitution.via.as_ref().map(|v| format!("via({})", v)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+            ),
             Type::BitString(bitstring) => (
                 Cow::Borrowed("bit_string"),
                 vec![vec![bitstring.size.to_constraint_string()]
@@ -389,6 +1230,13 @@ impl RustCodeGenerator {
                     .collect()],
             ),
             Type::Null => (Cow::Borrowed("null"), Vec::default()),
+            Type::Real(RealPrecision::Double) => (Cow::Borrowed("real"), Vec::default()),
+            Type::ObjectIdentifier => (Cow::Borrowed("object_identifier"), Vec::default()),
+            Type::RelativeOid => (Cow::Borrowed("relative_oid"), Vec::default()),
+            Type::OidIri => (Cow::Borrowed("oid_iri"), Vec::default()),
+            Type::Real(RealPrecision::Single) => {
+                (Cow::Borrowed("real"), vec!["single".to_string()])
+            }
             Type::Optional(inner) => (
                 Cow::Borrowed("optional"),
                 vec![Self::asn_attribute_type(inner)],
@@ -454,10 +1302,10 @@ impl RustCodeGenerator {
     }
 
     fn impl_definition(
+        &self,
         scope: &mut Scope,
         Definition(name, rust): &Definition<Rust>,
         generators: &[&dyn GeneratorSupplement<Rust>],
-        getter_and_setter: bool,
     ) {
         match rust {
             Rust::Struct {
@@ -473,10 +1321,24 @@ impl RustCodeGenerator {
                         .iter()
                         .map(|f| (f.name_type.0.as_str(), &f.name_type.1, &f.constants[..])),
                 );
-                let implementation = Self::impl_struct(scope, name, fields, getter_and_setter);
+                Self::impl_unit_consts(scope, name, fields);
+                let implementation = Self::impl_struct(
+                    scope,
+                    name,
+                    fields,
+                    self.getter_and_setter,
+                    self.scaled_value_helpers,
+                    self.collection_merge,
+                );
                 for g in generators {
                     g.extend_impl_of_struct(name, implementation, fields);
                 }
+                if self.derive_default && Self::has_default_values(fields) {
+                    Self::impl_struct_default(scope, name, fields);
+                }
+                if self.derive_serde && Self::has_default_values(fields) {
+                    Self::impl_serde_default_fns(scope, name, fields);
+                }
             }
             Rust::Enum(r_enum) => {
                 let implementation = Self::impl_enum(scope, name, r_enum);
@@ -485,11 +1347,17 @@ impl RustCodeGenerator {
                 }
             }
             Rust::DataEnum(enumeration) => {
-                let implementation = Self::impl_data_enum(scope, name, enumeration);
+                let implementation =
+                    Self::impl_data_enum(scope, name, enumeration, self.derive_default);
                 for g in generators {
                     g.extend_impl_of_data_enum(name, implementation, enumeration);
                 }
-                Self::impl_data_enum_default(scope, name, enumeration);
+                if self.derive_default {
+                    Self::impl_data_enum_default(scope, name, enumeration);
+                }
+                if self.choice_trait_objects {
+                    Self::impl_data_enum_trait_object(scope, name, enumeration);
+                }
             }
             Rust::TupleStruct {
                 r#type: inner,
@@ -505,18 +1373,333 @@ impl RustCodeGenerator {
                 Self::impl_tuple_struct_deref(scope, name, inner);
                 Self::impl_tuple_struct_deref_mut(scope, name, inner);
                 Self::impl_tuple_struct_from(scope, name, inner);
+                Self::impl_tuple_struct_collection(scope, name, inner);
             }
         }
+        if self.self_test {
+            Self::impl_representative(scope, name, rust);
+        }
     }
 
-    fn impl_tuple_struct_const_new(scope: &mut Scope, name: &str, rust: &RustType) {
-        scope
+    /// Implements `Representative` through the descriptor types that the `#[asn]` attribute
+    /// declares for the definition and its fields
+    fn impl_representative(scope: &mut Scope, name: &str, rust: &Rust) {
+        let representatives = |base: &str, field: &str| {
+            format!(
+                "<AsnDef{} as {}RepresentativeType>::representatives(depth - 1)",
+                AsnDefWriter::combined_field_type_name(base, field),
+                SELF_TEST_PREFIX,
+            )
+        };
+        let function = scope
             .new_impl(name)
-            .new_fn("new")
-            .vis("pub const")
-            .arg("value", rust.to_string())
-            .ret("Self")
-            .line("Self(value)");
+            .impl_trait(format!("{}Representative", SELF_TEST_PREFIX))
+            .new_fn("representatives")
+            .arg("depth", "usize")
+            .ret("Vec<Self>");
+        if let Rust::Enum(_) = rust {
+            function.line(format!(
+                "<AsnDef{} as {}RepresentativeType>::representatives(depth)",
+                name, SELF_TEST_PREFIX
+            ));
+            return;
+        }
+        function.line("if depth == 0 {");
+        function.line("    return Vec::new();");
+        function.line("}");
+        match rust {
+            Rust::Struct { fields, .. } if fields.is_empty() => {
+                function.line("vec![Self {}]");
+            }
+            Rust::Struct { fields, .. } => {
+                let names = fields
+                    .iter()
+                    .map(|field| Self::rust_field_name(field.name(), true))
+                    .collect::<Vec<_>>();
+                for (index, field) in names.iter().enumerate() {
+                    function.line(format!(
+                        "let field{} = {};",
+                        index,
+                        representatives(name, field)
+                    ));
+                }
+                function.line(format!(
+                    "{}combine(&[{}], |index| Self {{",
+                    SELF_TEST_PREFIX,
+                    (0..names.len())
+                        .map(|index| format!("field{}.len()", index))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+                for (index, field) in names.iter().enumerate() {
+                    function.line(format!(
+                        "    {}: field{1}[index % field{1}.len()].clone(),",
+                        field, index
+                    ));
+                }
+                function.line("})");
+            }
+            Rust::DataEnum(enumeration) => {
+                function.line("let mut values = Vec::new();");
+                for variant in enumeration.variants() {
+                    let variant = Self::rust_variant_name(variant.name());
+                    function.line(format!(
+                        "values.extend({}.into_iter().map(Self::{}));",
+                        representatives(name, &variant),
+                        variant
+                    ));
+                }
+                function.line("values");
+            }
+            Rust::TupleStruct { .. } => {
+                function.line(format!(
+                    "{}.into_iter().map(Self).collect()",
+                    representatives(name, "0")
+                ));
+            }
+            Rust::Enum(_) => {}
+        }
+    }
+
+    /// The `self_test()` fn of the module, which round-trips the representative values of each
+    /// definition
+    fn add_self_test_fn(&self, scope: &mut Scope, model: &Model<Rust>) {
+        let function = scope
+            .new_fn(&self.self_test_name)
+            .vis("pub")
+            .doc("Round-trips representative values of each type of this module - such as the\nbounds of their constraints, empty and huge collections and the extension variants -\nthrough each enabled encoding rule")
+            .ret(format!("Result<(), {}SelfTestError>", SELF_TEST_PREFIX));
+        for Definition(name, _) in &model.definitions {
+            function.line(format!(
+                "{}round_trip::<{}>(\"{}\")?;",
+                SELF_TEST_PREFIX, name, name
+            ));
+        }
+        function.line("Ok(())");
+    }
+
+    /// The `FooRef<'a>` view of a `SEQUENCE` or of a wrapping type, which has the borrowed forms
+    /// of the `OCTET STRING` and `UTF8String` fields and the owned types of the other fields
+    fn add_borrowed_view(&self, scope: &mut Scope, Definition(name, rust): &Definition<Rust>) {
+        let (fields, tuple) = match rust {
+            Rust::Struct {
+                fields,
+                ordering: EncodingOrdering::Keep,
+                ..
+            } => (
+                fields
+                    .iter()
+                    .map(|field| (Self::rust_field_name(field.name(), true), field.r#type()))
+                    .collect::<Vec<_>>(),
+                false,
+            ),
+            Rust::TupleStruct { r#type, .. } => (vec![("0".to_string(), r#type)], true),
+            _ => return,
+        };
+        let cow = match self.target_profile {
+            TargetProfile::Std => "::std::borrow::Cow",
+            TargetProfile::Alloc | TargetProfile::CoreHeapless => "alloc::borrow::Cow",
+        };
+        let borrowed = fields
+            .iter()
+            .map(|(_, r#type)| match r#type.as_no_option() {
+                RustType::VecU8(_) => Some(("[u8]", "read_octet_string_borrowed")),
+                RustType::String(_, Charset::Utf8) => Some(("str", "read_utf8string_borrowed")),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if borrowed.iter().all(Option::is_none) {
+            return;
+        }
+
+        let view = format!("{}Ref", name);
+        let str_ct = scope
+            .new_struct(&view)
+            .vis("pub")
+            .generic("'a")
+            .doc(&format!(
+                "A [`{}`] that borrows its octets and characters from the decoded slice, see\n[`{}::read_uper`]",
+                name, view
+            ))
+            .derive("Debug")
+            .derive("Clone")
+            .derive("PartialEq");
+        let mut reads = Vec::with_capacity(fields.len());
+        let mut comparisons = Vec::with_capacity(fields.len());
+        for ((field, r#type), borrowed) in fields.iter().zip(&borrowed) {
+            let field_type = match borrowed {
+                Some((target, _)) if r#type.is_option() => {
+                    format!("Option<{}<'a, {}>>", cow, target)
+                }
+                Some((target, _)) => format!("{}<'a, {}>", cow, target),
+                None => r#type.to_string(),
+            };
+            if tuple {
+                str_ct.tuple_field(format!("pub {}", field_type));
+            } else {
+                str_ct.field(&format!("pub {}", field), field_type);
+            }
+            let read = match borrowed {
+                Some((_, read_fn)) => {
+                    let read = format!(
+                        "reader.{}::<{}>()",
+                        read_fn,
+                        AsnDefWriter::constraint_type_name(name, field)
+                    );
+                    if r#type.is_option() {
+                        format!("reader.read_opt_with(|reader| {})?", read)
+                    } else {
+                        format!("{}?", read)
+                    }
+                }
+                None => format!(
+                    "AsnDef{}::read_value(reader)?",
+                    AsnDefWriter::combined_field_type_name(name, field)
+                ),
+            };
+            reads.push(if tuple {
+                read
+            } else {
+                format!("{}: {}", field, read)
+            });
+            comparisons.push(match borrowed {
+                Some(_) if r#type.is_option() => {
+                    format!("self.{0}.as_deref() == other.{0}.as_deref()", field)
+                }
+                Some(_) => format!("*self.{0} == *other.{0}", field),
+                None => format!("self.{0} == other.{0}", field),
+            });
+        }
+
+        scope
+            .new_impl(&view)
+            .generic("'a")
+            .target_generic("'a")
+            .new_fn("read_uper")
+            .vis("pub")
+            .doc(format!(
+                "Reads a [`{}`] like `UperReader::read` does, but borrows the octets and characters\nif they start at an octet boundary and are not fragmented",
+                name
+            ))
+            .arg("reader", "&mut UperReader<Bits<'a>>")
+            .ret("Result<Self, per::Error>")
+            .line(format!(
+                "reader.read_with(|reader| reader.read_sequence::<{}, _, _>(|reader| Ok(Self{}{}{})))",
+                name,
+                if tuple { "(" } else { " { " },
+                reads.join(", "),
+                if tuple { ")" } else { " }" },
+            ));
+        scope
+            .new_impl(&format!("{}<'_>", view))
+            .impl_trait(format!("PartialEq<{}>", name))
+            .new_fn("eq")
+            .arg_ref_self()
+            .arg("other", format!("&{}", name))
+            .ret("bool")
+            .line(comparisons.join(" && "));
+        scope
+            .new_impl(name)
+            .impl_trait(format!("PartialEq<{}<'_>>", view))
+            .new_fn("eq")
+            .arg_ref_self()
+            .arg("other", format!("&{}<'_>", view))
+            .ret("bool")
+            .line("other == self");
+    }
+
+    /// The `prototype()` fn of each `SEQUENCE` of the module that the module declares exactly one
+    /// value of, which constructs that value - such as the configuration defaults a standard
+    /// mandates. It is a `const fn` unless constructing the value allocates.
+    fn impl_prototypes(&self, scope: &mut Scope, model: &Model<Rust>) {
+        let is_local_struct = |name: &str| {
+            model
+                .definitions
+                .iter()
+                .any(|Definition(n, rust)| n == name && matches!(rust, Rust::Struct { .. }))
+        };
+        let prototypes = model
+            .value_references
+            .iter()
+            .filter_map(|vref| match (&vref.role, &vref.value) {
+                (RustType::Complex(name, _), LiteralValue::Sequence(_))
+                    if is_local_struct(name) =>
+                {
+                    Some((name.as_str(), vref))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        for (name, vref) in &prototypes {
+            if prototypes.iter().filter(|(n, _)| n == name).count() > 1 {
+                continue;
+            }
+            let (value, allocates) = self.fmt_value(&vref.role, &vref.value);
+            scope
+                .new_impl(name)
+                .new_fn("prototype")
+                .vis(if allocates { "pub" } else { "pub const" })
+                .doc(format!(
+                    "Constructs [`{}`], the value of this type that the ASN.1 module declares",
+                    vref.name
+                ))
+                .ret("Self")
+                .line(value);
+        }
+    }
+
+    /// The `definitions()` fn of the module, which lists the definitions that are not declared
+    /// inline by their ASN.1 names
+    fn add_definitions_fn(scope: &mut Scope, model: &Model<Rust>) {
+        let function = scope
+            .new_fn("definitions")
+            .vis("pub")
+            .doc("The definitions of this module, which decode values by the ASN.1 names of their types")
+            .ret("&'static [::asn1rs::registry::DefinitionInfo]");
+        function.line("const DEFINITIONS: &[::asn1rs::registry::DefinitionInfo] = &[");
+        for (rust_name, asn_name) in &model.asn_names {
+            function.line(format!(
+                "    ::asn1rs::registry::DefinitionInfo::new::<{}>({:?}, {:?}),",
+                rust_name, asn_name, rust_name
+            ));
+        }
+        function.line("];");
+        function.line("DEFINITIONS");
+    }
+
+    /// The `footprint()` fn of the module, which lists the definitions that are not declared
+    /// inline with the sizes of their types and encodings
+    fn add_footprint_fn(scope: &mut Scope, model: &Model<Rust>) {
+        let function = scope
+            .new_fn("footprint")
+            .vis("pub")
+            .doc("The sizes of the types of this module and of the UPER encodings of their values")
+            .ret("&'static [::asn1rs::footprint::Footprint]");
+        function.line("const FOOTPRINT: &[::asn1rs::footprint::Footprint] = &[");
+        for (rust_name, asn_name) in &model.asn_names {
+            let bits = footprint::uper_bits(model, rust_name);
+            let heap = match footprint::heap_expr(model, rust_name) {
+                Some(heap) => format!("Some({})", heap),
+                None => "None".to_string(),
+            };
+            function.line(format!(
+                "    ::asn1rs::footprint::Footprint::new::<{}>({:?}, {:?}, {}, {}, {:?}),",
+                rust_name, asn_name, rust_name, heap, bits.min, bits.max,
+            ));
+        }
+        function.line("];");
+        function.line("FOOTPRINT");
+    }
+
+    fn impl_tuple_struct_const_new(scope: &mut Scope, name: &str, rust: &RustType) {
+        scope
+            .new_impl(name)
+            .new_fn("new")
+            .vis("pub const")
+            .arg("value", rust.to_string())
+            .ret("Self")
+            .line("Self(value)");
     }
 
     fn impl_tuple_struct_deref(scope: &mut Scope, name: &str, rust: &RustType) {
@@ -555,6 +1738,109 @@ impl RustCodeGenerator {
             .arg("value", name)
             .ret("Self")
             .line("value.0");
+
+        // the borrowed forms of the owned values, so that literals convert as well
+        let borrowed = match rust {
+            RustType::String(..) => Some(("&str", "value.to_string()")),
+            RustType::VecU8(_) => Some(("&[u8]", "value.to_vec()")),
+            _ => None,
+        };
+        if let Some((borrowed, owned)) = borrowed {
+            scope
+                .new_impl(name)
+                .impl_trait(format!("::core::convert::From<{}>", borrowed))
+                .new_fn("from")
+                .arg("value", borrowed)
+                .ret("Self")
+                .line(format!("Self({})", owned));
+        }
+    }
+
+    /// Lets a tuple struct wrapping a `SEQUENCE OF` or `SET OF` be iterated, collected, extended
+    /// and indexed like the `Vec` it wraps
+    fn impl_tuple_struct_collection(scope: &mut Scope, name: &str, rust: &RustType) {
+        let RustType::Vec(element, ..) = rust else {
+            return;
+        };
+        let element = element.to_string();
+
+        scope
+            .new_impl(name)
+            .impl_trait("::core::iter::IntoIterator")
+            .associate_type("Item", &element)
+            .associate_type(
+                "IntoIter",
+                format!(
+                    "<{} as ::core::iter::IntoIterator>::IntoIter",
+                    rust.to_string()
+                ),
+            )
+            .new_fn("into_iter")
+            .arg_self()
+            .ret("Self::IntoIter")
+            .line("self.0.into_iter()");
+        scope
+            .new_impl(&format!("&'a {}", name))
+            .generic("'a")
+            .impl_trait("::core::iter::IntoIterator")
+            .associate_type("Item", format!("&'a {}", element))
+            .associate_type("IntoIter", format!("::core::slice::Iter<'a, {}>", element))
+            .new_fn("into_iter")
+            .arg_self()
+            .ret("Self::IntoIter")
+            .line("self.0.iter()");
+        scope
+            .new_impl(&format!("&'a mut {}", name))
+            .generic("'a")
+            .impl_trait("::core::iter::IntoIterator")
+            .associate_type("Item", format!("&'a mut {}", element))
+            .associate_type(
+                "IntoIter",
+                format!("::core::slice::IterMut<'a, {}>", element),
+            )
+            .new_fn("into_iter")
+            .arg_self()
+            .ret("Self::IntoIter")
+            .line("self.0.iter_mut()");
+
+        scope
+            .new_impl(name)
+            .impl_trait(format!("::core::iter::FromIterator<{}>", element))
+            .new_fn("from_iter")
+            .generic(format!("I: ::core::iter::IntoIterator<Item = {}>", element))
+            .arg("iter", "I")
+            .ret("Self")
+            .line("Self(iter.into_iter().collect())");
+        scope
+            .new_impl(name)
+            .impl_trait(format!("::core::iter::Extend<{}>", element))
+            .new_fn("extend")
+            .generic(format!("I: ::core::iter::IntoIterator<Item = {}>", element))
+            .arg_mut_self()
+            .arg("iter", "I")
+            .line("self.0.extend(iter)");
+
+        // generic over the index, so that ranges index the elements like they index a Vec
+        let slice_index = format!("I: ::core::slice::SliceIndex<[{}]>", element);
+        scope
+            .new_impl(name)
+            .generic(&slice_index)
+            .impl_trait("::core::ops::Index<I>")
+            .associate_type("Output", "I::Output")
+            .new_fn("index")
+            .arg_ref_self()
+            .arg("index", "I")
+            .ret("&Self::Output")
+            .line("&self.0[index]");
+        scope
+            .new_impl(name)
+            .generic(&slice_index)
+            .impl_trait("::core::ops::IndexMut<I>")
+            .new_fn("index_mut")
+            .arg_mut_self()
+            .arg("index", "I")
+            .ret("&mut Self::Output")
+            .line("&mut self.0[index]");
     }
 
     fn impl_tuple_struct<'a>(scope: &'a mut Scope, name: &str, rust: &RustType) -> &'a mut Impl {
@@ -568,6 +1854,8 @@ impl RustCodeGenerator {
         name: &str,
         fields: &[Field],
         getter_and_setter: bool,
+        scaled_value_helpers: bool,
+        collection_merge: CollectionMerge,
     ) -> &'a mut Impl {
         let implementation = scope.new_impl(name);
 
@@ -576,13 +1864,132 @@ impl RustCodeGenerator {
                 Self::impl_struct_field_get(implementation, field.name(), field.r#type());
                 Self::impl_struct_field_get_mut(implementation, field.name(), field.r#type());
                 Self::impl_struct_field_set(implementation, field.name(), field.r#type());
+                if let RustType::Option(inner) = field.r#type() {
+                    Self::impl_struct_field_take(implementation, field.name(), inner);
+                    Self::impl_struct_field_replace(implementation, field.name(), inner);
+                }
             }
 
             Self::add_min_max_fn_if_applicable(implementation, Some(field.name()), field.r#type());
+            if scaled_value_helpers {
+                Self::add_scaled_value_fn_if_applicable(implementation, field);
+            }
         }
+        Self::impl_struct_new(implementation, fields);
+        Self::impl_struct_merge_from(implementation, fields, collection_merge);
         implementation
     }
 
+    /// Present optional fields of the other value overwrite the fields of this value, mandatory
+    /// fields are always present. Collections are replaced or appended to, see [`CollectionMerge`].
+    fn impl_struct_merge_from(
+        implementation: &mut Impl,
+        fields: &[Field],
+        collection_merge: CollectionMerge,
+    ) {
+        let merge_fn = implementation
+            .new_fn("merge_from")
+            .vis("pub")
+            .doc("Overwrites the fields of this value with the present fields of the other value")
+            .arg_mut_self()
+            .arg("other", "&Self");
+        let is_copy = |r#type: &RustType| {
+            r#type.is_primitive() || matches!(r#type, RustType::F32 | RustType::F64)
+        };
+        for field in fields {
+            let field_name = Self::rust_field_name(field.name(), true);
+            match field.r#type() {
+                RustType::Option(inner)
+                    if inner.is_vec() && collection_merge == CollectionMerge::Append =>
+                {
+                    merge_fn.line(format!(
+                        "if let Some(value) = &other.{0} {{ self.{0}.get_or_insert_with(Vec::new).extend_from_slice(value); }}",
+                        field_name
+                    ));
+                }
+                RustType::Option(inner) if is_copy(inner) => {
+                    merge_fn.line(format!(
+                        "if other.{0}.is_some() {{ self.{0} = other.{0}; }}",
+                        field_name
+                    ));
+                }
+                RustType::Option(_) => {
+                    merge_fn.line(format!(
+                        "if let Some(value) = &other.{0} {{ self.{0} = Some(value.clone()); }}",
+                        field_name
+                    ));
+                }
+                r#type if r#type.is_vec() && collection_merge == CollectionMerge::Append => {
+                    merge_fn.line(format!(
+                        "self.{0}.extend_from_slice(&other.{0});",
+                        field_name
+                    ));
+                }
+                r#type if is_copy(r#type) => {
+                    merge_fn.line(format!("self.{0} = other.{0};", field_name));
+                }
+                _ => {
+                    merge_fn.line(format!("self.{0}.clone_from(&other.{0});", field_name));
+                }
+            }
+        }
+    }
+
+    /// Takes the mandatory fields in the order of their definition, optional fields are `None` and
+    /// fields with an ASN.1 `DEFAULT` value are initialized with it
+    fn impl_struct_new(implementation: &mut Impl, fields: &[Field]) {
+        let new_fn = implementation
+            .new_fn("new")
+            .vis("pub")
+            .ret("Self")
+            .line("Self {");
+        let mut arguments = 0;
+        for field in fields {
+            let field_name = Self::rust_field_name(field.name(), true);
+            match field.r#type() {
+                RustType::Option(_) => {
+                    new_fn.line(format!("    {}: None,", field_name));
+                }
+                RustType::Default(inner, default) => {
+                    new_fn.line(format!(
+                        "    {}: {},",
+                        field_name,
+                        Self::fmt_default_value(inner, default)
+                    ));
+                }
+                r#type => {
+                    new_fn.arg(&field_name, r#type.to_string());
+                    new_fn.line(format!("    {},", field_name));
+                    arguments += 1;
+                }
+            }
+        }
+        if arguments == 0 {
+            new_fn.attr("allow(clippy::new_without_default)");
+        } else if arguments > 7 {
+            new_fn.attr("allow(clippy::too_many_arguments)");
+        }
+        new_fn.line("}");
+    }
+
+    fn impl_unit_consts(scope: &mut Scope, name: &str, fields: &[Field]) {
+        let units = fields
+            .iter()
+            .filter_map(|field| Some((field.name(), field.unit()?)))
+            .collect::<Vec<_>>();
+        if !units.is_empty() {
+            scope.raw(format!("impl {} {{", name));
+            for (field, unit) in units {
+                scope.raw(format!(
+                    "    pub const {}_UNIT: &'static str = {:?};",
+                    field.to_uppercase(),
+                    unit
+                ));
+            }
+            scope.raw("}");
+        }
+    }
+
     fn impl_consts<'a>(
         scope: &mut Scope,
         name: &str,
@@ -612,13 +2019,24 @@ impl RustCodeGenerator {
         }
     }
 
+    /// Optional fields are returned as `Option<&T>` instead of `&Option<T>`
     fn impl_struct_field_get(implementation: &mut Impl, field_name: &str, field_type: &RustType) {
-        implementation
+        let get_fn = implementation
             .new_fn(&Self::rust_field_name(field_name, true))
             .vis("pub")
-            .arg_ref_self()
-            .ret(format!("&{}", field_type.to_string()))
-            .line(format!("&self.{}", Self::rust_field_name(field_name, true)));
+            .arg_ref_self();
+        if let RustType::Option(inner) = field_type {
+            get_fn
+                .ret(format!("Option<&{}>", inner.to_string()))
+                .line(format!(
+                    "self.{}.as_ref()",
+                    Self::rust_field_name(field_name, true)
+                ));
+        } else {
+            get_fn
+                .ret(format!("&{}", field_type.to_string()))
+                .line(format!("&self.{}", Self::rust_field_name(field_name, true)));
+        }
     }
 
     fn impl_struct_field_get_mut(
@@ -649,6 +2067,31 @@ impl RustCodeGenerator {
             ));
     }
 
+    fn impl_struct_field_take(implementation: &mut Impl, field_name: &str, inner: &RustType) {
+        implementation
+            .new_fn(&format!("take_{}", field_name))
+            .vis("pub")
+            .arg_mut_self()
+            .ret(format!("Option<{}>", inner.to_string()))
+            .line(format!(
+                "self.{}.take()",
+                Self::rust_field_name(field_name, true)
+            ));
+    }
+
+    fn impl_struct_field_replace(implementation: &mut Impl, field_name: &str, inner: &RustType) {
+        implementation
+            .new_fn(&format!("replace_{}", field_name))
+            .vis("pub")
+            .arg_mut_self()
+            .arg("value", inner.to_string())
+            .ret(format!("Option<{}>", inner.to_string()))
+            .line(format!(
+                "self.{}.replace(value)",
+                Self::rust_field_name(field_name, true)
+            ));
+    }
+
     fn impl_enum<'a>(scope: &'a mut Scope, name: &str, r_enum: &PlainEnum) -> &'a mut Impl {
         let implementation = scope.new_impl(name);
 
@@ -719,10 +2162,13 @@ impl RustCodeGenerator {
         scope: &'a mut Scope,
         name: &str,
         enumeration: &DataEnum,
+        variants_fn: bool,
     ) -> &'a mut Impl {
         let implementation = scope.new_impl(name);
 
-        Self::impl_data_enum_values_fn(implementation, name, enumeration);
+        if variants_fn {
+            Self::impl_data_enum_values_fn(implementation, name, enumeration);
+        }
         Self::impl_data_enum_value_index_fn(implementation, name, enumeration);
 
         for variant in enumeration.variants() {
@@ -790,6 +2236,149 @@ impl RustCodeGenerator {
             ));
     }
 
+    fn impl_data_enum_trait_object(scope: &mut Scope, name: &str, enumeration: &DataEnum) {
+        let inner_types = enumeration
+            .variants()
+            .map(|variant| variant.r#type().to_string())
+            .collect::<Vec<_>>();
+        if (1..inner_types.len()).any(|i| inner_types[..i].contains(&inner_types[i])) {
+            return;
+        }
+
+        let trait_name = format!("{}Like", name);
+        let into_fn = format!("into_{}", Self::rust_module_name(name));
+
+        let definition = scope
+            .new_trait(&trait_name)
+            .vis("pub")
+            .parent("::core::any::Any")
+            .doc(&format!(
+                "Implemented by the inner type of each variant of [`{}`]",
+                name
+            ));
+        definition
+            .new_fn(&into_fn)
+            .arg("self", "Box<Self>")
+            .ret(name);
+        definition
+            .new_fn("as_any")
+            .arg_ref_self()
+            .ret("&dyn ::core::any::Any");
+
+        for (variant, inner) in enumeration.variants().zip(&inner_types) {
+            let implementation = scope.new_impl(inner).impl_trait(&trait_name);
+            implementation
+                .new_fn(&into_fn)
+                .arg("self", "Box<Self>")
+                .ret(name)
+                .line(format!(
+                    "{}::{}(*self)",
+                    name,
+                    Self::rust_variant_name(variant.name())
+                ));
+            implementation
+                .new_fn("as_any")
+                .arg_ref_self()
+                .ret("&dyn ::core::any::Any")
+                .line("self");
+        }
+
+        let implementation = scope.new_impl(name);
+        let mut into_block = Block::new("match self");
+        let mut as_block = Block::new("match self");
+        for variant in enumeration.variants() {
+            let variant = Self::rust_variant_name(variant.name());
+            into_block.line(format!("{}::{}(value) => Box::new(value),", name, variant));
+            as_block.line(format!("{}::{}(value) => value,", name, variant));
+        }
+        implementation
+            .new_fn("into_boxed_dyn")
+            .vis("pub")
+            .arg_self()
+            .ret(format!("Box<dyn {}>", trait_name))
+            .push_block(into_block);
+        implementation
+            .new_fn("as_dyn")
+            .vis("pub")
+            .arg_ref_self()
+            .ret(format!("&dyn {}", trait_name))
+            .push_block(as_block);
+
+        scope
+            .new_impl(name)
+            .impl_trait(format!("From<Box<dyn {}>>", trait_name))
+            .new_fn("from")
+            .arg("value", format!("Box<dyn {}>", trait_name))
+            .ret("Self")
+            .line(format!("value.{}()", into_fn));
+    }
+
+    fn has_default_values(fields: &[Field]) -> bool {
+        fields
+            .iter()
+            .any(|field| matches!(field.r#type(), RustType::Default(..)))
+    }
+
+    /// A derived `Default` would ignore the ASN.1 `DEFAULT` values of the fields
+    fn impl_struct_default(scope: &mut Scope, name: &str, fields: &[Field]) {
+        let default_fn = scope
+            .new_impl(name)
+            .impl_trait("Default")
+            .new_fn("default")
+            .ret("Self")
+            .line("Self {");
+        for field in fields {
+            default_fn.line(format!(
+                "    {}: {},",
+                Self::rust_field_name(field.name(), true),
+                match field.r#type() {
+                    RustType::Default(inner, default) => Self::fmt_default_value(inner, default),
+                    _ => "Default::default()".to_string(),
+                }
+            ));
+        }
+        default_fn.line("}");
+    }
+
+    /// Missing fields are deserialized with the return value of the fn in their
+    /// `#[serde(default = "..")]` attribute
+    fn impl_serde_default_fns(scope: &mut Scope, name: &str, fields: &[Field]) {
+        let implementation = scope.new_impl(name);
+        for field in fields {
+            if let RustType::Default(inner, default) = field.r#type() {
+                implementation
+                    .new_fn(&Self::serde_default_fn_name(field.name()))
+                    .ret(inner.to_string())
+                    .line(Self::fmt_default_value(inner, default));
+            }
+        }
+    }
+
+    fn serde_default_fn_name(field_name: &str) -> String {
+        format!("{}_serde_default", Self::rust_field_name(field_name, false))
+    }
+
+    /// The expression constructing the owned value of an ASN.1 `DEFAULT` value
+    fn fmt_default_value(r#type: &RustType, default: &LiteralValue) -> String {
+        let literal = default.as_rust_const_literal(true);
+        match (r#type, default, default.as_bits()) {
+            (_, LiteralValue::Reference(name), _) => format!("{}.clone()", name),
+            (RustType::String(..), _, _) => format!("{}.to_string()", literal),
+            (RustType::VecU8(_), LiteralValue::OctetString(_) | LiteralValue::BitString(..), _) => {
+                format!("{}.to_vec()", literal)
+            }
+            (RustType::BitVec(_), _, Some((_bytes, bit_len))) => {
+                format!("BitVec::from_bytes({}.to_vec(), {})", literal, bit_len)
+            }
+            (RustType::Complex(name, _), _, _)
+                if !matches!(default, LiteralValue::EnumeratedVariant(..)) =>
+            {
+                format!("{}({})", name, literal)
+            }
+            _ => literal.to_string(),
+        }
+    }
+
     fn add_min_max_fn_if_applicable(
         implementation: &mut Impl,
         field_name: Option<&str>,
@@ -814,6 +2403,66 @@ impl RustCodeGenerator {
         }
     }
 
+    fn add_scaled_value_fn_if_applicable(implementation: &mut Impl, field: &Field) {
+        let unit = match field.unit() {
+            Some(unit) => unit,
+            None => return,
+        };
+        let (optional, inner) = match field.r#type() {
+            RustType::Option(inner) => (true, &**inner),
+            RustType::Default(inner, _) => (false, &**inner),
+            other => (false, other),
+        };
+        if inner.integer_range_str().is_none() {
+            return;
+        }
+
+        let (factor, unit) = split_unit(unit);
+        let scaled = if factor == 1.0 {
+            "value as f64".to_string()
+        } else {
+            format!("value as f64 * {:?}", factor)
+        };
+        let field_name = Self::rust_field_name(field.name(), true);
+        let scaled_fn = implementation
+            .new_fn(&format!("{}_{}", field.name(), Self::unit_fn_suffix(unit)))
+            .vis("pub")
+            .arg_ref_self()
+            .doc(format!("The value of `{}` in {}", field.name(), unit));
+        if optional {
+            scaled_fn
+                .ret("Option<f64>")
+                .line(format!("self.{}.map(|value| {})", field_name, scaled));
+        } else {
+            scaled_fn
+                .ret("f64")
+                .line(format!("let value = self.{};", field_name))
+                .line(scaled);
+        }
+    }
+
+    /// The unit as part of a fn name, such as `mps` for `m/s`
+    fn unit_fn_suffix(unit: &str) -> String {
+        let mut suffix = String::with_capacity(unit.len());
+        for c in unit.chars() {
+            match c {
+                '/' => suffix.push('p'),
+                '%' => suffix.push_str("percent"),
+                '°' => suffix.push_str("deg"),
+                c if c.is_ascii_alphanumeric() => suffix.push(c.to_ascii_lowercase()),
+                _ if !suffix.is_empty() && !suffix.ends_with('_') => suffix.push('_'),
+                _ => {}
+            }
+        }
+        let suffix = suffix.trim_end_matches('_');
+        match suffix {
+            "" => "scaled".to_string(),
+            // would collide with the fns of the range
+            "min" | "max" => format!("in_{}", suffix),
+            suffix => suffix.to_string(),
+        }
+    }
+
     fn format_number_nicely(string: &str) -> String {
         let mut out = String::with_capacity(string.len() * 2);
         let mut pos = (3 - string.len() % 3) % 3;
@@ -886,15 +2535,25 @@ impl RustCodeGenerator {
         out
     }
 
-    fn new_struct<'a>(&self, scope: &'a mut Scope, name: &str) -> &'a mut Struct {
-        let str_ct = scope
-            .new_struct(name)
-            .vis("pub")
-            .derive("Default")
-            .derive("Debug")
-            .derive("Clone")
-            .derive("PartialEq")
-            .derive("Hash");
+    fn new_struct<'a>(
+        &self,
+        scope: &'a mut Scope,
+        name: &str,
+        derive_default: bool,
+    ) -> &'a mut Struct {
+        let str_ct = scope.new_struct(name).vis("pub");
+        if derive_default {
+            str_ct.derive("Default");
+        }
+        str_ct.derive("Debug").derive("Clone").derive("PartialEq");
+        if self.is_hashable(name) {
+            str_ct.derive("Hash");
+        }
+        if self.derive_serde {
+            str_ct
+                .derive("serde::Serialize")
+                .derive("serde::Deserialize");
+        }
         self.global_derives.iter().for_each(|derive| {
             str_ct.derive(derive);
         });
@@ -907,11 +2566,16 @@ impl RustCodeGenerator {
             .vis("pub")
             .derive("Debug")
             .derive("Clone")
-            .derive("PartialEq")
-            .derive("Hash");
+            .derive("PartialEq");
+        if self.is_hashable(name) {
+            en_m.derive("Hash");
+        }
         if c_enum {
             en_m.derive("Copy").derive("PartialOrd").derive("Eq");
         }
+        if self.derive_serde {
+            en_m.derive("serde::Serialize").derive("serde::Deserialize");
+        }
         self.global_derives.iter().for_each(|derive| {
             en_m.derive(derive);
         });
@@ -970,6 +2634,180 @@ pub(crate) mod tests {
         );
     }
 
+    #[test]
+    pub fn test_struct_field_units() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"BasicUnit DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            Tank ::= SEQUENCE {
+                fuel INTEGER (0..1000), -- @unit 0.1 l
+                full BOOLEAN
+            }
+
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let (_file_name, file_content) = RustCodeGenerator::from(model)
+            .without_additional_global_derives()
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert_starts_with_lines(
+            r#"
+            use asn1rs::prelude::*;
+            
+            #[asn(sequence)]
+            #[derive(Default, Debug, Clone, PartialEq, Hash)]
+            pub struct Tank {
+                /// Unit: 0.1 l
+                #[asn(integer(0..1000), unit("0.1 l"))] pub fuel: u16,
+                #[asn(boolean)] pub full: bool,
+            }
+            
+            impl Tank {
+                pub const FUEL_UNIT: &'static str = "0.1 l";
+            }
+
+        "#,
+            &file_content,
+        );
+    }
+
+    #[test]
+    pub fn test_encoding_docs() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"BasicLayout DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            Tank ::= SEQUENCE {
+                fuel INTEGER (0..100), -- @unit l
+                full BOOLEAN,
+                ...,
+                name UTF8String OPTIONAL
+            }
+
+            Level ::= CHOICE {
+                tank Tank,
+                raw OCTET STRING
+            }
+
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model);
+        generator.set_encoding_docs(true);
+        let (_file_name, file_content) = generator
+            .without_additional_global_derives()
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert_starts_with_lines(
+            r#"
+            use asn1rs::prelude::*;
+            
+            #[asn(sequence, extensible_after(full))]
+            #[derive(Default, Debug, Clone, PartialEq, Hash)]
+            pub struct Tank {
+                /// Unit: l
+                ///
+                /// UPER: 7-bit constrained integer, offset 0..=100
+                #[asn(integer(0..100), unit("l"))] pub fuel: u8,
+                /// UPER: 1-bit boolean
+                #[asn(boolean)] pub full: bool,
+                /// UPER: extension addition, presence bit in the extension bit-field, then an open type of: length-determined UTF8String
+                #[asn(optional(utf8string))] pub name: Option<String>,
+            }
+        "#,
+            &file_content,
+        );
+        assert!(file_content.contains(
+            "/// UPER: 1-bit choice index 0, then: encoded as `Tank`\n    #[asn(complex(Tank, tag(UNIVERSAL(16))))] Tank(Tank),"
+        ));
+        assert!(file_content.contains(
+            "/// UPER: 1-bit choice index 1, then: length-determined octet string\n    #[asn(octet_string)] Raw(Vec<u8>),"
+        ));
+    }
+
+    #[test]
+    pub fn test_unit_fn_suffix() {
+        assert_eq!("mps", RustCodeGenerator::unit_fn_suffix("m/s"));
+        assert_eq!(
+            "degree_celsius",
+            RustCodeGenerator::unit_fn_suffix("degree Celsius")
+        );
+        assert_eq!("percent", RustCodeGenerator::unit_fn_suffix("%"));
+        assert_eq!("in_min", RustCodeGenerator::unit_fn_suffix("min"));
+        assert_eq!("scaled", RustCodeGenerator::unit_fn_suffix("µ"));
+    }
+
+    fn importing_model_to_file(keep_unused_imports: bool) -> String {
+        let mut resolver = crate::asn::MultiModuleResolver::default();
+        for asn in [
+            r"Other DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Used ::= BOOLEAN
+            Unused ::= BOOLEAN
+            some-value INTEGER ::= 5
+            END",
+            r"Importing DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            IMPORTS Used, Unused, some-value FROM Other;
+            MyTuple ::= SEQUENCE OF Used
+            END",
+        ] {
+            resolver.push(Model::try_from(Tokenizer.parse(asn)).unwrap());
+        }
+        let models = resolver.try_resolve_all().unwrap();
+        let scope = models.iter().collect::<Vec<_>>();
+
+        let mut generator = RustCodeGenerator::from(models[1].to_rust_with_scope(&scope));
+        generator.set_keep_unused_imports(keep_unused_imports);
+        generator
+            .without_additional_global_derives()
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap()
+            .1
+    }
+
+    #[test]
+    pub fn test_only_referenced_imports() {
+        assert_starts_with_lines(
+            r#"
+            use asn1rs::prelude::*;
+            use super::other::Used;
+            "#,
+            &importing_model_to_file(false),
+        );
+    }
+
+    #[test]
+    pub fn test_keep_unused_imports() {
+        assert_starts_with_lines(
+            r#"
+            use asn1rs::prelude::*;
+            
+            #[allow(unused_imports)]
+            use super::other::{Used, Unused, SOME_VALUE};
+            "#,
+            &importing_model_to_file(true),
+        );
+    }
+
     #[test]
     pub fn test_integer_tuple_constants() {
         let model = Model::try_from(Tokenizer::default().parse(
@@ -1010,4 +2848,701 @@ pub(crate) mod tests {
             &file_content,
         );
     }
+
+    #[test]
+    pub fn test_bit_string_default() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r"BitStringDefault DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Lights ::= SEQUENCE { id INTEGER (0..255), flags BIT STRING DEFAULT '101'B }
+            END",
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let (_file_name, file_content) = RustCodeGenerator::from(model)
+            .without_additional_global_derives()
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        // in the Default impl and in new()
+        assert_eq!(
+            2,
+            file_content
+                .matches("flags: BitVec::from_bytes([0xa0, ].to_vec(), 3)")
+                .count(),
+            "{}",
+            file_content
+        );
+    }
+
+    #[test]
+    pub fn test_no_default() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"NoDefault DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            
+            MyStruct ::= SEQUENCE { a BOOLEAN, b INTEGER DEFAULT 5 }
+            MyEnum ::= ENUMERATED { a, b }
+            MyChoice ::= CHOICE { a BOOLEAN, b MyStruct }
+            MyTuple ::= SEQUENCE OF MyChoice
+            
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model);
+        assert!(generator.derives_default());
+        generator.set_derive_default(false);
+        let (_file_name, file_content) = generator
+            .without_additional_global_derives()
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert!(!file_content.contains("Default"), "{}", file_content);
+        assert!(!file_content.contains("#[default]"), "{}", file_content);
+    }
+
+    #[test]
+    pub fn test_derive_serde() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Serde DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            MyStruct ::= SEQUENCE { a BOOLEAN, ..., b INTEGER (0..9) DEFAULT 5 }
+            MyEnum ::= ENUMERATED { a, b, ... }
+            MyChoice ::= CHOICE { a BOOLEAN, ..., b MyStruct }
+
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model);
+        assert!(!generator.derives_serde());
+        generator.set_derive_serde(true);
+        let (_file_name, file_content) = generator
+            .without_additional_global_derives()
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert_eq!(
+            3,
+            file_content
+                .matches("serde::Serialize, serde::Deserialize")
+                .count(),
+            "{}",
+            file_content
+        );
+        assert!(
+            file_content.contains(
+                r#"#[serde(default = "MyStruct::b_serde_default")] #[asn(default(integer(0..9), 5))] pub b: u8,"#
+            ),
+            "{}",
+            file_content
+        );
+        assert_starts_with_lines(
+            r#"
+            impl MyStruct {
+                fn b_serde_default() -> u8 {
+                    5
+                }
+            }"#,
+            &file_content[file_content
+                .find("impl MyStruct {\n    fn b_serde")
+                .unwrap()..],
+        );
+    }
+
+    #[test]
+    pub fn test_doc_examples() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Examples DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            Tree ::= SEQUENCE {
+                id       INTEGER (10..20),
+                code     NumericString (SIZE(2)),
+                kind     ENUMERATED { leaf, node },
+                parent   Tree OPTIONAL,
+                children SEQUENCE OF Tree
+            }
+            Shape ::= CHOICE { size INTEGER (-5..-1), tree Tree }
+
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model);
+        assert_eq!(None, generator.doc_examples());
+        generator.set_doc_examples(Some("my_crate::generated".to_string()));
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert_starts_with_lines(
+            r#"
+            /// ```rust
+            /// use asn1rs::prelude::*;
+            /// use my_crate::generated::examples::*;
+            ///
+            /// let value = Tree {
+            ///     id: 10,
+            ///     code: "11".to_string(),
+            ///     kind: TreeKind::Leaf,
+            ///     parent: None,
+            ///     children: Vec::new(),
+            /// };
+            ///
+            /// let mut writer = UperWriter::default();
+            /// writer.write(&value).unwrap();
+            ///
+            /// let mut reader = writer.as_reader();
+            /// assert_eq!(value, reader.read::<Tree>().unwrap());
+            /// ```"#,
+            &file_content[file_content.rfind("/// ```rust\n/// use asn1rs::prelude::*;\n/// use my_crate::generated::examples::*;\n///\n/// let value = Tree {").unwrap()..],
+        );
+        assert!(
+            file_content.contains("/// let value = TreeKind::Leaf;")
+                && file_content.contains("/// let value = Shape::Size(-1);"),
+            "{}",
+            file_content
+        );
+    }
+
+    #[test]
+    pub fn test_heapless_types() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Heapless DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            Names ::= SEQUENCE (SIZE(1..2)) OF IA5String (SIZE(1..3))
+            Data ::= CHOICE { raw OCTET STRING (SIZE(4)), text BMPString (SIZE(0..2)) }
+            Open ::= SEQUENCE (SIZE(1..2, ...)) OF UTF8String
+
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model);
+        assert!(!generator.uses_heapless_types());
+        generator.set_heapless_types(true);
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        for expected in [
+            "::heapless::Vec<::heapless::String<3>, 2>))] pub ::heapless::Vec<::heapless::String<3>, 2>);",
+            "::heapless::Vec<u8, 4>))] Raw(::heapless::Vec<u8, 4>),",
+            "::heapless::String<6>))] Text(::heapless::String<6>),",
+            "utf8string))] pub Vec<String>);",
+        ] {
+            assert!(
+                file_content.contains(expected),
+                "{}\n{}",
+                expected,
+                file_content
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_choice_trait_objects() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"TraitObjects DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            MyStruct ::= SEQUENCE { a BOOLEAN }
+            WoahDecision ::= CHOICE { flag BOOLEAN, my-struct MyStruct }
+            Ambiguous ::= CHOICE { a BOOLEAN, b BOOLEAN }
+
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model);
+        assert!(!generator.has_choice_trait_objects());
+        generator.set_choice_trait_objects(true);
+        let (_file_name, file_content) = generator
+            .without_additional_global_derives()
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        for expected in [
+            "pub trait WoahDecisionLike: ::core::any::Any {",
+            "fn into_woah_decision(self: Box<Self>) -> WoahDecision;",
+            "impl WoahDecisionLike for bool {",
+            "impl WoahDecisionLike for MyStruct {",
+            "WoahDecision::MyStruct(*self)",
+            "pub fn into_boxed_dyn(self) -> Box<dyn WoahDecisionLike> {",
+            "pub fn as_dyn(&self) -> &dyn WoahDecisionLike {",
+            "impl From<Box<dyn WoahDecisionLike>> for WoahDecision {",
+        ] {
+            assert!(file_content.contains(expected), "{}", file_content);
+        }
+        assert!(!file_content.contains("AmbiguousLike"), "{}", file_content);
+    }
+
+    #[test]
+    pub fn test_deduplicate_inline_types() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Dedup DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            First ::= SEQUENCE { mode ENUMERATED { on, off } }
+            Second ::= SEQUENCE { mode ENUMERATED { on, off } }
+
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model);
+        assert!(generator.deduplicates_inline_types());
+        let file_content = generator.to_string_without_generators().remove(0).1;
+        assert!(
+            file_content.contains("pub mode: FirstMode,"),
+            "{}",
+            file_content
+        );
+        assert!(!file_content.contains("SecondMode"), "{}", file_content);
+
+        generator.set_deduplicate_inline_types(false);
+        let file_content = generator.to_string_without_generators().remove(0).1;
+        assert!(
+            file_content.contains("pub enum SecondMode {"),
+            "{}",
+            file_content
+        );
+        assert!(
+            file_content.contains("pub mode: SecondMode,"),
+            "{}",
+            file_content
+        );
+    }
+
+    #[test]
+    pub fn test_naming_strategy() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Naming DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            Cell ::= SEQUENCE {
+                interval CHOICE { fdd SEQUENCE { period ENUMERATED { ms10, ms20 } }, tdd NULL }
+            }
+
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model);
+        generator.set_naming_strategy(|parent: &str, component: &str| {
+            format!("{}_{}", parent, component)
+        });
+        let file_content = generator.to_string_without_generators().remove(0).1;
+        for expected in [
+            "pub enum Cell_Interval {",
+            "pub struct Cell_Interval_Fdd {",
+            "pub enum Cell_Interval_Fdd_Period {",
+            "pub interval: Cell_Interval,",
+            "Fdd(Cell_Interval_Fdd),",
+            "pub period: Cell_Interval_Fdd_Period,",
+        ] {
+            assert!(file_content.contains(expected), "{}", file_content);
+        }
+        assert!(!file_content.contains("CellInterval {"), "{}", file_content);
+    }
+
+    #[test]
+    pub fn test_merge_from_appends_collections() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Merge DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            Config ::= SEQUENCE {
+                level INTEGER (0..7),
+                name UTF8String OPTIONAL,
+                ratio REAL OPTIONAL,
+                peers SEQUENCE OF UTF8String,
+                routes SEQUENCE OF INTEGER OPTIONAL
+            }
+
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model);
+        assert_eq!(CollectionMerge::Replace, generator.collection_merge());
+        generator.set_collection_merge(CollectionMerge::Append);
+        let (_file_name, file_content) = generator
+            .without_additional_global_derives()
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        for expected in [
+            "pub fn merge_from(&mut self, other: &Self) {",
+            "self.level = other.level;",
+            "if let Some(value) = &other.name { self.name = Some(value.clone()); }",
+            "if other.ratio.is_some() { self.ratio = other.ratio; }",
+            "self.peers.extend_from_slice(&other.peers);",
+            "if let Some(value) = &other.routes { self.routes.get_or_insert_with(Vec::new).extend_from_slice(value); }",
+        ] {
+            assert!(file_content.contains(expected), "{}", file_content);
+        }
+    }
+
+    #[test]
+    pub fn test_optional_field_accessors() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Accessors DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            MyStruct ::= SEQUENCE { a BOOLEAN, b UTF8String OPTIONAL }
+
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model);
+        generator.set_fields_have_getter_and_setter(true);
+        let (_file_name, file_content) = generator
+            .without_additional_global_derives()
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+        let file_content = file_content
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        for expected in [
+            "pub fn a(&self) -> &bool { &self.a }",
+            "pub fn set_a(&mut self, value: bool) { self.a = value; }",
+            "pub fn b(&self) -> Option<&String> { self.b.as_ref() }",
+            "pub fn b_mut(&mut self) -> &mut Option<String> { &mut self.b }",
+            "pub fn set_b(&mut self, value: Option<String>) { self.b = value; }",
+            "pub fn take_b(&mut self) -> Option<String> { self.b.take() }",
+            "pub fn replace_b(&mut self, value: String) -> Option<String> { self.b.replace(value) }",
+        ] {
+            assert!(file_content.contains(expected), "{}", file_content);
+        }
+        assert!(!file_content.contains("take_a"), "{}", file_content);
+    }
+
+    #[test]
+    pub fn test_target_profiles() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Profiles DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            Fixed ::= SEQUENCE { a BOOLEAN, b INTEGER (0..255) OPTIONAL }
+            Growing ::= SEQUENCE { name UTF8String DEFAULT "none", items SEQUENCE OF Fixed }
+
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let violations = |profile: TargetProfile| {
+            let mut generator = RustCodeGenerator::from(model.clone());
+            generator.set_target_profile(profile);
+            generator.target_profile_violations()
+        };
+
+        assert!(violations(TargetProfile::Std).is_empty());
+        assert!(violations(TargetProfile::Alloc).is_empty());
+        assert_eq!(
+            vec![(
+                "profiles.rs".to_string(),
+                ["String", "Vec", "to_string"].map(String::from).to_vec()
+            )],
+            violations(TargetProfile::CoreHeapless)
+        );
+    }
+
+    #[test]
+    pub fn test_self_test_fn() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"SelfTest DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            MyStruct ::= SEQUENCE { type BOOLEAN, my-flag BOOLEAN OPTIONAL }
+            Decision ::= CHOICE { flag BOOLEAN, my-struct MyStruct }
+            Color ::= ENUMERATED { red, green }
+
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model);
+        assert!(generator.has_self_test());
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        for expected in [
+            "impl ::asn1rs::descriptor::selftest::Representative for MyStruct {",
+            "let field0 = <AsnDefMyStructFieldType as ::asn1rs::descriptor::selftest::RepresentativeType>::representatives(depth - 1);",
+            "::asn1rs::descriptor::selftest::combine(&[field0.len(), field1.len()], |index| Self {",
+            "type_: field0[index % field0.len()].clone(),",
+            "my_flag: field1[index % field1.len()].clone(),",
+            "values.extend(<AsnDefDecisionFieldMyStruct as ::asn1rs::descriptor::selftest::RepresentativeType>::representatives(depth - 1).into_iter().map(Self::MyStruct));",
+            "<AsnDefColor as ::asn1rs::descriptor::selftest::RepresentativeType>::representatives(depth)",
+            "pub fn self_test() -> Result<(), ::asn1rs::descriptor::selftest::SelfTestError> {",
+            "::asn1rs::descriptor::selftest::round_trip::<Decision>(\"Decision\")?;",
+        ] {
+            assert!(file_content.contains(expected), "{}", file_content);
+        }
+
+        generator.set_self_test_name("self_test_of_self_test");
+        assert_eq!("self_test_of_self_test", generator.self_test_name());
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+        assert!(file_content.contains(
+            "pub fn self_test_of_self_test() -> Result<(), ::asn1rs::descriptor::selftest::SelfTestError> {"
+        ));
+
+        generator.set_self_test(false);
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+        assert!(!file_content.contains("selftest"), "{}", file_content);
+    }
+
+    #[test]
+    pub fn test_borrowed_views() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Views DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            Message ::= SEQUENCE { id INTEGER (0..255), note UTF8String OPTIONAL }
+            Plain ::= SEQUENCE { id INTEGER (0..255), label IA5String }
+            Unordered ::= SET { note UTF8String }
+            Payload ::= OCTET STRING
+
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model);
+        assert!(!generator.has_borrowed_views());
+        generator.set_borrowed_views(true);
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        for expected in [
+            "pub struct MessageRef<'a> {",
+            "pub note: Option<::std::borrow::Cow<'a, str>>,",
+            "reader.read_with(|reader| reader.read_sequence::<Message, _, _>(|reader| Ok(Self { id: AsnDefMessageFieldId::read_value(reader)?, note: reader.read_opt_with(|reader| reader.read_utf8string_borrowed::<___asn1rs_MessageFieldNoteConstraint>())? })))",
+            "impl PartialEq<Message> for MessageRef<'_> {",
+            "self.id == other.id && self.note.as_deref() == other.note.as_deref()",
+            "impl PartialEq<MessageRef<'_>> for Message {",
+            "pub struct PayloadRef<'a>(pub ::std::borrow::Cow<'a, [u8]>);",
+            "*self.0 == *other.0",
+        ] {
+            assert!(file_content.contains(expected), "{}", file_content);
+        }
+        assert!(!file_content.contains("PlainRef"), "{}", file_content);
+        assert!(!file_content.contains("UnorderedRef"), "{}", file_content);
+    }
+
+    #[test]
+    pub fn test_definitions_fn() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Registry DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            Ping-Request ::= SEQUENCE { mode ENUMERATED { fast, slow } }
+
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model);
+        assert!(generator.has_registry());
+        let file_content = generator.to_string_without_generators().remove(0).1;
+        assert!(
+            file_content.contains(
+                "pub fn definitions() -> &'static [::asn1rs::registry::DefinitionInfo] {"
+            ),
+            "{}",
+            file_content
+        );
+        assert!(
+            file_content.contains(
+                r#"::asn1rs::registry::DefinitionInfo::new::<PingRequest>("Ping-Request", "PingRequest"),"#
+            ),
+            "{}",
+            file_content
+        );
+        assert!(
+            !file_content.contains("DefinitionInfo::new::<PingRequestMode>"),
+            "{}",
+            file_content
+        );
+
+        generator.set_registry(false);
+        let file_content = generator.to_string_without_generators().remove(0).1;
+        assert!(!file_content.contains("registry"), "{}", file_content);
+    }
+
+    #[test]
+    pub fn test_footprint_fn() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Footprint DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            Ping-Request ::= SEQUENCE {
+                mode    ENUMERATED { fast, slow },
+                payload OCTET STRING (SIZE(0..4))
+            }
+
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model);
+        assert!(!generator.has_footprint());
+        let file_content = generator.to_string_without_generators().remove(0).1;
+        assert!(!file_content.contains("footprint"), "{}", file_content);
+
+        generator.set_footprint(true);
+        let file_content = generator.to_string_without_generators().remove(0).1;
+        assert!(
+            file_content
+                .contains("pub fn footprint() -> &'static [::asn1rs::footprint::Footprint] {"),
+            "{}",
+            file_content
+        );
+        assert!(
+            file_content.contains(
+                r#"::asn1rs::footprint::Footprint::new::<PingRequest>("Ping-Request", "PingRequest", Some(4), 4, Some(36)),"#
+            ),
+            "{}",
+            file_content
+        );
+        assert!(
+            !file_content.contains("Footprint::new::<PingRequestMode>"),
+            "{}",
+            file_content
+        );
+    }
+
+    #[test]
+    pub fn test_prototype_only_for_the_only_value() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Prototypes DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            Config ::= SEQUENCE { timeout INTEGER (0..255) }
+            Server ::= SEQUENCE { port INTEGER (0..65535) }
+
+            defaultConfig Config ::= { timeout 5 }
+            primary Server ::= { port 1 }
+            backup Server ::= { port 2 }
+
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let file_content = RustCodeGenerator::from(model)
+            .to_string_without_generators()
+            .remove(0)
+            .1;
+        assert!(
+            file_content
+                .contains("pub const fn prototype() -> Self {\n        Config { timeout: 5 }"),
+            "{}",
+            file_content
+        );
+        assert_eq!(
+            1,
+            file_content.matches("fn prototype()").count(),
+            "{}",
+            file_content
+        );
+    }
 }
@@ -73,7 +73,7 @@ impl AsnDefWriter {
     #[must_use]
     pub fn type_declaration(r#type: &RustType, name: &str) -> String {
         match r#type {
-            RustType::Bool => format!("{}Boolean", CRATE_SYN_PREFIX),
+            RustType::Bool => format!("{}Boolean<{}Constraint>", CRATE_SYN_PREFIX, name),
             RustType::I8(_) => format!("{}Integer<i8, {}Constraint>", CRATE_SYN_PREFIX, name),
             RustType::U8(_) => format!("{}Integer<u8, {}Constraint>", CRATE_SYN_PREFIX, name),
             RustType::I16(_) => format!("{}Integer<i16, {}Constraint>", CRATE_SYN_PREFIX, name),
@@ -82,13 +82,41 @@ impl AsnDefWriter {
             RustType::U32(_) => format!("{}Integer<u32, {}Constraint>", CRATE_SYN_PREFIX, name),
             RustType::I64(_) => format!("{}Integer<i64, {}Constraint>", CRATE_SYN_PREFIX, name),
             RustType::U64(_) => format!("{}Integer<u64, {}Constraint>", CRATE_SYN_PREFIX, name),
+            RustType::F32 => format!("{}Real<f32, {}Constraint>", CRATE_SYN_PREFIX, name),
+            RustType::F64 => format!("{}Real<f64, {}Constraint>", CRATE_SYN_PREFIX, name),
+            RustType::ObjectIdentifier => {
+                format!("{}ObjectIdentifier<{}Constraint>", CRATE_SYN_PREFIX, name)
+            }
+            RustType::RelativeOid => {
+                format!("{}RelativeOid<{}Constraint>", CRATE_SYN_PREFIX, name)
+            }
+            RustType::OidIri => format!("{}OidIri<{}Constraint>", CRATE_SYN_PREFIX, name),
             RustType::String(_, charset) => format!(
                 "{}{:?}String<{}Constraint>",
                 CRATE_SYN_PREFIX, charset, name
             ),
             RustType::VecU8(_) => format!("{}OctetString<{}Constraint>", CRATE_SYN_PREFIX, name),
+            RustType::Containing(inner, encoded_by) => {
+                let virtual_field = Self::containing_virtual_field_name(name);
+                format!(
+                    "{}Containing<{}, {}, {}Constraint>",
+                    CRATE_SYN_PREFIX,
+                    Self::type_declaration(inner, &virtual_field),
+                    encoded_by
+                        .clone()
+                        .unwrap_or_else(|| format!("{}containing::Identity", CRATE_SYN_PREFIX)),
+                    name
+                )
+            }
+            RustType::Substituted(inner, substitution) => format!(
+                "{}Substituted<{}, {}, {}>",
+                CRATE_SYN_PREFIX,
+                substitution.rust_type,
+                substitution.converter(),
+                Self::type_declaration(inner, name)
+            ),
             RustType::BitVec(_) => format!("{}BitString<{}Constraint>", CRATE_SYN_PREFIX, name),
-            RustType::Null => format!("{}NullT", CRATE_SYN_PREFIX),
+            RustType::Null => format!("{}NullT<{}Constraint>", CRATE_SYN_PREFIX, name),
             RustType::Vec(inner, _, ordering) => {
                 let virtual_field = Self::vec_virtual_field_name(name);
                 format!(
@@ -170,6 +198,8 @@ impl AsnDefWriter {
                         name_type: (variant.name().to_string(), variant.r#type().clone()),
                         tag: variant.tag(),
                         constants: Vec::default(),
+                        sensitive: false,
+                        unit: None,
                     })
                     .collect::<Vec<_>>();
 
@@ -188,6 +218,8 @@ impl AsnDefWriter {
                     name_type: ("0".to_string(), r#type.clone()),
                     tag: *tag,
                     constants: constants.to_vec(),
+                    sensitive: false,
+                    unit: None,
                 }];
                 self.write_field_constraints(scope, name, &fields[..]);
                 self.write_sequence_or_set_constraint(
@@ -223,6 +255,7 @@ impl AsnDefWriter {
                     constraint_type_name,
                     field.tag.unwrap_or(Tag::DEFAULT_BOOLEAN),
                 );
+                Self::write_tag_only_constraint("boolean", scope, constraint_type_name);
             }
             RustType::I8(range) => {
                 Self::write_common_constraint_type(
@@ -235,6 +268,8 @@ impl AsnDefWriter {
                     constraint_type_name,
                     &field.r#type().to_string(),
                     &range.wrap_opt(),
+                    field.unit(),
+                    (name, field.name()),
                 )
             }
             RustType::U8(range) => {
@@ -248,6 +283,8 @@ impl AsnDefWriter {
                     constraint_type_name,
                     &field.r#type().to_string(),
                     &range.wrap_opt(),
+                    field.unit(),
+                    (name, field.name()),
                 )
             }
             RustType::I16(range) => {
@@ -261,6 +298,8 @@ impl AsnDefWriter {
                     constraint_type_name,
                     &field.r#type().to_string(),
                     &range.wrap_opt(),
+                    field.unit(),
+                    (name, field.name()),
                 )
             }
             RustType::U16(range) => {
@@ -274,6 +313,8 @@ impl AsnDefWriter {
                     constraint_type_name,
                     &field.r#type().to_string(),
                     &range.wrap_opt(),
+                    field.unit(),
+                    (name, field.name()),
                 )
             }
             RustType::I32(range) => {
@@ -287,6 +328,8 @@ impl AsnDefWriter {
                     constraint_type_name,
                     &field.r#type().to_string(),
                     &range.wrap_opt(),
+                    field.unit(),
+                    (name, field.name()),
                 )
             }
             RustType::U32(range) => {
@@ -300,6 +343,8 @@ impl AsnDefWriter {
                     constraint_type_name,
                     &field.r#type().to_string(),
                     &range.wrap_opt(),
+                    field.unit(),
+                    (name, field.name()),
                 )
             }
             RustType::I64(range) => {
@@ -313,6 +358,8 @@ impl AsnDefWriter {
                     constraint_type_name,
                     &field.r#type().to_string(),
                     &range.wrap_opt(),
+                    field.unit(),
+                    (name, field.name()),
                 )
             }
             RustType::U64(range) => {
@@ -326,6 +373,8 @@ impl AsnDefWriter {
                     constraint_type_name,
                     &field.r#type().to_string(),
                     range,
+                    field.unit(),
+                    (name, field.name()),
                 )
             }
             RustType::String(size, charset) => {
@@ -341,6 +390,8 @@ impl AsnDefWriter {
                         Charset::Numeric => "numericstring",
                         Charset::Printable => "printablestring",
                         Charset::Visible => "visiblestring",
+                        Charset::Bmp => "bmpstring",
+                        Charset::Teletex => "teletexstring",
                     },
                     scope,
                     constraint_type_name,
@@ -355,6 +406,31 @@ impl AsnDefWriter {
                 );
                 Self::write_size_constraint("octetstring", scope, constraint_type_name, size)
             }
+            RustType::Containing(inner, _) => {
+                Self::write_common_constraint_type(
+                    scope,
+                    constraint_type_name,
+                    field.tag.unwrap_or(Tag::DEFAULT_OCTET_STRING),
+                );
+                Self::write_size_constraint("octetstring", scope, constraint_type_name, &Size::Any);
+
+                let virtual_field_name = Self::containing_virtual_field_name(field.name());
+                let constraint_type_name = Self::constraint_type_name(name, &virtual_field_name);
+                Self::write_constraint_type_decl(scope, &constraint_type_name);
+
+                self.write_field_constraint(
+                    scope,
+                    name,
+                    &Field {
+                        name_type: (virtual_field_name, *inner.clone()),
+                        tag: None,
+                        constants: Vec::default(),
+                        sensitive: field.sensitive,
+                        unit: field.unit.clone(),
+                    },
+                    &constraint_type_name,
+                )
+            }
             RustType::BitVec(size) => {
                 Self::write_common_constraint_type(
                     scope,
@@ -390,6 +466,8 @@ impl AsnDefWriter {
                         name_type: (virtual_field_name, *inner.clone()),
                         tag: None,
                         constants: field.constants().to_vec(),
+                        sensitive: field.sensitive,
+                        unit: field.unit.clone(),
                     },
                     &constraint_type_name,
                 )
@@ -400,17 +478,53 @@ impl AsnDefWriter {
                     constraint_type_name,
                     field.tag.unwrap_or(Tag::DEFAULT_NULL),
                 );
+                Self::write_tag_only_constraint("null", scope, constraint_type_name);
             }
-            RustType::Option(inner) => self.write_field_constraint(
-                scope,
-                name,
-                &Field {
-                    name_type: (field.name().to_string(), *inner.clone()),
-                    tag: field.tag(),
-                    constants: field.constants().to_vec(),
-                },
-                constraint_type_name,
-            ),
+            RustType::F32 | RustType::F64 => {
+                Self::write_common_constraint_type(
+                    scope,
+                    constraint_type_name,
+                    field.tag.unwrap_or(Tag::DEFAULT_REAL),
+                );
+                Self::write_tag_only_constraint("real", scope, constraint_type_name);
+            }
+            RustType::ObjectIdentifier => {
+                Self::write_common_constraint_type(
+                    scope,
+                    constraint_type_name,
+                    field.tag.unwrap_or(Tag::DEFAULT_OBJECT_IDENTIFIER),
+                );
+                Self::write_tag_only_constraint("objectidentifier", scope, constraint_type_name);
+            }
+            RustType::RelativeOid => {
+                Self::write_common_constraint_type(
+                    scope,
+                    constraint_type_name,
+                    field.tag.unwrap_or(Tag::DEFAULT_RELATIVE_OID),
+                );
+                Self::write_tag_only_constraint("relativeoid", scope, constraint_type_name);
+            }
+            RustType::OidIri => {
+                Self::write_common_constraint_type(
+                    scope,
+                    constraint_type_name,
+                    field.tag.unwrap_or(Tag::DEFAULT_OID_IRI),
+                );
+                Self::write_tag_only_constraint("oidiri", scope, constraint_type_name);
+            }
+            RustType::Option(inner) | RustType::Substituted(inner, _) => self
+                .write_field_constraint(
+                    scope,
+                    name,
+                    &Field {
+                        name_type: (field.name().to_string(), *inner.clone()),
+                        tag: field.tag(),
+                        constants: field.constants().to_vec(),
+                        sensitive: field.sensitive,
+                        unit: field.unit.clone(),
+                    },
+                    constraint_type_name,
+                ),
             RustType::Default(inner, default) => {
                 Self::write_common_constraint_type(
                     scope,
@@ -430,6 +544,8 @@ impl AsnDefWriter {
                         name_type: (virtual_field_name, *inner.clone()),
                         tag: field.tag,
                         constants: field.constants().to_vec(),
+                        sensitive: field.sensitive,
+                        unit: field.unit.clone(),
                     },
                     &constraint_type_name,
                 )
@@ -466,6 +582,10 @@ impl AsnDefWriter {
         field_name.to_string() + "Value"
     }
 
+    fn containing_virtual_field_name(field_name: &str) -> String {
+        field_name.to_string() + "Content"
+    }
+
     fn write_sequence_or_set_constraint(
         &self,
         scope: &mut Scope,
@@ -528,6 +648,285 @@ impl AsnDefWriter {
             .line(format!("AsnDef{}::write_value(writer, self)", name));
     }
 
+    fn impl_der_fns(&self, scope: &mut Scope, name: &str) {
+        scope
+            .new_impl(name)
+            .new_fn("write_der")
+            .vis("pub")
+            .doc("Encodes this value according to the Distinguished Encoding Rules")
+            .arg_ref_self()
+            .ret("Result<Vec<u8>, ::asn1rs::protocol::basic::Error>")
+            .line("let mut writer = ::asn1rs::rw::DerWriter::default();")
+            .line(format!(
+                "{}Writable::write(self, &mut writer)?;",
+                CRATE_SYN_PREFIX
+            ))
+            .line("Ok(writer.into_bytes_vec())");
+    }
+
+    fn impl_redacted_debug(&self, scope: &mut Scope, Definition(name, r#type): &Definition<Rust>) {
+        let redacted_debug = format!("{}redact::RedactedDebug::redacted_debug", CRATE_SYN_PREFIX);
+        let body = scope
+            .new_impl(name)
+            .impl_trait(format!("{}redact::RedactedDebug", CRATE_SYN_PREFIX))
+            .new_fn("fmt_redacted")
+            .arg_ref_self()
+            .arg("f", "&mut ::core::fmt::Formatter<'_>")
+            .ret("::core::fmt::Result");
+
+        match r#type {
+            Rust::Struct { fields, .. } => {
+                body.line(format!("f.debug_struct(\"{}\")", name));
+                for field in fields {
+                    body.line(format!(
+                        ".field(\"{}\", &{})",
+                        field.name().trim_start_matches("r#"),
+                        if field.sensitive() {
+                            format!("{}redact::Masked", CRATE_SYN_PREFIX)
+                        } else if Self::is_substituted(field.r#type()) {
+                            format!("self.{}", field.name())
+                        } else {
+                            format!("{}(&self.{})", redacted_debug, field.name())
+                        }
+                    ));
+                }
+                body.line(".finish()");
+            }
+            Rust::TupleStruct { r#type, .. } if Self::is_substituted(r#type) => {
+                body.line(format!(
+                    "f.debug_tuple(\"{}\").field(&self.0).finish()",
+                    name
+                ));
+            }
+            Rust::TupleStruct { .. } => {
+                body.line(format!(
+                    "f.debug_tuple(\"{}\").field(&{}(&self.0)).finish()",
+                    name, redacted_debug
+                ));
+            }
+            Rust::Enum(enumerated) => {
+                body.push_block({
+                    let mut match_block = Block::new("match self");
+                    for variant in enumerated.variants() {
+                        match_block.line(format!(
+                            "Self::{} => f.write_str(\"{}\"),",
+                            variant, variant
+                        ));
+                    }
+                    match_block
+                });
+            }
+            Rust::DataEnum(choice) => {
+                body.push_block({
+                    let mut match_block = Block::new("match self");
+                    for variant in choice.variants() {
+                        match_block.line(format!(
+                            "Self::{}(value) => f.debug_tuple(\"{}\").field(&{}).finish(),",
+                            variant.name(),
+                            variant.name(),
+                            if variant.sensitive() {
+                                format!("{}redact::Masked", CRATE_SYN_PREFIX)
+                            } else if Self::is_substituted(variant.r#type()) {
+                                "value".to_string()
+                            } else {
+                                format!("{}(value)", redacted_debug)
+                            }
+                        ));
+                    }
+                    match_block
+                });
+            }
+        }
+    }
+
+    fn impl_visitable(&self, scope: &mut Scope, Definition(name, r#type): &Definition<Rust>) {
+        let visit = format!("{}visit", CRATE_SYN_PREFIX);
+        let body = scope
+            .new_impl(name)
+            .impl_trait(format!("{}::Visitable", visit))
+            .new_fn("accept_at")
+            .arg_ref_self()
+            .arg("path", format!("&mut {}::Path", visit))
+            .arg("visitor", format!("&mut impl {}::Visitor", visit))
+            .line(format!("visitor.enter(path, \"{}\");", name));
+
+        match r#type {
+            Rust::Struct { fields, .. } => {
+                for field in fields {
+                    body.line(format!(
+                        "path.push({}::Segment::Field(\"{}\"));",
+                        visit,
+                        field.name().trim_start_matches("r#")
+                    ));
+                    body.line(Self::visit_value(
+                        field.r#type(),
+                        &format!("&self.{}", field.name()),
+                        0,
+                    ));
+                    body.line("path.pop();");
+                }
+            }
+            Rust::TupleStruct { r#type, .. } => {
+                body.line(Self::visit_value(r#type, "&self.0", 0));
+            }
+            Rust::Enum(enumerated) => {
+                body.push_block({
+                    let mut match_block = Block::new("match self");
+                    for variant in enumerated.variants() {
+                        match_block.line(format!(
+                            "Self::{} => visitor.visit_enumerated(path, \"{}\"),",
+                            variant, variant
+                        ));
+                    }
+                    match_block
+                });
+            }
+            Rust::DataEnum(choice) => {
+                body.push_block({
+                    let mut match_block = Block::new("match self");
+                    for variant in choice.variants() {
+                        match_block.line(format!(
+                            "Self::{}({}) => {{ path.push({}::Segment::Field(\"{}\")); {} path.pop(); }}",
+                            variant.name(),
+                            Self::binding(variant.r#type(), "value"),
+                            visit,
+                            variant.name(),
+                            Self::visit_value(variant.r#type(), "value", 0),
+                        ));
+                    }
+                    match_block
+                });
+            }
+        }
+
+        body.line(format!("visitor.leave(path, \"{}\");", name));
+    }
+
+    fn impl_writable_field(&self, scope: &mut Scope, Definition(name, r#type): &Definition<Rust>) {
+        let has_fields = matches!(r#type, Rust::Struct { .. } | Rust::DataEnum(_));
+        let (name_arg, writer_arg) = if has_fields {
+            ("name", "writer")
+        } else {
+            ("_name", "_writer")
+        };
+        let body = scope
+            .new_impl(name)
+            .impl_trait(format!("{}detached::WritableField", CRATE_SYN_PREFIX))
+            .new_fn("write_field")
+            .generic(format!("W: {}Writer", CRATE_SYN_PREFIX))
+            .arg_ref_self()
+            .arg(name_arg, "&str")
+            .arg(writer_arg, "&mut W")
+            .ret("Option<Result<(), W::Error>>");
+
+        match r#type {
+            Rust::Struct { fields, .. } => {
+                body.push_block({
+                    let mut match_block = Block::new("match name");
+                    for field in fields {
+                        match_block.line(format!(
+                            "\"{}\" => Some(AsnDef{}::write_value(writer, &self.{})),",
+                            field.name().trim_start_matches("r#"),
+                            Self::combined_field_type_name(name, field.name()),
+                            field.name(),
+                        ));
+                    }
+                    match_block.line("_ => None,");
+                    match_block
+                });
+            }
+            Rust::DataEnum(choice) => {
+                body.push_block({
+                    let mut match_block = Block::new("match (self, name)");
+                    for variant in choice.variants() {
+                        match_block.line(format!(
+                            "(Self::{}(value), \"{}\") => Some(AsnDef{}::write_value(writer, value)),",
+                            variant.name(),
+                            variant.name(),
+                            Self::combined_field_type_name(name, variant.name()),
+                        ));
+                    }
+                    match_block.line("_ => None,");
+                    match_block
+                });
+            }
+            Rust::TupleStruct { .. } | Rust::Enum(_) => {
+                body.line("None");
+            }
+        }
+    }
+
+    /// The statement that visits the value of the given type behind the given reference
+    fn visit_value(r#type: &RustType, value: &str, depth: usize) -> String {
+        match r#type {
+            RustType::Bool => format!("visitor.visit_boolean(path, *{});", value),
+            RustType::I8(_)
+            | RustType::U8(_)
+            | RustType::I16(_)
+            | RustType::U16(_)
+            | RustType::I32(_)
+            | RustType::U32(_)
+            | RustType::I64(_)
+            | RustType::U64(_) => format!("visitor.visit_integer(path, i128::from(*{}));", value),
+            RustType::F32 => format!("visitor.visit_real(path, f64::from(*{}));", value),
+            RustType::F64 => format!("visitor.visit_real(path, *{});", value),
+            RustType::ObjectIdentifier => {
+                format!("visitor.visit_object_identifier(path, {});", value)
+            }
+            RustType::RelativeOid => format!("visitor.visit_relative_oid(path, {});", value),
+            RustType::OidIri => format!("visitor.visit_oid_iri(path, {});", value),
+            RustType::String(..) => format!("visitor.visit_string(path, {});", value),
+            RustType::VecU8(_) => format!("visitor.visit_octet_string(path, {});", value),
+            RustType::BitVec(_) => format!("visitor.visit_bit_string(path, {});", value),
+            RustType::Null => "visitor.visit_null(path);".to_string(),
+            RustType::Containing(inner, _) | RustType::Default(inner, _) => {
+                Self::visit_value(inner, value, depth)
+            }
+            RustType::Option(inner) => format!(
+                "match {} {{ Some({}) => {{ {} }} None => visitor.visit_absent(path), }}",
+                value,
+                Self::binding(inner, &format!("value{}", depth)),
+                Self::visit_value(inner, &format!("value{}", depth), depth + 1),
+            ),
+            RustType::Vec(inner, ..) => format!(
+                "for (index{depth}, {}) in <[_]>::iter({}).enumerate() {{ path.push({}visit::Segment::Index(index{depth})); {} path.pop(); }}",
+                Self::binding(inner, &format!("value{}", depth)),
+                value,
+                CRATE_SYN_PREFIX,
+                Self::visit_value(inner, &format!("value{}", depth), depth + 1),
+            ),
+            RustType::Complex(..) => format!(
+                "{}visit::Visitable::accept_at({}, path, visitor);",
+                CRATE_SYN_PREFIX, value
+            ),
+            RustType::Substituted(..) => format!("visitor.visit_substituted(path, {});", value),
+        }
+    }
+
+    /// The name to bind a value of the given type to, `_` if visiting it does not need the value
+    fn binding(r#type: &RustType, name: &str) -> String {
+        match r#type {
+            RustType::Null => "_".to_string(),
+            RustType::Containing(inner, _) | RustType::Default(inner, _) => {
+                Self::binding(inner, name)
+            }
+            _ => name.to_string(),
+        }
+    }
+
+    /// Substitutes are user provided and therefore do not implement `RedactedDebug`, their
+    /// `Debug` implementation is used instead
+    fn is_substituted(r#type: &RustType) -> bool {
+        match r#type {
+            RustType::Substituted(..) => true,
+            RustType::Option(inner)
+            | RustType::Default(inner, _)
+            | RustType::Vec(inner, ..)
+            | RustType::Containing(inner, _) => Self::is_substituted(inner),
+            _ => false,
+        }
+    }
+
     fn write_enumerated_constraint(&self, scope: &mut Scope, name: &str, enumerated: &PlainEnum) {
         Self::write_common_constraint_type(
             scope,
@@ -566,6 +965,10 @@ impl AsnDefWriter {
             scope,
             imp,
             &[
+                format!(
+                    "const VARIANT_NAMES: &'static [&'static str] = &{:?};",
+                    enumerated.variants().collect::<Vec<_>>()
+                ),
                 format!("const NAME: &'static str = \"{}\";", name),
                 format!("const VARIANT_COUNT: u64 = {};", enumerated.len()),
                 format!(
@@ -647,6 +1050,10 @@ impl AsnDefWriter {
             scope,
             imp,
             &[
+                format!(
+                    "const VARIANT_NAMES: &'static [&'static str] = &{:?};",
+                    choice.variants().map(|v| v.name()).collect::<Vec<_>>()
+                ),
                 format!("const NAME: &'static str = \"{}\";", name),
                 format!("const VARIANT_COUNT: u64 = {};", choice.len()),
                 format!(
@@ -678,11 +1085,27 @@ impl AsnDefWriter {
         constraint_type_name: &str,
         r#type: &str,
         range: &Range<Option<T>>,
+        unit: Option<&str>,
+        (definition, field): (&str, &str),
     ) {
         scope.raw(&format!(
             "impl {}numbers::Constraint<{}> for {} {{",
             CRATE_SYN_PREFIX, r#type, constraint_type_name
         ));
+        scope.raw(format!(
+            "const DEFINITION_NAME: Option<&'static str> = Some({:?});",
+            definition
+        ));
+        scope.raw(format!(
+            "const FIELD_NAME: Option<&'static str> = Some({:?});",
+            field.trim_start_matches("r#")
+        ));
+        if let Some(unit) = unit {
+            scope.raw(format!(
+                "const UNIT: Option<&'static str> = Some({:?});",
+                unit
+            ));
+        }
         if let Some(min) = range.min() {
             // scope.raw(&format!("const MIN: Option<{}> = Some({});", r#type, min));
             // scope.raw(&format!("const MIN_I64: Option<i64> = Some({});", min));
@@ -699,7 +1122,7 @@ impl AsnDefWriter {
         scope.raw("}");
     }
 
-    fn constraint_type_name(name: &str, field: &str) -> String {
+    pub(crate) fn constraint_type_name(name: &str, field: &str) -> String {
         let combined = Self::combined_field_type_name(name, field) + "Constraint";
         Self::constraint_impl_name(&combined)
     }
@@ -731,6 +1154,13 @@ impl AsnDefWriter {
         scope.raw("}");
     }
 
+    fn write_tag_only_constraint(module: &str, scope: &mut Scope, constraint_type_name: &str) {
+        scope.raw(format!(
+            "impl {}{}::Constraint for {} {{}}",
+            CRATE_SYN_PREFIX, module, constraint_type_name
+        ));
+    }
+
     fn write_default_constraint(
         scope: &mut Scope,
         constraint_type_name: &str,
@@ -746,6 +1176,23 @@ impl AsnDefWriter {
         let (owned, borrowed, default) = match r#type.as_no_option() {
             RustType::Option(_) => unreachable!(),
             RustType::Default(..) => panic!("Nested default detected"),
+            t if matches!(default, LiteralValue::Reference(..)) => {
+                // the referenced constant or static might need to be dropped or be lazy, so
+                // that a reference to it cannot be returned directly
+                let name = t.to_string();
+                scope.raw(format!("type Owned = {};", name));
+                scope.raw(format!("type Borrowed = {};", name));
+                scope.raw("fn default_value() -> &'static Self::Borrowed {");
+                scope.raw(format!(
+                    "static VALUE: std::sync::LazyLock<{}> = std::sync::LazyLock::new(|| {}.clone());",
+                    name,
+                    default.as_rust_const_literal(false)
+                ));
+                scope.raw("&VALUE");
+                scope.raw("}");
+                scope.raw("}");
+                return;
+            }
             RustType::Complex(name, _tag)
                 if !matches!(default, LiteralValue::EnumeratedVariant(..)) =>
             {
@@ -791,7 +1238,7 @@ impl AsnDefWriter {
         scope.raw(&format!("type Owned = {};", owned));
         scope.raw(&format!("type Borrowed = {};", borrowed));
         scope.raw(&format!(
-            "const DEFAULT_VALUE: &'static Self::Borrowed = &{};",
+            "fn default_value() -> &'static Self::Borrowed {{ &{} }}",
             default
         ));
         scope.raw("}");
@@ -808,6 +1255,13 @@ impl AsnDefWriter {
             scope,
             imp,
             &[
+                format!(
+                    "const FIELD_NAMES: &'static [&'static str] = &{:?};",
+                    fields
+                        .iter()
+                        .map(|f| f.name().trim_start_matches("r#"))
+                        .collect::<Vec<_>>()
+                ),
                 format!(
                     "const EXTENDED_AFTER_FIELD: Option<u64> = {:?};",
                     extension_after_field
@@ -905,6 +1359,10 @@ impl AsnDefWriter {
             Self.write_constraints(&mut scope, definition);
             Self.impl_readable(&mut scope, &definition.0);
             Self.impl_writable(&mut scope, &definition.0);
+            Self.impl_der_fns(&mut scope, &definition.0);
+            Self.impl_redacted_debug(&mut scope, definition);
+            Self.impl_visitable(&mut scope, definition);
+            Self.impl_writable_field(&mut scope, definition);
         }
 
         scope.to_string()
@@ -1115,6 +1573,7 @@ pub(crate) mod tests {
                 const STD_OPTIONAL_FIELDS: u64 = 2;
                 const FIELD_COUNT: u64 = 3;
                 const EXTENDED_AFTER_FIELD: Option<u64> = None;
+                const FIELD_NAMES: &'static [&'static str] = &["name", "opt", "some"];
                 
                 #[inline]
                 fn read_seq<R: ::asn1rs::syn::Reader>(reader: &mut R) -> Result<Self, R::Error>
@@ -1211,6 +1670,7 @@ pub(crate) mod tests {
                 const STD_OPTIONAL_FIELDS: u64 = 1;
                 const FIELD_COUNT: u64 = 3;
                 const EXTENDED_AFTER_FIELD: Option<u64> = Some(1);
+                const FIELD_NAMES: &'static [&'static str] = &["name", "opt", "some"];
 
                 #[inline]
                 fn read_seq<R: ::asn1rs::syn::Reader>(reader: &mut R) -> Result<Self, R::Error>
@@ -0,0 +1,267 @@
+//! The layout of the UPER encodings of the fields and variants, derived from their constraints -
+//! as reference for the engineers that implement the other side of an interface. Generated into
+//! the doc comments of the fields and variants by
+//! [`RustCodeGenerator::set_encoding_docs`](crate::generate::rust::RustCodeGenerator::set_encoding_docs).
+//!
+//! ```rust
+//! use asn1rs_model::asn::{Range, Size};
+//! use asn1rs_model::layout::uper_layout;
+//! use asn1rs_model::rust::RustType;
+//!
+//! assert_eq!(
+//!     "7-bit constrained integer, offset 0..=100",
+//!     uper_layout(&RustType::U8(Range::inclusive(0, 100)))
+//! );
+//! assert_eq!("length-determined octet string", uper_layout(&RustType::VecU8(Size::Any)));
+//! ```
+
+use crate::asn::{Charset, Size};
+use crate::footprint::width;
+use crate::rust::{DataEnum, Field, RustType};
+use std::convert::TryFrom;
+
+/// The layout of the UPER encoding of a value of the type
+pub fn uper_layout(r#type: &RustType) -> String {
+    match r#type {
+        RustType::Bool => "1-bit boolean".to_string(),
+        RustType::I8(range) => integer(*range.min(), *range.max(), range.extensible()),
+        RustType::U8(range) => integer(*range.min(), *range.max(), range.extensible()),
+        RustType::I16(range) => integer(*range.min(), *range.max(), range.extensible()),
+        RustType::U16(range) => integer(*range.min(), *range.max(), range.extensible()),
+        RustType::I32(range) => integer(*range.min(), *range.max(), range.extensible()),
+        RustType::U32(range) => integer(*range.min(), *range.max(), range.extensible()),
+        RustType::I64(range) => integer(*range.min(), *range.max(), range.extensible()),
+        RustType::U64(range) => match (range.min(), range.max()) {
+            (None, None) if !range.extensible() => {
+                "length-determined unconstrained integer".to_string()
+            }
+            (min, max) => integer(
+                min.unwrap_or(0),
+                max.unwrap_or(i64::MAX as u64),
+                range.extensible(),
+            ),
+        },
+        RustType::F32 | RustType::F64 => "length-determined REAL, binary encoded".to_string(),
+        RustType::ObjectIdentifier => "length-determined OBJECT IDENTIFIER".to_string(),
+        RustType::RelativeOid => "length-determined RELATIVE-OID".to_string(),
+        RustType::OidIri => "length-determined OID-IRI".to_string(),
+        RustType::String(_, charset @ (Charset::Utf8 | Charset::Teletex)) => {
+            // not a known-multiplier character string type, the SIZE does not constrain the
+            // length determinant of the octets
+            format!("length-determined {}", charset)
+        }
+        RustType::String(size, charset) => sized(
+            size,
+            &format!(
+                "{} of {}-bit characters",
+                charset,
+                match charset {
+                    Charset::Numeric => 4,
+                    Charset::Bmp => 16,
+                    _ => 7,
+                }
+            ),
+        ),
+        RustType::VecU8(size) => sized(size, "octet string"),
+        RustType::BitVec(size) => sized(size, "bit string"),
+        RustType::Vec(inner, size, _) => {
+            format!(
+                "{}, each element: {}",
+                sized(size, "list"),
+                uper_layout(inner)
+            )
+        }
+        RustType::Containing(inner, None) => format!(
+            "length-determined octet string, containing: {}",
+            uper_layout(inner)
+        ),
+        RustType::Containing(_, Some(_)) => {
+            "length-determined octet string, containing the transformed content".to_string()
+        }
+        RustType::Null => "no bits (NULL)".to_string(),
+        RustType::Option(inner) => {
+            format!("presence bit in the preamble, then: {}", uper_layout(inner))
+        }
+        RustType::Default(inner, _) => format!(
+            "presence bit in the preamble (absent for the default value), then: {}",
+            uper_layout(inner)
+        ),
+        RustType::Complex(name, _) => format!("encoded as `{}`", name),
+        RustType::Substituted(inner, _) => uper_layout(inner),
+    }
+}
+
+/// The layout of the UPER encoding of the field at the given position of a `SEQUENCE` or `SET`
+/// with the given extension marker
+pub fn uper_field_layout(field: &Field, position: usize, extension_after: Option<usize>) -> String {
+    if extension_after.is_some_and(|after| position > after) {
+        let r#type = match field.r#type() {
+            RustType::Option(inner) | RustType::Default(inner, _) => inner,
+            r#type => r#type,
+        };
+        format!(
+            "extension addition, presence bit in the extension bit-field, then an open type of: {}",
+            uper_layout(r#type)
+        )
+    } else {
+        uper_layout(field.r#type())
+    }
+}
+
+/// The layout of the UPER encoding of the variant at the given position of a `CHOICE`
+pub fn uper_variant_layout(choice: &DataEnum, position: usize) -> String {
+    let content = uper_layout(
+        choice
+            .variants()
+            .nth(position)
+            .map_or(&RustType::Null, |v| v.r#type()),
+    );
+    let extension_after = choice.extension_after_index();
+    let root = extension_after.map_or(choice.len(), |after| after + 1);
+    if position < root {
+        let bits = width(u64::try_from(root.saturating_sub(1)).unwrap_or(u64::MAX));
+        format!(
+            "{}{}, then: {}",
+            if extension_after.is_some() {
+                "extension bit, then "
+            } else {
+                ""
+            },
+            if bits == 0 {
+                "no choice index".to_string()
+            } else {
+                format!("{}-bit choice index {}", bits, position)
+            },
+            content
+        )
+    } else {
+        format!(
+            "extension bit, normally small choice index {}, then an open type of: {}",
+            position - root,
+            content
+        )
+    }
+}
+
+fn integer(min: impl Into<i128>, max: impl Into<i128>, extensible: bool) -> String {
+    let (min, max) = (min.into(), max.into());
+    let bits = width(u64::try_from(max - min).unwrap_or(u64::MAX));
+    let root = if bits == 0 {
+        format!("no bits for the only value {}", min)
+    } else {
+        format!("{}-bit constrained integer, offset {}..={}", bits, min, max)
+    };
+    if extensible {
+        format!(
+            "extension bit, then {} - or a length-determined unconstrained integer beyond",
+            root
+        )
+    } else {
+        root
+    }
+}
+
+/// The layout of a string or list with the given `SIZE`
+fn sized(size: &Size, noun: &str) -> String {
+    let root = match size {
+        Size::Any => return format!("length-determined {}", noun),
+        Size::Fix(len, _) => format!("{} of fixed size {}, without length determinant", noun, len),
+        Size::Range(min, max, _) => format!(
+            "{} with {}-bit length determinant, offset {}..={}",
+            noun,
+            width(max.saturating_sub(*min) as u64),
+            min,
+            max
+        ),
+    };
+    if size.extensible() {
+        format!(
+            "extension bit, then {} - or a length-determined {} beyond",
+            root, noun
+        )
+    } else {
+        root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Tokenizer;
+    use crate::rust::Rust;
+    use crate::Model;
+
+    #[test]
+    fn test_layouts() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r"Layout DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Report ::= SEQUENCE {
+                id      INTEGER (0..255),
+                offset  INTEGER (-5..5, ...),
+                count   INTEGER (10..MAX),
+                name    IA5String (SIZE(1..8)),
+                code    NumericString (SIZE(4)),
+                values  SEQUENCE (SIZE(0..4, ...)) OF BOOLEAN,
+                comment UTF8String (SIZE(1..8)) OPTIONAL,
+                ...,
+                extra   Pick OPTIONAL
+            }
+            Pick ::= CHOICE {
+                a NULL,
+                b OCTET STRING,
+                c BIT STRING (SIZE(16)),
+                ...,
+                d REAL
+            }
+            END",
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let (fields, extension_after) = match &model.definitions[0].1 {
+            Rust::Struct {
+                fields,
+                extension_after,
+                ..
+            } => (fields, *extension_after),
+            _ => unreachable!(),
+        };
+        let layouts = fields
+            .iter()
+            .enumerate()
+            .map(|(position, field)| uper_field_layout(field, position, extension_after))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "8-bit constrained integer, offset 0..=255",
+                "extension bit, then 4-bit constrained integer, offset -5..=5 - or a length-determined unconstrained integer beyond",
+                "63-bit constrained integer, offset 10..=9223372036854775807",
+                "IA5String of 7-bit characters with 3-bit length determinant, offset 1..=8",
+                "NumericString of 4-bit characters of fixed size 4, without length determinant",
+                "extension bit, then list with 3-bit length determinant, offset 0..=4 - or a length-determined list beyond, each element: 1-bit boolean",
+                "presence bit in the preamble, then: length-determined UTF8String",
+                "extension addition, presence bit in the extension bit-field, then an open type of: encoded as `Pick`",
+            ],
+            layouts
+        );
+
+        let choice = match &model.definitions[1].1 {
+            Rust::DataEnum(choice) => choice,
+            _ => unreachable!(),
+        };
+        let layouts = (0..choice.len())
+            .map(|position| uper_variant_layout(choice, position))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "extension bit, then 2-bit choice index 0, then: no bits (NULL)",
+                "extension bit, then 2-bit choice index 1, then: length-determined octet string",
+                "extension bit, then 2-bit choice index 2, then: bit string of fixed size 16, without length determinant",
+                "extension bit, normally small choice index 0, then an open type of: length-determined REAL, binary encoded",
+            ],
+            layouts
+        );
+    }
+}
@@ -5,11 +5,17 @@ extern crate strum_macros;
 pub mod protobuf;
 
 pub mod asn;
+pub mod catalog;
+pub mod footprint;
 pub mod generate;
+pub mod layout;
+pub mod lint;
+pub mod overlay;
 pub mod parse;
 pub mod proc_macro;
 pub mod resolve;
 pub mod rust;
+pub mod schema;
 
 mod model;
 
@@ -0,0 +1,260 @@
+//! Reports patterns of resolved [`Model`]s that blow up the size of their PER encoding.
+//!
+//! None of the reported patterns is wrong, but each of them costs octets or bits in every
+//! encoded value. The lints are meant to help authors of specifications to notice them early.
+//!
+//! ```rust
+//! use asn1rs_model::lint::{lint, LintKind};
+//! use asn1rs_model::parse::Tokenizer;
+//! use asn1rs_model::Model;
+//!
+//! let model = Model::try_from(Tokenizer::default().parse(
+//!     r"Basic DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+//!     Tank ::= SEQUENCE {
+//!         fuel INTEGER
+//!     }
+//!     END",
+//! ))
+//! .unwrap()
+//! .try_resolve()
+//! .unwrap();
+//!
+//! let lints = lint(&[model]);
+//! assert_eq!(LintKind::UnboundedInteger, lints[0].kind);
+//! assert_eq!("Basic: Tank.fuel: INTEGER without a lower or upper bound is encoded with a length determinant and at least one octet, even for small values", lints[0].to_string());
+//! ```
+
+use crate::asn::{Asn, Type};
+use crate::model::{Definition, Model};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    pub module: String,
+    pub definition: String,
+    /// The names of the enclosing components and of the component itself, separated by `.`.
+    /// The element of a `SEQUENCE OF` or `SET OF` is named `[]`. `None` for the definition
+    /// itself.
+    pub path: Option<String>,
+    pub kind: LintKind,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LintKind {
+    /// An `INTEGER` without a lower or upper bound is encoded with a length determinant and
+    /// at least one octet, ITU-T X.691 | ISO/IEC 8825-2, 12.2.3 and 12.2.4
+    UnboundedInteger,
+    /// A `SEQUENCE OF` or `SET OF` without an upper `SIZE` bound is encoded with a length
+    /// determinant of up to two octets and fragmented above 16K elements,
+    /// ITU-T X.691 | ISO/IEC 8825-2, 11.9
+    UnboundedSize,
+    /// The element of a `SEQUENCE OF` or `SET OF` is extensible, so every single element is
+    /// encoded with an extension bit
+    ExtensibleElement,
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.module, self.definition)?;
+        if let Some(path) = &self.path {
+            write!(f, ".{path}")?;
+        }
+        write!(f, ": ")?;
+        match self.kind {
+            LintKind::UnboundedInteger => write!(
+                f,
+                "INTEGER without a lower or upper bound is encoded with a length determinant and at least one octet, even for small values"
+            ),
+            LintKind::UnboundedSize => write!(
+                f,
+                "SEQUENCE OF or SET OF without an upper SIZE bound is encoded with a length determinant of up to two octets"
+            ),
+            LintKind::ExtensibleElement => write!(
+                f,
+                "the element of the SEQUENCE OF or SET OF is extensible, every element is encoded with an extension bit"
+            ),
+        }
+    }
+}
+
+/// Reports the PER-inefficient patterns of all definitions of the given models. Type references
+/// are looked up in all of the given models.
+pub fn lint(models: &[Model<Asn>]) -> Vec<Lint> {
+    let types = models
+        .iter()
+        .flat_map(|model| &model.definitions)
+        .map(|Definition(name, asn)| (name.as_str(), &asn.r#type))
+        .collect::<HashMap<_, _>>();
+
+    let mut lints = Vec::new();
+    for model in models {
+        for Definition(name, asn) in &model.definitions {
+            let mut report = |path: Option<String>, kind| {
+                lints.push(Lint {
+                    module: model.name.clone(),
+                    definition: name.clone(),
+                    path,
+                    kind,
+                })
+            };
+            visit(&types, &mut report, None, &asn.r#type);
+        }
+    }
+    lints
+}
+
+fn visit(
+    types: &HashMap<&str, &Type>,
+    report: &mut impl FnMut(Option<String>, LintKind),
+    path: Option<&str>,
+    ty: &Type,
+) {
+    let child = |name: &str| match path {
+        Some(path) => format!("{}.{}", path, name),
+        None => name.to_string(),
+    };
+    match unwrap(ty) {
+        Type::Integer(integer)
+            if integer.range.min().is_none() || integer.range.max().is_none() =>
+        {
+            report(path.map(str::to_string), LintKind::UnboundedInteger);
+        }
+        Type::Sequence(list) | Type::Set(list) => {
+            for field in &list.fields {
+                visit(types, report, Some(&child(&field.name)), &field.role.r#type);
+            }
+        }
+        Type::Choice(choice) => {
+            for variant in choice.variants() {
+                visit(
+                    types,
+                    report,
+                    Some(&child(variant.name())),
+                    variant.r#type(),
+                );
+            }
+        }
+        Type::SequenceOf(inner, size) | Type::SetOf(inner, size) => {
+            if size.max().is_none() {
+                report(path.map(str::to_string), LintKind::UnboundedSize);
+            }
+            if is_extensible(types, inner) {
+                report(Some(child("[]")), LintKind::ExtensibleElement);
+            }
+            visit(types, report, Some(&child("[]")), inner);
+        }
+        _ => {}
+    }
+}
+
+fn is_extensible(types: &HashMap<&str, &Type>, ty: &Type) -> bool {
+    match unwrap(ty) {
+        Type::Integer(integer) => integer.range.extensible(),
+        Type::Sequence(list) | Type::Set(list) => list.extension_after.is_some(),
+        Type::Enumerated(enumerated) => enumerated.is_extensible(),
+        Type::Choice(choice) => choice.is_extensible(),
+        Type::TypeReference(name, _) => types
+            .get(name.as_str())
+            .is_some_and(|ty| is_extensible(types, ty)),
+        _ => false,
+    }
+}
+
+/// Removes the wrappers that do not change how the value is encoded
+fn unwrap(ty: &Type) -> &Type {
+    match ty {
+        Type::Optional(inner) | Type::Default(inner, _) | Type::Substituted(inner, _) => {
+            unwrap(inner)
+        }
+        ty => ty,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Tokenizer;
+
+    fn lint_str(asn: &str) -> Vec<(String, Option<String>, LintKind)> {
+        let model = Model::try_from(Tokenizer.parse(asn))
+            .unwrap()
+            .try_resolve()
+            .unwrap();
+        lint(&[model])
+            .into_iter()
+            .map(|lint| (lint.definition, lint.path, lint.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_bounded_definitions_are_fine() {
+        assert!(lint_str(
+            r"Fine DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Small ::= INTEGER (0..255)
+            List ::= SEQUENCE (SIZE(0..8)) OF SEQUENCE { a INTEGER (-5..5), b BOOLEAN }
+            Open ::= SEQUENCE { a INTEGER (0..7), ... }
+            END"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_unbounded_integers() {
+        assert_eq!(
+            vec![
+                ("Plain".to_string(), None, LintKind::UnboundedInteger),
+                (
+                    "Container".to_string(),
+                    Some("counter".to_string()),
+                    LintKind::UnboundedInteger
+                ),
+                (
+                    "Container".to_string(),
+                    Some("choice.offset".to_string()),
+                    LintKind::UnboundedInteger
+                ),
+            ],
+            lint_str(
+                r"Integers DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Plain ::= INTEGER
+                Container ::= SEQUENCE {
+                    counter INTEGER (0..MAX) OPTIONAL,
+                    bounded INTEGER (0..10),
+                    choice CHOICE { offset INTEGER (MIN..10), flag BOOLEAN }
+                }
+                END"
+            )
+        );
+    }
+
+    #[test]
+    fn test_unbounded_and_extensible_collections() {
+        assert_eq!(
+            vec![
+                (
+                    "Points".to_string(),
+                    Some("items".to_string()),
+                    LintKind::UnboundedSize
+                ),
+                (
+                    "Points".to_string(),
+                    Some("items.[]".to_string()),
+                    LintKind::ExtensibleElement
+                ),
+                (
+                    "Kinds".to_string(),
+                    Some("[]".to_string()),
+                    LintKind::ExtensibleElement
+                ),
+            ],
+            lint_str(
+                r"Collections DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Point ::= SEQUENCE { x INTEGER (0..7), ... }
+                Points ::= SEQUENCE { items SEQUENCE OF Point }
+                Kinds ::= SEQUENCE (SIZE(1..4)) OF ENUMERATED { a, b, ... }
+                END"
+            )
+        );
+    }
+}
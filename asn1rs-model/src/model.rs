@@ -1,13 +1,31 @@
-use crate::asn::ObjectIdentifier;
+use crate::asn::{IgnoredAnnotation, InvisibleConstraint, ObjectIdentifier};
 use std::fmt::Debug;
 
+/// An ASN.1 module, or its conversion into a [`Target`] such as [`crate::rust::Rust`].
+///
+/// The fields change shape as the model evolves - such as when the conversion needs to carry
+/// more information - so code that only reads the model, like a custom generator, should prefer
+/// the accessor methods, which are kept stable across these changes.
 #[derive(Debug, Clone)]
 pub struct Model<T: Target> {
     pub name: String,
     pub oid: Option<ObjectIdentifier>,
+    /// Whether the module is declared with `AUTOMATIC TAGS`, so that the components of its
+    /// `SEQUENCE`, `SET` and `CHOICE` types without any tag are tagged by their position
+    pub automatic_tags: bool,
     pub imports: Vec<Import>,
     pub definitions: Vec<Definition<T::DefinitionType>>,
     pub value_references: Vec<ValueReference<T::ValueReferenceType>>,
+    /// Constraints of the ASN.1 module that are not PER-visible, see [`InvisibleConstraint`]
+    pub invisible_constraints: Vec<InvisibleConstraint>,
+    /// Annotation comments of the ASN.1 module that are not applied, see [`IgnoredAnnotation`]
+    pub ignored_annotations: Vec<IgnoredAnnotation>,
+    /// The definitions that were declared inline within another definition, such as the
+    /// `ENUMERATED` type of a `SEQUENCE` component
+    pub inline_definitions: Vec<InlineDefinition>,
+    /// The names of the converted definitions with the names they are declared with in the
+    /// ASN.1 module, only for the definitions that are not declared inline
+    pub asn_names: Vec<(String, String)>,
 }
 
 pub trait Target {
@@ -20,13 +38,63 @@ impl<T: Target> Default for Model<T> {
         Model {
             name: Default::default(),
             oid: None,
+            automatic_tags: false,
             imports: Default::default(),
             definitions: Default::default(),
             value_references: Vec::default(),
+            invisible_constraints: Vec::default(),
+            ignored_annotations: Vec::default(),
+            inline_definitions: Vec::default(),
+            asn_names: Vec::default(),
         }
     }
 }
 
+impl<T: Target> Model<T> {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn oid(&self) -> Option<&ObjectIdentifier> {
+        self.oid.as_ref()
+    }
+
+    pub fn imports(&self) -> &[Import] {
+        &self.imports
+    }
+
+    pub fn definitions(&self) -> &[Definition<T::DefinitionType>] {
+        &self.definitions
+    }
+
+    /// The definition with the given name, which for a converted model is its converted name
+    pub fn definition(&self, name: &str) -> Option<&Definition<T::DefinitionType>> {
+        self.definitions
+            .iter()
+            .find(|definition| definition.name() == name)
+    }
+
+    pub fn value_references(&self) -> &[ValueReference<T::ValueReferenceType>] {
+        &self.value_references
+    }
+
+    /// Whether the definition with the given name was declared inline within another definition
+    pub fn is_inline(&self, name: &str) -> bool {
+        self.inline_definitions
+            .iter()
+            .any(|inline| inline.name == name)
+    }
+
+    /// The name the definition with the given converted name is declared with in the ASN.1
+    /// module, unless it was declared inline
+    pub fn asn_name(&self, name: &str) -> Option<&str> {
+        self.asn_names
+            .iter()
+            .find(|(converted, _)| converted == name)
+            .map(|(_, asn)| asn.as_str())
+    }
+}
+
 #[derive(Debug, Clone, PartialOrd, PartialEq, Eq)]
 pub struct ValueReference<T> {
     pub name: String,
@@ -34,13 +102,40 @@ pub struct ValueReference<T> {
     pub value: LiteralValue,
 }
 
+impl<T> ValueReference<T> {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn role(&self) -> &T {
+        &self.role
+    }
+
+    pub fn value(&self) -> &LiteralValue {
+        &self.value
+    }
+}
+
 #[derive(Debug, Clone, PartialOrd, PartialEq, Eq)]
 pub enum LiteralValue {
     Boolean(bool),
     String(String),
     Integer(i64),
     OctetString(Vec<u8>),
+    /// A bstring or an hstring with an odd number of digits: the bits - MSB-first and
+    /// left-aligned in the octets - and the number of bits
+    BitString(Vec<u8>, u64),
     EnumeratedVariant(String, String),
+    /// The value of a `SEQUENCE` or `SET`: the values of the components by their names
+    Sequence(Vec<(String, LiteralValue)>),
+    /// The value of a `SEQUENCE OF` or `SET OF`
+    SequenceOf(Vec<LiteralValue>),
+    /// The value of a `CHOICE`: the name of the alternative and its value
+    Choice(String, Box<LiteralValue>),
+    /// An identifier within a structured value, which is either the name of a variant of an
+    /// `ENUMERATED` or refers to another value. It is replaced while resolving. In a Rust
+    /// `DEFAULT` value, it is the name of the constant or static holding the value.
+    Reference(String),
 }
 
 impl LiteralValue {
@@ -51,6 +146,23 @@ impl LiteralValue {
             None
         }
     }
+
+    /// The octets and the number of bits of the value of a `BIT STRING`
+    pub fn as_bits(&self) -> Option<(&[u8], u64)> {
+        match self {
+            LiteralValue::OctetString(bytes) => Some((bytes, bytes.len() as u64 * 8)),
+            LiteralValue::BitString(bytes, bit_len) => Some((bytes, *bit_len)),
+            _ => None,
+        }
+    }
+
+    /// Whether this is the value of a `SEQUENCE`, `SET`, `SEQUENCE OF`, `SET OF` or `CHOICE`
+    pub fn is_structured(&self) -> bool {
+        matches!(
+            self,
+            LiteralValue::Sequence(_) | LiteralValue::SequenceOf(_) | LiteralValue::Choice(..)
+        )
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialOrd, PartialEq, Eq)]
@@ -60,11 +172,37 @@ pub struct Import {
     pub from_oid: Option<ObjectIdentifier>,
 }
 
+impl Import {
+    /// The names of the imported definitions and values
+    pub fn what(&self) -> &[String] {
+        &self.what
+    }
+
+    /// The name of the module imported from
+    pub fn from(&self) -> &str {
+        &self.from
+    }
+
+    pub fn from_oid(&self) -> Option<&ObjectIdentifier> {
+        self.from_oid.as_ref()
+    }
+}
+
+/// A definition that was declared inline as the type of a component or alternative of another
+/// definition, see [`Model::inline_definitions`]
+#[derive(Debug, Default, Clone, PartialOrd, PartialEq, Eq)]
+pub struct InlineDefinition {
+    pub name: String,
+    /// The name of the definition it is declared in
+    pub parent: String,
+    /// The name of the component or alternative it is the type of, named like a type
+    pub component: String,
+}
+
 #[derive(Debug, Clone, PartialOrd, PartialEq, Eq)]
 pub struct Definition<T>(pub String, pub T);
 
 impl<T> Definition<T> {
-    #[cfg(test)]
     pub fn new<I: ToString>(name: I, value: T) -> Self {
         Definition(name.to_string(), value)
     }
@@ -84,17 +222,35 @@ pub struct Field<T> {
     pub role: T,
 }
 
+impl<T> Field<T> {
+    pub fn new<I: ToString>(name: I, role: T) -> Self {
+        Field {
+            name: name.to_string(),
+            role,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn role(&self) -> &T {
+        &self.role
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
-    use crate::asn::ObjectIdentifierComponent;
+    use crate::asn::{Asn, ObjectIdentifierComponent};
     use crate::asn::{BitString, Choice, ChoiceVariant, Enumerated, EnumeratedVariant, Integer};
-    use crate::asn::{Charset, Range, TagProperty};
+    use crate::asn::{Charset, PresenceConstraint, Range, TagProperty};
+    use crate::asn::{IgnoredAnnotation, IgnoredAnnotationKind};
     use crate::asn::{Size, Tag, Type};
     use crate::parse::Error;
     use crate::parse::Location;
     use crate::parse::Token;
     use crate::parse::Tokenizer;
-    use crate::resolve::Resolved;
+    use crate::resolve::{LitOrRef, ResolveState, Resolved};
     use crate::rust::Rust;
 
     use super::*;
@@ -445,6 +601,28 @@ pub(crate) mod tests {
         );
     }
 
+    #[test]
+    fn test_accessors() {
+        let model = Model::try_from(Tokenizer::default().parse(INLINE_ASN_WITH_ENUM))
+            .unwrap()
+            .try_resolve()
+            .unwrap();
+
+        assert_eq!("SimpleSchema", model.name());
+        assert_eq!(None, model.oid());
+        assert!(model.imports().is_empty());
+        assert_eq!(1, model.definitions().len());
+        assert_eq!(None, model.definition("Decision"));
+
+        let rust = model.to_rust();
+        assert_eq!(Some("Woah"), rust.definition("Woah").map(Definition::name));
+        assert!(rust.definition("WoahDecision").is_some());
+        assert!(rust.is_inline("WoahDecision"));
+        assert!(!rust.is_inline("Woah"));
+        assert_eq!(Some("Woah"), rust.asn_name("Woah"));
+        assert_eq!(None, rust.asn_name("WoahDecision"));
+    }
+
     #[test]
     fn test_nice_names() {
         let mut model = Model::default();
@@ -865,6 +1043,62 @@ pub(crate) mod tests {
         );
     }
 
+    #[test]
+    pub fn test_parsing_of_extensibility_implied() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r"SimpleSchema DEFINITIONS AUTOMATIC TAGS EXTENSIBILITY IMPLIED ::=
+            BEGIN
+
+            Outer ::= SEQUENCE {
+                inner SEQUENCE {
+                    abc BOOLEAN
+                },
+                kind ENUMERATED { a, b, ..., c }
+            }
+
+            Variants ::= CHOICE {
+                abc UTF8String,
+                def UTF8String
+            }
+
+            Empty ::= SEQUENCE {}
+
+            END
+        ",
+        ))
+        .expect("Failed to parse")
+        .try_resolve()
+        .expect("Failed to resolve");
+
+        let extension_after = |r#type: &Type| match r#type {
+            Type::Sequence(components) => components.extension_after,
+            Type::Choice(choice) => choice.extension_after_index(),
+            Type::Enumerated(enumerated) => enumerated.extension_after_index(),
+            other => panic!("Unexpected type {:?}", other),
+        };
+        let outer = &model.definitions[0].1.r#type;
+        let outer_fields = match outer {
+            Type::Sequence(components) => &components.fields,
+            other => panic!("Unexpected type {:?}", other),
+        };
+
+        assert_eq!(Some(1), extension_after(outer));
+        assert_eq!(Some(0), extension_after(&outer_fields[0].role.r#type));
+        // the explicit extension marker is kept
+        assert_eq!(Some(1), extension_after(&outer_fields[1].role.r#type));
+        assert_eq!(Some(1), extension_after(&model.definitions[1].1.r#type));
+        assert_eq!(Some(0), extension_after(&model.definitions[2].1.r#type));
+    }
+
+    #[test]
+    pub fn test_parsing_of_invalid_extensibility_clause() {
+        assert!(Model::try_from(
+            Tokenizer::default()
+                .parse("SimpleSchema DEFINITIONS EXTENSIBILITY EXPLICIT ::= BEGIN END")
+        )
+        .is_err());
+    }
+
     #[test]
     pub fn test_parsing_module_definition_oid() {
         let model = Model::try_from(Tokenizer::default().parse(
@@ -1198,7 +1432,7 @@ pub(crate) mod tests {
                     constants: Vec::default()
                 })
                 .untagged(),
-                value: LiteralValue::OctetString(vec![0x09])
+                value: LiteralValue::BitString(vec![0x90], 4)
             },
             model.value_references[1]
         );
@@ -1224,6 +1458,190 @@ pub(crate) mod tests {
         )
     }
 
+    #[test]
+    pub fn test_octet_string_containing() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r"SomeName DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+
+                Plain ::= OCTET STRING (CONTAINING Inner)
+                Compressed ::= OCTET STRING (CONTAINING Inner ENCODED BY deflate)
+
+                END",
+        ))
+        .expect("Failed to load model");
+        assert_eq!(
+            &[
+                Definition(
+                    "Plain".to_string(),
+                    Type::Containing(Box::new(Type::TypeReference("Inner".into(), None)), None)
+                        .untagged()
+                ),
+                Definition(
+                    "Compressed".to_string(),
+                    Type::Containing(
+                        Box::new(Type::TypeReference("Inner".into(), None)),
+                        Some("deflate".to_string())
+                    )
+                    .untagged()
+                ),
+            ],
+            &model.definitions[..]
+        )
+    }
+
+    #[test]
+    pub fn test_sensitive_annotations() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r"SomeName DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+
+                Vehicle ::= SEQUENCE {
+                    speed INTEGER (0..255), -- @sensitive
+                    -- @sensitive
+                    vin IA5String (SIZE(17)) OPTIONAL,
+                    owner SEQUENCE OF SEQUENCE {
+                        name UTF8String, -- @sensitive
+                        age INTEGER -- @unknown
+                    },
+                    position CHOICE {
+                        exact SEQUENCE {
+                            lat INTEGER, -- @sensitive
+                            lon INTEGER
+                        },
+                        -- @sensitive
+                        area UTF8String,
+                        none NULL
+                    }
+                }
+
+                END",
+        ))
+        .expect("Failed to load model");
+
+        let fields = match &model.definitions[0].value().r#type {
+            Type::Sequence(sequence) => &sequence.fields,
+            _ => panic!("Expected SEQUENCE"),
+        };
+        let sensitive = |name: &str| {
+            fields
+                .iter()
+                .find(|f| f.name == name)
+                .unwrap()
+                .role
+                .sensitive
+        };
+        assert!(sensitive("speed"));
+        assert!(sensitive("vin"));
+        assert!(!sensitive("owner"));
+        assert!(!sensitive("position"));
+
+        match &fields[2].role.r#type {
+            Type::SequenceOf(inner, _) => match inner.as_ref() {
+                Type::Sequence(owner) => {
+                    assert!(owner.fields[0].role.sensitive);
+                    assert!(!owner.fields[1].role.sensitive);
+                }
+                _ => panic!("Expected SEQUENCE"),
+            },
+            _ => panic!("Expected SEQUENCE OF"),
+        }
+
+        match &fields[3].role.r#type {
+            Type::Choice(choice) => match &choice.variants().next().unwrap().r#type {
+                Type::Sequence(exact) => {
+                    assert!(exact.fields[0].role.sensitive);
+                    assert!(!exact.fields[1].role.sensitive);
+                }
+                _ => panic!("Expected SEQUENCE"),
+            },
+            _ => panic!("Expected CHOICE"),
+        }
+
+        match &fields[3].role.r#type {
+            Type::Choice(choice) => {
+                let sensitive = choice.variants().map(|v| v.sensitive).collect::<Vec<_>>();
+                assert_eq!(vec![false, true, false], sensitive);
+            }
+            _ => panic!("Expected CHOICE"),
+        }
+
+        assert_eq!(
+            vec![IgnoredAnnotation {
+                location: Location::at(9, 37),
+                text: "unknown".to_string(),
+                kind: IgnoredAnnotationKind::Unknown,
+            }],
+            model.ignored_annotations
+        );
+    }
+
+    #[test]
+    pub fn test_ignored_annotations() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r"SomeName DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+
+                -- @sensitive
+                Position ::= SEQUENCE {
+                    lat INTEGER, -- @sensitve
+                    lon INTEGER
+                } -- @unit cm
+
+                Area ::= CHOICE {
+                    circle INTEGER, -- @unit m
+                    none NULL
+                }
+
+                END",
+        ))
+        .expect("Failed to load model");
+
+        let ignored = |line, column, text: &str, kind| IgnoredAnnotation {
+            location: Location::at(line, column),
+            text: text.to_string(),
+            kind,
+        };
+        assert_eq!(
+            vec![
+                ignored(3, 17, "sensitive", IgnoredAnnotationKind::NoComponent),
+                ignored(5, 34, "sensitve", IgnoredAnnotationKind::Unknown),
+                ignored(7, 19, "unit cm", IgnoredAnnotationKind::NoComponent),
+                ignored(10, 37, "unit m", IgnoredAnnotationKind::NotApplicable),
+            ],
+            model.ignored_annotations
+        );
+        assert_eq!(
+            "line 10, column 37: The annotation `@unit m` cannot be applied to the component",
+            model.ignored_annotations[3].to_string()
+        );
+    }
+
+    #[test]
+    pub fn test_unit_annotations() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r"SomeName DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+
+                Position ::= SEQUENCE {
+                    -- @unit 0.1 microdegree
+                    lat INTEGER (-900000000..900000001),
+                    lon INTEGER (-1800000000..1800000001), -- @unit 0.1 microdegree
+                    alt INTEGER, -- @unit
+                    heading INTEGER (0..3601) -- @sensitive
+                }
+
+                END",
+        ))
+        .expect("Failed to load model");
+
+        let fields = match &model.definitions[0].value().r#type {
+            Type::Sequence(sequence) => &sequence.fields,
+            _ => panic!("Expected SEQUENCE"),
+        };
+        assert_eq!(Some("0.1 microdegree"), fields[0].role.unit.as_deref());
+        assert_eq!(Some("0.1 microdegree"), fields[1].role.unit.as_deref());
+        assert_eq!(None, fields[2].role.unit);
+        assert_eq!(None, fields[3].role.unit);
+        assert!(fields[3].role.sensitive);
+    }
+
     #[test]
     pub fn test_value_reference_string() {
         let model = Model::try_from(Tokenizer::default().parse(
@@ -1252,6 +1670,270 @@ pub(crate) mod tests {
         );
     }
 
+    #[test]
+    pub fn test_value_reference_string_escapes() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"SomeName DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+
+                quoted UTF8String ::= "say ""hi"" -- twice"
+                folded UTF8String ::= "first line,  
+                    second line"
+                concatenated UTF8String ::= "con" "cat"
+                    "enated"
+                empty UTF8String ::= ""
+                hex OCTET STRING ::= '0A 0B
+                    0C'H
+                bits BIT STRING ::= '1010 0101'B
+
+                END"#,
+        ))
+        .expect("Failed to load model");
+        assert_eq!(
+            vec![
+                LiteralValue::String(r#"say "hi" -- twice"#.to_string()),
+                LiteralValue::String("first line,second line".to_string()),
+                LiteralValue::String("concatenated".to_string()),
+                LiteralValue::String(String::new()),
+                LiteralValue::OctetString(vec![0x0A, 0x0B, 0x0C]),
+            ],
+            model.value_references[..5]
+                .iter()
+                .map(|vref| vref.value.clone())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(&LiteralValue::try_from_asn_str("'10100101'B").unwrap()),
+            model.value_references.get(5).map(|vref| &vref.value)
+        );
+    }
+
+    #[test]
+    pub fn test_value_reference_structured_values() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"SomeName DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+
+                Mode ::= ENUMERATED { fast, safe }
+                Pair ::= SEQUENCE { mode Mode, count INTEGER }
+                Pairs ::= SEQUENCE OF Pair
+                Either ::= CHOICE { pair Pair, nothing BOOLEAN }
+
+                two INTEGER ::= 2
+                pair Pair ::= { mode safe, count two }
+                pairs Pairs ::= { pair, { mode fast, count 1 } }
+                none Pairs ::= { }
+                either Either ::= pair : { mode fast, count 3 }
+
+                END"#,
+        ))
+        .expect("Failed to load model");
+        assert_eq!(
+            vec![
+                LiteralValue::Integer(2),
+                LiteralValue::Sequence(vec![
+                    (
+                        "mode".to_string(),
+                        LiteralValue::Reference("safe".to_string())
+                    ),
+                    (
+                        "count".to_string(),
+                        LiteralValue::Reference("two".to_string())
+                    ),
+                ]),
+                LiteralValue::SequenceOf(vec![
+                    LiteralValue::Reference("pair".to_string()),
+                    LiteralValue::Sequence(vec![
+                        (
+                            "mode".to_string(),
+                            LiteralValue::Reference("fast".to_string())
+                        ),
+                        ("count".to_string(), LiteralValue::Integer(1)),
+                    ]),
+                ]),
+                LiteralValue::Sequence(Vec::new()),
+                LiteralValue::Choice(
+                    "pair".to_string(),
+                    Box::new(LiteralValue::Sequence(vec![
+                        (
+                            "mode".to_string(),
+                            LiteralValue::Reference("fast".to_string())
+                        ),
+                        ("count".to_string(), LiteralValue::Integer(3)),
+                    ])),
+                ),
+            ],
+            model
+                .value_references
+                .iter()
+                .map(|vref| vref.value.clone())
+                .collect::<Vec<_>>()
+        );
+
+        let resolved = model.try_resolve().expect("Failed to resolve model");
+        let pair = LiteralValue::Sequence(vec![
+            (
+                "mode".to_string(),
+                LiteralValue::EnumeratedVariant("Mode".to_string(), "safe".to_string()),
+            ),
+            ("count".to_string(), LiteralValue::Integer(2)),
+        ]);
+        assert_eq!(pair, resolved.value_references[1].value);
+        assert_eq!(
+            LiteralValue::SequenceOf(vec![
+                pair,
+                LiteralValue::Sequence(vec![
+                    (
+                        "mode".to_string(),
+                        LiteralValue::EnumeratedVariant("Mode".to_string(), "fast".to_string()),
+                    ),
+                    ("count".to_string(), LiteralValue::Integer(1)),
+                ]),
+            ]),
+            resolved.value_references[2].value
+        );
+        assert_eq!(
+            LiteralValue::SequenceOf(Vec::new()),
+            resolved.value_references[3].value
+        );
+    }
+
+    #[test]
+    pub fn test_value_reference_with_unknown_component() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"SomeName DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+
+                Pair ::= SEQUENCE { count INTEGER }
+                pair Pair ::= { amount 1 }
+
+                END"#,
+        ))
+        .expect("Failed to load model");
+        assert_eq!(
+            Err(crate::resolve::Error::FailedToResolveReference(
+                "amount".to_string()
+            )),
+            model.try_resolve().map(drop)
+        );
+    }
+
+    #[test]
+    pub fn test_default_structured_values() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"SomeName DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+
+                Mode ::= ENUMERATED { fast, safe }
+                Pair ::= SEQUENCE { mode Mode, count INTEGER }
+                safeMode Mode ::= safe
+
+                Container ::= SEQUENCE {
+                    mode  Mode DEFAULT safeMode,
+                    pair  Pair DEFAULT { mode fast, count 1 },
+                    pairs SEQUENCE OF Pair DEFAULT { }
+                }
+
+                END"#,
+        ))
+        .expect("Failed to load model");
+        fn defaults<RS: ResolveState>(model: &Model<Asn<RS>>) -> Vec<Option<RS::ConstType>> {
+            match &model.definitions[2].1.r#type {
+                Type::Sequence(sequence) => sequence
+                    .fields
+                    .iter()
+                    .map(|field| field.role.default.clone())
+                    .collect(),
+                _ => panic!("Container is not a SEQUENCE"),
+            }
+        }
+        assert_eq!(
+            vec![
+                Some(LitOrRef::Ref("safeMode".to_string())),
+                Some(LitOrRef::Lit(LiteralValue::Sequence(vec![
+                    (
+                        "mode".to_string(),
+                        LiteralValue::Reference("fast".to_string())
+                    ),
+                    ("count".to_string(), LiteralValue::Integer(1)),
+                ]))),
+                Some(LitOrRef::Lit(LiteralValue::Sequence(Vec::new()))),
+            ],
+            defaults(&model)
+        );
+
+        let resolved = model.try_resolve().expect("Failed to resolve model");
+        assert_eq!(
+            vec![
+                Some(LiteralValue::EnumeratedVariant(
+                    "Mode".to_string(),
+                    "safe".to_string()
+                )),
+                Some(LiteralValue::Sequence(vec![
+                    (
+                        "mode".to_string(),
+                        LiteralValue::EnumeratedVariant("Mode".to_string(), "fast".to_string()),
+                    ),
+                    ("count".to_string(), LiteralValue::Integer(1)),
+                ])),
+                Some(LiteralValue::SequenceOf(Vec::new())),
+            ],
+            defaults(&resolved)
+        );
+    }
+
+    #[test]
+    pub fn test_with_components_are_stored() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r"SomeName DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+
+                Inline ::= SEQUENCE {
+                    a INTEGER OPTIONAL,
+                    b BOOLEAN OPTIONAL
+                } (WITH COMPONENTS { a (0..10) PRESENT, b ABSENT })
+
+                Reference ::= Inline (WITH COMPONENTS { ..., b PRESENT })
+
+                Container ::= SEQUENCE {
+                    decision CHOICE { x BOOLEAN, y NULL } (WITH COMPONENTS { ..., y ABSENT }),
+                    plain Inline
+                }
+
+                END",
+        ))
+        .expect("Failed to load model");
+
+        let components = |asn: &crate::asn::Asn<_>| asn.components.clone().unwrap();
+        let inline = components(model.definitions[0].value());
+        assert!(!inline.is_partial_specification());
+        assert_eq!(vec!["a", "b"], inline.components().collect::<Vec<_>>());
+        assert_eq!(Some("0..10"), inline.value_constraint("a"));
+        assert_eq!(Some(PresenceConstraint::Present), inline.presence("a"));
+        assert_eq!(Some(PresenceConstraint::Absent), inline.presence("b"));
+        assert_eq!(None, inline.presence("c"));
+
+        let reference = components(model.definitions[1].value());
+        assert!(reference.is_partial_specification());
+        assert_eq!(Some(PresenceConstraint::Present), reference.presence("b"));
+        assert_eq!(
+            Type::TypeReference("Inline".to_string(), None),
+            model.definitions[1].value().r#type
+        );
+
+        let fields = match &model.definitions[2].value().r#type {
+            Type::Sequence(sequence) => &sequence.fields,
+            _ => panic!("Expected SEQUENCE"),
+        };
+        assert_eq!(
+            Some(PresenceConstraint::Absent),
+            components(&fields[0].role).presence("y")
+        );
+        assert_eq!(None, fields[1].role.components);
+
+        // not PER-visible and therefore not resolved into the types
+        let resolved = model.try_resolve().unwrap();
+        assert_eq!(
+            Some(inline),
+            resolved.definitions[0].value().components.clone()
+        );
+    }
+
     #[test]
     pub fn test_value_reference_in_size() {
         let model = Model::try_from(Tokenizer::default().parse(
@@ -0,0 +1,264 @@
+//! Merges a patch module into a base module, such as the vendor profile of a standard
+//! specification that adds constraints to or replaces some of its definitions - without editing
+//! the files of the standard.
+//!
+//! The definitions and values of the patch replace the ones of the base module with the same
+//! name, all others are added. What the patch imports from the base module is declared by the
+//! merged module itself. Replacing a definition by one of another type or with another tag is
+//! reported as a conflict, because it changes the encoding of all of its values.
+//!
+//! ```rust
+//! use asn1rs_model::overlay::{overlay, OverlayKind};
+//! use asn1rs_model::parse::Tokenizer;
+//! use asn1rs_model::Model;
+//!
+//! let mut base = Model::try_from(Tokenizer::default().parse(
+//!     r"Standard DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+//!     Speed ::= INTEGER
+//!     Report ::= SEQUENCE { speed Speed }
+//!     END",
+//! ))
+//! .unwrap();
+//!
+//! let patch = Model::try_from(Tokenizer::default().parse(
+//!     r"Vendor DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+//!     IMPORTS Report FROM Standard;
+//!     Speed ::= INTEGER (0..255)
+//!     Reports ::= SEQUENCE OF Report
+//!     END",
+//! ))
+//! .unwrap();
+//!
+//! let diagnostics = overlay(&mut base, patch);
+//! assert_eq!(OverlayKind::Replaced, diagnostics[0].kind);
+//! assert_eq!("Standard: Reports: added by the overlay", diagnostics[1].to_string());
+//! assert!(diagnostics.iter().all(|diagnostic| !diagnostic.is_conflict()));
+//!
+//! let resolved = base.try_resolve().unwrap();
+//! assert_eq!(3, resolved.definitions.len());
+//! ```
+
+use crate::asn::{Asn, Type};
+use crate::model::{Import, Model};
+use crate::resolve::{ResolveState, Unresolved};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverlayDiagnostic {
+    /// The name of the base module
+    pub module: String,
+    /// The name of the definition or value
+    pub name: String,
+    pub kind: OverlayKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OverlayKind {
+    /// The patch declares a definition or value that the base module does not declare
+    Added,
+    /// The patch replaces a definition or value of the base module by one of the same type, such
+    /// as an `INTEGER` by an `INTEGER` with a tighter range
+    Replaced,
+    /// The patch replaces a definition or value of the base module by one of another type or
+    /// with another tag, described by `base` and `patch`
+    Conflict { base: String, patch: String },
+}
+
+impl OverlayDiagnostic {
+    pub fn is_conflict(&self) -> bool {
+        matches!(self.kind, OverlayKind::Conflict { .. })
+    }
+}
+
+impl Display for OverlayDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}: ", self.module, self.name)?;
+        match &self.kind {
+            OverlayKind::Added => write!(f, "added by the overlay"),
+            OverlayKind::Replaced => write!(f, "replaced by the overlay"),
+            OverlayKind::Conflict { base, patch } => {
+                write!(f, "the overlay replaces {base} by {patch}")
+            }
+        }
+    }
+}
+
+/// Merges the patch into the base module, see the [module documentation](self)
+pub fn overlay(
+    base: &mut Model<Asn<Unresolved>>,
+    patch: Model<Asn<Unresolved>>,
+) -> Vec<OverlayDiagnostic> {
+    let module = base.name.clone();
+    let mut diagnostics = Vec::new();
+    let mut diagnose = |name: &str, kind: OverlayKind| {
+        diagnostics.push(OverlayDiagnostic {
+            module: module.clone(),
+            name: name.to_string(),
+            kind,
+        })
+    };
+
+    for definition in patch.definitions {
+        match base
+            .definitions
+            .iter()
+            .position(|existing| existing.name() == definition.name())
+        {
+            Some(index) => {
+                diagnose(
+                    definition.name(),
+                    compare(base.definitions[index].value(), definition.value()),
+                );
+                base.invisible_constraints.retain(|constraint| {
+                    constraint.definition.as_deref() != Some(definition.name())
+                });
+                base.definitions[index] = definition;
+            }
+            None => {
+                diagnose(definition.name(), OverlayKind::Added);
+                base.definitions.push(definition);
+            }
+        }
+    }
+
+    for value in patch.value_references {
+        match base
+            .value_references
+            .iter()
+            .position(|v| v.name == value.name)
+        {
+            Some(index) => {
+                diagnose(
+                    &value.name,
+                    compare(&base.value_references[index].role, &value.role),
+                );
+                base.value_references[index] = value;
+            }
+            None => {
+                diagnose(&value.name, OverlayKind::Added);
+                base.value_references.push(value);
+            }
+        }
+    }
+
+    for import in patch.imports {
+        merge_import(base, import);
+    }
+    base.invisible_constraints
+        .extend(patch.invisible_constraints);
+    base.ignored_annotations.extend(patch.ignored_annotations);
+
+    diagnostics
+}
+
+fn merge_import(base: &mut Model<Asn<Unresolved>>, import: Import) {
+    let from_base = (base.oid.is_some() && base.oid == import.from_oid) || base.name == import.from;
+    if from_base {
+        return;
+    }
+    match base
+        .imports
+        .iter_mut()
+        .find(|existing| existing.from == import.from)
+    {
+        Some(existing) => {
+            for what in import.what {
+                if !existing.what.contains(&what) {
+                    existing.what.push(what);
+                }
+            }
+        }
+        None => base.imports.push(import),
+    }
+}
+
+fn compare(base: &Asn<Unresolved>, patch: &Asn<Unresolved>) -> OverlayKind {
+    let (base, patch) = (describe(base), describe(patch));
+    if base == patch {
+        OverlayKind::Replaced
+    } else {
+        OverlayKind::Conflict { base, patch }
+    }
+}
+
+/// The type of the definition without its constraints, prefixed by its tag if it has one
+fn describe(asn: &Asn<Unresolved>) -> String {
+    match &asn.tag {
+        Some(tag) => format!("{tag:?} {}", describe_type(&asn.r#type)),
+        None => describe_type(&asn.r#type),
+    }
+}
+
+fn describe_type<RS: ResolveState>(ty: &Type<RS>) -> String {
+    match ty {
+        Type::Boolean => "BOOLEAN",
+        Type::Integer(_) => "INTEGER",
+        Type::String(_, charset) => return charset.to_string(),
+        Type::OctetString(_) | Type::Containing(..) => "OCTET STRING",
+        Type::BitString(_) => "BIT STRING",
+        Type::Null => "NULL",
+        Type::Real(_) => "REAL",
+        Type::ObjectIdentifier => "OBJECT IDENTIFIER",
+        Type::RelativeOid => "RELATIVE-OID",
+        Type::OidIri => "OID-IRI",
+        Type::Sequence(_) => "SEQUENCE",
+        Type::SequenceOf(..) => "SEQUENCE OF",
+        Type::Set(_) => "SET",
+        Type::SetOf(..) => "SET OF",
+        Type::Enumerated(_) => "ENUMERATED",
+        Type::Choice(_) => "CHOICE",
+        Type::TypeReference(name, _) => return name.clone(),
+        Type::Optional(inner) | Type::Default(inner, _) | Type::Substituted(inner, _) => {
+            return describe_type(inner)
+        }
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Tokenizer;
+
+    fn parse(asn: &str) -> Model<Asn<Unresolved>> {
+        Model::try_from(Tokenizer::default().parse(asn)).unwrap()
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let mut base = parse(
+            r"Base DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Kind ::= ENUMERATED { a, b }
+            Tagged ::= [APPLICATION 1] BOOLEAN
+            limit INTEGER ::= 5
+            END",
+        );
+        let patch = parse(
+            r"Patch DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            IMPORTS Other FROM Elsewhere;
+            Kind ::= CHOICE { a BOOLEAN }
+            Tagged ::= [APPLICATION 2] BOOLEAN
+            limit INTEGER ::= 7
+            END",
+        );
+
+        let diagnostics = overlay(&mut base, patch);
+        assert_eq!(
+            vec![
+                "Base: Kind: the overlay replaces ENUMERATED by CHOICE",
+                "Base: Tagged: the overlay replaces Application(1) BOOLEAN by Application(2) BOOLEAN",
+                "Base: limit: replaced by the overlay",
+            ],
+            diagnostics
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(3, base.definitions.len() + base.value_references.len());
+        assert!(matches!(
+            base.definitions[0].value().r#type,
+            Type::Choice(_)
+        ));
+        assert_eq!(vec!["Other".to_string()], base.imports[0].what);
+    }
+}
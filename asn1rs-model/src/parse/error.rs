@@ -20,6 +20,12 @@ pub enum ErrorKind {
     InvalidIntText(Token),
     UnsupportedLiteral(Token),
     InvalidLiteral(Token),
+    /// The reference to a parameterized definition and its number of parameters
+    InvalidArgumentCount(Token, usize),
+    ParameterizationTooDeep(Token),
+    /// The reference to a parameterized definition and the name of its instance, which is
+    /// already the name of another definition or instance
+    InstanceNameClash(Token, String),
 }
 
 pub struct Error {
@@ -99,6 +105,22 @@ impl Error {
         ErrorKind::UnsupportedLiteral(token).into()
     }
 
+    pub fn invalid_argument_count(token: Token, expected: usize) -> Self {
+        ErrorKind::InvalidArgumentCount(token, expected).into()
+    }
+
+    pub fn parameterization_too_deep(token: Token) -> Self {
+        ErrorKind::ParameterizationTooDeep(token).into()
+    }
+
+    pub fn instance_name_clash(token: Token, name: String) -> Self {
+        ErrorKind::InstanceNameClash(token, name).into()
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
     fn backtrace(&self) -> &Backtrace {
         &self.backtrace
     }
@@ -120,6 +142,9 @@ impl Error {
             ErrorKind::InvalidIntText(t) => Some(t),
             ErrorKind::UnsupportedLiteral(t) => Some(t),
             ErrorKind::InvalidLiteral(t) => Some(t),
+            ErrorKind::InvalidArgumentCount(t, _) => Some(t),
+            ErrorKind::ParameterizationTooDeep(t) => Some(t),
+            ErrorKind::InstanceNameClash(t, _) => Some(t),
         }
     }
 }
@@ -233,6 +258,29 @@ impl Display for Error {
                 token.location().column(),
                 token
             ),
+            ErrorKind::InvalidArgumentCount(token, expected) => write!(
+                f,
+                "At line {}, column {} the parameterized type {} is referenced without its {} parameters",
+                token.location().line(),
+                token.location().column(),
+                token,
+                expected
+            ),
+            ErrorKind::ParameterizationTooDeep(token) => write!(
+                f,
+                "At line {}, column {} the instances of the parameterized type {} are nested too deeply",
+                token.location().line(),
+                token.location().column(),
+                token
+            ),
+            ErrorKind::InstanceNameClash(token, name) => write!(
+                f,
+                "At line {}, column {} the name {} of the instance of the parameterized type {} is already taken by another definition",
+                token.location().line(),
+                token.location().column(),
+                name,
+                token
+            ),
         }
     }
 }
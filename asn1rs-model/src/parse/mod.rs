@@ -102,6 +102,92 @@ mod tests {
         assert!(iter.next().unwrap().eq_text("None"));
         assert!(iter.next().is_none());
     }
+    #[test]
+    pub fn test_keeps_annotation_comments() {
+        let result = Tokenizer::default().parse(
+            r"
+                Some ::= None -- @sensitive
+                -- @ unit ( cm )
+        ",
+        );
+        let mut iter = result.into_iter();
+        assert!(iter.next().unwrap().eq_text("Some"));
+        assert!(iter.next().unwrap().eq_separator(':'));
+        assert!(iter.next().unwrap().eq_separator(':'));
+        assert!(iter.next().unwrap().eq_separator('='));
+        assert!(iter.next().unwrap().eq_text("None"));
+        assert_eq!(
+            Token::Annotation(Location::at(2, 31), "sensitive".to_string()),
+            iter.next().unwrap()
+        );
+        assert_eq!(Some("unit ( cm )"), iter.next().unwrap().annotation());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    pub fn test_profile_turns_plain_comments_into_annotations() {
+        let asn = r"
+                speed INTEGER -- Units of 0.02 m/s
+                /**
+                 * @unit: 0,1 microdegree
+                 */
+                lat INTEGER -- some other comment
+        ";
+        assert!(Tokenizer
+            .parse(asn)
+            .iter()
+            .all(|t| t.annotation().is_none()));
+
+        let annotations = Tokenizer
+            .parse_with_profile(asn, &crate::asn::AnnotationProfile::etsi())
+            .into_iter()
+            .filter_map(|t| Some((t.location(), t.annotation()?.to_string())))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (Location::at(2, 31), "unit 0.02 m/s".to_string()),
+                (Location::at(4, 18), "unit 0,1 microdegree".to_string()),
+            ],
+            annotations
+        );
+    }
+
+    #[test]
+    pub fn test_cstring_is_kept_as_one_text() {
+        let result = Tokenizer.parse(
+            r#"a ::= "it's -- not /* a */ comment, ""quoted""" b
+            c ::= "spanning   
+                   several
+               lines" """#,
+        );
+        let mut iter = result.into_iter();
+        assert!(iter.next().unwrap().eq_text("a"));
+        assert!(iter.next().unwrap().eq_separator(':'));
+        assert!(iter.next().unwrap().eq_separator(':'));
+        assert!(iter.next().unwrap().eq_separator('='));
+        assert!(iter.next().unwrap().eq_separator('"'));
+        assert_eq!(
+            Token::Text(
+                Location::at(1, 8),
+                r#"it's -- not /* a */ comment, "quoted""#.to_string()
+            ),
+            iter.next().unwrap()
+        );
+        assert!(iter.next().unwrap().eq_separator('"'));
+        assert!(iter.next().unwrap().eq_text("b"));
+        assert!(iter.next().unwrap().eq_text("c"));
+        assert!(iter.next().unwrap().eq_separator(':'));
+        assert!(iter.next().unwrap().eq_separator(':'));
+        assert!(iter.next().unwrap().eq_separator('='));
+        assert!(iter.next().unwrap().eq_separator('"'));
+        assert!(iter.next().unwrap().eq_text("spanningseverallines"));
+        assert!(iter.next().unwrap().eq_separator('"'));
+        assert!(iter.next().unwrap().eq_separator('"'));
+        assert!(iter.next().unwrap().eq_text(""));
+        assert!(iter.next().unwrap().eq_separator('"'));
+        assert!(iter.next().is_none());
+    }
+
     #[test]
     pub fn test_ignores_multiline_comments() {
         let result = Tokenizer::default().parse(
@@ -5,6 +5,8 @@ use std::fmt::{Display, Formatter};
 pub enum Token {
     Text(Location, String),
     Separator(Location, char),
+    /// The content of a single-line comment starting with `@`, such as `-- @sensitive`
+    Annotation(Location, String),
 }
 
 impl From<char> for Token {
@@ -24,6 +26,7 @@ impl Display for Token {
         match self {
             Token::Text(_, text) => write!(f, "\"{}\"", text),
             Token::Separator(_, separator) => write!(f, "\'{}\'", separator),
+            Token::Annotation(_, annotation) => write!(f, "@{}", annotation),
         }
     }
 }
@@ -46,6 +49,7 @@ impl Token {
         match self {
             Token::Text(location, _) => *location,
             Token::Separator(location, _) => *location,
+            Token::Annotation(location, _) => *location,
         }
     }
 
@@ -85,6 +89,13 @@ impl Token {
         self.separator().is_some()
     }
 
+    pub fn annotation(&self) -> Option<&str> {
+        match self {
+            Token::Annotation(_, annotation) => Some(annotation),
+            _ => None,
+        }
+    }
+
     pub fn into_text(self) -> Option<String> {
         if let Token::Text(_, text) = self {
             Some(text)
@@ -1,3 +1,4 @@
+use crate::asn::{Annotation, AnnotationProfile};
 use crate::parse::{Location, Token};
 
 #[derive(Default)]
@@ -10,17 +11,62 @@ impl Tokenizer {
     /// Ignore single-line comments defined with "--".
     /// Ignore multi-line comments defined with /*  */.
     /// Comment terminates when a matching "*/" has been found for each "/*"
+    /// Single-line comments starting with "@" (such as `-- @sensitive`) are kept as
+    /// [`Token::Annotation`].
+    /// The content of a cstring (12.14) is kept as a single [`Token::Text`] between the quote
+    /// separators: a pair of quotes represents a quote and the whitespace around the line breaks
+    /// of a cstring spanning several lines is removed together with these.
     pub fn parse(&self, asn: &str) -> Vec<Token> {
+        self.parse_with_profile(asn, &AnnotationProfile::default())
+    }
+
+    /// Like [`Tokenizer::parse`], but plain single-line comments and lines of block comments
+    /// that match the given [`AnnotationProfile`] are kept as [`Token::Annotation`] as well.
+    pub fn parse_with_profile(&self, asn: &str, profile: &AnnotationProfile) -> Vec<Token> {
         let mut previous = None;
         let mut tokens = Vec::new();
         let mut nest_lvl = 0; // Nest level of the comments
+        let mut cstring: Option<(Location, String)> = None;
 
         for (line_0, line) in asn.lines().enumerate() {
             let mut token = None;
+            let mut annotation = None;
+            let mut block_comment: Option<(usize, String)> = None;
             let mut content_iterator = line.chars().enumerate().peekable();
+            let mut line_start = true;
 
             while let Some((column_0, char)) = content_iterator.next() {
+                if let Some((location, content)) = &mut cstring {
+                    if char != '"' {
+                        if !(line_start && char.is_whitespace()) {
+                            line_start = false;
+                            content.push(char);
+                        }
+                        continue;
+                    } else if let Some((_, '"')) = content_iterator.peek() {
+                        content_iterator.next(); // remove the second '"'
+                        line_start = false;
+                        content.push('"');
+                        continue;
+                    }
+                    if let Some(token) = previous.take() {
+                        tokens.push(token);
+                    }
+                    tokens.push(Token::Text(*location, std::mem::take(content)));
+                    cstring = None;
+                    previous = Some(Token::Separator(
+                        Location::at(line_0 + 1, column_0 + 1),
+                        char,
+                    ));
+                    continue;
+                }
                 if nest_lvl > 0 {
+                    if !profile.is_empty() && (block_comment.is_some() || !char.is_whitespace()) {
+                        block_comment
+                            .get_or_insert_with(|| (column_0, String::new()))
+                            .1
+                            .push(char);
+                    }
                     match char {
                         '*' => {
                             if let Some((_, '/')) = content_iterator.peek() {
@@ -52,6 +98,19 @@ impl Tokenizer {
                     && content_iterator.peek().map(|&(_, ch)| ch) == Some('-')
                 {
                     content_iterator.next(); // remove second '-'
+                    let comment = content_iterator.map(|(_, c)| c).collect::<String>();
+                    let text = match comment.trim().strip_prefix('@') {
+                        Some(text) if Annotation::from_text(text.trim()).is_some() => {
+                            Some(text.trim().to_string())
+                        }
+                        text => profile
+                            .extract(&comment)
+                            .map(|a| a.to_string())
+                            .or_else(|| text.map(|text| text.trim().to_string())),
+                    };
+                    annotation = text.map(|text| {
+                        Token::Annotation(Location::at(line_0 + 1, column_0 + 1), text)
+                    });
                     break; // ignore rest of the line
                 }
                 match char {
@@ -59,9 +118,16 @@ impl Tokenizer {
                         content_iterator.next(); // remove opening '*'
                         nest_lvl += 1;
                     }
+                    '"' => {
+                        cstring = Some((Location::at(line_0 + 1, column_0 + 2), String::new()));
+                        line_start = false;
+                        token = Some(Token::Separator(
+                            Location::at(line_0 + 1, column_0 + 1),
+                            char,
+                        ))
+                    }
                     // asn syntax
-                    ':' | ';' | '=' | '(' | ')' | '{' | '}' | '.' | ',' | '[' | ']' | '\''
-                    | '"' => {
+                    ':' | ';' | '=' | '(' | ')' | '{' | '}' | '.' | ',' | '[' | ']' | '\'' => {
                         token = Some(Token::Separator(
                             Location::at(line_0 + 1, column_0 + 1),
                             char,
@@ -106,12 +172,34 @@ impl Tokenizer {
             if let Some(token) = previous.take() {
                 tokens.push(token);
             }
+
+            if let Some((_location, content)) = &mut cstring {
+                content.truncate(content.trim_end().len());
+            }
+
+            if let Some((column_0, comment)) = block_comment {
+                if let Some(annotation) = profile.extract(comment.trim().trim_matches('*')) {
+                    tokens.push(Token::Annotation(
+                        Location::at(line_0 + 1, column_0 + 1),
+                        annotation.to_string(),
+                    ));
+                }
+            }
+
+            if let Some(annotation) = annotation {
+                tokens.push(annotation);
+            }
         }
 
         if let Some(token) = previous {
             tokens.push(token);
         }
 
+        if let Some((location, content)) = cstring {
+            // the missing closing quote is reported by the parser
+            tokens.push(Token::Text(location, content));
+        }
+
         tokens
     }
 }
@@ -3,7 +3,7 @@ use super::range::ident_or_literal_or_punct;
 use super::range::IntegerRange;
 use super::tag::AttrTag;
 use crate::asn::{Charset, Choice, ChoiceVariant, Enumerated, EnumeratedVariant};
-use crate::asn::{Range, Size, Tag, Type};
+use crate::asn::{Range, RealPrecision, Size, Substitution, Tag, Type};
 use crate::model::LiteralValue;
 use std::fmt::Debug;
 use std::fmt::Display;
@@ -22,6 +22,8 @@ pub(crate) struct AsnAttribute<C: Context> {
     pub(crate) consts: Vec<ConstLit>,
     pub(crate) extensible_after: Option<String>,
     pub(crate) default_value: Option<LiteralValue>,
+    pub(crate) sensitive: bool,
+    pub(crate) unit: Option<String>,
     _c: PhantomData<C>,
 }
 
@@ -33,6 +35,8 @@ impl<C: Context> AsnAttribute<C> {
             consts: Vec::default(),
             extensible_after: None,
             default_value: None,
+            sensitive: false,
+            unit: None,
             _c: Default::default(),
         }
     }
@@ -63,6 +67,14 @@ impl<C: Context> Parse for AsnAttribute<C> {
                         .step(|s| s.ident().ok_or_else(|| content.error("Not a valid ident")))?;
                     asn.extensible_after = Some(ident.to_string());
                 }
+                "sensitive" if C::SENSITIVE && !asn.sensitive => {
+                    asn.sensitive = true;
+                }
+                "unit" if C::UNIT && asn.unit.is_none() => {
+                    let content;
+                    parenthesized!(content in input);
+                    asn.unit = Some(content.parse::<syn::LitStr>()?.value());
+                }
                 "const" if C::CONSTS => {
                     let content;
                     parenthesized!(content in input);
@@ -188,14 +200,17 @@ fn parse_type_pre_stepped<'a>(
                     })
                     .or_else(|| {
                         content.parse::<syn::Path>().ok().and_then(|path| {
-                            if path.segments.len() == 2 {
-                                let mut iter = path.segments.iter();
-                                Some(LiteralValue::EnumeratedVariant(
+                            let mut iter = path.segments.iter();
+                            match path.segments.len() {
+                                // a constant or static holding the value
+                                1 => Some(LiteralValue::Reference(
                                     iter.next().unwrap().ident.to_string(),
+                                )),
+                                2 => Some(LiteralValue::EnumeratedVariant(
                                     iter.next().unwrap().ident.to_string(),
-                                ))
-                            } else {
-                                None
+                                    iter.next().unwrap().ident.to_string(),
+                                )),
+                                _ => None,
                             }
                         })
                     })
@@ -204,8 +219,63 @@ fn parse_type_pre_stepped<'a>(
                     })?,
             ))
         }
+        "containing" => {
+            let content;
+            parenthesized!(content in input);
+            let inner = parse_type(&content)?;
+            let encoded_by = if content.is_empty() {
+                None
+            } else {
+                content.parse::<Token![,]>()?;
+                let ident = parse_ident(&content, "Expected identifier 'encoded_by'")?;
+                if !"encoded_by".eq_ignore_ascii_case(&ident) {
+                    return Err(content.error("Expected identifier 'encoded_by'"));
+                }
+                let encoded_by;
+                parenthesized!(encoded_by in content);
+                let path = encoded_by.parse::<syn::Path>()?;
+                Some(quote::quote! { #path }.to_string())
+            };
+            Ok(Type::Containing(Box::new(inner), encoded_by))
+        }
+        "substituted" => {
+            let content;
+            parenthesized!(content in input);
+            let inner = parse_type(&content)?;
+            content.parse::<Token![,]>()?;
+            let rust_type = content.parse::<syn::Type>()?;
+            let mut substitution = Substitution::new(tokens_to_string(&rust_type));
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+                let ident = parse_ident(&content, "Expected identifier 'via'")?;
+                if !"via".eq_ignore_ascii_case(&ident) {
+                    return Err(content.error("Expected identifier 'via'"));
+                }
+                let via;
+                parenthesized!(via in content);
+                let path = via.parse::<syn::Type>()?;
+                substitution = substitution.with_via(tokens_to_string(&path));
+            }
+            Ok(Type::Substituted(Box::new(inner), substitution))
+        }
         "boolean" => Ok(Type::Boolean),
         "null" => Ok(Type::Null),
+        "object_identifier" => Ok(Type::ObjectIdentifier),
+        "relative_oid" => Ok(Type::RelativeOid),
+        "oid_iri" => Ok(Type::OidIri),
+        "real" => {
+            if input.peek(token::Paren) {
+                let content;
+                parenthesized!(content in input);
+                let precision = parse_ident(&content, "Expected identifier 'single'")?;
+                if !"single".eq_ignore_ascii_case(&precision) {
+                    return Err(content.error("Expected identifier 'single'"));
+                }
+                Ok(Type::Real(RealPrecision::Single))
+            } else {
+                Ok(Type::Real(RealPrecision::Double))
+            }
+        }
         "sequence_of" | "set_of" => {
             let content;
             parenthesized!(content in input);
@@ -238,6 +308,10 @@ fn parse_type_pre_stepped<'a>(
     }
 }
 
+fn tokens_to_string<T: quote::ToTokens>(tokens: &T) -> String {
+    quote::quote! { #tokens }.to_string().replace(" :: ", "::")
+}
+
 fn parse_opt_size_or_any(input: ParseStream) -> syn::Result<Size> {
     if input.is_empty() || !input.peek(token::Paren) {
         Ok(Size::Any)
@@ -320,6 +394,8 @@ pub trait Context: Debug {
     const EXTENSIBLE_AFTER: bool;
     const TAGGABLE: bool;
     const CONSTS: bool;
+    const SENSITIVE: bool;
+    const UNIT: bool;
 }
 
 impl Context for Choice {
@@ -327,6 +403,8 @@ impl Context for Choice {
     const EXTENSIBLE_AFTER: bool = true;
     const TAGGABLE: bool = true;
     const CONSTS: bool = false;
+    const SENSITIVE: bool = false;
+    const UNIT: bool = false;
 }
 
 impl Context for ChoiceVariant {
@@ -334,6 +412,8 @@ impl Context for ChoiceVariant {
     const EXTENSIBLE_AFTER: bool = false;
     const TAGGABLE: bool = true;
     const CONSTS: bool = false;
+    const SENSITIVE: bool = true;
+    const UNIT: bool = false;
 }
 
 impl Context for Enumerated {
@@ -341,6 +421,8 @@ impl Context for Enumerated {
     const EXTENSIBLE_AFTER: bool = true;
     const TAGGABLE: bool = true;
     const CONSTS: bool = false;
+    const SENSITIVE: bool = false;
+    const UNIT: bool = false;
 }
 
 impl Context for EnumeratedVariant {
@@ -348,6 +430,8 @@ impl Context for EnumeratedVariant {
     const EXTENSIBLE_AFTER: bool = false;
     const TAGGABLE: bool = false;
     const CONSTS: bool = false;
+    const SENSITIVE: bool = false;
+    const UNIT: bool = false;
 }
 
 #[derive(Debug)]
@@ -357,6 +441,8 @@ impl Context for Transparent {
     const EXTENSIBLE_AFTER: bool = false;
     const TAGGABLE: bool = true;
     const CONSTS: bool = true;
+    const SENSITIVE: bool = true;
+    const UNIT: bool = true;
 }
 
 #[derive(Debug)]
@@ -366,6 +452,8 @@ impl Context for DefinitionHeader {
     const EXTENSIBLE_AFTER: bool = true;
     const TAGGABLE: bool = true;
     const CONSTS: bool = false;
+    const SENSITIVE: bool = false;
+    const UNIT: bool = false;
 }
 
 impl Deref for DefinitionHeader {
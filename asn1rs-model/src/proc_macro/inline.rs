@@ -1,16 +1,34 @@
+use crate::asn::{MultiModuleResolver, Substitutions};
 use crate::generate::rust::RustCodeGenerator as RustGenerator;
 use crate::generate::Generator;
-use crate::model::Model;
+use crate::model::{Model, Target};
 use crate::parse::Tokenizer;
 
 pub fn asn_to_rust(input: &str) -> String {
+    asn_to_rust_substituted(input, &Substitutions::default(), |_| {})
+}
+
+/// Like [`asn_to_rust`], but replaces the types of the given [`Substitutions`] and lets the
+/// generator be adjusted
+pub fn asn_to_rust_substituted<A: Fn(&mut RustGenerator)>(
+    input: &str,
+    substitutions: &Substitutions,
+    custom_adjustments: A,
+) -> String {
     let tokens = Tokenizer.parse(input);
-    let model = Model::try_from(tokens)
-        .expect("Failed to parse tokens")
+    let model = Model::try_from(tokens).expect("Failed to parse tokens");
+    let errors = ignored_annotation_errors(&model);
+    let mut model = model
         .try_resolve()
         .expect("Failed to resolve value references");
+    substitutions.apply(&mut model);
 
-    let output = RustGenerator::from(model.to_rust())
+    // several modules might be inlined into the same scope, where their definitions fns
+    // collide - unlike the self_test fns, these cannot be renamed
+    let mut generator = RustGenerator::from(model.to_rust());
+    generator.set_registry(false);
+    custom_adjustments(&mut generator);
+    let output = generator
         .to_string()
         .unwrap()
         .into_iter()
@@ -18,11 +36,114 @@ pub fn asn_to_rust(input: &str) -> String {
         .collect::<Vec<_>>()
         .join("\n");
 
+    let output = errors + &output;
+    debug_output(&output);
+    output
+}
+
+/// Resolves the given modules within the same scope - so that `IMPORTS` between them can be
+/// resolved - and emits each of them as `pub mod` with the name the CLI would give its file.
+pub fn asn_to_rust_modules<A: Fn(&mut RustGenerator)>(
+    inputs: &[&str],
+    substitutions: &Substitutions,
+    custom_adjustments: A,
+) -> String {
+    let mut resolver = MultiModuleResolver::default();
+    let mut errors = String::new();
+    for input in inputs {
+        let tokens = Tokenizer.parse(input);
+        let model = Model::try_from(tokens).expect("Failed to parse tokens");
+        errors += &ignored_annotation_errors(&model);
+        resolver.push(model);
+    }
+
+    let mut models = resolver
+        .try_resolve_all()
+        .expect("Failed to resolve value references");
+    models
+        .iter_mut()
+        .for_each(|model| substitutions.apply(model));
+    let scope = models.iter().collect::<Vec<_>>();
+
+    let output = models
+        .iter()
+        .map(|model| {
+            let mut generator = RustGenerator::from(model.to_rust_with_scope(&scope[..]));
+            custom_adjustments(&mut generator);
+            let content = generator
+                .to_string()
+                .unwrap()
+                .into_iter()
+                .map(|(_file, content)| content)
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!(
+                "pub mod {} {{\n{}\n}}",
+                RustGenerator::rust_module_name(&model.name),
+                content
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let output = errors + &output;
+    debug_output(&output);
+    output
+}
+
+/// A `compile_error!` for each annotation that is not applied, so that a misspelled
+/// `-- @sensitve` does not silently leave the value unmasked
+fn ignored_annotation_errors<T: Target>(model: &Model<T>) -> String {
+    model
+        .ignored_annotations
+        .iter()
+        .map(|annotation| {
+            format!(
+                "::core::compile_error!({:?});\n",
+                format!("{}, {}", model.name, annotation)
+            )
+        })
+        .collect()
+}
+
+fn debug_output(output: &str) {
     if cfg!(feature = "debug-proc-macro") {
         println!("-------- output start");
         println!("{}", output);
         println!("-------- output end");
     }
+}
 
-    output
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ignored_annotations_fail_to_compile() {
+        let output = asn_to_rust(
+            r"Annotated DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Person ::= SEQUENCE {
+                name UTF8String -- @sensitve
+            }
+            END",
+        );
+        assert!(output.starts_with(
+            "::core::compile_error!(\"Annotated, line 3, column 33: Unknown annotation `@sensitve`\");\n"
+        ));
+        assert!(output.contains("pub struct Person"));
+    }
+
+    #[test]
+    fn test_applied_annotations_compile() {
+        let output = asn_to_rust(
+            r"Annotated DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Person ::= CHOICE {
+                name UTF8String, -- @sensitive
+                unknown NULL
+            }
+            END",
+        );
+        assert!(!output.contains("compile_error"));
+        assert!(output.contains("sensitive"));
+    }
 }
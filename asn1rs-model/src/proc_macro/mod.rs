@@ -4,6 +4,7 @@ mod inline;
 mod range;
 mod size;
 mod tag;
+mod verify;
 
 use crate::asn::{Choice, ChoiceVariant, Enumerated, EnumeratedVariant};
 use crate::asn::{ComponentTypeList, TagProperty, TagResolver, Type};
@@ -19,7 +20,8 @@ use syn::spanned::Spanned;
 use syn::{Attribute, Item};
 
 use crate::model::{Definition, Field, Model};
-pub use inline::asn_to_rust;
+pub use inline::{asn_to_rust, asn_to_rust_modules, asn_to_rust_substituted};
+pub use verify::verify;
 
 pub type AsnModelType = crate::asn::Asn<Resolved>;
 
@@ -304,6 +306,7 @@ fn parse_choice(
                     name: v.ident.to_string(),
                     tag: asn.tag,
                     r#type: asn.r#type,
+                    sensitive: asn.sensitive,
                 }
             })
         })
@@ -385,6 +388,9 @@ fn into_asn<C: Context<Primary = Type>>(ty: &syn::Type, mut asn: AsnAttribute<C>
             asn.primary
         },
         default: asn.default_value,
+        sensitive: asn.sensitive,
+        unit: asn.unit,
+        components: None,
     }
 }
 
@@ -0,0 +1,374 @@
+use crate::generate::rust::RustCodeGenerator as RustGenerator;
+use crate::model::{Definition, Model};
+use crate::parse::Tokenizer;
+use crate::rust::{rust_struct_or_enum_name, Rust, RustType};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{Fields, GenericArgument, Item, LitStr, PathArguments, Token};
+
+/// Arguments of `#[asn_verify(schema = "..", type = "..")]`. The schema is either the ASN.1
+/// module itself or the path of a file - relative to the directory of the crate manifest -
+/// containing it.
+struct VerifyAttribute {
+    schema: LitStr,
+    r#type: LitStr,
+}
+
+impl Parse for VerifyAttribute {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut schema = None;
+        let mut r#type = None;
+
+        while !input.is_empty() {
+            let key = if input.peek(Token![type]) {
+                input.parse::<Token![type]>()?;
+                "type".to_string()
+            } else {
+                input.parse::<syn::Ident>()?.to_string()
+            };
+            input.parse::<Token![=]>()?;
+            let value = input.parse::<LitStr>()?;
+            match key.as_str() {
+                "schema" => schema = Some(value),
+                "type" => r#type = Some(value),
+                _ => {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        format!("Unknown key `{key}`"),
+                    ))
+                }
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(Self {
+            schema: schema.ok_or_else(|| input.error("Missing `schema = \"..\"`"))?,
+            r#type: r#type.ok_or_else(|| input.error("Missing `type = \"..\"`"))?,
+        })
+    }
+}
+
+/// Checks that the given item structurally matches - names, types and optionality of its fields
+/// or variants - the Rust type that would be generated for the ASN.1 definition. The item is
+/// returned as is, accompanied by a `compile_error!` listing the differences, if there are any.
+/// A schema file is included into the item, so that cargo checks the item again once the file
+/// changes.
+pub fn verify(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut dependency = None;
+    let result = syn::parse2::<VerifyAttribute>(attr).and_then(|attr| {
+        // a missing file is reported by expected_definition already
+        dependency = schema_path(&attr)
+            .filter(|path| path.is_file())
+            .map(|path| {
+                let path = path.display().to_string();
+                quote! { const _: &str = include_str!(#path); }
+            });
+        let item = syn::parse2::<Item>(item.clone())?;
+        let expected = expected_definition(&attr)?;
+        let differences = differences(&expected, &item)?;
+        if differences.is_empty() {
+            Ok(())
+        } else {
+            Err(syn::Error::new(
+                item_span(&item),
+                format!(
+                    "Type does not match the ASN.1 definition `{}` (- expected, + found):\n{}",
+                    attr.r#type.value(),
+                    differences.join("\n")
+                ),
+            ))
+        }
+    });
+
+    let error = result.err().map(|e| e.to_compile_error());
+    quote! {
+        #item
+        #dependency
+        #error
+    }
+}
+
+/// The path of the schema file, `None` if the schema is the ASN.1 module itself
+fn schema_path(attr: &VerifyAttribute) -> Option<std::path::PathBuf> {
+    let schema = attr.schema.value();
+    if schema.contains("DEFINITIONS") {
+        None
+    } else {
+        let mut path = std::env::var("CARGO_MANIFEST_DIR")
+            .map(std::path::PathBuf::from)
+            .unwrap_or_default();
+        path.push(&schema);
+        Some(path)
+    }
+}
+
+fn expected_definition(attr: &VerifyAttribute) -> syn::Result<Rust> {
+    let schema = match schema_path(attr) {
+        None => attr.schema.value(),
+        Some(path) => std::fs::read_to_string(&path).map_err(|e| {
+            syn::Error::new(
+                attr.schema.span(),
+                format!("Failed to read schema {}: {e}", path.display()),
+            )
+        })?,
+    };
+
+    let model = Model::try_from(Tokenizer.parse(&schema))
+        .map_err(|e| syn::Error::new(attr.schema.span(), format!("Invalid schema: {e}")))?
+        .try_resolve()
+        .map_err(|e| syn::Error::new(attr.schema.span(), format!("Invalid schema: {e:?}")))?
+        .to_rust();
+
+    let name = rust_struct_or_enum_name(&attr.r#type.value());
+    model
+        .definitions
+        .into_iter()
+        .find_map(|Definition(n, rust)| if n == name { Some(rust) } else { None })
+        .ok_or_else(|| {
+            syn::Error::new(
+                attr.r#type.span(),
+                format!(
+                    "The schema has no definition named `{}`",
+                    attr.r#type.value()
+                ),
+            )
+        })
+}
+
+fn differences(expected: &Rust, item: &Item) -> syn::Result<Vec<String>> {
+    let mut differences = Vec::new();
+    match (expected, item) {
+        (Rust::Struct { fields, .. }, Item::Struct(item)) => {
+            let expected = fields
+                .iter()
+                .map(|f| {
+                    (
+                        RustGenerator::rust_field_name(f.name(), true),
+                        Some(f.r#type()),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let found = match &item.fields {
+                Fields::Named(fields) => fields
+                    .named
+                    .iter()
+                    .map(|f| {
+                        let name = f
+                            .ident
+                            .as_ref()
+                            .map(ToString::to_string)
+                            .unwrap_or_default();
+                        (name.trim_start_matches("r#").to_string(), Some(&f.ty))
+                    })
+                    .collect::<Vec<_>>(),
+                _ => return Err(kind_mismatch(item, "a struct with named fields")),
+            };
+            compare(&expected, &found, &mut differences);
+        }
+        (Rust::TupleStruct { r#type, .. }, Item::Struct(item)) => match &item.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => compare(
+                &[("0".to_string(), Some(r#type))],
+                &[("0".to_string(), fields.unnamed.first().map(|f| &f.ty))],
+                &mut differences,
+            ),
+            _ => return Err(kind_mismatch(item, "a tuple struct with a single field")),
+        },
+        (Rust::Enum(enumeration), Item::Enum(item)) => {
+            let expected = enumeration
+                .variants()
+                .map(|v| (RustGenerator::rust_variant_name(v), None))
+                .collect::<Vec<_>>();
+            let found = item
+                .variants
+                .iter()
+                .map(|v| match &v.fields {
+                    Fields::Unit => Ok((v.ident.to_string(), None)),
+                    _ => Err(kind_mismatch(v, "a unit variant")),
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            compare(&expected, &found, &mut differences);
+        }
+        (Rust::DataEnum(enumeration), Item::Enum(item)) => {
+            let expected = enumeration
+                .variants()
+                .map(|v| (RustGenerator::rust_variant_name(v.name()), Some(v.r#type())))
+                .collect::<Vec<_>>();
+            let found = item
+                .variants
+                .iter()
+                .map(|v| match &v.fields {
+                    Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                        Ok((v.ident.to_string(), fields.unnamed.first().map(|f| &f.ty)))
+                    }
+                    _ => Err(kind_mismatch(v, "a variant with a single unnamed field")),
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            compare(&expected, &found, &mut differences);
+        }
+        (Rust::Struct { .. }, item) => return Err(kind_mismatch(item, "a struct")),
+        (Rust::TupleStruct { .. }, item) => return Err(kind_mismatch(item, "a tuple struct")),
+        (Rust::Enum(_) | Rust::DataEnum(_), item) => return Err(kind_mismatch(item, "an enum")),
+    }
+    Ok(differences)
+}
+
+fn compare(
+    expected: &[(String, Option<&RustType>)],
+    found: &[(String, Option<&syn::Type>)],
+    differences: &mut Vec<String>,
+) {
+    for (name, r#type) in expected {
+        let expected_type = r#type.map(|t| normalize_str(&t.to_string()));
+        match found.iter().find(|(n, _)| n == name) {
+            None => differences.push(describe('-', name, expected_type.as_deref())),
+            Some((_, found_type)) => {
+                let found_type = found_type.map(normalize);
+                if found_type != expected_type {
+                    differences.push(describe('-', name, expected_type.as_deref()));
+                    differences.push(describe('+', name, found_type.as_deref()));
+                }
+            }
+        }
+    }
+    for (name, r#type) in found {
+        if !expected.iter().any(|(n, _)| n == name) {
+            let found_type = r#type.map(normalize);
+            differences.push(describe('+', name, found_type.as_deref()));
+        }
+    }
+}
+
+fn describe(sign: char, name: &str, r#type: Option<&str>) -> String {
+    match r#type {
+        Some(r#type) => format!("{sign} {name}: {type}"),
+        None => format!("{sign} {name}"),
+    }
+}
+
+fn kind_mismatch<T: Spanned>(item: &T, expected: &str) -> syn::Error {
+    syn::Error::new(
+        item.span(),
+        format!("The ASN.1 definition requires {expected} here"),
+    )
+}
+
+fn item_span(item: &Item) -> proc_macro2::Span {
+    match item {
+        Item::Struct(item) => item.ident.span(),
+        Item::Enum(item) => item.ident.span(),
+        item => item.span(),
+    }
+}
+
+fn normalize_str(r#type: &str) -> String {
+    syn::parse_str::<syn::Type>(r#type)
+        .map(|t| normalize(&t))
+        .unwrap_or_else(|_| r#type.replace(' ', ""))
+}
+
+/// Renders the type without whitespace and without the module path of each segment, so that
+/// `std::vec::Vec<u8>` and `Vec<u8>` are considered equal
+fn normalize(r#type: &syn::Type) -> String {
+    match r#type {
+        syn::Type::Path(path) if path.qself.is_none() => {
+            let Some(segment) = path.path.segments.last() else {
+                return String::new();
+            };
+            let mut normalized = segment.ident.to_string();
+            if let PathArguments::AngleBracketed(arguments) = &segment.arguments {
+                let arguments = arguments
+                    .args
+                    .iter()
+                    .map(|argument| match argument {
+                        GenericArgument::Type(r#type) => normalize(r#type),
+                        argument => quote!(#argument).to_string().replace(' ', ""),
+                    })
+                    .collect::<Vec<_>>();
+                normalized.push('<');
+                normalized.push_str(&arguments.join(","));
+                normalized.push('>');
+            }
+            normalized
+        }
+        syn::Type::Group(group) => normalize(&group.elem),
+        syn::Type::Paren(paren) => normalize(&paren.elem),
+        r#type => quote!(#r#type).to_string().replace(' ', ""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#""Verify DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+        Vehicle ::= SEQUENCE {
+            speed INTEGER (0..255),
+            type  UTF8String OPTIONAL
+        }
+        Kind ::= ENUMERATED { car, truck }
+        END""#;
+
+    fn verify_str(r#type: &str, item: &str) -> String {
+        let attr = format!("schema = {SCHEMA}, type = \"{type}\"");
+        verify(attr.parse().unwrap(), item.parse().unwrap()).to_string()
+    }
+
+    #[test]
+    fn test_matching_struct_is_accepted() {
+        let result = verify_str(
+            "Vehicle",
+            "pub struct Vehicle { pub speed: u8, pub type_: std::option::Option<String> }",
+        );
+        assert!(!result.contains("compile_error"), "{}", result);
+    }
+
+    #[test]
+    fn test_struct_differences_are_listed() {
+        let result = verify_str(
+            "Vehicle",
+            "pub struct Vehicle { pub speed: u16, pub type_: String, pub color: u8 }",
+        );
+        assert!(result.contains("compile_error"), "{}", result);
+        assert!(result.contains(r"- speed: u8\n+ speed: u16"), "{}", result);
+        assert!(
+            result.contains(r"- type_: Option<String>\n+ type_: String"),
+            "{}",
+            result
+        );
+        assert!(result.contains(r"+ color: u8"), "{}", result);
+    }
+
+    #[test]
+    fn test_enum_variants_are_compared() {
+        let result = verify_str("Kind", "pub enum Kind { Car, Bike }");
+        assert!(result.contains(r"- Truck\n+ Bike"), "{}", result);
+        let result = verify_str("Kind", "pub enum Kind { Car, Truck }");
+        assert!(!result.contains("compile_error"), "{}", result);
+    }
+
+    #[test]
+    fn test_kind_mismatch_and_unknown_definition() {
+        let result = verify_str("Kind", "pub struct Kind(u8);");
+        assert!(result.contains("requires an enum"), "{}", result);
+        let result = verify_str("Unknown", "pub struct Unknown(u8);");
+        assert!(result.contains("no definition named"), "{}", result);
+    }
+
+    #[test]
+    fn test_schema_file_is_included() {
+        let result = verify_str("Kind", "pub enum Kind { Car, Truck }");
+        assert!(!result.contains("include_str"), "{}", result);
+
+        let attr = "schema = \"../tests/asn_verify.asn1\", type = \"Position\"";
+        let item = "pub struct Position { pub x: u16, pub y: u16 }";
+        let result = verify(attr.parse().unwrap(), item.parse().unwrap()).to_string();
+        assert!(!result.contains("compile_error"), "{}", result);
+        assert!(result.contains("include_str"), "{}", result);
+        assert!(result.contains("asn_verify.asn1"), "{}", result);
+    }
+}
@@ -20,10 +20,15 @@ pub enum ProtobufType {
     UInt64,
     SInt32,
     SInt64,
+    Float,
+    Double,
     String,
     Bytes,
     BitsReprByBytesAndBitsLen,
     Repeated(Box<ProtobufType>),
+    /// A field with explicit presence (proto3 `optional`), which is serialized if set - even if
+    /// it is set to the default value of its type
+    Optional(Box<ProtobufType>),
     OneOf(Vec<(String, ProtobufType)>),
     /// Indicates a complex, custom type that is
     /// not one of rusts known types
@@ -45,12 +50,15 @@ impl ProtobufType {
             ProtobufType::UInt64 => RustType::U64(Range::none()),
             ProtobufType::SInt32 => RustType::I32(Range::inclusive(0, i32::MAX)),
             ProtobufType::SInt64 => RustType::I64(Range::inclusive(0, i64::MAX)),
+            ProtobufType::Float => RustType::F32,
+            ProtobufType::Double => RustType::F64,
             ProtobufType::String => RustType::String(Size::Any, Charset::Utf8),
             ProtobufType::Bytes => RustType::VecU8(Size::Any),
             ProtobufType::BitsReprByBytesAndBitsLen => RustType::BitVec(Size::Any),
             ProtobufType::Repeated(inner) => {
                 RustType::Vec(Box::new(inner.to_rust()), Size::Any, EncodingOrdering::Keep)
             }
+            ProtobufType::Optional(inner) => RustType::Option(Box::new(inner.to_rust())),
             ProtobufType::OneOf(_) => panic!("ProtobufType::OneOf cannot be mapped to a RustType"),
             ProtobufType::Complex(name) => RustType::Complex(name.clone(), None),
         }
@@ -66,11 +74,14 @@ impl ProtobufType {
             ProtobufType::UInt64 => true,
             ProtobufType::SInt32 => true,
             ProtobufType::SInt64 => true,
+            ProtobufType::Float => true,
+            ProtobufType::Double => true,
             ProtobufType::String => true,
             ProtobufType::Bytes | ProtobufType::BitsReprByBytesAndBitsLen => true,
             ProtobufType::OneOf(_) => false,
             ProtobufType::Complex(_) => false,
             ProtobufType::Repeated(_) => false,
+            ProtobufType::Optional(inner) => inner.is_primitive(),
         }
     }
 }
@@ -85,12 +96,15 @@ impl ToString for ProtobufType {
             ProtobufType::UInt64 => "uint64",
             ProtobufType::SInt32 => "sint32",
             ProtobufType::SInt64 => "sint64",
+            ProtobufType::Float => "float",
+            ProtobufType::Double => "double",
             ProtobufType::String => "string",
             ProtobufType::Bytes => "bytes",
             ProtobufType::BitsReprByBytesAndBitsLen => "bytes",
             ProtobufType::OneOf(_) => "oneof",
             ProtobufType::Complex(name) => return name.clone(),
             ProtobufType::Repeated(name) => return format!("repeated {}", name.to_string()),
+            ProtobufType::Optional(name) => return format!("optional {}", name.to_string()),
         }
         .into()
     }
@@ -122,9 +136,14 @@ impl Model<Protobuf> {
         let mut model = Model {
             name: rust_model.name.clone(),
             oid: rust_model.oid.clone(),
+            automatic_tags: rust_model.automatic_tags,
             imports: rust_model.imports.clone(),
             definitions: Vec::with_capacity(rust_model.definitions.len()),
             value_references: Vec::default(),
+            invisible_constraints: rust_model.invisible_constraints.clone(),
+            ignored_annotations: rust_model.ignored_annotations.clone(),
+            inline_definitions: rust_model.inline_definitions.clone(),
+            asn_names: rust_model.asn_names.clone(),
         };
         for Definition(name, rust) in &rust_model.definitions {
             let proto = Self::definition_to_protobuf(rust);
@@ -188,21 +207,29 @@ impl Model<Protobuf> {
             RustType::I32(_) => ProtobufType::SInt32,
             RustType::U64(_) => ProtobufType::UInt64,
             RustType::I64(_) => ProtobufType::SInt64,
+            RustType::F32 => ProtobufType::Float,
+            RustType::F64 => ProtobufType::Double,
+            RustType::ObjectIdentifier | RustType::RelativeOid | RustType::OidIri => {
+                ProtobufType::String
+            }
             RustType::String(..) => ProtobufType::String,
             RustType::VecU8(_) => ProtobufType::Bytes,
+            RustType::Containing(..) => ProtobufType::Bytes,
             RustType::BitVec(_) => ProtobufType::BitsReprByBytesAndBitsLen,
             RustType::Null => ProtobufType::Bytes,
 
             RustType::Complex(complex, _) => ProtobufType::Complex(complex.clone()),
 
-            RustType::Option(inner) => {
-                // in protobuf everything is optional...
-                Self::definition_type_to_protobuf_type(inner)
-            }
+            RustType::Option(inner) => match Self::definition_type_to_protobuf_type(inner) {
+                // proto3 cannot tell an absent from an empty repeated field
+                repeated @ ProtobufType::Repeated(_) => repeated,
+                inner => ProtobufType::Optional(Box::new(inner)),
+            },
             RustType::Default(inner, ..) => {
                 // TODO ignoring it in protobuf, is there a proper solution?
                 Self::definition_type_to_protobuf_type(inner)
             }
+            RustType::Substituted(inner, _) => Self::definition_type_to_protobuf_type(inner),
 
             RustType::Vec(inner, _size, _ordering) => {
                 ProtobufType::Repeated(Box::new(Self::definition_type_to_protobuf_type(inner)))
@@ -314,7 +341,10 @@ mod tests {
             )],
             &[Definition(
                 "SuchStruct".into(),
-                Protobuf::Message(vec![("very_optional".into(), ProtobufType::String)]),
+                Protobuf::Message(vec![(
+                    "very_optional".into(),
+                    ProtobufType::Optional(Box::new(ProtobufType::String)),
+                )]),
             )],
         );
     }
@@ -1,3 +1,4 @@
+use crate::asn::Type;
 use crate::model::LiteralValue;
 use std::fmt::{Debug, Display, Formatter};
 
@@ -55,6 +56,18 @@ pub enum Error {
     FailedToResolveType(String),
     FailedToResolveReference(String),
     FailedToParseLiteral(String),
+    /// The names of the definitions whose tags depend on each other, the first and last being
+    /// the same
+    CyclicTagReference(Vec<String>),
+    /// The path to the `SET`, such as `Outer.inner` for one declared inline, and the names of its
+    /// two components with the same tag
+    DuplicateSetTag(String, String, String),
+    /// The name of the importing module and the name of the module it imports from, which is
+    /// not loaded
+    UnknownImportModule(String, String),
+    /// The name of the importing module, the name of the module it imports from and the name of
+    /// the item, which that module neither declares nor imports itself
+    UnknownImportItem(String, String, String),
 }
 
 impl std::error::Error for Error {}
@@ -70,6 +83,30 @@ impl std::fmt::Display for Error {
             Error::FailedToParseLiteral(literal) => {
                 write!(f, "Failed to parse literal: {}", literal)
             }
+            Error::CyclicTagReference(names) => {
+                write!(f, "Cyclic tag reference: {}", names.join(" -> "))
+            }
+            Error::DuplicateSetTag(set, first, second) => {
+                write!(
+                    f,
+                    "The components {} and {} of the SET {} have the same tag",
+                    first, second, set
+                )
+            }
+            Error::UnknownImportModule(module, from) => {
+                write!(
+                    f,
+                    "Module {} imports from {}, which is not loaded",
+                    module, from
+                )
+            }
+            Error::UnknownImportItem(module, from, item) => {
+                write!(
+                    f,
+                    "Module {} imports {} from {}, which does not declare it",
+                    module, item, from
+                )
+            }
         }
     }
 }
@@ -78,6 +115,18 @@ pub trait Resolver<T> {
     fn resolve(&self, lor: &LitOrRef<T>) -> Result<T, Error>;
 }
 
+/// Resolves the values of `DEFAULT` and value references, whose meaning depends on their type
+pub trait ValueResolver {
+    /// Replaces the references within the given value by the values they refer to and the
+    /// identifiers of `ENUMERATED` values by their variants, as determined by the type of the
+    /// value
+    fn resolve_value(
+        &self,
+        value: &LiteralValue,
+        r#type: &Type<Unresolved>,
+    ) -> Result<LiteralValue, Error>;
+}
+
 pub trait TryResolve<T, R: Sized> {
     fn try_resolve(&self, resolver: &impl Resolver<T>) -> Result<R, Error>;
 }
@@ -1,14 +1,17 @@
 use crate::asn::{
     Asn, ComponentTypeList, Range, Size, Tag, TagProperty, TagResolver, Type as AsnType, Type,
 };
-use crate::asn::{Charset, ChoiceVariant, Integer};
-use crate::model::Import;
+use crate::asn::{Charset, ChoiceVariant, Integer, RealPrecision, Substitution};
+use crate::generate::NamingStrategy;
 use crate::model::Model;
 use crate::model::ValueReference;
 use crate::model::{Definition, LiteralValue, Target};
+use crate::model::{Import, InlineDefinition};
 use crate::resolve::{ResolveState, Resolved};
 use crate::rust::Field as RustField;
 use std::borrow::Cow;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 
 const I8_MAX: i64 = i8::MAX as i64;
 const I16_MAX: i64 = i16::MAX as i64;
@@ -40,8 +43,19 @@ pub enum RustType {
     U32(Range<u32>),
     I64(Range<i64>),
     U64(Range<Option<u64>>),
+    F32,
+    F64,
+    /// An `OBJECT IDENTIFIER`, represented by `Oid`
+    ObjectIdentifier,
+    /// A `RELATIVE-OID`, represented by `Oid` with the arcs relative to an unspecified node
+    RelativeOid,
+    /// An `OID-IRI`, represented by a `String` like `/ISO/Registration_Authority`
+    OidIri,
     String(Size, Charset),
     VecU8(Size),
+    /// An `OCTET STRING` containing the encoding of the inner type, optionally transformed
+    /// by the type with the given name, which implements the content transformation
+    Containing(Box<RustType>, Option<String>),
     BitVec(Size),
     Vec(Box<RustType>, Size, EncodingOrdering),
     Null,
@@ -54,6 +68,9 @@ pub enum RustType {
     /// thought of as a "ReferenceType"; declaring usage,
     /// but not being declared here
     Complex(String, Option<Tag>),
+
+    /// The inner type, represented by a user provided Rust type
+    Substituted(Box<RustType>, Substitution),
 }
 
 impl RustType {
@@ -81,6 +98,91 @@ impl RustType {
         self.as_inner_type().to_string()
     }
 
+    /// Lets a reference to a definition with one of the given names refer to the definition
+    /// with the name it is mapped to instead
+    pub fn rename_complex(&mut self, renames: &HashMap<String, String>) {
+        match self {
+            RustType::Complex(name, _) => {
+                if let Some(rename) = renames.get(name) {
+                    name.clone_from(rename);
+                }
+            }
+            RustType::Containing(inner, _)
+            | RustType::Vec(inner, ..)
+            | RustType::Option(inner)
+            | RustType::Default(inner, _)
+            | RustType::Substituted(inner, _) => inner.rename_complex(renames),
+            RustType::Bool
+            | RustType::I8(_)
+            | RustType::U8(_)
+            | RustType::I16(_)
+            | RustType::U16(_)
+            | RustType::I32(_)
+            | RustType::U32(_)
+            | RustType::I64(_)
+            | RustType::U64(_)
+            | RustType::F32
+            | RustType::F64
+            | RustType::ObjectIdentifier
+            | RustType::RelativeOid
+            | RustType::OidIri
+            | RustType::String(..)
+            | RustType::VecU8(_)
+            | RustType::BitVec(_)
+            | RustType::Null => {}
+        }
+    }
+
+    /// Replaces the `String`s, `Vec<u8>`s and `Vec`s with an upper `SIZE` bound that is not
+    /// extensible by [`Substitution`]s with `heapless::String`s and `heapless::Vec`s of that
+    /// capacity - in octets for strings, enough for the widest characters of the charset.
+    /// `DEFAULT` values and types that are substituted already are left as they are.
+    pub fn use_heapless_types(&mut self) {
+        fn capacity(size: &Size) -> Option<usize> {
+            size.max().copied().filter(|_| !size.extensible())
+        }
+
+        let heapless = match self {
+            RustType::String(size, charset) => capacity(size)
+                .map(|max| format!("::heapless::String<{}>", max * charset.max_utf8_len())),
+            RustType::VecU8(size) => {
+                capacity(size).map(|max| format!("::heapless::Vec<u8, {}>", max))
+            }
+            RustType::Vec(inner, size, _) => {
+                inner.use_heapless_types();
+                capacity(size).map(|max| format!("::heapless::Vec<{}, {}>", inner.to_string(), max))
+            }
+            RustType::Option(inner) => {
+                inner.use_heapless_types();
+                None
+            }
+            RustType::Bool
+            | RustType::I8(_)
+            | RustType::U8(_)
+            | RustType::I16(_)
+            | RustType::U16(_)
+            | RustType::I32(_)
+            | RustType::U32(_)
+            | RustType::I64(_)
+            | RustType::U64(_)
+            | RustType::F32
+            | RustType::F64
+            | RustType::ObjectIdentifier
+            | RustType::RelativeOid
+            | RustType::OidIri
+            | RustType::Containing(..)
+            | RustType::BitVec(_)
+            | RustType::Null
+            | RustType::Default(..)
+            | RustType::Complex(..)
+            | RustType::Substituted(..) => None,
+        };
+
+        if let Some(heapless) = heapless {
+            *self = RustType::Substituted(Box::new(self.clone()), Substitution::new(heapless));
+        }
+    }
+
     pub fn no_option(self) -> Self {
         match self {
             RustType::Option(inner) => *inner,
@@ -156,8 +258,12 @@ impl RustType {
             RustType::I64(Range(min, max, extensible)) => {
                 Some(Range(min.to_string(), max.to_string(), *extensible))
             }
+            RustType::F32 | RustType::F64 => None,
+            RustType::ObjectIdentifier | RustType::RelativeOid | RustType::OidIri => None,
             RustType::String(..) => None,
             RustType::VecU8(_) => None,
+            RustType::Containing(..) => None,
+            RustType::Substituted(..) => None,
             RustType::BitVec(_) => None,
             RustType::Vec(inner, _size, _ordering) => inner.integer_range_str(),
             RustType::Null => None,
@@ -208,8 +314,19 @@ impl RustType {
                 range.max().map(|v| v as i64),
                 range.extensible(),
             )),
+            RustType::F32 => AsnType::Real(RealPrecision::Single),
+            RustType::F64 => AsnType::Real(RealPrecision::Double),
+            RustType::ObjectIdentifier => AsnType::ObjectIdentifier,
+            RustType::RelativeOid => AsnType::RelativeOid,
+            RustType::OidIri => AsnType::OidIri,
             RustType::String(size, charset) => AsnType::String(size, charset),
             RustType::VecU8(size) => AsnType::OctetString(size),
+            RustType::Containing(inner, encoded_by) => {
+                AsnType::Containing(Box::new(inner.into_asn()), encoded_by)
+            }
+            RustType::Substituted(inner, substitution) => {
+                AsnType::Substituted(Box::new(inner.into_asn()), substitution)
+            }
             RustType::BitVec(size) => AsnType::bit_vec_with_size(size),
             RustType::Vec(inner, size, EncodingOrdering::Keep) => {
                 AsnType::SequenceOf(Box::new(inner.into_asn()), size)
@@ -237,8 +354,19 @@ impl RustType {
             RustType::I32(_) => matches!(other, RustType::I32(_)),
             RustType::U64(_) => matches!(other, RustType::U64(_)),
             RustType::I64(_) => matches!(other, RustType::I64(_)),
+            RustType::F32 => RustType::F32 == *other,
+            RustType::F64 => RustType::F64 == *other,
+            RustType::ObjectIdentifier => RustType::ObjectIdentifier == *other,
+            RustType::RelativeOid => RustType::RelativeOid == *other,
+            RustType::OidIri => RustType::OidIri == *other,
             RustType::String(..) => matches!(other, RustType::String(..)),
             RustType::VecU8(_) => matches!(other, RustType::VecU8(_)),
+            RustType::Containing(inner_a, _) => {
+                matches!(other, RustType::Containing(inner_b, _) if inner_a.similar(inner_b))
+            }
+            RustType::Substituted(inner_a, a) => {
+                matches!(other, RustType::Substituted(inner_b, b) if a == b && inner_a.similar(inner_b))
+            }
             RustType::BitVec(_) => matches!(other, RustType::BitVec(_)),
             RustType::Vec(inner_a, _size, _ordering) => {
                 if let RustType::Vec(inner_b, _other_size, _ordering) = other {
@@ -279,14 +407,19 @@ impl RustType {
             | RustType::U32(_)
             | RustType::I64(_)
             | RustType::U64(_) => Tag::DEFAULT_INTEGER,
+            RustType::F32 | RustType::F64 => Tag::DEFAULT_REAL,
+            RustType::ObjectIdentifier => Tag::DEFAULT_OBJECT_IDENTIFIER,
+            RustType::RelativeOid => Tag::DEFAULT_RELATIVE_OID,
+            RustType::OidIri => Tag::DEFAULT_OID_IRI,
             RustType::BitVec(_) => Tag::DEFAULT_BIT_STRING,
-            RustType::VecU8(_) => Tag::DEFAULT_OCTET_STRING,
+            RustType::VecU8(_) | RustType::Containing(..) => Tag::DEFAULT_OCTET_STRING,
             RustType::String(_, charset) => charset.default_tag(),
             RustType::Vec(_, _, EncodingOrdering::Keep) => Tag::DEFAULT_SEQUENCE_OF,
             RustType::Vec(_, _, EncodingOrdering::Sort) => Tag::DEFAULT_SET_OF,
             RustType::Null => Tag::DEFAULT_NULL,
             RustType::Option(inner) => return inner.tag(),
             RustType::Default(inner, ..) => return inner.tag(),
+            RustType::Substituted(inner, _) => return inner.tag(),
             // TODO this is wrong. This should resolve the tag from the referenced type instead, but atm the infrastructure is missing to do such a thing, see github#13
             RustType::Complex(_, tag) => return *tag,
         })
@@ -339,6 +472,37 @@ impl Rust {
             constants: Vec::default(),
         }
     }
+
+    /// Lets the types referring to a definition with one of the given names refer to the
+    /// definition with the name it is mapped to instead
+    fn rename_complex(&mut self, renames: &HashMap<String, String>) {
+        match self {
+            Rust::Struct { fields, .. } => fields
+                .iter_mut()
+                .for_each(|field| field.name_type.1.rename_complex(renames)),
+            Rust::Enum(_) => {}
+            Rust::DataEnum(enumeration) => enumeration
+                .variants
+                .iter_mut()
+                .for_each(|variant| variant.name_type.1.rename_complex(renames)),
+            Rust::TupleStruct { r#type, .. } => r#type.rename_complex(renames),
+        }
+    }
+
+    /// See [`RustType::use_heapless_types`]
+    fn use_heapless_types(&mut self) {
+        match self {
+            Rust::Struct { fields, .. } => fields
+                .iter_mut()
+                .for_each(|field| field.name_type.1.use_heapless_types()),
+            Rust::Enum(_) => {}
+            Rust::DataEnum(enumeration) => enumeration
+                .variants
+                .iter_mut()
+                .for_each(|variant| variant.name_type.1.use_heapless_types()),
+            Rust::TupleStruct { r#type, .. } => r#type.use_heapless_types(),
+        }
+    }
 }
 
 impl Target for Rust {
@@ -390,8 +554,14 @@ impl RustType {
             RustType::I32(_) => "i32",
             RustType::U64(_) => "u64",
             RustType::I64(_) => "i64",
+            RustType::F32 => "f32",
+            RustType::F64 => "f64",
+            RustType::ObjectIdentifier | RustType::RelativeOid => "&'static [u64]",
+            RustType::OidIri => "&'static str",
             RustType::String(..) => "&'static str",
             RustType::VecU8(_) => "&'static [u8]",
+            RustType::Containing(inner, _) => return inner.to_const_lit_string(),
+            RustType::Substituted(inner, _) => return inner.to_const_lit_string(),
             RustType::BitVec(_) => "u64",
             RustType::Vec(inner, _size, _ordering) => {
                 return Cow::Owned(format!("&'static [{}]", inner.to_const_lit_string()))
@@ -418,14 +588,20 @@ impl ToString for RustType {
             RustType::I32(_) => "i32",
             RustType::U64(_) => "u64",
             RustType::I64(_) => "i64",
+            RustType::F32 => "f32",
+            RustType::F64 => "f64",
+            RustType::ObjectIdentifier | RustType::RelativeOid => "Oid",
+            RustType::OidIri => "String",
             RustType::String(..) => "String",
             RustType::VecU8(_) => "Vec<u8>",
+            RustType::Containing(inner, _) => return inner.to_string(),
             RustType::BitVec(_) => "BitVec",
             RustType::Vec(inner, _size, _ordering) => return format!("Vec<{}>", inner.to_string()),
             RustType::Null => "Null",
             RustType::Option(inner) => return format!("Option<{}>", inner.to_string()),
             RustType::Default(inner, ..) => return inner.to_string(),
             RustType::Complex(name, _) => return name.clone(),
+            RustType::Substituted(_, substitution) => return substitution.rust_type.clone(),
         }
         .into()
     }
@@ -436,6 +612,8 @@ pub struct Field {
     pub(crate) name_type: (String, RustType),
     pub(crate) tag: Option<Tag>,
     pub(crate) constants: Vec<(String, String)>,
+    pub(crate) sensitive: bool,
+    pub(crate) unit: Option<String>,
 }
 
 impl Field {
@@ -444,6 +622,8 @@ impl Field {
             name_type: (name.to_string(), r#type),
             tag: None,
             constants: Vec::default(),
+            sensitive: false,
+            unit: None,
         }
     }
 
@@ -467,6 +647,26 @@ impl Field {
         self.constants = constants;
         self
     }
+
+    /// Whether the value must be masked in the `RedactedDebug` representation
+    pub fn sensitive(&self) -> bool {
+        self.sensitive
+    }
+
+    pub fn with_sensitive(mut self, sensitive: bool) -> Self {
+        self.sensitive = sensitive;
+        self
+    }
+
+    /// The physical unit of the value, see [`Annotation::Unit`](crate::asn::Annotation::Unit)
+    pub fn unit(&self) -> Option<&str> {
+        self.unit.as_deref()
+    }
+
+    pub fn with_unit(mut self, unit: Option<String>) -> Self {
+        self.unit = unit;
+        self
+    }
 }
 
 impl TagProperty for Field {
@@ -556,6 +756,7 @@ impl PlainEnum {
 pub struct DataVariant {
     name_type: (String, RustType),
     tag: Option<Tag>,
+    sensitive: bool,
 }
 
 impl DataVariant {
@@ -563,6 +764,7 @@ impl DataVariant {
         Self {
             name_type: (name.to_string(), r#type),
             tag: None,
+            sensitive: false,
         }
     }
 
@@ -577,6 +779,16 @@ impl DataVariant {
     pub fn r#type(&self) -> &RustType {
         &self.name_type.1
     }
+
+    /// Whether the value must be masked in the `RedactedDebug` representation
+    pub fn sensitive(&self) -> bool {
+        self.sensitive
+    }
+
+    pub fn with_sensitive(mut self, sensitive: bool) -> Self {
+        self.sensitive = sensitive;
+        self
+    }
 }
 
 impl TagProperty for DataVariant {
@@ -603,43 +815,187 @@ impl Model<Rust> {
         let mut ctxt = Context {
             resolver: TagResolver::new(asn_model, scope),
             target: &mut definitions,
+            inline: Vec::default(),
+            component: None,
             make_names_nice,
         };
         let mut model = Model {
             name: ctxt.module_name(&asn_model.name),
             oid: asn_model.oid.clone(),
+            automatic_tags: asn_model.automatic_tags,
             imports: asn_model
                 .imports
                 .iter()
                 .map(|i| Import {
-                    what: i.what.iter().map(|w| ctxt.struct_or_enum_name(w)).collect(),
+                    what: i
+                        .what
+                        .iter()
+                        .map(|w| {
+                            // value references start with a lowercase letter, types do not
+                            if w.starts_with(|c: char| c.is_ascii_lowercase()) {
+                                ctxt.constant_name(w)
+                            } else {
+                                ctxt.struct_or_enum_name(w)
+                            }
+                        })
+                        .collect(),
                     from: ctxt.module_name(&i.from),
                     from_oid: i.from_oid.clone(),
                 })
                 .collect(),
             definitions: Vec::default(),
             value_references: Vec::with_capacity(asn_model.value_references.len()),
+            invisible_constraints: asn_model.invisible_constraints.clone(),
+            ignored_annotations: asn_model.ignored_annotations.clone(),
+            inline_definitions: Vec::default(),
+            asn_names: Vec::default(),
         };
         for Definition(name, asn) in &asn_model.definitions {
             let rust_name = ctxt.struct_or_enum_name(name);
             Self::definition_to_rust(&rust_name, &asn.r#type, asn.tag, &mut ctxt);
+            model.asn_names.push((rust_name, name.clone()));
         }
         for vref in &asn_model.value_references {
             if let Some(rust_type) = Self::map_asn_type_to_rust_type_flat(&vref.role.r#type) {
                 model.value_references.push(ValueReference {
                     name: ctxt.constant_name(&vref.name),
-                    role: rust_type,
-                    value: vref.value.clone(),
+                    role: match rust_type {
+                        RustType::Complex(name, tag) => {
+                            RustType::Complex(ctxt.struct_or_enum_name(&name), tag)
+                        }
+                        rust_type => rust_type,
+                    },
+                    value: Self::value_to_rust(&vref.value, &ctxt),
                 });
             } else {
                 // TODO some kind of debug-log?
                 println!("Ignoring ValueReference {}", vref.name);
             }
         }
+        model.inline_definitions = ctxt.inline;
         model.definitions = definitions;
         model
     }
 
+    /// Renames the inline definitions and the references to them as given by the strategy
+    pub fn rename_inline_definitions(&mut self, strategy: &dyn NamingStrategy) {
+        fn renamed(
+            name: &str,
+            inline: &HashMap<String, InlineDefinition>,
+            strategy: &dyn NamingStrategy,
+            renames: &mut HashMap<String, String>,
+        ) -> String {
+            if let Some(rename) = renames.get(name) {
+                return rename.clone();
+            }
+            match inline.get(name) {
+                Some(definition) if definition.parent != name => {
+                    let parent = renamed(&definition.parent, inline, strategy, renames);
+                    let rename = strategy.inline_type_name(&parent, &definition.component);
+                    renames.insert(name.to_string(), rename.clone());
+                    rename
+                }
+                _ => name.to_string(),
+            }
+        }
+
+        let inline = self
+            .inline_definitions
+            .iter()
+            .map(|definition| (definition.name.clone(), definition.clone()))
+            .collect::<HashMap<_, _>>();
+        let mut renames = HashMap::new();
+        for definition in &mut self.inline_definitions {
+            definition.name = renamed(&definition.name, &inline, strategy, &mut renames);
+            definition.parent = renamed(&definition.parent, &inline, strategy, &mut renames);
+        }
+
+        for Definition(name, rust) in &mut self.definitions {
+            if let Some(rename) = renames.get(name) {
+                name.clone_from(rename);
+            }
+            rust.rename_complex(&renames);
+        }
+        for vref in &mut self.value_references {
+            vref.role.rename_complex(&renames);
+        }
+    }
+
+    /// Merges the inline definitions that are identical - apart from their names - into the
+    /// first of them and lets the references to the others refer to it instead. Because merging
+    /// nested definitions can make the definitions they are nested in identical, this is
+    /// repeated until there is nothing left to merge.
+    pub fn deduplicate_inline_definitions(&mut self) {
+        loop {
+            let inline = self
+                .inline_definitions
+                .iter()
+                .map(|inline| &inline.name)
+                .collect::<HashSet<_>>();
+            let mut first = HashMap::<_, &String>::new();
+            let mut renames = HashMap::new();
+            for Definition(name, rust) in &self.definitions {
+                if inline.contains(name) {
+                    match first.entry(format!("{:?}", rust)) {
+                        Entry::Occupied(entry) => {
+                            renames.insert(name.clone(), entry.get().to_string());
+                        }
+                        Entry::Vacant(entry) => {
+                            entry.insert(name);
+                        }
+                    }
+                }
+            }
+
+            if renames.is_empty() {
+                return;
+            }
+
+            self.definitions
+                .retain(|Definition(name, _)| !renames.contains_key(name));
+            self.inline_definitions
+                .retain(|inline| !renames.contains_key(&inline.name));
+            for Definition(_, rust) in &mut self.definitions {
+                rust.rename_complex(&renames);
+            }
+            for vref in &mut self.value_references {
+                vref.role.rename_complex(&renames);
+            }
+        }
+    }
+
+    /// Lets the bounded strings and lists of all definitions be fixed-capacity `heapless` types,
+    /// which do not allocate, see [`RustType::use_heapless_types`]
+    pub fn use_heapless_types(&mut self) {
+        for Definition(_, rust) in &mut self.definitions {
+            rust.use_heapless_types();
+        }
+    }
+
+    /// Renames the components and alternatives of structured values like their Rust fields and
+    /// variants
+    fn value_to_rust(value: &LiteralValue, ctxt: &Context<'_>) -> LiteralValue {
+        match value {
+            LiteralValue::Sequence(components) => LiteralValue::Sequence(
+                components
+                    .iter()
+                    .map(|(name, value)| (ctxt.field_name(name), Self::value_to_rust(value, ctxt)))
+                    .collect(),
+            ),
+            LiteralValue::SequenceOf(values) => LiteralValue::SequenceOf(
+                values
+                    .iter()
+                    .map(|value| Self::value_to_rust(value, ctxt))
+                    .collect(),
+            ),
+            LiteralValue::Choice(name, value) => LiteralValue::Choice(
+                ctxt.variant_name(name),
+                Box::new(Self::value_to_rust(value, ctxt)),
+            ),
+            value => value.clone(),
+        }
+    }
+
     fn map_asn_type_to_rust_type_flat(r#type: &Type) -> Option<RustType> {
         Some(match &r#type {
             Type::Boolean => RustType::Bool,
@@ -651,6 +1007,10 @@ impl Model<Rust> {
             Type::OctetString(size) => RustType::VecU8(size.clone()),
             Type::BitString(bs) => RustType::BitVec(bs.size.clone()),
             Type::Null => RustType::Null,
+            Type::Real(precision) => Self::asn_real_to_rust_type(*precision),
+            Type::ObjectIdentifier => RustType::ObjectIdentifier,
+            Type::RelativeOid => RustType::RelativeOid,
+            Type::OidIri => RustType::OidIri,
             Type::Optional(opt) => {
                 RustType::Option(Box::new(Self::map_asn_type_to_rust_type_flat(opt)?))
             }
@@ -659,7 +1019,12 @@ impl Model<Rust> {
                 default.clone(),
             ),
             Type::TypeReference(name, tag) => RustType::Complex(name.clone(), *tag),
-            Type::Sequence(_)
+            Type::Substituted(inner, substitution) => RustType::Substituted(
+                Box::new(Self::map_asn_type_to_rust_type_flat(inner)?),
+                substitution.clone(),
+            ),
+            Type::Containing(..)
+            | Type::Sequence(_)
             | Type::SequenceOf(_, _)
             | Type::Set(_)
             | Type::SetOf(_, _)
@@ -679,8 +1044,13 @@ impl Model<Rust> {
         match asn {
             AsnType::Boolean
             | AsnType::Null
+            | AsnType::Real(_)
+            | AsnType::ObjectIdentifier
+            | AsnType::RelativeOid
+            | AsnType::OidIri
             | AsnType::String(..)
             | AsnType::OctetString(_)
+            | AsnType::Containing(..)
             | AsnType::BitString(_) => {
                 let rust_type = Self::definition_type_to_rust_type(name, asn, tag, ctxt);
                 ctxt.add_definition(Definition(
@@ -696,7 +1066,7 @@ impl Model<Rust> {
                 ));
             }
 
-            me @ AsnType::Integer(_) => {
+            me @ (AsnType::Integer(_) | AsnType::Substituted(..)) => {
                 let rust_type = Self::definition_type_to_rust_type(name, asn, tag, ctxt);
                 let constants = ctxt.to_rust_constants(me);
                 ctxt.add_definition(Definition(
@@ -794,14 +1164,20 @@ impl Model<Rust> {
                     name: variant_name,
                     r#type,
                     tag,
+                    sensitive,
                 } in choice.variants()
                 {
-                    let rust_name = format!("{}{}", name, ctxt.struct_or_enum_name(variant_name));
+                    let component = ctxt.struct_or_enum_name(variant_name);
+                    let rust_name = format!("{}{}", name, component);
+                    ctxt.component = Some((name.to_string(), component));
                     let rust_role =
                         Self::definition_type_to_rust_type(&rust_name, r#type, *tag, ctxt);
+                    ctxt.component = None;
                     let rust_field_name = ctxt.variant_name(variant_name);
                     enumeration.variants.push(
-                        DataVariant::from_name_type(rust_field_name, rust_role).with_tag_opt(*tag),
+                        DataVariant::from_name_type(rust_field_name, rust_role)
+                            .with_tag_opt(*tag)
+                            .with_sensitive(*sensitive),
                     );
                 }
 
@@ -833,12 +1209,18 @@ impl Model<Rust> {
         let mut rust_fields = Vec::with_capacity(fields.len());
 
         for (index, field) in fields.iter().enumerate() {
-            let rust_name = format!("{}{}", name, ctxt.struct_or_enum_name(&field.name));
+            let component = ctxt.struct_or_enum_name(&field.name);
+            let rust_name = format!("{}{}", name, component);
             let tag = field.role.tag;
+            ctxt.component = Some((name.to_string(), component));
             let rust_role =
                 Self::definition_type_to_rust_type(&rust_name, &field.role.r#type, tag, ctxt);
+            ctxt.component = None;
             let rust_role = if let Some(def) = &field.role.default {
-                RustType::Default(Box::new(rust_role.no_option()), def.clone())
+                RustType::Default(
+                    Box::new(rust_role.no_option()),
+                    Self::value_to_rust(def, ctxt),
+                )
             } else if extension_after.map(|e| index > e).unwrap_or(false)
                 && !rust_role.is_optional()
             {
@@ -851,7 +1233,9 @@ impl Model<Rust> {
             rust_fields.push(
                 RustField::from_name_type(rust_field_name, rust_role)
                     .with_constants(constants)
-                    .with_tag_opt(tag),
+                    .with_tag_opt(tag)
+                    .with_sensitive(field.role.sensitive)
+                    .with_unit(field.role.unit.clone()),
             );
         }
 
@@ -867,6 +1251,10 @@ impl Model<Rust> {
         match asn {
             AsnType::Boolean => RustType::Bool,
             AsnType::Null => RustType::Null,
+            AsnType::Real(precision) => Self::asn_real_to_rust_type(*precision),
+            AsnType::ObjectIdentifier => RustType::ObjectIdentifier,
+            AsnType::RelativeOid => RustType::RelativeOid,
+            AsnType::OidIri => RustType::OidIri,
             AsnType::Integer(int) if int.range.extensible() => {
                 Self::asn_extensible_integer_to_rust(int)
             }
@@ -874,7 +1262,20 @@ impl Model<Rust> {
 
             AsnType::String(size, charset) => RustType::String(size.clone(), *charset),
             AsnType::OctetString(size) => RustType::VecU8(size.clone()),
+            AsnType::Containing(inner, encoded_by) => RustType::Containing(
+                Box::new(Self::definition_type_to_rust_type(
+                    name,
+                    inner,
+                    ctxt.resolver().resolve_no_default(inner),
+                    ctxt,
+                )),
+                encoded_by.as_ref().map(|e| ctxt.struct_or_enum_name(e)),
+            ),
             AsnType::BitString(bitstring) => RustType::BitVec(bitstring.size.clone()),
+            AsnType::Substituted(inner, substitution) => RustType::Substituted(
+                Box::new(Self::definition_type_to_rust_type(name, inner, tag, ctxt)),
+                substitution.clone(),
+            ),
             Type::Optional(inner) => {
                 RustType::Option(Box::new(Self::definition_type_to_rust_type(
                     name,
@@ -917,7 +1318,15 @@ impl Model<Rust> {
             | ty @ AsnType::Enumerated(_)
             | ty @ AsnType::Choice(_) => {
                 let name = ctxt.struct_or_enum_name(name);
+                let component = ctxt.component.take();
                 Self::definition_to_rust(&name, asn, tag, ctxt);
+                if let Some((parent, component)) = component {
+                    ctxt.inline.push(InlineDefinition {
+                        name: name.clone(),
+                        parent,
+                        component,
+                    });
+                }
                 RustType::Complex(name, tag.or_else(|| ctxt.resolver().resolve_type_tag(ty)))
             }
             AsnType::TypeReference(name, tag) => RustType::Complex(
@@ -945,6 +1354,13 @@ impl Model<Rust> {
         }
     }
 
+    const fn asn_real_to_rust_type(precision: RealPrecision) -> RustType {
+        match precision {
+            RealPrecision::Single => RustType::F32,
+            RealPrecision::Double => RustType::F64,
+        }
+    }
+
     fn asn_fixed_integer_to_rust_type(
         int: &Integer<<Resolved as ResolveState>::RangeType>,
     ) -> RustType {
@@ -984,6 +1400,10 @@ impl Model<Rust> {
 struct Context<'a> {
     resolver: TagResolver<'a>,
     target: &'a mut Vec<Definition<Rust>>,
+    /// The definitions declared inline within other definitions
+    inline: Vec<InlineDefinition>,
+    /// The name of the definition and of its component or alternative whose type is converted
+    component: Option<(String, String)>,
     make_names_nice: bool,
 }
 
@@ -1001,10 +1421,17 @@ impl Context<'_> {
                 .map(|(name, value)| (self.constant_name(name), format!("{}", value)))
                 .collect(),
 
+            AsnType::Substituted(inner, _) => self.to_rust_constants(inner),
+
             Type::Boolean
             | Type::Null
+            | Type::Real(_)
+            | Type::ObjectIdentifier
+            | Type::RelativeOid
+            | Type::OidIri
             | Type::String(..)
             | Type::OctetString(_)
+            | Type::Containing(..)
             | Type::Optional(_)
             | Type::Default(..)
             | Type::Sequence(_)
@@ -1161,15 +1588,17 @@ impl LiteralValue {
         }
     }
 
+    /// The literal of this value in a const context. Structured values have none, because their
+    /// representation depends on their type, and a reference is the name of the constant.
     pub fn as_rust_const_literal(&self, make_names_nice: bool) -> impl std::fmt::Display + '_ {
         struct Ref<'a>(&'a LiteralValue, bool);
         impl std::fmt::Display for Ref<'_> {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 match self.0 {
                     LiteralValue::Boolean(v) => write!(f, "{}", v),
-                    LiteralValue::String(v) => write!(f, "\"{}\"", v),
+                    LiteralValue::String(v) => write!(f, "{:?}", v),
                     LiteralValue::Integer(v) => write!(f, "{}", v),
-                    LiteralValue::OctetString(v) => {
+                    LiteralValue::OctetString(v) | LiteralValue::BitString(v, _) => {
                         write!(f, "[")?;
                         for b in v {
                             write!(f, "0x{:02x}, ", *b)?;
@@ -1192,6 +1621,13 @@ impl LiteralValue {
                             }
                         )
                     }
+                    LiteralValue::Reference(name) => write!(f, "{}", name),
+                    LiteralValue::Sequence(..)
+                    | LiteralValue::SequenceOf(..)
+                    | LiteralValue::Choice(..) => {
+                        // the representation of these depends on the type of the value
+                        panic!("No const literal for structured value {:?}", self.0)
+                    }
                 }
             }
         }
@@ -1883,6 +2319,7 @@ mod tests {
                         name: "ghi".to_string(),
                         tag: Some(Tag::Universal(4)),
                         r#type: Type::Boolean,
+                        sensitive: false,
                     },
                 ])
                 .with_extension_after(2),
@@ -1975,6 +2412,7 @@ mod tests {
         let asn = Model::<Asn<Resolved>> {
             name: "SomeGreatName".to_string(),
             oid: None,
+            automatic_tags: false,
             imports: Vec::default(),
             definitions: Vec::default(),
             value_references: vec![
@@ -1993,6 +2431,10 @@ mod tests {
                     value: LiteralValue::Boolean(true),
                 },
             ],
+            invisible_constraints: vec![],
+            ignored_annotations: vec![],
+            inline_definitions: vec![],
+            asn_names: vec![],
         };
 
         assert_starts_with_lines(
@@ -2017,6 +2459,7 @@ mod tests {
         let asn = Model::<Asn<Resolved>> {
             name: "CoherentComplexRenaming".to_string(),
             oid: None,
+            automatic_tags: false,
             imports: vec![],
             definitions: vec![
                 Definition("Some-Name-WithID".to_string(), Type::Boolean.untagged()),
@@ -2040,6 +2483,10 @@ mod tests {
                 ),
             ],
             value_references: vec![],
+            invisible_constraints: vec![],
+            ignored_annotations: vec![],
+            inline_definitions: vec![],
+            asn_names: vec![],
         };
         assert_eq!(
             vec![
@@ -2076,4 +2523,69 @@ mod tests {
             asn.to_rust().definitions
         );
     }
+
+    fn definition_names(model: &Model<Rust>) -> Vec<&str> {
+        model
+            .definitions
+            .iter()
+            .map(|Definition(name, _)| name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_identical_inline_definitions_are_merged() {
+        let mut model = Model::try_from(Tokenizer.parse(
+            r"Dedup DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            First ::= SEQUENCE {
+                interval SEQUENCE { unit ENUMERATED { ms, s }, value INTEGER (0..255) },
+                mode ENUMERATED { on, off }
+            }
+            Second ::= SEQUENCE {
+                period SEQUENCE { unit ENUMERATED { ms, s }, value INTEGER (0..255) },
+                mode ENUMERATED { on, off, unknown }
+            }
+            Third ::= ENUMERATED { on, off }
+            END",
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+        assert_eq!(
+            vec![
+                "FirstIntervalUnit",
+                "FirstInterval",
+                "FirstMode",
+                "SecondPeriodUnit"
+            ],
+            model.inline_definitions[..4]
+                .iter()
+                .map(|inline| inline.name.as_str())
+                .collect::<Vec<_>>()
+        );
+
+        model.deduplicate_inline_definitions();
+        assert_eq!(
+            vec![
+                "FirstIntervalUnit",
+                "FirstInterval",
+                "FirstMode",
+                "First",
+                "SecondMode",
+                "Second",
+                "Third"
+            ],
+            definition_names(&model)
+        );
+        assert_eq!(
+            Some(&RustType::Complex(
+                "FirstInterval".to_string(),
+                Some(Tag::DEFAULT_SEQUENCE)
+            )),
+            match &model.definitions[5].1 {
+                Rust::Struct { fields, .. } => fields.first().map(RustField::r#type),
+                _ => None,
+            }
+        );
+    }
 }
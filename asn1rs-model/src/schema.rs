@@ -0,0 +1,929 @@
+//! A compact binary representation of resolved [`Model`]s.
+//!
+//! The schema artifact contains everything the descriptors of the generated types know about
+//! a definition (tags, constraints, optionality, extension markers, default values, ...). This
+//! allows processes that do not compile the generated Rust types (for example sidecar
+//! validators) to load the schema at runtime and inspect it. Decoding or validating values
+//! against a loaded schema is not supported yet, this still requires the generated types.
+//!
+//! ```rust
+//! use asn1rs_model::parse::Tokenizer;
+//! use asn1rs_model::schema::Schema;
+//! use asn1rs_model::Model;
+//!
+//! let model = Model::try_from(Tokenizer::default().parse(
+//!     "Basic DEFINITIONS AUTOMATIC TAGS ::= BEGIN Small ::= INTEGER (0..255) END",
+//! ))
+//! .unwrap()
+//! .try_resolve()
+//! .unwrap();
+//!
+//! let bytes = Schema::from(vec![model]).to_bytes();
+//! let schema = Schema::from_bytes(&bytes).unwrap();
+//! assert!(schema.definition("Basic", "Small").is_some());
+//! ```
+
+use crate::asn::{Asn, BitString, Charset, Choice, ChoiceVariant, ComponentTypeList};
+use crate::asn::{Enumerated, EnumeratedVariant, Integer, Range, Size, Tag, Type};
+use crate::asn::{ObjectIdentifier, ObjectIdentifierComponent, RealPrecision, Substitution};
+use crate::model::{Definition, Field, Import, LiteralValue, Model, ValueReference};
+use crate::resolve::Resolved;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+/// The first bytes of every schema artifact
+pub const MAGIC: &[u8; 6] = b"ASN1RS";
+
+/// The version of the binary layout, incremented on incompatible changes
+pub const VERSION: u8 = 3;
+
+/// The file extension used for schema artifacts written by the converter
+pub const FILE_EXTENSION: &str = "asn1rs-schema";
+
+/// The nesting depth of types, components and values that [`Schema::from_bytes`] accepts, so
+/// that crafted artifacts fail instead of overflowing the stack
+pub const MAX_NESTING_DEPTH: usize = 128;
+
+#[derive(Debug, Clone, PartialOrd, PartialEq, Eq)]
+pub enum Error {
+    InvalidMagic,
+    UnsupportedVersion(u8),
+    UnexpectedEndOfData(usize),
+    InvalidDiscriminant(&'static str, u8, usize),
+    InvalidUtf8(usize),
+    ValueOverflow(usize),
+    TrailingData(usize),
+    NestingTooDeep(usize),
+}
+
+impl std::error::Error for Error {}
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidMagic => write!(f, "The data is not an asn1rs schema"),
+            Error::UnsupportedVersion(version) => {
+                write!(f, "Unsupported schema version: {}", version)
+            }
+            Error::UnexpectedEndOfData(offset) => {
+                write!(f, "Unexpected end of data at offset {}", offset)
+            }
+            Error::InvalidDiscriminant(what, value, offset) => write!(
+                f,
+                "Invalid discriminant {} for {} at offset {}",
+                value, what, offset
+            ),
+            Error::InvalidUtf8(offset) => write!(f, "Invalid UTF-8 string at offset {}", offset),
+            Error::ValueOverflow(offset) => {
+                write!(f, "Value at offset {} exceeds the supported range", offset)
+            }
+            Error::TrailingData(offset) => {
+                write!(f, "Unexpected trailing data at offset {}", offset)
+            }
+            Error::NestingTooDeep(offset) => write!(
+                f,
+                "Nesting depth exceeds the limit of {} at offset {}",
+                MAX_NESTING_DEPTH, offset
+            ),
+        }
+    }
+}
+
+/// A set of resolved [`Model`]s that can be stored as and loaded from a binary artifact.
+#[derive(Debug, Clone, Default)]
+pub struct Schema {
+    pub models: Vec<Model<Asn>>,
+}
+
+impl From<Vec<Model<Asn>>> for Schema {
+    fn from(models: Vec<Model<Asn>>) -> Self {
+        Self { models }
+    }
+}
+
+impl Schema {
+    pub fn model(&self, module: &str) -> Option<&Model<Asn>> {
+        self.models.iter().find(|m| m.name == module)
+    }
+
+    pub fn definition(&self, module: &str, name: &str) -> Option<&Definition<Asn>> {
+        self.model(module)?
+            .definitions
+            .iter()
+            .find(|d| d.name() == name)
+    }
+
+    /// Searches all models for a definition with the given name, in the order of the models
+    pub fn find_definition(&self, name: &str) -> Option<(&Model<Asn>, &Definition<Asn>)> {
+        self.models.iter().find_map(|model| {
+            model
+                .definitions
+                .iter()
+                .find(|d| d.name() == name)
+                .map(|d| (model, d))
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = SchemaWriter::default();
+        writer.bytes.extend_from_slice(MAGIC);
+        writer.u8(VERSION);
+        writer.len(self.models.len());
+        self.models.iter().for_each(|m| writer.model(m));
+        writer.bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = SchemaReader {
+            bytes,
+            offset: 0,
+            depth: 0,
+        };
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(Error::InvalidMagic);
+        }
+        let version = reader.u8()?;
+        if version != VERSION {
+            return Err(Error::UnsupportedVersion(version));
+        }
+        let models = reader.vec(SchemaReader::model)?;
+        if reader.offset < bytes.len() {
+            Err(Error::TrailingData(reader.offset))
+        } else {
+            Ok(Self { models })
+        }
+    }
+}
+
+#[derive(Default)]
+struct SchemaWriter {
+    bytes: Vec<u8>,
+}
+
+impl SchemaWriter {
+    fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.u8(u8::from(value));
+    }
+
+    /// LEB128 encoding of an unsigned value
+    fn u64(&mut self, mut value: u64) {
+        loop {
+            let byte = (value & 0x7F) as u8;
+            value >>= 7;
+            if value == 0 {
+                self.u8(byte);
+                break;
+            } else {
+                self.u8(byte | 0x80);
+            }
+        }
+    }
+
+    /// zig-zag encoding of a signed value
+    fn i64(&mut self, value: i64) {
+        self.u64(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    fn len(&mut self, len: usize) {
+        self.u64(len as u64);
+    }
+
+    fn opt_len(&mut self, value: Option<usize>) {
+        self.option(value.as_ref(), |w, v| w.len(*v));
+    }
+
+    fn str(&mut self, value: &str) {
+        self.len(value.len());
+        self.bytes.extend_from_slice(value.as_bytes());
+    }
+
+    fn option<T>(&mut self, value: Option<&T>, f: impl FnOnce(&mut Self, &T)) {
+        match value {
+            None => self.bool(false),
+            Some(value) => {
+                self.bool(true);
+                f(self, value)
+            }
+        }
+    }
+
+    fn slice<T>(&mut self, values: &[T], mut f: impl FnMut(&mut Self, &T)) {
+        self.len(values.len());
+        values.iter().for_each(|v| f(self, v));
+    }
+
+    fn model(&mut self, model: &Model<Asn>) {
+        self.str(&model.name);
+        self.option(model.oid.as_ref(), Self::oid);
+        self.slice(&model.imports, Self::import);
+        self.slice(&model.definitions, |w, d| {
+            w.str(d.name());
+            w.asn(d.value());
+        });
+        self.slice(&model.value_references, Self::value_reference);
+    }
+
+    fn oid(&mut self, oid: &ObjectIdentifier) {
+        self.slice(&oid.0, |w, c| match c {
+            ObjectIdentifierComponent::NameForm(name) => {
+                w.u8(0);
+                w.str(name);
+            }
+            ObjectIdentifierComponent::NumberForm(number) => {
+                w.u8(1);
+                w.u64(*number);
+            }
+            ObjectIdentifierComponent::NameAndNumberForm(name, number) => {
+                w.u8(2);
+                w.str(name);
+                w.u64(*number);
+            }
+        });
+    }
+
+    fn import(&mut self, import: &Import) {
+        self.slice(&import.what, |w, what| w.str(what));
+        self.str(&import.from);
+        self.option(import.from_oid.as_ref(), Self::oid);
+    }
+
+    fn value_reference(&mut self, value_reference: &ValueReference<Asn>) {
+        self.str(&value_reference.name);
+        self.asn(&value_reference.role);
+        self.literal(&value_reference.value);
+    }
+
+    fn asn(&mut self, asn: &Asn) {
+        self.option(asn.tag.as_ref(), |w, t| w.tag(*t));
+        self.r#type(&asn.r#type);
+        self.option(asn.default.as_ref(), Self::literal);
+        self.bool(asn.sensitive);
+        self.option(asn.unit.as_ref(), |w, unit| w.str(unit));
+    }
+
+    fn tag(&mut self, tag: Tag) {
+        let (class, value) = match tag {
+            Tag::Universal(value) => (0, value),
+            Tag::Application(value) => (1, value),
+            Tag::ContextSpecific(value) => (2, value),
+            Tag::Private(value) => (3, value),
+        };
+        self.u8(class);
+        self.len(value);
+    }
+
+    fn literal(&mut self, literal: &LiteralValue) {
+        match literal {
+            LiteralValue::Boolean(value) => {
+                self.u8(0);
+                self.bool(*value);
+            }
+            LiteralValue::String(value) => {
+                self.u8(1);
+                self.str(value);
+            }
+            LiteralValue::Integer(value) => {
+                self.u8(2);
+                self.i64(*value);
+            }
+            LiteralValue::OctetString(value) => {
+                self.u8(3);
+                self.len(value.len());
+                self.bytes.extend_from_slice(value);
+            }
+            LiteralValue::BitString(value, bit_len) => {
+                self.u8(5);
+                self.len(value.len());
+                self.bytes.extend_from_slice(value);
+                self.u64(*bit_len);
+            }
+            LiteralValue::EnumeratedVariant(r#type, variant) => {
+                self.u8(4);
+                self.str(r#type);
+                self.str(variant);
+            }
+            LiteralValue::Sequence(components) => {
+                self.u8(6);
+                self.len(components.len());
+                for (name, value) in components {
+                    self.str(name);
+                    self.literal(value);
+                }
+            }
+            LiteralValue::SequenceOf(values) => {
+                self.u8(7);
+                self.len(values.len());
+                values.iter().for_each(|value| self.literal(value));
+            }
+            LiteralValue::Choice(alternative, value) => {
+                self.u8(8);
+                self.str(alternative);
+                self.literal(value);
+            }
+            LiteralValue::Reference(name) => {
+                self.u8(9);
+                self.str(name);
+            }
+        }
+    }
+
+    fn size(&mut self, size: &Size) {
+        match size {
+            Size::Any => self.u8(0),
+            Size::Fix(len, extensible) => {
+                self.u8(1);
+                self.len(*len);
+                self.bool(*extensible);
+            }
+            Size::Range(min, max, extensible) => {
+                self.u8(2);
+                self.len(*min);
+                self.len(*max);
+                self.bool(*extensible);
+            }
+        }
+    }
+
+    fn charset(&mut self, charset: Charset) {
+        self.u8(match charset {
+            Charset::Utf8 => 0,
+            Charset::Numeric => 1,
+            Charset::Printable => 2,
+            Charset::Ia5 => 3,
+            Charset::Visible => 4,
+            Charset::Bmp => 5,
+            Charset::Teletex => 6,
+        });
+    }
+
+    fn components(&mut self, components: &ComponentTypeList<Resolved>) {
+        self.slice(&components.fields, |w, field| {
+            w.str(&field.name);
+            w.asn(&field.role);
+        });
+        self.opt_len(components.extension_after);
+    }
+
+    fn r#type(&mut self, r#type: &Type) {
+        match r#type {
+            Type::Boolean => self.u8(0),
+            Type::Integer(integer) => {
+                self.u8(1);
+                self.option(integer.range.min().as_ref(), |w, v| w.i64(*v));
+                self.option(integer.range.max().as_ref(), |w, v| w.i64(*v));
+                self.bool(integer.range.extensible());
+                self.slice(&integer.constants, |w, (name, value)| {
+                    w.str(name);
+                    w.i64(*value);
+                });
+            }
+            Type::String(size, charset) => {
+                self.u8(2);
+                self.size(size);
+                self.charset(*charset);
+            }
+            Type::OctetString(size) => {
+                self.u8(3);
+                self.size(size);
+            }
+            Type::Containing(inner, encoded_by) => {
+                self.u8(15);
+                self.r#type(inner);
+                self.option(encoded_by.as_ref(), |w, e| w.str(e));
+            }
+            Type::Substituted(inner, substitution) => {
+                self.u8(16);
+                self.r#type(inner);
+                self.str(&substitution.rust_type);
+                self.option(substitution.via.as_ref(), |w, v| w.str(v));
+            }
+            Type::BitString(bit_string) => {
+                self.u8(4);
+                self.size(&bit_string.size);
+                self.slice(&bit_string.constants, |w, (name, value)| {
+                    w.str(name);
+                    w.u64(*value);
+                });
+            }
+            Type::Null => self.u8(5),
+            Type::Real(precision) => {
+                self.u8(17);
+                self.bool(*precision == RealPrecision::Single);
+            }
+            Type::ObjectIdentifier => self.u8(18),
+            Type::RelativeOid => self.u8(19),
+            Type::OidIri => self.u8(20),
+            Type::Optional(inner) => {
+                self.u8(6);
+                self.r#type(inner);
+            }
+            Type::Default(inner, value) => {
+                self.u8(7);
+                self.r#type(inner);
+                self.literal(value);
+            }
+            Type::Sequence(components) => {
+                self.u8(8);
+                self.components(components);
+            }
+            Type::SequenceOf(inner, size) => {
+                self.u8(9);
+                self.r#type(inner);
+                self.size(size);
+            }
+            Type::Set(components) => {
+                self.u8(10);
+                self.components(components);
+            }
+            Type::SetOf(inner, size) => {
+                self.u8(11);
+                self.r#type(inner);
+                self.size(size);
+            }
+            Type::Enumerated(enumerated) => {
+                self.u8(12);
+                self.len(enumerated.len());
+                enumerated.variants().for_each(|v| {
+                    self.str(v.name());
+                    self.opt_len(v.number());
+                });
+                self.opt_len(enumerated.extension_after_index());
+            }
+            Type::Choice(choice) => {
+                self.u8(13);
+                self.len(choice.len());
+                choice.variants().for_each(|v| {
+                    self.str(v.name());
+                    self.option(v.tag.as_ref(), |w, t| w.tag(*t));
+                    self.r#type(v.r#type());
+                    self.bool(v.sensitive);
+                });
+                self.opt_len(choice.extension_after_index());
+            }
+            Type::TypeReference(name, tag) => {
+                self.u8(14);
+                self.str(name);
+                self.option(tag.as_ref(), |w, t| w.tag(*t));
+            }
+        }
+    }
+}
+
+struct SchemaReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    depth: usize,
+}
+
+impl<'a> SchemaReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(Error::UnexpectedEndOfData(self.offset))?;
+        let slice = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T, Error>) -> Result<T, Error> {
+        if self.depth >= MAX_NESTING_DEPTH {
+            return Err(Error::NestingTooDeep(self.offset));
+        }
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn discriminant(&mut self, what: &'static str, max: u8) -> Result<u8, Error> {
+        let offset = self.offset;
+        let value = self.u8()?;
+        if value > max {
+            Err(Error::InvalidDiscriminant(what, value, offset))
+        } else {
+            Ok(value)
+        }
+    }
+
+    fn bool(&mut self) -> Result<bool, Error> {
+        Ok(self.discriminant("bool", 1)? == 1)
+    }
+
+    fn u64(&mut self) -> Result<u64, Error> {
+        let offset = self.offset;
+        let mut value = 0_u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            let bits = u64::from(byte & 0x7F);
+            if shift == 63 && bits > 1 {
+                return Err(Error::ValueOverflow(offset));
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(Error::ValueOverflow(offset))
+    }
+
+    fn i64(&mut self) -> Result<i64, Error> {
+        let value = self.u64()?;
+        Ok(((value >> 1) as i64) ^ -((value & 1) as i64))
+    }
+
+    fn len(&mut self) -> Result<usize, Error> {
+        let offset = self.offset;
+        usize::try_from(self.u64()?).map_err(|_| Error::ValueOverflow(offset))
+    }
+
+    fn opt_len(&mut self) -> Result<Option<usize>, Error> {
+        self.option(Self::len)
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        let len = self.len()?;
+        let offset = self.offset;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| Error::InvalidUtf8(offset))
+    }
+
+    fn option<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, Error>,
+    ) -> Result<Option<T>, Error> {
+        if self.bool()? {
+            f(self).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn vec<T>(
+        &mut self,
+        mut f: impl FnMut(&mut Self) -> Result<T, Error>,
+    ) -> Result<Vec<T>, Error> {
+        let len = self.len()?;
+        // every element occupies at least one byte, do not trust the length blindly
+        let mut values = Vec::with_capacity(len.min(self.bytes.len() - self.offset));
+        for _ in 0..len {
+            values.push(f(self)?);
+        }
+        Ok(values)
+    }
+
+    fn model(&mut self) -> Result<Model<Asn>, Error> {
+        Ok(Model {
+            name: self.string()?,
+            oid: self.option(Self::oid)?,
+            automatic_tags: false,
+            imports: self.vec(Self::import)?,
+            definitions: self.vec(|r| Ok(Definition(r.string()?, r.asn()?)))?,
+            value_references: self.vec(Self::value_reference)?,
+            invisible_constraints: Vec::default(),
+            ignored_annotations: Vec::default(),
+            inline_definitions: Vec::default(),
+            asn_names: Vec::default(),
+        })
+    }
+
+    fn oid(&mut self) -> Result<ObjectIdentifier, Error> {
+        Ok(ObjectIdentifier(self.vec(|r| {
+            Ok(match r.discriminant("ObjectIdentifierComponent", 2)? {
+                0 => ObjectIdentifierComponent::NameForm(r.string()?),
+                1 => ObjectIdentifierComponent::NumberForm(r.u64()?),
+                _ => ObjectIdentifierComponent::NameAndNumberForm(r.string()?, r.u64()?),
+            })
+        })?))
+    }
+
+    fn import(&mut self) -> Result<Import, Error> {
+        Ok(Import {
+            what: self.vec(Self::string)?,
+            from: self.string()?,
+            from_oid: self.option(Self::oid)?,
+        })
+    }
+
+    fn value_reference(&mut self) -> Result<ValueReference<Asn>, Error> {
+        Ok(ValueReference {
+            name: self.string()?,
+            role: self.asn()?,
+            value: self.literal()?,
+        })
+    }
+
+    fn asn(&mut self) -> Result<Asn, Error> {
+        Ok(Asn {
+            tag: self.option(Self::tag)?,
+            r#type: self.r#type()?,
+            default: self.option(Self::literal)?,
+            sensitive: self.bool()?,
+            unit: self.option(Self::string)?,
+            // not known to the descriptors and therefore not part of the schema
+            components: None,
+        })
+    }
+
+    fn tag(&mut self) -> Result<Tag, Error> {
+        let class = self.discriminant("Tag", 3)?;
+        let value = self.len()?;
+        Ok(match class {
+            0 => Tag::Universal(value),
+            1 => Tag::Application(value),
+            2 => Tag::ContextSpecific(value),
+            _ => Tag::Private(value),
+        })
+    }
+
+    fn literal(&mut self) -> Result<LiteralValue, Error> {
+        self.nested(Self::literal_content)
+    }
+
+    fn literal_content(&mut self) -> Result<LiteralValue, Error> {
+        Ok(match self.discriminant("LiteralValue", 9)? {
+            0 => LiteralValue::Boolean(self.bool()?),
+            1 => LiteralValue::String(self.string()?),
+            2 => LiteralValue::Integer(self.i64()?),
+            3 => {
+                let len = self.len()?;
+                LiteralValue::OctetString(self.take(len)?.to_vec())
+            }
+            4 => LiteralValue::EnumeratedVariant(self.string()?, self.string()?),
+            5 => {
+                let len = self.len()?;
+                let bytes = self.take(len)?.to_vec();
+                LiteralValue::BitString(bytes, self.u64()?)
+            }
+            6 => LiteralValue::Sequence(self.vec(|r| Ok((r.string()?, r.literal()?)))?),
+            7 => LiteralValue::SequenceOf(self.vec(Self::literal)?),
+            8 => LiteralValue::Choice(self.string()?, Box::new(self.literal()?)),
+            _ => LiteralValue::Reference(self.string()?),
+        })
+    }
+
+    fn size(&mut self) -> Result<Size, Error> {
+        Ok(match self.discriminant("Size", 2)? {
+            0 => Size::Any,
+            1 => Size::Fix(self.len()?, self.bool()?),
+            _ => Size::Range(self.len()?, self.len()?, self.bool()?),
+        })
+    }
+
+    fn charset(&mut self) -> Result<Charset, Error> {
+        Ok(match self.discriminant("Charset", 6)? {
+            0 => Charset::Utf8,
+            1 => Charset::Numeric,
+            2 => Charset::Printable,
+            3 => Charset::Ia5,
+            4 => Charset::Visible,
+            5 => Charset::Bmp,
+            _ => Charset::Teletex,
+        })
+    }
+
+    fn components(&mut self) -> Result<ComponentTypeList<Resolved>, Error> {
+        self.nested(Self::components_content)
+    }
+
+    fn components_content(&mut self) -> Result<ComponentTypeList<Resolved>, Error> {
+        Ok(ComponentTypeList {
+            fields: self.vec(|r| {
+                Ok(Field {
+                    name: r.string()?,
+                    role: r.asn()?,
+                })
+            })?,
+            extension_after: self.opt_len()?,
+        })
+    }
+
+    fn r#type(&mut self) -> Result<Type, Error> {
+        self.nested(Self::type_content)
+    }
+
+    fn type_content(&mut self) -> Result<Type, Error> {
+        Ok(match self.discriminant("Type", 20)? {
+            0 => Type::Boolean,
+            1 => Type::Integer(Integer {
+                range: Range(
+                    self.option(Self::i64)?,
+                    self.option(Self::i64)?,
+                    self.bool()?,
+                ),
+                constants: self.vec(|r| Ok((r.string()?, r.i64()?)))?,
+            }),
+            2 => Type::String(self.size()?, self.charset()?),
+            3 => Type::OctetString(self.size()?),
+            4 => Type::BitString(BitString {
+                size: self.size()?,
+                constants: self.vec(|r| Ok((r.string()?, r.u64()?)))?,
+            }),
+            5 => Type::Null,
+            6 => Type::Optional(Box::new(self.r#type()?)),
+            7 => Type::Default(Box::new(self.r#type()?), self.literal()?),
+            8 => Type::Sequence(self.components()?),
+            9 => Type::SequenceOf(Box::new(self.r#type()?), self.size()?),
+            10 => Type::Set(self.components()?),
+            11 => Type::SetOf(Box::new(self.r#type()?), self.size()?),
+            12 => Type::Enumerated(
+                Enumerated::from_variants(self.vec(|r| {
+                    Ok(EnumeratedVariant::from_name(r.string()?).with_number_opt(r.opt_len()?))
+                })?)
+                .with_maybe_extension_after(self.opt_len()?),
+            ),
+            13 => Type::Choice(
+                Choice::from(self.vec(|r| {
+                    Ok(ChoiceVariant {
+                        name: r.string()?,
+                        tag: r.option(Self::tag)?,
+                        r#type: r.r#type()?,
+                        sensitive: r.bool()?,
+                    })
+                })?)
+                .with_maybe_extension_after(self.opt_len()?),
+            ),
+            14 => Type::TypeReference(self.string()?, self.option(Self::tag)?),
+            15 => Type::Containing(Box::new(self.r#type()?), self.option(Self::string)?),
+            16 => Type::Substituted(
+                Box::new(self.r#type()?),
+                Substitution {
+                    rust_type: self.string()?,
+                    via: self.option(Self::string)?,
+                },
+            ),
+            17 => Type::Real(if self.bool()? {
+                RealPrecision::Single
+            } else {
+                RealPrecision::Double
+            }),
+            18 => Type::ObjectIdentifier,
+            19 => Type::RelativeOid,
+            _ => Type::OidIri,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Tokenizer;
+
+    const SCHEMA_ASN: &str = r#"
+        SchemaTest { iso(1) identified-organization(3) test(42) } DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+
+        IMPORTS Other FROM OtherModule { 1 2 3 };
+
+        Neg ::= INTEGER (-1024..1024, ...)
+        Flags ::= BIT STRING { first(0), second(1) } (SIZE(2..8))
+        Kind ::= ENUMERATED { a, b(5), ..., c }
+        Whatever ::= SEQUENCE {
+            name UTF8String (SIZE(1..16)),
+            id [APPLICATION 7] OCTET STRING (SIZE(4)) OPTIONAL,
+            kind Kind DEFAULT b,
+            flag BOOLEAN DEFAULT TRUE,
+            ...,
+            list SEQUENCE (SIZE(0..4, ...)) OF IA5String,
+            choice CHOICE { number INTEGER, nothing NULL, ... }
+        }
+
+        neg-max Neg ::= 1024
+        END
+    "#;
+
+    fn model() -> Model<Asn> {
+        Model::try_from(Tokenizer::default().parse(SCHEMA_ASN))
+            .unwrap()
+            .try_resolve()
+            .unwrap()
+    }
+
+    #[test]
+    pub fn test_schema_round_trip() {
+        let model = model();
+        let bytes = Schema::from(vec![model.clone()]).to_bytes();
+        assert!(bytes.starts_with(MAGIC));
+
+        let schema = Schema::from_bytes(&bytes).unwrap();
+        assert_eq!(1, schema.models.len());
+
+        let loaded = &schema.models[0];
+        assert_eq!(model.name, loaded.name);
+        assert_eq!(model.oid, loaded.oid);
+        assert_eq!(model.imports, loaded.imports);
+        assert_eq!(model.definitions, loaded.definitions);
+        assert_eq!(model.value_references, loaded.value_references);
+    }
+
+    #[test]
+    pub fn test_schema_round_trip_of_structured_values() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r"Structured DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            P ::= SEQUENCE { a INTEGER, b BOOLEAN }
+            Pick ::= CHOICE { number INTEGER, flag BOOLEAN }
+            Numbers ::= SEQUENCE OF INTEGER
+            Holder ::= SEQUENCE {
+                list SEQUENCE OF INTEGER DEFAULT { 1, 2, 3 },
+                pick Pick DEFAULT number : 7
+            }
+            p P ::= { a 1, b TRUE }
+            numbers Numbers ::= { 1, 2, 3 }
+            pick Pick ::= number : 7
+            END",
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap();
+        let bytes = Schema::from(vec![model.clone()]).to_bytes();
+        let schema = Schema::from_bytes(&bytes).unwrap();
+
+        let loaded = &schema.models[0];
+        assert_eq!(model.definitions, loaded.definitions);
+        assert_eq!(model.value_references, loaded.value_references);
+        assert!(matches!(
+            loaded.value_references[0].value,
+            LiteralValue::Sequence(_)
+        ));
+    }
+
+    #[test]
+    pub fn test_schema_lookup() {
+        let schema = Schema::from(vec![model()]);
+        assert!(schema.definition("SchemaTest", "Whatever").is_some());
+        assert!(schema.definition("SchemaTest", "Missing").is_none());
+        assert!(schema.definition("Missing", "Whatever").is_none());
+        assert_eq!(
+            Some("Kind"),
+            schema.find_definition("Kind").map(|(_, d)| d.name())
+        );
+    }
+
+    #[test]
+    pub fn test_schema_rejects_invalid_data() {
+        let bytes = Schema::from(vec![model()]).to_bytes();
+
+        assert_eq!(
+            Err(Error::InvalidMagic),
+            Schema::from_bytes(b"ASN1RX\x01").map(drop)
+        );
+
+        let mut version = bytes.clone();
+        version[MAGIC.len()] = VERSION + 1;
+        assert_eq!(
+            Err(Error::UnsupportedVersion(VERSION + 1)),
+            Schema::from_bytes(&version).map(drop)
+        );
+
+        assert!(matches!(
+            Schema::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(Error::UnexpectedEndOfData(_))
+        ));
+
+        let mut trailing = bytes;
+        trailing.push(0);
+        assert!(matches!(
+            Schema::from_bytes(&trailing),
+            Err(Error::TrailingData(_))
+        ));
+    }
+
+    #[test]
+    pub fn test_schema_rejects_deep_nesting() {
+        let nested = |levels: usize| {
+            let mut bytes = MAGIC.to_vec();
+            bytes.push(VERSION);
+            // one model named "M" without oid and imports, with one definition named "D"
+            bytes.extend_from_slice(&[1, 1, b'M', 0, 0, 1, 1, b'D']);
+            // no tag, then OPTIONAL of OPTIONAL of ... NULL
+            bytes.push(0);
+            bytes.resize(bytes.len() + levels, 6);
+            bytes.push(5);
+            // no default, not sensitive, no unit and no value references
+            bytes.extend_from_slice(&[0, 0, 0, 0]);
+            bytes
+        };
+
+        let schema = Schema::from_bytes(&nested(MAX_NESTING_DEPTH - 1)).unwrap();
+        assert!(matches!(
+            schema.definition("M", "D").map(|d| &d.value().r#type),
+            Some(Type::Optional(_))
+        ));
+        assert!(matches!(
+            Schema::from_bytes(&nested(MAX_NESTING_DEPTH)),
+            Err(Error::NestingTooDeep(_))
+        ));
+        assert!(matches!(
+            Schema::from_bytes(&nested(1_000_000)),
+            Err(Error::NestingTooDeep(_))
+        ));
+    }
+}
@@ -0,0 +1,40 @@
+//! Bit buffers for custom bit-level protocols, independent of any ASN.1 type. These are the
+//! buffers the UPER reader and writer are built upon.
+//!
+//! Bits are numbered from the most significant bit of the first octet on: bit `0` is `0x80` of
+//! `bytes[0]`, bit `9` is `0x40` of `bytes[1]`. Positions and lengths are always given in bits.
+//!
+//!  - [`BitBuffer`] owns its octets and has a separate write and read position. Bits are
+//!    written at the write position - which is also the length of the content - and read
+//!    from the read position up to the write position. Unused bits of the last octet are `0`.
+//!  - [`Bits`] reads the bits of a borrowed slice up to a given length, without copying them.
+//!
+//! The primitive operations are provided by [`BitRead`] and [`BitWrite`], the (un)aligned PER
+//! encoding of integers, lengths and strings on top of them by
+//! [`PackedRead`](crate::protocol::per::PackedRead) and
+//! [`PackedWrite`](crate::protocol::per::PackedWrite).
+//!
+//! ```rust
+//! use asn1rs::bits::{BitBuffer, BitRead, BitWrite, Bits};
+//!
+//! let mut header = BitBuffer::default();
+//! header.write_bits_with_len(&[0b1010_0000], 3).unwrap();
+//!
+//! let mut payload = BitBuffer::default();
+//! payload.write_bits(&[0xFF]).unwrap();
+//!
+//! header.append(&payload).unwrap();
+//! assert_eq!(11, header.bit_len());
+//! assert_eq!(5, header.align_write_position().unwrap());
+//! assert_eq!(&[0b1011_1111, 0b1110_0000], header.content());
+//!
+//! let (head, mut tail) = Bits::from(&header).split_at(3);
+//! assert_eq!(3, head.remaining_bits());
+//! let mut octet = [0_u8];
+//! tail.read_bits(&mut octet).unwrap();
+//! assert_eq!([0xFF], octet);
+//! ```
+
+pub use crate::protocol::per::unaligned::buffer::{BitBuffer, Bits};
+pub use crate::protocol::per::unaligned::{BitRead, BitWrite, ScopedBitRead, BYTE_LEN};
+pub use crate::protocol::per::Error;
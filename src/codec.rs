@@ -0,0 +1,123 @@
+//! A [`tokio_util::codec`] for UPER encoded messages, so that a [`Framed`] stream of an
+//! asynchronous transport - such as a TCP connection - yields and accepts the generated types.
+//! Each message is prefixed by the number of its octets, as [`LengthDelimitedCodec`] does.
+//!
+//! ```rust
+//! use asn1rs::prelude::*;
+//! use asn1rs::codec::UperCodec;
+//! use bytes::BytesMut;
+//! use tokio_util::codec::{Decoder, Encoder};
+//!
+//! #[asn(sequence)]
+//! #[derive(Debug, Default, PartialEq)]
+//! pub struct Ping {
+//!     #[asn(integer(0..255))]
+//!     id: u8,
+//! }
+//!
+//! let mut codec = UperCodec::<Ping>::new();
+//! let mut frames = BytesMut::new();
+//! codec.encode(Ping { id: 42 }, &mut frames).unwrap();
+//! assert_eq!(&[0x00, 0x00, 0x00, 0x01, 42][..], &frames[..]);
+//! assert_eq!(Some(Ping { id: 42 }), codec.decode(&mut frames).unwrap());
+//! ```
+//!
+//! [`Framed`]: tokio_util::codec::Framed
+
+use crate::descriptor::{Readable, Reader, Writable, Writer};
+use crate::protocol::per::unaligned::BYTE_LEN;
+use crate::rw::{UperReader, UperWriter};
+use crate::Error;
+use bytes::{Bytes, BytesMut};
+use core::marker::PhantomData;
+use tokio_util::codec::{Decoder, Encoder, LengthDelimitedCodec};
+
+/// Decodes and encodes messages of the type `T`, each as one length-prefixed frame. By default,
+/// the prefix is a big-endian `u32` and frames are limited to 8 MiB, other framings are created
+/// from a [`LengthDelimitedCodec`] with [`UperCodec::from`].
+///
+/// To decode other messages than encoded, such as requests and responses of a service, split the
+/// transport into a `FramedRead` and a `FramedWrite` with a codec of each type.
+pub struct UperCodec<T> {
+    frames: LengthDelimitedCodec,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> UperCodec<T> {
+    pub fn new() -> Self {
+        Self::from(LengthDelimitedCodec::new())
+    }
+
+    /// The most octets of a message, longer messages are neither decoded nor encoded
+    pub fn max_frame_length(&self) -> usize {
+        self.frames.max_frame_length()
+    }
+
+    pub fn set_max_frame_length(&mut self, max_frame_length: usize) {
+        self.frames.set_max_frame_length(max_frame_length)
+    }
+}
+
+impl<T> Default for UperCodec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<LengthDelimitedCodec> for UperCodec<T> {
+    fn from(frames: LengthDelimitedCodec) -> Self {
+        Self {
+            frames,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for UperCodec<T> {
+    fn clone(&self) -> Self {
+        Self::from(self.frames.clone())
+    }
+}
+
+impl<T> core::fmt::Debug for UperCodec<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("UperCodec")
+            .field("frames", &self.frames)
+            .finish()
+    }
+}
+
+impl<T: Readable> Decoder for UperCodec<T> {
+    type Item = T;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.frames.decode(src)? {
+            Some(frame) => {
+                let mut reader = UperReader::from((&frame[..], frame.len() * BYTE_LEN));
+                Ok(Some(reader.read::<T>()?))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T: Writable> Encoder<&T> for UperCodec<T> {
+    type Error = Error;
+
+    fn encode(&mut self, item: &T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let mut writer = UperWriter::default();
+        writer.write(item)?;
+        self.frames
+            .encode(Bytes::from(writer.into_bytes_vec()), dst)?;
+        Ok(())
+    }
+}
+
+impl<T: Writable> Encoder<T> for UperCodec<T> {
+    type Error = Error;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        self.encode(&item, dst)
+    }
+}
@@ -1,10 +1,17 @@
-use asn1rs_model::asn::MultiModuleResolver;
+use asn1rs_model::asn::{
+    AnnotationProfile, IgnoredAnnotation, InvisibleConstraint, MultiModuleResolver,
+    OctetStringType, StringType, Substitutions,
+};
 use asn1rs_model::generate::rust::RustCodeGenerator as RustGenerator;
 use asn1rs_model::generate::Generator;
+use asn1rs_model::overlay::{overlay, OverlayDiagnostic};
 use asn1rs_model::parse::Tokenizer;
 use asn1rs_model::Model;
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 use std::path::Path;
+use std::time::{Duration, Instant};
 
 #[derive(Debug)]
 pub enum Error {
@@ -14,6 +21,9 @@ pub enum Error {
     Model(asn1rs_model::parse::Error),
     Io(std::io::Error),
     ResolveFailure(asn1rs_model::resolve::Error),
+    Substitution(asn1rs_model::asn::SubstitutionError),
+    /// The name of an overlay module, for which no module of the same name is loaded
+    UnknownOverlayBase(String),
 }
 
 #[cfg(feature = "protobuf")]
@@ -41,80 +51,674 @@ impl From<asn1rs_model::resolve::Error> for Error {
     }
 }
 
+impl From<asn1rs_model::asn::SubstitutionError> for Error {
+    fn from(e: asn1rs_model::asn::SubstitutionError) -> Self {
+        Error::Substitution(e)
+    }
+}
+
+/// The name of a module and the names and contents of the files generated for it
+type ModuleFiles = (String, Vec<(String, String)>);
+
+/// A file in the destination directory of the converter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrittenFile {
+    pub name: String,
+    /// Whether the content differs from the previous content of the file. Files with identical
+    /// content are not rewritten, so that their modification time is kept and tools like cargo
+    /// do not consider them changed.
+    pub changed: bool,
+}
+
+impl WrittenFile {
+    fn write_if_changed(directory: &Path, name: String, content: &[u8]) -> Result<Self, Error> {
+        let path = directory.join(&name);
+        let changed = match ::std::fs::read(&path) {
+            Ok(previous) => previous != content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => true,
+            Err(e) => return Err(e.into()),
+        };
+        if changed {
+            ::std::fs::write(path, content)?;
+        }
+        Ok(Self { name, changed })
+    }
+}
+
+/// The phases of a conversion that are measured by a [`Profile`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Phase {
+    Tokenize,
+    Parse,
+    Resolve,
+    Convert,
+    Generate,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 5] = [
+        Phase::Tokenize,
+        Phase::Parse,
+        Phase::Resolve,
+        Phase::Convert,
+        Phase::Generate,
+    ];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Phase::Tokenize => "tokenize",
+            Phase::Parse => "parse",
+            Phase::Resolve => "resolve",
+            Phase::Convert => "convert",
+            Phase::Generate => "generate",
+        }
+    }
+}
+
+/// The time spent per [`Phase`] and generating each definition, see
+/// [`Converter::enable_profiling`]
+#[derive(Debug, Default, Clone)]
+pub struct Profile {
+    phases: Vec<(Phase, Duration)>,
+    definitions: Vec<(String, String, Duration)>,
+}
+
+impl Profile {
+    /// The total time spent in the given phase
+    pub fn phase(&self, phase: Phase) -> Duration {
+        self.phases
+            .iter()
+            .filter(|(p, _)| *p == phase)
+            .map(|(_, duration)| *duration)
+            .sum()
+    }
+
+    /// The module name, definition name and time spent generating the code of each definition
+    pub fn definitions(&self) -> impl Iterator<Item = (&str, &str, Duration)> {
+        self.definitions
+            .iter()
+            .map(|(module, definition, duration)| (module.as_str(), definition.as_str(), *duration))
+    }
+
+    fn add_phase(&mut self, phase: Phase, duration: Duration) {
+        self.phases.push((phase, duration));
+    }
+
+    fn add_definition(&mut self, module: &str, definition: &str, duration: Duration) {
+        self.definitions
+            .push((module.to_string(), definition.to_string(), duration));
+    }
+}
+
+impl Display for Profile {
+    /// Lists the phases in order and the definitions with the slowest first
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Time spent per phase:")?;
+        for phase in Phase::ALL {
+            writeln!(f, "  {:<10} {:?}", phase.name(), self.phase(phase))?;
+        }
+        if !self.definitions.is_empty() {
+            let mut definitions = self.definitions().collect::<Vec<_>>();
+            definitions.sort_by_key(|d| std::cmp::Reverse(d.2));
+            writeln!(f, "Time spent generating each definition:")?;
+            for (module, definition, duration) in definitions {
+                writeln!(f, "  {}.{} {:?}", module, definition, duration)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Default)]
 pub struct Converter {
     models: MultiModuleResolver,
+    substitutions: Substitutions,
+    annotation_profile: AnnotationProfile,
+    profile: Option<RefCell<Profile>>,
 }
 
 impl Converter {
     pub fn load_file<F: AsRef<Path>>(&mut self, file: F) -> Result<(), Error> {
         let input = ::std::fs::read_to_string(file)?;
-        let tokens = Tokenizer.parse(&input);
-        let model = Model::try_from(tokens)?;
+        self.load_str(&input)
+    }
+
+    /// Loads the ASN.1 module(s) from the given string instead of a file
+    pub fn load_str(&mut self, asn: &str) -> Result<(), Error> {
+        let tokens = self.timed(Phase::Tokenize, || {
+            Tokenizer.parse_with_profile(asn, &self.annotation_profile)
+        });
+        let model = self.timed(Phase::Parse, || Model::try_from(tokens))?;
         self.models.push(model);
         Ok(())
     }
 
+    /// Loads the ASN.1 module from the given file as overlay of the loaded module with the same
+    /// name, see [`asn1rs_model::overlay`]
+    pub fn load_overlay_file<F: AsRef<Path>>(
+        &mut self,
+        file: F,
+    ) -> Result<Vec<OverlayDiagnostic>, Error> {
+        let input = ::std::fs::read_to_string(file)?;
+        self.load_overlay_str(&input)
+    }
+
+    /// Loads the ASN.1 module from the given string as overlay of the loaded module with the
+    /// same name, see [`asn1rs_model::overlay`]
+    pub fn load_overlay_str(&mut self, asn: &str) -> Result<Vec<OverlayDiagnostic>, Error> {
+        let tokens = self.timed(Phase::Tokenize, || {
+            Tokenizer.parse_with_profile(asn, &self.annotation_profile)
+        });
+        let patch = self.timed(Phase::Parse, || Model::try_from(tokens))?;
+        match self.models.model_mut(&patch.name) {
+            Some(base) => Ok(overlay(base, patch)),
+            None => Err(Error::UnknownOverlayBase(patch.name)),
+        }
+    }
+
+    /// Extracts annotations from the plain comments of the ASN.1 modules loaded afterwards
+    pub fn set_annotation_profile(&mut self, profile: AnnotationProfile) {
+        self.annotation_profile = profile;
+    }
+
+    /// Measures the time spent in each [`Phase`] of the following loads and conversions and for
+    /// generating each definition. Generating the definitions is done a second time for the
+    /// latter, so that profiling slows down the conversion.
+    pub fn enable_profiling(&mut self) {
+        self.profile.get_or_insert_with(Default::default);
+    }
+
+    /// The measurements since profiling has been enabled, if at all
+    pub fn profile(&self) -> Option<Profile> {
+        self.profile
+            .as_ref()
+            .map(|profile| profile.borrow().clone())
+    }
+
+    fn timed<T>(&self, phase: Phase, f: impl FnOnce() -> T) -> T {
+        if let Some(profile) = &self.profile {
+            let start = Instant::now();
+            let result = f();
+            profile.borrow_mut().add_phase(phase, start.elapsed());
+            result
+        } else {
+            f()
+        }
+    }
+
+    /// The constraints of all loaded modules that are not PER-visible, with the module name
+    pub fn invisible_constraints(&self) -> impl Iterator<Item = (&str, &InvisibleConstraint)> {
+        self.models.models().iter().flat_map(|model| {
+            model
+                .invisible_constraints
+                .iter()
+                .map(move |constraint| (model.name.as_str(), constraint))
+        })
+    }
+
+    /// The annotation comments of all loaded modules that are not applied, with the module name
+    pub fn ignored_annotations(&self) -> impl Iterator<Item = (&str, &IgnoredAnnotation)> {
+        self.models.models().iter().flat_map(|model| {
+            model
+                .ignored_annotations
+                .iter()
+                .map(move |annotation| (model.name.as_str(), annotation))
+        })
+    }
+
+    /// Checks that the imports of all loaded modules refer to loaded modules that declare the
+    /// imported items, so that their tags and constraints can be resolved across modules
+    pub fn link(&self) -> Result<(), asn1rs_model::resolve::Error> {
+        self.models.link()
+    }
+
+    /// Loads the [`Substitutions`] that are applied to the generated Rust code
+    pub fn load_substitutions<F: AsRef<Path>>(&mut self, file: F) -> Result<(), Error> {
+        let input = ::std::fs::read_to_string(file)?;
+        self.substitutions = Substitutions::parse(&input)?
+            .with_string_type(self.substitutions.string_type())
+            .with_octet_string_type(self.substitutions.octet_string_type());
+        Ok(())
+    }
+
+    /// Sets the Rust type of the string fields of the generated Rust code
+    pub fn set_string_type(&mut self, string_type: StringType) {
+        self.substitutions.set_string_type(string_type);
+    }
+
+    /// Sets the Rust type of the `OCTET STRING` fields of the generated Rust code
+    pub fn set_octet_string_type(&mut self, octet_string_type: OctetStringType) {
+        self.substitutions.set_octet_string_type(octet_string_type);
+    }
+
+    pub fn set_substitutions(&mut self, substitutions: Substitutions) {
+        self.substitutions = substitutions;
+    }
+
     pub fn to_rust<D: AsRef<Path>, A: Fn(&mut RustGenerator)>(
         &self,
         directory: D,
         custom_adjustments: A,
-    ) -> Result<HashMap<String, Vec<String>>, Error> {
-        let models = self.models.try_resolve_all()?;
+    ) -> Result<HashMap<String, Vec<WrittenFile>>, Error> {
+        Self::write_files(directory, self.rust_files(custom_adjustments)?)
+    }
+
+    /// Generates the Rust code like [`Converter::to_rust`], but returns the file names and their
+    /// content instead of writing them to the filesystem
+    pub fn to_rust_string<A: Fn(&mut RustGenerator)>(
+        &self,
+        custom_adjustments: A,
+    ) -> Result<Vec<(String, String)>, Error> {
+        Ok(Self::flatten(self.rust_files(custom_adjustments)?))
+    }
+
+    /// The generated files per module name
+    fn rust_files<A: Fn(&mut RustGenerator)>(
+        &self,
+        custom_adjustments: A,
+    ) -> Result<Vec<ModuleFiles>, Error> {
+        let models = self.timed(Phase::Resolve, || {
+            let mut models = self.models.try_resolve_all()?;
+            models
+                .iter_mut()
+                .for_each(|model| self.substitutions.apply(model));
+            Ok::<_, Error>(models)
+        })?;
         let scope = models.iter().collect::<Vec<_>>();
-        let mut files = HashMap::with_capacity(models.len());
 
-        for model in &models {
-            let mut generator = RustGenerator::default();
-            generator.add_model(model.to_rust_with_scope(&scope[..]));
+        models
+            .iter()
+            .map(|model| {
+                let mut generator = RustGenerator::default();
+                generator
+                    .add_model(self.timed(Phase::Convert, || model.to_rust_with_scope(&scope[..])));
+
+                custom_adjustments(&mut generator);
+
+                let files = self
+                    .timed(Phase::Generate, || generator.to_string())
+                    .map_err(|_| Error::RustGenerator)?;
 
-            custom_adjustments(&mut generator);
+                if let Some(profile) = &self.profile {
+                    for definition in &generator.models()[0].definitions {
+                        let start = Instant::now();
+                        generator.definition_to_string(definition);
+                        profile.borrow_mut().add_definition(
+                            &model.name,
+                            &definition.0,
+                            start.elapsed(),
+                        );
+                    }
+                }
+
+                Ok((model.name.clone(), files))
+            })
+            .collect()
+    }
 
+    fn write_files<D: AsRef<Path>>(
+        directory: D,
+        modules: Vec<ModuleFiles>,
+    ) -> Result<HashMap<String, Vec<WrittenFile>>, Error> {
+        let mut files = HashMap::with_capacity(modules.len());
+        for (module, contents) in modules {
             files.insert(
-                model.name.clone(),
-                generator
-                    .to_string()
-                    .map_err(|_| Error::RustGenerator)?
+                module,
+                contents
                     .into_iter()
                     .map(|(file, content)| {
-                        ::std::fs::write(directory.as_ref().join(&file), content)?;
-                        Ok::<_, Error>(file)
+                        WrittenFile::write_if_changed(directory.as_ref(), file, content.as_bytes())
                     })
                     .collect::<Result<Vec<_>, _>>()?,
             );
         }
-
         Ok(files)
     }
 
-    #[cfg(feature = "protobuf")]
-    pub fn to_protobuf<D: AsRef<Path>>(
+    fn flatten(modules: Vec<ModuleFiles>) -> Vec<(String, String)> {
+        modules
+            .into_iter()
+            .flat_map(|(_module, files)| files)
+            .collect()
+    }
+
+    /// Writes the resolved models as binary schema artifacts (see [`asn1rs_model::schema`]).
+    /// Each artifact starts with its own model followed by all other loaded models, so that
+    /// imported definitions can be looked up without loading further artifacts.
+    pub fn to_schema<D: AsRef<Path>>(
         &self,
         directory: D,
-    ) -> Result<HashMap<String, Vec<String>>, Error> {
-        use asn1rs_model::protobuf::ToProtobufModel;
+    ) -> Result<HashMap<String, Vec<WrittenFile>>, Error> {
+        use asn1rs_model::schema::{Schema, FILE_EXTENSION};
 
-        let models = self.models.try_resolve_all()?;
-        let scope = models.iter().collect::<Vec<_>>();
+        let models = self.timed(Phase::Resolve, || self.models.try_resolve_all())?;
         let mut files = HashMap::with_capacity(models.len());
 
         for model in &models {
-            let mut generator = asn1rs_model::generate::protobuf::ProtobufDefGenerator::default();
-            generator.add_model(model.to_rust_with_scope(&scope[..]).to_protobuf());
-
+            let schema = Schema::from(
+                std::iter::once(model)
+                    .chain(models.iter().filter(|m| m.name != model.name))
+                    .cloned()
+                    .collect::<Vec<_>>(),
+            );
+            let file = format!(
+                "{}.{}",
+                RustGenerator::rust_module_name(&model.name),
+                FILE_EXTENSION
+            );
             files.insert(
                 model.name.clone(),
-                generator
-                    .to_string()?
-                    .into_iter()
-                    .map(|(file, content)| {
-                        ::std::fs::write(directory.as_ref().join(&file), content)?;
-                        Ok::<_, Error>(file)
-                    })
-                    .collect::<Result<Vec<_>, _>>()?,
+                vec![WrittenFile::write_if_changed(
+                    directory.as_ref(),
+                    file,
+                    &schema.to_bytes(),
+                )?],
             );
         }
 
         Ok(files)
     }
+
+    /// Writes a JSON catalog of the definitions of each resolved model, including the flattened
+    /// fields, their constraints and units (see [`asn1rs_model::catalog`]).
+    pub fn to_catalog<D: AsRef<Path>>(
+        &self,
+        directory: D,
+    ) -> Result<HashMap<String, Vec<WrittenFile>>, Error> {
+        use asn1rs_model::catalog::{Catalog, FILE_EXTENSION};
+
+        let models = self.timed(Phase::Resolve, || self.models.try_resolve_all())?;
+        let mut files = HashMap::with_capacity(models.len());
+
+        for model in models {
+            let file = format!(
+                "{}.{}",
+                RustGenerator::rust_module_name(&model.name),
+                FILE_EXTENSION
+            );
+            let name = model.name.clone();
+            let catalog = Catalog::from(vec![model]);
+            files.insert(
+                name,
+                vec![WrittenFile::write_if_changed(
+                    directory.as_ref(),
+                    file,
+                    catalog.to_json().as_bytes(),
+                )?],
+            );
+        }
+
+        Ok(files)
+    }
+
+    /// Reports the patterns of the resolved models that blow up the size of their PER encoding
+    /// (see [`asn1rs_model::lint`])
+    pub fn lint(&self) -> Result<Vec<asn1rs_model::lint::Lint>, Error> {
+        let models = self.timed(Phase::Resolve, || self.models.try_resolve_all())?;
+        Ok(asn1rs_model::lint::lint(&models))
+    }
+
+    #[cfg(feature = "protobuf")]
+    pub fn to_protobuf<D: AsRef<Path>>(
+        &self,
+        directory: D,
+    ) -> Result<HashMap<String, Vec<WrittenFile>>, Error> {
+        Self::write_files(directory, self.protobuf_files()?)
+    }
+
+    /// Generates the protobuf definitions like [`Converter::to_protobuf`], but returns the file
+    /// names and their content instead of writing them to the filesystem
+    #[cfg(feature = "protobuf")]
+    pub fn to_protobuf_string(&self) -> Result<Vec<(String, String)>, Error> {
+        Ok(Self::flatten(self.protobuf_files()?))
+    }
+
+    #[cfg(feature = "protobuf")]
+    fn protobuf_files(&self) -> Result<Vec<ModuleFiles>, Error> {
+        use asn1rs_model::protobuf::ToProtobufModel;
+
+        let models = self.timed(Phase::Resolve, || self.models.try_resolve_all())?;
+        let scope = models.iter().collect::<Vec<_>>();
+
+        models
+            .iter()
+            .map(|model| {
+                let mut generator =
+                    asn1rs_model::generate::protobuf::ProtobufDefGenerator::default();
+                generator.add_model(self.timed(Phase::Convert, || {
+                    model.to_rust_with_scope(&scope[..]).to_protobuf()
+                }));
+                Ok((
+                    model.name.clone(),
+                    self.timed(Phase::Generate, || generator.to_string())?,
+                ))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asn1rs_model::resolve::Error as ResolveError;
+
+    const ASN: &str = r"InMemory DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+        Ping ::= SEQUENCE { id INTEGER (0..255) }
+        END";
+
+    #[test]
+    fn test_to_rust_string_does_not_need_files() {
+        let mut converter = Converter::default();
+        converter.load_str(ASN).unwrap();
+
+        let files = converter.to_rust_string(|_| {}).unwrap();
+        assert_eq!(1, files.len());
+        assert_eq!("in_memory.rs", files[0].0);
+        assert!(files[0].1.contains("pub struct Ping {"));
+    }
+
+    #[test]
+    fn test_catalog_is_written_per_module() {
+        let directory = std::env::temp_dir().join(format!("asn1rs-catalog-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let mut converter = Converter::default();
+        converter.load_str(ASN).unwrap();
+        let files = converter.to_catalog(&directory).unwrap();
+        let json = std::fs::read_to_string(directory.join("in_memory.catalog.json")).unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!("in_memory.catalog.json", files["InMemory"][0].name);
+        assert!(json.contains(r#""name": "Ping""#));
+        assert!(json.contains(r#""path": "id""#));
+    }
+
+    #[test]
+    fn test_annotation_profile_applies_to_later_loads() {
+        let mut converter = Converter::default();
+        converter.set_annotation_profile(AnnotationProfile::etsi());
+        converter
+            .load_str(
+                r"InMemory DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Ping ::= SEQUENCE { delay INTEGER (0..255) -- Unit: ms
+                }
+                END",
+            )
+            .unwrap();
+
+        let files = converter.to_rust_string(|_| {}).unwrap();
+        assert!(files[0].1.contains(r#"unit("ms")"#));
+    }
+
+    #[test]
+    fn test_ignored_annotations_are_reported() {
+        let mut converter = Converter::default();
+        converter
+            .load_str(
+                r"InMemory DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Ping ::= SEQUENCE { delay INTEGER (0..255) -- @sensitve
+                }
+                END",
+            )
+            .unwrap();
+
+        let ignored = converter.ignored_annotations().collect::<Vec<_>>();
+        assert_eq!(1, ignored.len());
+        assert_eq!("InMemory", ignored[0].0);
+        assert_eq!(
+            "line 2, column 60: Unknown annotation `@sensitve`",
+            ignored[0].1.to_string()
+        );
+    }
+
+    #[test]
+    fn test_unchanged_files_are_not_rewritten() {
+        let directory =
+            std::env::temp_dir().join(format!("asn1rs-unchanged-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let mut converter = Converter::default();
+        converter.load_str(ASN).unwrap();
+        let first = converter.to_rust(&directory, |_| {}).unwrap();
+        let second = converter.to_rust(&directory, |_| {}).unwrap();
+
+        let mut converter = Converter::default();
+        converter
+            .load_str(&ASN.replace("(0..255)", "(0..65535)"))
+            .unwrap();
+        let third = converter.to_rust(&directory, |_| {}).unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        let file = |changed| {
+            vec![WrittenFile {
+                name: "in_memory.rs".to_string(),
+                changed,
+            }]
+        };
+        assert_eq!(&file(true), &first["InMemory"]);
+        assert_eq!(&file(false), &second["InMemory"]);
+        assert_eq!(&file(true), &third["InMemory"]);
+    }
+
+    #[test]
+    fn test_profile_covers_phases_and_definitions() {
+        let mut converter = Converter::default();
+        assert!(converter.profile().is_none());
+
+        converter.enable_profiling();
+        converter.load_str(ASN).unwrap();
+        converter.to_rust_string(|_| {}).unwrap();
+
+        let profile = converter.profile().unwrap();
+        assert_eq!(
+            vec![("InMemory", "Ping")],
+            profile
+                .definitions()
+                .map(|(module, definition, _)| (module, definition))
+                .collect::<Vec<_>>()
+        );
+        let report = profile.to_string();
+        for phase in Phase::ALL {
+            assert!(report.contains(phase.name()));
+        }
+        assert!(report.contains("InMemory.Ping"));
+    }
+
+    const COMMON: &str = r"Common DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+        Identifier ::= [APPLICATION 3] INTEGER (0..255)
+        END";
+
+    #[test]
+    fn test_imports_are_linked_across_loaded_modules() {
+        let mut converter = Converter::default();
+        converter
+            .load_str(
+                r"Vehicle DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                IMPORTS Identifier FROM Common;
+                Vehicle ::= CHOICE { id Identifier, name UTF8String }
+                END",
+            )
+            .unwrap();
+        converter.load_str(COMMON).unwrap();
+        assert_eq!(Ok(()), converter.link());
+
+        let files = converter.to_rust_string(|_| {}).unwrap();
+        // the tag of the imported type is resolved from the other module
+        assert_eq!("vehicle.rs", files[0].0);
+        assert!(files[0]
+            .1
+            .contains("#[asn(complex(Identifier, tag(APPLICATION(3))))]"));
+    }
+
+    #[test]
+    fn test_unresolved_imports_fail_to_link() {
+        let mut converter = Converter::default();
+        converter
+            .load_str(
+                r"Vehicle DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                IMPORTS Identifier, Name FROM Common;
+                Vehicle ::= SEQUENCE { id Identifier }
+                END",
+            )
+            .unwrap();
+        assert_eq!(
+            Err(ResolveError::UnknownImportModule(
+                "Vehicle".to_string(),
+                "Common".to_string()
+            )),
+            converter.link()
+        );
+
+        converter.load_str(COMMON).unwrap();
+        let error = converter.link().unwrap_err();
+        assert_eq!(
+            "Module Vehicle imports Name from Common, which does not declare it",
+            error.to_string()
+        );
+        assert!(matches!(
+            converter.to_rust_string(|_| {}),
+            Err(Error::ResolveFailure(ResolveError::UnknownImportItem(..)))
+        ));
+    }
+
+    #[test]
+    fn test_overlay_replaces_definitions_of_the_loaded_module() {
+        let mut converter = Converter::default();
+        converter.load_str(ASN).unwrap();
+
+        let diagnostics = converter
+            .load_overlay_str(
+                r"InMemory DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Ping ::= SEQUENCE { id INTEGER (0..15) }
+                Pong ::= SEQUENCE { id INTEGER (0..15) }
+                END",
+            )
+            .unwrap();
+        assert_eq!(2, diagnostics.len());
+        assert!(diagnostics
+            .iter()
+            .all(|diagnostic| !diagnostic.is_conflict()));
+
+        let files = converter.to_rust_string(|_| {}).unwrap();
+        assert_eq!(1, files.len());
+        assert!(files[0].1.contains("pub struct Pong"));
+        assert!(files[0].1.contains("integer(0..15)"));
+        assert!(!files[0].1.contains("integer(0..255)"));
+
+        assert!(matches!(
+            converter.load_overlay_str("Unknown DEFINITIONS ::= BEGIN END"),
+            Err(Error::UnknownOverlayBase(name)) if name == "Unknown"
+        ));
+    }
+
+    #[cfg(feature = "protobuf")]
+    #[test]
+    fn test_to_protobuf_string_does_not_need_files() {
+        let mut converter = Converter::default();
+        converter.load_str(ASN).unwrap();
+
+        let files = converter.to_protobuf_string().unwrap();
+        assert_eq!(1, files.len());
+        assert!(files[0].1.contains("message Ping {"));
+    }
 }
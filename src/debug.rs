@@ -0,0 +1,344 @@
+//! Helpers to look into encodings, such as for documentation or interoperability reports.
+//!
+//! ```rust
+//! use asn1rs::prelude::*;
+//!
+//! #[asn(sequence)]
+//! #[derive(Debug, Default, PartialEq)]
+//! pub struct Vehicle {
+//!     #[asn(integer(0..255))]
+//!     speed: u8,
+//!     #[asn(sequence_of(utf8string))]
+//!     passengers: Vec<String>,
+//! }
+//!
+//! let mut writer = UperWriter::default();
+//! writer
+//!     .write(&Vehicle {
+//!         speed: 42,
+//!         passengers: vec!["Jane".to_string()],
+//!     })
+//!     .unwrap();
+//!
+//! let annotation = asn1rs::debug::annotate::<Vehicle>(writer.byte_content()).unwrap();
+//! let paths = annotation
+//!     .regions()
+//!     .iter()
+//!     .map(|region| region.path.to_string())
+//!     .collect::<Vec<_>>();
+//! assert_eq!(vec!["", "speed", "passengers", "passengers[0]"], paths);
+//! println!("{annotation}");
+//! ```
+
+use crate::descriptor::visit::{Path, Segment};
+use crate::descriptor::{choice, Readable, Reader};
+use crate::protocol::per::err::Error;
+use crate::rw::{PositionedReader, UperReader};
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+/// The number of bytes of a region that are shown in hex
+const MAX_HEX_BYTES: usize = 8;
+
+/// The number of bits of a region that are shown in binary
+const MAX_BINARY_BITS: usize = 32;
+
+/// Decodes a value of `T` from the given UPER encoding and labels the bits each (nested) value
+/// was read from with the [`Path`] of the value. The [`Display`] implementation of the result
+/// renders a hex and bit dump with one line per value.
+///
+/// The preamble of a `SEQUENCE` - such as the presence bits of its `OPTIONAL` fields - is only
+/// part of the region of the `SEQUENCE` itself. Values that are not present or that are encoded
+/// in zero bits (such as `NULL`) are not listed. The content of a `CONTAINING` value is
+/// listed as a whole.
+pub fn annotate<T: Readable>(bytes: &[u8]) -> Result<Annotation, Error> {
+    let mut reader = UperReader::from((bytes, bytes.len() * 8)).with_trace();
+    let start = reader.bit_position();
+    reader.read::<T>()?;
+    let end = reader.bit_position();
+
+    let mut regions = reader
+        .take_trace()
+        .map(Trace::into_regions)
+        .unwrap_or_default();
+    regions.push(Region {
+        path: Path::default(),
+        bits: start..end,
+    });
+    // the nested values are recorded before the values they are nested in
+    regions.sort_by(|a, b| {
+        a.bits
+            .start
+            .cmp(&b.bits.start)
+            .then(b.bits.end.cmp(&a.bits.end))
+            .then(a.path.segments().len().cmp(&b.path.segments().len()))
+    });
+
+    Ok(Annotation {
+        type_name: short_type_name::<T>(),
+        bytes: bytes.to_vec(),
+        regions,
+    })
+}
+
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// The bits a value was read from
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Region {
+    /// The location of the value, empty for the decoded value itself
+    pub path: Path,
+    pub bits: Range<usize>,
+}
+
+/// An encoding with the regions of the values it consists of, see [`annotate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    type_name: &'static str,
+    bytes: Vec<u8>,
+    regions: Vec<Region>,
+}
+
+impl Annotation {
+    /// The regions of all values, ordered by their position. A value is listed before the values
+    /// nested in it.
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+
+    /// The region of the value at the given path, such as `passengers[0]`
+    pub fn region(&self, path: &str) -> Option<&Region> {
+        self.regions
+            .iter()
+            .find(|region| region.path.to_string() == path)
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn bit(&self, position: usize) -> bool {
+        self.bytes[position / 8] & (0x80 >> (position % 8)) != 0
+    }
+
+    fn hex(&self, bits: &Range<usize>) -> String {
+        let bytes = &self.bytes[bits.start / 8..bits.end.div_ceil(8)];
+        let mut hex = bytes
+            .iter()
+            .take(MAX_HEX_BYTES)
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if bytes.len() > MAX_HEX_BYTES {
+            hex.push_str(" ..");
+        }
+        hex
+    }
+
+    fn binary(&self, bits: &Range<usize>) -> String {
+        let mut binary = bits
+            .clone()
+            .take(MAX_BINARY_BITS)
+            .map(|position| if self.bit(position) { '1' } else { '0' })
+            .collect::<String>();
+        if bits.len() > MAX_BINARY_BITS {
+            binary.push_str("..");
+        }
+        binary
+    }
+}
+
+impl Display for Annotation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let hex_width = MAX_HEX_BYTES * 3 + 2;
+        let binary_width = MAX_BINARY_BITS + 2;
+        writeln!(
+            f,
+            "{:>13}  {:<hex_width$}  {:<binary_width$}  value",
+            "bits", "bytes", "binary"
+        )?;
+        for region in &self.regions {
+            let depth = region.path.segments().len();
+            writeln!(
+                f,
+                "{:>6}..{:<5}  {:<hex_width$}  {:<binary_width$}  {:indent$}{}",
+                region.bits.start,
+                region.bits.end,
+                self.hex(&region.bits),
+                self.binary(&region.bits),
+                "",
+                if region.path.is_empty() {
+                    self.type_name.to_string()
+                } else {
+                    region.path.to_string()
+                },
+                indent = depth * 2,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The values whose regions are recorded by a [`Trace`]
+#[derive(Debug, Clone)]
+pub(crate) enum Children {
+    /// The fields of a `SEQUENCE` or `SET`, empty if the names are unknown
+    Fields(&'static [&'static str]),
+    /// The elements of a `SEQUENCE OF` or `SET OF`
+    Elements,
+    /// The alternative of a `CHOICE`, named by its index if the name is unknown
+    Alternative(Segment),
+}
+
+impl Children {
+    pub fn alternative<C: choice::Constraint>(index: u64) -> Self {
+        let index = usize::try_from(index).unwrap_or(usize::MAX);
+        Children::Alternative(
+            C::VARIANT_NAMES
+                .get(index)
+                .map(|name| Segment::Field(name))
+                .unwrap_or(Segment::Index(index)),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Frame {
+    children: Children,
+    index: usize,
+    current: Option<(Segment, usize)>,
+}
+
+/// Records the regions of the values read by an [`UperReader`], see
+/// [`UperReader::with_trace`]. The values nested in a value are opened as [`Children`] and each
+/// of them is started by [`Trace::next`] - the region of a child ends where the next one starts or
+/// where its parent is closed.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Trace {
+    frames: Vec<Frame>,
+    regions: Vec<Region>,
+}
+
+impl Trace {
+    pub fn open(&mut self, children: Children) {
+        self.frames.push(Frame {
+            children,
+            index: 0,
+            current: None,
+        });
+    }
+
+    /// Ends the current child at `end` and starts the next one at `start`
+    pub fn next(&mut self, end: usize, start: usize) {
+        self.end_current(end);
+        if let Some(frame) = self.frames.last_mut() {
+            let segment = match frame.children {
+                Children::Fields(names) => names
+                    .get(frame.index)
+                    .map(|name| Segment::Field(name))
+                    .unwrap_or(Segment::Index(frame.index)),
+                Children::Elements => Segment::Index(frame.index),
+                Children::Alternative(segment) => segment,
+            };
+            frame.index += 1;
+            frame.current = Some((segment, start));
+        }
+    }
+
+    pub fn close(&mut self, end: usize) {
+        self.end_current(end);
+        self.frames.pop();
+    }
+
+    fn end_current(&mut self, end: usize) {
+        let Some((segment, start)) = self.frames.last_mut().and_then(|f| f.current.take()) else {
+            return;
+        };
+        if start < end {
+            let mut path = Path::default();
+            for (segment, _) in self
+                .frames
+                .iter()
+                .filter_map(|frame| frame.current.as_ref())
+            {
+                path.push(*segment);
+            }
+            path.push(segment);
+            self.regions.push(Region {
+                path,
+                bits: start..end,
+            });
+        }
+    }
+
+    pub fn into_regions(self) -> Vec<Region> {
+        self.regions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_records_nested_regions() {
+        let mut trace = Trace::default();
+        trace.open(Children::Fields(&["id", "tags"]));
+        trace.next(0, 1);
+        trace.next(3, 3);
+        trace.open(Children::Elements);
+        trace.next(5, 5);
+        trace.next(7, 7);
+        trace.close(7);
+        trace.close(7);
+
+        assert_eq!(
+            vec![
+                ("id".to_string(), 1..3),
+                ("tags[0]".to_string(), 5..7),
+                ("tags".to_string(), 3..7),
+            ],
+            trace
+                .into_regions()
+                .into_iter()
+                .map(|region| (region.path.to_string(), region.bits))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_trace_names_unknown_fields_by_index() {
+        let mut trace = Trace::default();
+        trace.open(Children::Fields(&[]));
+        trace.next(0, 0);
+        trace.open(Children::Alternative(Segment::Field("leaf")));
+        trace.next(2, 2);
+        trace.close(4);
+        trace.close(4);
+
+        assert_eq!(
+            vec!["[0].leaf", "[0]"],
+            trace
+                .into_regions()
+                .iter()
+                .map(|region| region.path.to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_binary_and_hex_of_region() {
+        let annotation = Annotation {
+            type_name: "Test",
+            bytes: vec![0b1010_0000, 0xff],
+            regions: Vec::new(),
+        };
+        assert_eq!("101", annotation.binary(&(0..3)));
+        assert_eq!("a0 ff", annotation.hex(&(6..10)));
+        assert_eq!("a0", annotation.hex(&(0..8)));
+    }
+}
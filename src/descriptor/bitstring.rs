@@ -39,6 +39,8 @@ impl<C: Constraint> ReadableType for BitString<C> {
 }
 
 #[derive(Debug, Default, Clone, PartialOrd, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "(Vec<u8>, u64)"))]
 pub struct BitVec(Vec<u8>, u64);
 
 impl BitVec {
@@ -145,6 +147,64 @@ impl BitVec {
     }
 }
 
+impl From<BitVecConst> for BitVec {
+    fn from(value: BitVecConst) -> Self {
+        BitVec::from_bytes(value.bytes.to_vec(), value.bit_len)
+    }
+}
+
+/// The inverse of [`BitVec::split`], with the unused bits zeroed like by [`BitVec::from_bytes`]
+impl From<(Vec<u8>, u64)> for BitVec {
+    fn from((bytes, bit_len): (Vec<u8>, u64)) -> Self {
+        BitVec::from_bytes(bytes, bit_len)
+    }
+}
+
+impl PartialEq<BitVecConst> for BitVec {
+    fn eq(&self, other: &BitVecConst) -> bool {
+        self.1 == other.bit_len && self.0 == other.bytes
+    }
+}
+
+/// The `const` counterpart of a [`BitVec`], used for value references of `BIT STRING`s
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq, Eq, Hash)]
+pub struct BitVecConst {
+    bytes: &'static [u8],
+    bit_len: u64,
+}
+
+impl BitVecConst {
+    pub const fn new(bytes: &'static [u8], bit_len: u64) -> Self {
+        Self { bytes, bit_len }
+    }
+
+    pub const fn bit_len(&self) -> u64 {
+        self.bit_len
+    }
+
+    pub const fn as_byte_slice(&self) -> &'static [u8] {
+        self.bytes
+    }
+
+    pub fn is_bit_set(&self, bit: u64) -> bool {
+        bit < self.bit_len
+            && self
+                .bytes
+                .get((bit / 8) as usize)
+                .is_some_and(|b| *b & (0x80_u8 >> (bit % 8)) != 0)
+    }
+
+    pub fn to_bit_vec(&self) -> BitVec {
+        BitVec::from(*self)
+    }
+}
+
+impl PartialEq<BitVec> for BitVecConst {
+    fn eq(&self, other: &BitVec) -> bool {
+        other.eq(self)
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
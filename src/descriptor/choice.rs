@@ -8,6 +8,8 @@ pub trait Constraint: super::common::Constraint + Sized {
     const VARIANT_COUNT: u64;
     const STD_VARIANT_COUNT: u64;
     const EXTENSIBLE: bool = false;
+    /// The names of the variants in the order of their index, empty if unknown
+    const VARIANT_NAMES: &'static [&'static str] = &[];
 
     fn to_choice_index(&self) -> u64;
 
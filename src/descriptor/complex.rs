@@ -13,7 +13,7 @@ impl<V: Writable, C: Constraint> WritableType for Complex<V, C> {
         writer: &mut W,
         value: &Self::Type,
     ) -> Result<(), <W as Writer>::Error> {
-        value.write(writer)
+        writer.write_complex::<C, V>(value)
     }
 }
 
@@ -22,6 +22,6 @@ impl<V: Readable, C: Constraint> ReadableType for Complex<V, C> {
 
     #[inline]
     fn read_value<R: Reader>(reader: &mut R) -> Result<Self::Type, <R as Reader>::Error> {
-        V::read(reader)
+        reader.read_complex::<C, V>()
     }
 }
@@ -0,0 +1,179 @@
+use crate::descriptor::octetstring::{self, NoConstraint};
+use crate::descriptor::{ReadableType, Reader, WritableType, Writer};
+use core::any::{Any, TypeId};
+use core::marker::PhantomData;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::Arc;
+
+/// An `OCTET STRING (CONTAINING T ENCODED BY X)`: the value is encoded on its own, the resulting
+/// bytes are passed through the [`Transform`] `X` and the result is written as `OCTET STRING`.
+/// The instance of `X` is the one registered on the writer or reader, see [`RegisterTransform`].
+pub struct Containing<T, X: Transform = Identity, C: octetstring::Constraint = NoConstraint>(
+    PhantomData<T>,
+    PhantomData<X>,
+    PhantomData<C>,
+);
+
+/// User provided hook (such as compression or encryption) that is applied between the encoding
+/// of the contained value and the wrapping `OCTET STRING`.
+pub trait Transform: Send + Sync + 'static {
+    /// Transforms the encoded content into the bytes of the `OCTET STRING`
+    fn encode(&self, content: Vec<u8>) -> Result<Vec<u8>, TransformError>;
+
+    /// Reverts [`Transform::encode`], transforming the bytes of the `OCTET STRING` back into
+    /// the encoded content
+    fn decode(&self, octets: Vec<u8>) -> Result<Vec<u8>, TransformError>;
+
+    /// The instance to use if none is registered on the writer or reader. Stateless transforms
+    /// may return one, the default is to fail.
+    #[inline]
+    fn unregistered() -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+/// The [`Transform`] for plain `CONTAINING` constraints, which passes the content through
+#[derive(Default)]
+pub struct Identity;
+
+impl Transform for Identity {
+    #[inline]
+    fn encode(&self, content: Vec<u8>) -> Result<Vec<u8>, TransformError> {
+        Ok(content)
+    }
+
+    #[inline]
+    fn decode(&self, octets: Vec<u8>) -> Result<Vec<u8>, TransformError> {
+        Ok(octets)
+    }
+
+    #[inline]
+    fn unregistered() -> Option<Self> {
+        Some(Identity)
+    }
+}
+
+/// The [`Transform`] instances of a writer or reader, one per type. Cloning shares the
+/// instances, so that nested `CONTAINING` values are transformed by the same ones.
+#[derive(Default, Clone)]
+pub struct Transforms(HashMap<TypeId, (&'static str, Arc<dyn Any + Send + Sync>)>);
+
+impl Transforms {
+    /// Registers the instance to use for `X`, replacing a previously registered one
+    pub fn register<X: Transform>(&mut self, transform: X) {
+        self.0.insert(
+            TypeId::of::<X>(),
+            (core::any::type_name::<X>(), Arc::new(transform)),
+        );
+    }
+
+    /// The registered instance of `X`
+    pub fn get<X: Transform>(&self) -> Option<&X> {
+        self.0
+            .get(&TypeId::of::<X>())
+            .and_then(|(_, transform)| transform.downcast_ref::<X>())
+    }
+
+    /// Applies [`Transform::encode`] of the registered or the [`Transform::unregistered`]
+    /// instance of `X`
+    pub fn encode<X: Transform>(&self, content: Vec<u8>) -> Result<Vec<u8>, TransformError> {
+        match self.get::<X>() {
+            Some(transform) => transform.encode(content),
+            None => Self::unregistered::<X>()?.encode(content),
+        }
+    }
+
+    /// Applies [`Transform::decode`] of the registered or the [`Transform::unregistered`]
+    /// instance of `X`
+    pub fn decode<X: Transform>(&self, octets: Vec<u8>) -> Result<Vec<u8>, TransformError> {
+        match self.get::<X>() {
+            Some(transform) => transform.decode(octets),
+            None => Self::unregistered::<X>()?.decode(octets),
+        }
+    }
+
+    fn unregistered<X: Transform>() -> Result<X, TransformError> {
+        X::unregistered().ok_or_else(|| {
+            TransformError::new(format!(
+                "no instance of {} is registered",
+                core::any::type_name::<X>()
+            ))
+        })
+    }
+}
+
+impl Debug for Transforms {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set()
+            .entries(self.0.values().map(|(name, _)| name))
+            .finish()
+    }
+}
+
+/// The writers and readers that apply the [`Transform`] instances registered on them
+pub trait RegisterTransform {
+    fn transforms_mut(&mut self) -> &mut Transforms;
+
+    /// Registers the instance to use for every `CONTAINING .. ENCODED BY X`
+    #[inline]
+    fn register_transform<X: Transform>(&mut self, transform: X) {
+        self.transforms_mut().register(transform)
+    }
+
+    /// Registers the instance to use for every `CONTAINING .. ENCODED BY X`
+    #[inline]
+    fn with_transform<X: Transform>(mut self, transform: X) -> Self
+    where
+        Self: Sized,
+    {
+        self.register_transform(transform);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransformError(String);
+
+impl TransformError {
+    pub fn new<T: ToString>(message: T) -> Self {
+        Self(message.to_string())
+    }
+
+    pub fn message(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for TransformError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for TransformError {}
+
+impl<T: WritableType, X: Transform, C: octetstring::Constraint> WritableType
+    for Containing<T, X, C>
+{
+    type Type = T::Type;
+
+    #[inline]
+    fn write_value<W: Writer>(writer: &mut W, value: &Self::Type) -> Result<(), W::Error> {
+        writer.write_containing::<C, X, T>(value)
+    }
+}
+
+impl<T: ReadableType, X: Transform, C: octetstring::Constraint> ReadableType
+    for Containing<T, X, C>
+{
+    type Type = T::Type;
+
+    #[inline]
+    fn read_value<R: Reader>(reader: &mut R) -> Result<Self::Type, <R as Reader>::Error> {
+        reader.read_containing::<C, X, T>()
+    }
+}
@@ -12,7 +12,10 @@ pub trait Constraint: super::common::Constraint {
         + 'static
         + ?Sized;
 
-    const DEFAULT_VALUE: &'static Self::Borrowed;
+    /// The value of an absent field. This is a function rather than a constant, so that values
+    /// which need to allocate - such as the `DEFAULT` value of a `SEQUENCE` - can be created
+    /// lazily.
+    fn default_value() -> &'static Self::Borrowed;
 }
 
 impl<T: WritableType, C: Constraint<Owned = T::Type>> WritableType for DefaultValue<T, C> {
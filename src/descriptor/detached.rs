@@ -0,0 +1,41 @@
+use crate::descriptor::Writer;
+
+/// Writes a single field of a `SEQUENCE` or `SET`, or the value of the set alternative of a
+/// `CHOICE`, on its own. The field is written with its constraints and its tag, so the encoding
+/// is exactly the one within the encoding of the enclosing value. This is what security layers
+/// compute signatures and hashes over. Implemented by the generated types.
+///
+/// ```rust
+/// use asn1rs::prelude::*;
+///
+/// #[asn(sequence)]
+/// #[derive(Debug, Default, PartialEq)]
+/// pub struct Signed {
+///     #[asn(integer(0..255), tag(APPLICATION(7)))]
+///     counter: u8,
+///     #[asn(octet_string)]
+///     signature: Vec<u8>,
+/// }
+///
+/// let signed = Signed {
+///     counter: 42,
+///     signature: vec![0xCA, 0xFE],
+/// };
+///
+/// let mut writer = DerWriter::default();
+/// signed.write_field("counter", &mut writer).unwrap().unwrap();
+/// let counter = writer.into_bytes_vec();
+/// assert_eq!(&[0x47, 0x01, 0x2A], &counter[..]);
+///
+/// let encoded = signed.write_der().unwrap();
+/// assert_eq!(&counter[..], &encoded[2..5]);
+/// ```
+pub trait WritableField {
+    /// Writes the field or alternative with the given name - the name of the Rust field or
+    /// variant, like in [`Segment::Field`](crate::descriptor::visit::Segment::Field). Returns
+    /// `None` if there is no such field or if the alternative with the given name is not set.
+    ///
+    /// Because UPER encodes the presence of `OPTIONAL` fields in front of all fields, the
+    /// presence bit of such a field is written in front of its value instead.
+    fn write_field<W: Writer>(&self, name: &str, writer: &mut W) -> Option<Result<(), W::Error>>;
+}
@@ -0,0 +1,240 @@
+use crate::descriptor::visit::{Path, Visitable, Visitor};
+use crate::descriptor::{BitVec, Oid};
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+
+/// A value nested in a [`Visitable`] value, as reported to a [`Visitor`]
+#[derive(Debug, Clone)]
+pub enum FieldValue {
+    Boolean(bool),
+    Integer(i128),
+    Real(f64),
+    String(String),
+    OctetString(Vec<u8>),
+    BitString(BitVec),
+    /// The name of the variant of an `ENUMERATED`
+    Enumerated(&'static str),
+    Null,
+    /// The value of an `OBJECT IDENTIFIER` or a `RELATIVE-OID`
+    ObjectIdentifier(Oid),
+    /// An `OPTIONAL` field that is not present
+    Absent,
+    /// The `Debug` representation of a value of a user provided type
+    Substituted(String),
+}
+
+/// Two `REAL`s are the same if their bits are: `NaN` equals itself and `0.0` and `-0.0` differ,
+/// because they are different values in ASN.1
+impl PartialEq for FieldValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Boolean(a), Self::Boolean(b)) => a == b,
+            (Self::Integer(a), Self::Integer(b)) => a == b,
+            (Self::Real(a), Self::Real(b)) => a.to_bits() == b.to_bits(),
+            (Self::String(a), Self::String(b)) => a == b,
+            (Self::OctetString(a), Self::OctetString(b)) => a == b,
+            (Self::BitString(a), Self::BitString(b)) => a == b,
+            (Self::Enumerated(a), Self::Enumerated(b)) => a == b,
+            (Self::Null, Self::Null) | (Self::Absent, Self::Absent) => true,
+            (Self::ObjectIdentifier(a), Self::ObjectIdentifier(b)) => a == b,
+            (Self::Substituted(a), Self::Substituted(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Display for FieldValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Boolean(value) => write!(f, "{value}"),
+            Self::Integer(value) => write!(f, "{value}"),
+            Self::Real(value) => write!(f, "{value}"),
+            Self::String(value) => write!(f, "{value:?}"),
+            Self::OctetString(value) => {
+                f.write_str("'")?;
+                for byte in value {
+                    write!(f, "{byte:02X}")?;
+                }
+                f.write_str("'H")
+            }
+            Self::BitString(value) => {
+                f.write_str("'")?;
+                for bit in 0..value.bit_len() {
+                    f.write_str(if value.is_bit_set(bit) { "1" } else { "0" })?;
+                }
+                f.write_str("'B")
+            }
+            Self::Enumerated(variant) => f.write_str(variant),
+            Self::Null => f.write_str("NULL"),
+            Self::ObjectIdentifier(value) => write!(f, "{value}"),
+            Self::Absent => f.write_str("absent"),
+            Self::Substituted(value) => f.write_str(value),
+        }
+    }
+}
+
+/// A difference between two values of the same type, see [`Visitable::diff`]. A path that exists
+/// in only one of the values - because the `CHOICE`s have different alternatives or the
+/// `SEQUENCE OF`s have different lengths - has no value on the other side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub path: Path,
+    pub old: Option<FieldValue>,
+    pub new: Option<FieldValue>,
+}
+
+/// Formatted like `passengers[1].seat: absent -> 3`, a missing value is printed as `-`
+impl Display for FieldChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fn side(f: &mut Formatter<'_>, value: &Option<FieldValue>) -> std::fmt::Result {
+            match value {
+                Some(value) => write!(f, "{value}"),
+                None => f.write_str("-"),
+            }
+        }
+        write!(f, "{}: ", self.path)?;
+        side(f, &self.old)?;
+        f.write_str(" -> ")?;
+        side(f, &self.new)
+    }
+}
+
+/// The changes from the old to the new value, in the order their paths are visited in the old
+/// value followed by the paths that only exist in the new value
+pub fn diff<T: Visitable>(old: &T, new: &T) -> Vec<FieldChange> {
+    let mut old_values = Collector::default();
+    let mut new_values = Collector::default();
+    old.accept(&mut old_values);
+    new.accept(&mut new_values);
+
+    let mut new_values = new_values.0.into_iter().map(Some).collect::<Vec<_>>();
+    let new_index = new_values
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| Some((entry.as_ref()?.0.clone(), index)))
+        .collect::<HashMap<_, _>>();
+
+    let mut changes = Vec::new();
+    for (path, old) in old_values.0 {
+        let new = new_index
+            .get(&path)
+            .and_then(|index| new_values[*index].take())
+            .map(|(_path, value)| value);
+        if new.as_ref() != Some(&old) {
+            changes.push(FieldChange {
+                path,
+                old: Some(old),
+                new,
+            });
+        }
+    }
+    changes.extend(
+        new_values
+            .into_iter()
+            .flatten()
+            .map(|(path, new)| FieldChange {
+                path,
+                old: None,
+                new: Some(new),
+            }),
+    );
+    changes
+}
+
+/// Records the visited values with their paths
+#[derive(Default)]
+struct Collector(Vec<(Path, FieldValue)>);
+
+impl Collector {
+    fn push(&mut self, path: &Path, value: FieldValue) {
+        self.0.push((path.clone(), value));
+    }
+}
+
+impl Visitor for Collector {
+    fn visit_boolean(&mut self, path: &Path, value: bool) {
+        self.push(path, FieldValue::Boolean(value));
+    }
+
+    fn visit_integer(&mut self, path: &Path, value: i128) {
+        self.push(path, FieldValue::Integer(value));
+    }
+
+    fn visit_real(&mut self, path: &Path, value: f64) {
+        self.push(path, FieldValue::Real(value));
+    }
+
+    fn visit_string(&mut self, path: &Path, value: &str) {
+        self.push(path, FieldValue::String(value.to_string()));
+    }
+
+    fn visit_octet_string(&mut self, path: &Path, value: &[u8]) {
+        self.push(path, FieldValue::OctetString(value.to_vec()));
+    }
+
+    fn visit_bit_string(&mut self, path: &Path, value: &BitVec) {
+        self.push(path, FieldValue::BitString(value.clone()));
+    }
+
+    fn visit_enumerated(&mut self, path: &Path, variant: &'static str) {
+        self.push(path, FieldValue::Enumerated(variant));
+    }
+
+    fn visit_null(&mut self, path: &Path) {
+        self.push(path, FieldValue::Null);
+    }
+
+    fn visit_object_identifier(&mut self, path: &Path, value: &Oid) {
+        self.push(path, FieldValue::ObjectIdentifier(value.clone()));
+    }
+
+    fn visit_relative_oid(&mut self, path: &Path, value: &Oid) {
+        self.push(path, FieldValue::ObjectIdentifier(value.clone()));
+    }
+
+    fn visit_oid_iri(&mut self, path: &Path, value: &str) {
+        self.push(path, FieldValue::String(value.to_string()));
+    }
+
+    fn visit_absent(&mut self, path: &Path) {
+        self.push(path, FieldValue::Absent);
+    }
+
+    fn visit_substituted(&mut self, path: &Path, value: &dyn Debug) {
+        self.push(path, FieldValue::Substituted(format!("{value:?}")));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::descriptor::visit::Segment;
+
+    #[test]
+    fn test_field_change_display() {
+        let mut path = Path::default();
+        path.push(Segment::Field("lights"));
+        let change = FieldChange {
+            path: path.clone(),
+            old: Some(FieldValue::BitString(BitVec::from_bytes(
+                vec![0b1010_0000],
+                4,
+            ))),
+            new: Some(FieldValue::OctetString(vec![0xC0, 0xFF])),
+        };
+        assert_eq!("lights: '1010'B -> 'C0FF'H", change.to_string());
+        let change = FieldChange {
+            path,
+            old: None,
+            new: Some(FieldValue::Absent),
+        };
+        assert_eq!("lights: - -> absent", change.to_string());
+    }
+
+    #[test]
+    fn test_reals_are_compared_by_their_bits() {
+        assert_eq!(FieldValue::Real(f64::NAN), FieldValue::Real(f64::NAN));
+        assert_ne!(FieldValue::Real(0.0), FieldValue::Real(-0.0));
+        assert_ne!(FieldValue::Real(1.0), FieldValue::Integer(1));
+    }
+}
@@ -1,48 +1,77 @@
 pub mod bitstring;
+pub mod bmpstring;
 pub mod boolean;
 pub mod choice;
 pub mod common;
 pub mod complex;
+pub mod containing;
 pub mod default;
+pub mod detached;
+pub mod diff;
 pub mod enumerated;
 pub mod ia5string;
 pub mod null;
 pub mod numbers;
 pub mod numericstring;
+pub mod objectidentifier;
 pub mod octetstring;
+pub mod oidiri;
 pub mod optional;
 pub mod printablestring;
+pub mod real;
+pub mod redact;
+pub mod relativeoid;
+pub mod selftest;
 pub mod sequence;
 pub mod sequenceof;
 pub mod set;
 pub mod setof;
+pub mod substituted;
+pub mod teletexstring;
 pub mod utf8string;
 pub mod visiblestring;
+pub mod visit;
 
 pub use crate::descriptor::null::Null;
 pub use bitstring::BitString;
 pub use bitstring::BitVec;
+pub use bitstring::BitVecConst;
+pub use bmpstring::BmpString;
 pub use boolean::Boolean;
 pub use choice::Choice;
 pub use complex::Complex;
+pub use containing::Containing;
 pub use default::DefaultValue;
 pub use enumerated::Enumerated;
 pub use ia5string::Ia5String;
 pub use null::NullT;
 pub use numbers::Integer;
 pub use numericstring::NumericString;
+pub use objectidentifier::{ObjectIdentifier, Oid};
 pub use octetstring::OctetString;
+pub use oidiri::OidIri;
 pub use printablestring::PrintableString;
+pub use real::Real;
+pub use relativeoid::RelativeOid;
 pub use sequence::Sequence;
 pub use sequenceof::SequenceOf;
 pub use set::Set;
 pub use setof::SetOf;
+pub use substituted::Substituted;
+pub use teletexstring::TeletexString;
 pub use utf8string::Utf8String;
 pub use visiblestring::VisibleString;
 
 pub mod prelude {
     pub use super::bitstring::BitVec;
+    pub use super::bitstring::BitVecConst;
+    pub use super::containing::RegisterTransform;
+    pub use super::detached::WritableField;
+    pub use super::diff::{FieldChange, FieldValue};
+    pub use super::redact::RedactedDebug;
+    pub use super::visit::{Path, Segment, Visitable, Visitor};
     pub use super::Null;
+    pub use super::Oid;
     pub use super::Readable;
     pub use super::ReadableType;
     pub use super::Reader;
@@ -59,7 +88,18 @@ pub trait Reader {
     where
         Self: Sized,
     {
-        T::read(self)
+        #[cfg(feature = "stats")]
+        let start = self.bits_read();
+        let value = T::read(self);
+        #[cfg(feature = "stats")]
+        crate::stats::record_decoded::<T>(start, self.bits_read(), value.is_ok());
+        value
+    }
+
+    /// The number of bits read so far, `None` if this reader does not keep track of it
+    #[inline]
+    fn bits_read(&self) -> Option<usize> {
+        None
     }
 
     fn read_sequence<
@@ -110,13 +150,45 @@ pub trait Reader {
         &mut self,
     ) -> Result<String, Self::Error>;
 
+    fn read_bmp_string<C: bmpstring::Constraint>(&mut self) -> Result<String, Self::Error>;
+
+    fn read_teletex_string<C: teletexstring::Constraint>(&mut self) -> Result<String, Self::Error>;
+
     fn read_octet_string<C: octetstring::Constraint>(&mut self) -> Result<Vec<u8>, Self::Error>;
 
+    fn read_containing<C: octetstring::Constraint, X: containing::Transform, T: ReadableType>(
+        &mut self,
+    ) -> Result<T::Type, Self::Error>;
+
+    fn read_substituted<S, X: substituted::Substitute<S, T::Type>, T: ReadableType>(
+        &mut self,
+    ) -> Result<S, Self::Error>;
+
     fn read_bit_string<C: bitstring::Constraint>(&mut self) -> Result<(Vec<u8>, u64), Self::Error>;
 
     fn read_boolean<C: boolean::Constraint>(&mut self) -> Result<bool, Self::Error>;
 
     fn read_null<C: null::Constraint>(&mut self) -> Result<Null, Self::Error>;
+
+    fn read_real<T: real::Float, C: real::Constraint>(&mut self) -> Result<T, Self::Error>;
+
+    fn read_object_identifier<C: objectidentifier::Constraint>(
+        &mut self,
+    ) -> Result<Oid, Self::Error>;
+
+    fn read_relative_oid<C: relativeoid::Constraint>(&mut self) -> Result<Oid, Self::Error>;
+
+    fn read_oid_iri<C: oidiri::Constraint>(&mut self) -> Result<String, Self::Error>;
+
+    /// Reads a value of a referenced type, `C` describes the component that references it. Tag
+    /// based representations override the tag of the referenced type with `C::TAG`.
+    #[inline]
+    fn read_complex<C: complex::Constraint, V: Readable>(&mut self) -> Result<V, Self::Error>
+    where
+        Self: Sized,
+    {
+        V::read(self)
+    }
 }
 
 pub trait Readable: Sized {
@@ -146,7 +218,13 @@ pub trait Writer {
     where
         Self: Sized,
     {
-        value.write(self)
+        write_recorded(self, value)
+    }
+
+    /// The number of bits written so far, `None` if this writer does not keep track of it
+    #[inline]
+    fn bits_written(&self) -> Option<usize> {
+        None
     }
 
     fn write_sequence<C: sequence::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
@@ -211,11 +289,26 @@ pub trait Writer {
         value: &str,
     ) -> Result<(), Self::Error>;
 
+    fn write_bmp_string<C: bmpstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error>;
+
+    fn write_teletex_string<C: teletexstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error>;
+
     fn write_octet_string<C: octetstring::Constraint>(
         &mut self,
         value: &[u8],
     ) -> Result<(), Self::Error>;
 
+    fn write_containing<C: octetstring::Constraint, X: containing::Transform, T: WritableType>(
+        &mut self,
+        value: &T::Type,
+    ) -> Result<(), Self::Error>;
+
     fn write_bit_string<C: bitstring::Constraint>(
         &mut self,
         value: &[u8],
@@ -225,6 +318,50 @@ pub trait Writer {
     fn write_boolean<C: boolean::Constraint>(&mut self, value: bool) -> Result<(), Self::Error>;
 
     fn write_null<C: null::Constraint>(&mut self, value: &Null) -> Result<(), Self::Error>;
+
+    fn write_real<T: real::Float, C: real::Constraint>(
+        &mut self,
+        value: T,
+    ) -> Result<(), Self::Error>;
+
+    fn write_object_identifier<C: objectidentifier::Constraint>(
+        &mut self,
+        value: &Oid,
+    ) -> Result<(), Self::Error>;
+
+    fn write_relative_oid<C: relativeoid::Constraint>(
+        &mut self,
+        value: &Oid,
+    ) -> Result<(), Self::Error>;
+
+    fn write_oid_iri<C: oidiri::Constraint>(&mut self, value: &str) -> Result<(), Self::Error>;
+
+    /// Writes a value of a referenced type, `C` describes the component that references it. Tag
+    /// based representations override the tag of the referenced type with `C::TAG`.
+    #[inline]
+    fn write_complex<C: complex::Constraint, V: Writable>(
+        &mut self,
+        value: &V,
+    ) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+    {
+        value.write(self)
+    }
+}
+
+/// Writes the value like [`Writer::write`] does by default, for writers that override it
+#[inline]
+pub(crate) fn write_recorded<W: Writer, T: Writable>(
+    writer: &mut W,
+    value: &T,
+) -> Result<(), W::Error> {
+    #[cfg(feature = "stats")]
+    let start = writer.bits_written();
+    let result = value.write(writer);
+    #[cfg(feature = "stats")]
+    crate::stats::record_encoded::<T>(start, writer.bits_written(), result.is_ok());
+    result
 }
 
 pub trait Writable {
@@ -35,6 +35,7 @@ impl<C: Constraint> ReadableType for NullT<C> {
 }
 
 #[derive(Default, Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Null;
 
 impl From<()> for Null {
@@ -1,4 +1,5 @@
 use crate::descriptor::{ReadableType, Reader, WritableType, Writer};
+pub use asn1rs_model::asn::split_unit;
 use asn1rs_model::asn::Tag;
 use core::marker::PhantomData;
 
@@ -20,6 +21,19 @@ pub trait Constraint<T: Number>: super::common::Constraint {
     const MIN_T: Option<T> = None;
     const MAX_T: Option<T> = None;
     const EXTENSIBLE: bool = false;
+    /// The physical unit of the value, such as `0.1 microdegree`
+    const UNIT: Option<&'static str> = None;
+    /// The name of the definition the value belongs to, reported by range violations
+    const DEFINITION_NAME: Option<&'static str> = None;
+    /// The name of the field of the definition that holds the value
+    const FIELD_NAME: Option<&'static str> = None;
+
+    /// The physical value and its unit, the value is multiplied by the scale factor the unit
+    /// starts with (if any, see [`split_unit`])
+    fn physical(value: T) -> Option<(f64, &'static str)> {
+        let (factor, unit) = split_unit(Self::UNIT?);
+        Some((value.to_i64() as f64 * factor, unit))
+    }
 }
 
 #[derive(Default)]
@@ -50,6 +64,119 @@ impl<T: Number, C: Constraint<T>> ReadableType for Integer<T, C> {
     }
 }
 
+/// How a reader treats a constrained `INTEGER` whose decoded value is not within its
+/// constraint, as sent by peers that violate the constraint
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DecodePolicy {
+    /// Fail with the error of the encoding rules for a value that is not in range
+    #[default]
+    Strict,
+    /// Read the bound of the constraint that the value exceeds instead
+    Clamp,
+    /// Read the value as it is, unless the Rust type is not able to represent it - then it is
+    /// clamped
+    Lenient,
+}
+
+impl DecodePolicy {
+    /// What to read instead of the given value that is not within the constraint, `None` if the
+    /// value is rejected
+    pub fn recover<T: Number, C: Constraint<T>>(self, raw: i64) -> Option<RecoveredValue> {
+        let min = C::MIN.unwrap_or(i64::MIN);
+        let max = C::MAX.unwrap_or(i64::MAX);
+        let clamped = raw.max(min).min(max);
+        let value = match self {
+            DecodePolicy::Strict => return None,
+            DecodePolicy::Clamp => clamped,
+            DecodePolicy::Lenient if T::from_i64(raw).to_i64() == raw => raw,
+            DecodePolicy::Lenient => clamped,
+        };
+        Some(RecoveredValue {
+            definition: C::DEFINITION_NAME,
+            field: C::FIELD_NAME,
+            raw,
+            min,
+            max,
+            value,
+        })
+    }
+}
+
+/// A value that is not within the constraint of its `INTEGER` and was read anyway, because of
+/// the [`DecodePolicy`] of the reader
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecoveredValue {
+    /// The name of the definition the value belongs to, if known
+    pub definition: Option<&'static str>,
+    /// The name of the field that holds the value, if known
+    pub field: Option<&'static str>,
+    /// The value as it was decoded
+    pub raw: i64,
+    pub min: i64,
+    pub max: i64,
+    /// The value that was read instead
+    pub value: i64,
+}
+
+/// The [`DecodePolicy`] of a reader and the values it recovered so far. This is an
+/// implementation detail of the [`DecodePolicyReader`]s.
+#[doc(hidden)]
+#[derive(Debug, Default, Clone)]
+pub struct Recovery {
+    pub(crate) policy: DecodePolicy,
+    pub(crate) values: Vec<RecoveredValue>,
+}
+
+/// The readers that read constrained `INTEGER`s according to a [`DecodePolicy`]
+pub trait DecodePolicyReader {
+    #[doc(hidden)]
+    fn recovery(&self) -> &Recovery;
+
+    #[doc(hidden)]
+    fn recovery_mut(&mut self) -> &mut Recovery;
+
+    #[inline]
+    fn decode_policy(&self) -> DecodePolicy {
+        self.recovery().policy
+    }
+
+    /// How a constrained `INTEGER` whose value is not within its constraint is read, see
+    /// [`DecodePolicy`]
+    #[inline]
+    fn set_decode_policy(&mut self, policy: DecodePolicy) {
+        self.recovery_mut().policy = policy;
+    }
+
+    /// The values that were not within the constraints of their `INTEGER`s and were read
+    /// anyway, because of the [`DecodePolicy`]
+    #[inline]
+    fn recovered_values(&self) -> &[RecoveredValue] {
+        &self.recovery().values
+    }
+}
+
+impl Recovery {
+    /// The value to read, given whether the decoded value is within the constraint
+    #[inline]
+    pub(crate) fn apply<T: Number, C: Constraint<T>, E>(
+        &mut self,
+        value: i64,
+        in_range: Result<(), E>,
+    ) -> Result<T, E> {
+        match in_range {
+            Ok(()) => Ok(T::from_i64(value)),
+            Err(e) => match self.policy.recover::<T, C>(value) {
+                Some(recovered) => {
+                    let value = recovered.value;
+                    self.values.push(recovered);
+                    Ok(T::from_i64(value))
+                }
+                None => Err(e),
+            },
+        }
+    }
+}
+
 macro_rules! impl_number {
     ( $($T:ident),+ ) => {$(
         impl Number for $T {
@@ -0,0 +1,211 @@
+use crate::descriptor::{ReadableType, Reader, WritableType, Writer};
+use asn1rs_model::asn::Tag;
+use core::marker::PhantomData;
+use std::fmt::{Display, Formatter};
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+pub struct ObjectIdentifier<C: Constraint = NoConstraint>(PhantomData<C>);
+
+pub trait Constraint: super::common::Constraint {}
+
+#[derive(Default)]
+pub struct NoConstraint;
+impl super::common::Constraint for NoConstraint {
+    const TAG: Tag = Tag::DEFAULT_OBJECT_IDENTIFIER;
+}
+impl Constraint for NoConstraint {}
+
+impl<C: Constraint> WritableType for ObjectIdentifier<C> {
+    type Type = Oid;
+
+    #[inline]
+    fn write_value<W: Writer>(
+        writer: &mut W,
+        value: &Self::Type,
+    ) -> Result<(), <W as Writer>::Error> {
+        writer.write_object_identifier::<C>(value)
+    }
+}
+
+impl<C: Constraint> ReadableType for ObjectIdentifier<C> {
+    type Type = Oid;
+
+    #[inline]
+    fn read_value<R: Reader>(reader: &mut R) -> Result<Self::Type, <R as Reader>::Error> {
+        reader.read_object_identifier::<C>()
+    }
+}
+
+/// The value of an `OBJECT IDENTIFIER`, ITU-T X.680 | ISO/IEC 8824-1, 32: the arcs from the root
+/// of the object identifier tree to the identified object, such as `1.2.840.113549.1.1.1`.
+///
+/// The arcs are not validated until the value is encoded: the encoding rules require at least
+/// two arcs, a first arc of 0, 1 or 2 and - below the first arcs 0 and 1 - a second arc of at
+/// most 39. The ordering is the lexicographic ordering of the arcs, so that every object
+/// identifier is directly followed by the objects below it.
+///
+/// ```rust
+/// use asn1rs::prelude::Oid;
+///
+/// let pkcs1: Oid = "1.2.840.113549.1.1".parse().unwrap();
+/// let rsa_encryption = pkcs1.child(1);
+///
+/// assert_eq!("1.2.840.113549.1.1.1", rsa_encryption.to_string());
+/// assert!(rsa_encryption.starts_with(&pkcs1));
+/// assert!(rsa_encryption.is_child_of(&pkcs1));
+/// assert_eq!(Some(pkcs1), rsa_encryption.parent());
+/// assert_eq!(Some(113549), rsa_encryption.arcs().nth(3));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Oid(Vec<u64>);
+
+impl Oid {
+    pub fn new(arcs: impl Into<Vec<u64>>) -> Self {
+        Self(arcs.into())
+    }
+
+    pub fn as_slice(&self) -> &[u64] {
+        &self.0
+    }
+
+    pub fn into_vec(self) -> Vec<u64> {
+        self.0
+    }
+
+    /// The arcs from the root to the identified object
+    pub fn arcs(&self) -> impl DoubleEndedIterator<Item = u64> + ExactSizeIterator + '_ {
+        self.0.iter().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether this object identifier is the given one or identifies an object below it
+    pub fn starts_with(&self, prefix: &Oid) -> bool {
+        self.0.starts_with(&prefix.0)
+    }
+
+    /// Whether this object identifier identifies an object directly below the given one
+    pub fn is_child_of(&self, parent: &Oid) -> bool {
+        self.len() == parent.len() + 1 && self.starts_with(parent)
+    }
+
+    /// The object identifier of the object this one is directly below, `None` for the root
+    pub fn parent(&self) -> Option<Oid> {
+        self.0
+            .split_last()
+            .map(|(_last, parent)| Oid(parent.to_vec()))
+    }
+
+    /// The object identifier of the object with the given arc directly below this one
+    pub fn child(&self, arc: u64) -> Oid {
+        let mut arcs = Vec::with_capacity(self.len() + 1);
+        arcs.extend_from_slice(&self.0);
+        arcs.push(arc);
+        Oid(arcs)
+    }
+
+    /// The object identifier of the object that the `RELATIVE-OID` identifies relative to this one
+    pub fn join(&self, relative: &Oid) -> Oid {
+        let mut arcs = Vec::with_capacity(self.len() + relative.len());
+        arcs.extend_from_slice(&self.0);
+        arcs.extend_from_slice(&relative.0);
+        Oid(arcs)
+    }
+}
+
+impl From<Vec<u64>> for Oid {
+    fn from(arcs: Vec<u64>) -> Self {
+        Self(arcs)
+    }
+}
+
+impl From<&[u64]> for Oid {
+    fn from(arcs: &[u64]) -> Self {
+        Self(arcs.to_vec())
+    }
+}
+
+impl<const N: usize> From<[u64; N]> for Oid {
+    fn from(arcs: [u64; N]) -> Self {
+        Self(arcs.to_vec())
+    }
+}
+
+impl From<Oid> for Vec<u64> {
+    fn from(oid: Oid) -> Self {
+        oid.0
+    }
+}
+
+/// The dotted notation, such as `1.2.840.113549`
+impl Display for Oid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, arc) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(".")?;
+            }
+            write!(f, "{arc}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the dotted notation, such as `1.2.840.113549`
+impl FromStr for Oid {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split('.')
+            .map(u64::from_str)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Oid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ordering_keeps_subtrees_together() {
+        let mut oids = ["1.3", "1.2.840", "2.5.4.3", "1.2", "1.2.840.10045", "1.10"]
+            .iter()
+            .map(|oid| oid.parse::<Oid>().unwrap())
+            .collect::<Vec<_>>();
+        oids.sort();
+        assert_eq!(
+            vec!["1.2", "1.2.840", "1.2.840.10045", "1.3", "1.10", "2.5.4.3"],
+            oids.iter().map(Oid::to_string).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!("".parse::<Oid>().is_err());
+        assert!("1..2".parse::<Oid>().is_err());
+        assert!("1.2.".parse::<Oid>().is_err());
+        assert!("1.-2".parse::<Oid>().is_err());
+    }
+
+    #[test]
+    fn test_relations() {
+        let root = Oid::new([2]);
+        let ds = Oid::new([2, 5]);
+        let cn = Oid::new([2, 5, 4, 3]);
+        assert!(ds.is_child_of(&root));
+        assert!(!cn.is_child_of(&root));
+        assert!(cn.starts_with(&root) && cn.starts_with(&ds) && cn.starts_with(&cn));
+        assert!(!ds.starts_with(&cn));
+        assert_eq!(Some(Oid::default()), root.parent());
+        assert_eq!(None, Oid::default().parent());
+        assert_eq!(cn, ds.join(&Oid::new([4, 3])));
+    }
+}
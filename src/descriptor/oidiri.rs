@@ -0,0 +1,37 @@
+use crate::descriptor::{ReadableType, Reader, WritableType, Writer};
+use asn1rs_model::asn::Tag;
+use core::marker::PhantomData;
+
+/// An `OID-IRI`, ITU-T X.680 | ISO/IEC 8824-1, 34: the Unicode labels of the arcs of an object
+/// identifier, each after a `/`, such as `/ISO/Registration_Authority/19785.CBEFF`
+pub struct OidIri<C: Constraint = NoConstraint>(PhantomData<C>);
+
+pub trait Constraint: super::common::Constraint {}
+
+#[derive(Default)]
+pub struct NoConstraint;
+impl super::common::Constraint for NoConstraint {
+    const TAG: Tag = Tag::DEFAULT_OID_IRI;
+}
+impl Constraint for NoConstraint {}
+
+impl<C: Constraint> WritableType for OidIri<C> {
+    type Type = String;
+
+    #[inline]
+    fn write_value<W: Writer>(
+        writer: &mut W,
+        value: &Self::Type,
+    ) -> Result<(), <W as Writer>::Error> {
+        writer.write_oid_iri::<C>(value)
+    }
+}
+
+impl<C: Constraint> ReadableType for OidIri<C> {
+    type Type = String;
+
+    #[inline]
+    fn read_value<R: Reader>(reader: &mut R) -> Result<Self::Type, <R as Reader>::Error> {
+        reader.read_oid_iri::<C>()
+    }
+}
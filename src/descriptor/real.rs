@@ -0,0 +1,74 @@
+use crate::descriptor::{ReadableType, Reader, WritableType, Writer};
+use asn1rs_model::asn::Tag;
+use core::marker::PhantomData;
+
+pub struct Real<T: Float = f64, C: Constraint = NoConstraint>(PhantomData<T>, PhantomData<C>);
+
+pub trait Float: Copy {
+    /// Whether the type is an IEEE 754 binary32, which representations with a fixed size
+    /// (ITU-T X.696, 12.2) encode in four instead of eight octets
+    const SINGLE_PRECISION: bool;
+
+    fn to_f64(self) -> f64;
+
+    /// Rounds the value to the nearest value of this type
+    fn from_f64(value: f64) -> Self;
+}
+
+impl Float for f32 {
+    const SINGLE_PRECISION: bool = true;
+
+    #[inline]
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+
+    #[inline]
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl Float for f64 {
+    const SINGLE_PRECISION: bool = false;
+
+    #[inline]
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    #[inline]
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
+
+pub trait Constraint: super::common::Constraint {}
+
+#[derive(Default)]
+pub struct NoConstraint;
+impl super::common::Constraint for NoConstraint {
+    const TAG: Tag = Tag::DEFAULT_REAL;
+}
+impl Constraint for NoConstraint {}
+
+impl<T: Float, C: Constraint> WritableType for Real<T, C> {
+    type Type = T;
+
+    #[inline]
+    fn write_value<W: Writer>(
+        writer: &mut W,
+        value: &Self::Type,
+    ) -> Result<(), <W as Writer>::Error> {
+        writer.write_real::<T, C>(*value)
+    }
+}
+
+impl<T: Float, C: Constraint> ReadableType for Real<T, C> {
+    type Type = T;
+
+    #[inline]
+    fn read_value<R: Reader>(reader: &mut R) -> Result<Self::Type, <R as Reader>::Error> {
+        reader.read_real::<T, C>()
+    }
+}
@@ -0,0 +1,103 @@
+use crate::descriptor::{BitVec, Null, Oid};
+use std::fmt::{Debug, Formatter};
+
+/// Text that replaces the values of sensitive fields
+pub const REDACTED: &str = "<redacted>";
+
+/// A [`Debug`] representation in which the values of fields and `CHOICE` alternatives marked as
+/// sensitive - either by the `-- @sensitive` annotation in the ASN.1 definition or by
+/// `#[asn(.., sensitive)]` - are masked.
+/// This allows decoded PDUs containing personal data (such as vehicle identifiers or positions)
+/// to be logged safely.
+///
+/// ```rust
+/// use asn1rs::prelude::*;
+///
+/// #[asn(sequence)]
+/// #[derive(Debug, Default, PartialEq)]
+/// pub struct Vehicle {
+///     #[asn(integer(0..255))]
+///     speed: u8,
+///     #[asn(utf8string, sensitive)]
+///     vin: String,
+/// }
+///
+/// let vehicle = Vehicle {
+///     speed: 42,
+///     vin: "WVWZZZ1JZXW000001".to_string(),
+/// };
+///
+/// assert_eq!(
+///     "Vehicle { speed: 42, vin: <redacted> }",
+///     format!("{:?}", vehicle.redacted_debug())
+/// );
+/// ```
+pub trait RedactedDebug {
+    fn fmt_redacted(&self, f: &mut Formatter<'_>) -> std::fmt::Result;
+
+    /// Wraps this value so that its [`Debug`] implementation masks all sensitive values
+    fn redacted_debug(&self) -> Redacted<'_, Self> {
+        Redacted(self)
+    }
+}
+
+/// See [`RedactedDebug::redacted_debug`]
+pub struct Redacted<'a, T: RedactedDebug + ?Sized>(&'a T);
+
+impl<T: RedactedDebug + ?Sized> Debug for Redacted<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt_redacted(f)
+    }
+}
+
+/// Placeholder for the value of a sensitive field
+pub struct Masked;
+
+impl Debug for Masked {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+macro_rules! redacted_debug_as_debug {
+    ($($t:ty),*) => {
+        $(
+            impl RedactedDebug for $t {
+                #[inline]
+                fn fmt_redacted(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                    Debug::fmt(self, f)
+                }
+            }
+        )*
+    };
+}
+
+redacted_debug_as_debug!(
+    bool, u8, u16, u32, u64, i8, i16, i32, i64, f32, f64, String, BitVec, Null, Oid
+);
+
+impl<T: RedactedDebug> RedactedDebug for Option<T> {
+    fn fmt_redacted(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Some(value) => f
+                .debug_tuple("Some")
+                .field(&value.redacted_debug())
+                .finish(),
+            None => f.write_str("None"),
+        }
+    }
+}
+
+impl<T: RedactedDebug> RedactedDebug for Vec<T> {
+    fn fmt_redacted(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.iter().map(RedactedDebug::redacted_debug))
+            .finish()
+    }
+}
+
+impl<T: RedactedDebug + ?Sized> RedactedDebug for Box<T> {
+    fn fmt_redacted(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        T::fmt_redacted(self, f)
+    }
+}
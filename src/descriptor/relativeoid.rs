@@ -0,0 +1,38 @@
+use crate::descriptor::{Oid, ReadableType, Reader, WritableType, Writer};
+use asn1rs_model::asn::Tag;
+use core::marker::PhantomData;
+
+/// A `RELATIVE-OID`, ITU-T X.680 | ISO/IEC 8824-1, 33: the arcs from a node of the object
+/// identifier tree that is known from the context - like a module-wide base - to the identified
+/// object. Its value is an [`Oid`] of at least one arc, see [`Oid::join`].
+pub struct RelativeOid<C: Constraint = NoConstraint>(PhantomData<C>);
+
+pub trait Constraint: super::common::Constraint {}
+
+#[derive(Default)]
+pub struct NoConstraint;
+impl super::common::Constraint for NoConstraint {
+    const TAG: Tag = Tag::DEFAULT_RELATIVE_OID;
+}
+impl Constraint for NoConstraint {}
+
+impl<C: Constraint> WritableType for RelativeOid<C> {
+    type Type = Oid;
+
+    #[inline]
+    fn write_value<W: Writer>(
+        writer: &mut W,
+        value: &Self::Type,
+    ) -> Result<(), <W as Writer>::Error> {
+        writer.write_relative_oid::<C>(value)
+    }
+}
+
+impl<C: Constraint> ReadableType for RelativeOid<C> {
+    type Type = Oid;
+
+    #[inline]
+    fn read_value<R: Reader>(reader: &mut R) -> Result<Self::Type, <R as Reader>::Error> {
+        reader.read_relative_oid::<C>()
+    }
+}
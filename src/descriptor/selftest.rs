@@ -0,0 +1,503 @@
+use crate::descriptor::containing::Transform;
+use crate::descriptor::numbers::Number;
+use crate::descriptor::real::Float;
+use crate::descriptor::substituted::Substitute;
+use crate::descriptor::*;
+use crate::rw::{BerReader, BerWriter, DerWriter, UperReader, UperWriter};
+use asn1rs_model::asn::Charset;
+use std::fmt::{Debug, Display, Formatter};
+
+/// The nesting depth of the generated types up to which [`Representative::representatives`] are
+/// created by [`round_trip`]
+pub const DEPTH: usize = 4;
+
+/// The size of the representative values of an unbounded `SIZE`, which needs the long form of
+/// the length determinants of all encoding rules while keeping the round trips fast
+pub const HUGE_SIZE: u64 = 1024;
+
+/// Sizes beyond this limit - of constraints like `SIZE(100000)` - are not represented
+pub const SIZE_LIMIT: u64 = 65536;
+
+/// Representative values of a generated type, such as the bounds of its constraints, empty and
+/// huge collections and the extension variants - which the generated `self_test()` fn of each
+/// module passes through the encoding rules. The first value is the smallest one.
+pub trait Representative: Sized {
+    /// The values of the nested generated types are created up to the given depth, a type that
+    /// cannot be created within the depth has no representative values
+    fn representatives(depth: usize) -> Vec<Self>;
+}
+
+/// The [`Representative`] values of a descriptor type
+pub trait RepresentativeType {
+    type Type: Clone;
+
+    fn representatives(depth: usize) -> Vec<Self::Type>;
+}
+
+/// The values of a struct, of which the `n`-th one consists of the `n`-th representative value
+/// of each field - wrapping around for the fields with fewer values. There are none if a field
+/// has none.
+pub fn combine<T>(field_counts: &[usize], value: impl FnMut(usize) -> T) -> Vec<T> {
+    if field_counts.contains(&0) {
+        return Vec::new();
+    }
+    let count = field_counts.iter().copied().max().unwrap_or(1);
+    (0..count).map(value).collect()
+}
+
+/// Why a representative value did not survive the round trip through the encoding rules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelfTestErrorKind {
+    Encode(String),
+    Decode(String),
+    /// The `Debug` representation of the decoded value, which differs from the encoded one
+    Mismatch(String),
+}
+
+/// A representative value of a generated type that did not survive the round trip through the
+/// encoding rules, see [`round_trip`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelfTestError {
+    pub type_name: &'static str,
+    /// The encoding rules, such as `UPER`
+    pub codec: &'static str,
+    /// The `Debug` representation of the value, shortened to [`SelfTestError::VALUE_LEN`]
+    /// characters
+    pub value: String,
+    pub kind: SelfTestErrorKind,
+}
+
+impl SelfTestError {
+    pub const VALUE_LEN: usize = 256;
+
+    fn new(type_name: &'static str, codec: &'static str, value: &impl Debug) -> Self {
+        let mut value = format!("{value:?}");
+        if let Some((index, _)) = value.char_indices().nth(Self::VALUE_LEN) {
+            value.truncate(index);
+            value.push_str("...");
+        }
+        Self {
+            type_name,
+            codec,
+            value,
+            kind: SelfTestErrorKind::Encode(String::new()),
+        }
+    }
+}
+
+impl Display for SelfTestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} of {} ", self.codec, self.type_name)?;
+        match &self.kind {
+            SelfTestErrorKind::Encode(e) => write!(f, "failed to encode {}: {e}", self.value),
+            SelfTestErrorKind::Decode(e) => write!(f, "failed to decode {}: {e}", self.value),
+            SelfTestErrorKind::Mismatch(decoded) => {
+                write!(f, "decoded {} as {decoded}", self.value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SelfTestError {}
+
+/// Encodes and decodes each representative value of the type with each of the enabled encoding
+/// rules: UPER, BER and DER and - with their features - OER, CBOR and Protobuf
+pub fn round_trip<T: Representative + Readable + Writable + PartialEq + Debug>(
+    type_name: &'static str,
+) -> Result<(), SelfTestError> {
+    for value in T::representatives(DEPTH) {
+        round_trip_value("UPER", type_name, &value, |value| {
+            let mut writer = UperWriter::default();
+            writer.write(value).map_err(|e| (true, e.to_string()))?;
+            let bits = writer.bit_len();
+            UperReader::from((writer.byte_content(), bits))
+                .read::<T>()
+                .map_err(|e| (false, e.to_string()))
+        })?;
+        round_trip_value("BER", type_name, &value, |value| {
+            let mut writer = BerWriter::default();
+            writer.write(value).map_err(|e| (true, e.to_string()))?;
+            BerReader::from(writer.byte_content())
+                .read::<T>()
+                .map_err(|e| (false, e.to_string()))
+        })?;
+        round_trip_value("DER", type_name, &value, |value| {
+            let mut writer = DerWriter::default();
+            writer.write(value).map_err(|e| (true, e.to_string()))?;
+            BerReader::from(writer.byte_content())
+                .read::<T>()
+                .map_err(|e| (false, e.to_string()))
+        })?;
+        #[cfg(feature = "oer")]
+        round_trip_value("OER", type_name, &value, |value| {
+            let mut writer = crate::rw::CoerWriter::default();
+            writer.write(value).map_err(|e| (true, e.to_string()))?;
+            crate::rw::CoerReader::from(writer.byte_content())
+                .read::<T>()
+                .map_err(|e| (false, e.to_string()))
+        })?;
+        #[cfg(feature = "cbor")]
+        round_trip_value("CBOR", type_name, &value, |value| {
+            let mut writer = crate::rw::CborWriter::default();
+            writer.write(value).map_err(|e| (true, e.to_string()))?;
+            crate::rw::CborReader::from(writer.byte_content())
+                .read::<T>()
+                .map_err(|e| (false, e.to_string()))
+        })?;
+        #[cfg(feature = "protobuf")]
+        round_trip_value("Protobuf", type_name, &value, |value| {
+            let mut writer = crate::rw::ProtobufWriter::default();
+            writer.write(value).map_err(|e| (true, e.to_string()))?;
+            crate::rw::ProtobufReader::from(writer.as_bytes())
+                .read::<T>()
+                .map_err(|e| (false, e.to_string()))
+        })?;
+    }
+    Ok(())
+}
+
+/// The error of the round trip is flagged whether it occurred while encoding
+fn round_trip_value<T: PartialEq + Debug>(
+    codec: &'static str,
+    type_name: &'static str,
+    value: &T,
+    round_trip: impl FnOnce(&T) -> Result<T, (bool, String)>,
+) -> Result<(), SelfTestError> {
+    let kind = match round_trip(value) {
+        Ok(decoded) if decoded == *value => return Ok(()),
+        Ok(decoded) => SelfTestErrorKind::Mismatch(format!("{decoded:?}")),
+        Err((true, e)) => SelfTestErrorKind::Encode(e),
+        Err((false, e)) => SelfTestErrorKind::Decode(e),
+    };
+    Err(SelfTestError {
+        kind,
+        ..SelfTestError::new(type_name, codec, value)
+    })
+}
+
+/// The lower and the upper bound, the size after the upper bound if the constraint is
+/// extensible and [`HUGE_SIZE`] if there is no upper bound
+fn sizes(min: Option<u64>, max: Option<u64>, extensible: bool) -> Vec<usize> {
+    let min = min.unwrap_or(0);
+    let mut sizes = vec![min];
+    match max {
+        Some(max) if extensible => sizes.extend([max, max.saturating_add(1)]),
+        Some(max) => sizes.push(max),
+        None => sizes.push(min.max(HUGE_SIZE)),
+    }
+    sizes.dedup();
+    sizes
+        .into_iter()
+        .filter(|size| *size <= SIZE_LIMIT)
+        .map(|size| size as usize)
+        .collect()
+}
+
+fn strings(min: Option<u64>, max: Option<u64>, extensible: bool, alphabet: &str) -> Vec<String> {
+    sizes(min, max, extensible)
+        .into_iter()
+        .map(|size| alphabet.chars().cycle().take(size).collect())
+        .collect()
+}
+
+/// Some of the characters of the character set, which cover the different widths of their
+/// encodings
+const fn alphabet(charset: Charset) -> &'static str {
+    match charset {
+        Charset::Utf8 => "aé€\u{1F600}",
+        Charset::Numeric => Charset::NUMERIC_STRING_CHARACTERS,
+        Charset::Printable => Charset::PRINTABLE_STRING_CHARACTERS,
+        Charset::Bmp => "aé€\u{FFFD}",
+        Charset::Teletex => "a\u{0}é\u{FF}",
+        Charset::Ia5 => Charset::IA5_STRING_CHARACTERS,
+        Charset::Visible => Charset::VISIBLE_STRING_CHARACTERS,
+    }
+}
+
+macro_rules! representative_string {
+    ($descriptor:ident, $module:ident, $charset:expr) => {
+        impl<C: $module::Constraint> RepresentativeType for $descriptor<C> {
+            type Type = String;
+
+            fn representatives(_depth: usize) -> Vec<String> {
+                strings(C::MIN, C::MAX, C::EXTENSIBLE, alphabet($charset))
+            }
+        }
+    };
+}
+
+representative_string!(Utf8String, utf8string, Charset::Utf8);
+representative_string!(NumericString, numericstring, Charset::Numeric);
+representative_string!(PrintableString, printablestring, Charset::Printable);
+representative_string!(BmpString, bmpstring, Charset::Bmp);
+representative_string!(TeletexString, teletexstring, Charset::Teletex);
+representative_string!(Ia5String, ia5string, Charset::Ia5);
+representative_string!(VisibleString, visiblestring, Charset::Visible);
+
+impl<C: octetstring::Constraint> RepresentativeType for OctetString<C> {
+    type Type = Vec<u8>;
+
+    fn representatives(_depth: usize) -> Vec<Vec<u8>> {
+        sizes(C::MIN, C::MAX, C::EXTENSIBLE)
+            .into_iter()
+            .map(|size| (0..size).map(|index| index as u8).collect())
+            .collect()
+    }
+}
+
+impl<C: bitstring::Constraint> RepresentativeType for BitString<C> {
+    type Type = BitVec;
+
+    fn representatives(_depth: usize) -> Vec<BitVec> {
+        sizes(C::MIN, C::MAX, C::EXTENSIBLE)
+            .into_iter()
+            .map(|bits| BitVec::from_bytes(vec![0b1010_0101; bits.div_ceil(8)], bits as u64))
+            .collect()
+    }
+}
+
+/// The bounds of the range - or the extremes of `i64` without them - and the values next to the
+/// bounds of an extensible range, as far as the type is able to represent them
+impl<T: Number, C: numbers::Constraint<T>> RepresentativeType for Integer<T, C> {
+    type Type = T;
+
+    fn representatives(_depth: usize) -> Vec<T> {
+        let min = C::MIN.unwrap_or(i64::MIN);
+        let max = C::MAX.unwrap_or(i64::MAX);
+        let mut candidates = vec![C::MIN.unwrap_or(0), max, min];
+        if C::EXTENSIBLE {
+            candidates.extend(C::MAX.and_then(|max| max.checked_add(1)));
+            candidates.extend(C::MIN.and_then(|min| min.checked_sub(1)));
+        }
+        let mut values: Vec<i64> = Vec::with_capacity(candidates.len());
+        for candidate in candidates {
+            if !values.contains(&candidate) && T::from_i64(candidate).to_i64() == candidate {
+                values.push(candidate);
+            }
+        }
+        values.into_iter().map(T::from_i64).collect()
+    }
+}
+
+impl<T: Float, C: real::Constraint> RepresentativeType for Real<T, C> {
+    type Type = T;
+
+    fn representatives(_depth: usize) -> Vec<T> {
+        [0.0, 1.5, -2.25].into_iter().map(T::from_f64).collect()
+    }
+}
+
+impl<C: boolean::Constraint> RepresentativeType for Boolean<C> {
+    type Type = bool;
+
+    fn representatives(_depth: usize) -> Vec<bool> {
+        vec![false, true]
+    }
+}
+
+impl<C: null::Constraint> RepresentativeType for NullT<C> {
+    type Type = Null;
+
+    fn representatives(_depth: usize) -> Vec<Null> {
+        vec![Null]
+    }
+}
+
+impl<C: objectidentifier::Constraint> RepresentativeType for ObjectIdentifier<C> {
+    type Type = Oid;
+
+    fn representatives(_depth: usize) -> Vec<Oid> {
+        vec![Oid::new([0, 0]), Oid::new([2, 999, u64::MAX])]
+    }
+}
+
+impl<C: relativeoid::Constraint> RepresentativeType for RelativeOid<C> {
+    type Type = Oid;
+
+    fn representatives(_depth: usize) -> Vec<Oid> {
+        vec![Oid::new([0]), Oid::new([8571, 3, u64::MAX])]
+    }
+}
+
+impl<C: oidiri::Constraint> RepresentativeType for OidIri<C> {
+    type Type = String;
+
+    fn representatives(_depth: usize) -> Vec<String> {
+        vec!["/ISO/Registration_Authority/19785.CBEFF".to_string()]
+    }
+}
+
+impl<C: enumerated::Constraint + Clone> RepresentativeType for Enumerated<C> {
+    type Type = C;
+
+    fn representatives(_depth: usize) -> Vec<C> {
+        (0..C::VARIANT_COUNT)
+            .filter_map(C::from_choice_index)
+            .collect()
+    }
+}
+
+impl<C: sequence::Constraint + Representative + Clone> RepresentativeType for Sequence<C> {
+    type Type = C;
+
+    fn representatives(depth: usize) -> Vec<C> {
+        C::representatives(depth)
+    }
+}
+
+impl<C: set::Constraint + Representative + Clone> RepresentativeType for Set<C> {
+    type Type = C;
+
+    fn representatives(depth: usize) -> Vec<C> {
+        C::representatives(depth)
+    }
+}
+
+impl<C: choice::Constraint + Representative + Clone> RepresentativeType for Choice<C> {
+    type Type = C;
+
+    fn representatives(depth: usize) -> Vec<C> {
+        C::representatives(depth)
+    }
+}
+
+impl<V: Representative + Clone, C: complex::Constraint> RepresentativeType for Complex<V, C> {
+    type Type = V;
+
+    fn representatives(depth: usize) -> Vec<V> {
+        V::representatives(depth)
+    }
+}
+
+/// The representative values of the elements, followed by copies of the smallest one
+impl<T: RepresentativeType, C: sequenceof::Constraint> RepresentativeType for SequenceOf<T, C> {
+    type Type = Vec<T::Type>;
+
+    fn representatives(depth: usize) -> Vec<Self::Type> {
+        let elements = T::representatives(depth);
+        sizes(C::MIN, C::MAX, C::EXTENSIBLE)
+            .into_iter()
+            .filter_map(|size| {
+                let mut values = elements.iter().take(size).cloned().collect::<Vec<_>>();
+                if values.len() < size {
+                    values.resize(size, elements.first()?.clone());
+                }
+                Some(values)
+            })
+            .collect()
+    }
+}
+
+/// Copies of the smallest representative value of the elements, so that the order of the
+/// elements is not changed by the encoding rules that sort them
+impl<T: RepresentativeType, C: setof::Constraint> RepresentativeType for SetOf<T, C> {
+    type Type = Vec<T::Type>;
+
+    fn representatives(depth: usize) -> Vec<Self::Type> {
+        let smallest = T::representatives(depth).into_iter().next();
+        sizes(C::MIN, C::MAX, C::EXTENSIBLE)
+            .into_iter()
+            .filter_map(|size| match &smallest {
+                Some(smallest) => Some(vec![smallest.clone(); size]),
+                None => Some(Vec::new()).filter(|_| size == 0),
+            })
+            .collect()
+    }
+}
+
+impl<T: RepresentativeType> RepresentativeType for Option<T> {
+    type Type = Option<T::Type>;
+
+    fn representatives(depth: usize) -> Vec<Self::Type> {
+        let mut values = vec![None];
+        values.extend(T::representatives(depth).into_iter().map(Some));
+        values
+    }
+}
+
+impl<T: RepresentativeType, C: default::Constraint<Owned = T::Type>> RepresentativeType
+    for DefaultValue<T, C>
+{
+    type Type = T::Type;
+
+    fn representatives(depth: usize) -> Vec<Self::Type> {
+        T::representatives(depth)
+    }
+}
+
+impl<T: RepresentativeType, X: Transform, C: octetstring::Constraint> RepresentativeType
+    for Containing<T, X, C>
+{
+    type Type = T::Type;
+
+    fn representatives(depth: usize) -> Vec<Self::Type> {
+        T::representatives(depth)
+    }
+}
+
+/// The representative values of the replaced type that the user provided type is able to
+/// represent
+impl<S: Clone, X: Substitute<S, T::Type>, T: RepresentativeType> RepresentativeType
+    for Substituted<S, X, T>
+{
+    type Type = S;
+
+    fn representatives(depth: usize) -> Vec<S> {
+        T::representatives(depth)
+            .into_iter()
+            .filter_map(|value| X::from_asn(value).ok())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sizes() {
+        assert_eq!(vec![0, HUGE_SIZE as usize], sizes(None, None, false));
+        assert_eq!(vec![4, 6, 7], sizes(Some(4), Some(6), true));
+        assert_eq!(vec![8], sizes(Some(8), Some(8), false));
+        assert_eq!(
+            Vec::<usize>::new(),
+            sizes(Some(100_000), Some(100_000), false)
+        );
+    }
+
+    #[test]
+    fn test_combine_wraps_around() {
+        let names = ["a", "b", "c"];
+        let flags = [false, true];
+        assert_eq!(
+            vec![("a", false), ("b", true), ("c", false)],
+            combine(&[names.len(), flags.len()], |i| (
+                names[i % names.len()],
+                flags[i % flags.len()]
+            ))
+        );
+        assert!(combine(&[names.len(), 0], |i| i).is_empty());
+        assert_eq!(vec![0], combine(&[], |i| i));
+    }
+
+    #[test]
+    fn test_integer_bounds() {
+        struct Extensible;
+        impl common::Constraint for Extensible {
+            const TAG: asn1rs_model::asn::Tag = asn1rs_model::asn::Tag::DEFAULT_INTEGER;
+        }
+        impl numbers::Constraint<u8> for Extensible {
+            const MIN: Option<i64> = Some(0);
+            const MAX: Option<i64> = Some(255);
+            const EXTENSIBLE: bool = true;
+        }
+        assert_eq!(
+            vec![0, 255],
+            <Integer<u8, Extensible> as RepresentativeType>::representatives(DEPTH)
+        );
+        assert_eq!(
+            vec![0, i64::MAX, i64::MIN],
+            <Integer<i64> as RepresentativeType>::representatives(DEPTH)
+        );
+    }
+}
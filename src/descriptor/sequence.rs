@@ -8,6 +8,8 @@ pub trait Constraint: super::common::Constraint {
     const STD_OPTIONAL_FIELDS: u64;
     const FIELD_COUNT: u64;
     const EXTENDED_AFTER_FIELD: Option<u64>;
+    /// The names of the fields in the order of their definition, empty if unknown
+    const FIELD_NAMES: &'static [&'static str] = &[];
 
     fn read_seq<R: Reader>(reader: &mut R) -> Result<Self, R::Error>
     where
@@ -0,0 +1,174 @@
+use crate::descriptor::{ReadableType, Reader, WritableType, Writer};
+use core::marker::PhantomData;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+
+/// A value of the ASN.1 type `T`, represented by the user provided Rust type `S`. The values are
+/// converted by `X`, which is `S` itself unless the substitution names a dedicated converter
+/// (`=> uuid::Uuid via crate::UuidBytes`) - as needed for types of other crates.
+pub struct Substituted<S, X, T>(PhantomData<S>, PhantomData<X>, PhantomData<T>);
+
+/// Converts between the Rust type `S` that replaces an ASN.1 type and the value `T` of the
+/// replaced ASN.1 type.
+///
+/// ```rust
+/// use asn1rs::descriptor::substituted::{Substitute, SubstituteError};
+///
+/// #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd)]
+/// pub struct Timestamp(std::time::Duration);
+///
+/// // for `INTEGER (0..4294967295) => crate::Timestamp`
+/// impl Substitute<Timestamp, u32> for Timestamp {
+///     fn to_asn(value: &Timestamp) -> u32 {
+///         value.0.as_secs() as u32
+///     }
+///
+///     fn from_asn(value: u32) -> Result<Timestamp, SubstituteError> {
+///         Ok(Timestamp(std::time::Duration::from_secs(u64::from(value))))
+///     }
+/// }
+/// ```
+pub trait Substitute<S, T> {
+    fn to_asn(value: &S) -> T;
+
+    /// Fails for values that cannot be represented by `S`
+    fn from_asn(value: T) -> Result<S, SubstituteError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubstituteError(String);
+
+impl SubstituteError {
+    pub fn new<T: ToString>(message: T) -> Self {
+        Self(message.to_string())
+    }
+
+    pub fn message(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for SubstituteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for SubstituteError {}
+
+/// For the string types of `asn1rs_model::asn::StringType`
+impl Substitute<Box<str>, String> for Box<str> {
+    #[inline]
+    fn to_asn(value: &Box<str>) -> String {
+        value.to_string()
+    }
+
+    #[inline]
+    fn from_asn(value: String) -> Result<Box<str>, SubstituteError> {
+        Ok(value.into_boxed_str())
+    }
+}
+
+impl Substitute<Arc<str>, String> for Arc<str> {
+    #[inline]
+    fn to_asn(value: &Arc<str>) -> String {
+        value.to_string()
+    }
+
+    #[inline]
+    fn from_asn(value: String) -> Result<Arc<str>, SubstituteError> {
+        Ok(Arc::from(value))
+    }
+}
+
+#[cfg(feature = "smol_str")]
+impl Substitute<smol_str::SmolStr, String> for smol_str::SmolStr {
+    #[inline]
+    fn to_asn(value: &smol_str::SmolStr) -> String {
+        value.to_string()
+    }
+
+    #[inline]
+    fn from_asn(value: String) -> Result<smol_str::SmolStr, SubstituteError> {
+        Ok(smol_str::SmolStr::from(value))
+    }
+}
+
+/// For `asn1rs_model::asn::OctetStringType::Bytes`, the decoded `Vec<u8>` is taken over without
+/// copying it
+#[cfg(feature = "bytes")]
+impl Substitute<bytes::Bytes, Vec<u8>> for bytes::Bytes {
+    #[inline]
+    fn to_asn(value: &bytes::Bytes) -> Vec<u8> {
+        value.to_vec()
+    }
+
+    #[inline]
+    fn from_asn(value: Vec<u8>) -> Result<bytes::Bytes, SubstituteError> {
+        Ok(bytes::Bytes::from(value))
+    }
+}
+
+/// For `asn1rs_model::generate::rust::RustCodeGenerator::set_heapless_types`, fails for strings
+/// that exceed the capacity
+#[cfg(feature = "heapless")]
+impl<const N: usize> Substitute<heapless::String<N>, String> for heapless::String<N> {
+    #[inline]
+    fn to_asn(value: &heapless::String<N>) -> String {
+        value.as_str().to_string()
+    }
+
+    #[inline]
+    fn from_asn(value: String) -> Result<heapless::String<N>, SubstituteError> {
+        heapless::String::try_from(value.as_str()).map_err(|_| {
+            SubstituteError::new(format!(
+                "{} octets exceed the capacity of {} octets",
+                value.len(),
+                N
+            ))
+        })
+    }
+}
+
+/// For `asn1rs_model::generate::rust::RustCodeGenerator::set_heapless_types`, fails for lists
+/// that exceed the capacity
+#[cfg(feature = "heapless")]
+impl<T: Clone, const N: usize> Substitute<heapless::Vec<T, N>, Vec<T>> for heapless::Vec<T, N> {
+    #[inline]
+    fn to_asn(value: &heapless::Vec<T, N>) -> Vec<T> {
+        value.to_vec()
+    }
+
+    #[inline]
+    fn from_asn(value: Vec<T>) -> Result<heapless::Vec<T, N>, SubstituteError> {
+        let len = value.len();
+        let mut vec = heapless::Vec::new();
+        for element in value {
+            vec.push(element).map_err(|_| {
+                SubstituteError::new(format!(
+                    "{} elements exceed the capacity of {} elements",
+                    len, N
+                ))
+            })?;
+        }
+        Ok(vec)
+    }
+}
+
+impl<S, X: Substitute<S, T::Type>, T: WritableType> WritableType for Substituted<S, X, T> {
+    type Type = S;
+
+    #[inline]
+    fn write_value<W: Writer>(writer: &mut W, value: &Self::Type) -> Result<(), W::Error> {
+        T::write_value(writer, &X::to_asn(value))
+    }
+}
+
+impl<S, X: Substitute<S, T::Type>, T: ReadableType> ReadableType for Substituted<S, X, T> {
+    type Type = S;
+
+    #[inline]
+    fn read_value<R: Reader>(reader: &mut R) -> Result<Self::Type, <R as Reader>::Error> {
+        reader.read_substituted::<S, X, T>()
+    }
+}
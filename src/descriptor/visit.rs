@@ -0,0 +1,159 @@
+use crate::descriptor::diff::FieldChange;
+use crate::descriptor::{BitVec, Oid};
+use std::fmt::{Debug, Display, Formatter};
+
+/// A step from a value to one of its nested values
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Segment {
+    /// The field of a `SEQUENCE` or `SET` or the alternative of a `CHOICE`
+    Field(&'static str),
+    /// The element of a `SEQUENCE OF` or `SET OF`
+    Index(usize),
+}
+
+/// The location of a value within the value that is visited, such as `passengers[0].name`. The
+/// path of the visited value itself is empty.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Path(Vec<Segment>);
+
+impl Path {
+    pub fn segments(&self) -> &[Segment] {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn push(&mut self, segment: Segment) {
+        self.0.push(segment);
+    }
+
+    pub fn pop(&mut self) -> Option<Segment> {
+        self.0.pop()
+    }
+}
+
+impl Display for Path {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, segment) in self.0.iter().enumerate() {
+            match segment {
+                Segment::Field(name) if index == 0 => write!(f, "{name}")?,
+                Segment::Field(name) => write!(f, ".{name}")?,
+                Segment::Index(element) => write!(f, "[{element}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Receives every value that is nested in a [`Visitable`] value together with its [`Path`]. All
+/// methods do nothing by default, so an implementation only overrides the ones it is interested
+/// in.
+///
+/// ```rust
+/// use asn1rs::prelude::*;
+///
+/// #[asn(sequence)]
+/// #[derive(Debug, Default, PartialEq)]
+/// pub struct Vehicle {
+///     #[asn(integer(0..255))]
+///     speed: u8,
+///     #[asn(sequence_of(utf8string))]
+///     passengers: Vec<String>,
+/// }
+///
+/// #[derive(Default)]
+/// struct Strings(Vec<String>);
+///
+/// impl Visitor for Strings {
+///     fn visit_string(&mut self, path: &Path, value: &str) {
+///         self.0.push(format!("{path}={value}"));
+///     }
+/// }
+///
+/// let vehicle = Vehicle {
+///     speed: 42,
+///     passengers: vec!["Jane".to_string(), "John".to_string()],
+/// };
+///
+/// let mut strings = Strings::default();
+/// vehicle.accept(&mut strings);
+/// assert_eq!(vec!["passengers[0]=Jane", "passengers[1]=John"], strings.0);
+/// ```
+#[allow(unused_variables)]
+pub trait Visitor {
+    /// Called before the content of a value of the definition with the given name is visited
+    fn enter(&mut self, path: &Path, definition: &'static str) {}
+
+    /// Called after the content of a value of the definition with the given name was visited
+    fn leave(&mut self, path: &Path, definition: &'static str) {}
+
+    fn visit_boolean(&mut self, path: &Path, value: bool) {}
+
+    fn visit_integer(&mut self, path: &Path, value: i128) {}
+
+    fn visit_real(&mut self, path: &Path, value: f64) {}
+
+    /// Any of the character string types
+    fn visit_string(&mut self, path: &Path, value: &str) {}
+
+    fn visit_octet_string(&mut self, path: &Path, value: &[u8]) {}
+
+    fn visit_bit_string(&mut self, path: &Path, value: &BitVec) {}
+
+    /// The name of the variant of an `ENUMERATED`
+    fn visit_enumerated(&mut self, path: &Path, variant: &'static str) {}
+
+    fn visit_null(&mut self, path: &Path) {}
+
+    fn visit_object_identifier(&mut self, path: &Path, value: &Oid) {}
+
+    fn visit_relative_oid(&mut self, path: &Path, value: &Oid) {}
+
+    fn visit_oid_iri(&mut self, path: &Path, value: &str) {}
+
+    /// An `OPTIONAL` field that is not present
+    fn visit_absent(&mut self, path: &Path) {}
+
+    /// A value that is represented by a user provided type, which is opaque to the visitor
+    fn visit_substituted(&mut self, path: &Path, value: &dyn Debug) {}
+}
+
+/// Walks all values nested in this value and reports them to a [`Visitor`]. Implemented by the
+/// generated types.
+pub trait Visitable {
+    /// Visits this value and all of its nested values, with paths relative to the given one
+    fn accept_at(&self, path: &mut Path, visitor: &mut impl Visitor);
+
+    /// Visits this value and all of its nested values
+    fn accept(&self, visitor: &mut impl Visitor) {
+        self.accept_at(&mut Path::default(), visitor)
+    }
+
+    /// The nested values that differ between this and the other value, see
+    /// [`diff`](crate::descriptor::diff::diff)
+    fn diff(&self, other: &Self) -> Vec<FieldChange>
+    where
+        Self: Sized,
+    {
+        crate::descriptor::diff::diff(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_display() {
+        let mut path = Path::default();
+        assert_eq!("", path.to_string());
+        path.push(Segment::Field("passengers"));
+        path.push(Segment::Index(3));
+        path.push(Segment::Field("name"));
+        assert_eq!("passengers[3].name", path.to_string());
+        assert_eq!(Some(Segment::Field("name")), path.pop());
+        assert_eq!("passengers[3]", path.to_string());
+    }
+}
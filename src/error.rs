@@ -0,0 +1,340 @@
+//! A single error type for all codecs, so that code using more than one representation does not
+//! need to handle the error type of each one.
+//!
+//! ```rust
+//! use asn1rs::prelude::*;
+//!
+//! fn decode<T: Readable>(bytes: &[u8]) -> Result<T, asn1rs::Error> {
+//!     Ok(UperReader::from((bytes, bytes.len() * 8)).read::<T>()?)
+//! }
+//! ```
+
+use crate::protocol::{basic, per};
+use std::fmt::{Display, Formatter};
+
+/// The codec independent category of an [`Error`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Kind {
+    /// The underlying reader or writer failed
+    Io,
+    /// The data ends before the value is completely read
+    EndOfData,
+    /// The data is not a valid encoding of the expected type
+    InvalidEncoding,
+    /// A value, size or choice index violates the constraints of the definition
+    ConstraintViolation,
+    /// A string is not valid for its character set
+    InvalidString,
+    /// A value without `DEFAULT` is not `OPTIONAL` but missing
+    MissingValue,
+    /// A limit of the reader or writer is exceeded, such as the nesting depth or the buffer size
+    LimitExceeded,
+    /// The codec does not support the operation
+    Unsupported,
+    /// Transforming the content of an `OCTET STRING (CONTAINING ...)` failed
+    ContentTransform,
+    /// Converting a value to or from its substitute failed
+    Substitution,
+}
+
+impl Kind {
+    /// The numeric code of the kind, which does not change between releases
+    pub const fn code(self) -> u16 {
+        match self {
+            Kind::Io => 1,
+            Kind::EndOfData => 2,
+            Kind::InvalidEncoding => 3,
+            Kind::ConstraintViolation => 4,
+            Kind::InvalidString => 5,
+            Kind::MissingValue => 6,
+            Kind::LimitExceeded => 7,
+            Kind::Unsupported => 8,
+            Kind::ContentTransform => 9,
+            Kind::Substitution => 10,
+        }
+    }
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "E{:04}", self.code())
+    }
+}
+
+/// The error of the codec that caused an [`Error`], with all of its details
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CodecError {
+    /// The source or destination of a codec failed, such as the source of a
+    /// [`UperStreamReader`](crate::rw::UperStreamReader)
+    Io(std::io::Error),
+    Uper(per::Error),
+    Basic(basic::Error),
+    #[cfg(feature = "cbor")]
+    Cbor(crate::protocol::cbor::Error),
+    #[cfg(feature = "oer")]
+    Oer(crate::protocol::oer::Error),
+    #[cfg(feature = "protobuf")]
+    Protobuf(crate::protocol::protobuf::Error),
+}
+
+impl Display for CodecError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CodecError::Io(e) => Display::fmt(e, f),
+            CodecError::Uper(e) => Display::fmt(e, f),
+            CodecError::Basic(e) => Display::fmt(e, f),
+            #[cfg(feature = "cbor")]
+            CodecError::Cbor(e) => Display::fmt(e, f),
+            #[cfg(feature = "oer")]
+            CodecError::Oer(e) => Display::fmt(e, f),
+            #[cfg(feature = "protobuf")]
+            CodecError::Protobuf(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+/// The error of any of the codecs, see [`Kind`] for its category and [`Error::codec`] for the
+/// details of the codec. Every codec error converts into this error, so that `?` works for all
+/// of them.
+#[derive(Debug)]
+pub struct Error {
+    kind: Kind,
+    codec: CodecError,
+}
+
+impl Error {
+    #[inline]
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Shorthand for `self.kind().code()`
+    #[inline]
+    pub fn code(&self) -> u16 {
+        self.kind.code()
+    }
+
+    #[inline]
+    pub fn codec(&self) -> &CodecError {
+        &self.codec
+    }
+
+    #[inline]
+    pub fn into_codec(self) -> CodecError {
+        self.codec
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.kind, self.codec)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(match &self.codec {
+            CodecError::Io(e) => e,
+            CodecError::Uper(e) => e,
+            CodecError::Basic(e) => e,
+            #[cfg(feature = "cbor")]
+            CodecError::Cbor(e) => e,
+            #[cfg(feature = "oer")]
+            CodecError::Oer(e) => e,
+            #[cfg(feature = "protobuf")]
+            CodecError::Protobuf(e) => e,
+        })
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Self {
+            kind: io_kind(&error),
+            codec: CodecError::Io(error),
+        }
+    }
+}
+
+impl From<per::Error> for Error {
+    fn from(error: per::Error) -> Self {
+        use per::ErrorKind as K;
+        let kind = match error.kind() {
+            K::FromUtf8Error(_) | K::InvalidString(..) => Kind::InvalidString,
+            K::UnsupportedOperation(_) => Kind::Unsupported,
+            K::InsufficientDataInSourceBuffer(_) | K::EndOfStream => Kind::EndOfData,
+            K::InsufficientSpaceInDestinationBuffer(_)
+            | K::LengthDeterminantExceedsLimit { .. }
+            | K::ValueExceedsMaxInt
+            | K::NestingDepthExceedsLimit(_)
+            | K::PduExceedsLimit(_) => Kind::LimitExceeded,
+            K::InvalidChoiceIndex(..)
+            | K::ValueNotInRange { .. }
+            | K::ValueIsNegativeButExpectedUnsigned(_)
+            | K::SizeNotInRange(..)
+            | K::BitLenNotInRange(..) => Kind::ConstraintViolation,
+            K::ExtensionFieldsInconsistent(_)
+            | K::OptFlagsExhausted
+            | K::TrailingBits(..)
+            | K::InvalidReal(_)
+            | K::InvalidObjectIdentifier(_) => Kind::InvalidEncoding,
+            K::ContentTransformFailed(_) => Kind::ContentTransform,
+            K::SubstitutionFailed(_) => Kind::Substitution,
+        };
+        Self {
+            kind,
+            codec: CodecError::Uper(error),
+        }
+    }
+}
+
+impl From<basic::Error> for Error {
+    fn from(error: basic::Error) -> Self {
+        use basic::ErrorKind as K;
+        let kind = match error.kind() {
+            K::IoError(e) => io_kind(e),
+            K::EndOfContent => Kind::EndOfData,
+            K::UnexpectedTypeTag { .. }
+            | K::UnexpectedTypeLength { .. }
+            | K::TrailingContent(_)
+            | K::InvalidEncoding(_) => Kind::InvalidEncoding,
+            K::UnexpectedChoiceIndex { .. }
+            | K::ValueNotInRange { .. }
+            | K::SizeNotInRange { .. } => Kind::ConstraintViolation,
+            K::InvalidString(..) => Kind::InvalidString,
+            K::UnsupportedByteLen { .. } | K::NestingDepthExceedsLimit(_) => Kind::LimitExceeded,
+            K::UnsupportedOperation(_) => Kind::Unsupported,
+            K::ContentTransformFailed(_) => Kind::ContentTransform,
+            K::SubstitutionFailed(_) => Kind::Substitution,
+        };
+        Self {
+            kind,
+            codec: CodecError::Basic(error),
+        }
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl From<crate::protocol::cbor::Error> for Error {
+    fn from(error: crate::protocol::cbor::Error) -> Self {
+        use crate::protocol::cbor::ErrorKind as K;
+        let kind = match error.kind() {
+            K::EndOfStream => Kind::EndOfData,
+            K::UnexpectedItem { .. }
+            | K::NotDeterministic(_)
+            | K::UnexpectedItemCount { .. }
+            | K::InvalidObjectIdentifier(_) => Kind::InvalidEncoding,
+            K::ItemsExhausted => Kind::MissingValue,
+            K::InvalidChoiceIndex(..) | K::ValueNotInRange { .. } | K::SizeNotInRange(..) => {
+                Kind::ConstraintViolation
+            }
+            K::InvalidString(..) | K::InvalidUtf8 => Kind::InvalidString,
+            K::ValueExceedsMaxInt | K::NestingDepthExceedsLimit(_) => Kind::LimitExceeded,
+            K::ContentTransformFailed(_) => Kind::ContentTransform,
+            K::SubstitutionFailed(_) => Kind::Substitution,
+        };
+        Self {
+            kind,
+            codec: CodecError::Cbor(error),
+        }
+    }
+}
+
+#[cfg(feature = "oer")]
+impl From<crate::protocol::oer::Error> for Error {
+    fn from(error: crate::protocol::oer::Error) -> Self {
+        use crate::protocol::oer::ErrorKind as K;
+        let kind = match error.kind() {
+            K::EndOfStream => Kind::EndOfData,
+            K::UnexpectedTag { .. } | K::InvalidEncoding(_) => Kind::InvalidEncoding,
+            K::InvalidChoiceIndex(..) | K::ValueNotInRange { .. } | K::SizeNotInRange(..) => {
+                Kind::ConstraintViolation
+            }
+            K::InvalidString(..) | K::InvalidUtf8 => Kind::InvalidString,
+            K::ValueExceedsMaxInt | K::NestingDepthExceedsLimit(_) => Kind::LimitExceeded,
+            K::ContentTransformFailed(_) => Kind::ContentTransform,
+            K::SubstitutionFailed(_) => Kind::Substitution,
+        };
+        Self {
+            kind,
+            codec: CodecError::Oer(error),
+        }
+    }
+}
+
+#[cfg(feature = "protobuf")]
+impl From<crate::protocol::protobuf::Error> for Error {
+    fn from(error: crate::protocol::protobuf::Error) -> Self {
+        use crate::protocol::protobuf::Error as E;
+        let kind = match &error {
+            E::Io(_, e) => io_kind(e),
+            E::InvalidUtf8Received => Kind::InvalidString,
+            E::MissingRequiredField(_) => Kind::MissingValue,
+            E::InvalidTagReceived(..)
+            | E::InvalidFormat(..)
+            | E::UnexpectedFormat(..)
+            | E::UnexpectedTag(..)
+            | E::InvalidObjectIdentifier(..) => Kind::InvalidEncoding,
+            E::InvalidVariant(..) => Kind::ConstraintViolation,
+            E::ContentTransformFailed(..) => Kind::ContentTransform,
+            E::SubstitutionFailed(..) => Kind::Substitution,
+            E::UnnamedField(..) => Kind::Unsupported,
+        };
+        Self {
+            kind,
+            codec: CodecError::Protobuf(error),
+        }
+    }
+}
+
+fn io_kind(error: &std::io::Error) -> Kind {
+    if error.kind() == std::io::ErrorKind::UnexpectedEof {
+        Kind::EndOfData
+    } else {
+        Kind::Io
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_are_unique() {
+        let kinds = [
+            Kind::Io,
+            Kind::EndOfData,
+            Kind::InvalidEncoding,
+            Kind::ConstraintViolation,
+            Kind::InvalidString,
+            Kind::MissingValue,
+            Kind::LimitExceeded,
+            Kind::Unsupported,
+            Kind::ContentTransform,
+            Kind::Substitution,
+        ];
+        let mut codes = kinds.iter().map(|k| k.code()).collect::<Vec<_>>();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(kinds.len(), codes.len());
+    }
+
+    #[test]
+    fn test_io_eof_is_end_of_data() {
+        let error = Error::from(basic::Error::from(std::io::Error::from(
+            std::io::ErrorKind::UnexpectedEof,
+        )));
+        assert_eq!(Kind::EndOfData, error.kind());
+        assert!(matches!(error.codec(), CodecError::Basic(_)));
+        assert!(std::error::Error::source(&error).is_some());
+    }
+
+    #[test]
+    fn test_io_error() {
+        let error = Error::from(std::io::Error::from(std::io::ErrorKind::WouldBlock));
+        assert_eq!(Kind::Io, error.kind());
+        assert!(matches!(error.codec(), CodecError::Io(_)));
+    }
+}
@@ -0,0 +1,105 @@
+//! The memory footprint of the generated types and the sizes of their UPER encodings, to budget
+//! the RAM and the link-time size per PDU. With `RustCodeGenerator::set_footprint`, each
+//! generated module lists its definitions in its `pub fn footprint() -> &'static [Footprint]`,
+//! which [`table`] renders as Markdown table - an artifact to check in or to attach to a build.
+//!
+//! ```rust
+//! use asn1rs::prelude::*;
+//! use asn1rs::footprint::{table, Footprint};
+//!
+//! #[asn(sequence)]
+//! #[derive(Debug, Default, PartialEq)]
+//! pub struct Ping {
+//!     #[asn(integer(0..255))]
+//!     id: u8,
+//!     #[asn(octet_string(size(0..4)))]
+//!     payload: Vec<u8>,
+//! }
+//!
+//! fn footprint() -> &'static [Footprint] {
+//!     const FOOTPRINT: &[Footprint] = &[Footprint::new::<Ping>("Ping-Request", "Ping", Some(4), 11, Some(43))];
+//!     FOOTPRINT
+//! }
+//!
+//! assert_eq!(
+//!     format!(
+//!         "| Type | Size | Heap | Min bits | Max bits |\n\
+//!          |------|-----:|-----:|---------:|---------:|\n\
+//!          | Ping-Request | {} | 4 | 11 | 43 |\n",
+//!         std::mem::size_of::<Ping>()
+//!     ),
+//!     table(footprint())
+//! );
+//! ```
+
+use std::fmt::Write;
+
+/// A definition of a generated module with the sizes of its type and of the UPER encodings of
+/// its values
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Footprint {
+    /// The name of the definition in the ASN.1 module, such as `Ping-Request`
+    pub asn_name: &'static str,
+    /// The name of the generated type, such as `PingRequest`
+    pub rust_name: &'static str,
+    /// The `size_of` the generated type on the target
+    pub size: usize,
+    /// The most bytes a value owns on the heap, `None` if unbounded
+    pub heap: Option<usize>,
+    /// The fewest bits of the UPER encoding of a value
+    pub min_bits: u64,
+    /// The most bits of the UPER encoding of a value, `None` if unbounded
+    pub max_bits: Option<u64>,
+}
+
+impl Footprint {
+    pub const fn new<T>(
+        asn_name: &'static str,
+        rust_name: &'static str,
+        heap: Option<usize>,
+        min_bits: u64,
+        max_bits: Option<u64>,
+    ) -> Self {
+        Self {
+            asn_name,
+            rust_name,
+            size: core::mem::size_of::<T>(),
+            heap,
+            min_bits,
+            max_bits,
+        }
+    }
+}
+
+/// The larger one of both values, which the heap estimates of the `CHOICE` types refer to
+pub const fn max(a: usize, b: usize) -> usize {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+/// Renders the footprints as Markdown table, in which unbounded values are marked as `-`
+pub fn table(footprints: &[Footprint]) -> String {
+    fn or_unbounded(value: Option<impl ToString>) -> String {
+        value.map_or_else(|| "-".to_string(), |value| value.to_string())
+    }
+
+    let mut table = String::from(
+        "| Type | Size | Heap | Min bits | Max bits |\n\
+         |------|-----:|-----:|---------:|---------:|\n",
+    );
+    for footprint in footprints {
+        let _ = writeln!(
+            table,
+            "| {} | {} | {} | {} | {} |",
+            footprint.asn_name,
+            footprint.size,
+            or_unbounded(footprint.heap),
+            footprint.min_bits,
+            or_unbounded(footprint.max_bits),
+        );
+    }
+    table
+}
@@ -0,0 +1,371 @@
+//! Golden UPER encodings: values together with the bytes they are expected to be encoded to.
+//! Each value is encoded twice - to detect a non-deterministic writer -, compared to its
+//! expected bytes and these are decoded back to the value. A failure names the first differing
+//! bit and the field it belongs to.
+//!
+//! ```rust
+//! use asn1rs::golden::Goldens;
+//! use asn1rs::prelude::*;
+//!
+//! #[asn(sequence)]
+//! #[derive(Debug, Default, PartialEq)]
+//! pub struct Vehicle {
+//!     #[asn(integer(0..255))]
+//!     speed: u8,
+//!     #[asn(boolean)]
+//!     parked: bool,
+//! }
+//!
+//! Goldens::default()
+//!     .add("stopped", Vehicle { speed: 0, parked: true }, "0x00 0x80")
+//!     .add("moving", Vehicle { speed: 42, parked: false }, "2a 00")
+//!     .assert();
+//!
+//! asn1rs::assert_uper_eq!(Vehicle { speed: 255, parked: true }, "0xFF 0x80");
+//! ```
+
+// the assertions are test helpers and report mismatches by panicking, like `assert_eq!`
+#![allow(clippy::panic)]
+
+use crate::debug::annotate;
+use crate::descriptor::{Readable, Reader, Writable, Writer};
+use crate::rw::{UperReader, UperWriter};
+use std::fmt::{Debug, Display, Formatter};
+
+/// The number of bytes shown before the first differing byte
+const CONTEXT_BYTES: usize = 2;
+
+/// The number of bytes shown of each encoding
+const SHOWN_BYTES: usize = 8;
+
+/// Asserts that the UPER encoding of the value equals the given bytes - such as `"0xAB 0xCD"` -
+/// and that these decode to the value, see [`Golden::check`]
+#[macro_export]
+macro_rules! assert_uper_eq {
+    ($value:expr, $hex:expr $(,)?) => {
+        $crate::golden::assert_uper_eq(&$value, $hex)
+    };
+}
+
+/// The function behind [`assert_uper_eq!`](crate::assert_uper_eq)
+#[track_caller]
+pub fn assert_uper_eq<T: Readable + Writable + Debug + PartialEq>(value: &T, hex: &str) {
+    let bytes = parse_hex(hex).unwrap_or_else(|e| panic!("{}", e));
+    if let Err(mismatch) = Golden::check_value(String::new(), value, &bytes) {
+        panic!("{}", mismatch);
+    }
+}
+
+/// Parses bytes written in hex, such as `"0xAB 0xCD"`, `"ab cd"`, `"0xABCD"` or `"AB, CD"`
+pub fn parse_hex(hex: &str) -> Result<Vec<u8>, InvalidHex> {
+    let mut bytes = Vec::new();
+    for token in hex
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+    {
+        let digits = token
+            .strip_prefix("0x")
+            .or_else(|| token.strip_prefix("0X"))
+            .unwrap_or(token);
+        if digits.is_empty()
+            || digits.len() % 2 != 0
+            || !digits.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return Err(InvalidHex(token.to_string()));
+        }
+        for index in (0..digits.len()).step_by(2) {
+            bytes.push(
+                u8::from_str_radix(&digits[index..index + 2], 16)
+                    .map_err(|_| InvalidHex(token.to_string()))?,
+            );
+        }
+    }
+    Ok(bytes)
+}
+
+/// A part of a hex string that is not a byte or a sequence of bytes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidHex(pub String);
+
+impl Display for InvalidHex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid hex bytes: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidHex {}
+
+/// A value with its expected UPER encoding
+#[derive(Debug, Clone, PartialEq)]
+pub struct Golden<T> {
+    pub name: String,
+    pub value: T,
+    pub bytes: Vec<u8>,
+}
+
+impl<T: Readable + Writable + Debug + PartialEq> Golden<T> {
+    /// Checks that the value is encoded to the same bytes twice, that these are the expected
+    /// bytes and that the expected bytes decode to the value
+    pub fn check(&self) -> Result<(), Mismatch> {
+        Self::check_value(self.name.clone(), &self.value, &self.bytes)
+    }
+
+    fn check_value(name: String, value: &T, expected: &[u8]) -> Result<(), Mismatch> {
+        let mismatch = |kind| Mismatch {
+            name: name.clone(),
+            type_name: std::any::type_name::<T>(),
+            expected: expected.to_vec(),
+            kind,
+        };
+        let encode = || {
+            let mut writer = UperWriter::default();
+            writer
+                .write(value)
+                .map(|_| writer.into_bytes_vec())
+                .map_err(|e| mismatch(MismatchKind::Encode(e.to_string())))
+        };
+
+        let actual = encode()?;
+        let again = encode()?;
+        if actual != again {
+            return Err(mismatch(MismatchKind::NonDeterministic(actual, again)));
+        }
+
+        if actual != expected {
+            let bit = first_differing_bit(expected, &actual);
+            let path = annotate::<T>(&actual).ok().and_then(|annotation| {
+                annotation
+                    .regions()
+                    .iter()
+                    .filter(|region| region.bits.contains(&bit) && !region.path.is_empty())
+                    .max_by_key(|region| region.path.segments().len())
+                    .map(|region| region.path.to_string())
+            });
+            return Err(mismatch(MismatchKind::Bytes { actual, bit, path }));
+        }
+
+        match UperReader::from((expected, expected.len() * 8)).read::<T>() {
+            Ok(decoded) if decoded.eq(value) => Ok(()),
+            Ok(decoded) => Err(mismatch(MismatchKind::Decoded(format!("{:?}", decoded)))),
+            Err(e) => Err(mismatch(MismatchKind::Decode(e.to_string()))),
+        }
+    }
+}
+
+/// The index of the first bit in which the given bytes differ, a missing byte differs in all bits
+fn first_differing_bit(a: &[u8], b: &[u8]) -> usize {
+    a.iter()
+        .zip(b)
+        .position(|(a, b)| a != b)
+        .map(|index| index * 8 + (a[index] ^ b[index]).leading_zeros() as usize)
+        .unwrap_or_else(|| a.len().min(b.len()) * 8)
+}
+
+/// The golden encodings of the values of a type, which are checked together
+#[derive(Debug, Clone, PartialEq)]
+pub struct Goldens<T> {
+    goldens: Vec<Golden<T>>,
+}
+
+impl<T> Default for Goldens<T> {
+    fn default() -> Self {
+        Self {
+            goldens: Vec::new(),
+        }
+    }
+}
+
+impl<T: Readable + Writable + Debug + PartialEq> Goldens<T> {
+    /// Adds a value with its expected encoding written in hex, see [`parse_hex`]
+    ///
+    /// # Panics
+    ///
+    /// If the hex string is invalid
+    #[track_caller]
+    pub fn add(self, name: impl Into<String>, value: T, hex: &str) -> Self {
+        let bytes = parse_hex(hex).unwrap_or_else(|e| panic!("{}", e));
+        self.add_bytes(name, value, bytes)
+    }
+
+    pub fn add_bytes(mut self, name: impl Into<String>, value: T, bytes: Vec<u8>) -> Self {
+        self.goldens.push(Golden {
+            name: name.into(),
+            value,
+            bytes,
+        });
+        self
+    }
+
+    pub fn goldens(&self) -> &[Golden<T>] {
+        &self.goldens
+    }
+
+    /// Checks all values, see [`Golden::check`]
+    pub fn check(&self) -> Result<(), Vec<Mismatch>> {
+        let mismatches = self
+            .goldens
+            .iter()
+            .filter_map(|golden| golden.check().err())
+            .collect::<Vec<_>>();
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(mismatches)
+        }
+    }
+
+    /// Like [`Goldens::check`], but panics with a description of all mismatches
+    #[track_caller]
+    pub fn assert(&self) {
+        if let Err(mismatches) = self.check() {
+            panic!(
+                "{} of {} golden encodings do not match\n\n{}",
+                mismatches.len(),
+                self.goldens.len(),
+                mismatches
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+    }
+}
+
+/// Why a value does not match its golden encoding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MismatchKind {
+    Encode(String),
+    /// The value was encoded to these different bytes the first and the second time
+    NonDeterministic(Vec<u8>, Vec<u8>),
+    /// The value was encoded to other bytes, which differ from the expected ones in the given
+    /// bit - that belongs to the field at the path, if known
+    Bytes {
+        actual: Vec<u8>,
+        bit: usize,
+        path: Option<String>,
+    },
+    /// The expected bytes failed to decode
+    Decode(String),
+    /// The `Debug` representation of the value the expected bytes decode to, which differs from
+    /// the encoded one
+    Decoded(String),
+}
+
+/// A value that does not match its golden encoding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// The name of the golden encoding, empty for [`assert_uper_eq!`](crate::assert_uper_eq)
+    pub name: String,
+    pub type_name: &'static str,
+    pub expected: Vec<u8>,
+    pub kind: MismatchKind,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.name.is_empty() {
+            write!(f, "UPER encoding of {}", self.type_name)?;
+        } else {
+            write!(f, "Golden encoding {:?} of {}", self.name, self.type_name)?;
+        }
+        match &self.kind {
+            MismatchKind::Encode(error) => writeln!(f, " failed to encode: {}", error),
+            MismatchKind::NonDeterministic(first, second) => {
+                writeln!(f, " is not deterministic")?;
+                write_diff(f, ("first", first), ("second", second))
+            }
+            MismatchKind::Bytes { actual, bit, path } => {
+                write!(f, " differs at bit {} (byte {})", bit, bit / 8)?;
+                if let Some(path) = path {
+                    write!(f, " in `{}`", path)?;
+                }
+                writeln!(f)?;
+                write_diff(f, ("expected", &self.expected), ("actual", actual))
+            }
+            MismatchKind::Decode(error) => {
+                writeln!(f, " failed to decode the expected bytes: {}", error)
+            }
+            MismatchKind::Decoded(value) => writeln!(
+                f,
+                " matches, but the expected bytes decode to another value: {}",
+                value
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+/// Writes the hex and bits of both encodings around their first difference, marking the
+/// differing bit
+fn write_diff(
+    f: &mut Formatter<'_>,
+    (a_label, a): (&str, &[u8]),
+    (b_label, b): (&str, &[u8]),
+) -> std::fmt::Result {
+    let bit = first_differing_bit(a, b);
+    let start = (bit / 8).saturating_sub(CONTEXT_BYTES);
+    let width = a_label.len().max(b_label.len());
+    let shown = |bytes: &[u8]| {
+        let shown = bytes.iter().skip(start).take(SHOWN_BYTES);
+        let hex = shown
+            .clone()
+            .map(|byte| format!("{:02x}{:7}", byte, ""))
+            .collect::<String>();
+        let bits = shown
+            .map(|byte| format!("{:08b}", byte))
+            .collect::<Vec<_>>()
+            .join(" ");
+        (hex, bits)
+    };
+
+    let prefix = if start > 0 { ".. " } else { "" };
+    for (label, bytes) in [(a_label, a), (b_label, b)] {
+        let (hex, bits) = shown(bytes);
+        let suffix = if bytes.len() > start + SHOWN_BYTES {
+            " .."
+        } else {
+            ""
+        };
+        writeln!(
+            f,
+            "  {:>width$} ({:>3} bytes): {}{}",
+            label,
+            bytes.len(),
+            prefix,
+            hex.trim_end()
+        )?;
+        writeln!(
+            f,
+            "  {:>width$}              {}{}{}",
+            "", prefix, bits, suffix
+        )?;
+    }
+    // one space between the bits of two bytes
+    let column = (bit - start * 8) + (bit / 8 - start);
+    writeln!(f, "  {:>width$}              {}{:column$}^", "", prefix, "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(Ok(vec![0xAB, 0xCD]), parse_hex("0xAB 0xCD"));
+        assert_eq!(Ok(vec![0xAB, 0xCD, 0x01]), parse_hex("abcd, 01"));
+        assert_eq!(Ok(vec![0xAB, 0xCD]), parse_hex(" 0XAB\n CD "));
+        assert_eq!(Ok(Vec::new()), parse_hex(""));
+        assert_eq!(Err(InvalidHex("0xABC".to_string())), parse_hex("0xABC"));
+        assert_eq!(Err(InvalidHex("0x".to_string())), parse_hex("0x"));
+        assert_eq!(Err(InvalidHex("zz".to_string())), parse_hex("00 zz"));
+    }
+
+    #[test]
+    fn test_first_differing_bit() {
+        assert_eq!(3, first_differing_bit(&[0x00], &[0x10]));
+        assert_eq!(15, first_differing_bit(&[0x00, 0x00], &[0x00, 0x01]));
+        assert_eq!(8, first_differing_bit(&[0x00], &[0x00, 0x01]));
+    }
+}
@@ -1,5 +1,16 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 #![warn(unused_extern_crates)]
+// Decoding untrusted input must never panic, failures are reported as typed errors instead
+#![cfg_attr(
+    not(test),
+    deny(
+        clippy::panic,
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::todo,
+        clippy::unimplemented
+    )
+)]
 
 #[cfg(feature = "macros")]
 pub extern crate asn1rs_macros as macros;
@@ -11,10 +22,22 @@ pub mod macros {}
 #[macro_use]
 pub mod internal_macros;
 
+pub mod bits;
+#[cfg(feature = "async")]
+pub mod codec;
+pub mod debug;
 pub mod descriptor;
+pub mod error;
+pub mod footprint;
+pub mod golden;
 pub mod prelude;
 pub mod protocol;
+pub mod registry;
 pub mod rw;
+#[cfg(feature = "stats")]
+pub mod stats;
+
+pub use error::Error;
 
 #[cfg(feature = "model")]
 pub mod converter;
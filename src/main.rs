@@ -2,12 +2,32 @@
 #![warn(unused_extern_crates)]
 
 mod converter;
+use asn1rs_model::generate::naming::HashSuffixed;
 use converter::Converter;
 
 pub fn main() {
     let params = <Parameters as clap::Parser>::parse();
+    if let Some(Command::Lint { source_files }) = &params.command {
+        return lint(source_files);
+    }
+    let destination_dir = params.destination_dir.clone().unwrap_or_default();
+
     let mut converter = Converter::default();
 
+    if params.profile {
+        converter.enable_profiling();
+    }
+
+    let mut annotation_profile = if params.etsi_unit_comments {
+        asn1rs_model::asn::AnnotationProfile::etsi()
+    } else {
+        asn1rs_model::asn::AnnotationProfile::default()
+    };
+    for pattern in &params.unit_patterns {
+        annotation_profile.add_unit_pattern(pattern);
+    }
+    converter.set_annotation_profile(annotation_profile);
+
     for source in &params.source_files {
         if let Err(e) = converter.load_file(source) {
             println!("Failed to load file {}: {:?}", source, e);
@@ -15,31 +35,127 @@ pub fn main() {
         }
     }
 
+    for overlay in &params.overlays {
+        match converter.load_overlay_file(overlay) {
+            Err(e) => {
+                println!("Failed to load overlay {}: {:?}", overlay, e);
+                return;
+            }
+            Ok(diagnostics) => {
+                for diagnostic in diagnostics.iter().filter(|d| d.is_conflict()) {
+                    println!("Warning: {}", diagnostic);
+                }
+            }
+        }
+    }
+
+    if let Err(e) = converter.link() {
+        println!("Failed to link: {}", e);
+        return;
+    }
+
+    for (module, constraint) in converter.invisible_constraints() {
+        println!("Warning: {}, {}", module, constraint);
+    }
+
+    for (module, annotation) in converter.ignored_annotations() {
+        println!("Warning: {}, {}", module, annotation);
+    }
+
+    if let Some(substitutions) = &params.substitutions {
+        if let Err(e) = converter.load_substitutions(substitutions) {
+            println!("Failed to load substitutions {}: {:?}", substitutions, e);
+            return;
+        }
+    }
+
+    converter.set_string_type(params.rust_string_type.into());
+    converter.set_octet_string_type(params.rust_octet_string_type.into());
+
     let result = match params.conversion_target {
-        ConversionTarget::Rust => converter.to_rust(&params.destination_dir, |rust| {
+        ConversionTarget::Rust => converter.to_rust(&destination_dir, |rust| {
             rust.set_fields_pub(!params.rust_fields_not_public);
             rust.set_fields_have_getter_and_setter(params.rust_getter_and_setter);
+            rust.set_keep_unused_imports(params.rust_keep_unused_imports);
+            rust.set_derive_default(!params.rust_no_default);
+            rust.set_derive_serde(params.rust_derive_serde);
+            rust.set_heapless_types(params.rust_heapless);
+            rust.set_footprint(params.rust_footprint);
+            rust.set_self_test(!params.rust_no_self_test);
+            rust.set_encoding_docs(params.rust_encoding_docs);
+            rust.set_borrowed_views(params.rust_borrowed_views);
+            rust.set_doc_examples(params.rust_doc_examples.clone());
+            rust.set_scaled_value_helpers(!params.rust_no_scaled_value_helpers);
+            rust.set_choice_trait_objects(params.rust_choice_trait_objects);
+            rust.set_deduplicate_inline_types(!params.rust_keep_duplicate_inline_types);
+            if let Some(max_len) = params.rust_inline_type_max_len {
+                rust.set_naming_strategy(HashSuffixed { max_len });
+            }
+            rust.set_collection_merge(params.rust_collection_merge.into());
+            rust.set_target_profile(params.rust_target_profile.into());
         }),
         #[cfg(feature = "protobuf")]
-        ConversionTarget::Proto => converter.to_protobuf(&params.destination_dir),
+        ConversionTarget::Proto => converter.to_protobuf(&destination_dir),
+        ConversionTarget::Schema => converter.to_schema(&destination_dir),
+        ConversionTarget::Catalog => converter.to_catalog(&destination_dir),
     };
 
     match result {
         Err(e) => println!("Failed to convert: {:?}", e),
         Ok(files) => {
             for (source, mut files) in files {
-                println!("Successfully converted {} => {}", source, files.remove(0));
-                files
-                    .iter()
-                    .for_each(|f| println!("                          => {}", f));
+                let first = files.remove(0);
+                println!(
+                    "Successfully converted {} => {}{}",
+                    source,
+                    first.name,
+                    unchanged_suffix(&first)
+                );
+                files.iter().for_each(|f| {
+                    println!(
+                        "                          => {}{}",
+                        f.name,
+                        unchanged_suffix(f)
+                    )
+                });
             }
         }
     }
+
+    if let Some(profile) = converter.profile() {
+        print!("{}", profile);
+    }
+}
+
+fn lint(source_files: &[String]) {
+    let mut converter = Converter::default();
+    for source in source_files {
+        if let Err(e) = converter.load_file(source) {
+            println!("Failed to load file {}: {:?}", source, e);
+            return;
+        }
+    }
+
+    match converter.lint() {
+        Err(e) => println!("Failed to lint: {:?}", e),
+        Ok(lints) => lints.iter().for_each(|lint| println!("{}", lint)),
+    }
+}
+
+fn unchanged_suffix(file: &converter::WrittenFile) -> &'static str {
+    if file.changed {
+        ""
+    } else {
+        " (unchanged)"
+    }
 }
 
 #[derive(clap::Parser, Debug)]
 #[command(author, version, about, long_about = None)] // Read from `Cargo.toml`
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Parameters {
+    #[command(subcommand)]
+    pub command: Option<Command>,
     #[arg(
         short = 'n',
         long = "rust-fields-not-public",
@@ -54,6 +170,116 @@ pub struct Parameters {
         help = "Whether to generate getter and setter for the fields of the generated rust structs"
     )]
     pub rust_getter_and_setter: bool,
+    #[arg(
+        long = "rust-keep-unused-imports",
+        env = "RUST_KEEP_UNUSED_IMPORTS",
+        help = "Whether to keep imported items that are not referenced, marked with '#[allow(unused_imports)]'"
+    )]
+    pub rust_keep_unused_imports: bool,
+    #[arg(
+        long = "rust-no-default",
+        env = "RUST_NO_DEFAULT",
+        help = "Whether to not derive or implement 'Default' for the generated types"
+    )]
+    pub rust_no_default: bool,
+    #[arg(
+        long = "rust-derive-serde",
+        env = "RUST_DERIVE_SERDE",
+        help = "Whether to derive 'serde::Serialize' and 'serde::Deserialize' for the generated types"
+    )]
+    pub rust_derive_serde: bool,
+    #[arg(
+        long = "rust-heapless",
+        env = "RUST_HEAPLESS",
+        help = "Whether to generate strings and lists with an upper SIZE bound as 'heapless::String's and 'heapless::Vec's"
+    )]
+    pub rust_heapless: bool,
+    #[arg(
+        long = "rust-footprint",
+        env = "RUST_FOOTPRINT",
+        help = "Whether to generate a 'footprint()' fn in each module, which lists the sizes of the types and of their UPER encodings"
+    )]
+    pub rust_footprint: bool,
+    #[arg(
+        long = "rust-no-self-test",
+        env = "RUST_NO_SELF_TEST",
+        help = "Whether to not generate a 'self_test()' fn in each module, which round-trips representative values of its types through the enabled encoding rules"
+    )]
+    pub rust_no_self_test: bool,
+    #[arg(
+        long = "rust-encoding-docs",
+        env = "RUST_ENCODING_DOCS",
+        help = "Whether to state the layout of the UPER encoding of each field and CHOICE variant in its doc comment"
+    )]
+    pub rust_encoding_docs: bool,
+    #[arg(
+        long = "rust-borrowed-views",
+        env = "RUST_BORROWED_VIEWS",
+        help = "Whether to generate views like 'FooRef<'a>' of the types with OCTET STRING and UTF8String fields, which borrow these when decoding UPER"
+    )]
+    pub rust_borrowed_views: bool,
+    #[arg(
+        long = "rust-doc-examples",
+        env = "RUST_DOC_EXAMPLES",
+        help = "Path of the module the generated modules are declared in, such as 'my_crate::generated', to generate doc examples for each type that are run as doctests"
+    )]
+    pub rust_doc_examples: Option<String>,
+    #[arg(
+        long = "rust-no-scaled-value-helpers",
+        env = "RUST_NO_SCALED_VALUE_HELPERS",
+        help = "Whether to not generate fns returning the physical value of integer fields with a unit"
+    )]
+    pub rust_no_scaled_value_helpers: bool,
+    #[arg(
+        long = "rust-choice-trait-objects",
+        env = "RUST_CHOICE_TRAIT_OBJECTS",
+        help = "Whether to generate a trait for each CHOICE that is implemented by the types of its variants, with conversions from and into 'Box<dyn ...>'"
+    )]
+    pub rust_choice_trait_objects: bool,
+    #[arg(
+        long = "rust-keep-duplicate-inline-types",
+        env = "RUST_KEEP_DUPLICATE_INLINE_TYPES",
+        help = "Whether to generate identical types that are declared inline separately instead of once"
+    )]
+    pub rust_keep_duplicate_inline_types: bool,
+    #[arg(
+        long = "rust-inline-type-max-len",
+        env = "RUST_INLINE_TYPE_MAX_LEN",
+        help = "The maximum length of the names of types that are declared inline, longer names are cut and suffixed by a hash of the whole name"
+    )]
+    pub rust_inline_type_max_len: Option<usize>,
+    #[arg(
+        value_enum,
+        long = "rust-collection-merge",
+        env = "RUST_COLLECTION_MERGE",
+        help = "Whether the generated 'merge_from' fns replace or append to SEQUENCE OF and SET OF fields",
+        default_value = "replace"
+    )]
+    pub rust_collection_merge: RustCollectionMerge,
+    #[arg(
+        value_enum,
+        long = "rust-string-type",
+        env = "RUST_STRING_TYPE",
+        help = "The type of the string fields in the generated rust code",
+        default_value = "string"
+    )]
+    pub rust_string_type: RustStringType,
+    #[arg(
+        value_enum,
+        long = "rust-octet-string-type",
+        env = "RUST_OCTET_STRING_TYPE",
+        help = "The type of the OCTET STRING fields in the generated rust code",
+        default_value = "vec-u8"
+    )]
+    pub rust_octet_string_type: RustOctetStringType,
+    #[arg(
+        value_enum,
+        long = "rust-target-profile",
+        env = "RUST_TARGET_PROFILE",
+        help = "The environment the generated rust code is compiled for",
+        default_value = "std"
+    )]
+    pub rust_target_profile: RustTargetProfile,
     #[arg(
         value_enum,
         short = 't',
@@ -63,15 +289,128 @@ pub struct Parameters {
         default_value = "rust"
     )]
     pub conversion_target: ConversionTarget,
-    #[arg(env = "DESTINATION_DIR")]
-    pub destination_dir: String,
+    #[arg(
+        short = 's',
+        long = "substitutions",
+        env = "SUBSTITUTIONS",
+        help = "File with substitutions of ASN.1 types by custom Rust types, one `<ASN.1 type> => <Rust type> [via <Rust type>]` per line"
+    )]
+    pub substitutions: Option<String>,
+    #[arg(
+        long = "overlay",
+        env = "OVERLAYS",
+        help = "ASN.1 module that replaces or adds definitions of the loaded module with the same name, such as a vendor profile of a standard"
+    )]
+    pub overlays: Vec<String>,
+    #[arg(
+        long = "unit-pattern",
+        env = "UNIT_PATTERN",
+        help = "Pattern for comments that document the unit of a component, such as 'in units of {}', where '{}' is the unit"
+    )]
+    pub unit_patterns: Vec<String>,
+    #[arg(
+        long = "etsi-unit-comments",
+        env = "ETSI_UNIT_COMMENTS",
+        help = "Whether to extract the units of components from comments like 'Unit: 0.01 degree' as used by ETSI specifications"
+    )]
+    pub etsi_unit_comments: bool,
+    #[arg(
+        long = "profile",
+        help = "Whether to report the time spent per phase and for generating each definition"
+    )]
+    pub profile: bool,
+    /// Always present, unless a [`Command`] is given
+    #[arg(env = "DESTINATION_DIR", required = true)]
+    pub destination_dir: Option<String>,
     #[arg(env = "SOURCE_FILES")]
     pub source_files: Vec<String>,
 }
 
+#[derive(clap::Subcommand, Debug)]
+pub enum Command {
+    /// Reports patterns of the definitions that blow up the size of their PER encoding, such as
+    /// unbounded INTEGERs and SEQUENCE OFs
+    Lint {
+        #[arg(required = true)]
+        source_files: Vec<String>,
+    },
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
 pub enum ConversionTarget {
     Rust,
     #[cfg(feature = "protobuf")]
     Proto,
+    Schema,
+    Catalog,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum RustStringType {
+    String,
+    BoxStr,
+    ArcStr,
+    /// Requires the `smol_str` feature of `asn1rs` in the crate using the generated code
+    SmolStr,
+}
+
+impl From<RustStringType> for asn1rs_model::asn::StringType {
+    fn from(value: RustStringType) -> Self {
+        match value {
+            RustStringType::String => Self::String,
+            RustStringType::BoxStr => Self::BoxStr,
+            RustStringType::ArcStr => Self::ArcStr,
+            RustStringType::SmolStr => Self::SmolStr,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum RustOctetStringType {
+    VecU8,
+    /// Requires the `bytes` feature of `asn1rs` in the crate using the generated code
+    Bytes,
+}
+
+impl From<RustOctetStringType> for asn1rs_model::asn::OctetStringType {
+    fn from(value: RustOctetStringType) -> Self {
+        match value {
+            RustOctetStringType::VecU8 => Self::VecU8,
+            RustOctetStringType::Bytes => Self::Bytes,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum RustCollectionMerge {
+    Replace,
+    Append,
+}
+
+impl From<RustCollectionMerge> for asn1rs_model::generate::CollectionMerge {
+    fn from(value: RustCollectionMerge) -> Self {
+        match value {
+            RustCollectionMerge::Replace => Self::Replace,
+            RustCollectionMerge::Append => Self::Append,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum RustTargetProfile {
+    Std,
+    /// `#![no_std]` with the `alloc` crate
+    Alloc,
+    /// `#![no_std]` without an allocator
+    CoreHeapless,
+}
+
+impl From<RustTargetProfile> for asn1rs_model::generate::TargetProfile {
+    fn from(value: RustTargetProfile) -> Self {
+        match value {
+            RustTargetProfile::Std => Self::Std,
+            RustTargetProfile::Alloc => Self::Alloc,
+            RustTargetProfile::CoreHeapless => Self::CoreHeapless,
+        }
+    }
 }
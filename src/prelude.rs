@@ -1,7 +1,44 @@
+//! Everything needed to use the generated types and the code of the `asn_to_rust!` and `asn`
+//! macros. The items exported here are the stable API surface and are pinned by the
+//! `prelude_api` tests. Everything else is reachable through its module, like
+//! [`crate::rw`], [`crate::protocol`] and [`crate::descriptor`].
+
+// the traits implemented by and the types used in the generated code
 pub use crate::descriptor::prelude::*;
 #[cfg(feature = "macros")]
 pub use crate::macros::*;
+
+// the protocols, for their `Error` and `ErrorKind` types
+pub use crate::protocol::basic;
+#[cfg(feature = "cbor")]
+pub use crate::protocol::cbor;
+#[cfg(feature = "oer")]
+pub use crate::protocol::oer;
+pub use crate::protocol::per;
+#[cfg(feature = "protobuf")]
+pub use crate::protocol::protobuf;
 #[cfg(feature = "protobuf")]
 pub use crate::protocol::protobuf::ProtobufEq;
-pub use crate::protocol::*;
-pub use crate::rw::*;
+
+// the readers and writers
+pub use crate::descriptor::numbers::{DecodePolicy, DecodePolicyReader, RecoveredValue};
+pub use crate::rw::DepthLimit;
+pub use crate::rw::PrintlnWriter;
+#[cfg(feature = "descriptive-deserialize-errors")]
+pub use crate::rw::ScopeDescription;
+#[cfg(feature = "serde")]
+pub use crate::rw::UperTranscoder;
+pub use crate::rw::{BasicReader, BasicWriter};
+pub use crate::rw::{BerReader, BerWriter, DerWriter};
+pub use crate::rw::{
+    Bits, Decoded, ReaderMark, UperDecodable, UperIter, UperReader, UperSizeHint, UperStreamReader,
+    UperWriter, WriterMark,
+};
+#[cfg(feature = "cbor")]
+pub use crate::rw::{CborReader, CborWriter};
+#[cfg(feature = "oer")]
+pub use crate::rw::{CoerReader, CoerWriter};
+pub use crate::rw::{Landmark, Mutant, Mutation, UperMutator};
+pub use crate::rw::{PositionedReader, PositionedWriter};
+#[cfg(feature = "protobuf")]
+pub use crate::rw::{ProtobufJsonWriter, ProtobufReader, ProtobufWriter};
@@ -0,0 +1,287 @@
+//! The identifier and length octets of the Basic Encoding Rules (BER), according to ITU-T X.690,
+//! chapter 8.1. Other than the [`BasicRead`] and [`BasicWrite`] implementations, these
+//! functions support the constructed flag, tag numbers greater than 30 and indefinite lengths.
+//!
+//! [`BasicRead`]: crate::protocol::basic::BasicRead
+//! [`BasicWrite`]: crate::protocol::basic::BasicWrite
+
+#![allow(clippy::unusual_byte_groupings)]
+
+use crate::protocol::basic::err::{Error, ErrorKind};
+use crate::protocol::basic::EncodingRules;
+use crate::rw::{BerReader, BerWriter};
+use asn1rs_model::asn::Tag;
+
+pub type BER = BasicEncodingRules;
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BasicEncodingRules;
+
+impl EncodingRules for BasicEncodingRules {
+    const DISTINGUISHED: bool = false;
+}
+
+impl BasicEncodingRules {
+    #[inline]
+    pub fn writer() -> BerWriter {
+        BerWriter::default()
+    }
+
+    #[inline]
+    pub fn reader(bytes: &[u8]) -> BerReader<'_> {
+        BerReader::from(bytes)
+    }
+}
+
+const CLASS_BITS_UNIVERSAL: u8 = 0b_00_000000;
+const CLASS_BITS_APPLICATION: u8 = 0b_01_000000;
+const CLASS_BITS_CONTEXT_SPECIFIC: u8 = 0b_10_000000;
+const CLASS_BITS_PRIVATE: u8 = 0b_11_000000;
+const CONSTRUCTED_BIT: u8 = 0b_00_1_00000;
+const NUMBER_BITS: u8 = 0b_00_0_11111;
+
+const LENGTH_BIT_LONG_FORM: u8 = 0b1_0000000;
+const LENGTH_INDEFINITE: u8 = 0b1_0000000;
+
+/// Writes the identifier octets (ITU-T X.690, chapter 8.1.2) of a primitive or constructed value
+pub fn write_identifier(buffer: &mut Vec<u8>, tag: Tag, constructed: bool) {
+    let class = match tag {
+        Tag::Universal(_) => CLASS_BITS_UNIVERSAL,
+        Tag::Application(_) => CLASS_BITS_APPLICATION,
+        Tag::ContextSpecific(_) => CLASS_BITS_CONTEXT_SPECIFIC,
+        Tag::Private(_) => CLASS_BITS_PRIVATE,
+    };
+    let class = if constructed {
+        class | CONSTRUCTED_BIT
+    } else {
+        class
+    };
+    let number = tag.value();
+    if number < usize::from(NUMBER_BITS) {
+        buffer.push(class | number as u8);
+    } else {
+        // high tag number form 8.1.2.4, base 128 with the most significant group first
+        buffer.push(class | NUMBER_BITS);
+        let groups = (usize::BITS - number.leading_zeros()).div_ceil(7);
+        for group in (0..groups).rev() {
+            let more = if group > 0 { 0x80 } else { 0x00 };
+            buffer.push(more | ((number >> (group * 7)) & 0x7F) as u8);
+        }
+    }
+}
+
+/// Reads the identifier octets at the given position and returns the tag and whether the value
+/// is constructed. The position is advanced to the length octets.
+pub fn read_identifier(bytes: &[u8], position: &mut usize) -> Result<(Tag, bool), Error> {
+    let octet = next(bytes, position)?;
+    let constructed = octet & CONSTRUCTED_BIT != 0;
+    let mut number = usize::from(octet & NUMBER_BITS);
+
+    if number == usize::from(NUMBER_BITS) {
+        number = 0;
+        loop {
+            let octet = next(bytes, position)?;
+            if number.leading_zeros() < 7 {
+                return Err(Error::invalid_encoding(
+                    "tag number exceeds the supported range",
+                ));
+            }
+            number = (number << 7) | usize::from(octet & 0x7F);
+            if octet & 0x80 == 0 {
+                break;
+            }
+        }
+    }
+
+    let tag = match octet & !(CONSTRUCTED_BIT | NUMBER_BITS) {
+        CLASS_BITS_UNIVERSAL => Tag::Universal(number),
+        CLASS_BITS_APPLICATION => Tag::Application(number),
+        CLASS_BITS_CONTEXT_SPECIFIC => Tag::ContextSpecific(number),
+        _ => Tag::Private(number),
+    };
+    Ok((tag, constructed))
+}
+
+/// Reads the length octets (ITU-T X.690, chapter 8.1.3) at the given position, `None` denotes
+/// the indefinite form (8.1.3.6). The position is advanced to the content octets.
+pub fn read_length(bytes: &[u8], position: &mut usize) -> Result<Option<u64>, Error> {
+    let octet = next(bytes, position)?;
+    if octet & LENGTH_BIT_LONG_FORM == 0 {
+        Ok(Some(u64::from(octet)))
+    } else if octet == LENGTH_INDEFINITE {
+        Ok(None)
+    } else {
+        let octets = usize::from(octet & !LENGTH_BIT_LONG_FORM);
+        let mut length = 0_u64;
+        for _ in 0..octets {
+            if length.leading_zeros() < u8::BITS {
+                return Err(Error::unsupported_byte_len(
+                    core::mem::size_of::<u64>() as u8,
+                    octets as u8,
+                ));
+            }
+            length = (length << u8::BITS) | u64::from(next(bytes, position)?);
+        }
+        Ok(Some(length))
+    }
+}
+
+/// Finds the end-of-contents octets (ITU-T X.690, chapter 8.1.5) that end the content of a value
+/// of indefinite length, which starts at the given position, and returns their position. The
+/// nested values are skipped, including those of indefinite length themselves.
+pub fn find_end_of_contents(bytes: &[u8], mut position: usize) -> Result<usize, Error> {
+    let mut depth = 0_usize;
+    loop {
+        if bytes.get(position..position + 2) == Some(&[0x00, 0x00]) {
+            if depth == 0 {
+                return Ok(position);
+            }
+            depth -= 1;
+            position += 2;
+            continue;
+        }
+
+        let (_tag, constructed) = read_identifier(bytes, &mut position)?;
+        match read_length(bytes, &mut position)? {
+            Some(length) => {
+                position = usize::try_from(length)
+                    .ok()
+                    .and_then(|length| position.checked_add(length))
+                    .filter(|end| *end <= bytes.len())
+                    .ok_or(ErrorKind::EndOfContent)?;
+            }
+            None if constructed => depth += 1,
+            None => {
+                return Err(Error::invalid_encoding(
+                    "indefinite length of a primitive value",
+                ))
+            }
+        }
+    }
+}
+
+/// Writes the content octets of an integer (ITU-T X.690, chapter 8.3) in its shortest two's
+/// complement form
+pub fn write_integer(buffer: &mut Vec<u8>, value: i64) {
+    let bytes = value.to_be_bytes();
+    let redundant = if value < 0 {
+        value.leading_ones()
+    } else {
+        value.leading_zeros()
+    };
+    // one bit of the first octet has to remain for the sign
+    let offset = ((redundant.saturating_sub(1)) / u8::BITS) as usize;
+    buffer.extend_from_slice(&bytes[offset.min(bytes.len() - 1)..]);
+}
+
+/// Reads the two's complement content octets of an integer (ITU-T X.690, chapter 8.3)
+pub fn read_integer(content: &[u8]) -> Result<i64, Error> {
+    match content.first() {
+        None => Err(Error::invalid_encoding("integer without content octets")),
+        Some(_) if content.len() > core::mem::size_of::<i64>() => Err(Error::unsupported_byte_len(
+            core::mem::size_of::<i64>() as u8,
+            content.len() as u8,
+        )),
+        Some(first) => {
+            let init = if *first & 0x80 != 0 { -1_i64 } else { 0_i64 };
+            Ok(content
+                .iter()
+                .fold(init, |value, byte| (value << u8::BITS) | i64::from(*byte)))
+        }
+    }
+}
+
+#[inline]
+fn next(bytes: &[u8], position: &mut usize) -> Result<u8, Error> {
+    let byte = *bytes.get(*position).ok_or(ErrorKind::EndOfContent)?;
+    *position += 1;
+    Ok(byte)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identifier_round_trip(tag: Tag, constructed: bool, expected: &[u8]) {
+        let mut buffer = Vec::new();
+        write_identifier(&mut buffer, tag, constructed);
+        assert_eq!(expected, &buffer[..]);
+
+        let mut position = 0;
+        assert_eq!(
+            (tag, constructed),
+            read_identifier(&buffer, &mut position).unwrap()
+        );
+        assert_eq!(buffer.len(), position);
+    }
+
+    #[test]
+    fn test_identifier() {
+        identifier_round_trip(Tag::DEFAULT_BOOLEAN, false, &[0x01]);
+        identifier_round_trip(Tag::DEFAULT_SEQUENCE, true, &[0x30]);
+        identifier_round_trip(Tag::ContextSpecific(2), true, &[0xA2]);
+        identifier_round_trip(Tag::Application(30), false, &[0x5E]);
+        identifier_round_trip(Tag::Application(31), false, &[0x5F, 0x1F]);
+        identifier_round_trip(Tag::Private(201), true, &[0xFF, 0x81, 0x49]);
+    }
+
+    fn integer_round_trip(value: i64, expected: &[u8]) {
+        let mut buffer = Vec::new();
+        write_integer(&mut buffer, value);
+        assert_eq!(expected, &buffer[..]);
+        assert_eq!(value, read_integer(&buffer).unwrap());
+    }
+
+    #[test]
+    fn test_integer() {
+        integer_round_trip(0, &[0x00]);
+        integer_round_trip(127, &[0x7F]);
+        integer_round_trip(128, &[0x00, 0x80]);
+        integer_round_trip(256, &[0x01, 0x00]);
+        integer_round_trip(-1, &[0xFF]);
+        integer_round_trip(-128, &[0x80]);
+        integer_round_trip(-129, &[0xFF, 0x7F]);
+        integer_round_trip(i64::MAX, &i64::MAX.to_be_bytes());
+        integer_round_trip(i64::MIN, &i64::MIN.to_be_bytes());
+    }
+
+    #[test]
+    fn test_length_forms() {
+        let mut position = 0;
+        assert_eq!(Some(5), read_length(&[0x05], &mut position).unwrap());
+        position = 0;
+        assert_eq!(
+            Some(0x0100),
+            read_length(&[0x82, 0x01, 0x00], &mut position).unwrap()
+        );
+        assert_eq!(3, position);
+        position = 0;
+        assert_eq!(None, read_length(&[0x80], &mut position).unwrap());
+        position = 0;
+        assert!(matches!(
+            read_length(&[0x82, 0x01], &mut position)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::EndOfContent
+        ));
+    }
+
+    #[test]
+    fn test_find_end_of_contents() {
+        // INTEGER 1, nested SEQUENCE of indefinite length with an INTEGER 2, end-of-contents
+        let bytes = [
+            0x02, 0x01, 0x01, 0x30, 0x80, 0x02, 0x01, 0x02, 0x00, 0x00, 0x00, 0x00,
+        ];
+        assert_eq!(10, find_end_of_contents(&bytes, 0).unwrap());
+        assert_eq!(8, find_end_of_contents(&bytes, 5).unwrap());
+        assert!(matches!(
+            find_end_of_contents(&bytes[..10], 0).unwrap_err().kind(),
+            ErrorKind::EndOfContent
+        ));
+        assert!(matches!(
+            find_end_of_contents(&[0x02, 0x80, 0x00, 0x00], 0)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::InvalidEncoding(_)
+        ));
+    }
+}
@@ -1,14 +1,19 @@
 #![allow(clippy::unusual_byte_groupings)]
 
 use crate::protocol::basic::err::Error;
-use crate::protocol::basic::{BasicRead, BasicWrite};
+use crate::protocol::basic::{BasicRead, BasicWrite, BasicWriteLengthPrefixed, EncodingRules};
 use crate::rw::{BasicReader, BasicWriter};
 use asn1rs_model::asn::Tag;
 use std::io::{Read, Write};
 
 pub type DER = DistinguishedEncodingRules;
+#[derive(Debug, Default, Clone, Copy)]
 pub struct DistinguishedEncodingRules;
 
+impl EncodingRules for DistinguishedEncodingRules {
+    const DISTINGUISHED: bool = true;
+}
+
 impl DistinguishedEncodingRules {
     #[inline]
     pub fn writer<W: BasicWrite<Flavor = Self>>(write: W) -> BasicWriter<W> {
@@ -156,6 +161,29 @@ impl<T: Write> BasicWrite for T {
     }
 }
 
+impl BasicWriteLengthPrefixed for Vec<u8> {
+    fn write_length_prefixed<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<T, Error> {
+        let start = self.len();
+        // reserve the short form, which is sufficient for most values
+        self.push(LENGTH_BIT_SHORT_FORM);
+        let result = f(self)?;
+        let length = (self.len() - start - 1) as u64;
+
+        if length <= LENGTH_SHORT_MAX_VALUE {
+            self[start] = LENGTH_BIT_SHORT_FORM | length as u8;
+        } else {
+            let mut long_form = Vec::with_capacity(1 + core::mem::size_of::<u64>());
+            long_form.write_length(length)?;
+            self.splice(start..start + 1, long_form);
+        }
+
+        Ok(result)
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -184,4 +212,31 @@ pub mod tests {
         write_read_length_check(u64::MAX - 1);
         write_read_length_check(u64::MAX);
     }
+
+    fn write_length_prefixed_check(len: usize) {
+        let content = (0..len).map(|i| i as u8).collect::<Vec<_>>();
+
+        let mut expected = vec![0xAB];
+        expected.write_length(len as u64).unwrap();
+        expected.extend_from_slice(&content);
+
+        let mut buffer = vec![0xAB];
+        buffer
+            .write_length_prefixed(|b| {
+                b.extend_from_slice(&content);
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(expected, buffer);
+    }
+
+    #[test]
+    pub fn test_length_prefixed() {
+        write_length_prefixed_check(0);
+        write_length_prefixed_check(LENGTH_SHORT_MAX_VALUE as usize);
+        write_length_prefixed_check(LENGTH_SHORT_MAX_VALUE as usize + 1);
+        write_length_prefixed_check(u8::MAX as usize + 1);
+        write_length_prefixed_check(u16::MAX as usize + 1);
+    }
 }
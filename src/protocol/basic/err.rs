@@ -1,4 +1,5 @@
-use asn1rs_model::asn::Tag;
+use crate::protocol::FieldName;
+use asn1rs_model::asn::{Charset, Tag};
 use backtrace::Backtrace;
 use std::fmt::{Debug, Display, Formatter};
 use std::ops::Range;
@@ -34,6 +35,61 @@ impl Error {
     pub fn unsupported_byte_len(max: u8, got: u8) -> Self {
         Self::from(ErrorKind::UnsupportedByteLen { max, got })
     }
+
+    #[cold]
+    #[inline(never)]
+    pub fn unsupported_operation(operation: &'static str) -> Self {
+        Self::from(ErrorKind::UnsupportedOperation(operation))
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn invalid_encoding(reason: &'static str) -> Self {
+        Self::from(ErrorKind::InvalidEncoding(reason))
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn value_not_in_range(
+        value: i64,
+        min: i64,
+        max: i64,
+        definition: Option<&'static str>,
+        field: Option<&'static str>,
+    ) -> Self {
+        Self::from(ErrorKind::ValueNotInRange {
+            value,
+            min,
+            max,
+            definition,
+            field,
+        })
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn size_not_in_range(size: u64, min: u64, max: u64) -> Self {
+        Self::from(ErrorKind::SizeNotInRange { size, min, max })
+    }
+
+    pub fn ensure_string_valid(charset: Charset, str: &str) -> Result<(), Self> {
+        match charset.find_invalid(str) {
+            None => Ok(()),
+            Some((index, char)) => Err(ErrorKind::InvalidString(charset, char, index).into()),
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn content_transform_failed<E: Display>(error: E) -> Self {
+        Self::from(ErrorKind::ContentTransformFailed(error.to_string()))
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn substitution_failed<E: Display>(error: E) -> Self {
+        Self::from(ErrorKind::SubstitutionFailed(error.to_string()))
+    }
 }
 
 impl From<ErrorKind> for Error {
@@ -70,6 +126,13 @@ impl std::error::Error for Error {
     fn description(&self) -> &str {
         "encoding or decoding with basic rules failed"
     }
+
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self.kind() {
+            ErrorKind::IoError(e) => Some(e),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -90,10 +153,47 @@ impl From<ErrorKind> for Inner {
 
 #[derive(Debug)]
 pub enum ErrorKind {
-    UnexpectedTypeTag { expected: Tag, got: Tag },
-    UnexpectedTypeLength { expected: Range<u64>, got: u64 },
-    UnexpectedChoiceIndex { expected: Range<u64>, got: u64 },
-    UnsupportedByteLen { max: u8, got: u8 },
+    UnexpectedTypeTag {
+        expected: Tag,
+        got: Tag,
+    },
+    UnexpectedTypeLength {
+        expected: Range<u64>,
+        got: u64,
+    },
+    UnexpectedChoiceIndex {
+        expected: Range<u64>,
+        got: u64,
+    },
+    UnsupportedByteLen {
+        max: u8,
+        got: u8,
+    },
+    UnsupportedOperation(&'static str),
+    /// The content ends before the announced length or an expected value
+    EndOfContent,
+    /// The content of the constructed value was not completely consumed
+    TrailingContent(usize),
+    InvalidEncoding(&'static str),
+    ValueNotInRange {
+        value: i64,
+        min: i64,
+        max: i64,
+        /// The name of the definition the value belongs to, if known
+        definition: Option<&'static str>,
+        /// The name of the field that holds the value, if known
+        field: Option<&'static str>,
+    },
+    SizeNotInRange {
+        size: u64,
+        min: u64,
+        max: u64,
+    },
+    InvalidString(Charset, char, usize),
+    /// The values are nested deeper than the maximum depth of the reader or writer
+    NestingDepthExceedsLimit(usize),
+    ContentTransformFailed(String),
+    SubstitutionFailed(String),
     IoError(std::io::Error),
 }
 
@@ -115,6 +215,49 @@ impl Display for ErrorKind {
                     "Unsupported byte length received, max={max:?} but got {got:?}"
                 )
             }
+            ErrorKind::UnsupportedOperation(operation) => {
+                write!(f, "The operation {operation} is not supported (yet)")
+            }
+            ErrorKind::EndOfContent => write!(f, "Unexpected end of content"),
+            ErrorKind::TrailingContent(bytes) => {
+                write!(f, "The content has {bytes} unexpected trailing bytes")
+            }
+            ErrorKind::InvalidEncoding(reason) => write!(f, "Invalid encoding: {reason}"),
+            ErrorKind::ValueNotInRange {
+                value,
+                min,
+                max,
+                definition,
+                field,
+            } => {
+                let name = FieldName(*definition, *field);
+                write!(f, "The value {value}{name} is not within {min}..={max}")
+            }
+            ErrorKind::SizeNotInRange { size, min, max } => {
+                write!(f, "The size {size} is not within {min}..={max}")
+            }
+            ErrorKind::InvalidString(charset, char, index) => {
+                write!(
+                    f,
+                    "The character {char:?} (U+{:04X}) at index {index} is not valid for a {charset}",
+                    *char as u32
+                )
+            }
+            ErrorKind::NestingDepthExceedsLimit(max) => {
+                write!(
+                    f,
+                    "The values are nested deeper than the limit of {max} levels"
+                )
+            }
+            ErrorKind::ContentTransformFailed(message) => {
+                write!(f, "Failed to transform the content: {message}")
+            }
+            ErrorKind::SubstitutionFailed(message) => {
+                write!(
+                    f,
+                    "Failed to convert the value into its substitute: {message}"
+                )
+            }
             ErrorKind::IoError(e) => {
                 write!(f, "Experienced underlying IO error: {e:?}")
             }
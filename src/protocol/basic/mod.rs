@@ -1,11 +1,16 @@
 //! This module contains defines traits to encode and decode basic ASN.1 primitives and types of
 //! the basic family (BER, DER, CER).
 
+pub mod ber;
 mod distinguished;
 mod err;
+pub(crate) mod oid;
+pub(crate) mod real;
 
+pub use ber::{BasicEncodingRules, BER};
 pub use distinguished::*;
 pub use err::Error;
+pub use err::ErrorKind;
 
 use asn1rs_model::asn::Tag;
 
@@ -54,3 +59,21 @@ pub trait BasicWrite {
     /// According to ITU-T X.690, chapter 8.3, the integer type is represented in a series of bytes.
     fn write_integer_u64(&mut self, value: u64) -> Result<(), Error>;
 }
+
+/// The rules of the basic family that a [`TlvWriter`](crate::rw::TlvWriter) encodes with
+pub trait EncodingRules {
+    /// Whether the restrictions of the Distinguished Encoding Rules (ITU-T X.690, chapter 10 and
+    /// 11) apply, which leave a single encoding for each value
+    const DISTINGUISHED: bool;
+}
+
+/// Writers that are able to reserve the length of a value and patch it once the content has been
+/// written, so that no temporary buffer is required to determine the definite length (ITU-T
+/// X.690, chapter 8.1.3) of constructed types
+pub trait BasicWriteLengthPrefixed: BasicWrite {
+    /// Writes the definite length of everything `f` writes, followed by that content
+    fn write_length_prefixed<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<T, Error>;
+}
@@ -0,0 +1,194 @@
+//! The contents octets of an `OBJECT IDENTIFIER`, ITU-T X.690, 8.19, of a `RELATIVE-OID`, 8.20,
+//! and of an `OID-IRI`, 8.21. Besides the basic encoding rules, the packed (ITU-T X.691) and the
+//! octet encoding rules (ITU-T X.696) embed these contents octets after a length determinant.
+
+/// Encodes the arcs as subidentifiers of base 128, of which the first one combines the first two
+/// arcs, ITU-T X.690, 8.19.4
+pub(crate) fn encode(arcs: &[u64]) -> Result<Vec<u8>, &'static str> {
+    let (first, second, rest) = match arcs {
+        [first, second, rest @ ..] => (*first, *second, rest),
+        _ => return Err("an object identifier needs at least two arcs"),
+    };
+    if first > 2 {
+        return Err("the first arc of an object identifier must be 0, 1 or 2");
+    } else if first < 2 && second > 39 {
+        return Err("the second arc of an object identifier must be less than 40");
+    }
+    let combined = (first * 40)
+        .checked_add(second)
+        .ok_or("the second arc of an object identifier is too large")?;
+
+    let mut contents = Vec::with_capacity(arcs.len() + 1);
+    write_subidentifier(&mut contents, combined);
+    for arc in rest {
+        write_subidentifier(&mut contents, *arc);
+    }
+    Ok(contents)
+}
+
+/// Decodes the contents octets into the arcs of the object identifier
+pub(crate) fn decode(contents: &[u8]) -> Result<Vec<u64>, &'static str> {
+    let mut arcs = Vec::with_capacity(contents.len() + 1);
+    let mut subidentifiers = Subidentifiers(contents);
+    let combined = subidentifiers
+        .next()
+        .ok_or("an object identifier needs at least one subidentifier")??;
+    let first = (combined / 40).min(2);
+    arcs.push(first);
+    arcs.push(combined - first * 40);
+    for subidentifier in subidentifiers {
+        arcs.push(subidentifier?);
+    }
+    Ok(arcs)
+}
+
+/// Encodes each arc as subidentifier of base 128, ITU-T X.690, 8.20
+pub(crate) fn encode_relative(arcs: &[u64]) -> Result<Vec<u8>, &'static str> {
+    if arcs.is_empty() {
+        return Err("a relative object identifier needs at least one arc");
+    }
+    let mut contents = Vec::with_capacity(arcs.len());
+    for arc in arcs {
+        write_subidentifier(&mut contents, *arc);
+    }
+    Ok(contents)
+}
+
+/// Decodes the contents octets into the arcs of the relative object identifier
+pub(crate) fn decode_relative(contents: &[u8]) -> Result<Vec<u64>, &'static str> {
+    if contents.is_empty() {
+        return Err("a relative object identifier needs at least one subidentifier");
+    }
+    Subidentifiers(contents).collect()
+}
+
+/// Checks the structure of an `OID-IRI` like `/ISO/Registration_Authority/19785.CBEFF`: a `/`
+/// before each of at least one non-empty arc, ITU-T X.680, 34.3. The characters of the arcs
+/// (ITU-T X.660, 7.5) are not checked.
+pub(crate) fn check_iri(iri: &str) -> Result<(), &'static str> {
+    match iri.strip_prefix('/') {
+        None => Err("an OID-IRI must start with a '/'"),
+        Some(arcs) if arcs.split('/').any(str::is_empty) => {
+            Err("the arcs of an OID-IRI must not be empty")
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+fn write_subidentifier(contents: &mut Vec<u8>, value: u64) {
+    let groups = (64 - value.leading_zeros()).div_ceil(7).max(1);
+    for group in (0..groups).rev() {
+        let more = if group > 0 { 0x80 } else { 0x00 };
+        contents.push(more | ((value >> (group * 7)) & 0x7F) as u8);
+    }
+}
+
+struct Subidentifiers<'a>(&'a [u8]);
+
+impl Iterator for Subidentifiers<'_> {
+    type Item = Result<u64, &'static str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0.is_empty() {
+            return None;
+        }
+        let Some(end) = self.0.iter().position(|octet| octet & 0x80 == 0) else {
+            self.0 = &[];
+            return Some(Err("the last subidentifier is incomplete"));
+        };
+        let (octets, rest) = self.0.split_at(end + 1);
+        self.0 = rest;
+        if octets[0] == 0x80 {
+            // ITU-T X.690, 8.19.2
+            return Some(Err(
+                "a subidentifier must be encoded in the fewest possible octets",
+            ));
+        }
+        let mut value = 0_u64;
+        for octet in octets {
+            if value.leading_zeros() < 7 {
+                return Some(Err("a subidentifier exceeds 64 bits"));
+            }
+            value = (value << 7) | u64::from(octet & 0x7F);
+        }
+        Some(Ok(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        // ITU-T X.690, 8.19.5: {2 100 3}
+        assert_eq!(Ok(vec![0x81, 0x34, 0x03]), encode(&[2, 100, 3]));
+        // rsaEncryption, 1.2.840.113549.1.1.1
+        assert_eq!(
+            Ok(vec![0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x01]),
+            encode(&[1, 2, 840, 113549, 1, 1, 1])
+        );
+        assert_eq!(Ok(vec![0x00]), encode(&[0, 0]));
+        assert_eq!(
+            Ok(vec![
+                0x01, 0x81, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00
+            ]),
+            encode(&[0, 1, 1 << 63])
+        );
+    }
+
+    #[test]
+    fn test_encode_invalid() {
+        assert!(encode(&[]).is_err());
+        assert!(encode(&[1]).is_err());
+        assert!(encode(&[3, 1]).is_err());
+        assert!(encode(&[1, 40]).is_err());
+        assert!(encode(&[2, u64::MAX]).is_err());
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(Ok(vec![2, 100, 3]), decode(&[0x81, 0x34, 0x03]));
+        assert_eq!(
+            Ok(vec![1, 2, 840, 113549, 1, 1, 1]),
+            decode(&[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x01])
+        );
+        assert_eq!(Ok(vec![0, 39]), decode(&[39]));
+        assert_eq!(Ok(vec![1, 0]), decode(&[40]));
+        assert!(decode(&[]).is_err());
+        assert!(decode(&[0x2A, 0x86]).is_err());
+        assert!(decode(&[0x2A, 0x80, 0x01]).is_err());
+        assert!(
+            decode(&[0x2A, 0x82, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_relative() {
+        // ITU-T X.690, 8.20.5: {8571 3 2}
+        assert_eq!(
+            Ok(vec![0xC2, 0x7B, 0x03, 0x02]),
+            encode_relative(&[8571, 3, 2])
+        );
+        assert_eq!(
+            Ok(vec![8571, 3, 2]),
+            decode_relative(&[0xC2, 0x7B, 0x03, 0x02])
+        );
+        // the first arcs are not combined
+        assert_eq!(Ok(vec![0x02, 0x64]), encode_relative(&[2, 100]));
+        assert!(encode_relative(&[]).is_err());
+        assert!(decode_relative(&[]).is_err());
+        assert!(decode_relative(&[0x02, 0x81]).is_err());
+    }
+
+    #[test]
+    fn test_check_iri() {
+        assert_eq!(Ok(()), check_iri("/ISO/Registration_Authority/19785.CBEFF"));
+        assert_eq!(Ok(()), check_iri("/Joint-ISO-ITU-T"));
+        assert!(check_iri("").is_err());
+        assert!(check_iri("/").is_err());
+        assert!(check_iri("ISO/Registration_Authority").is_err());
+        assert!(check_iri("/ISO//Registration_Authority").is_err());
+        assert!(check_iri("/ISO/").is_err());
+    }
+}
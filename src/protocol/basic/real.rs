@@ -0,0 +1,274 @@
+//! The contents octets of a `REAL`, ITU-T X.690, 8.5. Besides the basic encoding rules, the
+//! packed (ITU-T X.691, 14) and the octet encoding rules (ITU-T X.696, 12) embed these contents
+//! octets after a length determinant.
+
+const PLUS_INFINITY: u8 = 0x40;
+const MINUS_INFINITY: u8 = 0x41;
+const NOT_A_NUMBER: u8 = 0x42;
+const MINUS_ZERO: u8 = 0x43;
+
+const F64_MANTISSA_BITS: u32 = 52;
+const F64_EXPONENT_BIAS: i64 = 1075;
+
+/// Encodes the value in the canonical form of ITU-T X.690, 11.3.1: base 2, no scale factor and
+/// an odd mantissa of as few octets as possible (and therefore also as CER and DER require it)
+pub(crate) fn encode(value: f64) -> Vec<u8> {
+    if value.is_nan() {
+        return vec![NOT_A_NUMBER];
+    } else if value.is_infinite() {
+        return vec![if value > 0.0 {
+            PLUS_INFINITY
+        } else {
+            MINUS_INFINITY
+        }];
+    } else if value == 0.0 {
+        return if value.is_sign_negative() {
+            vec![MINUS_ZERO]
+        } else {
+            Vec::new()
+        };
+    }
+
+    let bits = value.to_bits();
+    let biased_exponent = ((bits >> F64_MANTISSA_BITS) & 0x7FF) as i64;
+    let fraction = bits & ((1 << F64_MANTISSA_BITS) - 1);
+    let (mut mantissa, mut exponent) = if biased_exponent == 0 {
+        // subnormal
+        (fraction, 1 - F64_EXPONENT_BIAS)
+    } else {
+        (
+            fraction | (1 << F64_MANTISSA_BITS),
+            biased_exponent - F64_EXPONENT_BIAS,
+        )
+    };
+    let trailing_zeros = mantissa.trailing_zeros();
+    mantissa >>= trailing_zeros;
+    exponent += i64::from(trailing_zeros);
+
+    let exponent_octets = if i8::try_from(exponent).is_ok() {
+        1
+    } else {
+        // the exponent of a f64 is always within the range of an i16
+        2
+    };
+    let mantissa_octets = (8 - mantissa.leading_zeros() as usize / 8).max(1);
+
+    let sign = if value.is_sign_negative() { 0x40 } else { 0x00 };
+    let mut contents = Vec::with_capacity(1 + exponent_octets + mantissa_octets);
+    contents.push(0x80 | sign | (exponent_octets as u8 - 1));
+    contents.extend_from_slice(&exponent.to_be_bytes()[8 - exponent_octets..]);
+    contents.extend_from_slice(&mantissa.to_be_bytes()[8 - mantissa_octets..]);
+    contents
+}
+
+/// Decodes contents octets of any of the forms of ITU-T X.690, 8.5: binary with any base and
+/// scale factor, decimal (ISO 6093) and the special real values
+pub(crate) fn decode(contents: &[u8]) -> Result<f64, &'static str> {
+    let Some((&first, rest)) = contents.split_first() else {
+        return Ok(0.0);
+    };
+    match first & 0xC0 {
+        0x00 => decode_decimal(rest),
+        0x40 if !rest.is_empty() => Err("a special real value must be a single octet"),
+        0x40 => match first {
+            PLUS_INFINITY => Ok(f64::INFINITY),
+            MINUS_INFINITY => Ok(f64::NEG_INFINITY),
+            NOT_A_NUMBER => Ok(f64::NAN),
+            MINUS_ZERO => Ok(-0.0),
+            _ => Err("reserved special real value"),
+        },
+        _ => decode_binary(first, rest),
+    }
+}
+
+fn decode_binary(first: u8, rest: &[u8]) -> Result<f64, &'static str> {
+    let negative = first & 0x40 != 0;
+    let base_bits = match (first >> 4) & 0x03 {
+        0 => 1,
+        1 => 3,
+        2 => 4,
+        _ => return Err("reserved base"),
+    };
+    let scale_factor = i64::from((first >> 2) & 0x03);
+
+    let (exponent, mantissa) = match first & 0x03 {
+        3 => {
+            let (&len, rest) = rest.split_first().ok_or("missing exponent length")?;
+            let len = usize::from(len);
+            if len == 0 || rest.len() < len {
+                return Err("invalid exponent length");
+            }
+            rest.split_at(len)
+        }
+        format => {
+            let len = usize::from(format) + 1;
+            if rest.len() < len {
+                return Err("missing exponent octets");
+            }
+            rest.split_at(len)
+        }
+    };
+    if mantissa.is_empty() {
+        return Err("missing mantissa octets");
+    }
+
+    let exponent = signed(exponent).ok_or("exponent exceeds 64 bits")?;
+    let mantissa = unsigned(mantissa).ok_or("mantissa exceeds 64 bits")?;
+
+    let exponent = exponent
+        .saturating_mul(base_bits)
+        .saturating_add(scale_factor);
+    let value = scale(mantissa as f64, exponent);
+    Ok(if negative { -value } else { value })
+}
+
+fn decode_decimal(rest: &[u8]) -> Result<f64, &'static str> {
+    let text = core::str::from_utf8(rest).map_err(|_| "decimal encoding is not ISO 6093")?;
+    let text = text.trim_start_matches(' ').replace(',', ".");
+    // excludes `inf` and `NaN`, which `f64::from_str` accepts but ISO 6093 does not
+    if text.is_empty()
+        || !text
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | '+' | '-' | 'e' | 'E'))
+    {
+        return Err("decimal encoding is not ISO 6093");
+    }
+    text.parse::<f64>()
+        .map_err(|_| "decimal encoding is not ISO 6093")
+}
+
+/// The value of the two's complement big-endian octets, if it fits into an `i64`
+fn signed(mut octets: &[u8]) -> Option<i64> {
+    // drop the leading octets that only repeat the sign bit
+    while let [first, second, ..] = octets {
+        if (*first == 0x00 && second & 0x80 == 0) || (*first == 0xFF && second & 0x80 != 0) {
+            octets = &octets[1..];
+        } else {
+            break;
+        }
+    }
+    if octets.len() > 8 {
+        return None;
+    }
+    let negative = octets.first().is_some_and(|first| first & 0x80 != 0);
+    let mut bytes = [if negative { 0xFF } else { 0x00 }; 8];
+    bytes[8 - octets.len()..].copy_from_slice(octets);
+    Some(i64::from_be_bytes(bytes))
+}
+
+/// The value of the unsigned big-endian octets, if it fits into an `u64`
+fn unsigned(octets: &[u8]) -> Option<u64> {
+    let significant = octets
+        .iter()
+        .position(|octet| *octet != 0)
+        .map_or(&[][..], |index| &octets[index..]);
+    if significant.len() > 8 {
+        return None;
+    }
+    let mut bytes = [0_u8; 8];
+    bytes[8 - significant.len()..].copy_from_slice(significant);
+    Some(u64::from_be_bytes(bytes))
+}
+
+/// `value * 2^exponent`, in steps that keep all but the last product normal, so that the result
+/// is rounded only once
+fn scale(mut value: f64, exponent: i64) -> f64 {
+    const STEP: i32 = 1000;
+    let mut exponent = exponent.clamp(-4 * i64::from(STEP), 4 * i64::from(STEP)) as i32;
+    while exponent > STEP {
+        value *= 2_f64.powi(STEP);
+        exponent -= STEP;
+    }
+    while exponent < -STEP {
+        value *= 2_f64.powi(-STEP);
+        exponent += STEP;
+    }
+    value * 2_f64.powi(exponent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_special_values() {
+        assert_eq!(Vec::<u8>::new(), encode(0.0));
+        assert_eq!(vec![0x43], encode(-0.0));
+        assert_eq!(vec![0x40], encode(f64::INFINITY));
+        assert_eq!(vec![0x41], encode(f64::NEG_INFINITY));
+        assert_eq!(vec![0x42], encode(f64::NAN));
+
+        assert_eq!(Ok(0.0), decode(&[]));
+        assert!(decode(&[0x43]).unwrap().is_sign_negative());
+        assert_eq!(Ok(f64::INFINITY), decode(&[0x40]));
+        assert_eq!(Ok(f64::NEG_INFINITY), decode(&[0x41]));
+        assert!(decode(&[0x42]).unwrap().is_nan());
+        assert!(decode(&[0x44]).is_err());
+        assert!(decode(&[0x40, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_canonical_binary_encoding() {
+        // 1 = 1 * 2^0
+        assert_eq!(vec![0x80, 0x00, 0x01], encode(1.0));
+        // 0.5 = 1 * 2^-1
+        assert_eq!(vec![0x80, 0xFF, 0x01], encode(0.5));
+        // -6 = -3 * 2^1
+        assert_eq!(vec![0xC0, 0x01, 0x03], encode(-6.0));
+        // 1024 = 1 * 2^10
+        assert_eq!(vec![0x80, 0x0A, 0x01], encode(1024.0));
+        // 0.1 = 3602879701896397 * 2^-55
+        assert_eq!(
+            vec![0x80, 0xC9, 0x0C, 0xCC, 0xCC, 0xCC, 0xCC, 0xCC, 0xCD],
+            encode(0.1)
+        );
+        // the smallest subnormal, 1 * 2^-1074
+        assert_eq!(vec![0x81, 0xFB, 0xCE, 0x01], encode(f64::from_bits(1)));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for value in [
+            1.0,
+            -1.0,
+            0.1,
+            core::f64::consts::PI,
+            -1e-300,
+            6.02214076e23,
+            f64::MAX,
+            f64::MIN,
+            f64::MIN_POSITIVE,
+            f64::from_bits(1),
+            f64::from(f32::MAX),
+            f64::from(-1.5e-40_f32),
+        ] {
+            assert_eq!(Ok(value), decode(&encode(value)), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_decode_other_bases_and_scale_factors() {
+        // 1 * 8^1
+        assert_eq!(Ok(8.0), decode(&[0x90, 0x01, 0x01]));
+        // 3 * 16^-1
+        assert_eq!(Ok(0.1875), decode(&[0xA0, 0xFF, 0x03]));
+        // -1 * 2^2 (scale factor) * 2^1
+        assert_eq!(Ok(-8.0), decode(&[0xC8, 0x01, 0x01]));
+        // exponent length in its own octet, even mantissa with leading zero octet
+        assert_eq!(Ok(4.0), decode(&[0x83, 0x01, 0x01, 0x00, 0x02]));
+        assert!(decode(&[0xB0, 0x00, 0x01]).is_err());
+        assert!(decode(&[0x80, 0x00]).is_err());
+        assert!(decode(&[0x83, 0x00, 0x01]).is_err());
+        assert_eq!(Ok(f64::INFINITY), decode(&[0x81, 0x7F, 0xFF, 0x01]));
+        assert_eq!(Ok(0.0), decode(&[0x81, 0x80, 0x00, 0x01]));
+    }
+
+    #[test]
+    fn test_decode_decimal() {
+        assert_eq!(Ok(12.0), decode(b"\x0112"));
+        assert_eq!(Ok(-1.5), decode(b"\x02 -1,5"));
+        assert_eq!(Ok(1500.0), decode(b"\x031.5E3"));
+        assert!(decode(b"\x03inf").is_err());
+        assert!(decode(b"\x03").is_err());
+    }
+}
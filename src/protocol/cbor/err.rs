@@ -0,0 +1,194 @@
+use crate::protocol::FieldName;
+use asn1rs_model::asn::Charset;
+use backtrace::Backtrace;
+use std::fmt::{Debug, Display, Formatter};
+
+pub struct Error(pub(crate) Box<Inner>);
+
+impl Error {
+    #[inline]
+    pub fn kind(&self) -> &ErrorKind {
+        &self.0.kind
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn unexpected_item(expected: &'static str, initial_byte: u8) -> Self {
+        Self::from(ErrorKind::UnexpectedItem {
+            expected,
+            initial_byte,
+        })
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn ensure_string_valid(charset: Charset, str: &str) -> Result<(), Self> {
+        match charset.find_invalid(str) {
+            None => Ok(()),
+            Some((index, char)) => Err(ErrorKind::InvalidString(charset, char, index).into()),
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn content_transform_failed<E: Display>(error: E) -> Self {
+        Self::from(ErrorKind::ContentTransformFailed(error.to_string()))
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn substitution_failed<E: Display>(error: E) -> Self {
+        Self::from(ErrorKind::SubstitutionFailed(error.to_string()))
+    }
+}
+
+impl From<ErrorKind> for Error {
+    #[inline]
+    fn from(kind: ErrorKind) -> Self {
+        Error(Box::new(Inner::from(kind)))
+    }
+}
+
+impl Debug for Error {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.0.kind)?;
+        let mut backtrace = self.0.backtrace.clone();
+        backtrace.resolve();
+        writeln!(f, "{backtrace:?}")
+    }
+}
+
+impl std::error::Error for Error {
+    fn description(&self) -> &str {
+        "encoding or decoding CBOR failed"
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Inner {
+    pub(crate) kind: ErrorKind,
+    pub(crate) backtrace: Backtrace,
+}
+
+impl From<ErrorKind> for Inner {
+    #[inline]
+    fn from(kind: ErrorKind) -> Self {
+        Self {
+            kind,
+            backtrace: Backtrace::new_unresolved(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorKind {
+    /// The data item starting with the given byte is not of the expected kind
+    UnexpectedItem {
+        expected: &'static str,
+        initial_byte: u8,
+    },
+    /// Indefinite lengths and arguments that are not encoded in their shortest form are rejected,
+    /// RFC 8949, 4.2.1
+    NotDeterministic(u8),
+    /// The `SEQUENCE` has fewer items than there are fields that need a value
+    ItemsExhausted,
+    /// The `SEQUENCE` has more items than it has fields, but it is not extensible
+    UnexpectedItemCount {
+        expected: u64,
+        got: u64,
+    },
+    InvalidChoiceIndex(u64, u64),
+    InvalidString(Charset, char, usize),
+    InvalidUtf8,
+    ValueNotInRange {
+        value: i64,
+        min: i64,
+        max: i64,
+        /// The name of the definition the value belongs to, if known
+        definition: Option<&'static str>,
+        /// The name of the field that holds the value, if known
+        field: Option<&'static str>,
+    },
+    ValueExceedsMaxInt,
+    SizeNotInRange(u64, u64, u64),
+    EndOfStream,
+    /// The values are nested deeper than the maximum depth of the reader or writer
+    NestingDepthExceedsLimit(usize),
+    ContentTransformFailed(String),
+    SubstitutionFailed(String),
+    /// The value of an `OBJECT IDENTIFIER` is not valid according to ITU-T X.690, 8.19
+    InvalidObjectIdentifier(&'static str),
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorKind::UnexpectedItem {
+                expected,
+                initial_byte,
+            } => write!(
+                f,
+                "Expected {expected} but got a data item with the initial byte 0x{initial_byte:02x}"
+            ),
+            ErrorKind::NotDeterministic(initial_byte) => write!(
+                f,
+                "The data item with the initial byte 0x{initial_byte:02x} is not encoded deterministically"
+            ),
+            ErrorKind::ItemsExhausted => write!(f, "There are no more items to read"),
+            ErrorKind::UnexpectedItemCount { expected, got } => {
+                write!(f, "Expected {expected} items but got {got}")
+            }
+            ErrorKind::InvalidChoiceIndex(index, variant_count) => write!(
+                f,
+                "Unexpected choice-index {index} with variant count {variant_count}"
+            ),
+            ErrorKind::InvalidString(charset, char, index) => write!(
+                f,
+                "Character {char:?} (U+{:04X}) at index {index} is not valid for a {charset}",
+                *char as u32
+            ),
+            ErrorKind::InvalidUtf8 => write!(f, "The text string is not valid UTF-8"),
+            ErrorKind::ValueNotInRange {
+                value,
+                min,
+                max,
+                definition,
+                field,
+            } => write!(
+                f,
+                "The value {value}{} is not within the inclusive range of {min} and {max}",
+                FieldName(*definition, *field)
+            ),
+            ErrorKind::ValueExceedsMaxInt => {
+                write!(f, "The value exceeds the range of a 64-bit integer")
+            }
+            ErrorKind::SizeNotInRange(size, min, max) => write!(
+                f,
+                "The size {size} is not within the inclusive range of {min} and {max}"
+            ),
+            ErrorKind::EndOfStream => write!(f, "Unexpected end of the encoding"),
+            ErrorKind::NestingDepthExceedsLimit(max) => {
+                write!(f, "The values are nested deeper than the limit of {max} levels")
+            }
+            ErrorKind::ContentTransformFailed(message) => {
+                write!(f, "Failed to transform the content: {message}")
+            }
+            ErrorKind::SubstitutionFailed(message) => {
+                write!(
+                    f,
+                    "Failed to convert the value into its substitute: {message}"
+                )
+            }
+            ErrorKind::InvalidObjectIdentifier(reason) => {
+                write!(f, "Invalid OBJECT IDENTIFIER: {reason}")
+            }
+        }
+    }
+}
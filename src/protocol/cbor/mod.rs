@@ -0,0 +1,264 @@
+//! This module contains the primitives of the deterministic encoding of the Concise Binary Object
+//! Representation (CBOR), according to RFC 8949, chapter 4.2.1: arguments are always encoded in
+//! their shortest form and only definite lengths are used.
+
+mod err;
+
+pub use err::Error;
+pub use err::ErrorKind;
+
+/// RFC 8949, 3.1
+pub const MAJOR_UNSIGNED: u8 = 0;
+pub const MAJOR_NEGATIVE: u8 = 1;
+pub const MAJOR_BYTES: u8 = 2;
+pub const MAJOR_TEXT: u8 = 3;
+pub const MAJOR_ARRAY: u8 = 4;
+pub const MAJOR_MAP: u8 = 5;
+pub const MAJOR_TAG: u8 = 6;
+pub const MAJOR_SIMPLE: u8 = 7;
+
+/// RFC 8949, 3.3
+pub const FALSE: u8 = 0xF4;
+pub const TRUE: u8 = 0xF5;
+pub const NULL: u8 = 0xF6;
+pub const UNDEFINED: u8 = 0xF7;
+pub const FLOAT16: u8 = 0xF9;
+pub const FLOAT32: u8 = 0xFA;
+pub const FLOAT64: u8 = 0xFB;
+
+/// The tag of an object identifier in the BER encoding without tag and length, RFC 9090, 2.1
+pub const TAG_OID: u64 = 111;
+/// The tag of a relative object identifier in the BER encoding without tag and length, RFC 9090, 2.2
+pub const TAG_RELATIVE_OID: u64 = 110;
+
+/// Writes the head of a data item with the argument in its shortest form
+pub fn write_head(buffer: &mut Vec<u8>, major: u8, argument: u64) {
+    let major = major << 5;
+    match argument {
+        0..=23 => buffer.push(major | argument as u8),
+        24..=0xFF => buffer.extend_from_slice(&[major | 24, argument as u8]),
+        0x100..=0xFFFF => {
+            buffer.push(major | 25);
+            buffer.extend_from_slice(&(argument as u16).to_be_bytes());
+        }
+        0x1_0000..=0xFFFF_FFFF => {
+            buffer.push(major | 26);
+            buffer.extend_from_slice(&(argument as u32).to_be_bytes());
+        }
+        _ => {
+            buffer.push(major | 27);
+            buffer.extend_from_slice(&argument.to_be_bytes());
+        }
+    }
+}
+
+/// Writes the value as unsigned or negative integer
+pub fn write_integer(buffer: &mut Vec<u8>, value: i64) {
+    if value < 0 {
+        write_head(buffer, MAJOR_NEGATIVE, (-1 - value) as u64);
+    } else {
+        write_head(buffer, MAJOR_UNSIGNED, value as u64);
+    }
+}
+
+/// Writes the value as floating point number of the shortest width that preserves the value, NaN
+/// as `0xF97E00` (RFC 8949, 4.2.2)
+pub fn write_float(buffer: &mut Vec<u8>, value: f64) {
+    if value.is_nan() {
+        buffer.extend_from_slice(&[FLOAT16, 0x7E, 0x00]);
+    } else if let Some(half) = f16_bits(value) {
+        buffer.push(FLOAT16);
+        buffer.extend_from_slice(&half.to_be_bytes());
+    } else if f64::from(value as f32) == value {
+        buffer.push(FLOAT32);
+        buffer.extend_from_slice(&(value as f32).to_be_bytes());
+    } else {
+        buffer.push(FLOAT64);
+        buffer.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+/// The value of the floating point number with the given initial byte and argument
+pub fn float_value(initial: u8, argument: u64) -> Option<f64> {
+    match initial {
+        FLOAT16 => Some(f16_value(argument as u16)),
+        FLOAT32 => Some(f64::from(f32::from_bits(argument as u32))),
+        FLOAT64 => Some(f64::from_bits(argument)),
+        _ => None,
+    }
+}
+
+/// The bits of the IEEE 754 binary16 that represents the value exactly, if there is one
+fn f16_bits(value: f64) -> Option<u16> {
+    let sign = if value.is_sign_negative() { 0x8000 } else { 0 };
+    let abs = value.abs();
+    if abs == 0.0 {
+        return Some(sign);
+    } else if abs.is_infinite() {
+        return Some(sign | 0x7C00);
+    }
+    // subnormal: a multiple of 2^-24
+    let subnormal = abs * 2_f64.powi(24);
+    if subnormal < 1024.0 {
+        return (subnormal.fract() == 0.0).then_some(sign | subnormal as u16);
+    }
+    let exponent = ((abs.to_bits() >> 52) & 0x7FF) as i32 - 1023;
+    if exponent > 15 {
+        return None;
+    }
+    let mantissa = (abs * 2_f64.powi(10 - exponent)) - 1024.0;
+    (mantissa.fract() == 0.0).then_some(sign | (((exponent + 15) as u16) << 10) | mantissa as u16)
+}
+
+fn f16_value(bits: u16) -> f64 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = i32::from((bits >> 10) & 0x1F);
+    let mantissa = f64::from(bits & 0x3FF);
+    sign * match exponent {
+        0 => mantissa * 2_f64.powi(-24),
+        0x1F if mantissa == 0.0 => f64::INFINITY,
+        0x1F => f64::NAN,
+        _ => (1024.0 + mantissa) * 2_f64.powi(exponent - 25),
+    }
+}
+
+/// Reads the head of the data item at the given position and returns its major type and argument.
+/// The position is advanced to the content of the data item.
+pub fn read_head(bytes: &[u8], position: &mut usize) -> Result<(u8, u64), Error> {
+    let initial = *bytes.get(*position).ok_or(ErrorKind::EndOfStream)?;
+    let major = initial >> 5;
+    let additional = initial & 0x1F;
+    let len = match additional {
+        0..=23 => 0,
+        24 => 1,
+        25 => 2,
+        26 => 4,
+        27 => 8,
+        _ => return Err(ErrorKind::NotDeterministic(initial).into()),
+    };
+    let start = *position + 1;
+    let argument_bytes = bytes
+        .get(start..start + len)
+        .ok_or(ErrorKind::EndOfStream)?;
+    let argument = if len == 0 {
+        u64::from(additional)
+    } else {
+        argument_bytes
+            .iter()
+            .fold(0_u64, |argument, byte| (argument << 8) | u64::from(*byte))
+    };
+
+    // floating point values are simple values and have no shortest form for their argument
+    let shortest = match len {
+        1 if major == MAJOR_SIMPLE => argument >= 32,
+        1 => argument >= 24,
+        2 | 4 | 8 if major == MAJOR_SIMPLE => true,
+        2 => argument > 0xFF,
+        4 => argument > 0xFFFF,
+        8 => argument > 0xFFFF_FFFF,
+        _ => true,
+    };
+    if !shortest {
+        return Err(ErrorKind::NotDeterministic(initial).into());
+    }
+
+    *position = start + len;
+    Ok((major, argument))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shortest_heads() {
+        // RFC 8949, Appendix A
+        for (value, expected) in [
+            (0_i64, &[0x00][..]),
+            (23, &[0x17]),
+            (24, &[0x18, 0x18]),
+            (1000, &[0x19, 0x03, 0xE8]),
+            (1_000_000, &[0x1A, 0x00, 0x0F, 0x42, 0x40]),
+            (
+                i64::MAX,
+                &[0x1B, 0x7F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+            ),
+            (-1, &[0x20]),
+            (-100, &[0x38, 0x63]),
+            (-1000, &[0x39, 0x03, 0xE7]),
+        ] {
+            let mut buffer = Vec::new();
+            write_integer(&mut buffer, value);
+            assert_eq!(expected, &buffer[..], "{value}");
+
+            let mut position = 0;
+            let (major, argument) = read_head(&buffer, &mut position).unwrap();
+            assert_eq!(buffer.len(), position);
+            let decoded = if major == MAJOR_NEGATIVE {
+                -1 - argument as i64
+            } else {
+                argument as i64
+            };
+            assert_eq!(value, decoded);
+        }
+    }
+
+    #[test]
+    fn test_shortest_floats() {
+        // RFC 8949, Appendix A
+        for (value, expected) in [
+            (0.0, &[0xF9, 0x00, 0x00][..]),
+            (-0.0, &[0xF9, 0x80, 0x00]),
+            (1.0, &[0xF9, 0x3C, 0x00]),
+            (1.5, &[0xF9, 0x3E, 0x00]),
+            (65504.0, &[0xF9, 0x7B, 0xFF]),
+            (5.960464477539063e-8, &[0xF9, 0x00, 0x01]),
+            (0.00006103515625, &[0xF9, 0x04, 0x00]),
+            (-4.0, &[0xF9, 0xC4, 0x00]),
+            (100000.0, &[0xFA, 0x47, 0xC3, 0x50, 0x00]),
+            (3.4028234663852886e+38, &[0xFA, 0x7F, 0x7F, 0xFF, 0xFF]),
+            (1.1, &[0xFB, 0x3F, 0xF1, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9A]),
+            (
+                1.0e+300,
+                &[0xFB, 0x7E, 0x37, 0xE4, 0x3C, 0x88, 0x00, 0x75, 0x9C],
+            ),
+            (f64::INFINITY, &[0xF9, 0x7C, 0x00]),
+            (f64::NEG_INFINITY, &[0xF9, 0xFC, 0x00]),
+        ] {
+            let mut buffer = Vec::new();
+            write_float(&mut buffer, value);
+            assert_eq!(expected, &buffer[..], "{value}");
+
+            let mut position = 0;
+            let (major, argument) = read_head(&buffer, &mut position).unwrap();
+            assert_eq!(MAJOR_SIMPLE, major);
+            let decoded = float_value(buffer[0], argument).unwrap();
+            assert_eq!(value.to_bits(), decoded.to_bits(), "{value}");
+        }
+
+        let mut buffer = Vec::new();
+        write_float(&mut buffer, f64::NAN);
+        assert_eq!(&[0xF9, 0x7E, 0x00], &buffer[..]);
+        assert!(float_value(FLOAT16, 0x7E00).unwrap().is_nan());
+    }
+
+    #[test]
+    fn test_reject_non_deterministic_heads() {
+        for bytes in [
+            &[0x18, 0x17][..],
+            &[0x19, 0x00, 0xFF],
+            &[0x1A, 0x00, 0x00, 0xFF, 0xFF],
+            &[0x9F],
+            &[0x1C],
+        ] {
+            assert_eq!(
+                &ErrorKind::NotDeterministic(bytes[0]),
+                read_head(bytes, &mut 0).unwrap_err().kind()
+            );
+        }
+        assert_eq!(
+            &ErrorKind::EndOfStream,
+            read_head(&[0x19, 0x01], &mut 0).unwrap_err().kind()
+        );
+    }
+}
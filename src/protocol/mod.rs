@@ -6,7 +6,27 @@
 //!      ::io::...                  Other ASN.1 representations (e.g der, xer, ber, ...)
 //! ```
 
+use std::fmt::{Display, Formatter};
+
 pub mod basic;
+#[cfg(feature = "cbor")]
+pub mod cbor;
+#[cfg(feature = "oer")]
+pub mod oer;
 pub mod per;
 #[cfg(feature = "protobuf")]
 pub mod protobuf;
+
+/// Formats the definition and field names of a value as ` of Definition.field`, or nothing if
+/// neither is known
+pub(crate) struct FieldName(pub Option<&'static str>, pub Option<&'static str>);
+
+impl Display for FieldName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.0, self.1) {
+            (Some(definition), Some(field)) => write!(f, " of {definition}.{field}"),
+            (Some(name), None) | (None, Some(name)) => write!(f, " of {name}"),
+            (None, None) => Ok(()),
+        }
+    }
+}
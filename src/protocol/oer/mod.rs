@@ -0,0 +1,394 @@
+//! This module contains the primitives of the Octet Encoding Rules (OER), according to ITU-T
+//! X.696. The writer only produces the canonical form (COER, chapter 26): integers and lengths
+//! in their shortest form and zero padding bits.
+
+#![allow(clippy::unusual_byte_groupings)]
+
+mod err;
+
+pub use err::Error;
+pub use err::ErrorKind;
+
+use asn1rs_model::asn::Tag;
+
+const CLASS_BITS_UNIVERSAL: u8 = 0b_00_000000;
+const CLASS_BITS_APPLICATION: u8 = 0b_01_000000;
+const CLASS_BITS_CONTEXT_SPECIFIC: u8 = 0b_10_000000;
+const CLASS_BITS_PRIVATE: u8 = 0b_11_000000;
+const NUMBER_BITS: u8 = 0b_00_111111;
+
+const LENGTH_BIT_LONG_FORM: u8 = 0b1_0000000;
+
+/// How the value of an `INTEGER` is encoded, which depends on its constraint (ITU-T X.696,
+/// chapter 10)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum IntegerWidth {
+    /// The given number of octets, without a length determinant
+    Fixed { octets: usize, signed: bool },
+    /// A length determinant followed by the shortest form of the value
+    Variable { signed: bool },
+}
+
+impl IntegerWidth {
+    /// The encoding for the given bounds, extensible constraints are not OER-visible (10.1)
+    pub const fn of(min: Option<i64>, max: Option<i64>, extensible: bool) -> Self {
+        match (min, max) {
+            _ if extensible => IntegerWidth::Variable { signed: true },
+            (Some(min), Some(max)) if min >= 0 => IntegerWidth::Fixed {
+                octets: if max <= u8::MAX as i64 {
+                    1
+                } else if max <= u16::MAX as i64 {
+                    2
+                } else if max <= u32::MAX as i64 {
+                    4
+                } else {
+                    8
+                },
+                signed: false,
+            },
+            (Some(min), Some(max)) => IntegerWidth::Fixed {
+                octets: if min >= i8::MIN as i64 && max <= i8::MAX as i64 {
+                    1
+                } else if min >= i16::MIN as i64 && max <= i16::MAX as i64 {
+                    2
+                } else if min >= i32::MIN as i64 && max <= i32::MAX as i64 {
+                    4
+                } else {
+                    8
+                },
+                signed: true,
+            },
+            (Some(min), None) if min >= 0 => IntegerWidth::Variable { signed: false },
+            _ => IntegerWidth::Variable { signed: true },
+        }
+    }
+}
+
+/// Writes the length determinant (ITU-T X.696, chapter 8.6) in its shortest form
+pub fn write_length(buffer: &mut Vec<u8>, length: u64) {
+    if length < u64::from(LENGTH_BIT_LONG_FORM) {
+        buffer.push(length as u8);
+    } else {
+        let bytes = length.to_be_bytes();
+        let offset = (length.leading_zeros() / u8::BITS) as usize;
+        buffer.push(LENGTH_BIT_LONG_FORM | (bytes.len() - offset) as u8);
+        buffer.extend_from_slice(&bytes[offset..]);
+    }
+}
+
+/// Reads the length determinant (ITU-T X.696, chapter 8.6) at the given position, the position
+/// is advanced to the first octet after it
+pub fn read_length(bytes: &[u8], position: &mut usize) -> Result<u64, Error> {
+    let octet = next(bytes, position)?;
+    if octet & LENGTH_BIT_LONG_FORM == 0 {
+        Ok(u64::from(octet))
+    } else {
+        let octets = usize::from(octet & !LENGTH_BIT_LONG_FORM);
+        if octets > core::mem::size_of::<u64>() {
+            return Err(ErrorKind::ValueExceedsMaxInt.into());
+        }
+        read_unsigned(take(bytes, position, octets)?)
+    }
+}
+
+/// Writes the tag of a `CHOICE` alternative (ITU-T X.696, chapter 8.7)
+pub fn write_tag(buffer: &mut Vec<u8>, tag: Tag) {
+    let class = match tag {
+        Tag::Universal(_) => CLASS_BITS_UNIVERSAL,
+        Tag::Application(_) => CLASS_BITS_APPLICATION,
+        Tag::ContextSpecific(_) => CLASS_BITS_CONTEXT_SPECIFIC,
+        Tag::Private(_) => CLASS_BITS_PRIVATE,
+    };
+    let number = tag.value();
+    if number < usize::from(NUMBER_BITS) {
+        buffer.push(class | number as u8);
+    } else {
+        // base 128 with the most significant group first, like the high tag number form of BER
+        buffer.push(class | NUMBER_BITS);
+        let groups = (usize::BITS - number.leading_zeros()).div_ceil(7);
+        for group in (0..groups).rev() {
+            let more = if group > 0 { 0x80 } else { 0x00 };
+            buffer.push(more | ((number >> (group * 7)) & 0x7F) as u8);
+        }
+    }
+}
+
+/// Reads the tag of a `CHOICE` alternative (ITU-T X.696, chapter 8.7) at the given position, the
+/// position is advanced to the encoding of the alternative
+pub fn read_tag(bytes: &[u8], position: &mut usize) -> Result<Tag, Error> {
+    let octet = next(bytes, position)?;
+    let mut number = usize::from(octet & NUMBER_BITS);
+
+    if number == usize::from(NUMBER_BITS) {
+        number = 0;
+        loop {
+            let octet = next(bytes, position)?;
+            if number.leading_zeros() < 7 {
+                return Err(ErrorKind::ValueExceedsMaxInt.into());
+            }
+            number = (number << 7) | usize::from(octet & 0x7F);
+            if octet & 0x80 == 0 {
+                break;
+            }
+        }
+    }
+
+    Ok(match octet & !NUMBER_BITS {
+        CLASS_BITS_UNIVERSAL => Tag::Universal(number),
+        CLASS_BITS_APPLICATION => Tag::Application(number),
+        CLASS_BITS_CONTEXT_SPECIFIC => Tag::ContextSpecific(number),
+        _ => Tag::Private(number),
+    })
+}
+
+/// Writes the value of an `INTEGER` (ITU-T X.696, chapter 10). The caller ensures that the value
+/// is within the bounds the width is derived from.
+pub fn write_integer(buffer: &mut Vec<u8>, width: IntegerWidth, value: i64) {
+    let bytes = value.to_be_bytes();
+    match width {
+        IntegerWidth::Fixed { octets, .. } => {
+            buffer.extend_from_slice(&bytes[bytes.len() - octets.min(bytes.len())..]);
+        }
+        IntegerWidth::Variable { signed } => {
+            let offset = shortest_offset(value, signed);
+            write_length(buffer, (bytes.len() - offset) as u64);
+            buffer.extend_from_slice(&bytes[offset..]);
+        }
+    }
+}
+
+/// Reads the value of an `INTEGER` (ITU-T X.696, chapter 10) at the given position
+pub fn read_integer(bytes: &[u8], position: &mut usize, width: IntegerWidth) -> Result<i64, Error> {
+    let (octets, signed) = match width {
+        IntegerWidth::Fixed { octets, signed } => (octets, signed),
+        IntegerWidth::Variable { signed } => (
+            usize::try_from(read_length(bytes, position)?)
+                .map_err(|_| ErrorKind::ValueExceedsMaxInt)?,
+            signed,
+        ),
+    };
+    let content = take(bytes, position, octets)?;
+    if signed {
+        read_signed(content)
+    } else {
+        let value = read_unsigned(content)?;
+        i64::try_from(value).map_err(|_| ErrorKind::ValueExceedsMaxInt.into())
+    }
+}
+
+/// Writes the value of an `ENUMERATED` (ITU-T X.696, chapter 11): values from 0 to 127 in a
+/// single octet, all others as length prefixed two's complement
+pub fn write_enumerated(buffer: &mut Vec<u8>, value: i64) {
+    if (0..i64::from(LENGTH_BIT_LONG_FORM)).contains(&value) {
+        buffer.push(value as u8);
+    } else {
+        let bytes = value.to_be_bytes();
+        let offset = shortest_offset(value, true);
+        buffer.push(LENGTH_BIT_LONG_FORM | (bytes.len() - offset) as u8);
+        buffer.extend_from_slice(&bytes[offset..]);
+    }
+}
+
+/// Reads the value of an `ENUMERATED` (ITU-T X.696, chapter 11) at the given position
+pub fn read_enumerated(bytes: &[u8], position: &mut usize) -> Result<i64, Error> {
+    let octet = next(bytes, position)?;
+    if octet & LENGTH_BIT_LONG_FORM == 0 {
+        Ok(i64::from(octet))
+    } else {
+        let octets = usize::from(octet & !LENGTH_BIT_LONG_FORM);
+        read_signed(take(bytes, position, octets)?)
+    }
+}
+
+/// Writes a quantity, such as the number of elements of a `SEQUENCE OF` (ITU-T X.696, chapter
+/// 8.6 and 20), as length determinant followed by the shortest unsigned form
+pub fn write_quantity(buffer: &mut Vec<u8>, quantity: u64) {
+    let bytes = quantity.to_be_bytes();
+    let offset = ((quantity.leading_zeros() / u8::BITS) as usize).min(bytes.len() - 1);
+    write_length(buffer, (bytes.len() - offset) as u64);
+    buffer.extend_from_slice(&bytes[offset..]);
+}
+
+/// Reads a quantity (ITU-T X.696, chapter 20) at the given position
+pub fn read_quantity(bytes: &[u8], position: &mut usize) -> Result<u64, Error> {
+    let octets = usize::try_from(read_length(bytes, position)?)
+        .map_err(|_| ErrorKind::ValueExceedsMaxInt)?;
+    read_unsigned(take(bytes, position, octets)?)
+}
+
+/// Takes the given number of octets at the given position and advances the position after them
+pub fn take<'a>(bytes: &'a [u8], position: &mut usize, len: usize) -> Result<&'a [u8], Error> {
+    let end = position
+        .checked_add(len)
+        .filter(|end| *end <= bytes.len())
+        .ok_or(ErrorKind::EndOfStream)?;
+    let slice = &bytes[*position..end];
+    *position = end;
+    Ok(slice)
+}
+
+/// The offset of the first octet of the big endian representation of the value that is needed
+fn shortest_offset(value: i64, signed: bool) -> usize {
+    let redundant = match (signed, value < 0) {
+        (true, true) => value.leading_ones().saturating_sub(1),
+        (true, false) => value.leading_zeros().saturating_sub(1),
+        (false, _) => value.leading_zeros(),
+    };
+    ((redundant / u8::BITS) as usize).min(core::mem::size_of::<i64>() - 1)
+}
+
+fn read_unsigned(content: &[u8]) -> Result<u64, Error> {
+    if content.len() > core::mem::size_of::<u64>() {
+        Err(ErrorKind::ValueExceedsMaxInt.into())
+    } else {
+        Ok(content
+            .iter()
+            .fold(0_u64, |value, byte| (value << u8::BITS) | u64::from(*byte)))
+    }
+}
+
+fn read_signed(content: &[u8]) -> Result<i64, Error> {
+    match content.first() {
+        None => Err(ErrorKind::InvalidEncoding("integer without content octets").into()),
+        Some(_) if content.len() > core::mem::size_of::<i64>() => {
+            Err(ErrorKind::ValueExceedsMaxInt.into())
+        }
+        Some(first) => {
+            let init = if *first & 0x80 != 0 { -1_i64 } else { 0_i64 };
+            Ok(content
+                .iter()
+                .fold(init, |value, byte| (value << u8::BITS) | i64::from(*byte)))
+        }
+    }
+}
+
+#[inline]
+fn next(bytes: &[u8], position: &mut usize) -> Result<u8, Error> {
+    let byte = *bytes.get(*position).ok_or(ErrorKind::EndOfStream)?;
+    *position += 1;
+    Ok(byte)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integer_widths() {
+        for (min, max, extensible, expected) in [
+            (Some(0), Some(255), false, (Some(1), false)),
+            (Some(0), Some(256), false, (Some(2), false)),
+            (Some(1), Some(0xFFFF_FFFF), false, (Some(4), false)),
+            (Some(0), Some(i64::MAX), false, (Some(8), false)),
+            (Some(-128), Some(127), false, (Some(1), true)),
+            (Some(-129), Some(127), false, (Some(2), true)),
+            (Some(i64::MIN), Some(0), false, (Some(8), true)),
+            (Some(0), None, false, (None, false)),
+            (None, Some(10), false, (None, true)),
+            (Some(0), Some(255), true, (None, true)),
+        ] {
+            let width = IntegerWidth::of(min, max, extensible);
+            let actual = match width {
+                IntegerWidth::Fixed { octets, signed } => (Some(octets), signed),
+                IntegerWidth::Variable { signed } => (None, signed),
+            };
+            assert_eq!(expected, actual, "{min:?}..{max:?}");
+        }
+    }
+
+    #[test]
+    fn test_integers() {
+        for (width, value, expected) in [
+            (
+                IntegerWidth::of(Some(0), Some(255), false),
+                200,
+                &[0xC8][..],
+            ),
+            (IntegerWidth::of(Some(-5), Some(5), false), -1, &[0xFF]),
+            (
+                IntegerWidth::of(Some(0), Some(70_000), false),
+                1,
+                &[0x00, 0x00, 0x00, 0x01],
+            ),
+            (IntegerWidth::of(Some(0), None, false), 0, &[0x01, 0x00]),
+            (IntegerWidth::of(Some(0), None, false), 128, &[0x01, 0x80]),
+            (
+                IntegerWidth::of(None, None, false),
+                128,
+                &[0x02, 0x00, 0x80],
+            ),
+            (
+                IntegerWidth::of(None, None, false),
+                -129,
+                &[0x02, 0xFF, 0x7F],
+            ),
+            (IntegerWidth::of(None, None, false), -1, &[0x01, 0xFF]),
+        ] {
+            let mut buffer = Vec::new();
+            write_integer(&mut buffer, width, value);
+            assert_eq!(expected, &buffer[..], "{value} as {width:?}");
+
+            let mut position = 0;
+            assert_eq!(value, read_integer(&buffer, &mut position, width).unwrap());
+            assert_eq!(buffer.len(), position);
+        }
+    }
+
+    #[test]
+    fn test_length_and_quantity() {
+        for (length, expected) in [
+            (0_u64, &[0x00][..]),
+            (127, &[0x7F]),
+            (128, &[0x81, 0x80]),
+            (256, &[0x82, 0x01, 0x00]),
+        ] {
+            let mut buffer = Vec::new();
+            write_length(&mut buffer, length);
+            assert_eq!(expected, &buffer[..]);
+            assert_eq!(length, read_length(&buffer, &mut 0).unwrap());
+        }
+
+        let mut buffer = Vec::new();
+        write_quantity(&mut buffer, 0);
+        write_quantity(&mut buffer, 300);
+        assert_eq!(&[0x01, 0x00, 0x02, 0x01, 0x2C], &buffer[..]);
+        let mut position = 0;
+        assert_eq!(0, read_quantity(&buffer, &mut position).unwrap());
+        assert_eq!(300, read_quantity(&buffer, &mut position).unwrap());
+
+        assert_eq!(
+            &ErrorKind::EndOfStream,
+            read_length(&[0x82, 0x01], &mut 0).unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn test_tags_and_enumerated() {
+        for (tag, expected) in [
+            (Tag::ContextSpecific(0), &[0x80][..]),
+            (Tag::Universal(1), &[0x01]),
+            (Tag::Application(62), &[0x7E]),
+            (Tag::Private(63), &[0xFF, 0x3F]),
+            (Tag::ContextSpecific(201), &[0xBF, 0x81, 0x49]),
+        ] {
+            let mut buffer = Vec::new();
+            write_tag(&mut buffer, tag);
+            assert_eq!(expected, &buffer[..], "{tag:?}");
+            let mut position = 0;
+            assert_eq!(tag, read_tag(&buffer, &mut position).unwrap());
+            assert_eq!(buffer.len(), position);
+        }
+
+        for (value, expected) in [
+            (0_i64, &[0x00][..]),
+            (127, &[0x7F]),
+            (128, &[0x82, 0x00, 0x80]),
+            (-1, &[0x81, 0xFF]),
+        ] {
+            let mut buffer = Vec::new();
+            write_enumerated(&mut buffer, value);
+            assert_eq!(expected, &buffer[..], "{value}");
+            assert_eq!(value, read_enumerated(&buffer, &mut 0).unwrap());
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::protocol::FieldName;
 use asn1rs_model::asn::Charset;
 use backtrace::Backtrace;
 use std::string::FromUtf8Error;
@@ -11,6 +12,13 @@ impl Error {
         &self.0.kind
     }
 
+    /// The number of bits the source lacks at least to complete the failed read, if the read
+    /// failed with [`ErrorKind::InsufficientDataInSourceBuffer`] and the number is known
+    #[inline]
+    pub fn missing_bits(&self) -> Option<usize> {
+        self.0.missing_bits
+    }
+
     #[cfg(feature = "descriptive-deserialize-errors")]
     pub fn scope_description(&self) -> &[crate::prelude::ScopeDescription] {
         &self.0.description[..]
@@ -23,6 +31,7 @@ impl From<ErrorKind> for Error {
     fn from(kind: ErrorKind) -> Self {
         Self(Box::new(Inner {
             kind,
+            missing_bits: None,
             #[cfg(feature = "descriptive-deserialize-errors")]
             description: Vec::new(),
         }))
@@ -71,11 +80,19 @@ impl std::error::Error for Error {
     fn description(&self) -> &str {
         "encoding or decoding UPER failed"
     }
+
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self.kind() {
+            ErrorKind::FromUtf8Error(e) => Some(e),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct Inner {
     pub(crate) kind: ErrorKind,
+    pub(crate) missing_bits: Option<usize>,
     #[cfg(feature = "descriptive-deserialize-errors")]
     pub(crate) description: Vec<crate::rw::ScopeDescription>,
 }
@@ -94,13 +111,34 @@ pub enum ErrorKind {
     },
     InvalidChoiceIndex(u64, u64),
     ExtensionFieldsInconsistent(String),
-    ValueNotInRange(i64, i64, i64),
+    ValueNotInRange {
+        value: i64,
+        min: i64,
+        max: i64,
+        /// The name of the definition the value belongs to, if known
+        definition: Option<&'static str>,
+        /// The name of the field that holds the value, if known
+        field: Option<&'static str>,
+    },
     ValueExceedsMaxInt,
     ValueIsNegativeButExpectedUnsigned(i64),
     SizeNotInRange(u64, u64, u64),
     BitLenNotInRange(u64, u64, u64),
     OptFlagsExhausted,
     EndOfStream,
+    ContentTransformFailed(String),
+    SubstitutionFailed(String),
+    /// The number of bits consumed by the decoded value and the number of bits remaining
+    /// that are not only the zero padding of the final octet
+    TrailingBits(usize, usize),
+    /// The values are nested deeper than the given limit
+    NestingDepthExceedsLimit(usize),
+    /// The PDU is longer than the given limit of octets
+    PduExceedsLimit(usize),
+    /// The contents of a `REAL` are not valid according to ITU-T X.690, 8.5
+    InvalidReal(&'static str),
+    /// The value of an `OBJECT IDENTIFIER` is not valid according to ITU-T X.690, 8.19
+    InvalidObjectIdentifier(&'static str),
 }
 
 impl Error {
@@ -113,6 +151,38 @@ impl Error {
         }
     }
 
+    #[cold]
+    #[inline(never)]
+    pub fn value_not_in_range(value: i64, min: i64, max: i64) -> Self {
+        ErrorKind::ValueNotInRange {
+            value,
+            min,
+            max,
+            definition: None,
+            field: None,
+        }
+        .into()
+    }
+
+    /// Assigns the names of the definition and field to a [`ErrorKind::ValueNotInRange`] that
+    /// does not name them yet
+    pub fn with_field_name(
+        mut self,
+        definition_name: Option<&'static str>,
+        field_name: Option<&'static str>,
+    ) -> Self {
+        if let ErrorKind::ValueNotInRange {
+            definition: definition @ None,
+            field: field @ None,
+            ..
+        } = &mut self.0.kind
+        {
+            *definition = definition_name;
+            *field = field_name;
+        }
+        self
+    }
+
     #[cold]
     #[inline(never)]
     pub fn insufficient_space_in_destination_buffer() -> Self {
@@ -125,6 +195,16 @@ impl Error {
         ErrorKind::InsufficientDataInSourceBuffer(Backtrace::new_unresolved()).into()
     }
 
+    /// Like [`Error::insufficient_data_in_source_buffer`], with the number of
+    /// [`Error::missing_bits`]
+    #[cold]
+    #[inline(never)]
+    pub fn insufficient_data_in_source_buffer_by(missing_bits: usize) -> Self {
+        let mut error = Self::insufficient_data_in_source_buffer();
+        error.0.missing_bits = Some(missing_bits);
+        error
+    }
+
     #[cold]
     #[inline(never)]
     pub fn length_determinant_exceeds_limit(length: usize, limit: usize) -> Self {
@@ -147,8 +227,8 @@ impl std::fmt::Display for ErrorKind {
             Self::InvalidString(charset, char, index) => {
                 write!(
                     f,
-                    "Invalid character for a string with the charset {:?} at index {}: {}",
-                    charset, index, char
+                    "Invalid character for a {} at index {}: {:?} (U+{:04X})",
+                    charset, index, char, *char as u32
                 )
             }
             Self::UnsupportedOperation(o) => write!(f, "The operation is not supported: {}", o),
@@ -198,10 +278,19 @@ impl std::fmt::Display for ErrorKind {
                     name
                 )
             }
-            Self::ValueNotInRange(value, min, max) => write!(
+            Self::ValueNotInRange {
+                value,
+                min,
+                max,
+                definition,
+                field,
+            } => write!(
                 f,
-                "The value {} is not within the inclusive range of {} and {}",
-                value, min, max
+                "The value {}{} is not within the inclusive range of {} and {}",
+                value,
+                FieldName(*definition, *field),
+                min,
+                max
             ),
             Self::ValueExceedsMaxInt => {
                 write!(f, "The value exceeds the maximum supported integer size",)
@@ -226,6 +315,35 @@ impl std::fmt::Display for ErrorKind {
                 f,
                 "Can no longer read or write any bytes from the underlying dataset"
             ),
+            Self::ContentTransformFailed(message) => write!(
+                f,
+                "Failed to transform the content of an OCTET STRING: {}",
+                message
+            ),
+            Self::SubstitutionFailed(message) => {
+                write!(
+                    f,
+                    "Failed to convert the value into its substitute: {}",
+                    message
+                )
+            }
+            Self::TrailingBits(consumed, remaining) => write!(
+                f,
+                "After decoding {} bits, {} bits remain that are not only the zero padding of the final octet",
+                consumed, remaining
+            ),
+            Self::NestingDepthExceedsLimit(limit) => write!(
+                f,
+                "The values are nested deeper than the limit of {} levels",
+                limit
+            ),
+            Self::PduExceedsLimit(limit) => {
+                write!(f, "The PDU is longer than the limit of {} octets", limit)
+            }
+            Self::InvalidReal(reason) => write!(f, "Invalid encoding of a REAL: {}", reason),
+            Self::InvalidObjectIdentifier(reason) => {
+                write!(f, "Invalid OBJECT IDENTIFIER: {}", reason)
+            }
         }
     }
 }
@@ -255,8 +373,14 @@ impl PartialEq for ErrorKind {
             Self::ExtensionFieldsInconsistent(a) => {
                 matches!(other, Self::ExtensionFieldsInconsistent(oa) if a == oa)
             }
-            Self::ValueNotInRange(a, b, c) => {
-                matches!(other, Self::ValueNotInRange(oa, ob, oc) if (a, b, c) == (oa, ob, oc))
+            Self::ValueNotInRange {
+                value,
+                min,
+                max,
+                definition,
+                field,
+            } => {
+                matches!(other, Self::ValueNotInRange { value: ov, min: omin, max: omax, definition: od, field: of } if (value, min, max, definition, field) == (ov, omin, omax, od, of))
             }
             Self::ValueExceedsMaxInt => matches!(other, Self::ValueExceedsMaxInt),
             Self::ValueIsNegativeButExpectedUnsigned(a) => {
@@ -270,6 +394,23 @@ impl PartialEq for ErrorKind {
             }
             Self::OptFlagsExhausted => matches!(other, Self::OptFlagsExhausted),
             Self::EndOfStream => matches!(other, Self::EndOfStream),
+            Self::ContentTransformFailed(a) => {
+                matches!(other, Self::ContentTransformFailed(oa) if a == oa)
+            }
+            Self::SubstitutionFailed(a) => {
+                matches!(other, Self::SubstitutionFailed(oa) if a == oa)
+            }
+            Self::TrailingBits(a, b) => {
+                matches!(other, Self::TrailingBits(oa, ob) if (a, b) == (oa, ob))
+            }
+            Self::NestingDepthExceedsLimit(a) => {
+                matches!(other, Self::NestingDepthExceedsLimit(oa) if a == oa)
+            }
+            Self::PduExceedsLimit(a) => matches!(other, Self::PduExceedsLimit(oa) if a == oa),
+            Self::InvalidReal(a) => matches!(other, Self::InvalidReal(oa) if a == oa),
+            Self::InvalidObjectIdentifier(a) => {
+                matches!(other, Self::InvalidObjectIdentifier(oa) if a == oa)
+            }
         }
     }
 }
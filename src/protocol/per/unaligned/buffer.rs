@@ -1,6 +1,9 @@
+use super::slice::bit_string_copy_bulked;
 use super::*;
 use crate::protocol::per::Error;
 use crate::protocol::per::ErrorKind;
+use crate::protocol::per::PackedRead;
+use std::borrow::Cow;
 
 #[derive(Debug, Default)]
 pub struct BitBuffer {
@@ -105,12 +108,81 @@ impl BitBuffer {
         result
     }
 
+    /// Appends the written bits of the other buffer, starting at the write position
+    pub fn append(&mut self, other: &BitBuffer) -> Result<(), Error> {
+        self.write_bits_with_len(other.content(), other.bit_len())
+    }
+
+    /// Shortens the buffer to the given number of bits, the read position is moved back if it
+    /// is beyond. Has no effect if the buffer is already shorter.
+    pub fn truncate(&mut self, bit_len: usize) {
+        let bit_len = bit_len.min(self.write_position);
+        self.buffer.truncate(bit_len.div_ceil(BYTE_LEN));
+        let trailing_bits = bit_len % BYTE_LEN;
+        if trailing_bits > 0 {
+            if let Some(last) = self.buffer.last_mut() {
+                *last &= 0xFF << (BYTE_LEN - trailing_bits);
+            }
+        }
+        self.write_position = bit_len;
+        self.read_position = self.read_position.min(bit_len);
+    }
+
+    /// Splits the buffer at the given bit. Returns the bits after it as new buffer - with the
+    /// read position relative to its start - and keeps the bits before it.
+    ///
+    /// # Panics
+    /// If `at` is beyond the written bits
+    pub fn split_off(&mut self, at: usize) -> BitBuffer {
+        assert!(
+            at <= self.write_position,
+            "Split at bit {} of {}",
+            at,
+            self.write_position
+        );
+        let len = self.write_position - at;
+        let mut tail = BitBuffer::with_capacity(len.div_ceil(BYTE_LEN));
+        tail.ensure_can_write_additional_bits(len);
+        // the length was checked and the capacity of the tail ensured, copying cannot fail
+        let copied = bit_string_copy_bulked(&self.buffer, at, &mut tail.buffer, 0, len);
+        debug_assert!(copied.is_ok());
+        tail.write_position = len;
+        tail.read_position = self.read_position.saturating_sub(at);
+        self.truncate(at);
+        tail
+    }
+
+    /// Writes `0` bits up to the next octet boundary and returns their number
+    pub fn align_write_position(&mut self) -> Result<usize, Error> {
+        let padding = self.write_position.next_multiple_of(BYTE_LEN) - self.write_position;
+        for _ in 0..padding {
+            self.write_bit(false)?;
+        }
+        Ok(padding)
+    }
+
+    /// Skips the bits up to the next octet boundary - but not beyond the written bits - and
+    /// returns their number
+    pub fn align_read_position(&mut self) -> usize {
+        let aligned = self
+            .read_position
+            .next_multiple_of(BYTE_LEN)
+            .min(self.write_position.max(self.read_position));
+        core::mem::replace(&mut self.read_position, aligned).abs_diff(aligned)
+    }
+
+    /// Reserves the capacity for at least the given number of bits to be written, without
+    /// changing the content
+    pub fn reserve(&mut self, bit_len: usize) {
+        let required_len = (self.write_position + bit_len).div_ceil(BYTE_LEN);
+        self.buffer
+            .reserve(required_len.saturating_sub(self.buffer.len()));
+    }
+
     pub fn ensure_can_write_additional_bits(&mut self, bit_len: usize) {
         if self.write_position + bit_len >= self.buffer.len() * BYTE_LEN {
-            let required_len = ((self.write_position + bit_len) + 7) / BYTE_LEN;
-            let extend_by_len = required_len - self.buffer.len();
-            self.buffer
-                .extend(core::iter::repeat(0u8).take(extend_by_len))
+            let required_len = (self.write_position + bit_len).div_ceil(BYTE_LEN);
+            self.buffer.resize(required_len, 0u8);
         }
     }
 }
@@ -178,6 +250,11 @@ impl BitRead for BitBuffer {
             dst_bit_len,
         )
     }
+
+    #[inline]
+    fn remaining_bits(&self) -> usize {
+        self.write_position.saturating_sub(self.read_position)
+    }
 }
 
 impl BitWrite for BitBuffer {
@@ -306,6 +383,73 @@ impl BitRead for Bits<'_> {
             dst_bit_len,
         )
     }
+
+    #[inline]
+    fn remaining_bits(&self) -> usize {
+        ScopedBitRead::remaining(self)
+    }
+}
+
+impl<'a> Bits<'a> {
+    /// Splits the bits at the given (absolute) position. Both halves share the underlying slice:
+    /// the first ends at `at`, the second starts reading at `at` - or at the current position,
+    /// if it is beyond.
+    ///
+    /// # Panics
+    /// If `at` is beyond the length
+    pub fn split_at(&self, at: usize) -> (Bits<'a>, Bits<'a>) {
+        assert!(at <= self.len, "Split at bit {} of {}", at, self.len);
+        (
+            Bits {
+                slice: self.slice,
+                pos: self.pos.min(at),
+                len: at,
+            },
+            Bits {
+                slice: self.slice,
+                pos: self.pos.max(at),
+                len: self.len,
+            },
+        )
+    }
+
+    /// Skips the bits up to the next octet boundary - but not beyond the length - and returns
+    /// their number
+    pub fn align_position(&mut self) -> usize {
+        let aligned = self
+            .pos
+            .next_multiple_of(BYTE_LEN)
+            .min(self.len.max(self.pos));
+        core::mem::replace(&mut self.pos, aligned).abs_diff(aligned)
+    }
+
+    /// Reads an octet string like [`PackedRead::read_octetstring`], but borrows the octets from
+    /// the underlying slice instead of copying them - if they start at an octet boundary and are
+    /// not fragmented. Otherwise, the octets are copied into an owned buffer.
+    pub fn read_octetstring_borrowed(
+        &mut self,
+        lower_bound_size: Option<u64>,
+        upper_bound_size: Option<u64>,
+        extensible: bool,
+    ) -> Result<Cow<'a, [u8]>, Error> {
+        let start = self.pos;
+        let (byte_len, fragmentation_possible) =
+            read_octetstring_len(self, lower_bound_size, upper_bound_size, extensible)?;
+
+        if self.pos.is_multiple_of(BYTE_LEN) && !(fragmentation_possible && byte_len >= LENGTH_16K)
+        {
+            self.ensure_remaining_bits(byte_len.saturating_mul(BYTE_LEN as u64))?;
+            let offset = self.pos / BYTE_LEN;
+            self.pos += byte_len as usize * BYTE_LEN;
+            Ok(Cow::Borrowed(
+                &self.slice[offset..offset + byte_len as usize],
+            ))
+        } else {
+            self.pos = start;
+            self.read_octetstring(lower_bound_size, upper_bound_size, extensible)
+                .map(Cow::Owned)
+        }
+    }
 }
 
 impl ScopedBitRead for Bits<'_> {
@@ -496,6 +640,54 @@ pub mod tests {
         Ok(())
     }
 
+    #[test]
+    fn bit_buffer_bits_match_single_bits_at_all_positions() -> Result<(), Error> {
+        let content = &[
+            0b1011_0010,
+            0b0111_0100,
+            0b1010_0110,
+            0b0000_1111,
+            0b1100_0011,
+        ];
+        let bit = |index: usize| content[index / BYTE_LEN] & (0x80 >> (index % BYTE_LEN)) != 0;
+
+        for position in 0..BYTE_LEN * 2 {
+            for offset in 0..BYTE_LEN {
+                for len in 0..content.len() * BYTE_LEN - offset {
+                    let mut expected = BitBuffer::default();
+                    let mut buffer = BitBuffer::default();
+                    for _ in 0..position {
+                        expected.write_bit(true)?;
+                        buffer.write_bit(true)?;
+                    }
+                    for index in offset..offset + len {
+                        expected.write_bit(bit(index))?;
+                    }
+                    buffer.write_bits_with_offset_len(content, offset, len)?;
+                    assert_eq!(expected.content(), buffer.content());
+                    assert_eq!(expected.bit_len(), buffer.bit_len());
+
+                    // the bits around the read bits must not change
+                    let mut read = vec![0xFF_u8; content.len()];
+                    let mut bits = Bits::from(&buffer);
+                    bits.pos = position;
+                    bits.read_bits_with_offset_len(&mut read[..], offset, len)?;
+                    for index in 0..read.len() * BYTE_LEN {
+                        let read_bit = read[index / BYTE_LEN] & (0x80 >> (index % BYTE_LEN)) != 0;
+                        let in_range = (offset..offset + len).contains(&index);
+                        assert_eq!(
+                            !in_range || bit(index),
+                            read_bit,
+                            "{position} {offset} {len} {index}"
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn bit_buffer_length_determinant_0() -> Result<(), Error> {
         const DET: u64 = 0;
@@ -789,12 +981,12 @@ pub mod tests {
         // lower check
         assert_eq!(
             buffer.write_constrained_whole_number(10, 127, 0),
-            Err(ErrorKind::ValueNotInRange(0, 10, 127).into())
+            Err(Error::value_not_in_range(0, 10, 127))
         );
         // upper check
         assert_eq!(
             buffer.write_constrained_whole_number(10, 127, 128),
-            Err(ErrorKind::ValueNotInRange(128, 10, 127).into())
+            Err(Error::value_not_in_range(128, 10, 127))
         );
     }
 
@@ -804,12 +996,12 @@ pub mod tests {
         // lower check
         assert_eq!(
             buffer.write_constrained_whole_number(-10, -1, -11),
-            Err(ErrorKind::ValueNotInRange(-11, -10, -1).into())
+            Err(Error::value_not_in_range(-11, -10, -1))
         );
         // upper check
         assert_eq!(
             buffer.write_constrained_whole_number(-10, -1, 0),
-            Err(ErrorKind::ValueNotInRange(0, -10, -1).into())
+            Err(Error::value_not_in_range(0, -10, -1))
         );
     }
 
@@ -819,12 +1011,12 @@ pub mod tests {
         // lower check
         assert_eq!(
             buffer.write_constrained_whole_number(-10, 1, -11),
-            Err(ErrorKind::ValueNotInRange(-11, -10, 1).into())
+            Err(Error::value_not_in_range(-11, -10, 1))
         );
         // upper check
         assert_eq!(
             buffer.write_constrained_whole_number(-10, 1, 2),
-            Err(ErrorKind::ValueNotInRange(2, -10, 1).into())
+            Err(Error::value_not_in_range(2, -10, 1))
         );
     }
 
@@ -997,4 +1189,77 @@ pub mod tests {
         assert_eq!(3, read_once(&[0x81], 8, 2)?);
         Ok(())
     }
+
+    #[test]
+    fn bit_buffer_append_and_split_off() -> Result<(), Error> {
+        let mut buffer = BitBuffer::default();
+        buffer.write_bits_with_len(&[0b1010_0000], 3)?;
+        let mut other = BitBuffer::default();
+        other.write_bits_with_len(&[0b1100_1100, 0b1000_0000], 9)?;
+
+        buffer.append(&other)?;
+        assert_eq!(12, buffer.bit_len());
+        assert_eq!(&[0b1011_1001, 0b1001_0000], buffer.content());
+
+        buffer.read_bits_with_len(&mut [0_u8], 5)?;
+        let mut tail = buffer.split_off(3);
+        assert_eq!(3, buffer.bit_len());
+        assert_eq!(&[0b1010_0000], buffer.content());
+        assert_eq!(0, buffer.remaining_bits());
+        assert_eq!(other.bit_len(), tail.bit_len());
+        assert_eq!(other.content(), tail.content());
+        assert_eq!(7, tail.remaining_bits());
+
+        // the cleared bits of the shortened buffer are overwritten as expected
+        buffer.write_bit(true)?;
+        assert_eq!(&[0b1011_0000], buffer.content());
+        assert!(tail.split_off(9).content().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn bit_buffer_alignment() -> Result<(), Error> {
+        let mut buffer = BitBuffer::default();
+        assert_eq!(0, buffer.align_write_position()?);
+        buffer.write_bits_with_len(&[0xFF], 3)?;
+        assert_eq!(5, buffer.align_write_position()?);
+        assert_eq!(0, buffer.align_write_position()?);
+        buffer.write_bits_with_len(&[0xFF], 2)?;
+        assert_eq!(&[0b1110_0000, 0b1100_0000], buffer.content());
+
+        buffer.read_bit()?;
+        assert_eq!(7, buffer.align_read_position());
+        assert_eq!(0, buffer.align_read_position());
+        buffer.read_bit()?;
+        // not beyond the written bits
+        assert_eq!(1, buffer.align_read_position());
+        assert_eq!(0, buffer.remaining_bits());
+        Ok(())
+    }
+
+    #[test]
+    fn bits_split_at_and_alignment() -> Result<(), Error> {
+        let content = [0b1010_1010, 0b1111_0000];
+        let mut bits = Bits::from((&content[..], 12));
+        bits.read_bit()?;
+
+        let (mut head, mut tail) = bits.split_at(4);
+        assert_eq!(3, head.remaining_bits());
+        assert_eq!(8, tail.remaining_bits());
+        let mut octet = [0_u8];
+        tail.read_bits(&mut octet)?;
+        assert_eq!([0b1010_1111], octet);
+        assert!(tail.read_bit().is_err());
+
+        // not beyond the length
+        assert_eq!(3, head.align_position());
+        assert_eq!(4, head.pos());
+        let (_, mut tail) = bits.split_at(0);
+        assert_eq!(7, tail.align_position());
+        assert_eq!(4, tail.remaining_bits());
+        assert_eq!(0, tail.align_position());
+        tail.read_bit()?;
+        assert_eq!(3, tail.align_position());
+        Ok(())
+    }
 }
@@ -11,8 +11,8 @@ const MAX_FRAGMENTS: u8 = 4  /* 11.9.3.8, NOTE */ ;
 const MIN_FRAGMENT_SIZE: u64 = FRAGMENT_SIZE;
 const MAX_FRAGMENTS_SIZE: u64 = FRAGMENT_SIZE * MAX_FRAGMENTS as u64;
 
-const LENGTH_127: u64 = 127;
-const LENGTH_16K: u64 = 16 * 1024;
+pub(crate) const LENGTH_127: u64 = 127;
+pub(crate) const LENGTH_16K: u64 = 16 * 1024;
 const LENGTH_64K: u64 = 64 * 1024;
 
 const SMALL_NON_NEGATIVE_NUMBER: u64 = 64;
@@ -33,6 +33,26 @@ pub trait BitRead {
         dst_bit_offset: usize,
         dst_bit_len: usize,
     ) -> Result<(), Error>;
+
+    /// Number of bits that can still be read, `usize::MAX` if unknown - as by default - which
+    /// skips the check of [`BitRead::ensure_remaining_bits`]
+    #[inline]
+    fn remaining_bits(&self) -> usize {
+        usize::MAX
+    }
+
+    /// Fails if less than `bits` remain to be read, so that a (malicious) length determinant
+    /// cannot cause an allocation that exceeds the available data
+    #[inline]
+    fn ensure_remaining_bits(&self, bits: u64) -> Result<(), Error> {
+        let remaining = self.remaining_bits() as u64;
+        if bits > remaining {
+            let missing = usize::try_from(bits - remaining).unwrap_or(usize::MAX);
+            Err(Error::insufficient_data_in_source_buffer_by(missing))
+        } else {
+            Ok(())
+        }
+    }
 }
 
 pub trait ScopedBitRead: BitRead {
@@ -96,7 +116,9 @@ impl<T: BitRead> PackedRead for T {
             let offset_bits = range.leading_zeros() as usize;
             let mut bytes = [0u8; std::mem::size_of::<u64>()];
             self.read_bits_with_offset(&mut bytes, offset_bits)?;
-            Ok(lower + u64::from_be_bytes(bytes))
+            lower
+                .checked_add(u64::from_be_bytes(bytes))
+                .ok_or_else(|| ErrorKind::ValueExceedsMaxInt.into())
         } else {
             let mut bytes = [0u8; std::mem::size_of::<u64>()];
             let length = self.read_length_determinant(None, None)? as usize;
@@ -148,10 +170,12 @@ impl<T: BitRead> PackedRead for T {
         lower_bound: i64,
         upper_bound: i64,
     ) -> Result<i64, Error> {
-        let range = upper_bound - lower_bound;
-        if range > 0 {
-            Ok(lower_bound
-                + self.read_non_negative_binary_integer(None, Some(range as u64))? as i64)
+        if upper_bound > lower_bound {
+            let range = upper_bound.abs_diff(lower_bound);
+            let value = self.read_non_negative_binary_integer(None, Some(range))?;
+            lower_bound
+                .checked_add_unsigned(value)
+                .ok_or_else(|| ErrorKind::ValueExceedsMaxInt.into())
         } else {
             Ok(lower_bound)
         }
@@ -175,7 +199,9 @@ impl<T: BitRead> PackedRead for T {
     #[inline]
     fn read_semi_constrained_whole_number(&mut self, lower_bound: i64) -> Result<i64, Error> {
         let n = self.read_non_negative_binary_integer(None, None)?;
-        Ok((n as i64) + lower_bound)
+        lower_bound
+            .checked_add_unsigned(n)
+            .ok_or_else(|| ErrorKind::ValueExceedsMaxInt.into())
     }
 
     /// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 11.8
@@ -209,8 +235,9 @@ impl<T: BitRead> PackedRead for T {
             if lower_bound == upper_bound {
                 Ok(lower_bound_unwrapped)
             } else {
-                Ok(lower_bound_unwrapped
-                    + self.read_non_negative_binary_integer(lower_bound, upper_bound)?)
+                lower_bound_unwrapped
+                    .checked_add(self.read_non_negative_binary_integer(lower_bound, upper_bound)?)
+                    .ok_or_else(|| ErrorKind::ValueExceedsMaxInt.into())
             }
         } else if const_is_some!(upper_bound) && upper_bound_unwrapped <= LENGTH_64K {
             // 11.9.4.1 -> 11.9.3.4 -> 11.6.1
@@ -272,16 +299,16 @@ impl<T: BitRead> PackedRead for T {
             )
         };
 
-        let mut byte_len = (bit_len + 7) / 8;
-        let mut buffer = vec![0u8; byte_len as usize];
+        self.ensure_remaining_bits(bit_len)?;
+        let mut buffer = vec![0u8; bit_len.div_ceil(8) as usize];
         self.read_bits_with_len(&mut buffer[..], bit_len as usize)?;
 
         // fragmentation?
         if fragmentation_possible && bit_len >= LENGTH_16K {
             loop {
                 let ext_bit_len = self.read_length_determinant(None, None)?;
-                let ext_byte_len = byte_len - ((bit_len + ext_bit_len) + 7) / 8;
-                buffer.extend(core::iter::repeat(0x00).take(ext_byte_len as usize));
+                self.ensure_remaining_bits(ext_bit_len)?;
+                buffer.resize((bit_len + ext_bit_len).div_ceil(8) as usize, 0x00);
                 self.read_bits_with_offset_len(
                     &mut buffer[..],
                     bit_len as usize,
@@ -289,7 +316,6 @@ impl<T: BitRead> PackedRead for T {
                 )?;
 
                 bit_len += ext_bit_len;
-                byte_len += ext_bit_len;
 
                 if ext_bit_len < LENGTH_16K {
                     break;
@@ -302,47 +328,16 @@ impl<T: BitRead> PackedRead for T {
 
     /// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 17
     #[inline]
-    #[allow(clippy::suspicious_else_formatting)] // for 17.6 else-if comment block
-    #[allow(clippy::redundant_pattern_matching)] // allow for const_*!
     fn read_octetstring(
         &mut self,
         lower_bound_size: Option<u64>,
         upper_bound_size: Option<u64>,
         extensible: bool,
     ) -> Result<Vec<u8>, Error> {
-        // let lower_bound = const_unwrap_or!(lower_bound_size, 0);
-        let upper_bound = const_unwrap_or!(upper_bound_size, i64::MAX as u64);
-
-        let (mut byte_len, fragmentation_possible) = if extensible && self.read_bit()? {
-            // 17.3
-            // self.read_semi_constrained_whole_number(0)
-            // self.read_non_negative_binary_integer(0, MAX) + lb  | lb=0=>MIN for unsigned
-            (self.read_length_determinant(None, None)?, true)
-        } else if upper_bound == 0 {
-            // 17.5
-            return Ok(Vec::default());
-        }
-        /* else if const_is_some!(lower_bound_size)
-            && lower_bound_size == upper_bound_size
-            && upper_bound <= 2
-        {
-            // 17.6
-            (upper_bound, false)
-        }*/
-        else if const_is_some!(lower_bound_size)
-            && lower_bound_size == upper_bound_size
-            && upper_bound < LENGTH_64K
-        {
-            // 17.7
-            (upper_bound, false)
-        } else {
-            // 17.8
-            (
-                self.read_length_determinant(lower_bound_size, upper_bound_size)?,
-                true,
-            )
-        };
+        let (mut byte_len, fragmentation_possible) =
+            read_octetstring_len(self, lower_bound_size, upper_bound_size, extensible)?;
 
+        self.ensure_remaining_bits(byte_len.saturating_mul(BYTE_LEN as u64))?;
         let mut buffer = vec![0u8; byte_len as usize];
         self.read_bits(&mut buffer[..])?;
 
@@ -350,6 +345,7 @@ impl<T: BitRead> PackedRead for T {
         if fragmentation_possible && byte_len >= LENGTH_16K {
             loop {
                 let ext_byte_len = self.read_length_determinant(None, None)?;
+                self.ensure_remaining_bits(ext_byte_len.saturating_mul(BYTE_LEN as u64))?;
                 buffer.extend(core::iter::repeat(0u8).take(ext_byte_len as usize));
                 self.read_bits(&mut buffer[byte_len as usize..])?;
                 byte_len += ext_byte_len;
@@ -382,6 +378,51 @@ impl<T: BitRead> PackedRead for T {
     }
 }
 
+/// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 17: reads the number of octets of an octet string
+/// (of its first fragment) and whether further fragments might follow them
+#[inline]
+#[allow(clippy::suspicious_else_formatting)] // for 17.6 else-if comment block
+#[allow(clippy::redundant_pattern_matching)] // allow for const_*!
+pub(crate) fn read_octetstring_len<T: BitRead>(
+    bits: &mut T,
+    lower_bound_size: Option<u64>,
+    upper_bound_size: Option<u64>,
+    extensible: bool,
+) -> Result<(u64, bool), Error> {
+    // let lower_bound = const_unwrap_or!(lower_bound_size, 0);
+    let upper_bound = const_unwrap_or!(upper_bound_size, i64::MAX as u64);
+
+    Ok(if extensible && bits.read_bit()? {
+        // 17.3
+        // self.read_semi_constrained_whole_number(0)
+        // self.read_non_negative_binary_integer(0, MAX) + lb  | lb=0=>MIN for unsigned
+        (bits.read_length_determinant(None, None)?, true)
+    } else if upper_bound == 0 {
+        // 17.5
+        (0, false)
+    }
+    /* else if const_is_some!(lower_bound_size)
+        && lower_bound_size == upper_bound_size
+        && upper_bound <= 2
+    {
+        // 17.6
+        (upper_bound, false)
+    }*/
+    else if const_is_some!(lower_bound_size)
+        && lower_bound_size == upper_bound_size
+        && upper_bound < LENGTH_64K
+    {
+        // 17.7
+        (upper_bound, false)
+    } else {
+        // 17.8
+        (
+            bits.read_length_determinant(lower_bound_size, upper_bound_size)?,
+            true,
+        )
+    })
+}
+
 pub trait BitWrite {
     fn write_bit(&mut self, bit: bool) -> Result<(), Error>;
 
@@ -457,15 +498,14 @@ impl<T: BitWrite> PackedWrite for T {
         upper_bound: i64,
         value: i64,
     ) -> Result<(), Error> {
-        let range = upper_bound - lower_bound;
-        if range > 0 {
+        if upper_bound > lower_bound {
             if value < lower_bound || value > upper_bound {
-                Err(ErrorKind::ValueNotInRange(value, lower_bound, upper_bound).into())
+                Err(Error::value_not_in_range(value, lower_bound, upper_bound))
             } else {
                 self.write_non_negative_binary_integer(
                     None,
-                    Some(range as u64),
-                    (value - lower_bound) as u64,
+                    Some(upper_bound.abs_diff(lower_bound)),
+                    value.abs_diff(lower_bound),
                 )
             }
         } else {
@@ -496,9 +536,9 @@ impl<T: BitWrite> PackedWrite for T {
         value: i64,
     ) -> Result<(), Error> {
         if value < lower_bound {
-            Err(ErrorKind::ValueNotInRange(value, lower_bound, i64::MAX).into())
+            Err(Error::value_not_in_range(value, lower_bound, i64::MAX))
         } else {
-            self.write_non_negative_binary_integer(None, None, (value - lower_bound) as u64)
+            self.write_non_negative_binary_integer(None, None, value.abs_diff(lower_bound))
         }
     }
 
@@ -541,12 +581,11 @@ impl<T: BitWrite> PackedWrite for T {
             if lower_bound == upper_bound {
                 Ok(None)
             } else if value < lower_bound_unwrapped {
-                Err(ErrorKind::ValueNotInRange(
+                Err(Error::value_not_in_range(
                     value as i64,
                     lower_bound_unwrapped as i64,
                     upper_bound_unwrapped as i64,
-                )
-                .into())
+                ))
             } else {
                 self.write_non_negative_binary_integer(
                     lower_bound,
@@ -6,7 +6,7 @@ use crate::protocol::per::{Error, ErrorKind};
 impl BitRead for (&[u8], &mut usize) {
     #[inline]
     fn read_bit(&mut self) -> Result<bool, Error> {
-        if *self.1 > self.0.len() * BYTE_LEN {
+        if *self.1 >= self.0.len() * BYTE_LEN {
             return Err(ErrorKind::EndOfStream.into());
         }
         let bit = self.0[*self.1 / BYTE_LEN] & (0x80 >> (*self.1 % BYTE_LEN)) != 0;
@@ -44,6 +44,11 @@ impl BitRead for (&[u8], &mut usize) {
         *self.1 += dst_bit_len;
         Ok(())
     }
+
+    #[inline]
+    fn remaining_bits(&self) -> usize {
+        (self.0.len() * BYTE_LEN).saturating_sub(*self.1)
+    }
 }
 
 impl<'a> BitWrite for (&'a mut [u8], &mut usize) {
@@ -101,7 +106,9 @@ fn bit_string_copy(
         return Err(Error::insufficient_space_in_destination_buffer());
     }
     if src.len() * BYTE_LEN < src_bit_position + len {
-        return Err(Error::insufficient_data_in_source_buffer());
+        return Err(Error::insufficient_data_in_source_buffer_by(
+            src_bit_position + len - src.len() * BYTE_LEN,
+        ));
     }
     for bit in 0..len {
         let dst_byte_pos = (dst_bit_position + bit) / BYTE_LEN;
@@ -135,16 +142,22 @@ pub(crate) fn bit_string_copy_bulked(
     dst_bit_position: usize,
     len: usize,
 ) -> Result<(), Error> {
-    // chosen by real world tests
-    if len <= BYTE_LEN * 2 {
-        return bit_string_copy(src, src_bit_position, dst, dst_bit_position, len);
-    }
-
     if dst.len() * BYTE_LEN < dst_bit_position + len {
         return Err(Error::insufficient_space_in_destination_buffer());
     }
     if src.len() * BYTE_LEN < src_bit_position + len {
-        return Err(Error::insufficient_data_in_source_buffer());
+        return Err(Error::insufficient_data_in_source_buffer_by(
+            src_bit_position + len - src.len() * BYTE_LEN,
+        ));
+    }
+
+    if src_bit_position.is_multiple_of(BYTE_LEN) && dst_bit_position.is_multiple_of(BYTE_LEN) {
+        return bit_string_copy_aligned(src, src_bit_position, dst, dst_bit_position, len);
+    }
+
+    // chosen by real world tests
+    if len <= BYTE_LEN * 2 {
+        return bit_string_copy(src, src_bit_position, dst, dst_bit_position, len);
     }
 
     let bits_till_full_byte_src = (BYTE_LEN - (src_bit_position % BYTE_LEN)) % BYTE_LEN;
@@ -177,20 +190,21 @@ pub(crate) fn bit_string_copy_bulked(
 
     if dst_byte_offset == 0 {
         // both align
-        dst[dst_byte_index..dst_byte_index + len_in_bytes]
-            .copy_from_slice(&src[src_byte_index..src_byte_index + len_in_bytes]);
-    } else {
-        for index in 0..len_in_bytes {
-            let byte = src[index + src_byte_index];
-            let half_left = byte >> dst_byte_offset;
-            let half_right = byte << (BYTE_LEN - dst_byte_offset);
+        return bit_string_copy_aligned(src, src_bit_position, dst, dst_bit_position, len);
+    }
 
-            dst[index + dst_byte_index] = (dst[index + dst_byte_index]
-                & (0xFF << (BYTE_LEN - dst_byte_offset))) // do not destroy current values on the furthe left side
-                | half_left;
+    for index in 0..len_in_bytes {
+        let byte = src[index + src_byte_index];
+        let half_left = byte >> dst_byte_offset;
+        let half_right = byte << (BYTE_LEN - dst_byte_offset);
 
-            dst[index + dst_byte_index + 1] = half_right;
-        }
+        dst[index + dst_byte_index] = (dst[index + dst_byte_index]
+            & (0xFF << (BYTE_LEN - dst_byte_offset))) // do not destroy current values on the furthe left side
+            | half_left;
+
+        dst[index + dst_byte_index + 1] = (dst[index + dst_byte_index + 1]
+            & (0xFF >> dst_byte_offset)) // nor the ones on the right side of the copied bits
+            | half_right;
     }
 
     if len % BYTE_LEN == 0 {
@@ -206,3 +220,30 @@ pub(crate) fn bit_string_copy_bulked(
         )
     }
 }
+
+/// Copies the octets at once and masks the remaining bits into the last octet, for positions at
+/// octet boundaries within the bounds of both slices
+#[inline]
+fn bit_string_copy_aligned(
+    src: &[u8],
+    src_bit_position: usize,
+    dst: &mut [u8],
+    dst_bit_position: usize,
+    len: usize,
+) -> Result<(), Error> {
+    let src_byte_index = src_bit_position / BYTE_LEN;
+    let dst_byte_index = dst_bit_position / BYTE_LEN;
+    let len_in_bytes = len / BYTE_LEN;
+
+    dst[dst_byte_index..dst_byte_index + len_in_bytes]
+        .copy_from_slice(&src[src_byte_index..src_byte_index + len_in_bytes]);
+
+    let remaining = len % BYTE_LEN;
+    if remaining > 0 {
+        let mask = 0xFF_u8 << (BYTE_LEN - remaining);
+        let src_byte = src[src_byte_index + len_in_bytes];
+        let dst_byte = &mut dst[dst_byte_index + len_in_bytes];
+        *dst_byte = (*dst_byte & !mask) | (src_byte & mask);
+    }
+    Ok(())
+}
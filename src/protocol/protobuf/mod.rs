@@ -23,6 +23,12 @@ pub enum Error {
     InvalidVariant(Backtrace, u64),
     UnexpectedFormat(Backtrace, Format),
     UnexpectedTag(Backtrace, (u32, Format)),
+    ContentTransformFailed(Backtrace, String),
+    SubstitutionFailed(Backtrace, String),
+    /// The name of the field at the index of the definition is unknown
+    UnnamedField(Backtrace, &'static str, usize),
+    /// The string is not the dotted notation of an `OBJECT IDENTIFIER`
+    InvalidObjectIdentifier(Backtrace, String),
 }
 
 impl Error {
@@ -50,6 +56,22 @@ impl Error {
     pub fn unexpected_tag(tag: (u32, Format)) -> Self {
         Error::UnexpectedTag(Backtrace::new(), tag)
     }
+
+    pub fn content_transform_failed<E: std::fmt::Display>(error: E) -> Self {
+        Error::ContentTransformFailed(Backtrace::new(), error.to_string())
+    }
+
+    pub fn substitution_failed<E: std::fmt::Display>(error: E) -> Self {
+        Error::SubstitutionFailed(Backtrace::new(), error.to_string())
+    }
+
+    pub fn unnamed_field(definition: &'static str, index: usize) -> Self {
+        Error::UnnamedField(Backtrace::new(), definition, index)
+    }
+
+    pub fn invalid_object_identifier(value: &str) -> Self {
+        Error::InvalidObjectIdentifier(Backtrace::new(), value.to_string())
+    }
 }
 
 impl std::fmt::Display for Error {
@@ -69,11 +91,38 @@ impl std::fmt::Display for Error {
             Error::UnexpectedTag(b, (tag, format)) => {
                 write!(f, "Tag({}/{:?}) is unexpected\n{:?}", tag, format, b)
             }
+            Error::ContentTransformFailed(b, message) => {
+                write!(f, "Failed to transform the content: {}\n{:?}", message, b)
+            }
+            Error::SubstitutionFailed(b, message) => {
+                write!(
+                    f,
+                    "Failed to convert into the substitute: {}\n{:?}",
+                    message, b
+                )
+            }
+            Error::UnnamedField(b, definition, index) => {
+                write!(
+                    f,
+                    "The name of the field {} of {} is unknown\n{:?}",
+                    index, definition, b
+                )
+            }
+            Error::InvalidObjectIdentifier(b, value) => {
+                write!(f, "'{}' is not a valid OBJECT IDENTIFIER\n{:?}", value, b)
+            }
         }
     }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
 
 #[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy)]
 #[repr(u32)]
@@ -126,6 +175,16 @@ pub trait ProtoWrite {
 
     fn write_sfixed32(&mut self, value: i32) -> Result<(), Error>;
 
+    fn write_fixed64(&mut self, value: u64) -> Result<(), Error>;
+
+    fn write_float(&mut self, value: f32) -> Result<(), Error> {
+        self.write_sfixed32(value.to_bits() as i32)
+    }
+
+    fn write_double(&mut self, value: f64) -> Result<(), Error> {
+        self.write_fixed64(value.to_bits())
+    }
+
     fn write_uint32(&mut self, value: u32) -> Result<(), Error> {
         self.write_varint(u64::from(value))
     }
@@ -168,6 +227,16 @@ pub trait ProtoWrite {
         self.write_sfixed32(value)
     }
 
+    fn write_tagged_float(&mut self, field: u32, value: f32) -> Result<(), Error> {
+        self.write_tag(field, Format::Fixed32)?;
+        self.write_float(value)
+    }
+
+    fn write_tagged_double(&mut self, field: u32, value: f64) -> Result<(), Error> {
+        self.write_tag(field, Format::Fixed64)?;
+        self.write_double(value)
+    }
+
     fn write_tagged_uint32(&mut self, field: u32, value: u32) -> Result<(), Error> {
         self.write_tag(field, Format::VarInt)?;
         self.write_uint32(value)
@@ -224,6 +293,11 @@ impl<W: Write> ProtoWrite for W {
         Ok(())
     }
 
+    fn write_fixed64(&mut self, value: u64) -> Result<(), Error> {
+        self.write_u64::<E>(value)?;
+        Ok(())
+    }
+
     fn write_string(&mut self, value: &str) -> Result<(), Error> {
         self.write_bytes(value.as_bytes())?;
         Ok(())
@@ -258,6 +332,16 @@ pub trait ProtoRead {
 
     fn read_sfixed32(&mut self) -> Result<i32, Error>;
 
+    fn read_fixed64(&mut self) -> Result<u64, Error>;
+
+    fn read_float(&mut self) -> Result<f32, Error> {
+        Ok(f32::from_bits(self.read_sfixed32()? as u32))
+    }
+
+    fn read_double(&mut self) -> Result<f64, Error> {
+        Ok(f64::from_bits(self.read_fixed64()?))
+    }
+
     fn read_uint32(&mut self) -> Result<u32, Error> {
         Ok(self.read_varint()? as u32)
     }
@@ -267,22 +351,59 @@ pub trait ProtoRead {
     }
 
     fn read_sint32(&mut self) -> Result<i32, Error> {
-        // remove leading negative sign to allow further size reduction
-        // protobuf magic, probably something like value - I32_MIN
-        let value = self.read_varint()? as u32;
-        Ok(((value >> 1) as i32) ^ (-((value & 0x01) as i32)))
+        Ok(zigzag_decode32(self.read_varint()? as u32))
     }
 
     fn read_sint64(&mut self) -> Result<i64, Error> {
-        // remove leading negative sign to allow further size reduction
-        // protobuf magic, probably something like value - I64_MIN
-        let value = self.read_varint()?;
-        Ok(((value >> 1) as i64) ^ (-((value & 0x01) as i64)))
+        Ok(zigzag_decode64(self.read_varint()?))
     }
 
     fn read_string(&mut self) -> Result<String, Error>;
 }
 
+/// Removes the leading negative sign that is moved to the lowest bit to allow further size
+/// reduction - protobuf magic, probably something like value - I32_MIN
+#[inline]
+pub(crate) fn zigzag_decode32(value: u32) -> i32 {
+    ((value >> 1) as i32) ^ (-((value & 0x01) as i32))
+}
+
+/// See [`zigzag_decode32`]
+#[inline]
+pub(crate) fn zigzag_decode64(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ (-((value & 0x01) as i64))
+}
+
+/// Reads a varint directly from the slice and advances it past the varint. Unlike
+/// [`ProtoRead::read_varint`] this neither goes through [`Read`] for each byte nor checks the
+/// length of the slice more than once for varints that are not at its end.
+#[inline]
+pub(crate) fn read_varint_from_slice(slice: &mut &[u8]) -> Result<u64, Error> {
+    // one byte is by far the most common case: tags, lengths, enum variants, small numbers
+    if let Some((&first, rest)) = slice.split_first() {
+        if first & 0x80 == 0 {
+            *slice = rest;
+            return Ok(u64::from(first));
+        }
+    }
+
+    let mut value = 0;
+    for (index, byte) in slice.iter().copied().enumerate() {
+        let shift = index * 7;
+        value |= u64::from(byte & 0x7F) << shift;
+        // like the Read impl, stop after the tenth byte which completes 64 bits
+        if byte & 0x80 == 0 || shift + 7 >= 64 {
+            *slice = &slice[index + 1..];
+            return Ok(value);
+        }
+    }
+
+    *slice = &[];
+    Err(Error::from(IoError::from(
+        std::io::ErrorKind::UnexpectedEof,
+    )))
+}
+
 impl<R: Read> ProtoRead for R {
     fn read_varint(&mut self) -> Result<u64, Error> {
         let mut value = 0;
@@ -308,6 +429,10 @@ impl<R: Read> ProtoRead for R {
         Ok(self.read_i32::<E>()?)
     }
 
+    fn read_fixed64(&mut self) -> Result<u64, Error> {
+        Ok(self.read_u64::<E>()?)
+    }
+
     fn read_string(&mut self) -> Result<String, Error> {
         let bytes = self.read_bytes()?;
         if let Ok(string) = String::from_utf8(bytes) {
@@ -317,3 +442,56 @@ impl<R: Read> ProtoRead for R {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_both(bytes: &[u8]) -> (Result<u64, Error>, Result<u64, Error>, usize) {
+        let slice = &mut &bytes[..];
+        let from_slice = read_varint_from_slice(slice);
+        (from_slice, (&mut &bytes[..]).read_varint(), slice.len())
+    }
+
+    #[test]
+    fn test_read_varint_from_slice_matches_read() {
+        for value in [0, 1, 127, 128, 300, 16384, u64::from(u32::MAX), u64::MAX] {
+            let mut bytes = Vec::new();
+            bytes.write_varint(value).unwrap();
+            bytes.push(0xFF);
+
+            let (from_slice, from_read, remaining) = read_both(&bytes);
+            assert_eq!(value, from_slice.unwrap());
+            assert_eq!(value, from_read.unwrap());
+            assert_eq!(1, remaining);
+        }
+    }
+
+    #[test]
+    fn test_read_varint_from_slice_stops_after_ten_bytes() {
+        let (from_slice, from_read, remaining) = read_both(&[0xFF; 12]);
+        assert_eq!(from_read.unwrap(), from_slice.unwrap());
+        assert_eq!(2, remaining);
+    }
+
+    #[test]
+    fn test_read_varint_from_slice_truncated() {
+        let (from_slice, _, remaining) = read_both(&[0x80, 0x80]);
+        assert!(matches!(from_slice, Err(Error::Io(..))));
+        assert_eq!(0, remaining);
+        assert!(matches!(
+            read_varint_from_slice(&mut &[][..]),
+            Err(Error::Io(..))
+        ));
+    }
+
+    #[test]
+    fn test_zigzag_decode() {
+        assert_eq!(0, zigzag_decode32(0));
+        assert_eq!(-1, zigzag_decode32(1));
+        assert_eq!(1, zigzag_decode32(2));
+        assert_eq!(i32::MIN, zigzag_decode32(u32::MAX));
+        assert_eq!(i64::MIN, zigzag_decode64(u64::MAX));
+        assert_eq!(i64::MAX, zigzag_decode64(u64::MAX - 1));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::descriptor::BitVec;
+use crate::descriptor::{BitVec, Oid};
 
 /// In protobuf default-ish-values - such as '0' for numbers - might be serialized as `null`/`None`
 /// if this is possible in the current context. [`ProtobufEq`] will consider these values as equal
@@ -120,6 +120,24 @@ impl ProtobufEq<i64> for i64 {
     }
 }
 
+impl ProtobufEq<f32> for f32 {
+    fn protobuf_eq(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+impl ProtobufEq<f64> for f64 {
+    fn protobuf_eq(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+impl ProtobufEq<Oid> for Oid {
+    fn protobuf_eq(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
 impl ProtobufEq<String> for String {
     fn protobuf_eq(&self, other: &Self) -> bool {
         self == other
@@ -0,0 +1,112 @@
+//! The definitions of a generated module at runtime, so that the type to decode can be selected
+//! by its ASN.1 name - such as the one named by the header of a message. Each generated module
+//! lists its definitions in its `pub fn definitions() -> &'static [DefinitionInfo]`.
+//!
+//! ```rust
+//! use asn1rs::prelude::*;
+//! use asn1rs::registry::{find, Codec, DefinitionInfo};
+//!
+//! #[asn(sequence)]
+//! #[derive(Debug, Default, PartialEq)]
+//! pub struct Ping {
+//!     #[asn(integer(0..255))]
+//!     id: u8,
+//! }
+//!
+//! fn definitions() -> &'static [DefinitionInfo] {
+//!     const DEFINITIONS: &[DefinitionInfo] = &[DefinitionInfo::new::<Ping>("Ping-Request", "Ping")];
+//!     DEFINITIONS
+//! }
+//!
+//! let value = find(definitions(), "Ping-Request")
+//!     .unwrap()
+//!     .decode(Codec::Uper, &[0x2a])
+//!     .unwrap();
+//! assert_eq!(Some(&Ping { id: 42 }), value.downcast_ref::<Ping>());
+//! ```
+
+use crate::descriptor::{Readable, Reader};
+use crate::error::Error;
+use crate::rw::{BerReader, UperReader};
+use std::any::{Any, TypeId};
+use std::fmt::{Debug, Formatter};
+
+/// Decodes a value of the type of a [`DefinitionInfo`]
+type Decode = fn(Codec, &[u8]) -> Result<Box<dyn Any>, Error>;
+
+/// The encoding rules a [`DefinitionInfo`] decodes from
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Codec {
+    Uper,
+    /// Also decodes DER, which is a subset of BER
+    Ber,
+    #[cfg(feature = "oer")]
+    Oer,
+    #[cfg(feature = "cbor")]
+    Cbor,
+}
+
+/// A definition of a generated module, which decodes values of its type without the caller
+/// naming the type at compile time
+#[derive(Copy, Clone)]
+pub struct DefinitionInfo {
+    /// The name of the definition in the ASN.1 module, such as `Ping-Request`
+    pub asn_name: &'static str,
+    /// The name of the generated type, such as `PingRequest`
+    pub rust_name: &'static str,
+    type_id: fn() -> TypeId,
+    decode: Decode,
+}
+
+impl DefinitionInfo {
+    pub const fn new<T: Readable + 'static>(
+        asn_name: &'static str,
+        rust_name: &'static str,
+    ) -> Self {
+        Self {
+            asn_name,
+            rust_name,
+            type_id: TypeId::of::<T>,
+            decode: decode::<T>,
+        }
+    }
+
+    /// Decodes a value of the type of the definition, which the returned [`Box`] can be
+    /// downcast to
+    pub fn decode(&self, codec: Codec, bytes: &[u8]) -> Result<Box<dyn Any>, Error> {
+        (self.decode)(codec, bytes)
+    }
+
+    /// Whether the definition is of the given type
+    pub fn is<T: Any>(&self) -> bool {
+        (self.type_id)() == TypeId::of::<T>()
+    }
+}
+
+impl Debug for DefinitionInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DefinitionInfo")
+            .field("asn_name", &self.asn_name)
+            .field("rust_name", &self.rust_name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The definition with the given ASN.1 name
+pub fn find<'a>(definitions: &'a [DefinitionInfo], asn_name: &str) -> Option<&'a DefinitionInfo> {
+    definitions
+        .iter()
+        .find(|definition| definition.asn_name == asn_name)
+}
+
+fn decode<T: Readable + 'static>(codec: Codec, bytes: &[u8]) -> Result<Box<dyn Any>, Error> {
+    let value = match codec {
+        Codec::Uper => UperReader::from((bytes, bytes.len() * 8)).read::<T>()?,
+        Codec::Ber => BerReader::from(bytes).read::<T>()?,
+        #[cfg(feature = "oer")]
+        Codec::Oer => crate::rw::CoerReader::from(bytes).read::<T>()?,
+        #[cfg(feature = "cbor")]
+        Codec::Cbor => crate::rw::CborReader::from(bytes).read::<T>()?,
+    };
+    Ok(Box::new(value))
+}
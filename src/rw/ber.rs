@@ -0,0 +1,965 @@
+//! Basic Encoding Rules (ITU-T X.690, chapter 8) representation of the generated types, as
+//! required by many legacy protocols such as LDAP and SNMP.
+//!
+//! Every value is written as tag, definite length and content. The tag of a component is the tag
+//! of its definition or the one assigned to the component (such as the context-specific tags of
+//! `AUTOMATIC TAGS` modules), which replaces the tag of the referenced type (implicit tagging).
+//! Tagged `CHOICE` types are always tagged explicitly, as required by ITU-T X.680, 31.2.9.
+//! `OPTIONAL` components that are absent and `DEFAULT` components that equal their default value
+//! are omitted.
+//!
+//! The [`DerWriter`] applies the restrictions of the Distinguished Encoding Rules (ITU-T X.690,
+//! chapter 10 and 11) on top: the elements of a `SET OF` are sorted by their encoding and the
+//! unused bits of a `BIT STRING` are zero. The components of a `SET` are already written in the
+//! canonical order of their tags by the generated code.
+//!
+//! The reader accepts the constructed form of strings with primitive segments and skips unknown
+//! components at the end of extensible `SEQUENCE` and `SET` types. Like the writers, it expects
+//! the components of a `SET` in the canonical order of their tags. It accepts the indefinite
+//! length form only if enabled by [`BerReader::set_accept_indefinite_length`] - the writers
+//! always use the definite form.
+
+use crate::descriptor::*;
+use crate::protocol::basic::ber::{
+    find_end_of_contents, read_identifier, read_integer, read_length, write_identifier,
+    write_integer,
+};
+use crate::protocol::basic::{
+    BasicEncodingRules, BasicWrite, DistinguishedEncodingRules, EncodingRules, Error, ErrorKind,
+};
+use crate::rw::depth::{Depth, DepthLimit};
+use asn1rs_model::asn::{Charset, Tag};
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+pub type BerWriter = TlvWriter<BasicEncodingRules>;
+pub type DerWriter = TlvWriter<DistinguishedEncodingRules>;
+
+/// Writes the tag, length and content of each value, according to the rules `R`
+#[derive(Debug, Clone)]
+pub struct TlvWriter<R: EncodingRules> {
+    buffer: Vec<u8>,
+    /// The tag that replaces the tag of the next value, assigned by the component that references
+    /// the type of the value
+    tag: Option<Tag>,
+    depth: Depth,
+    transforms: containing::Transforms,
+    rules: PhantomData<R>,
+}
+
+impl<R: EncodingRules> Default for TlvWriter<R> {
+    fn default() -> Self {
+        Self::with_capacity(0)
+    }
+}
+
+impl<R: EncodingRules> TlvWriter<R> {
+    pub fn with_capacity(capacity_bytes: usize) -> Self {
+        Self {
+            buffer: Vec::with_capacity(capacity_bytes),
+            tag: None,
+            depth: Depth::default(),
+            transforms: containing::Transforms::default(),
+            rules: PhantomData,
+        }
+    }
+
+    pub fn byte_content(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    pub fn into_bytes_vec(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    /// A writer for the encoding of a value that is embedded into this one, at the same depth
+    fn child(&self) -> Self {
+        Self {
+            depth: self.depth,
+            transforms: self.transforms.clone(),
+            ..Self::default()
+        }
+    }
+
+    #[inline]
+    fn nested<F: FnOnce(&mut Self) -> Result<(), Error>>(&mut self, f: F) -> Result<(), Error> {
+        self.depth
+            .enter()
+            .map_err(ErrorKind::NestingDepthExceedsLimit)?;
+        let result = f(self);
+        self.depth.leave();
+        result
+    }
+
+    fn write_primitive(&mut self, tag: Tag, content: &[u8]) -> Result<(), Error> {
+        let tag = self.tag.take().unwrap_or(tag);
+        write_identifier(&mut self.buffer, tag, false);
+        self.buffer.write_length(content.len() as u64)?;
+        self.buffer.extend_from_slice(content);
+        Ok(())
+    }
+
+    fn write_constructed<F: FnOnce(&mut Self) -> Result<(), Error>>(
+        &mut self,
+        tag: Tag,
+        f: F,
+    ) -> Result<(), Error> {
+        let tag = self.tag.take().unwrap_or(tag);
+        write_identifier(&mut self.buffer, tag, true);
+        // the length is only known after the content is written, insert it in front of it
+        let start = self.buffer.len();
+        f(self)?;
+        let mut length = Vec::with_capacity(1 + core::mem::size_of::<u64>());
+        length.write_length((self.buffer.len() - start) as u64)?;
+        self.buffer.splice(start..start, length);
+        Ok(())
+    }
+
+    fn write_string(
+        &mut self,
+        tag: Tag,
+        charset: Charset,
+        (min, max, extensible): (Option<u64>, Option<u64>, bool),
+        value: &str,
+    ) -> Result<(), Error> {
+        ensure_size_in_range(min, max, extensible, value.chars().count() as u64)?;
+        Error::ensure_string_valid(charset, value)?;
+        self.write_primitive(tag, &charset.encode(value))
+    }
+}
+
+impl<R: EncodingRules> containing::RegisterTransform for TlvWriter<R> {
+    fn transforms_mut(&mut self) -> &mut containing::Transforms {
+        &mut self.transforms
+    }
+}
+
+impl<R: EncodingRules> DepthLimit for TlvWriter<R> {
+    fn depth(&self) -> &Depth {
+        &self.depth
+    }
+
+    fn depth_mut(&mut self) -> &mut Depth {
+        &mut self.depth
+    }
+}
+
+impl<R: EncodingRules> Writer for TlvWriter<R> {
+    type Error = Error;
+
+    #[inline]
+    fn bits_written(&self) -> Option<usize> {
+        Some(self.buffer.len() * 8)
+    }
+
+    #[inline]
+    fn write_sequence<C: sequence::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        self.nested(|w| w.write_constructed(C::TAG, f))
+    }
+
+    #[inline]
+    fn write_sequence_of<C: sequenceof::Constraint, T: WritableType>(
+        &mut self,
+        slice: &[T::Type],
+    ) -> Result<(), Self::Error> {
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, slice.len() as u64)?;
+        self.nested(|w| {
+            w.write_constructed(C::TAG, |w| {
+                slice.iter().try_for_each(|value| T::write_value(w, value))
+            })
+        })
+    }
+
+    #[inline]
+    fn write_set<C: set::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        self.write_sequence::<C, F>(f)
+    }
+
+    #[inline]
+    fn write_set_of<C: setof::Constraint, T: WritableType>(
+        &mut self,
+        slice: &[T::Type],
+    ) -> Result<(), Self::Error> {
+        if !R::DISTINGUISHED {
+            return self.write_sequence_of::<C, T>(slice);
+        }
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, slice.len() as u64)?;
+        self.nested(|w| {
+            // 11.6, the elements are sorted by their encodings
+            let mut elements = slice
+                .iter()
+                .map(|value| {
+                    let mut writer = w.child();
+                    T::write_value(&mut writer, value)?;
+                    Ok(writer.into_bytes_vec())
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+            elements.sort_by(|a, b| compare_padded(a, b));
+            w.write_constructed(C::TAG, |w| {
+                elements
+                    .iter()
+                    .for_each(|element| w.buffer.extend_from_slice(element));
+                Ok(())
+            })
+        })
+    }
+
+    #[inline]
+    fn write_enumerated<C: enumerated::Constraint>(
+        &mut self,
+        enumerated: &C,
+    ) -> Result<(), Self::Error> {
+        let mut content = Vec::with_capacity(core::mem::size_of::<i64>());
+        write_integer(&mut content, enumerated.to_choice_index() as i64);
+        self.write_primitive(C::TAG, &content)
+    }
+
+    #[inline]
+    fn write_choice<C: choice::Constraint>(&mut self, choice: &C) -> Result<(), Self::Error> {
+        let tag = self.tag.take().filter(|tag| C::TAG.ne(tag));
+        self.nested(|w| match tag {
+            Some(tag) => w.write_constructed(tag, |w| choice.write_content(w)),
+            None => choice.write_content(w),
+        })
+    }
+
+    #[inline]
+    fn write_opt<T: WritableType>(&mut self, value: Option<&T::Type>) -> Result<(), Self::Error> {
+        match value {
+            Some(value) => T::write_value(self, value),
+            None => Ok(()),
+        }
+    }
+
+    #[inline]
+    fn write_default<C: default::Constraint<Owned = T::Type>, T: WritableType>(
+        &mut self,
+        value: &T::Type,
+    ) -> Result<(), Self::Error> {
+        if C::default_value().ne(value) {
+            T::write_value(self, value)
+        } else {
+            Ok(())
+        }
+    }
+
+    #[inline]
+    fn write_number<T: numbers::Number, C: numbers::Constraint<T>>(
+        &mut self,
+        value: T,
+    ) -> Result<(), Self::Error> {
+        let value = value.to_i64();
+        ensure_value_in_range::<T, C>(value)?;
+        let mut content = Vec::with_capacity(core::mem::size_of::<i64>());
+        write_integer(&mut content, value);
+        self.write_primitive(C::TAG, &content)
+    }
+
+    #[inline]
+    fn write_utf8string<C: utf8string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        let size = (C::MIN, C::MAX, C::EXTENSIBLE);
+        self.write_string(C::TAG, Charset::Utf8, size, value)
+    }
+
+    #[inline]
+    fn write_ia5string<C: ia5string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        let size = (C::MIN, C::MAX, C::EXTENSIBLE);
+        self.write_string(C::TAG, Charset::Ia5, size, value)
+    }
+
+    #[inline]
+    fn write_numeric_string<C: numericstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        let size = (C::MIN, C::MAX, C::EXTENSIBLE);
+        self.write_string(C::TAG, Charset::Numeric, size, value)
+    }
+
+    #[inline]
+    fn write_visible_string<C: visiblestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        let size = (C::MIN, C::MAX, C::EXTENSIBLE);
+        self.write_string(C::TAG, Charset::Visible, size, value)
+    }
+
+    #[inline]
+    fn write_printable_string<C: printablestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        let size = (C::MIN, C::MAX, C::EXTENSIBLE);
+        self.write_string(C::TAG, Charset::Printable, size, value)
+    }
+
+    #[inline]
+    fn write_bmp_string<C: bmpstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        let size = (C::MIN, C::MAX, C::EXTENSIBLE);
+        self.write_string(C::TAG, Charset::Bmp, size, value)
+    }
+
+    #[inline]
+    fn write_teletex_string<C: teletexstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        let size = (C::MIN, C::MAX, C::EXTENSIBLE);
+        self.write_string(C::TAG, Charset::Teletex, size, value)
+    }
+
+    #[inline]
+    fn write_octet_string<C: octetstring::Constraint>(
+        &mut self,
+        value: &[u8],
+    ) -> Result<(), Self::Error> {
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, value.len() as u64)?;
+        self.write_primitive(C::TAG, value)
+    }
+
+    #[inline]
+    fn write_containing<C: octetstring::Constraint, X: containing::Transform, T: WritableType>(
+        &mut self,
+        value: &T::Type,
+    ) -> Result<(), Self::Error> {
+        let mut writer = self.child();
+        T::write_value(&mut writer, value)?;
+        let octets = self
+            .transforms
+            .encode::<X>(writer.into_bytes_vec())
+            .map_err(Error::content_transform_failed)?;
+        self.write_octet_string::<C>(&octets)
+    }
+
+    #[inline]
+    fn write_bit_string<C: bitstring::Constraint>(
+        &mut self,
+        value: &[u8],
+        bit_len: u64,
+    ) -> Result<(), Self::Error> {
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, bit_len)?;
+        let value = value
+            .get(..bit_len.div_ceil(8) as usize)
+            .ok_or_else(|| Error::size_not_in_range(bit_len, 0, value.len() as u64 * 8))?;
+        // 8.6.2.2, the initial octet is the number of unused bits in the final octet
+        let unused = ((8 - bit_len % 8) % 8) as u8;
+        let mut content = Vec::with_capacity(1 + value.len());
+        content.push(unused);
+        content.extend_from_slice(value);
+        if R::DISTINGUISHED {
+            // 11.2.1, the unused bits are zero
+            if let Some(last) = content.last_mut().filter(|_| !value.is_empty()) {
+                *last &= 0xFF << unused;
+            }
+        }
+        self.write_primitive(C::TAG, &content)
+    }
+
+    #[inline]
+    fn write_boolean<C: boolean::Constraint>(&mut self, value: bool) -> Result<(), Self::Error> {
+        self.write_primitive(C::TAG, &[if value { 0xFF } else { 0x00 }])
+    }
+
+    #[inline]
+    fn write_null<C: null::Constraint>(&mut self, _value: &Null) -> Result<(), Self::Error> {
+        self.write_primitive(C::TAG, &[])
+    }
+
+    #[inline]
+    fn write_real<T: real::Float, C: real::Constraint>(
+        &mut self,
+        value: T,
+    ) -> Result<(), Self::Error> {
+        // the canonical form of 11.3.1 is a valid BER encoding as well
+        self.write_primitive(
+            C::TAG,
+            &crate::protocol::basic::real::encode(value.to_f64()),
+        )
+    }
+
+    #[inline]
+    fn write_object_identifier<C: objectidentifier::Constraint>(
+        &mut self,
+        value: &Oid,
+    ) -> Result<(), Self::Error> {
+        let contents = crate::protocol::basic::oid::encode(value.as_slice())
+            .map_err(Error::invalid_encoding)?;
+        self.write_primitive(C::TAG, &contents)
+    }
+
+    #[inline]
+    fn write_relative_oid<C: relativeoid::Constraint>(
+        &mut self,
+        value: &Oid,
+    ) -> Result<(), Self::Error> {
+        let contents = crate::protocol::basic::oid::encode_relative(value.as_slice())
+            .map_err(Error::invalid_encoding)?;
+        self.write_primitive(C::TAG, &contents)
+    }
+
+    #[inline]
+    fn write_oid_iri<C: oidiri::Constraint>(&mut self, value: &str) -> Result<(), Self::Error> {
+        crate::protocol::basic::oid::check_iri(value).map_err(Error::invalid_encoding)?;
+        self.write_primitive(C::TAG, value.as_bytes())
+    }
+
+    #[inline]
+    fn write_complex<C: complex::Constraint, V: Writable>(
+        &mut self,
+        value: &V,
+    ) -> Result<(), Self::Error> {
+        // the outermost component that assigns a tag determines the tag of the value
+        if self.tag.is_none() {
+            self.tag = Some(C::TAG);
+        }
+        value.write(self)
+    }
+}
+
+pub struct BerReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    /// The end of the content of the innermost constructed value that is currently read
+    end: usize,
+    /// The tag that replaces the tag of the next value, assigned by the component that references
+    /// the type of the value
+    tag: Option<Tag>,
+    /// The position at which a value was expected but another tag or the end of the content was
+    /// found, to tell absent `OPTIONAL` components and other `CHOICE` variants from invalid
+    /// content
+    absent_at: Option<usize>,
+    recovery: numbers::Recovery,
+    accept_indefinite_length: bool,
+    depth: Depth,
+    transforms: containing::Transforms,
+}
+
+impl<'a> From<&'a [u8]> for BerReader<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            end: bytes.len(),
+            tag: None,
+            absent_at: None,
+            recovery: numbers::Recovery::default(),
+            accept_indefinite_length: false,
+            depth: Depth::default(),
+            transforms: containing::Transforms::default(),
+        }
+    }
+}
+
+impl<'a> BerReader<'a> {
+    /// The position of the next byte to read
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    #[inline]
+    pub fn bytes_remaining(&self) -> usize {
+        self.bytes.len().saturating_sub(self.position)
+    }
+
+    pub const fn accepts_indefinite_length(&self) -> bool {
+        self.accept_indefinite_length
+    }
+
+    /// If set, constructed values of indefinite length (ITU-T X.690, 8.1.3.6) - whose content
+    /// ends with the end-of-contents octets - are read as well, instead of failing with
+    /// [`ErrorKind::UnsupportedOperation`]. Older stacks still emit these.
+    pub fn set_accept_indefinite_length(&mut self, accept: bool) {
+        self.accept_indefinite_length = accept;
+    }
+
+    #[inline]
+    fn nested<T, F: FnOnce(&mut Self) -> Result<T, Error>>(&mut self, f: F) -> Result<T, Error> {
+        self.depth
+            .enter()
+            .map_err(ErrorKind::NestingDepthExceedsLimit)?;
+        let result = f(self);
+        self.depth.leave();
+        result
+    }
+
+    /// The bytes up to the end of the constructed value that is currently read
+    #[inline]
+    fn content(&self) -> &'a [u8] {
+        self.bytes.get(..self.end).unwrap_or_default()
+    }
+
+    #[inline]
+    fn is_absent(&self, position: usize, error: &Error) -> bool {
+        self.absent_at == Some(position)
+            && matches!(
+                error.kind(),
+                ErrorKind::UnexpectedTypeTag { .. } | ErrorKind::EndOfContent
+            )
+    }
+
+    /// Reads the identifier and length octets and returns whether the value is constructed,
+    /// where its content ends and where the value ends - which is after the end-of-contents
+    /// octets for the indefinite length form
+    fn read_header(&mut self, tag: Tag) -> Result<(bool, usize, usize), Error> {
+        let expected = self.tag.take().unwrap_or(tag);
+        let start = self.position;
+        if start >= self.end {
+            self.absent_at = Some(start);
+            return Err(ErrorKind::EndOfContent.into());
+        }
+
+        let (tag, constructed) = read_identifier(self.content(), &mut self.position)?;
+        if tag != expected {
+            self.position = start;
+            self.absent_at = Some(start);
+            return Err(Error::unexpected_tag(expected, tag));
+        }
+
+        match read_length(self.content(), &mut self.position)? {
+            Some(length) => {
+                let end = usize::try_from(length)
+                    .ok()
+                    .and_then(|length| self.position.checked_add(length))
+                    .filter(|end| *end <= self.end)
+                    .ok_or(ErrorKind::EndOfContent)?;
+                Ok((constructed, end, end))
+            }
+            None if self.accept_indefinite_length && constructed => {
+                let end = find_end_of_contents(self.content(), self.position)?;
+                Ok((constructed, end, end + 2))
+            }
+            None if self.accept_indefinite_length => Err(Error::invalid_encoding(
+                "indefinite length of a primitive value",
+            )),
+            None => Err(Error::unsupported_operation("indefinite length")),
+        }
+    }
+
+    fn read_primitive(&mut self, tag: Tag) -> Result<&'a [u8], Error> {
+        let (constructed, end, _) = self.read_header(tag)?;
+        if constructed {
+            return Err(Error::invalid_encoding(
+                "constructed encoding of a primitive type",
+            ));
+        }
+        let content = self
+            .bytes
+            .get(self.position..end)
+            .ok_or(ErrorKind::EndOfContent)?;
+        self.position = end;
+        Ok(content)
+    }
+
+    fn read_constructed<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
+        &mut self,
+        tag: Tag,
+        extensible: bool,
+        f: F,
+    ) -> Result<T, Error> {
+        let (constructed, end, value_end) = self.read_header(tag)?;
+        if !constructed {
+            return Err(Error::invalid_encoding(
+                "primitive encoding of a constructed type",
+            ));
+        }
+        let outer_end = core::mem::replace(&mut self.end, end);
+        let result = f(self).and_then(|value| {
+            if self.position < end && !extensible {
+                Err(ErrorKind::TrailingContent(end - self.position).into())
+            } else {
+                // skip unknown extensions
+                self.position = value_end;
+                Ok(value)
+            }
+        });
+        self.end = outer_end;
+        result
+    }
+
+    /// Reads the octets of a string type, which might be split into primitive segments
+    /// (8.7.3, 8.23.6)
+    fn read_octets(&mut self, tag: Tag) -> Result<Vec<u8>, Error> {
+        let tag = self.tag.take().unwrap_or(tag);
+        let start = self.position;
+        let (constructed, _end, _value_end) = self.read_header(tag)?;
+        self.position = start;
+        if constructed {
+            self.read_constructed(tag, false, |r| {
+                let mut octets = Vec::new();
+                while r.position < r.end {
+                    octets.extend_from_slice(r.read_primitive(Tag::DEFAULT_OCTET_STRING)?);
+                }
+                Ok(octets)
+            })
+        } else {
+            self.read_primitive(tag).map(<[u8]>::to_vec)
+        }
+    }
+
+    fn read_string(
+        &mut self,
+        tag: Tag,
+        charset: Charset,
+        (min, max, extensible): (Option<u64>, Option<u64>, bool),
+    ) -> Result<String, Error> {
+        let string = charset
+            .decode(&self.read_octets(tag)?)
+            .ok_or_else(|| Error::invalid_encoding("the string is not validly encoded"))?;
+        Error::ensure_string_valid(charset, &string)?;
+        ensure_size_in_range(min, max, extensible, string.chars().count() as u64)?;
+        Ok(string)
+    }
+
+    fn read_choice_variant<C: choice::Constraint>(&mut self) -> Result<C, Error> {
+        let start = self.position;
+        for index in 0..C::VARIANT_COUNT {
+            match C::read_content(index, self) {
+                Ok(Some(choice)) => return Ok(choice),
+                Ok(None) => {}
+                Err(e) if self.is_absent(start, &e) => {
+                    self.position = start;
+                    self.tag = None;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        self.absent_at = Some(start);
+        let mut position = start;
+        let (tag, _constructed) = read_identifier(self.content(), &mut position)?;
+        Err(Error::unexpected_tag(C::TAG, tag))
+    }
+}
+
+impl containing::RegisterTransform for BerReader<'_> {
+    fn transforms_mut(&mut self) -> &mut containing::Transforms {
+        &mut self.transforms
+    }
+}
+
+impl DepthLimit for BerReader<'_> {
+    fn depth(&self) -> &Depth {
+        &self.depth
+    }
+
+    fn depth_mut(&mut self) -> &mut Depth {
+        &mut self.depth
+    }
+}
+
+impl numbers::DecodePolicyReader for BerReader<'_> {
+    fn recovery(&self) -> &numbers::Recovery {
+        &self.recovery
+    }
+
+    fn recovery_mut(&mut self) -> &mut numbers::Recovery {
+        &mut self.recovery
+    }
+}
+
+impl Reader for BerReader<'_> {
+    type Error = Error;
+
+    #[inline]
+    fn bits_read(&self) -> Option<usize> {
+        Some(self.position * 8)
+    }
+
+    #[inline]
+    fn read_sequence<
+        C: sequence::Constraint,
+        S: Sized,
+        F: Fn(&mut Self) -> Result<S, Self::Error>,
+    >(
+        &mut self,
+        f: F,
+    ) -> Result<S, Self::Error> {
+        self.nested(|r| r.read_constructed(C::TAG, C::EXTENDED_AFTER_FIELD.is_some(), f))
+    }
+
+    #[inline]
+    fn read_sequence_of<C: sequenceof::Constraint, T: ReadableType>(
+        &mut self,
+    ) -> Result<Vec<T::Type>, Self::Error> {
+        self.nested(|r| {
+            r.read_constructed(C::TAG, false, |r| {
+                let mut values = Vec::new();
+                while r.position < r.end {
+                    values.push(T::read_value(r)?);
+                }
+                ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, values.len() as u64)?;
+                Ok(values)
+            })
+        })
+    }
+
+    #[inline]
+    fn read_set<C: set::Constraint, S: Sized, F: Fn(&mut Self) -> Result<S, Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<S, Self::Error> {
+        self.read_sequence::<C, S, F>(f)
+    }
+
+    #[inline]
+    fn read_set_of<C: setof::Constraint, T: ReadableType>(
+        &mut self,
+    ) -> Result<Vec<T::Type>, Self::Error> {
+        self.read_sequence_of::<C, T>()
+    }
+
+    #[inline]
+    fn read_enumerated<C: enumerated::Constraint>(&mut self) -> Result<C, Self::Error> {
+        let index = read_integer(self.read_primitive(C::TAG)?)?;
+        u64::try_from(index)
+            .ok()
+            .and_then(C::from_choice_index)
+            .ok_or_else(|| Error::unexpected_choice_index(0..C::VARIANT_COUNT, index as u64))
+    }
+
+    #[inline]
+    fn read_choice<C: choice::Constraint>(&mut self) -> Result<C, Self::Error> {
+        let tag = self.tag.take().filter(|tag| C::TAG.ne(tag));
+        self.nested(|r| match tag {
+            Some(tag) => r.read_constructed(tag, false, Self::read_choice_variant::<C>),
+            None => r.read_choice_variant::<C>(),
+        })
+    }
+
+    #[inline]
+    fn read_opt<T: ReadableType>(&mut self) -> Result<Option<T::Type>, Self::Error> {
+        let start = self.position;
+        let end = self.end;
+        match T::read_value(self) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if self.is_absent(start, &e) => {
+                self.position = start;
+                self.end = end;
+                self.tag = None;
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    #[inline]
+    fn read_default<C: default::Constraint<Owned = T::Type>, T: ReadableType>(
+        &mut self,
+    ) -> Result<T::Type, Self::Error> {
+        Ok(self
+            .read_opt::<T>()?
+            .unwrap_or_else(|| C::default_value().to_owned()))
+    }
+
+    #[inline]
+    fn read_number<T: numbers::Number, C: numbers::Constraint<T>>(
+        &mut self,
+    ) -> Result<T, Self::Error> {
+        let value = read_integer(self.read_primitive(C::TAG)?)?;
+        self.recovery
+            .apply::<T, C, _>(value, ensure_value_in_range::<T, C>(value))
+    }
+
+    #[inline]
+    fn read_utf8string<C: utf8string::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.read_string(C::TAG, Charset::Utf8, (C::MIN, C::MAX, C::EXTENSIBLE))
+    }
+
+    #[inline]
+    fn read_ia5string<C: ia5string::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.read_string(C::TAG, Charset::Ia5, (C::MIN, C::MAX, C::EXTENSIBLE))
+    }
+
+    #[inline]
+    fn read_numeric_string<C: numericstring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.read_string(C::TAG, Charset::Numeric, (C::MIN, C::MAX, C::EXTENSIBLE))
+    }
+
+    #[inline]
+    fn read_visible_string<C: visiblestring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.read_string(C::TAG, Charset::Visible, (C::MIN, C::MAX, C::EXTENSIBLE))
+    }
+
+    #[inline]
+    fn read_printable_string<C: printablestring::Constraint>(
+        &mut self,
+    ) -> Result<String, Self::Error> {
+        self.read_string(C::TAG, Charset::Printable, (C::MIN, C::MAX, C::EXTENSIBLE))
+    }
+
+    #[inline]
+    fn read_bmp_string<C: bmpstring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.read_string(C::TAG, Charset::Bmp, (C::MIN, C::MAX, C::EXTENSIBLE))
+    }
+
+    #[inline]
+    fn read_teletex_string<C: teletexstring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.read_string(C::TAG, Charset::Teletex, (C::MIN, C::MAX, C::EXTENSIBLE))
+    }
+
+    #[inline]
+    fn read_octet_string<C: octetstring::Constraint>(&mut self) -> Result<Vec<u8>, Self::Error> {
+        let octets = self.read_octets(C::TAG)?;
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, octets.len() as u64)?;
+        Ok(octets)
+    }
+
+    #[inline]
+    fn read_containing<C: octetstring::Constraint, X: containing::Transform, T: ReadableType>(
+        &mut self,
+    ) -> Result<T::Type, Self::Error> {
+        let octets = self.read_octet_string::<C>()?;
+        let content = self
+            .transforms
+            .decode::<X>(octets)
+            .map_err(Error::content_transform_failed)?;
+        let mut reader = BerReader::from(&content[..]);
+        reader.depth = self.depth;
+        reader.transforms = self.transforms.clone();
+        T::read_value(&mut reader)
+    }
+
+    #[inline]
+    fn read_substituted<S, X: substituted::Substitute<S, T::Type>, T: ReadableType>(
+        &mut self,
+    ) -> Result<S, Self::Error> {
+        let value = T::read_value(self)?;
+        X::from_asn(value).map_err(Error::substitution_failed)
+    }
+
+    #[inline]
+    fn read_bit_string<C: bitstring::Constraint>(&mut self) -> Result<(Vec<u8>, u64), Self::Error> {
+        let (unused, bytes) = self
+            .read_primitive(C::TAG)?
+            .split_first()
+            .ok_or_else(|| Error::invalid_encoding("bit string without initial octet"))?;
+        if *unused > 7 || (bytes.is_empty() && *unused > 0) {
+            return Err(Error::invalid_encoding("invalid number of unused bits"));
+        }
+        let bit_len = bytes.len() as u64 * 8 - u64::from(*unused);
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, bit_len)?;
+        Ok((bytes.to_vec(), bit_len))
+    }
+
+    #[inline]
+    fn read_boolean<C: boolean::Constraint>(&mut self) -> Result<bool, Self::Error> {
+        match self.read_primitive(C::TAG)? {
+            [value] => Ok(*value != 0x00),
+            content => Err(Error::unexpected_length(1..2, content.len() as u64)),
+        }
+    }
+
+    #[inline]
+    fn read_null<C: null::Constraint>(&mut self) -> Result<Null, Self::Error> {
+        match self.read_primitive(C::TAG)? {
+            [] => Ok(Null),
+            content => Err(Error::unexpected_length(0..1, content.len() as u64)),
+        }
+    }
+
+    #[inline]
+    fn read_real<T: real::Float, C: real::Constraint>(&mut self) -> Result<T, Self::Error> {
+        crate::protocol::basic::real::decode(self.read_primitive(C::TAG)?)
+            .map(T::from_f64)
+            .map_err(Error::invalid_encoding)
+    }
+
+    #[inline]
+    fn read_object_identifier<C: objectidentifier::Constraint>(
+        &mut self,
+    ) -> Result<Oid, Self::Error> {
+        crate::protocol::basic::oid::decode(self.read_primitive(C::TAG)?)
+            .map(Oid::from)
+            .map_err(Error::invalid_encoding)
+    }
+
+    #[inline]
+    fn read_relative_oid<C: relativeoid::Constraint>(&mut self) -> Result<Oid, Self::Error> {
+        crate::protocol::basic::oid::decode_relative(self.read_primitive(C::TAG)?)
+            .map(Oid::from)
+            .map_err(Error::invalid_encoding)
+    }
+
+    #[inline]
+    fn read_oid_iri<C: oidiri::Constraint>(&mut self) -> Result<String, Self::Error> {
+        let iri = self.read_string(C::TAG, Charset::Utf8, (None, None, false))?;
+        crate::protocol::basic::oid::check_iri(&iri).map_err(Error::invalid_encoding)?;
+        Ok(iri)
+    }
+
+    #[inline]
+    fn read_complex<C: complex::Constraint, V: Readable>(&mut self) -> Result<V, Self::Error> {
+        if self.tag.is_none() {
+            self.tag = Some(C::TAG);
+        }
+        V::read(self)
+    }
+}
+
+/// Compares the octets as if the shorter one was padded with trailing zeros (X.690, 11.6)
+fn compare_padded(a: &[u8], b: &[u8]) -> Ordering {
+    let len = a.len().min(b.len());
+    a[..len].cmp(&b[..len]).then_with(|| {
+        let is_padding = |tail: &[u8]| tail.iter().all(|octet| *octet == 0);
+        if a.len() > len {
+            if is_padding(&a[len..]) {
+                Ordering::Equal
+            } else {
+                Ordering::Greater
+            }
+        } else if is_padding(&b[len..]) {
+            Ordering::Equal
+        } else {
+            Ordering::Less
+        }
+    })
+}
+
+fn ensure_size_in_range(
+    min: Option<u64>,
+    max: Option<u64>,
+    extensible: bool,
+    size: u64,
+) -> Result<(), Error> {
+    let min = min.unwrap_or(0);
+    let max = max.unwrap_or(u64::MAX);
+    if extensible || (min..=max).contains(&size) {
+        Ok(())
+    } else {
+        Err(Error::size_not_in_range(size, min, max))
+    }
+}
+
+fn ensure_value_in_range<T: numbers::Number, C: numbers::Constraint<T>>(
+    value: i64,
+) -> Result<(), Error> {
+    let min = C::MIN.unwrap_or(i64::MIN);
+    let max = C::MAX.unwrap_or(i64::MAX);
+    if C::EXTENSIBLE || (min..=max).contains(&value) {
+        Ok(())
+    } else {
+        Err(Error::value_not_in_range(
+            value,
+            min,
+            max,
+            C::DEFINITION_NAME,
+            C::FIELD_NAME,
+        ))
+    }
+}
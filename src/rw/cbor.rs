@@ -0,0 +1,888 @@
+//! Deterministic CBOR (RFC 8949, 4.2.1) representation of the generated types, for protocols of
+//! constrained devices that need CBOR siblings of their ASN.1 messages.
+//!
+//! Other than a serde round-trip, the constraints of the ASN.1 definition are checked while
+//! writing and reading. The types are mapped as follows:
+//!  - `SEQUENCE` and `SET` to an array with one item per field, absent `OPTIONAL` fields and
+//!    fields that equal their `DEFAULT` value are written as `undefined`. Extensible types accept
+//!    arrays that are shorter (missing extension fields are absent) or longer (unknown extension
+//!    fields are skipped) than the number of known fields.
+//!  - `SEQUENCE OF` and `SET OF` to an array
+//!  - `CHOICE` to a map with a single entry, the index of the variant as key
+//!  - `ENUMERATED` to the index of the variant as unsigned integer
+//!  - `INTEGER` to an unsigned or negative integer
+//!  - character strings to text strings, `OCTET STRING` to a byte string
+//!  - `BIT STRING` to an array of the byte string and the number of bits
+//!  - `BOOLEAN` to `true` or `false` and `NULL` to `null`
+//!  - `REAL` to the shortest floating point number that preserves the value
+
+use crate::descriptor::*;
+use crate::protocol::cbor::{
+    float_value, read_head, write_float, write_head, write_integer, Error, ErrorKind, FALSE,
+    MAJOR_ARRAY, MAJOR_BYTES, MAJOR_MAP, MAJOR_NEGATIVE, MAJOR_TAG, MAJOR_TEXT, MAJOR_UNSIGNED,
+    NULL, TAG_OID, TAG_RELATIVE_OID, TRUE, UNDEFINED,
+};
+use crate::rw::depth::{Depth, DepthLimit};
+use asn1rs_model::asn::Charset;
+
+#[derive(Debug, Default, Clone)]
+pub struct CborWriter {
+    buffer: Vec<u8>,
+    depth: Depth,
+    transforms: containing::Transforms,
+}
+
+impl CborWriter {
+    pub fn with_capacity(capacity_bytes: usize) -> Self {
+        Self {
+            buffer: Vec::with_capacity(capacity_bytes),
+            ..Self::default()
+        }
+    }
+
+    pub fn byte_content(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    pub fn into_bytes_vec(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    #[inline]
+    fn nested<F: FnOnce(&mut Self) -> Result<(), Error>>(&mut self, f: F) -> Result<(), Error> {
+        self.depth
+            .enter()
+            .map_err(ErrorKind::NestingDepthExceedsLimit)?;
+        let result = f(self);
+        self.depth.leave();
+        result
+    }
+
+    fn write_string(&mut self, charset: Charset, value: &str) -> Result<(), Error> {
+        Error::ensure_string_valid(charset, value)?;
+        write_head(&mut self.buffer, MAJOR_TEXT, value.len() as u64);
+        self.buffer.extend_from_slice(value.as_bytes());
+        Ok(())
+    }
+}
+
+impl containing::RegisterTransform for CborWriter {
+    fn transforms_mut(&mut self) -> &mut containing::Transforms {
+        &mut self.transforms
+    }
+}
+
+impl DepthLimit for CborWriter {
+    fn depth(&self) -> &Depth {
+        &self.depth
+    }
+
+    fn depth_mut(&mut self) -> &mut Depth {
+        &mut self.depth
+    }
+}
+
+impl Writer for CborWriter {
+    type Error = Error;
+
+    #[inline]
+    fn bits_written(&self) -> Option<usize> {
+        Some(self.buffer.len() * 8)
+    }
+
+    #[inline]
+    fn write_sequence<C: sequence::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        self.nested(|w| {
+            write_head(&mut w.buffer, MAJOR_ARRAY, C::FIELD_COUNT);
+            f(w)
+        })
+    }
+
+    #[inline]
+    fn write_sequence_of<C: sequenceof::Constraint, T: WritableType>(
+        &mut self,
+        slice: &[T::Type],
+    ) -> Result<(), Self::Error> {
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, slice.len() as u64)?;
+        self.nested(|w| {
+            write_head(&mut w.buffer, MAJOR_ARRAY, slice.len() as u64);
+            slice.iter().try_for_each(|value| T::write_value(w, value))
+        })
+    }
+
+    #[inline]
+    fn write_set<C: set::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        self.write_sequence::<C, F>(f)
+    }
+
+    #[inline]
+    fn write_set_of<C: setof::Constraint, T: WritableType>(
+        &mut self,
+        slice: &[T::Type],
+    ) -> Result<(), Self::Error> {
+        self.write_sequence_of::<C, T>(slice)
+    }
+
+    #[inline]
+    fn write_enumerated<C: enumerated::Constraint>(
+        &mut self,
+        enumerated: &C,
+    ) -> Result<(), Self::Error> {
+        write_head(
+            &mut self.buffer,
+            MAJOR_UNSIGNED,
+            enumerated.to_choice_index(),
+        );
+        Ok(())
+    }
+
+    #[inline]
+    fn write_choice<C: choice::Constraint>(&mut self, choice: &C) -> Result<(), Self::Error> {
+        self.nested(|w| {
+            write_head(&mut w.buffer, MAJOR_MAP, 1);
+            write_head(&mut w.buffer, MAJOR_UNSIGNED, choice.to_choice_index());
+            choice.write_content(w)
+        })
+    }
+
+    #[inline]
+    fn write_opt<T: WritableType>(
+        &mut self,
+        value: Option<&<T as WritableType>::Type>,
+    ) -> Result<(), Self::Error> {
+        match value {
+            Some(value) => T::write_value(self, value),
+            None => {
+                self.buffer.push(UNDEFINED);
+                Ok(())
+            }
+        }
+    }
+
+    #[inline]
+    fn write_default<C: default::Constraint<Owned = T::Type>, T: WritableType>(
+        &mut self,
+        value: &T::Type,
+    ) -> Result<(), Self::Error> {
+        if C::default_value().ne(value) {
+            T::write_value(self, value)
+        } else {
+            self.buffer.push(UNDEFINED);
+            Ok(())
+        }
+    }
+
+    #[inline]
+    fn write_number<T: numbers::Number, C: numbers::Constraint<T>>(
+        &mut self,
+        value: T,
+    ) -> Result<(), Self::Error> {
+        let value = value.to_i64();
+        ensure_value_in_range::<T, C>(value)?;
+        write_integer(&mut self.buffer, value);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_utf8string<C: utf8string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, value.chars().count() as u64)?;
+        self.write_string(Charset::Utf8, value)
+    }
+
+    #[inline]
+    fn write_ia5string<C: ia5string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, value.chars().count() as u64)?;
+        self.write_string(Charset::Ia5, value)
+    }
+
+    #[inline]
+    fn write_numeric_string<C: numericstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, value.chars().count() as u64)?;
+        self.write_string(Charset::Numeric, value)
+    }
+
+    #[inline]
+    fn write_visible_string<C: visiblestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, value.chars().count() as u64)?;
+        self.write_string(Charset::Visible, value)
+    }
+
+    #[inline]
+    fn write_printable_string<C: printablestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, value.chars().count() as u64)?;
+        self.write_string(Charset::Printable, value)
+    }
+
+    #[inline]
+    fn write_bmp_string<C: bmpstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, value.chars().count() as u64)?;
+        self.write_string(Charset::Bmp, value)
+    }
+
+    #[inline]
+    fn write_teletex_string<C: teletexstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, value.chars().count() as u64)?;
+        self.write_string(Charset::Teletex, value)
+    }
+
+    #[inline]
+    fn write_octet_string<C: octetstring::Constraint>(
+        &mut self,
+        value: &[u8],
+    ) -> Result<(), Self::Error> {
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, value.len() as u64)?;
+        write_head(&mut self.buffer, MAJOR_BYTES, value.len() as u64);
+        self.buffer.extend_from_slice(value);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_containing<C: octetstring::Constraint, X: containing::Transform, T: WritableType>(
+        &mut self,
+        value: &T::Type,
+    ) -> Result<(), Self::Error> {
+        let mut writer = CborWriter {
+            depth: self.depth,
+            transforms: self.transforms.clone(),
+            ..CborWriter::default()
+        };
+        T::write_value(&mut writer, value)?;
+        let octets = self
+            .transforms
+            .encode::<X>(writer.into_bytes_vec())
+            .map_err(Error::content_transform_failed)?;
+        self.write_octet_string::<C>(&octets)
+    }
+
+    #[inline]
+    fn write_bit_string<C: bitstring::Constraint>(
+        &mut self,
+        value: &[u8],
+        bit_len: u64,
+    ) -> Result<(), Self::Error> {
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, bit_len)?;
+        let value = value
+            .get(..bit_len.div_ceil(8) as usize)
+            .ok_or(ErrorKind::SizeNotInRange(
+                bit_len,
+                0,
+                value.len() as u64 * 8,
+            ))?;
+        write_head(&mut self.buffer, MAJOR_ARRAY, 2);
+        write_head(&mut self.buffer, MAJOR_BYTES, value.len() as u64);
+        self.buffer.extend_from_slice(value);
+        write_head(&mut self.buffer, MAJOR_UNSIGNED, bit_len);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_boolean<C: boolean::Constraint>(&mut self, value: bool) -> Result<(), Self::Error> {
+        self.buffer.push(if value { TRUE } else { FALSE });
+        Ok(())
+    }
+
+    #[inline]
+    fn write_null<C: null::Constraint>(&mut self, _value: &Null) -> Result<(), Self::Error> {
+        self.buffer.push(NULL);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_real<T: real::Float, C: real::Constraint>(
+        &mut self,
+        value: T,
+    ) -> Result<(), Self::Error> {
+        write_float(&mut self.buffer, value.to_f64());
+        Ok(())
+    }
+
+    #[inline]
+    fn write_object_identifier<C: objectidentifier::Constraint>(
+        &mut self,
+        value: &Oid,
+    ) -> Result<(), Self::Error> {
+        let contents = crate::protocol::basic::oid::encode(value.as_slice())
+            .map_err(ErrorKind::InvalidObjectIdentifier)?;
+        write_head(&mut self.buffer, MAJOR_TAG, TAG_OID);
+        write_head(&mut self.buffer, MAJOR_BYTES, contents.len() as u64);
+        self.buffer.extend_from_slice(&contents);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_relative_oid<C: relativeoid::Constraint>(
+        &mut self,
+        value: &Oid,
+    ) -> Result<(), Self::Error> {
+        let contents = crate::protocol::basic::oid::encode_relative(value.as_slice())
+            .map_err(ErrorKind::InvalidObjectIdentifier)?;
+        write_head(&mut self.buffer, MAJOR_TAG, TAG_RELATIVE_OID);
+        write_head(&mut self.buffer, MAJOR_BYTES, contents.len() as u64);
+        self.buffer.extend_from_slice(&contents);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_oid_iri<C: oidiri::Constraint>(&mut self, value: &str) -> Result<(), Self::Error> {
+        crate::protocol::basic::oid::check_iri(value)
+            .map_err(ErrorKind::InvalidObjectIdentifier)?;
+        self.write_string(Charset::Utf8, value)
+    }
+}
+
+pub struct CborReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    /// The number of items left in each of the arrays and maps that are currently read
+    items: Vec<u64>,
+    recovery: numbers::Recovery,
+    depth: Depth,
+    transforms: containing::Transforms,
+}
+
+impl<'a> From<&'a [u8]> for CborReader<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            items: Vec::new(),
+            recovery: numbers::Recovery::default(),
+            depth: Depth::default(),
+            transforms: containing::Transforms::default(),
+        }
+    }
+}
+
+impl<'a> CborReader<'a> {
+    /// The position of the next byte to read
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    #[inline]
+    pub fn bytes_remaining(&self) -> usize {
+        self.bytes.len().saturating_sub(self.position)
+    }
+
+    #[inline]
+    fn nested<T, F: FnOnce(&mut Self) -> Result<T, Error>>(&mut self, f: F) -> Result<T, Error> {
+        self.depth
+            .enter()
+            .map_err(ErrorKind::NestingDepthExceedsLimit)?;
+        let result = f(self);
+        self.depth.leave();
+        result
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    /// Accounts for the next data item within the surrounding array or map
+    #[inline]
+    fn begin_item(&mut self) -> Result<(), Error> {
+        match self.items.last_mut() {
+            Some(0) => Err(ErrorKind::ItemsExhausted.into()),
+            Some(items) => {
+                *items -= 1;
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn read_head_of(&mut self, major: u8, expected: &'static str) -> Result<u64, Error> {
+        let initial = self.peek().ok_or(ErrorKind::EndOfStream)?;
+        let (actual, argument) = read_head(self.bytes, &mut self.position)?;
+        if actual == major {
+            Ok(argument)
+        } else {
+            self.position -= 1;
+            Err(Error::unexpected_item(expected, initial))
+        }
+    }
+
+    fn read_simple(&mut self, expected: &'static str) -> Result<u8, Error> {
+        let value = self.peek().ok_or(ErrorKind::EndOfStream)?;
+        match value {
+            FALSE | TRUE | NULL | UNDEFINED => {
+                self.position += 1;
+                Ok(value)
+            }
+            _ => Err(Error::unexpected_item(expected, value)),
+        }
+    }
+
+    fn take(&mut self, len: u64) -> Result<&'a [u8], Error> {
+        let end = usize::try_from(len)
+            .ok()
+            .and_then(|len| self.position.checked_add(len))
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(ErrorKind::EndOfStream)?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn read_string(&mut self, charset: Charset) -> Result<String, Error> {
+        self.begin_item()?;
+        let len = self.read_head_of(MAJOR_TEXT, "a text string")?;
+        let string = std::str::from_utf8(self.take(len)?)
+            .map_err(|_| ErrorKind::InvalidUtf8)?
+            .to_string();
+        Error::ensure_string_valid(charset, &string)?;
+        Ok(string)
+    }
+
+    /// Reads the byte string of a data item with the given tag, RFC 8949, 3.4
+    fn read_tagged_bytes(&mut self, tag: u64, expected: &'static str) -> Result<&'a [u8], Error> {
+        self.begin_item()?;
+        let initial = self.peek().ok_or(ErrorKind::EndOfStream)?;
+        if self.read_head_of(MAJOR_TAG, expected)? != tag {
+            return Err(Error::unexpected_item(expected, initial));
+        }
+        let len = self.read_head_of(MAJOR_BYTES, "a byte string")?;
+        self.take(len)
+    }
+
+    fn read_bytes(&mut self) -> Result<Vec<u8>, Error> {
+        self.begin_item()?;
+        let len = self.read_head_of(MAJOR_BYTES, "a byte string")?;
+        Ok(self.take(len)?.to_vec())
+    }
+
+    /// Skips the next data item, including all nested items
+    fn skip_item(&mut self) -> Result<(), Error> {
+        let mut pending = 1_u64;
+        while pending > 0 {
+            pending -= 1;
+            let (major, argument) = read_head(self.bytes, &mut self.position)?;
+            match major {
+                MAJOR_BYTES | MAJOR_TEXT => {
+                    self.take(argument)?;
+                }
+                MAJOR_ARRAY => pending = pending.saturating_add(argument),
+                MAJOR_MAP => pending = pending.saturating_add(argument.saturating_mul(2)),
+                MAJOR_TAG => pending += 1,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Calls `f` for the content of an array or map with the given number of items
+    fn within_items<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
+        &mut self,
+        items: u64,
+        f: F,
+    ) -> Result<T, Error> {
+        self.items.push(items);
+        let result = f(self);
+        let remaining = self.items.pop().unwrap_or_default();
+        let value = result?;
+        for _ in 0..remaining {
+            self.skip_item()?;
+        }
+        Ok(value)
+    }
+}
+
+impl containing::RegisterTransform for CborReader<'_> {
+    fn transforms_mut(&mut self) -> &mut containing::Transforms {
+        &mut self.transforms
+    }
+}
+
+impl DepthLimit for CborReader<'_> {
+    fn depth(&self) -> &Depth {
+        &self.depth
+    }
+
+    fn depth_mut(&mut self) -> &mut Depth {
+        &mut self.depth
+    }
+}
+
+impl numbers::DecodePolicyReader for CborReader<'_> {
+    fn recovery(&self) -> &numbers::Recovery {
+        &self.recovery
+    }
+
+    fn recovery_mut(&mut self) -> &mut numbers::Recovery {
+        &mut self.recovery
+    }
+}
+
+impl Reader for CborReader<'_> {
+    type Error = Error;
+
+    #[inline]
+    fn bits_read(&self) -> Option<usize> {
+        Some(self.position * 8)
+    }
+
+    #[inline]
+    fn read_sequence<
+        C: sequence::Constraint,
+        S: Sized,
+        F: Fn(&mut Self) -> Result<S, Self::Error>,
+    >(
+        &mut self,
+        f: F,
+    ) -> Result<S, Self::Error> {
+        self.begin_item()?;
+        self.nested(|r| {
+            let items = r.read_head_of(MAJOR_ARRAY, "an array")?;
+            if C::EXTENDED_AFTER_FIELD.is_none() && items != C::FIELD_COUNT {
+                return Err(ErrorKind::UnexpectedItemCount {
+                    expected: C::FIELD_COUNT,
+                    got: items,
+                }
+                .into());
+            }
+            r.within_items(items, f)
+        })
+    }
+
+    #[inline]
+    fn read_sequence_of<C: sequenceof::Constraint, T: ReadableType>(
+        &mut self,
+    ) -> Result<Vec<T::Type>, Self::Error> {
+        self.begin_item()?;
+        self.nested(|r| {
+            let items = r.read_head_of(MAJOR_ARRAY, "an array")?;
+            ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, items)?;
+            // every item needs at least one byte, so this does not trust the length on its own
+            let capacity = items.min(r.bytes_remaining() as u64) as usize;
+            r.within_items(items, |r| {
+                let mut vec = Vec::with_capacity(capacity);
+                for _ in 0..items {
+                    vec.push(T::read_value(r)?);
+                }
+                Ok(vec)
+            })
+        })
+    }
+
+    #[inline]
+    fn read_set<C: set::Constraint, S: Sized, F: Fn(&mut Self) -> Result<S, Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<S, Self::Error> {
+        self.read_sequence::<C, S, F>(f)
+    }
+
+    #[inline]
+    fn read_set_of<C: setof::Constraint, T: ReadableType>(
+        &mut self,
+    ) -> Result<Vec<<T as ReadableType>::Type>, Self::Error> {
+        self.read_sequence_of::<C, T>()
+    }
+
+    #[inline]
+    fn read_enumerated<C: enumerated::Constraint>(&mut self) -> Result<C, Self::Error> {
+        self.begin_item()?;
+        let index = self.read_head_of(MAJOR_UNSIGNED, "an enumeration index")?;
+        C::from_choice_index(index)
+            .ok_or_else(|| ErrorKind::InvalidChoiceIndex(index, C::VARIANT_COUNT).into())
+    }
+
+    #[inline]
+    fn read_choice<C: choice::Constraint>(&mut self) -> Result<C, Self::Error> {
+        self.begin_item()?;
+        self.nested(|r| {
+            let entries = r.read_head_of(MAJOR_MAP, "a map")?;
+            if entries != 1 {
+                return Err(ErrorKind::UnexpectedItemCount {
+                    expected: 1,
+                    got: entries,
+                }
+                .into());
+            }
+            let index = r.read_head_of(MAJOR_UNSIGNED, "a choice index")?;
+            r.within_items(1, |r| C::read_content(index, r))?
+                .ok_or_else(|| ErrorKind::InvalidChoiceIndex(index, C::VARIANT_COUNT).into())
+        })
+    }
+
+    #[inline]
+    fn read_opt<T: ReadableType>(
+        &mut self,
+    ) -> Result<Option<<T as ReadableType>::Type>, Self::Error> {
+        if self.items.last() == Some(&0) {
+            // the extension field is unknown to the writer
+            Ok(None)
+        } else if self.peek() == Some(UNDEFINED) {
+            self.begin_item()?;
+            self.position += 1;
+            Ok(None)
+        } else {
+            T::read_value(self).map(Some)
+        }
+    }
+
+    #[inline]
+    fn read_default<C: default::Constraint<Owned = T::Type>, T: ReadableType>(
+        &mut self,
+    ) -> Result<T::Type, Self::Error> {
+        Ok(self
+            .read_opt::<T>()?
+            .unwrap_or_else(|| C::default_value().to_owned()))
+    }
+
+    #[inline]
+    fn read_number<T: numbers::Number, C: numbers::Constraint<T>>(
+        &mut self,
+    ) -> Result<T, Self::Error> {
+        self.begin_item()?;
+        let initial = self.peek().ok_or(ErrorKind::EndOfStream)?;
+        let (major, argument) = read_head(self.bytes, &mut self.position)?;
+        let argument = i64::try_from(argument).map_err(|_| ErrorKind::ValueExceedsMaxInt)?;
+        let value = match major {
+            MAJOR_UNSIGNED => argument,
+            MAJOR_NEGATIVE => -1 - argument,
+            _ => return Err(Error::unexpected_item("an integer", initial)),
+        };
+        self.recovery
+            .apply::<T, C, _>(value, ensure_value_in_range::<T, C>(value))
+    }
+
+    #[inline]
+    fn read_utf8string<C: utf8string::Constraint>(&mut self) -> Result<String, Self::Error> {
+        let string = self.read_string(Charset::Utf8)?;
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, string.chars().count() as u64)?;
+        Ok(string)
+    }
+
+    #[inline]
+    fn read_ia5string<C: ia5string::Constraint>(&mut self) -> Result<String, Self::Error> {
+        let string = self.read_string(Charset::Ia5)?;
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, string.chars().count() as u64)?;
+        Ok(string)
+    }
+
+    #[inline]
+    fn read_numeric_string<C: numericstring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        let string = self.read_string(Charset::Numeric)?;
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, string.chars().count() as u64)?;
+        Ok(string)
+    }
+
+    #[inline]
+    fn read_visible_string<C: visiblestring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        let string = self.read_string(Charset::Visible)?;
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, string.chars().count() as u64)?;
+        Ok(string)
+    }
+
+    #[inline]
+    fn read_printable_string<C: printablestring::Constraint>(
+        &mut self,
+    ) -> Result<String, Self::Error> {
+        let string = self.read_string(Charset::Printable)?;
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, string.chars().count() as u64)?;
+        Ok(string)
+    }
+
+    #[inline]
+    fn read_bmp_string<C: bmpstring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        let string = self.read_string(Charset::Bmp)?;
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, string.chars().count() as u64)?;
+        Ok(string)
+    }
+
+    #[inline]
+    fn read_teletex_string<C: teletexstring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        let string = self.read_string(Charset::Teletex)?;
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, string.chars().count() as u64)?;
+        Ok(string)
+    }
+
+    #[inline]
+    fn read_octet_string<C: octetstring::Constraint>(&mut self) -> Result<Vec<u8>, Self::Error> {
+        let bytes = self.read_bytes()?;
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, bytes.len() as u64)?;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn read_containing<C: octetstring::Constraint, X: containing::Transform, T: ReadableType>(
+        &mut self,
+    ) -> Result<T::Type, Self::Error> {
+        let octets = self.read_octet_string::<C>()?;
+        let content = self
+            .transforms
+            .decode::<X>(octets)
+            .map_err(Error::content_transform_failed)?;
+        let mut reader = CborReader::from(&content[..]);
+        reader.depth = self.depth;
+        reader.transforms = self.transforms.clone();
+        T::read_value(&mut reader)
+    }
+
+    #[inline]
+    fn read_substituted<S, X: substituted::Substitute<S, T::Type>, T: ReadableType>(
+        &mut self,
+    ) -> Result<S, Self::Error> {
+        let value = T::read_value(self)?;
+        X::from_asn(value).map_err(Error::substitution_failed)
+    }
+
+    #[inline]
+    fn read_bit_string<C: bitstring::Constraint>(&mut self) -> Result<(Vec<u8>, u64), Self::Error> {
+        self.begin_item()?;
+        let items = self.read_head_of(MAJOR_ARRAY, "an array")?;
+        if items != 2 {
+            return Err(ErrorKind::UnexpectedItemCount {
+                expected: 2,
+                got: items,
+            }
+            .into());
+        }
+        let len = self.read_head_of(MAJOR_BYTES, "a byte string")?;
+        let bytes = self.take(len)?.to_vec();
+        let bit_len = self.read_head_of(MAJOR_UNSIGNED, "the number of bits")?;
+        if bit_len.div_ceil(8) != bytes.len() as u64 {
+            return Err(ErrorKind::SizeNotInRange(
+                bit_len,
+                (bytes.len() as u64 * 8).saturating_sub(7),
+                bytes.len() as u64 * 8,
+            )
+            .into());
+        }
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, bit_len)?;
+        Ok((bytes, bit_len))
+    }
+
+    #[inline]
+    fn read_boolean<C: boolean::Constraint>(&mut self) -> Result<bool, Self::Error> {
+        self.begin_item()?;
+        match self.read_simple("a boolean")? {
+            TRUE => Ok(true),
+            FALSE => Ok(false),
+            other => {
+                self.position -= 1;
+                Err(Error::unexpected_item("a boolean", other))
+            }
+        }
+    }
+
+    #[inline]
+    fn read_null<C: null::Constraint>(&mut self) -> Result<Null, Self::Error> {
+        self.begin_item()?;
+        match self.read_simple("null")? {
+            NULL => Ok(Null),
+            other => {
+                self.position -= 1;
+                Err(Error::unexpected_item("null", other))
+            }
+        }
+    }
+
+    #[inline]
+    fn read_real<T: real::Float, C: real::Constraint>(&mut self) -> Result<T, Self::Error> {
+        self.begin_item()?;
+        let start = self.position;
+        let initial = self.peek().ok_or(ErrorKind::EndOfStream)?;
+        let (_, argument) = read_head(self.bytes, &mut self.position)?;
+        let Some(value) = float_value(initial, argument) else {
+            self.position = start;
+            return Err(Error::unexpected_item("a floating point number", initial));
+        };
+        let mut shortest = Vec::with_capacity(9);
+        write_float(&mut shortest, value);
+        if shortest[..] != self.bytes[start..self.position] {
+            return Err(ErrorKind::NotDeterministic(initial).into());
+        }
+        Ok(T::from_f64(value))
+    }
+
+    #[inline]
+    fn read_object_identifier<C: objectidentifier::Constraint>(
+        &mut self,
+    ) -> Result<Oid, Self::Error> {
+        let contents = self.read_tagged_bytes(TAG_OID, "an object identifier (tag 111)")?;
+        crate::protocol::basic::oid::decode(contents)
+            .map(Oid::from)
+            .map_err(|reason| ErrorKind::InvalidObjectIdentifier(reason).into())
+    }
+
+    #[inline]
+    fn read_relative_oid<C: relativeoid::Constraint>(&mut self) -> Result<Oid, Self::Error> {
+        let contents =
+            self.read_tagged_bytes(TAG_RELATIVE_OID, "a relative object identifier (tag 110)")?;
+        crate::protocol::basic::oid::decode_relative(contents)
+            .map(Oid::from)
+            .map_err(|reason| ErrorKind::InvalidObjectIdentifier(reason).into())
+    }
+
+    #[inline]
+    fn read_oid_iri<C: oidiri::Constraint>(&mut self) -> Result<String, Self::Error> {
+        let iri = self.read_string(Charset::Utf8)?;
+        crate::protocol::basic::oid::check_iri(&iri).map_err(ErrorKind::InvalidObjectIdentifier)?;
+        Ok(iri)
+    }
+}
+
+fn ensure_size_in_range(
+    min: Option<u64>,
+    max: Option<u64>,
+    extensible: bool,
+    size: u64,
+) -> Result<(), Error> {
+    let min = min.unwrap_or(0);
+    let max = max.unwrap_or(u64::MAX);
+    if extensible || (min..=max).contains(&size) {
+        Ok(())
+    } else {
+        Err(ErrorKind::SizeNotInRange(size, min, max).into())
+    }
+}
+
+fn ensure_value_in_range<T: numbers::Number, C: numbers::Constraint<T>>(
+    value: i64,
+) -> Result<(), Error> {
+    let min = C::MIN.unwrap_or(i64::MIN);
+    let max = C::MAX.unwrap_or(i64::MAX);
+    if C::EXTENSIBLE || (min..=max).contains(&value) {
+        Ok(())
+    } else {
+        Err(ErrorKind::ValueNotInRange {
+            value,
+            min,
+            max,
+            definition: C::DEFINITION_NAME,
+            field: C::FIELD_NAME,
+        }
+        .into())
+    }
+}
@@ -0,0 +1,64 @@
+/// The nesting depth of `SEQUENCE`, `SET`, `SEQUENCE OF`, `SET OF` and `CHOICE` values that the
+/// writers and readers accept by default, see [`DepthLimit::set_max_depth`]
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// The writers and readers that limit the nesting depth of the values
+pub trait DepthLimit {
+    #[doc(hidden)]
+    fn depth(&self) -> &Depth;
+
+    #[doc(hidden)]
+    fn depth_mut(&mut self) -> &mut Depth;
+
+    #[inline]
+    fn max_depth(&self) -> usize {
+        self.depth().max
+    }
+
+    /// Limits the nesting depth of `SEQUENCE`, `SET`, `SEQUENCE OF`, `SET OF` and `CHOICE`
+    /// values (default: [`DEFAULT_MAX_DEPTH`]). Deeper nested values - such as of accidentally
+    /// self-referencing recursive types or of crafted input - fail with the
+    /// `NestingDepthExceedsLimit` error of the encoding rules instead of overflowing the stack.
+    #[inline]
+    fn set_max_depth(&mut self, max_depth: usize) {
+        self.depth_mut().max = max_depth;
+    }
+}
+
+/// The current and maximum nesting depth of a writer or reader, which fails instead of
+/// overflowing the stack on deeply nested recursive types or crafted input. This is an
+/// implementation detail of the [`DepthLimit`]s.
+#[doc(hidden)]
+#[derive(Debug, Copy, Clone)]
+pub struct Depth {
+    pub(crate) current: usize,
+    pub(crate) max: usize,
+}
+
+impl Default for Depth {
+    fn default() -> Self {
+        Self {
+            current: 0,
+            max: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+impl Depth {
+    /// Enters a nested value, fails with the maximum depth if it is reached already
+    #[inline]
+    pub(crate) fn enter(&mut self) -> Result<(), usize> {
+        if self.current >= self.max {
+            Err(self.max)
+        } else {
+            self.current += 1;
+            Ok(())
+        }
+    }
+
+    /// Leaves the nested value entered last
+    #[inline]
+    pub(crate) fn leave(&mut self) {
+        self.current -= 1;
+    }
+}
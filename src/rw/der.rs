@@ -31,28 +31,28 @@ impl<W: BasicWrite> Writer for BasicWriter<W> {
         &mut self,
         _f: F,
     ) -> Result<(), Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("write_sequence"))
     }
 
     fn write_sequence_of<C: crate::descriptor::sequenceof::Constraint, T: WritableType>(
         &mut self,
         _slice: &[T::Type],
     ) -> Result<(), Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("write_sequence_of"))
     }
 
     fn write_set<C: Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
         &mut self,
         _f: F,
     ) -> Result<(), Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("write_set"))
     }
 
     fn write_set_of<C: crate::descriptor::sequenceof::Constraint, T: WritableType>(
         &mut self,
         _slice: &[T::Type],
     ) -> Result<(), Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("write_set_of"))
     }
 
     #[inline]
@@ -80,11 +80,11 @@ impl<W: BasicWrite> Writer for BasicWriter<W> {
         &mut self,
         _choice: &C,
     ) -> Result<(), Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("write_choice"))
     }
 
     fn write_opt<T: WritableType>(&mut self, _value: Option<&T::Type>) -> Result<(), Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("write_opt"))
     }
 
     fn write_default<
@@ -94,7 +94,7 @@ impl<W: BasicWrite> Writer for BasicWriter<W> {
         &mut self,
         _value: &T::Type,
     ) -> Result<(), Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("write_default"))
     }
 
     fn write_number<T: Number, C: crate::descriptor::numbers::Constraint<T>>(
@@ -114,42 +114,67 @@ impl<W: BasicWrite> Writer for BasicWriter<W> {
         &mut self,
         _value: &str,
     ) -> Result<(), Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("write_utf8string"))
     }
 
     fn write_ia5string<C: crate::descriptor::ia5string::Constraint>(
         &mut self,
         _value: &str,
     ) -> Result<(), Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("write_ia5string"))
     }
 
     fn write_numeric_string<C: crate::descriptor::numericstring::Constraint>(
         &mut self,
         _value: &str,
     ) -> Result<(), Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("write_numeric_string"))
     }
 
     fn write_visible_string<C: crate::descriptor::visiblestring::Constraint>(
         &mut self,
         _value: &str,
     ) -> Result<(), Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("write_visible_string"))
     }
 
     fn write_printable_string<C: crate::descriptor::printablestring::Constraint>(
         &mut self,
         _value: &str,
     ) -> Result<(), Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("write_printable_string"))
+    }
+
+    fn write_bmp_string<C: crate::descriptor::bmpstring::Constraint>(
+        &mut self,
+        _value: &str,
+    ) -> Result<(), Self::Error> {
+        Err(Error::unsupported_operation("write_bmp_string"))
+    }
+
+    fn write_teletex_string<C: crate::descriptor::teletexstring::Constraint>(
+        &mut self,
+        _value: &str,
+    ) -> Result<(), Self::Error> {
+        Err(Error::unsupported_operation("write_teletex_string"))
     }
 
     fn write_octet_string<C: crate::descriptor::octetstring::Constraint>(
         &mut self,
         _value: &[u8],
     ) -> Result<(), Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("write_octet_string"))
+    }
+
+    fn write_containing<
+        C: crate::descriptor::octetstring::Constraint,
+        X: crate::descriptor::containing::Transform,
+        T: WritableType,
+    >(
+        &mut self,
+        _value: &T::Type,
+    ) -> Result<(), Self::Error> {
+        Err(Error::unsupported_operation("write_containing"))
     }
 
     fn write_bit_string<C: crate::descriptor::bitstring::Constraint>(
@@ -157,7 +182,7 @@ impl<W: BasicWrite> Writer for BasicWriter<W> {
         _value: &[u8],
         _bit_len: u64,
     ) -> Result<(), Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("write_bit_string"))
     }
 
     fn write_boolean<C: crate::descriptor::boolean::Constraint>(
@@ -174,7 +199,35 @@ impl<W: BasicWrite> Writer for BasicWriter<W> {
         &mut self,
         _value: &Null,
     ) -> Result<(), Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("write_null"))
+    }
+
+    fn write_real<T: crate::descriptor::real::Float, C: crate::descriptor::real::Constraint>(
+        &mut self,
+        _value: T,
+    ) -> Result<(), Self::Error> {
+        Err(Error::unsupported_operation("write_real"))
+    }
+
+    fn write_object_identifier<C: crate::descriptor::objectidentifier::Constraint>(
+        &mut self,
+        _value: &crate::descriptor::Oid,
+    ) -> Result<(), Self::Error> {
+        Err(Error::unsupported_operation("write_object_identifier"))
+    }
+
+    fn write_relative_oid<C: crate::descriptor::relativeoid::Constraint>(
+        &mut self,
+        _value: &crate::descriptor::Oid,
+    ) -> Result<(), Self::Error> {
+        Err(Error::unsupported_operation("write_relative_oid"))
+    }
+
+    fn write_oid_iri<C: crate::descriptor::oidiri::Constraint>(
+        &mut self,
+        _value: &str,
+    ) -> Result<(), Self::Error> {
+        Err(Error::unsupported_operation("write_oid_iri"))
     }
 }
 
@@ -203,26 +256,26 @@ impl<R: BasicRead> Reader for BasicReader<R> {
         &mut self,
         _f: F,
     ) -> Result<S, Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("read_sequence"))
     }
 
     fn read_sequence_of<C: crate::descriptor::sequenceof::Constraint, T: ReadableType>(
         &mut self,
     ) -> Result<Vec<T::Type>, Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("read_sequence_of"))
     }
 
     fn read_set<C: Constraint, S: Sized, F: Fn(&mut Self) -> Result<S, Self::Error>>(
         &mut self,
         _f: F,
     ) -> Result<S, Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("read_set"))
     }
 
     fn read_set_of<C: crate::descriptor::sequenceof::Constraint, T: ReadableType>(
         &mut self,
     ) -> Result<Vec<T::Type>, Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("read_set_of"))
     }
 
     #[inline]
@@ -246,17 +299,17 @@ impl<R: BasicRead> Reader for BasicReader<R> {
     }
 
     fn read_choice<C: crate::descriptor::choice::Constraint>(&mut self) -> Result<C, Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("read_choice"))
     }
 
     fn read_opt<T: ReadableType>(&mut self) -> Result<Option<T::Type>, Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("read_opt"))
     }
 
     fn read_default<C: crate::descriptor::default::Constraint<Owned = T::Type>, T: ReadableType>(
         &mut self,
     ) -> Result<T::Type, Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("read_default"))
     }
 
     fn read_number<T: Number, C: crate::descriptor::numbers::Constraint<T>>(
@@ -273,43 +326,75 @@ impl<R: BasicRead> Reader for BasicReader<R> {
     fn read_utf8string<C: crate::descriptor::utf8string::Constraint>(
         &mut self,
     ) -> Result<String, Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("read_utf8string"))
     }
 
     fn read_ia5string<C: crate::descriptor::ia5string::Constraint>(
         &mut self,
     ) -> Result<String, Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("read_ia5string"))
     }
 
     fn read_numeric_string<C: crate::descriptor::numericstring::Constraint>(
         &mut self,
     ) -> Result<String, Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("read_numeric_string"))
     }
 
     fn read_visible_string<C: crate::descriptor::visiblestring::Constraint>(
         &mut self,
     ) -> Result<String, Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("read_visible_string"))
     }
 
     fn read_printable_string<C: crate::descriptor::printablestring::Constraint>(
         &mut self,
     ) -> Result<String, Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("read_printable_string"))
+    }
+
+    fn read_bmp_string<C: crate::descriptor::bmpstring::Constraint>(
+        &mut self,
+    ) -> Result<String, Self::Error> {
+        Err(Error::unsupported_operation("read_bmp_string"))
+    }
+
+    fn read_teletex_string<C: crate::descriptor::teletexstring::Constraint>(
+        &mut self,
+    ) -> Result<String, Self::Error> {
+        Err(Error::unsupported_operation("read_teletex_string"))
     }
 
     fn read_octet_string<C: crate::descriptor::octetstring::Constraint>(
         &mut self,
     ) -> Result<Vec<u8>, Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("read_octet_string"))
+    }
+
+    fn read_containing<
+        C: crate::descriptor::octetstring::Constraint,
+        X: crate::descriptor::containing::Transform,
+        T: ReadableType,
+    >(
+        &mut self,
+    ) -> Result<T::Type, Self::Error> {
+        Err(Error::unsupported_operation("read_containing"))
+    }
+
+    fn read_substituted<
+        S,
+        X: crate::descriptor::substituted::Substitute<S, T::Type>,
+        T: ReadableType,
+    >(
+        &mut self,
+    ) -> Result<S, Self::Error> {
+        X::from_asn(T::read_value(self)?).map_err(Error::substitution_failed)
     }
 
     fn read_bit_string<C: crate::descriptor::bitstring::Constraint>(
         &mut self,
     ) -> Result<(Vec<u8>, u64), Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("read_bit_string"))
     }
 
     fn read_boolean<C: crate::descriptor::boolean::Constraint>(
@@ -328,6 +413,30 @@ impl<R: BasicRead> Reader for BasicReader<R> {
     }
 
     fn read_null<C: crate::descriptor::null::Constraint>(&mut self) -> Result<Null, Self::Error> {
-        todo!()
+        Err(Error::unsupported_operation("read_null"))
+    }
+
+    fn read_real<T: crate::descriptor::real::Float, C: crate::descriptor::real::Constraint>(
+        &mut self,
+    ) -> Result<T, Self::Error> {
+        Err(Error::unsupported_operation("read_real"))
+    }
+
+    fn read_object_identifier<C: crate::descriptor::objectidentifier::Constraint>(
+        &mut self,
+    ) -> Result<crate::descriptor::Oid, Self::Error> {
+        Err(Error::unsupported_operation("read_object_identifier"))
+    }
+
+    fn read_relative_oid<C: crate::descriptor::relativeoid::Constraint>(
+        &mut self,
+    ) -> Result<crate::descriptor::Oid, Self::Error> {
+        Err(Error::unsupported_operation("read_relative_oid"))
+    }
+
+    fn read_oid_iri<C: crate::descriptor::oidiri::Constraint>(
+        &mut self,
+    ) -> Result<String, Self::Error> {
+        Err(Error::unsupported_operation("read_oid_iri"))
     }
 }
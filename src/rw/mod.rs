@@ -1,15 +1,43 @@
+mod ber;
+#[cfg(feature = "cbor")]
+mod cbor;
+mod depth;
 mod der;
+mod mutation;
+#[cfg(feature = "oer")]
+mod oer;
+mod position;
 mod println;
 #[cfg(feature = "protobuf")]
+mod proto_json;
+#[cfg(feature = "protobuf")]
 mod proto_read;
 #[cfg(feature = "protobuf")]
 mod proto_write;
+mod size_hint;
 mod uper;
+#[cfg(feature = "serde")]
+mod uper_serde;
+mod uper_stream;
 
+pub use ber::*;
+#[cfg(feature = "cbor")]
+pub use cbor::*;
+pub use depth::{DepthLimit, DEFAULT_MAX_DEPTH};
 pub use der::*;
+pub use mutation::*;
+#[cfg(feature = "oer")]
+pub use oer::*;
+pub use position::*;
 pub use println::*;
 #[cfg(feature = "protobuf")]
+pub use proto_json::*;
+#[cfg(feature = "protobuf")]
 pub use proto_read::*;
 #[cfg(feature = "protobuf")]
 pub use proto_write::*;
+pub use size_hint::*;
 pub use uper::*;
+#[cfg(feature = "serde")]
+pub use uper_serde::*;
+pub use uper_stream::*;
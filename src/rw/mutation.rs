@@ -0,0 +1,269 @@
+//! Schema aware mutations of valid UPER encodings for negative testing.
+//!
+//! A valid value is encoded with a [`UperWriter::with_landmarks`], which remembers where
+//! extension bits and length determinants were written. The [`UperMutator`] then derives
+//! broken encodings from these positions - instead of flipping random bits that would most likely
+//! only hit some payload - and labels each of them with the [`Mutation`] that was applied.
+//!
+//! ```rust
+//! use asn1rs::prelude::*;
+//!
+//! asn_to_rust!(
+//!     r"Mutation DEFINITIONS AUTOMATIC TAGS ::=
+//!     BEGIN
+//!
+//!     Message ::= SEQUENCE {
+//!         id      INTEGER (0..255),
+//!         payload OCTET STRING,
+//!         ...
+//!     }
+//!
+//!     END"
+//! );
+//!
+//! let message = Message {
+//!     id: 42,
+//!     payload: vec![1, 2, 3],
+//! };
+//!
+//! let mutator = UperMutator::new(&message).unwrap();
+//! for mutant in mutator.corpus() {
+//!     // feed mutant.bytes to the receiver under test, labeled as mutant.mutation
+//!     println!("{}: {:02x?}", mutant.mutation, mutant.bytes);
+//! }
+//!
+//! assert!(mutator
+//!     .mutations()
+//!     .contains(&Mutation::OverflowLengthDeterminant {
+//!         bits: 9..17,
+//!         constrained: false,
+//!     }));
+//! ```
+
+use crate::descriptor::{Writable, Writer};
+use crate::protocol::per::err::Error;
+use crate::protocol::per::unaligned::BYTE_LEN;
+use crate::rw::UperWriter;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+/// A position of interest within an UPER encoding, recorded by [`UperWriter::with_landmarks`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Landmark {
+    /// The bit that tells whether the value of an extensible type is (or has) an extension
+    ExtensionBit(usize),
+    /// The bits of a length determinant, `constrained` length determinants are encoded as plain
+    /// binary integer, others start with the prefix of the unconstrained form
+    LengthDeterminant {
+        bits: Range<usize>,
+        constrained: bool,
+    },
+}
+
+impl Landmark {
+    /// The position of the first bit of this landmark
+    pub fn start(&self) -> usize {
+        match self {
+            Landmark::ExtensionBit(bit) => *bit,
+            Landmark::LengthDeterminant { bits, .. } => bits.start,
+        }
+    }
+
+    pub(crate) fn offset(self, offset: usize) -> Self {
+        match self {
+            Landmark::ExtensionBit(bit) => Landmark::ExtensionBit(bit + offset),
+            Landmark::LengthDeterminant { bits, constrained } => Landmark::LengthDeterminant {
+                bits: bits.start + offset..bits.end + offset,
+                constrained,
+            },
+        }
+    }
+}
+
+/// A schema aware modification of a valid encoding
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Mutation {
+    /// Inverts the extension bit at the given position
+    FlipExtensionBit(usize),
+    /// Sets the length determinant at the given bits to the largest value of its form, so it
+    /// claims more content than actually follows
+    OverflowLengthDeterminant {
+        bits: Range<usize>,
+        constrained: bool,
+    },
+    /// Cuts the encoding after the given number of bits
+    Truncate(usize),
+}
+
+impl Mutation {
+    /// Short label of this kind of mutation, suitable for file names of a corpus
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Mutation::FlipExtensionBit(_) => "flip-extension-bit",
+            Mutation::OverflowLengthDeterminant { .. } => "overflow-length-determinant",
+            Mutation::Truncate(_) => "truncate",
+        }
+    }
+
+    /// Applies this mutation to the given encoding, returning the new encoding and its bit-length.
+    /// A `bit_len` beyond the end of `bytes` is limited to the length of `bytes`.
+    pub fn apply(&self, bytes: &[u8], bit_len: usize) -> (Vec<u8>, usize) {
+        let bit_len = bit_len.min(bytes.len() * BYTE_LEN);
+        let mut bytes = bytes.to_vec();
+        match self {
+            Mutation::FlipExtensionBit(bit) => {
+                if *bit < bit_len {
+                    bytes[bit / BYTE_LEN] ^= 0x80 >> (bit % BYTE_LEN);
+                }
+                (bytes, bit_len)
+            }
+            Mutation::OverflowLengthDeterminant { bits, constrained } => {
+                // keep the prefix of the unconstrained form: 0xxxxxxx or 10xxxxxx xxxxxxxx
+                let prefix = match (constrained, bits.len()) {
+                    (false, 8) => 1,
+                    (false, 16) => 2,
+                    _ => 0,
+                };
+                for bit in (bits.start + prefix..bits.end).filter(|bit| *bit < bit_len) {
+                    bytes[bit / BYTE_LEN] |= 0x80 >> (bit % BYTE_LEN);
+                }
+                (bytes, bit_len)
+            }
+            Mutation::Truncate(len) => {
+                let len = (*len).min(bit_len);
+                bytes.truncate(len.div_ceil(BYTE_LEN));
+                if len % BYTE_LEN != 0 {
+                    if let Some(last) = bytes.last_mut() {
+                        *last &= 0xFF << (BYTE_LEN - len % BYTE_LEN);
+                    }
+                }
+                (bytes, len)
+            }
+        }
+    }
+}
+
+impl Display for Mutation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mutation::FlipExtensionBit(bit) => write!(f, "{}@{}", self.label(), bit),
+            Mutation::OverflowLengthDeterminant { bits, .. } => {
+                write!(f, "{}@{}..{}", self.label(), bits.start, bits.end)
+            }
+            Mutation::Truncate(len) => write!(f, "{}@{}", self.label(), len),
+        }
+    }
+}
+
+/// A mutated encoding, labeled with the [`Mutation`] it was derived with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mutant {
+    pub mutation: Mutation,
+    pub bytes: Vec<u8>,
+    pub bit_len: usize,
+}
+
+/// Derives negative test cases from the UPER encoding of a valid value
+#[derive(Debug, Clone)]
+pub struct UperMutator {
+    bytes: Vec<u8>,
+    bit_len: usize,
+    landmarks: Vec<Landmark>,
+}
+
+impl UperMutator {
+    pub fn new<T: Writable>(value: &T) -> Result<Self, Error> {
+        let mut writer = UperWriter::with_landmarks();
+        writer.write(value)?;
+        let mut landmarks = writer.landmarks().to_vec();
+        landmarks.sort_by_key(Landmark::start);
+        Ok(Self {
+            bit_len: writer.bit_len(),
+            bytes: writer.into_bytes_vec(),
+            landmarks,
+        })
+    }
+
+    /// The valid encoding the mutations are applied to
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn bit_len(&self) -> usize {
+        self.bit_len
+    }
+
+    pub fn landmarks(&self) -> &[Landmark] {
+        &self.landmarks
+    }
+
+    /// All mutations for the recorded landmarks: every extension bit is flipped, every length
+    /// determinant overflown and the encoding is truncated before each landmark and at every
+    /// byte boundary.
+    pub fn mutations(&self) -> Vec<Mutation> {
+        let mut mutations = Vec::new();
+        let mut truncations = (0..self.bit_len)
+            .step_by(BYTE_LEN)
+            .chain(self.landmarks.iter().map(Landmark::start))
+            .collect::<Vec<_>>();
+        truncations.sort_unstable();
+        truncations.dedup();
+
+        for landmark in &self.landmarks {
+            mutations.push(match landmark {
+                Landmark::ExtensionBit(bit) => Mutation::FlipExtensionBit(*bit),
+                Landmark::LengthDeterminant { bits, constrained } => {
+                    Mutation::OverflowLengthDeterminant {
+                        bits: bits.clone(),
+                        constrained: *constrained,
+                    }
+                }
+            });
+        }
+
+        mutations.extend(truncations.into_iter().map(Mutation::Truncate));
+        mutations
+    }
+
+    pub fn mutate(&self, mutation: Mutation) -> Mutant {
+        let (bytes, bit_len) = mutation.apply(&self.bytes, self.bit_len);
+        Mutant {
+            mutation,
+            bytes,
+            bit_len,
+        }
+    }
+
+    /// The [`Mutant`] for each of [`UperMutator::mutations`], mutants that equal the valid
+    /// encoding are skipped
+    pub fn corpus(&self) -> impl Iterator<Item = Mutant> + '_ {
+        self.mutations()
+            .into_iter()
+            .map(|mutation| self.mutate(mutation))
+            .filter(|mutant| mutant.bit_len != self.bit_len || mutant.bytes != self.bytes)
+    }
+
+    /// Randomly picks `count` mutations - truncations at arbitrary bit positions included - in a
+    /// reproducible manner for the given `seed`
+    pub fn random(&self, seed: u64, count: usize) -> Vec<Mutant> {
+        let mutations = self.mutations();
+        let mut state = seed | 1;
+        let mut next = move || {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize
+        };
+
+        (0..count)
+            .map(|_| {
+                let index = next() % (mutations.len() + 1);
+                match mutations.get(index) {
+                    Some(mutation) => self.mutate(mutation.clone()),
+                    None => self.mutate(Mutation::Truncate(next() % self.bit_len.max(1))),
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,1249 @@
+//! Canonical Octet Encoding Rules (COER, ITU-T X.696) representation of the generated types.
+//!
+//! [`CoerWriter`] only writes the canonical encoding, [`CoerReader`] also accepts encodings that
+//! are valid OER but not canonical, such as lengths that are not in their shortest form or
+//! `TRUE` values other than `0xFF`. The types are mapped as follows:
+//!  - `SEQUENCE` and `SET` to a preamble with the extension bit and one presence bit per
+//!    `OPTIONAL` or `DEFAULT` root component, followed by the present root components. Present
+//!    extension additions follow as open types after a presence bitmap. The components of a
+//!    `SET` are written in the order of their definition, which is the canonical order of their
+//!    tags with `AUTOMATIC TAGS`.
+//!  - `SEQUENCE OF` and `SET OF` to the number of elements as quantity, followed by the elements
+//!  - `CHOICE` to the tag of the alternative followed by its value, extension alternatives as
+//!    open type
+//!  - `ENUMERATED` to the index of the variant
+//!  - `INTEGER` to 1, 2, 4 or 8 octets if the constraint allows it, otherwise to a length
+//!    determinant followed by the shortest form of the value
+//!  - character strings and `OCTET STRING` to their octets, preceded by a length determinant
+//!    unless they have a fixed size (and a known-multiplier character set)
+//!  - `BIT STRING` to its octets, preceded by a length determinant and the number of unused bits
+//!    unless it has a fixed size
+//!  - `BOOLEAN` to `0xFF` or `0x00` and `NULL` to nothing
+//!  - `REAL` to the four octets of an IEEE 754 binary32 if it is represented as `f32`, otherwise
+//!    to a length determinant followed by the contents octets of its basic encoding
+
+use crate::descriptor::*;
+use crate::protocol::oer::{
+    read_enumerated, read_integer, read_length, read_quantity, read_tag, take, write_enumerated,
+    write_integer, write_length, write_quantity, write_tag, Error, ErrorKind, IntegerWidth,
+};
+use crate::rw::depth::{Depth, DepthLimit};
+use asn1rs_model::asn::{Charset, Tag};
+
+const TRUE: u8 = 0xFF;
+const FALSE: u8 = 0x00;
+
+/// The `SEQUENCE` or `SET` that is currently written
+#[derive(Debug, Clone)]
+struct WriteScope {
+    /// The position of the first octet of the preamble
+    preamble: usize,
+    /// The index of the next presence bit within the preamble
+    bit: usize,
+    /// The number of root components that are not written yet, `None` if not extensible
+    root_fields: Option<u64>,
+    /// Whether each of the written extension additions is present and where its encoding starts
+    additions: Vec<(bool, usize)>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct CoerWriter {
+    buffer: Vec<u8>,
+    scope: Option<WriteScope>,
+    /// Whether the next value is the alternative of a `CHOICE` and is preceded by its tag
+    choice_tag: bool,
+    depth: Depth,
+    transforms: containing::Transforms,
+}
+
+impl CoerWriter {
+    pub fn with_capacity(capacity_bytes: usize) -> Self {
+        Self {
+            buffer: Vec::with_capacity(capacity_bytes),
+            ..Self::default()
+        }
+    }
+
+    pub fn byte_content(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    pub fn into_bytes_vec(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    /// Accounts for the next component of the surrounding `SEQUENCE` or `SET`
+    fn begin_field(&mut self, optional: bool, present: bool) {
+        if let Some(scope) = &mut self.scope {
+            match &mut scope.root_fields {
+                Some(0) => scope.additions.push((present, self.buffer.len())),
+                root_fields => {
+                    if let Some(remaining) = root_fields {
+                        *remaining -= 1;
+                    }
+                    if optional {
+                        if present {
+                            if let Some(octet) = self.buffer.get_mut(scope.preamble + scope.bit / 8)
+                            {
+                                *octet |= 0x80 >> (scope.bit % 8);
+                            }
+                        }
+                        scope.bit += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Accounts for the next mandatory value and writes its tag if it is a `CHOICE` alternative
+    fn begin_value(&mut self, tag: Tag) {
+        self.begin_field(false, true);
+        if core::mem::take(&mut self.choice_tag) {
+            write_tag(&mut self.buffer, tag);
+        }
+    }
+
+    fn scope_stashed<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<T, Error> {
+        let scope = self.scope.take();
+        let result = f(self);
+        self.scope = scope;
+        result
+    }
+
+    #[inline]
+    fn nested<F: FnOnce(&mut Self) -> Result<(), Error>>(&mut self, f: F) -> Result<(), Error> {
+        self.depth
+            .enter()
+            .map_err(ErrorKind::NestingDepthExceedsLimit)?;
+        let result = f(self);
+        self.depth.leave();
+        result
+    }
+
+    fn write_sequence_content<C: sequence::Constraint, F: Fn(&mut Self) -> Result<(), Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Error> {
+        let extensible = C::EXTENDED_AFTER_FIELD.is_some();
+        let bits = usize::from(extensible) + C::STD_OPTIONAL_FIELDS as usize;
+        let preamble = self.buffer.len();
+        self.buffer.resize(preamble + bits.div_ceil(8), 0);
+
+        let outer = self.scope.replace(WriteScope {
+            preamble,
+            bit: usize::from(extensible),
+            root_fields: C::EXTENDED_AFTER_FIELD.map(|after| after + 1),
+            additions: Vec::new(),
+        });
+        let result = f(self);
+        let scope = core::mem::replace(&mut self.scope, outer);
+        result?;
+
+        if let Some(scope) = scope {
+            self.write_additions(scope);
+        }
+        Ok(())
+    }
+
+    /// Moves the encodings of the present extension additions into open types, preceded by the
+    /// extension presence bitmap (ITU-T X.696, chapter 16.4)
+    fn write_additions(&mut self, scope: WriteScope) {
+        let Some(start) = scope.additions.first().map(|(_, start)| *start) else {
+            return;
+        };
+        if !scope.additions.iter().any(|(present, _)| *present) {
+            return;
+        }
+
+        let content = self.buffer.split_off(start);
+        if let Some(octet) = self.buffer.get_mut(scope.preamble) {
+            *octet |= 0x80;
+        }
+
+        let count = scope.additions.len();
+        write_length(&mut self.buffer, 1 + count.div_ceil(8) as u64);
+        self.buffer.push(((8 - count % 8) % 8) as u8);
+        let bitmap = self.buffer.len();
+        self.buffer.resize(bitmap + count.div_ceil(8), 0);
+
+        for (index, (present, from)) in scope.additions.iter().enumerate() {
+            if *present {
+                if let Some(octet) = self.buffer.get_mut(bitmap + index / 8) {
+                    *octet |= 0x80 >> (index % 8);
+                }
+                let to = scope
+                    .additions
+                    .get(index + 1)
+                    .map(|(_, to)| *to)
+                    .unwrap_or(start + content.len());
+                let encoding = &content[from - start..to - start];
+                write_length(&mut self.buffer, encoding.len() as u64);
+                self.buffer.extend_from_slice(encoding);
+            }
+        }
+    }
+
+    fn write_string(
+        &mut self,
+        tag: Tag,
+        charset: Charset,
+        (min, max, extensible): (Option<u64>, Option<u64>, bool),
+        value: &str,
+    ) -> Result<(), Error> {
+        self.begin_value(tag);
+        ensure_size_in_range(min, max, extensible, value.chars().count() as u64)?;
+        Error::ensure_string_valid(charset, value)?;
+        let octets = charset.encode(value);
+        // UTF8String is not a known-multiplier character string type (X.696, 27.1)
+        if charset.octets_per_char().is_none() || fixed_size(min, max, extensible).is_none() {
+            write_length(&mut self.buffer, octets.len() as u64);
+        }
+        self.buffer.extend_from_slice(&octets);
+        Ok(())
+    }
+}
+
+impl containing::RegisterTransform for CoerWriter {
+    fn transforms_mut(&mut self) -> &mut containing::Transforms {
+        &mut self.transforms
+    }
+}
+
+impl DepthLimit for CoerWriter {
+    fn depth(&self) -> &Depth {
+        &self.depth
+    }
+
+    fn depth_mut(&mut self) -> &mut Depth {
+        &mut self.depth
+    }
+}
+
+impl Writer for CoerWriter {
+    type Error = Error;
+
+    #[inline]
+    fn bits_written(&self) -> Option<usize> {
+        Some(self.buffer.len() * 8)
+    }
+
+    #[inline]
+    fn write_sequence<C: sequence::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        self.nested(|w| {
+            w.begin_value(C::TAG);
+            w.write_sequence_content::<C, F>(f)
+        })
+    }
+
+    #[inline]
+    fn write_sequence_of<C: sequenceof::Constraint, T: WritableType>(
+        &mut self,
+        slice: &[T::Type],
+    ) -> Result<(), Self::Error> {
+        self.nested(|w| {
+            w.begin_value(C::TAG);
+            ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, slice.len() as u64)?;
+            write_quantity(&mut w.buffer, slice.len() as u64);
+            w.scope_stashed(|w| slice.iter().try_for_each(|value| T::write_value(w, value)))
+        })
+    }
+
+    #[inline]
+    fn write_set<C: set::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        self.nested(|w| {
+            w.begin_value(C::TAG);
+            w.write_sequence_content::<C, F>(f)
+        })
+    }
+
+    #[inline]
+    fn write_set_of<C: setof::Constraint, T: WritableType>(
+        &mut self,
+        slice: &[T::Type],
+    ) -> Result<(), Self::Error> {
+        self.write_sequence_of::<C, T>(slice)
+    }
+
+    #[inline]
+    fn write_enumerated<C: enumerated::Constraint>(
+        &mut self,
+        enumerated: &C,
+    ) -> Result<(), Self::Error> {
+        self.begin_value(C::TAG);
+        let index = i64::try_from(enumerated.to_choice_index())
+            .map_err(|_| ErrorKind::ValueExceedsMaxInt)?;
+        write_enumerated(&mut self.buffer, index);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_choice<C: choice::Constraint>(&mut self, choice: &C) -> Result<(), Self::Error> {
+        // the tag of an untagged CHOICE is the tag of its alternative, so a pending tag is
+        // written by the alternative too
+        self.begin_field(false, true);
+        let index = choice.to_choice_index();
+        self.nested(|w| {
+            w.scope_stashed(|w| {
+                let start = w.buffer.len();
+                w.choice_tag = true;
+                let result = choice.write_content(w);
+                w.choice_tag = false;
+                result?;
+
+                if index >= C::STD_VARIANT_COUNT {
+                    // extension alternatives are open types (X.696, 23.3)
+                    let mut position = start;
+                    read_tag(&w.buffer, &mut position)?;
+                    let content = w.buffer.split_off(position);
+                    write_length(&mut w.buffer, content.len() as u64);
+                    w.buffer.extend_from_slice(&content);
+                }
+                Ok(())
+            })
+        })
+    }
+
+    #[inline]
+    fn write_opt<T: WritableType>(
+        &mut self,
+        value: Option<&<T as WritableType>::Type>,
+    ) -> Result<(), Self::Error> {
+        self.begin_field(true, value.is_some());
+        match value {
+            Some(value) => self.scope_stashed(|w| T::write_value(w, value)),
+            None => Ok(()),
+        }
+    }
+
+    #[inline]
+    fn write_default<C: default::Constraint<Owned = T::Type>, T: WritableType>(
+        &mut self,
+        value: &T::Type,
+    ) -> Result<(), Self::Error> {
+        let present = C::default_value().ne(value);
+        self.begin_field(true, present);
+        if present {
+            self.scope_stashed(|w| T::write_value(w, value))
+        } else {
+            Ok(())
+        }
+    }
+
+    #[inline]
+    fn write_number<T: numbers::Number, C: numbers::Constraint<T>>(
+        &mut self,
+        value: T,
+    ) -> Result<(), Self::Error> {
+        self.begin_value(C::TAG);
+        let value = value.to_i64();
+        ensure_value_in_range::<T, C>(value)?;
+        write_integer(
+            &mut self.buffer,
+            IntegerWidth::of(C::MIN, C::MAX, C::EXTENSIBLE),
+            value,
+        );
+        Ok(())
+    }
+
+    #[inline]
+    fn write_utf8string<C: utf8string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_string(
+            C::TAG,
+            Charset::Utf8,
+            (C::MIN, C::MAX, C::EXTENSIBLE),
+            value,
+        )
+    }
+
+    #[inline]
+    fn write_ia5string<C: ia5string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_string(C::TAG, Charset::Ia5, (C::MIN, C::MAX, C::EXTENSIBLE), value)
+    }
+
+    #[inline]
+    fn write_numeric_string<C: numericstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_string(
+            C::TAG,
+            Charset::Numeric,
+            (C::MIN, C::MAX, C::EXTENSIBLE),
+            value,
+        )
+    }
+
+    #[inline]
+    fn write_visible_string<C: visiblestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_string(
+            C::TAG,
+            Charset::Visible,
+            (C::MIN, C::MAX, C::EXTENSIBLE),
+            value,
+        )
+    }
+
+    #[inline]
+    fn write_printable_string<C: printablestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_string(
+            C::TAG,
+            Charset::Printable,
+            (C::MIN, C::MAX, C::EXTENSIBLE),
+            value,
+        )
+    }
+
+    #[inline]
+    fn write_bmp_string<C: bmpstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_string(C::TAG, Charset::Bmp, (C::MIN, C::MAX, C::EXTENSIBLE), value)
+    }
+
+    #[inline]
+    fn write_teletex_string<C: teletexstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_string(
+            C::TAG,
+            Charset::Teletex,
+            (C::MIN, C::MAX, C::EXTENSIBLE),
+            value,
+        )
+    }
+
+    #[inline]
+    fn write_octet_string<C: octetstring::Constraint>(
+        &mut self,
+        value: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.begin_value(C::TAG);
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, value.len() as u64)?;
+        if fixed_size(C::MIN, C::MAX, C::EXTENSIBLE).is_none() {
+            write_length(&mut self.buffer, value.len() as u64);
+        }
+        self.buffer.extend_from_slice(value);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_containing<C: octetstring::Constraint, X: containing::Transform, T: WritableType>(
+        &mut self,
+        value: &T::Type,
+    ) -> Result<(), Self::Error> {
+        let mut writer = CoerWriter {
+            depth: self.depth,
+            transforms: self.transforms.clone(),
+            ..CoerWriter::default()
+        };
+        T::write_value(&mut writer, value)?;
+        let octets = self
+            .transforms
+            .encode::<X>(writer.into_bytes_vec())
+            .map_err(Error::content_transform_failed)?;
+        self.write_octet_string::<C>(&octets)
+    }
+
+    #[inline]
+    fn write_bit_string<C: bitstring::Constraint>(
+        &mut self,
+        value: &[u8],
+        bit_len: u64,
+    ) -> Result<(), Self::Error> {
+        self.begin_value(C::TAG);
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, bit_len)?;
+        let value = value
+            .get(..bit_len.div_ceil(8) as usize)
+            .ok_or(ErrorKind::SizeNotInRange(
+                bit_len,
+                0,
+                value.len() as u64 * 8,
+            ))?;
+        let unused = ((8 - bit_len % 8) % 8) as u8;
+        if fixed_size(C::MIN, C::MAX, C::EXTENSIBLE).is_none() {
+            write_length(&mut self.buffer, 1 + value.len() as u64);
+            self.buffer.push(unused);
+        }
+        self.buffer.extend_from_slice(value);
+        // the unused bits are zero in the canonical encoding
+        if let Some(last) = self.buffer.last_mut().filter(|_| !value.is_empty()) {
+            *last &= 0xFF << unused;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn write_boolean<C: boolean::Constraint>(&mut self, value: bool) -> Result<(), Self::Error> {
+        self.begin_value(C::TAG);
+        self.buffer.push(if value { TRUE } else { FALSE });
+        Ok(())
+    }
+
+    #[inline]
+    fn write_null<C: null::Constraint>(&mut self, _value: &Null) -> Result<(), Self::Error> {
+        self.begin_value(C::TAG);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_real<T: real::Float, C: real::Constraint>(
+        &mut self,
+        value: T,
+    ) -> Result<(), Self::Error> {
+        self.begin_value(C::TAG);
+        if T::SINGLE_PRECISION {
+            // ITU-T X.696, 12.2: the four octets of an IEEE 754 binary32
+            let value = value.to_f64() as f32;
+            self.buffer
+                .extend_from_slice(&value.to_bits().to_be_bytes());
+        } else {
+            let contents = crate::protocol::basic::real::encode(value.to_f64());
+            write_length(&mut self.buffer, contents.len() as u64);
+            self.buffer.extend_from_slice(&contents);
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn write_object_identifier<C: objectidentifier::Constraint>(
+        &mut self,
+        value: &Oid,
+    ) -> Result<(), Self::Error> {
+        // ITU-T X.696, 18: the contents octets of the BER encoding after a length determinant
+        let contents = crate::protocol::basic::oid::encode(value.as_slice())
+            .map_err(ErrorKind::InvalidEncoding)?;
+        self.begin_value(C::TAG);
+        write_length(&mut self.buffer, contents.len() as u64);
+        self.buffer.extend_from_slice(&contents);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_relative_oid<C: relativeoid::Constraint>(
+        &mut self,
+        value: &Oid,
+    ) -> Result<(), Self::Error> {
+        let contents = crate::protocol::basic::oid::encode_relative(value.as_slice())
+            .map_err(ErrorKind::InvalidEncoding)?;
+        self.begin_value(C::TAG);
+        write_length(&mut self.buffer, contents.len() as u64);
+        self.buffer.extend_from_slice(&contents);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_oid_iri<C: oidiri::Constraint>(&mut self, value: &str) -> Result<(), Self::Error> {
+        // like an unconstrained UTF8String
+        crate::protocol::basic::oid::check_iri(value).map_err(ErrorKind::InvalidEncoding)?;
+        self.write_string(C::TAG, Charset::Utf8, (None, None, false), value)
+    }
+
+    #[inline]
+    fn write_complex<C: complex::Constraint, V: Writable>(
+        &mut self,
+        value: &V,
+    ) -> Result<(), Self::Error> {
+        // the outermost component that assigns a tag determines the tag of the alternative
+        if core::mem::take(&mut self.choice_tag) {
+            write_tag(&mut self.buffer, C::TAG);
+        }
+        value.write(self)
+    }
+}
+
+/// The `SEQUENCE` or `SET` that is currently read
+#[derive(Debug, Clone)]
+struct ReadScope {
+    /// The position of the first octet of the preamble
+    preamble: usize,
+    /// The index of the next presence bit within the preamble
+    bit: usize,
+    /// Whether the extension bit is set
+    extended: bool,
+    /// The number of root components that are not read yet, `None` if not extensible
+    root_fields: Option<u64>,
+    /// The presence bits of the extension additions, once the bitmap is read
+    additions: Option<Vec<bool>>,
+    /// The index of the next extension addition
+    next_addition: usize,
+}
+
+/// How the next component of a `SEQUENCE` or `SET` is encoded
+enum Field {
+    Present,
+    Absent,
+    /// A present extension addition that ends at the given position
+    OpenType(usize),
+}
+
+pub struct CoerReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    scope: Option<ReadScope>,
+    /// The tag of the `CHOICE` alternative that is read next
+    choice_tag: Option<Tag>,
+    /// Whether the last failure was caused by a `CHOICE` alternative with a different tag
+    tag_mismatch: bool,
+    recovery: numbers::Recovery,
+    depth: Depth,
+    transforms: containing::Transforms,
+}
+
+impl<'a> From<&'a [u8]> for CoerReader<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            scope: None,
+            choice_tag: None,
+            tag_mismatch: false,
+            recovery: numbers::Recovery::default(),
+            depth: Depth::default(),
+            transforms: containing::Transforms::default(),
+        }
+    }
+}
+
+impl<'a> CoerReader<'a> {
+    /// The position of the next byte to read
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    #[inline]
+    pub fn bytes_remaining(&self) -> usize {
+        self.bytes.len().saturating_sub(self.position)
+    }
+
+    #[inline]
+    fn nested<T, F: FnOnce(&mut Self) -> Result<T, Error>>(&mut self, f: F) -> Result<T, Error> {
+        self.depth
+            .enter()
+            .map_err(ErrorKind::NestingDepthExceedsLimit)?;
+        let result = f(self);
+        self.depth.leave();
+        result
+    }
+
+    fn take(&mut self, len: u64) -> Result<&'a [u8], Error> {
+        let len = usize::try_from(len).map_err(|_| ErrorKind::EndOfStream)?;
+        take(self.bytes, &mut self.position, len)
+    }
+
+    fn is_bit_set(&self, position: usize, bit: usize) -> bool {
+        self.bytes
+            .get(position + bit / 8)
+            .is_some_and(|octet| octet & (0x80 >> (bit % 8)) != 0)
+    }
+
+    /// Accounts for the next component of the surrounding `SEQUENCE` or `SET`
+    fn begin_field(&mut self, optional: bool) -> Result<Field, Error> {
+        let Some(mut scope) = self.scope.take() else {
+            return Ok(Field::Present);
+        };
+        let result = self.enter_field(&mut scope, optional);
+        self.scope = Some(scope);
+        result
+    }
+
+    fn enter_field(&mut self, scope: &mut ReadScope, optional: bool) -> Result<Field, Error> {
+        match &mut scope.root_fields {
+            Some(0) if !scope.extended => Ok(Field::Absent),
+            Some(0) => {
+                let additions = match &mut scope.additions {
+                    Some(additions) => additions,
+                    additions => additions.insert(self.read_addition_bitmap()?),
+                };
+                let present = additions.get(scope.next_addition).copied().unwrap_or(false);
+                scope.next_addition += 1;
+                if present {
+                    let len = read_length(self.bytes, &mut self.position)?;
+                    let start = self.position;
+                    self.take(len)?;
+                    let end = self.position;
+                    self.position = start;
+                    Ok(Field::OpenType(end))
+                } else {
+                    Ok(Field::Absent)
+                }
+            }
+            root_fields => {
+                if let Some(remaining) = root_fields {
+                    *remaining -= 1;
+                }
+                if optional {
+                    let present = self.is_bit_set(scope.preamble, scope.bit);
+                    scope.bit += 1;
+                    Ok(if present {
+                        Field::Present
+                    } else {
+                        Field::Absent
+                    })
+                } else {
+                    Ok(Field::Present)
+                }
+            }
+        }
+    }
+
+    /// Accounts for the next mandatory value and ensures that it has the tag of the `CHOICE`
+    /// alternative that is read, if any
+    fn begin_value(&mut self, tag: Tag) -> Result<(), Error> {
+        match self.begin_field(false)? {
+            Field::Absent => {
+                Err(ErrorKind::InvalidEncoding("mandatory extension addition is absent").into())
+            }
+            Field::Present | Field::OpenType(_) => self.ensure_choice_tag(tag),
+        }
+    }
+
+    fn ensure_choice_tag(&mut self, tag: Tag) -> Result<(), Error> {
+        match self.choice_tag.take() {
+            Some(got) if got != tag => {
+                self.choice_tag = Some(got);
+                self.tag_mismatch = true;
+                Err(Error::unexpected_tag(tag, got))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn scope_stashed<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<T, Error> {
+        let scope = self.scope.take();
+        let result = f(self);
+        self.scope = scope;
+        result
+    }
+
+    /// Reads a value that is encoded as open type ending at the given position, content after
+    /// the value is skipped
+    fn read_open_type<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
+        &mut self,
+        end: usize,
+        f: F,
+    ) -> Result<T, Error> {
+        let bytes = self.bytes;
+        self.bytes = bytes.get(..end).ok_or(ErrorKind::EndOfStream)?;
+        let result = f(self);
+        self.bytes = bytes;
+        let value = result?;
+        self.position = end;
+        Ok(value)
+    }
+
+    /// Reads the extension presence bitmap (ITU-T X.696, chapter 16.4)
+    fn read_addition_bitmap(&mut self) -> Result<Vec<bool>, Error> {
+        let len = read_length(self.bytes, &mut self.position)?;
+        let bitmap = self.take(len)?;
+        match bitmap.split_first() {
+            Some((unused, bits)) if *unused < 8 && (!bits.is_empty() || *unused == 0) => {
+                let count = bits.len() * 8 - usize::from(*unused);
+                Ok((0..count)
+                    .map(|bit| bits[bit / 8] & (0x80 >> (bit % 8)) != 0)
+                    .collect())
+            }
+            _ => Err(ErrorKind::InvalidEncoding("invalid extension presence bitmap").into()),
+        }
+    }
+
+    /// Skips the extension additions that are unknown to this version of the definition
+    fn skip_additions(&mut self, mut scope: ReadScope) -> Result<(), Error> {
+        let additions = match scope.additions.take() {
+            Some(additions) => additions,
+            None => self.read_addition_bitmap()?,
+        };
+        for present in additions.into_iter().skip(scope.next_addition) {
+            if present {
+                let len = read_length(self.bytes, &mut self.position)?;
+                self.take(len)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_sequence_content<
+        C: sequence::Constraint,
+        S: Sized,
+        F: Fn(&mut Self) -> Result<S, Error>,
+    >(
+        &mut self,
+        f: F,
+    ) -> Result<S, Error> {
+        let extensible = C::EXTENDED_AFTER_FIELD.is_some();
+        let bits = usize::from(extensible) + C::STD_OPTIONAL_FIELDS as usize;
+        let preamble = self.position;
+        self.take(bits.div_ceil(8) as u64)?;
+
+        let outer = self.scope.replace(ReadScope {
+            preamble,
+            bit: usize::from(extensible),
+            extended: extensible && self.is_bit_set(preamble, 0),
+            root_fields: C::EXTENDED_AFTER_FIELD.map(|after| after + 1),
+            additions: None,
+            next_addition: 0,
+        });
+        let result = f(self);
+        let scope = core::mem::replace(&mut self.scope, outer);
+        let value = result?;
+
+        match scope {
+            Some(scope) if scope.extended => self.skip_additions(scope)?,
+            _ => {}
+        }
+        Ok(value)
+    }
+
+    /// Reads the `CHOICE` alternative with the given tag, by trying one alternative after the
+    /// other until one accepts the tag. If no root alternative does, the value is an extension
+    /// alternative within an open type (X.696, 23.3).
+    fn read_alternative<C: choice::Constraint>(&mut self, tag: Tag) -> Result<C, Error> {
+        if let Some(choice) = self.probe_alternatives(tag, 0..C::STD_VARIANT_COUNT)? {
+            return Ok(choice);
+        }
+        if C::VARIANT_COUNT > C::STD_VARIANT_COUNT {
+            let len = read_length(self.bytes, &mut self.position)?;
+            let content = self.position;
+            self.take(len)?;
+            let end = core::mem::replace(&mut self.position, content);
+            let extensions = C::STD_VARIANT_COUNT..C::VARIANT_COUNT;
+            if let Some(choice) =
+                self.read_open_type(end, |r| r.probe_alternatives(tag, extensions))?
+            {
+                return Ok(choice);
+            }
+        }
+        Err(Error::unexpected_tag(C::TAG, tag))
+    }
+
+    fn probe_alternatives<C: choice::Constraint>(
+        &mut self,
+        tag: Tag,
+        indices: core::ops::Range<u64>,
+    ) -> Result<Option<C>, Error> {
+        let start = self.position;
+        for index in indices {
+            self.choice_tag = Some(tag);
+            self.tag_mismatch = false;
+            match C::read_content(index, self) {
+                Ok(Some(choice)) => {
+                    self.choice_tag = None;
+                    return Ok(Some(choice));
+                }
+                Ok(None) => {}
+                Err(_) if self.tag_mismatch => {}
+                Err(e) => {
+                    self.choice_tag = None;
+                    self.tag_mismatch = false;
+                    return Err(e);
+                }
+            }
+            self.position = start;
+        }
+        self.choice_tag = None;
+        self.tag_mismatch = false;
+        Ok(None)
+    }
+
+    fn read_string(
+        &mut self,
+        tag: Tag,
+        charset: Charset,
+        (min, max, extensible): (Option<u64>, Option<u64>, bool),
+    ) -> Result<String, Error> {
+        self.begin_value(tag)?;
+        let len = match (fixed_size(min, max, extensible), charset.octets_per_char()) {
+            // UTF8String is not a known-multiplier character string type (X.696, 27.1)
+            (Some(size), Some(octets)) => size.saturating_mul(octets as u64),
+            _ => read_length(self.bytes, &mut self.position)?,
+        };
+        let string = charset
+            .decode(self.take(len)?)
+            .ok_or(ErrorKind::InvalidEncoding(
+                "the string is not validly encoded",
+            ))?;
+        Error::ensure_string_valid(charset, &string)?;
+        ensure_size_in_range(min, max, extensible, string.chars().count() as u64)?;
+        Ok(string)
+    }
+}
+
+impl containing::RegisterTransform for CoerReader<'_> {
+    fn transforms_mut(&mut self) -> &mut containing::Transforms {
+        &mut self.transforms
+    }
+}
+
+impl DepthLimit for CoerReader<'_> {
+    fn depth(&self) -> &Depth {
+        &self.depth
+    }
+
+    fn depth_mut(&mut self) -> &mut Depth {
+        &mut self.depth
+    }
+}
+
+impl numbers::DecodePolicyReader for CoerReader<'_> {
+    fn recovery(&self) -> &numbers::Recovery {
+        &self.recovery
+    }
+
+    fn recovery_mut(&mut self) -> &mut numbers::Recovery {
+        &mut self.recovery
+    }
+}
+
+impl Reader for CoerReader<'_> {
+    type Error = Error;
+
+    #[inline]
+    fn bits_read(&self) -> Option<usize> {
+        Some(self.position * 8)
+    }
+
+    #[inline]
+    fn read_sequence<
+        C: sequence::Constraint,
+        S: Sized,
+        F: Fn(&mut Self) -> Result<S, Self::Error>,
+    >(
+        &mut self,
+        f: F,
+    ) -> Result<S, Self::Error> {
+        self.nested(|r| {
+            r.begin_value(C::TAG)?;
+            r.read_sequence_content::<C, S, F>(f)
+        })
+    }
+
+    #[inline]
+    fn read_sequence_of<C: sequenceof::Constraint, T: ReadableType>(
+        &mut self,
+    ) -> Result<Vec<T::Type>, Self::Error> {
+        self.nested(|r| {
+            r.begin_value(C::TAG)?;
+            let quantity = read_quantity(r.bytes, &mut r.position)?;
+            ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, quantity)?;
+            // elements might be empty, so this does not trust the quantity on its own
+            let capacity = quantity.min(r.bytes_remaining() as u64) as usize;
+            r.scope_stashed(|r| {
+                let mut vec = Vec::with_capacity(capacity);
+                for _ in 0..quantity {
+                    vec.push(T::read_value(r)?);
+                }
+                Ok(vec)
+            })
+        })
+    }
+
+    #[inline]
+    fn read_set<C: set::Constraint, S: Sized, F: Fn(&mut Self) -> Result<S, Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<S, Self::Error> {
+        self.nested(|r| {
+            r.begin_value(C::TAG)?;
+            r.read_sequence_content::<C, S, F>(f)
+        })
+    }
+
+    #[inline]
+    fn read_set_of<C: setof::Constraint, T: ReadableType>(
+        &mut self,
+    ) -> Result<Vec<<T as ReadableType>::Type>, Self::Error> {
+        self.read_sequence_of::<C, T>()
+    }
+
+    #[inline]
+    fn read_enumerated<C: enumerated::Constraint>(&mut self) -> Result<C, Self::Error> {
+        self.begin_value(C::TAG)?;
+        let value = read_enumerated(self.bytes, &mut self.position)?;
+        u64::try_from(value)
+            .ok()
+            .and_then(C::from_choice_index)
+            .ok_or_else(|| ErrorKind::InvalidChoiceIndex(value as u64, C::VARIANT_COUNT).into())
+    }
+
+    #[inline]
+    fn read_choice<C: choice::Constraint>(&mut self) -> Result<C, Self::Error> {
+        if let Field::Absent = self.begin_field(false)? {
+            return Err(
+                ErrorKind::InvalidEncoding("mandatory extension addition is absent").into(),
+            );
+        }
+        self.nested(|r| {
+            r.scope_stashed(|r| {
+                // an untagged CHOICE alternative of a CHOICE has the tag of its own alternative
+                let tag = match r.choice_tag.take() {
+                    Some(tag) => tag,
+                    None => read_tag(r.bytes, &mut r.position)?,
+                };
+                r.read_alternative::<C>(tag)
+            })
+        })
+    }
+
+    #[inline]
+    fn read_opt<T: ReadableType>(
+        &mut self,
+    ) -> Result<Option<<T as ReadableType>::Type>, Self::Error> {
+        match self.begin_field(true)? {
+            Field::Absent => Ok(None),
+            Field::Present => self.scope_stashed(T::read_value).map(Some),
+            Field::OpenType(end) => self
+                .scope_stashed(|r| r.read_open_type(end, T::read_value))
+                .map(Some),
+        }
+    }
+
+    #[inline]
+    fn read_default<C: default::Constraint<Owned = T::Type>, T: ReadableType>(
+        &mut self,
+    ) -> Result<T::Type, Self::Error> {
+        Ok(self
+            .read_opt::<T>()?
+            .unwrap_or_else(|| C::default_value().to_owned()))
+    }
+
+    #[inline]
+    fn read_number<T: numbers::Number, C: numbers::Constraint<T>>(
+        &mut self,
+    ) -> Result<T, Self::Error> {
+        self.begin_value(C::TAG)?;
+        let value = read_integer(
+            self.bytes,
+            &mut self.position,
+            IntegerWidth::of(C::MIN, C::MAX, C::EXTENSIBLE),
+        )?;
+        self.recovery
+            .apply::<T, C, _>(value, ensure_value_in_range::<T, C>(value))
+    }
+
+    #[inline]
+    fn read_utf8string<C: utf8string::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.read_string(C::TAG, Charset::Utf8, (C::MIN, C::MAX, C::EXTENSIBLE))
+    }
+
+    #[inline]
+    fn read_ia5string<C: ia5string::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.read_string(C::TAG, Charset::Ia5, (C::MIN, C::MAX, C::EXTENSIBLE))
+    }
+
+    #[inline]
+    fn read_numeric_string<C: numericstring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.read_string(C::TAG, Charset::Numeric, (C::MIN, C::MAX, C::EXTENSIBLE))
+    }
+
+    #[inline]
+    fn read_visible_string<C: visiblestring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.read_string(C::TAG, Charset::Visible, (C::MIN, C::MAX, C::EXTENSIBLE))
+    }
+
+    #[inline]
+    fn read_printable_string<C: printablestring::Constraint>(
+        &mut self,
+    ) -> Result<String, Self::Error> {
+        self.read_string(C::TAG, Charset::Printable, (C::MIN, C::MAX, C::EXTENSIBLE))
+    }
+
+    #[inline]
+    fn read_bmp_string<C: bmpstring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.read_string(C::TAG, Charset::Bmp, (C::MIN, C::MAX, C::EXTENSIBLE))
+    }
+
+    #[inline]
+    fn read_teletex_string<C: teletexstring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.read_string(C::TAG, Charset::Teletex, (C::MIN, C::MAX, C::EXTENSIBLE))
+    }
+
+    #[inline]
+    fn read_octet_string<C: octetstring::Constraint>(&mut self) -> Result<Vec<u8>, Self::Error> {
+        self.begin_value(C::TAG)?;
+        let len = match fixed_size(C::MIN, C::MAX, C::EXTENSIBLE) {
+            Some(size) => size,
+            None => read_length(self.bytes, &mut self.position)?,
+        };
+        let bytes = self.take(len)?.to_vec();
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, bytes.len() as u64)?;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn read_containing<C: octetstring::Constraint, X: containing::Transform, T: ReadableType>(
+        &mut self,
+    ) -> Result<T::Type, Self::Error> {
+        let octets = self.read_octet_string::<C>()?;
+        let content = self
+            .transforms
+            .decode::<X>(octets)
+            .map_err(Error::content_transform_failed)?;
+        let mut reader = CoerReader::from(&content[..]);
+        reader.depth = self.depth;
+        reader.transforms = self.transforms.clone();
+        T::read_value(&mut reader)
+    }
+
+    #[inline]
+    fn read_substituted<S, X: substituted::Substitute<S, T::Type>, T: ReadableType>(
+        &mut self,
+    ) -> Result<S, Self::Error> {
+        let value = T::read_value(self)?;
+        X::from_asn(value).map_err(Error::substitution_failed)
+    }
+
+    #[inline]
+    fn read_bit_string<C: bitstring::Constraint>(&mut self) -> Result<(Vec<u8>, u64), Self::Error> {
+        self.begin_value(C::TAG)?;
+        let (bytes, bit_len) = match fixed_size(C::MIN, C::MAX, C::EXTENSIBLE) {
+            Some(bit_len) => (self.take(bit_len.div_ceil(8))?.to_vec(), bit_len),
+            None => {
+                let len = read_length(self.bytes, &mut self.position)?;
+                match self.take(len)?.split_first() {
+                    Some((unused, bits)) if *unused < 8 && (!bits.is_empty() || *unused == 0) => {
+                        (bits.to_vec(), bits.len() as u64 * 8 - u64::from(*unused))
+                    }
+                    _ => {
+                        return Err(ErrorKind::InvalidEncoding(
+                            "invalid number of unused bits of a BIT STRING",
+                        )
+                        .into())
+                    }
+                }
+            }
+        };
+        ensure_size_in_range(C::MIN, C::MAX, C::EXTENSIBLE, bit_len)?;
+        Ok((bytes, bit_len))
+    }
+
+    #[inline]
+    fn read_boolean<C: boolean::Constraint>(&mut self) -> Result<bool, Self::Error> {
+        self.begin_value(C::TAG)?;
+        // any other value than FALSE is TRUE, only the canonical encoding requires 0xFF
+        Ok(self.take(1)?.first() != Some(&FALSE))
+    }
+
+    #[inline]
+    fn read_null<C: null::Constraint>(&mut self) -> Result<Null, Self::Error> {
+        self.begin_value(C::TAG)?;
+        Ok(Null)
+    }
+
+    #[inline]
+    fn read_real<T: real::Float, C: real::Constraint>(&mut self) -> Result<T, Self::Error> {
+        self.begin_value(C::TAG)?;
+        if T::SINGLE_PRECISION {
+            let mut bits = [0_u8; 4];
+            bits.copy_from_slice(self.take(4)?);
+            Ok(T::from_f64(f64::from(f32::from_bits(u32::from_be_bytes(
+                bits,
+            )))))
+        } else {
+            let len = read_length(self.bytes, &mut self.position)?;
+            let contents = self.take(len)?;
+            crate::protocol::basic::real::decode(contents)
+                .map(T::from_f64)
+                .map_err(|reason| ErrorKind::InvalidEncoding(reason).into())
+        }
+    }
+
+    #[inline]
+    fn read_object_identifier<C: objectidentifier::Constraint>(
+        &mut self,
+    ) -> Result<Oid, Self::Error> {
+        self.begin_value(C::TAG)?;
+        let len = read_length(self.bytes, &mut self.position)?;
+        let contents = self.take(len)?;
+        crate::protocol::basic::oid::decode(contents)
+            .map(Oid::from)
+            .map_err(|reason| ErrorKind::InvalidEncoding(reason).into())
+    }
+
+    #[inline]
+    fn read_relative_oid<C: relativeoid::Constraint>(&mut self) -> Result<Oid, Self::Error> {
+        self.begin_value(C::TAG)?;
+        let len = read_length(self.bytes, &mut self.position)?;
+        let contents = self.take(len)?;
+        crate::protocol::basic::oid::decode_relative(contents)
+            .map(Oid::from)
+            .map_err(|reason| ErrorKind::InvalidEncoding(reason).into())
+    }
+
+    #[inline]
+    fn read_oid_iri<C: oidiri::Constraint>(&mut self) -> Result<String, Self::Error> {
+        let iri = self.read_string(C::TAG, Charset::Utf8, (None, None, false))?;
+        crate::protocol::basic::oid::check_iri(&iri).map_err(ErrorKind::InvalidEncoding)?;
+        Ok(iri)
+    }
+
+    #[inline]
+    fn read_complex<C: complex::Constraint, V: Readable>(&mut self) -> Result<V, Self::Error> {
+        self.ensure_choice_tag(C::TAG)?;
+        V::read(self)
+    }
+}
+
+/// The size of a value that is encoded without length determinant (X.696, 8.6)
+fn fixed_size(min: Option<u64>, max: Option<u64>, extensible: bool) -> Option<u64> {
+    min.filter(|min| !extensible && Some(*min) == max)
+}
+
+fn ensure_size_in_range(
+    min: Option<u64>,
+    max: Option<u64>,
+    extensible: bool,
+    size: u64,
+) -> Result<(), Error> {
+    let min = min.unwrap_or(0);
+    let max = max.unwrap_or(u64::MAX);
+    if extensible || (min..=max).contains(&size) {
+        Ok(())
+    } else {
+        Err(ErrorKind::SizeNotInRange(size, min, max).into())
+    }
+}
+
+fn ensure_value_in_range<T: numbers::Number, C: numbers::Constraint<T>>(
+    value: i64,
+) -> Result<(), Error> {
+    let min = C::MIN.unwrap_or(i64::MIN);
+    let max = C::MAX.unwrap_or(i64::MAX);
+    if C::EXTENSIBLE || (min..=max).contains(&value) {
+        Ok(())
+    } else {
+        Err(ErrorKind::ValueNotInRange {
+            value,
+            min,
+            max,
+            definition: C::DEFINITION_NAME,
+            field: C::FIELD_NAME,
+        }
+        .into())
+    }
+}
@@ -0,0 +1,59 @@
+use crate::descriptor::{Reader, Writer};
+
+/// A [`Reader`] that knows the position of the bits it reads and can return to an earlier one,
+/// for lookahead parsing and exact offsets in error reports
+pub trait PositionedReader: Reader {
+    /// The state of the reader at a given point in time, see [`PositionedReader::mark`]
+    type Mark: Clone;
+
+    /// The position of the next bit to read
+    fn bit_position(&self) -> usize;
+
+    /// Number of bits until the end of the current (sub-)slice is reached
+    fn remaining_bits(&self) -> usize;
+
+    /// Reads the next bit without advancing the read position
+    fn peek_bit(&mut self) -> Result<bool, Self::Error>;
+
+    /// Remembers the current state of the reader, see [`PositionedReader::rollback`]
+    fn mark(&self) -> Self::Mark;
+
+    /// Resets the reader to the state of the given mark, so that everything read since then is
+    /// read again. The mark must have been created by this reader on the same nesting level
+    /// (within the same closure of [`Reader::read_sequence`] for example).
+    fn rollback(&mut self, mark: Self::Mark);
+
+    /// Calls the given function and resets the reader afterwards - regardless of the result - so
+    /// that the next read starts at the same position again
+    #[inline]
+    fn lookahead<T, F: FnOnce(&mut Self) -> Result<T, Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<T, Self::Error>
+    where
+        Self: Sized,
+    {
+        let mark = self.mark();
+        let result = f(self);
+        self.rollback(mark);
+        result
+    }
+}
+
+/// A [`Writer`] that knows the position of the bits it writes and can discard what it wrote
+/// since an earlier one
+pub trait PositionedWriter: Writer {
+    /// The state of the writer at a given point in time, see [`PositionedWriter::mark`]
+    type Mark: Clone;
+
+    /// The position the next bit is written to
+    fn bit_position(&self) -> usize;
+
+    /// Remembers the current state of the writer, see [`PositionedWriter::rollback`]
+    fn mark(&self) -> Self::Mark;
+
+    /// Discards everything written since the given mark was created. The mark must have been
+    /// created by this writer on the same nesting level (within the same closure of
+    /// [`Writer::write_sequence`] for example).
+    fn rollback(&mut self, mark: Self::Mark);
+}
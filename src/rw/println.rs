@@ -138,9 +138,12 @@ impl Writer for PrintlnWriter {
         &mut self,
         value: &T::Type,
     ) -> Result<(), Self::Error> {
-        self.indented_println(format!("Writing DEFAULT (default: {:?})", C::DEFAULT_VALUE));
+        self.indented_println(format!(
+            "Writing DEFAULT (default: {:?})",
+            C::default_value()
+        ));
         self.with_increased_indentation(|w| {
-            if C::DEFAULT_VALUE.eq(value) {
+            if C::default_value().eq(value) {
                 w.indented_println("None");
                 Ok(())
             } else {
@@ -241,6 +244,42 @@ impl Writer for PrintlnWriter {
         Ok(())
     }
 
+    fn write_bmp_string<C: bmpstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.indented_println(format!(
+            "Writing BMPString({}..{}), tag={:?}",
+            C::MIN
+                .map(|v| format!("{}", v))
+                .unwrap_or_else(|| String::from("MIN")),
+            C::MAX
+                .map(|v| format!("{}", v))
+                .unwrap_or_else(|| String::from("MAX")),
+            C::TAG
+        ));
+        self.with_increased_indentation(|w| w.indented_println(format!("{:?}", value)));
+        Ok(())
+    }
+
+    fn write_teletex_string<C: teletexstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.indented_println(format!(
+            "Writing TeletexString({}..{}), tag={:?}",
+            C::MIN
+                .map(|v| format!("{}", v))
+                .unwrap_or_else(|| String::from("MIN")),
+            C::MAX
+                .map(|v| format!("{}", v))
+                .unwrap_or_else(|| String::from("MAX")),
+            C::TAG
+        ));
+        self.with_increased_indentation(|w| w.indented_println(format!("{:?}", value)));
+        Ok(())
+    }
+
     fn write_visible_string<C: visiblestring::Constraint>(
         &mut self,
         value: &str,
@@ -277,6 +316,18 @@ impl Writer for PrintlnWriter {
         Ok(())
     }
 
+    fn write_containing<C: octetstring::Constraint, X: containing::Transform, T: WritableType>(
+        &mut self,
+        value: &T::Type,
+    ) -> Result<(), Self::Error> {
+        self.indented_println(format!(
+            "WRITING OctetString CONTAINING, transform={}, tag={:?}",
+            core::any::type_name::<X>(),
+            C::TAG,
+        ));
+        self.with_increased_indentation(|w| T::write_value(w, value))
+    }
+
     fn write_bit_string<C: bitstring::Constraint>(
         &mut self,
         value: &[u8],
@@ -307,4 +358,37 @@ impl Writer for PrintlnWriter {
         self.indented_println(format!("WRITING NULL, tag={:?}", C::TAG));
         Ok(())
     }
+
+    fn write_real<T: real::Float, C: real::Constraint>(
+        &mut self,
+        value: T,
+    ) -> Result<(), Self::Error> {
+        self.indented_println(format!("WRITING Real, tag={:?}", C::TAG));
+        self.with_increased_indentation(|w| w.indented_println(value.to_f64().to_string()));
+        Ok(())
+    }
+
+    fn write_object_identifier<C: objectidentifier::Constraint>(
+        &mut self,
+        value: &Oid,
+    ) -> Result<(), Self::Error> {
+        self.indented_println(format!("WRITING ObjectIdentifier, tag={:?}", C::TAG));
+        self.with_increased_indentation(|w| w.indented_println(value.to_string()));
+        Ok(())
+    }
+
+    fn write_relative_oid<C: relativeoid::Constraint>(
+        &mut self,
+        value: &Oid,
+    ) -> Result<(), Self::Error> {
+        self.indented_println(format!("WRITING RelativeOid, tag={:?}", C::TAG));
+        self.with_increased_indentation(|w| w.indented_println(value.to_string()));
+        Ok(())
+    }
+
+    fn write_oid_iri<C: oidiri::Constraint>(&mut self, value: &str) -> Result<(), Self::Error> {
+        self.indented_println(format!("WRITING OidIri, tag={:?}", C::TAG));
+        self.with_increased_indentation(|w| w.indented_println(value));
+        Ok(())
+    }
 }
@@ -0,0 +1,506 @@
+use crate::descriptor::*;
+use crate::prelude::ProtobufWriter;
+use crate::protocol::protobuf::Error;
+use asn1rs_model::generate::protobuf::ProtobufDefGenerator;
+use asn1rs_model::protobuf::proto_field_name;
+use std::fmt::Write as _;
+
+/// The name of the single field of a message generated for a tuple struct
+const TUPLE_FIELD_NAME: &str = "value";
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[derive(Debug, Copy, Clone)]
+enum Scope {
+    /// A value without a name, such as the root value or an element of a repeated field
+    Value,
+    /// The fields of a message, `index` being the index of the next field
+    Message {
+        definition: &'static str,
+        names: &'static [&'static str],
+        index: usize,
+        empty: bool,
+    },
+    /// The value of the set alternative of a `oneof`
+    Alternative(&'static str),
+}
+
+/// Writes values in the canonical proto3 JSON mapping of the message types the protobuf
+/// generator derives from the same definitions. Fields are named in lowerCamelCase, enumerated
+/// values are written as the names of their protobuf enum values and fields with the proto3
+/// default value are omitted - like protobuf implementations print them.
+///
+/// Requires the generated constraints to provide the names of the fields and variants.
+pub struct ProtobufJsonWriter {
+    buffer: String,
+    scope: Scope,
+    transforms: containing::Transforms,
+}
+
+impl Default for ProtobufJsonWriter {
+    fn default() -> Self {
+        Self {
+            buffer: String::new(),
+            scope: Scope::Value,
+            transforms: containing::Transforms::default(),
+        }
+    }
+}
+
+impl ProtobufJsonWriter {
+    pub fn into_string(self) -> String {
+        self.buffer
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.buffer
+    }
+
+    /// Writes the name of the next field, if there is one. Returns whether the value of the
+    /// field needs to be written, which is not the case for a field with the default value.
+    fn begin_field(&mut self, is_default: bool) -> Result<bool, Error> {
+        match &mut self.scope {
+            Scope::Value => Ok(true),
+            Scope::Alternative(name) => {
+                let name = *name;
+                self.write_name(&proto_field_name(name));
+                Ok(true)
+            }
+            Scope::Message {
+                definition,
+                names,
+                index,
+                empty,
+            } => {
+                let name = names
+                    .get(*index)
+                    .ok_or_else(|| Error::unnamed_field(definition, *index))?;
+                *index += 1;
+                if is_default {
+                    Ok(false)
+                } else {
+                    if !core::mem::take(empty) {
+                        self.buffer.push(',');
+                    }
+                    let name = if *name == "0" {
+                        TUPLE_FIELD_NAME.to_string()
+                    } else {
+                        proto_field_name(name)
+                    };
+                    self.write_name(&name);
+                    Ok(true)
+                }
+            }
+        }
+    }
+
+    fn skip_field(&mut self) {
+        if let Scope::Message { index, .. } = &mut self.scope {
+            *index += 1;
+        }
+    }
+
+    fn write_name(&mut self, proto_name: &str) {
+        self.buffer.push('"');
+        self.buffer.push_str(&json_name(proto_name));
+        self.buffer.push_str("\":");
+    }
+
+    fn write_string(&mut self, value: &str) -> Result<(), Error> {
+        if self.begin_field(value.is_empty())? {
+            write_json_string(&mut self.buffer, value);
+        }
+        Ok(())
+    }
+
+    fn write_bytes(&mut self, value: &[u8]) -> Result<(), Error> {
+        if self.begin_field(value.is_empty())? {
+            self.buffer.push('"');
+            write_base64(&mut self.buffer, value);
+            self.buffer.push('"');
+        }
+        Ok(())
+    }
+
+    fn write_message<F: Fn(&mut Self) -> Result<(), Error>>(
+        &mut self,
+        definition: &'static str,
+        names: &'static [&'static str],
+        f: F,
+    ) -> Result<(), Error> {
+        if self.begin_field(false)? {
+            let scope = core::mem::replace(
+                &mut self.scope,
+                Scope::Message {
+                    definition,
+                    names,
+                    index: 0,
+                    empty: true,
+                },
+            );
+            self.buffer.push('{');
+            let result = f(self);
+            self.buffer.push('}');
+            self.scope = scope;
+            result?;
+        }
+        Ok(())
+    }
+
+    fn write_repeated<T: WritableType>(&mut self, slice: &[T::Type]) -> Result<(), Error> {
+        if let Scope::Alternative(_) = self.scope {
+            // a repeated field cannot be the member of a oneof, the values of the alternative
+            // are the only field of a nested message instead
+            return self.write_message("", &["0"], |writer| writer.write_repeated::<T>(slice));
+        }
+        if self.begin_field(slice.is_empty())? {
+            let scope = core::mem::replace(&mut self.scope, Scope::Value);
+            self.buffer.push('[');
+            let result = slice.iter().enumerate().try_for_each(|(index, value)| {
+                if index > 0 {
+                    self.buffer.push(',');
+                }
+                T::write_value(self, value)
+            });
+            self.buffer.push(']');
+            self.scope = scope;
+            result?;
+        }
+        Ok(())
+    }
+}
+
+impl containing::RegisterTransform for ProtobufJsonWriter {
+    fn transforms_mut(&mut self) -> &mut containing::Transforms {
+        &mut self.transforms
+    }
+}
+
+impl Writer for ProtobufJsonWriter {
+    type Error = Error;
+
+    #[inline]
+    fn write_sequence<C: sequence::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        self.write_message(C::NAME, C::FIELD_NAMES, f)
+    }
+
+    #[inline]
+    fn write_sequence_of<C: sequenceof::Constraint, T: WritableType>(
+        &mut self,
+        slice: &[T::Type],
+    ) -> Result<(), Self::Error> {
+        self.write_repeated::<T>(slice)
+    }
+
+    #[inline]
+    fn write_set<C: set::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        self.write_message(C::NAME, C::FIELD_NAMES, f)
+    }
+
+    #[inline]
+    fn write_set_of<C: setof::Constraint, T: WritableType>(
+        &mut self,
+        slice: &[T::Type],
+    ) -> Result<(), Self::Error> {
+        self.write_repeated::<T>(slice)
+    }
+
+    #[inline]
+    fn write_enumerated<C: enumerated::Constraint>(
+        &mut self,
+        enumerated: &C,
+    ) -> Result<(), Self::Error> {
+        let index = enumerated.to_choice_index();
+        if self.begin_field(index == 0)? {
+            let variant = usize::try_from(index)
+                .ok()
+                .and_then(|index| C::VARIANT_NAMES.get(index))
+                .ok_or_else(|| Error::unnamed_field(C::NAME, index as usize))?;
+            // like the prefixed values of the enum in the generated .proto file
+            let _ = write!(
+                self.buffer,
+                "\"{}_{}\"",
+                ProtobufDefGenerator::variant_name(C::NAME),
+                ProtobufDefGenerator::variant_name(variant)
+            );
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn write_choice<C: choice::Constraint>(&mut self, choice: &C) -> Result<(), Self::Error> {
+        let index = choice.to_choice_index();
+        let variant = usize::try_from(index)
+            .ok()
+            .and_then(|index| C::VARIANT_NAMES.get(index))
+            .ok_or_else(|| Error::unnamed_field(C::NAME, index as usize))?;
+
+        if self.begin_field(false)? {
+            // the members of a oneof are fields of the surrounding message
+            let scope = core::mem::replace(&mut self.scope, Scope::Alternative(variant));
+            self.buffer.push('{');
+            let result = choice.write_content(self);
+            self.buffer.push('}');
+            self.scope = scope;
+            result?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn write_opt<T: WritableType>(&mut self, value: Option<&T::Type>) -> Result<(), Self::Error> {
+        match value {
+            Some(value) => T::write_value(self, value),
+            None => {
+                self.skip_field();
+                Ok(())
+            }
+        }
+    }
+
+    #[inline]
+    fn write_default<C: default::Constraint<Owned = T::Type>, T: WritableType>(
+        &mut self,
+        value: &T::Type,
+    ) -> Result<(), Self::Error> {
+        // proto3 has no default values other than the ones of the types
+        T::write_value(self, value)
+    }
+
+    #[inline]
+    fn write_number<T: numbers::Number, C: numbers::Constraint<T>>(
+        &mut self,
+        value: T,
+    ) -> Result<(), Self::Error> {
+        let value = value.to_i64();
+        if self.begin_field(value == 0)? {
+            // same types as chosen by the ProtobufWriter, 64-bit integers are strings in JSON
+            if const_unwrap_or!(C::MIN, 0) >= 0 {
+                if const_unwrap_or!(C::MAX, i64::MAX) <= i64::from(u32::MAX) {
+                    let _ = write!(self.buffer, "{}", value);
+                } else {
+                    let _ = write!(self.buffer, "\"{}\"", value as u64);
+                }
+            } else if const_unwrap_or!(C::MIN, i64::MIN) >= i64::from(i32::MIN)
+                && const_unwrap_or!(C::MAX, i64::MAX) <= i64::from(i32::MAX)
+            {
+                let _ = write!(self.buffer, "{}", value);
+            } else {
+                let _ = write!(self.buffer, "\"{}\"", value);
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn write_utf8string<C: utf8string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_string(value)
+    }
+
+    #[inline]
+    fn write_ia5string<C: ia5string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_string(value)
+    }
+
+    #[inline]
+    fn write_numeric_string<C: numericstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_string(value)
+    }
+
+    #[inline]
+    fn write_visible_string<C: visiblestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_string(value)
+    }
+
+    #[inline]
+    fn write_printable_string<C: printablestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_string(value)
+    }
+
+    #[inline]
+    fn write_bmp_string<C: bmpstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_string(value)
+    }
+
+    #[inline]
+    fn write_teletex_string<C: teletexstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_string(value)
+    }
+
+    #[inline]
+    fn write_octet_string<C: octetstring::Constraint>(
+        &mut self,
+        value: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.write_bytes(value)
+    }
+
+    #[inline]
+    fn write_containing<C: octetstring::Constraint, X: containing::Transform, T: WritableType>(
+        &mut self,
+        value: &T::Type,
+    ) -> Result<(), Self::Error> {
+        let mut writer = ProtobufWriter::default();
+        *containing::RegisterTransform::transforms_mut(&mut writer) = self.transforms.clone();
+        T::write_value(&mut writer, value)?;
+        let octets = self
+            .transforms
+            .encode::<X>(writer.into_bytes_vec())
+            .map_err(Error::content_transform_failed)?;
+        self.write_bytes(&octets)
+    }
+
+    #[inline]
+    fn write_bit_string<C: bitstring::Constraint>(
+        &mut self,
+        value: &[u8],
+        bit_len: u64,
+    ) -> Result<(), Self::Error> {
+        // same representation as written by the ProtobufWriter
+        let mut value = value[..(bit_len as usize).div_ceil(8)].to_vec();
+        value.extend_from_slice(&bit_len.to_be_bytes());
+        self.write_bytes(&value)
+    }
+
+    #[inline]
+    fn write_boolean<C: boolean::Constraint>(&mut self, value: bool) -> Result<(), Self::Error> {
+        if self.begin_field(!value)? {
+            self.buffer.push_str(if value { "true" } else { "false" });
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn write_null<C: null::Constraint>(&mut self, _value: &Null) -> Result<(), Self::Error> {
+        // represented as empty bytes
+        self.write_bytes(&[])
+    }
+
+    #[inline]
+    fn write_real<T: real::Float, C: real::Constraint>(
+        &mut self,
+        value: T,
+    ) -> Result<(), Self::Error> {
+        let value = value.to_f64();
+        if self.begin_field(value.to_bits() == 0)? {
+            // special values are strings, a `float` is printed in its shortest single precision form
+            if value.is_nan() {
+                self.buffer.push_str("\"NaN\"");
+            } else if value.is_infinite() {
+                self.buffer.push_str(if value > 0.0 {
+                    "\"Infinity\""
+                } else {
+                    "\"-Infinity\""
+                });
+            } else if T::SINGLE_PRECISION {
+                let _ = write!(self.buffer, "{}", value as f32);
+            } else {
+                let _ = write!(self.buffer, "{}", value);
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn write_object_identifier<C: objectidentifier::Constraint>(
+        &mut self,
+        value: &Oid,
+    ) -> Result<(), Self::Error> {
+        self.write_string(&value.to_string())
+    }
+
+    #[inline]
+    fn write_relative_oid<C: relativeoid::Constraint>(
+        &mut self,
+        value: &Oid,
+    ) -> Result<(), Self::Error> {
+        self.write_string(&value.to_string())
+    }
+
+    #[inline]
+    fn write_oid_iri<C: oidiri::Constraint>(&mut self, value: &str) -> Result<(), Self::Error> {
+        self.write_string(value)
+    }
+}
+
+/// The JSON name of a protobuf field: lowerCamelCase without the underscores
+fn json_name(proto_name: &str) -> String {
+    let mut name = String::with_capacity(proto_name.len());
+    let mut upper = false;
+    for c in proto_name.chars() {
+        if c == '_' {
+            upper = !name.is_empty();
+        } else if upper {
+            name.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            name.push(c);
+        }
+    }
+    name
+}
+
+fn write_json_string(buffer: &mut String, value: &str) {
+    buffer.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => buffer.push_str("\\\""),
+            '\\' => buffer.push_str("\\\\"),
+            '\n' => buffer.push_str("\\n"),
+            '\r' => buffer.push_str("\\r"),
+            '\t' => buffer.push_str("\\t"),
+            '\u{8}' => buffer.push_str("\\b"),
+            '\u{c}' => buffer.push_str("\\f"),
+            c if u32::from(c) < 0x20 => {
+                let _ = write!(buffer, "\\u{:04x}", u32::from(c));
+            }
+            c => buffer.push(c),
+        }
+    }
+    buffer.push('"');
+}
+
+/// Standard base64 with padding, as required by the proto3 JSON mapping for `bytes`
+fn write_base64(buffer: &mut String, value: &[u8]) {
+    for chunk in value.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (i, byte)| {
+            bits | u32::from(*byte) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (bits >> (18 - 6 * i)) & 0x3F;
+                buffer.push(char::from(BASE64_ALPHABET[index as usize]));
+            } else {
+                buffer.push('=');
+            }
+        }
+    }
+}
@@ -1,24 +1,30 @@
 use crate::descriptor::*;
 use crate::protocol::protobuf::ProtoRead as _;
-use crate::protocol::protobuf::{Error, Format};
+use crate::protocol::protobuf::{
+    read_varint_from_slice, zigzag_decode32, zigzag_decode64, Error, Format,
+};
 use std::borrow::Cow;
 use std::collections::VecDeque;
 use std::ops::Range;
 
 #[derive(Debug, Clone)]
 enum State {
-    Root {
-        range: Range<usize>,
-    },
-    Enclosed {
-        tag_counter: u32,
-        tags: VecDeque<(u32, Format, Range<usize>)>,
-    },
+    Root { range: Range<usize> },
+    Enclosed { tag_counter: u32, tags: Tags },
 }
 
+/// The tag, format and content range of each field of an enclosing message
+type Tags = VecDeque<(u32, Format, Range<usize>)>;
+
 pub struct ProtobufReader<'a> {
     source: Cow<'a, [u8]>,
     state: State,
+    /// Cleared [`Tags`] of already read messages, so that reading nested messages does not
+    /// allocate for each of them
+    scratch: Vec<Tags>,
+    /// Whether the next value is the alternative of a `CHOICE` (a member of a `oneof`)
+    alternative: bool,
+    transforms: containing::Transforms,
 }
 
 impl<'a> From<&'a [u8]> for ProtobufReader<'a> {
@@ -28,6 +34,9 @@ impl<'a> From<&'a [u8]> for ProtobufReader<'a> {
                 range: 0..slice.len(),
             },
             source: Cow::Borrowed(slice),
+            scratch: Vec::new(),
+            alternative: false,
+            transforms: containing::Transforms::default(),
         }
     }
 }
@@ -39,20 +48,23 @@ impl From<Vec<u8>> for ProtobufReader<'static> {
                 range: 0..vec.len(),
             },
             source: Cow::Owned(vec),
+            scratch: Vec::new(),
+            alternative: false,
+            transforms: containing::Transforms::default(),
         }
     }
 }
 
 impl<'a> ProtobufReader<'a> {
-    fn index_enclosed(&self, range: Range<usize>) -> Result<State, <Self as Reader>::Error> {
+    fn index_enclosed(&mut self, range: Range<usize>) -> Result<State, <Self as Reader>::Error> {
         let mut position = range.start;
-        let mut tags = VecDeque::new();
+        let mut tags = self.scratch.pop().unwrap_or_default();
 
         while position < range.end {
             let slice = &self.source[position..range.end];
             let pos_before = slice.len();
             let reader = &mut &*slice;
-            let (tag, format) = reader.read_tag()?;
+            let (tag, format) = read_tag(reader)?;
             let pos_after = reader.len();
             let content_position = position + (pos_before - pos_after);
             let (content_offset, content_length) =
@@ -77,14 +89,14 @@ impl<'a> ProtobufReader<'a> {
         match format {
             Format::VarInt => {
                 let len_before = slice.len();
-                slice.read_varint()?;
+                read_varint_from_slice(slice)?;
                 let len_after = slice.len();
                 Ok((0, len_before - len_after))
             }
             Format::Fixed64 => Ok((0, 8)),
             Format::LengthDelimited => {
                 let len_before = slice.len();
-                let content_length = slice.read_varint()?;
+                let content_length = read_varint_from_slice(slice)?;
                 let len_after = slice.len();
                 Ok((len_before - len_after, content_length as usize))
             }
@@ -172,6 +184,7 @@ impl<'a> ProtobufReader<'a> {
         &mut self,
         f: F,
     ) -> Result<S, <Self as Reader>::Error> {
+        self.alternative = false;
         let range = self
             .next_tag_range_filter_format::<true>(Format::LengthDelimited)
             .unwrap_or(0..0);
@@ -180,15 +193,28 @@ impl<'a> ProtobufReader<'a> {
 
         core::mem::swap(&mut self.state, &mut state);
         let result = f(self);
-        self.state = state;
+        let state = core::mem::replace(&mut self.state, state);
+        self.recycle(state);
 
         result
     }
 
+    /// Keeps the allocation of the [`Tags`] of a read message for the next one
+    fn recycle(&mut self, state: State) {
+        if let State::Enclosed { mut tags, .. } = state {
+            tags.clear();
+            self.scratch.push(tags);
+        }
+    }
+
     #[inline]
     fn read_set_or_sequence_of<T: ReadableType>(
         &mut self,
     ) -> Result<Vec<<T as ReadableType>::Type>, <Self as Reader>::Error> {
+        if core::mem::take(&mut self.alternative) {
+            return self.read_wrapped_set_or_sequence_of::<T>();
+        }
+
         let mut vec = Vec::new();
 
         while let Some(range) = self.next_tag_range::<false>() {
@@ -201,6 +227,30 @@ impl<'a> ProtobufReader<'a> {
         self.increment_tag_counter();
         Ok(vec)
     }
+
+    /// See `ProtobufWriter::write_wrapped_set_or_sequence_of`
+    fn read_wrapped_set_or_sequence_of<T: ReadableType>(
+        &mut self,
+    ) -> Result<Vec<<T as ReadableType>::Type>, <Self as Reader>::Error> {
+        let range = self
+            .next_tag_range_filter_format::<true>(Format::LengthDelimited)
+            .unwrap_or(0..0);
+
+        let mut state = self.index_enclosed(range)?;
+
+        core::mem::swap(&mut self.state, &mut state);
+        let result = self.read_set_or_sequence_of::<T>();
+        let state = core::mem::replace(&mut self.state, state);
+        self.recycle(state);
+
+        result
+    }
+}
+
+impl containing::RegisterTransform for ProtobufReader<'_> {
+    fn transforms_mut(&mut self) -> &mut containing::Transforms {
+        &mut self.transforms
+    }
 }
 
 impl<'a> Reader for ProtobufReader<'a> {
@@ -244,7 +294,7 @@ impl<'a> Reader for ProtobufReader<'a> {
     fn read_enumerated<C: enumerated::Constraint>(&mut self) -> Result<C, Self::Error> {
         let index = if let Some(range) = self.next_tag_range_filter_format::<true>(Format::VarInt) {
             let reader = &mut &self.source[range];
-            reader.read_varint()?
+            read_varint_from_slice(reader)?
         } else {
             0
         };
@@ -260,9 +310,9 @@ impl<'a> Reader for ProtobufReader<'a> {
                 let (format, range, tag) = {
                     let reader = &mut &self.source[range.clone()];
                     let len_before = reader.len();
-                    let (tag, format) = reader.read_tag()?;
+                    let (tag, format) = read_tag(reader)?;
                     if format == Format::LengthDelimited {
-                        let _len = reader.read_varint()?;
+                        let _len = read_varint_from_slice(reader)?;
                     }
                     let len_after = reader.len();
                     let read = len_before - len_after;
@@ -272,14 +322,17 @@ impl<'a> Reader for ProtobufReader<'a> {
                 let mut state = State::Enclosed {
                     tag_counter: 1,
                     tags: {
-                        let mut v = VecDeque::with_capacity(1);
+                        let mut v = self.scratch.pop().unwrap_or_default();
                         v.push_back((1u32, format, range));
                         v
                     },
                 };
                 core::mem::swap(&mut self.state, &mut state);
+                self.alternative = true;
                 let result = C::read_content(u64::from(tag.saturating_sub(1)), self);
-                self.state = state;
+                self.alternative = false;
+                let state = core::mem::replace(&mut self.state, state);
+                self.recycle(state);
 
                 match result {
                     Err(e) => Err(e),
@@ -324,63 +377,94 @@ impl<'a> Reader for ProtobufReader<'a> {
         // This way is clearer, that the first branch is for unsigned and the second branch for
         // signed types, while the inner branches determine 32- or 64-bitness
         #[allow(clippy::collapsible_if)]
+        let value = read_varint_from_slice(&mut reader)?;
         if const_unwrap_or!(C::MIN, 0) >= 0 {
             if const_unwrap_or!(C::MAX, i64::MAX) <= i64::from(u32::MAX) {
-                reader.read_uint32().map(|v| T::from_i64(v as i64))
+                Ok(T::from_i64(i64::from(value as u32)))
             } else {
-                reader.read_uint64().map(|v| T::from_i64(v as i64))
+                Ok(T::from_i64(value as i64))
             }
         } else if const_unwrap_or!(C::MIN, i64::MIN) >= i64::from(i32::MIN)
             && const_unwrap_or!(C::MAX, i64::MAX) <= i64::from(i32::MAX)
         {
-            reader.read_sint32().map(|v| T::from_i64(v as i64))
+            Ok(T::from_i64(i64::from(zigzag_decode32(value as u32))))
         } else {
-            reader.read_sint64().map(|v| T::from_i64(v))
+            Ok(T::from_i64(zigzag_decode64(value)))
         }
     }
 
     #[inline]
     fn read_utf8string<C: utf8string::Constraint>(&mut self) -> Result<String, Self::Error> {
-        let mut reader = self.next_range_format_reader(Format::LengthDelimited);
-        reader.read_string()
+        read_string(self.next_range_format_reader(Format::LengthDelimited))
     }
 
     #[inline]
     fn read_ia5string<C: ia5string::Constraint>(&mut self) -> Result<String, Self::Error> {
-        let mut reader = self.next_range_format_reader(Format::LengthDelimited);
-        reader.read_string()
+        read_string(self.next_range_format_reader(Format::LengthDelimited))
     }
 
     #[inline]
     fn read_numeric_string<C: numericstring::Constraint>(&mut self) -> Result<String, Self::Error> {
-        let mut reader = self.next_range_format_reader(Format::LengthDelimited);
-        reader.read_string()
+        read_string(self.next_range_format_reader(Format::LengthDelimited))
     }
 
     #[inline]
     fn read_printable_string<C: printablestring::Constraint>(
         &mut self,
     ) -> Result<String, Self::Error> {
-        let mut reader = self.next_range_format_reader(Format::LengthDelimited);
-        reader.read_string()
+        read_string(self.next_range_format_reader(Format::LengthDelimited))
+    }
+
+    #[inline]
+    fn read_bmp_string<C: bmpstring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        read_string(self.next_range_format_reader(Format::LengthDelimited))
+    }
+
+    #[inline]
+    fn read_teletex_string<C: teletexstring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        read_string(self.next_range_format_reader(Format::LengthDelimited))
     }
 
     #[inline]
     fn read_visible_string<C: visiblestring::Constraint>(&mut self) -> Result<String, Self::Error> {
-        let mut reader = self.next_range_format_reader(Format::LengthDelimited);
-        reader.read_string()
+        read_string(self.next_range_format_reader(Format::LengthDelimited))
     }
 
     #[inline]
     fn read_octet_string<C: octetstring::Constraint>(&mut self) -> Result<Vec<u8>, Self::Error> {
-        let mut reader = self.next_range_format_reader(Format::LengthDelimited); // TODO Format::VarInt ??
-        reader.read_bytes()
+        // TODO Format::VarInt ??
+        Ok(self
+            .next_range_format_reader(Format::LengthDelimited)
+            .to_vec())
+    }
+
+    #[inline]
+    fn read_containing<C: octetstring::Constraint, X: containing::Transform, T: ReadableType>(
+        &mut self,
+    ) -> Result<T::Type, Self::Error> {
+        let octets = self.read_octet_string::<C>()?;
+        let content = self
+            .transforms
+            .decode::<X>(octets)
+            .map_err(Error::content_transform_failed)?;
+        let mut reader = ProtobufReader::from(content);
+        reader.transforms = self.transforms.clone();
+        T::read_value(&mut reader)
+    }
+
+    #[inline]
+    fn read_substituted<S, X: substituted::Substitute<S, T::Type>, T: ReadableType>(
+        &mut self,
+    ) -> Result<S, Self::Error> {
+        X::from_asn(T::read_value(self)?).map_err(Error::substitution_failed)
     }
 
     #[inline]
     fn read_bit_string<C: bitstring::Constraint>(&mut self) -> Result<(Vec<u8>, u64), Self::Error> {
-        let mut reader = self.next_range_format_reader(Format::LengthDelimited); // TODO Format::VarInt ??
-        let bytes = reader.read_bytes()?;
+        // TODO Format::VarInt ??
+        let bytes = self
+            .next_range_format_reader(Format::LengthDelimited)
+            .to_vec();
         let bits = BitVec::from_vec_with_trailing_bit_len(bytes);
         Ok(bits.split())
     }
@@ -394,11 +478,75 @@ impl<'a> Reader for ProtobufReader<'a> {
             return Ok(false);
         }
 
-        reader.read_bool()
+        Ok(read_varint_from_slice(&mut reader)? != 0)
     }
 
     #[inline]
     fn read_null<C: null::Constraint>(&mut self) -> Result<Null, Self::Error> {
         Ok(Null)
     }
+
+    #[inline]
+    fn read_real<T: real::Float, C: real::Constraint>(&mut self) -> Result<T, Self::Error> {
+        let format = if T::SINGLE_PRECISION {
+            Format::Fixed32
+        } else {
+            Format::Fixed64
+        };
+        let mut reader = self.next_range_format_reader(format);
+
+        // protobuf does not serialize null or 0-ish values
+        if reader.is_empty() {
+            return Ok(T::from_f64(0.0));
+        }
+
+        if T::SINGLE_PRECISION {
+            reader.read_float().map(|v| T::from_f64(f64::from(v)))
+        } else {
+            reader.read_double().map(T::from_f64)
+        }
+    }
+
+    #[inline]
+    fn read_object_identifier<C: objectidentifier::Constraint>(
+        &mut self,
+    ) -> Result<Oid, Self::Error> {
+        let reader = self.next_range_format_reader(Format::LengthDelimited);
+
+        // protobuf does not serialize empty strings
+        if reader.is_empty() {
+            return Ok(Oid::default());
+        }
+
+        let value = read_string(reader)?;
+        value
+            .parse()
+            .map_err(|_| Error::invalid_object_identifier(&value))
+    }
+
+    #[inline]
+    fn read_relative_oid<C: relativeoid::Constraint>(&mut self) -> Result<Oid, Self::Error> {
+        self.read_object_identifier::<objectidentifier::NoConstraint>()
+    }
+
+    #[inline]
+    fn read_oid_iri<C: oidiri::Constraint>(&mut self) -> Result<String, Self::Error> {
+        read_string(self.next_range_format_reader(Format::LengthDelimited))
+    }
+}
+
+#[inline]
+fn read_tag(slice: &mut &[u8]) -> Result<(u32, Format), Error> {
+    let tag = read_varint_from_slice(slice)? as u32;
+    let format = Format::from(tag & 0b0000_0111)?;
+    Ok((tag >> 3, format))
+}
+
+/// Validates the bytes in place, so that the [`String`] is allocated once and with the exact
+/// capacity
+#[inline]
+fn read_string(bytes: &[u8]) -> Result<String, Error> {
+    core::str::from_utf8(bytes)
+        .map(str::to_owned)
+        .map_err(|_| Error::InvalidUtf8Received)
 }
@@ -72,6 +72,9 @@ pub struct ProtobufWriter<'a> {
     buffer: SliceOrVec<'a>,
     state: State,
     is_root: bool,
+    /// Whether the next value is the alternative of a `CHOICE` (a member of a `oneof`)
+    alternative: bool,
+    transforms: containing::Transforms,
 }
 
 impl Default for ProtobufWriter<'_> {
@@ -80,6 +83,8 @@ impl Default for ProtobufWriter<'_> {
             buffer: SliceOrVec::default(),
             state: State::default(),
             is_root: true,
+            alternative: false,
+            transforms: containing::Transforms::default(),
         }
     }
 }
@@ -90,6 +95,8 @@ impl<'a> From<&'a mut [u8]> for ProtobufWriter<'a> {
             buffer: SliceOrVec::Slice(0, slice),
             state: State::default(),
             is_root: true,
+            alternative: false,
+            transforms: containing::Transforms::default(),
         }
     }
 }
@@ -127,6 +134,7 @@ impl<'a> ProtobufWriter<'a> {
     ) -> Result<(), <Self as Writer>::Error> {
         let root = core::mem::take(&mut self.is_root);
         let mut state = core::mem::take(&mut self.state);
+        self.alternative = false;
 
         let result = if !root {
             let tag = state.tag_counter + 1;
@@ -136,7 +144,7 @@ impl<'a> ProtobufWriter<'a> {
             core::mem::swap(&mut content, &mut self.buffer);
 
             if result.is_ok() {
-                let content = content.into_inner_vec().unwrap(); // fine because take creates a vec
+                let content = content.into_inner_vec().unwrap_or_default(); // take creates a vec
                 self.buffer.write_tag(tag, Format::LengthDelimited)?;
                 self.buffer.write_varint(content.len() as u64)?;
                 self.buffer.write_all(&content[..])?;
@@ -160,6 +168,10 @@ impl<'a> ProtobufWriter<'a> {
         &mut self,
         slice: &[<T as WritableType>::Type],
     ) -> Result<(), <Self as Writer>::Error> {
+        if core::mem::take(&mut self.alternative) {
+            return self.write_wrapped_set_or_sequence_of::<T>(slice);
+        }
+
         let state = self.state;
 
         for value in slice {
@@ -172,11 +184,49 @@ impl<'a> ProtobufWriter<'a> {
         //self.state.format = Some(Format::LengthDelimited);
         Ok(())
     }
+
+    /// A repeated field cannot be the member of a `oneof`, so the values of a `SEQUENCE OF` or
+    /// `SET OF` alternative are the repeated field of a nested message - just like the generated
+    /// `.proto` file declares it
+    fn write_wrapped_set_or_sequence_of<T: WritableType>(
+        &mut self,
+        slice: &[<T as WritableType>::Type],
+    ) -> Result<(), <Self as Writer>::Error> {
+        let tag = self.state.tag_counter + 1;
+        let state = core::mem::take(&mut self.state);
+        let mut content = core::mem::take(&mut self.buffer);
+
+        let result = self.write_set_or_sequence_of::<T>(slice);
+        core::mem::swap(&mut content, &mut self.buffer);
+        self.state = state;
+
+        if result.is_ok() {
+            let content = content.into_inner_vec().unwrap_or_default(); // take creates a vec
+            self.buffer.write_tag(tag, Format::LengthDelimited)?;
+            self.buffer.write_varint(content.len() as u64)?;
+            self.buffer.write_all(&content[..])?;
+            self.state.tag_counter = tag;
+            self.state.format = Some(Format::LengthDelimited);
+        }
+
+        result
+    }
+}
+
+impl containing::RegisterTransform for ProtobufWriter<'_> {
+    fn transforms_mut(&mut self) -> &mut containing::Transforms {
+        &mut self.transforms
+    }
 }
 
 impl Writer for ProtobufWriter<'_> {
     type Error = Error;
 
+    #[inline]
+    fn bits_written(&self) -> Option<usize> {
+        Some(self.len_written() * 8)
+    }
+
     #[inline]
     fn write_sequence<C: sequence::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
         &mut self,
@@ -230,6 +280,7 @@ impl Writer for ProtobufWriter<'_> {
     #[inline]
     fn write_choice<C: choice::Constraint>(&mut self, choice: &C) -> Result<(), Self::Error> {
         let root = core::mem::take(&mut self.is_root);
+        self.alternative = true;
 
         let result = if !root {
             let mut state = core::mem::take(&mut self.state);
@@ -244,7 +295,7 @@ impl Writer for ProtobufWriter<'_> {
             core::mem::swap(&mut state, &mut self.state);
 
             if result.is_ok() {
-                let buffer = buffer.into_inner_vec().unwrap(); // fine because take creates a vec
+                let buffer = buffer.into_inner_vec().unwrap_or_default(); // take creates a vec
                 let format = Format::LengthDelimited;
                 let tag = self.state.tag_counter + 1;
                 self.buffer.write_tag(tag, format)?;
@@ -259,6 +310,7 @@ impl Writer for ProtobufWriter<'_> {
             choice.write_content(self)
         };
 
+        self.alternative = false;
         self.state.format = Some(Format::LengthDelimited);
         result
     }
@@ -366,6 +418,30 @@ impl Writer for ProtobufWriter<'_> {
         Ok(())
     }
 
+    #[inline]
+    fn write_bmp_string<C: bmpstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        let tag = self.state.tag_counter + 1;
+        self.buffer.write_tagged_string(tag, value)?;
+        self.state.tag_counter = tag;
+        self.state.format = Some(Format::LengthDelimited);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_teletex_string<C: teletexstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        let tag = self.state.tag_counter + 1;
+        self.buffer.write_tagged_string(tag, value)?;
+        self.state.tag_counter = tag;
+        self.state.format = Some(Format::LengthDelimited);
+        Ok(())
+    }
+
     #[inline]
     fn write_visible_string<C: visiblestring::Constraint>(
         &mut self,
@@ -390,6 +466,23 @@ impl Writer for ProtobufWriter<'_> {
         Ok(())
     }
 
+    #[inline]
+    fn write_containing<C: octetstring::Constraint, X: containing::Transform, T: WritableType>(
+        &mut self,
+        value: &T::Type,
+    ) -> Result<(), Self::Error> {
+        let mut writer = ProtobufWriter {
+            transforms: self.transforms.clone(),
+            ..ProtobufWriter::default()
+        };
+        T::write_value(&mut writer, value)?;
+        let octets = self
+            .transforms
+            .encode::<X>(writer.into_bytes_vec())
+            .map_err(Error::content_transform_failed)?;
+        self.write_octet_string::<C>(&octets)
+    }
+
     #[inline]
     fn write_bit_string<C: bitstring::Constraint>(
         &mut self,
@@ -417,6 +510,61 @@ impl Writer for ProtobufWriter<'_> {
 
     #[inline]
     fn write_null<C: null::Constraint>(&mut self, _value: &Null) -> Result<(), Self::Error> {
+        // the member of a oneof that is set, is serialized even if empty
+        if core::mem::take(&mut self.alternative) {
+            let tag = self.state.tag_counter + 1;
+            self.buffer.write_tag(tag, Format::LengthDelimited)?;
+            self.buffer.write_varint(0)?;
+            self.state.tag_counter = tag;
+            self.state.format = Some(Format::LengthDelimited);
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn write_real<T: real::Float, C: real::Constraint>(
+        &mut self,
+        value: T,
+    ) -> Result<(), Self::Error> {
+        let tag = self.state.tag_counter + 1;
+        if T::SINGLE_PRECISION {
+            self.buffer.write_tagged_float(tag, value.to_f64() as f32)?;
+            self.state.format = Some(Format::Fixed32);
+        } else {
+            self.buffer.write_tagged_double(tag, value.to_f64())?;
+            self.state.format = Some(Format::Fixed64);
+        }
+        self.state.tag_counter = tag;
+        Ok(())
+    }
+
+    #[inline]
+    fn write_object_identifier<C: objectidentifier::Constraint>(
+        &mut self,
+        value: &Oid,
+    ) -> Result<(), Self::Error> {
+        // in the dotted notation, like the well known type `google.protobuf.Any` names its type
+        let tag = self.state.tag_counter + 1;
+        self.buffer.write_tagged_string(tag, &value.to_string())?;
+        self.state.tag_counter = tag;
+        self.state.format = Some(Format::LengthDelimited);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_relative_oid<C: relativeoid::Constraint>(
+        &mut self,
+        value: &Oid,
+    ) -> Result<(), Self::Error> {
+        self.write_object_identifier::<objectidentifier::NoConstraint>(value)
+    }
+
+    #[inline]
+    fn write_oid_iri<C: oidiri::Constraint>(&mut self, value: &str) -> Result<(), Self::Error> {
+        let tag = self.state.tag_counter + 1;
+        self.buffer.write_tagged_string(tag, value)?;
+        self.state.tag_counter = tag;
+        self.state.format = Some(Format::LengthDelimited);
         Ok(())
     }
 }
@@ -0,0 +1,374 @@
+use crate::descriptor::*;
+use crate::protocol::per::unaligned::BYTE_LEN;
+use crate::protocol::per::unaligned::LENGTH_16K;
+use crate::rw::depth::DEFAULT_MAX_DEPTH;
+
+/// The bits of a length determinant of less than 16K, plus the octet of each 16K fragment
+/// beyond
+#[inline]
+const fn length_bits(len: usize) -> usize {
+    2 * BYTE_LEN + (len / LENGTH_16K as usize) * BYTE_LEN
+}
+
+/// The bits of the index of one of `count` values
+#[inline]
+const fn index_bits(count: u64) -> usize {
+    (u64::BITS - count.saturating_sub(1).leading_zeros()) as usize
+}
+
+/// The extension bit of an extensible `SEQUENCE` or `SET`, the bit-field of its present
+/// additions and the open type lengths and padding of the additions
+#[inline]
+const fn extension_bits(field_count: u64, extended_after: Option<u64>) -> usize {
+    match extended_after {
+        Some(extended_after) => {
+            let additions = field_count.saturating_sub(extended_after + 1) as usize;
+            1 + 7 + additions * (1 + length_bits(0) + BYTE_LEN)
+        }
+        None => 0,
+    }
+}
+
+/// The most octets of the contents of a `REAL` in binary form: the header, two octets exponent
+/// and seven octets mantissa of an `f64`
+const REAL_OCTETS: usize = 10;
+
+/// The most octets of an arc of an `OBJECT IDENTIFIER`, base 128 encoded
+const OID_ARC_OCTETS: usize = u64::BITS.div_ceil(7) as usize;
+
+/// A [`Writer`] that sums up the bits of the UPER encoding of the written values - as upper
+/// bound, without encoding them. [`UperWriter`](crate::rw::UperWriter) reserves its buffer with
+/// it before writing a value, so that the buffer grows only once.
+///
+/// ```rust
+/// use asn1rs::prelude::*;
+///
+/// #[asn(sequence)]
+/// #[derive(Debug, Default, PartialEq)]
+/// pub struct Frame {
+///     #[asn(integer(0..255))]
+///     id: u8,
+///     #[asn(octet_string)]
+///     payload: Vec<u8>,
+/// }
+///
+/// let frame = Frame { id: 7, payload: vec![0xCA; 300] };
+/// let mut writer = UperWriter::default();
+/// writer.write(&frame).unwrap();
+/// assert!(writer.byte_content().len() <= UperSizeHint::of(&frame).bytes());
+/// ```
+#[derive(Debug)]
+pub struct UperSizeHint {
+    bits: usize,
+    depth: usize,
+    max_depth: usize,
+}
+
+impl Default for UperSizeHint {
+    fn default() -> Self {
+        Self::with_max_depth(DEFAULT_MAX_DEPTH)
+    }
+}
+
+impl UperSizeHint {
+    const fn with_max_depth(max_depth: usize) -> Self {
+        Self {
+            bits: 0,
+            depth: 0,
+            max_depth,
+        }
+    }
+
+    /// The size hint for the given value
+    pub fn of<T: Writable>(value: &T) -> Self {
+        Self::of_with_max_depth(value, DEFAULT_MAX_DEPTH)
+    }
+
+    /// The size hint for the given value, without the values nested deeper than `max_depth` -
+    /// which [`UperWriter`](crate::rw::UperWriter) fails to write anyway, see
+    /// [`DepthLimit::set_max_depth`](crate::rw::DepthLimit::set_max_depth)
+    pub fn of_with_max_depth<T: Writable>(value: &T, max_depth: usize) -> Self {
+        let mut hint = Self::with_max_depth(max_depth);
+        // infallible, and unlike `Writer::write` not counted by the `stats`
+        let _ = value.write(&mut hint);
+        hint
+    }
+
+    pub const fn bits(&self) -> usize {
+        self.bits
+    }
+
+    pub const fn bytes(&self) -> usize {
+        self.bits.div_ceil(BYTE_LEN)
+    }
+
+    #[inline]
+    fn add(&mut self, bits: usize) -> Result<(), core::convert::Infallible> {
+        self.bits += bits;
+        Ok(())
+    }
+
+    /// Stops counting once the value is nested deeper than `max_depth`
+    #[inline]
+    fn nested(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<(), core::convert::Infallible>,
+    ) -> Result<(), core::convert::Infallible> {
+        if self.depth >= self.max_depth {
+            return Ok(());
+        }
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
+    }
+
+    #[inline]
+    fn add_octets(&mut self, len: usize) -> Result<(), core::convert::Infallible> {
+        self.add(length_bits(len) + len * BYTE_LEN)
+    }
+}
+
+impl Writer for UperSizeHint {
+    type Error = core::convert::Infallible;
+
+    #[inline]
+    fn write_sequence<C: sequence::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        self.add(extension_bits(C::FIELD_COUNT, C::EXTENDED_AFTER_FIELD))?;
+        self.nested(|hint| f(hint))
+    }
+
+    #[inline]
+    fn write_sequence_of<C: sequenceof::Constraint, T: WritableType>(
+        &mut self,
+        slice: &[T::Type],
+    ) -> Result<(), Self::Error> {
+        self.add(1 + length_bits(slice.len()))?;
+        self.nested(|hint| {
+            for value in slice {
+                T::write_value(hint, value)?;
+            }
+            Ok(())
+        })
+    }
+
+    #[inline]
+    fn write_set<C: set::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        self.add(extension_bits(C::FIELD_COUNT, C::EXTENDED_AFTER_FIELD))?;
+        self.nested(|hint| f(hint))
+    }
+
+    #[inline]
+    fn write_set_of<C: setof::Constraint, T: WritableType>(
+        &mut self,
+        slice: &[T::Type],
+    ) -> Result<(), Self::Error> {
+        self.add(1 + length_bits(slice.len()))?;
+        self.nested(|hint| {
+            for value in slice {
+                T::write_value(hint, value)?;
+            }
+            Ok(())
+        })
+    }
+
+    #[inline]
+    fn write_enumerated<C: enumerated::Constraint>(
+        &mut self,
+        enumerated: &C,
+    ) -> Result<(), Self::Error> {
+        if enumerated.to_choice_index() < C::STD_VARIANT_COUNT {
+            self.add(1 + index_bits(C::STD_VARIANT_COUNT))
+        } else {
+            // a normally small non-negative whole number
+            self.add(1 + 1 + length_bits(0) + u64::BITS as usize)
+        }
+    }
+
+    #[inline]
+    fn write_choice<C: choice::Constraint>(&mut self, choice: &C) -> Result<(), Self::Error> {
+        if choice.to_choice_index() < C::STD_VARIANT_COUNT {
+            self.add(1 + index_bits(C::STD_VARIANT_COUNT))?;
+        } else {
+            // a normally small non-negative whole number and the open type length and padding
+            self.add(1 + 1 + 2 * length_bits(0) + u64::BITS as usize + BYTE_LEN)?;
+        }
+        self.nested(|hint| choice.write_content(hint))
+    }
+
+    #[inline]
+    fn write_opt<T: WritableType>(&mut self, value: Option<&T::Type>) -> Result<(), Self::Error> {
+        self.add(1)?;
+        match value {
+            Some(value) => T::write_value(self, value),
+            None => Ok(()),
+        }
+    }
+
+    #[inline]
+    fn write_default<C: default::Constraint<Owned = T::Type>, T: WritableType>(
+        &mut self,
+        value: &T::Type,
+    ) -> Result<(), Self::Error> {
+        self.add(1)?;
+        if C::default_value().eq(value) {
+            Ok(())
+        } else {
+            T::write_value(self, value)
+        }
+    }
+
+    #[inline]
+    fn write_number<T: numbers::Number, C: numbers::Constraint<T>>(
+        &mut self,
+        _value: T,
+    ) -> Result<(), Self::Error> {
+        match (C::MIN, C::MAX) {
+            (Some(min), Some(max)) if !C::EXTENSIBLE => {
+                self.add(index_bits(max.abs_diff(min).saturating_add(1)))
+            }
+            _ => self.add(1 + length_bits(0) + u64::BITS as usize),
+        }
+    }
+
+    #[inline]
+    fn write_utf8string<C: utf8string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.add(1)?;
+        self.add_octets(value.len())
+    }
+
+    #[inline]
+    fn write_ia5string<C: ia5string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.add(1)?;
+        self.add_octets(value.len())
+    }
+
+    #[inline]
+    fn write_numeric_string<C: numericstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.add(1)?;
+        self.add_octets(value.len())
+    }
+
+    #[inline]
+    fn write_visible_string<C: visiblestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.add(1)?;
+        self.add_octets(value.len())
+    }
+
+    #[inline]
+    fn write_printable_string<C: printablestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.add(1)?;
+        self.add_octets(value.len())
+    }
+
+    #[inline]
+    fn write_bmp_string<C: bmpstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.add(1)?;
+        self.add_octets(2 * value.len())
+    }
+
+    #[inline]
+    fn write_teletex_string<C: teletexstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.add(1)?;
+        self.add_octets(value.len())
+    }
+
+    #[inline]
+    fn write_octet_string<C: octetstring::Constraint>(
+        &mut self,
+        value: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.add(1)?;
+        self.add_octets(value.len())
+    }
+
+    #[inline]
+    fn write_containing<C: octetstring::Constraint, X: containing::Transform, T: WritableType>(
+        &mut self,
+        value: &T::Type,
+    ) -> Result<(), Self::Error> {
+        let mut content = Self {
+            depth: self.depth,
+            ..Self::with_max_depth(self.max_depth)
+        };
+        T::write_value(&mut content, value)?;
+        self.add(1)?;
+        self.add_octets(content.bytes())
+    }
+
+    #[inline]
+    fn write_bit_string<C: bitstring::Constraint>(
+        &mut self,
+        _value: &[u8],
+        bit_len: u64,
+    ) -> Result<(), Self::Error> {
+        let bit_len = bit_len as usize;
+        self.add(1 + length_bits(bit_len) + bit_len)
+    }
+
+    #[inline]
+    fn write_boolean<C: boolean::Constraint>(&mut self, _value: bool) -> Result<(), Self::Error> {
+        self.add(1)
+    }
+
+    #[inline]
+    fn write_null<C: null::Constraint>(&mut self, _value: &Null) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn write_real<T: real::Float, C: real::Constraint>(
+        &mut self,
+        _value: T,
+    ) -> Result<(), Self::Error> {
+        self.add_octets(REAL_OCTETS)
+    }
+
+    #[inline]
+    fn write_object_identifier<C: objectidentifier::Constraint>(
+        &mut self,
+        value: &Oid,
+    ) -> Result<(), Self::Error> {
+        self.add_octets(value.as_slice().len() * OID_ARC_OCTETS)
+    }
+
+    #[inline]
+    fn write_relative_oid<C: relativeoid::Constraint>(
+        &mut self,
+        value: &Oid,
+    ) -> Result<(), Self::Error> {
+        self.add_octets(value.as_slice().len() * OID_ARC_OCTETS)
+    }
+
+    #[inline]
+    fn write_oid_iri<C: oidiri::Constraint>(&mut self, value: &str) -> Result<(), Self::Error> {
+        self.add_octets(value.len())
+    }
+}
@@ -1,18 +1,31 @@
+use crate::debug::{Children, Trace};
 use crate::descriptor::*;
 use crate::protocol::per::err::Error;
 use crate::protocol::per::err::ErrorKind;
 use crate::protocol::per::unaligned::buffer::BitBuffer;
+use crate::protocol::per::unaligned::BitRead;
 use crate::protocol::per::unaligned::BitWrite;
 use crate::protocol::per::unaligned::BYTE_LEN;
+use crate::protocol::per::unaligned::LENGTH_127;
+use crate::protocol::per::unaligned::LENGTH_16K;
 use crate::protocol::per::PackedRead;
 use crate::protocol::per::PackedWrite;
+use crate::rw::depth::{Depth, DepthLimit};
+use crate::rw::mutation::Landmark;
+use crate::rw::position::{PositionedReader, PositionedWriter};
+use crate::rw::UperSizeHint;
 use asn1rs_model::asn::Charset;
+use core::marker::PhantomData;
+use std::borrow::Cow;
 use std::fmt::Debug;
 use std::ops::Range;
 
 pub use crate::protocol::per::unaligned::buffer::Bits;
 pub use crate::protocol::per::unaligned::ScopedBitRead;
 
+/// The state of the bit field of the sequence that is currently written or read. This is an
+/// implementation detail of [`UperWriter`] and [`UperReader`].
+#[doc(hidden)]
 #[derive(Debug, Clone)]
 pub enum Scope {
     OptBitField(Range<usize>),
@@ -44,6 +57,13 @@ pub enum Scope {
     },
     /// Indicates that the extensible sequence has no extension body
     ExtensibleSequenceEmpty(&'static str),
+    /// The presence flags of the OPTIONAL and DEFAULT fields of a non-extensible sequence, which
+    /// are read at once when entering the sequence instead of looking up each flag in the buffer.
+    /// The next flag is the most significant of the `remaining` lowest bits. Only used for reading.
+    PresenceBitmap {
+        bits: u64,
+        remaining: u8,
+    },
 }
 
 impl Scope {
@@ -63,9 +83,22 @@ impl Scope {
                 None => true,
             },
             Scope::ExtensibleSequenceEmpty(_) => true,
+            Scope::PresenceBitmap { remaining, .. } => *remaining == 0,
         }
     }
 
+    /// Reads the given number of presence flags - at most 64 - into a [`Scope::PresenceBitmap`]
+    #[inline]
+    fn read_presence_bitmap(bits: &mut impl ScopedBitRead, count: u8) -> Result<Self, Error> {
+        debug_assert!(count <= 64);
+        let mut bytes = [0_u8; 8];
+        bits.read_bits_with_offset(&mut bytes, 64 - usize::from(count))?;
+        Ok(Scope::PresenceBitmap {
+            bits: u64::from_be_bytes(bytes),
+            remaining: count,
+        })
+    }
+
     #[inline]
     pub const fn encode_as_open_type_field(&self) -> bool {
         matches!(
@@ -155,6 +188,10 @@ impl Scope {
                     Ok(())
                 }
             }
+            Scope::PresenceBitmap { .. } => Err(ErrorKind::UnsupportedOperation(
+                "Writing into a presence bitmap that has been read".to_string(),
+            )
+            .into()),
         }
     }
 
@@ -239,6 +276,16 @@ impl Scope {
                 }
             }
             Scope::ExtensibleSequenceEmpty(_) => Ok(Some(false)),
+            Scope::PresenceBitmap { bits, remaining } => {
+                if *remaining == 0 {
+                    Ok(Some(false))
+                } else if is_opt {
+                    *remaining -= 1;
+                    Ok(Some((*bits >> *remaining) & 1 == 1))
+                } else {
+                    Ok(None)
+                }
+            }
         }
     }
 }
@@ -247,6 +294,10 @@ impl Scope {
 pub struct UperWriter {
     bits: BitBuffer,
     scope: Option<Scope>,
+    landmarks: Option<Vec<Landmark>>,
+    depth: Depth,
+    substitute: Option<char>,
+    transforms: containing::Transforms,
 }
 
 impl UperWriter {
@@ -257,6 +308,65 @@ impl UperWriter {
         }
     }
 
+    /// Creates a writer that remembers the position of extension bits and length determinants
+    /// while encoding, see [`UperWriter::landmarks`]
+    pub fn with_landmarks() -> Self {
+        Self {
+            landmarks: Some(Vec::new()),
+            ..Default::default()
+        }
+    }
+
+    pub const fn substitute(&self) -> Option<char> {
+        self.substitute
+    }
+
+    /// If set, the characters of a string that are not valid for its charset - such as an `ü`
+    /// in an `IA5String` - are written as the substitute instead of failing with
+    /// [`ErrorKind::InvalidString`]. Strings still fail if the substitute itself is not valid for
+    /// their charset.
+    pub fn set_substitute(&mut self, substitute: Option<char>) {
+        self.substitute = substitute;
+    }
+
+    /// The string to write for the value of a string with the given charset, see
+    /// [`UperWriter::set_substitute`]
+    fn valid_string<'a>(&self, charset: Charset, value: &'a str) -> Result<Cow<'a, str>, Error> {
+        match self.substitute {
+            Some(substitute) if charset.is_valid(substitute) => {
+                Ok(charset.substitute_invalid(value, substitute))
+            }
+            _ => Error::ensure_string_valid(charset, value).map(|()| Cow::Borrowed(value)),
+        }
+    }
+
+    #[inline]
+    fn nested<T, F: FnOnce(&mut Self) -> Result<T, Error>>(&mut self, f: F) -> Result<T, Error> {
+        self.depth
+            .enter()
+            .map_err(ErrorKind::NestingDepthExceedsLimit)?;
+        let result = f(self);
+        self.depth.leave();
+        result
+    }
+
+    /// An empty writer for a part of the encoding that is written separately, with the nesting
+    /// depth and the settings of this writer
+    fn child(&self) -> UperWriter {
+        UperWriter {
+            depth: self.depth,
+            substitute: self.substitute,
+            transforms: self.transforms.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// The extension bits and length determinants written so far, always empty if this writer
+    /// was not created by [`UperWriter::with_landmarks`]
+    pub fn landmarks(&self) -> &[Landmark] {
+        self.landmarks.as_deref().unwrap_or_default()
+    }
+
     pub fn byte_content(&self) -> &[u8] {
         self.bits.content()
     }
@@ -273,10 +383,72 @@ impl UperWriter {
         self.bits.into()
     }
 
+    /// The written octets - with the unused bits of the final octet set to `0` - and the exact
+    /// number of written bits. Unlike the octets alone, this allows to compose encodings without
+    /// octet alignment, see [`UperWriter::append_bits`].
+    ///
+    /// ```rust
+    /// use asn1rs::prelude::*;
+    /// use asn1rs::descriptor::boolean::NoConstraint;
+    ///
+    /// let mut writer = UperWriter::default();
+    /// writer.write_boolean::<NoConstraint>(true).unwrap();
+    /// writer.write_boolean::<NoConstraint>(true).unwrap();
+    /// assert_eq!((vec![0b1100_0000], 2), writer.into_bytes_with_bit_len());
+    /// ```
+    pub fn into_bytes_with_bit_len(self) -> (Vec<u8>, usize) {
+        let bit_len = self.bit_len();
+        (self.into_bytes_vec(), bit_len)
+    }
+
+    /// Writes the first `bit_len` bits of the given octets at the current position, without
+    /// aligning them to an octet boundary - such as an encoding returned by
+    /// [`UperWriter::into_bytes_with_bit_len`], to pack multiple encodings into one
+    pub fn append_bits(&mut self, bytes: &[u8], bit_len: usize) -> Result<(), Error> {
+        if bit_len > bytes.len() * BYTE_LEN {
+            return Err(Error::insufficient_data_in_source_buffer());
+        }
+        self.bits.write_bits_with_len(bytes, bit_len)
+    }
+
+    /// Writes `0` bits up to the next octet boundary and returns their number, so that the next
+    /// encoding starts at an octet boundary
+    pub fn align_to_octet(&mut self) -> Result<usize, Error> {
+        self.bits.align_write_position()
+    }
+
+    /// Writes the value at the current bit position - without aligning it to an octet boundary -
+    /// as it would be written on its own, also if this is called while another value is written,
+    /// and returns the number of written bits. This allows custom containers and wrapper PDUs to
+    /// be built from the generated types, see [`UperReader::read_nested`] for the counterpart.
+    ///
+    /// ```rust
+    /// use asn1rs::prelude::*;
+    /// use asn1rs::descriptor::boolean::NoConstraint;
+    ///
+    /// #[asn(sequence)]
+    /// #[derive(Debug, Default, PartialEq)]
+    /// pub struct Header {
+    ///     #[asn(integer(0..7))]
+    ///     version: u8,
+    /// }
+    ///
+    /// let mut writer = UperWriter::default();
+    /// writer.write_boolean::<NoConstraint>(true).unwrap();
+    /// assert_eq!(3, writer.write_nested(&Header { version: 5 }).unwrap());
+    /// assert_eq!((vec![0b1101_0000], 4), writer.into_bytes_with_bit_len());
+    /// ```
+    pub fn write_nested<T: Writable>(&mut self, value: &T) -> Result<usize, Error> {
+        let start = self.bits.write_position;
+        self.scope_stashed(|writer| crate::descriptor::write_recorded(writer, value))?;
+        Ok(self.bits.write_position - start)
+    }
+
     pub fn as_reader(&self) -> UperReader<Bits> {
         UperReader::from(Bits::from((self.byte_content(), self.bit_len())))
     }
 
+    #[doc(hidden)]
     #[inline]
     pub fn scope_pushed<T, E, F: FnOnce(&mut Self) -> Result<T, E>>(
         &mut self,
@@ -287,11 +459,10 @@ impl UperWriter {
         let result = f(self);
         if cfg!(debug_assertions) && result.is_ok() {
             let scope = core::mem::replace(&mut self.scope, original);
-            // call to .unwrap() is save because this is supposed to be the original from above
+            // this is supposed to be the scope from above
             debug_assert!(
-                scope.clone().unwrap().exhausted(),
-                "Not exhausted: {:?}",
-                scope.unwrap()
+                scope.as_ref().is_some_and(Scope::exhausted),
+                "Not exhausted: {scope:?}"
             );
         } else {
             self.scope = original;
@@ -299,6 +470,7 @@ impl UperWriter {
         result
     }
 
+    #[doc(hidden)]
     #[inline]
     pub fn scope_stashed<R, F: FnOnce(&mut Self) -> R>(&mut self, f: F) -> R {
         let scope = self.scope.take();
@@ -307,6 +479,7 @@ impl UperWriter {
         result
     }
 
+    #[doc(hidden)]
     #[inline]
     pub fn write_bit_field_entry(&mut self, is_opt: bool, is_present: bool) -> Result<(), Error> {
         if let Some(scope) = &mut self.scope {
@@ -318,6 +491,7 @@ impl UperWriter {
         }
     }
 
+    #[doc(hidden)]
     #[inline]
     #[allow(clippy::redundant_pattern_matching)] // allow for const_*!
     pub fn with_buffer<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
@@ -325,16 +499,153 @@ impl UperWriter {
         f: F,
     ) -> Result<T, Error> {
         if const_map_or!(self.scope, Scope::encode_as_open_type_field, false) {
-            let mut writer = UperWriter::with_capacity(512);
-            let result = f(&mut writer)?;
-            self.bits
-                .write_octetstring(None, None, false, writer.bits.content())?;
-            Ok(result)
+            self.write_length_prefixed(f)
         } else {
             f(self)
         }
     }
 
+    /// Writes the content of the given closure as open type field: an unconstrained length
+    /// determinant of the number of octets followed by the content (padded to whole octets).
+    ///
+    /// Instead of encoding the content into a temporary writer, the short form of the length
+    /// determinant is reserved and patched once the content has been written. Only if the
+    /// content turns out to require the long form, the content is moved to make space for it.
+    pub fn write_length_prefixed<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<T, Error> {
+        let start = self.bits.write_position;
+        let landmarks = self.landmarks().len();
+
+        // reserve the short form 0xxxxxxx
+        self.bits.write_bits(&[0x00])?;
+        let content_start = self.bits.write_position;
+        let result = self.scope_stashed(f)?;
+
+        let content_bits = self.bits.write_position - content_start;
+        for _ in 0..content_bits.div_ceil(BYTE_LEN) * BYTE_LEN - content_bits {
+            self.bits.write_bit(false)?;
+        }
+
+        let len = ((self.bits.write_position - content_start) / BYTE_LEN) as u64;
+
+        if len >= LENGTH_16K {
+            // fragmented, the content has to be split into multiple chunks
+            let mut content = vec![0u8; len as usize];
+            let mut position = content_start;
+            BitRead::read_bits(&mut (&self.bits.buffer[..], &mut position), &mut content)?;
+            self.bits.buffer.truncate(start.div_ceil(BYTE_LEN));
+            self.bits.write_position = start;
+            if let Some(recorded) = &mut self.landmarks {
+                recorded.truncate(landmarks);
+            }
+            self.write_octetstring_with_landmarks(None, None, false, &content)?;
+            return Ok(result);
+        }
+
+        if len > LENGTH_127 {
+            // the long form 10xxxxxx xxxxxxxx requires another octet, inserting a copy of the
+            // octet at the beginning of the content shifts the content by exactly 8 bits,
+            // the affected bits are overwritten by the length determinant afterwards
+            let index = content_start / BYTE_LEN;
+            self.bits.buffer.insert(index, self.bits.buffer[index]);
+            self.bits.write_position += BYTE_LEN;
+            if let Some(recorded) = &mut self.landmarks {
+                for landmark in recorded.iter_mut().skip(landmarks) {
+                    *landmark = landmark.clone().offset(BYTE_LEN);
+                }
+            }
+        }
+
+        self.bits
+            .with_write_position_at(start, |b| b.write_length_determinant(None, None, len))?;
+
+        if let Some(recorded) = &mut self.landmarks {
+            let length_determinant = Landmark::LengthDeterminant {
+                bits: start..start + if len > LENGTH_127 { 16 } else { 8 },
+                constrained: false,
+            };
+            recorded.insert(landmarks, length_determinant);
+        }
+
+        Ok(result)
+    }
+
+    #[inline]
+    fn write_octetstring_with_landmarks(
+        &mut self,
+        min: Option<u64>,
+        max: Option<u64>,
+        extensible: bool,
+        value: &[u8],
+    ) -> Result<(), Error> {
+        let start = self.bits.write_position;
+        self.bits.write_octetstring(min, max, extensible, value)?;
+        self.record_size(
+            start,
+            min,
+            max,
+            extensible,
+            value.len() as u64,
+            value.len() * BYTE_LEN,
+        );
+        Ok(())
+    }
+
+    #[inline]
+    fn write_bitstring_with_landmarks(
+        &mut self,
+        min: Option<u64>,
+        max: Option<u64>,
+        extensible: bool,
+        value: &[u8],
+        bit_len: u64,
+    ) -> Result<(), Error> {
+        let start = self.bits.write_position;
+        self.bits
+            .write_bitstring(min, max, extensible, value, 0, bit_len)?;
+        self.record_size(start, min, max, extensible, bit_len, bit_len as usize);
+        Ok(())
+    }
+
+    /// Records the landmarks of a size that was written at `start` and is followed by
+    /// `payload_bits` of content
+    #[inline]
+    fn record_size(
+        &mut self,
+        start: usize,
+        min: Option<u64>,
+        max: Option<u64>,
+        extensible: bool,
+        len: u64,
+        payload_bits: usize,
+    ) {
+        if let Some(landmarks) = &mut self.landmarks {
+            let out_of_range = len < min.unwrap_or(0) || len > max.unwrap_or(u64::MAX);
+            let mut pos = start;
+            if extensible {
+                landmarks.push(Landmark::ExtensionBit(pos));
+                pos += 1;
+            }
+            // fragmented content is not contiguous, its length determinants are not tracked
+            let end = self.bits.write_position - payload_bits;
+            if len < LENGTH_16K && end > pos {
+                landmarks.push(Landmark::LengthDeterminant {
+                    bits: pos..end,
+                    constrained: !out_of_range && (min.is_some() || max.is_some()),
+                });
+            }
+        }
+    }
+
+    #[inline]
+    fn record(&mut self, landmark: Landmark) {
+        if let Some(landmarks) = &mut self.landmarks {
+            landmarks.push(landmark);
+        }
+    }
+
     #[inline]
     pub fn write_extensible_bit_and_length_or_err(
         &mut self,
@@ -349,9 +660,11 @@ impl UperWriter {
         let out_of_range = len < unwrapped_min || len > unwrapped_max;
 
         if extensible {
+            self.record(Landmark::ExtensionBit(self.bits.write_position));
             self.bits.write_bit(out_of_range)?;
         }
 
+        let start = self.bits.write_position;
         if out_of_range {
             if !extensible {
                 return Err(ErrorKind::SizeNotInRange(len, unwrapped_min, unwrapped_max).into());
@@ -362,57 +675,175 @@ impl UperWriter {
             self.bits.write_length_determinant(min, max, len)?;
         }
 
+        if self.bits.write_position > start && len < LENGTH_16K {
+            self.record(Landmark::LengthDeterminant {
+                bits: start..self.bits.write_position,
+                constrained: !out_of_range && (min.is_some() || max.is_some()),
+            });
+        }
+
         Ok(out_of_range)
     }
 }
 
+#[cfg(feature = "rayon")]
+impl UperWriter {
+    /// Writes the values as `SEQUENCE OF` (or `SET OF`) with the constraint `C`, encoding the
+    /// elements in parallel on the rayon thread pool. The elements are split into one chunk per
+    /// thread, each chunk is encoded into its own buffer and the buffers are appended in order,
+    /// so that the result is bit for bit the same as of the sequential encoding.
+    ///
+    /// This only pays off for large collections of independent elements. If landmarks are
+    /// recorded (see [`UperWriter::with_landmarks`]), the elements are encoded sequentially.
+    pub fn write_sequence_of_par<C: sequenceof::Constraint, T: Writable + Sync>(
+        &mut self,
+        values: &[T],
+    ) -> Result<(), Error> {
+        use rayon::prelude::*;
+
+        self.write_bit_field_entry(false, true)?;
+        self.nested(|w| {
+            w.scope_stashed(|w| {
+                w.write_extensible_bit_and_length_or_err(
+                    C::EXTENSIBLE,
+                    C::MIN,
+                    C::MAX,
+                    i64::MAX as u64,
+                    values.len() as u64,
+                )?;
+
+                if w.landmarks.is_some() {
+                    return values.iter().try_for_each(|value| value.write(w));
+                }
+
+                let chunk_len = values.len().div_ceil(rayon::current_num_threads()).max(1);
+
+                values
+                    .par_chunks(chunk_len)
+                    .map(|chunk| {
+                        let mut writer = w.child();
+                        chunk
+                            .iter()
+                            .try_for_each(|value| value.write(&mut writer))
+                            .map(|_| writer)
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?
+                    .into_iter()
+                    .try_for_each(|writer| {
+                        w.bits
+                            .write_bits_with_len(writer.byte_content(), writer.bit_len())
+                    })
+            })
+        })
+    }
+}
+
+impl PositionedWriter for UperWriter {
+    type Mark = WriterMark;
+
+    #[inline]
+    fn bit_position(&self) -> usize {
+        self.bits.write_position
+    }
+
+    #[inline]
+    fn mark(&self) -> WriterMark {
+        WriterMark {
+            position: self.bits.write_position,
+            scope: self.scope.clone(),
+            landmarks: self.landmarks().len(),
+        }
+    }
+
+    fn rollback(&mut self, mark: WriterMark) {
+        self.bits.truncate(mark.position);
+        self.scope = mark.scope;
+        if let Some(landmarks) = &mut self.landmarks {
+            landmarks.truncate(mark.landmarks);
+        }
+    }
+}
+
+impl containing::RegisterTransform for UperWriter {
+    fn transforms_mut(&mut self) -> &mut containing::Transforms {
+        &mut self.transforms
+    }
+}
+
+impl DepthLimit for UperWriter {
+    fn depth(&self) -> &Depth {
+        &self.depth
+    }
+
+    fn depth_mut(&mut self) -> &mut Depth {
+        &mut self.depth
+    }
+}
+
 impl Writer for UperWriter {
     type Error = Error;
 
+    /// Writes the value like [`Writer::write`] does, but reserves the buffer for the estimated
+    /// size of its encoding first, see [`UperSizeHint`]
+    #[inline]
+    fn write<T: Writable>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.bits
+            .reserve(UperSizeHint::of_with_max_depth(value, self.depth.max).bits());
+        crate::descriptor::write_recorded(self, value)
+    }
+
+    #[inline]
+    fn bits_written(&self) -> Option<usize> {
+        Some(self.bits.write_position)
+    }
+
     #[inline]
     fn write_sequence<C: sequence::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
         &mut self,
         f: F,
     ) -> Result<(), Self::Error> {
         self.write_bit_field_entry(false, true)?;
-        self.with_buffer(|w| {
-            let extension = if let Some(extension_after) = C::EXTENDED_AFTER_FIELD {
-                let bit_pos = w.bits.write_position;
-                // if no extension field is present, none will call into overwriting this
-                w.bits.write_bit(false)?;
-                Some((extension_after, bit_pos))
-            } else {
-                None
-            };
+        self.nested(|w| {
+            w.with_buffer(|w| {
+                let extension = if let Some(extension_after) = C::EXTENDED_AFTER_FIELD {
+                    let bit_pos = w.bits.write_position;
+                    // if no extension field is present, none will call into overwriting this
+                    w.bits.write_bit(false)?;
+                    w.record(Landmark::ExtensionBit(bit_pos));
+                    Some((extension_after, bit_pos))
+                } else {
+                    None
+                };
 
-            // In UPER the values for all OPTIONAL flags are written before any field
-            // value is written. This remembers their position, so a later call of `write_opt`
-            // can write them to the buffer
-            let write_pos = w.bits.write_position;
-            let range = write_pos..write_pos + C::STD_OPTIONAL_FIELDS as usize;
-            for _ in 0..C::STD_OPTIONAL_FIELDS {
-                // insert in reverse order so that a simple pop() in `write_opt` retrieves
-                // the relevant position
-                if let Err(e) = w.bits.write_bit(false) {
-                    w.bits.write_position = write_pos; // undo write_bits
-                    return Err(e);
+                // In UPER the values for all OPTIONAL flags are written before any field
+                // value is written. This remembers their position, so a later call of `write_opt`
+                // can write them to the buffer
+                let write_pos = w.bits.write_position;
+                let range = write_pos..write_pos + C::STD_OPTIONAL_FIELDS as usize;
+                for _ in 0..C::STD_OPTIONAL_FIELDS {
+                    // insert in reverse order so that a simple pop() in `write_opt` retrieves
+                    // the relevant position
+                    if let Err(e) = w.bits.write_bit(false) {
+                        w.bits.write_position = write_pos; // undo write_bits
+                        return Err(e);
+                    }
                 }
-            }
 
-            if let Some((extension_after, bit_pos)) = extension {
-                w.scope_pushed(
-                    Scope::ExtensibleSequence {
-                        name: C::NAME,
-                        bit_pos,
-                        opt_bit_field: Some(range),
-                        calls_until_ext_bitfield: (extension_after + 1) as usize,
-                        number_of_ext_fields: (C::FIELD_COUNT - (extension_after + 1)) as usize,
-                    },
-                    f,
-                )
-            } else {
-                w.scope_pushed(Scope::OptBitField(range), f)
-            }
+                if let Some((extension_after, bit_pos)) = extension {
+                    w.scope_pushed(
+                        Scope::ExtensibleSequence {
+                            name: C::NAME,
+                            bit_pos,
+                            opt_bit_field: Some(range),
+                            calls_until_ext_bitfield: (extension_after + 1) as usize,
+                            number_of_ext_fields: (C::FIELD_COUNT - (extension_after + 1)) as usize,
+                        },
+                        f,
+                    )
+                } else {
+                    w.scope_pushed(Scope::OptBitField(range), f)
+                }
+            })
         })
     }
 
@@ -423,20 +854,22 @@ impl Writer for UperWriter {
         slice: &[T::Type],
     ) -> Result<(), Self::Error> {
         self.write_bit_field_entry(false, true)?;
-        self.scope_stashed(|w| {
-            w.write_extensible_bit_and_length_or_err(
-                C::EXTENSIBLE,
-                C::MIN,
-                C::MAX,
-                i64::MAX as u64,
-                slice.len() as u64,
-            )?;
-
+        self.nested(|w| {
             w.scope_stashed(|w| {
-                for value in slice {
-                    T::write_value(w, value)?;
-                }
-                Ok(())
+                w.write_extensible_bit_and_length_or_err(
+                    C::EXTENSIBLE,
+                    C::MIN,
+                    C::MAX,
+                    i64::MAX as u64,
+                    slice.len() as u64,
+                )?;
+
+                w.scope_stashed(|w| {
+                    for value in slice {
+                        T::write_value(w, value)?;
+                    }
+                    Ok(())
+                })
             })
         })
     }
@@ -464,6 +897,9 @@ impl Writer for UperWriter {
     ) -> Result<(), Self::Error> {
         self.write_bit_field_entry(false, true)?;
         self.with_buffer(|w| {
+            if C::EXTENSIBLE {
+                w.record(Landmark::ExtensionBit(w.bits.write_position));
+            }
             w.bits.write_enumeration_index(
                 C::STD_VARIANT_COUNT,
                 C::EXTENSIBLE,
@@ -475,22 +911,24 @@ impl Writer for UperWriter {
     #[inline]
     fn write_choice<C: choice::Constraint>(&mut self, choice: &C) -> Result<(), Self::Error> {
         self.write_bit_field_entry(false, true)?;
-        self.scope_stashed(|w| {
-            let index = choice.to_choice_index();
+        self.nested(|w| {
+            w.scope_stashed(|w| {
+                let index = choice.to_choice_index();
 
-            // this fails if the index is out of range
-            w.bits
-                .write_choice_index(C::STD_VARIANT_COUNT, C::EXTENSIBLE, index)?;
+                if C::EXTENSIBLE {
+                    w.record(Landmark::ExtensionBit(w.bits.write_position));
+                }
 
-            if index >= C::STD_VARIANT_COUNT {
-                // TODO performance
-                let mut writer = UperWriter::with_capacity(512);
-                choice.write_content(&mut writer)?;
+                // this fails if the index is out of range
                 w.bits
-                    .write_octetstring(None, None, false, writer.byte_content())
-            } else {
-                choice.write_content(w)
-            }
+                    .write_choice_index(C::STD_VARIANT_COUNT, C::EXTENSIBLE, index)?;
+
+                if index >= C::STD_VARIANT_COUNT {
+                    w.write_length_prefixed(|w| choice.write_content(w))
+                } else {
+                    choice.write_content(w)
+                }
+            })
         })
     }
 
@@ -513,7 +951,7 @@ impl Writer for UperWriter {
         &mut self,
         value: &T::Type,
     ) -> Result<(), Self::Error> {
-        let present = C::DEFAULT_VALUE.ne(value);
+        let present = C::default_value().ne(value);
         self.write_bit_field_entry(true, present)?;
         if present {
             self.scope_stashed(|w| T::write_value(w, value))
@@ -542,6 +980,7 @@ impl Writer for UperWriter {
         if max_fn {
             self.with_buffer(|w| {
                 if C::EXTENSIBLE {
+                    w.record(Landmark::ExtensionBit(w.bits.write_position));
                     w.bits.write_bit(true)?;
                 }
                 w.bits.write_unconstrained_whole_number(value)
@@ -549,13 +988,16 @@ impl Writer for UperWriter {
         } else {
             self.with_buffer(|w| {
                 if C::EXTENSIBLE {
+                    w.record(Landmark::ExtensionBit(w.bits.write_position));
                     w.bits.write_bit(false)?;
                 }
-                w.bits.write_constrained_whole_number(
-                    const_unwrap_or!(C::MIN, 0),
-                    const_unwrap_or!(C::MAX, i64::MAX),
-                    value,
-                )
+                w.bits
+                    .write_constrained_whole_number(
+                        const_unwrap_or!(C::MIN, 0),
+                        const_unwrap_or!(C::MAX, i64::MAX),
+                        value,
+                    )
+                    .map_err(|e| e.with_field_name(C::DEFINITION_NAME, C::FIELD_NAME))
             })
         }
     }
@@ -579,8 +1021,7 @@ impl Writer for UperWriter {
 
             // ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 30.3
             // For 'known-multiplier character string types' there is no min/max in the encoding
-            w.bits
-                .write_octetstring(None, None, false, value.as_bytes())
+            w.write_octetstring_with_landmarks(None, None, false, value.as_bytes())
         })
     }
 
@@ -591,7 +1032,7 @@ impl Writer for UperWriter {
     ) -> Result<(), Self::Error> {
         self.write_bit_field_entry(false, true)?;
         self.with_buffer(|w| {
-            Error::ensure_string_valid(Charset::Ia5, value)?;
+            let value = w.valid_string(Charset::Ia5, value)?;
 
             w.write_extensible_bit_and_length_or_err(
                 C::EXTENSIBLE,
@@ -617,7 +1058,7 @@ impl Writer for UperWriter {
     ) -> Result<(), Self::Error> {
         self.write_bit_field_entry(false, true)?;
         self.with_buffer(|w| {
-            Error::ensure_string_valid(Charset::Numeric, value)?;
+            let value = w.valid_string(Charset::Numeric, value)?;
 
             w.write_extensible_bit_and_length_or_err(
                 C::EXTENSIBLE,
@@ -646,7 +1087,7 @@ impl Writer for UperWriter {
     ) -> Result<(), Self::Error> {
         self.write_bit_field_entry(false, true)?;
         self.with_buffer(|w| {
-            Error::ensure_string_valid(Charset::Printable, value)?;
+            let value = w.valid_string(Charset::Printable, value)?;
 
             w.write_extensible_bit_and_length_or_err(
                 C::EXTENSIBLE,
@@ -664,6 +1105,57 @@ impl Writer for UperWriter {
         })
     }
 
+    #[inline]
+    fn write_bmp_string<C: bmpstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_bit_field_entry(false, true)?;
+        self.with_buffer(|w| {
+            let value = w.valid_string(Charset::Bmp, value)?;
+
+            w.write_extensible_bit_and_length_or_err(
+                C::EXTENSIBLE,
+                C::MIN,
+                C::MAX,
+                u64::MAX,
+                value.chars().count() as u64,
+            )?;
+
+            // ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 30.5.4: 16 bits for each character
+            for char in value.chars() {
+                w.bits.write_bits(&(char as u16).to_be_bytes())?;
+            }
+
+            Ok(())
+        })
+    }
+
+    #[inline]
+    fn write_teletex_string<C: teletexstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_bit_field_entry(false, true)?;
+        self.with_buffer(|w| {
+            let value = w.valid_string(Charset::Teletex, value)?;
+
+            if !C::EXTENSIBLE {
+                let chars = value.chars().count() as u64;
+                let min = const_unwrap_or!(C::MIN, 0);
+                let max = const_unwrap_or!(C::MAX, u64::MAX);
+                if chars < min || chars > max {
+                    return Err(ErrorKind::SizeNotInRange(chars, min, max).into());
+                }
+            }
+
+            // ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 30.6
+            // Not a 'known-multiplier character string type', so there is no min/max in the
+            // encoding of the octets
+            w.write_octetstring_with_landmarks(None, None, false, &Charset::Teletex.encode(&value))
+        })
+    }
+
     #[inline]
     fn write_visible_string<C: visiblestring::Constraint>(
         &mut self,
@@ -671,7 +1163,7 @@ impl Writer for UperWriter {
     ) -> Result<(), Self::Error> {
         self.write_bit_field_entry(false, true)?;
         self.with_buffer(|w| {
-            Error::ensure_string_valid(Charset::Visible, value)?;
+            let value = w.valid_string(Charset::Visible, value)?;
 
             w.write_extensible_bit_and_length_or_err(
                 C::EXTENSIBLE,
@@ -696,11 +1188,29 @@ impl Writer for UperWriter {
     ) -> Result<(), Self::Error> {
         self.write_bit_field_entry(false, true)?;
         self.with_buffer(|w| {
-            w.bits
-                .write_octetstring(C::MIN, C::MAX, C::EXTENSIBLE, value)
+            w.write_octetstring_with_landmarks(C::MIN, C::MAX, C::EXTENSIBLE, value)
         })
     }
 
+    #[inline]
+    fn write_containing<C: octetstring::Constraint, X: containing::Transform, T: WritableType>(
+        &mut self,
+        value: &T::Type,
+    ) -> Result<(), Self::Error> {
+        let mut writer = self.child();
+        T::write_value(&mut writer, value)?;
+        let mut content = writer.into_bytes_vec();
+        if content.is_empty() {
+            // ITU-T X.691 | ISO/IEC 8825-2:2015, 11.1.3 (complete encoding)
+            content.push(0x00);
+        }
+        let octets = self
+            .transforms
+            .encode::<X>(content)
+            .map_err(|e| ErrorKind::ContentTransformFailed(e.to_string()))?;
+        self.write_octet_string::<C>(&octets)
+    }
+
     #[inline]
     fn write_bit_string<C: bitstring::Constraint>(
         &mut self,
@@ -709,8 +1219,7 @@ impl Writer for UperWriter {
     ) -> Result<(), Self::Error> {
         self.write_bit_field_entry(false, true)?;
         self.with_buffer(|w| {
-            w.bits
-                .write_bitstring(C::MIN, C::MAX, C::EXTENSIBLE, value, 0, bit_len)
+            w.write_bitstring_with_landmarks(C::MIN, C::MAX, C::EXTENSIBLE, value, bit_len)
         })
     }
 
@@ -724,21 +1233,126 @@ impl Writer for UperWriter {
     fn write_null<C: null::Constraint>(&mut self, _value: &Null) -> Result<(), Self::Error> {
         Ok(())
     }
+
+    #[inline]
+    fn write_real<T: real::Float, C: real::Constraint>(
+        &mut self,
+        value: T,
+    ) -> Result<(), Self::Error> {
+        // ITU-T X.691 | ISO/IEC 8825-2:2015, 14: the contents octets of the CER and DER encoding
+        // as unconstrained length octet string
+        let contents = crate::protocol::basic::real::encode(value.to_f64());
+        self.write_bit_field_entry(false, true)?;
+        self.with_buffer(|w| w.write_octetstring_with_landmarks(None, None, false, &contents))
+    }
+
+    #[inline]
+    fn write_object_identifier<C: objectidentifier::Constraint>(
+        &mut self,
+        value: &Oid,
+    ) -> Result<(), Self::Error> {
+        // ITU-T X.691 | ISO/IEC 8825-2:2015, 24: the contents octets of the BER encoding as
+        // unconstrained length octet string
+        let contents = crate::protocol::basic::oid::encode(value.as_slice())
+            .map_err(ErrorKind::InvalidObjectIdentifier)?;
+        self.write_bit_field_entry(false, true)?;
+        self.with_buffer(|w| w.write_octetstring_with_landmarks(None, None, false, &contents))
+    }
+
+    #[inline]
+    fn write_relative_oid<C: relativeoid::Constraint>(
+        &mut self,
+        value: &Oid,
+    ) -> Result<(), Self::Error> {
+        // like an OBJECT IDENTIFIER, but without combining the first two arcs
+        let contents = crate::protocol::basic::oid::encode_relative(value.as_slice())
+            .map_err(ErrorKind::InvalidObjectIdentifier)?;
+        self.write_bit_field_entry(false, true)?;
+        self.with_buffer(|w| w.write_octetstring_with_landmarks(None, None, false, &contents))
+    }
+
+    #[inline]
+    fn write_oid_iri<C: oidiri::Constraint>(&mut self, value: &str) -> Result<(), Self::Error> {
+        // the UTF-8 octets as unconstrained length octet string
+        crate::protocol::basic::oid::check_iri(value)
+            .map_err(ErrorKind::InvalidObjectIdentifier)?;
+        self.write_bit_field_entry(false, true)?;
+        self.with_buffer(|w| {
+            w.write_octetstring_with_landmarks(None, None, false, value.as_bytes())
+        })
+    }
+}
+
+/// The state of an [`UperWriter`] at a given point in time, see [`PositionedWriter::mark`]
+#[derive(Debug, Clone)]
+pub struct WriterMark {
+    position: usize,
+    scope: Option<Scope>,
+    landmarks: usize,
+}
+
+impl WriterMark {
+    #[inline]
+    pub const fn bit_position(&self) -> usize {
+        self.position
+    }
+}
+
+/// The state of an [`UperReader`] at a given point in time, see [`PositionedReader::mark`]
+#[derive(Debug, Clone)]
+pub struct ReaderMark {
+    position: usize,
+    scope: Option<Scope>,
+    #[cfg(feature = "descriptive-deserialize-errors")]
+    scope_description: usize,
+}
+
+impl ReaderMark {
+    #[inline]
+    pub const fn bit_position(&self) -> usize {
+        self.position
+    }
 }
 
 #[derive(Clone)]
 pub struct UperReader<B: ScopedBitRead> {
     bits: B,
     scope: Option<Scope>,
+    start_position: usize,
+    validate_trailing_padding: bool,
+    tolerate_truncated_extensions: bool,
+    /// Whether a truncation was tolerated since the last call of [`UperReader::read_decoded`]
+    recovered_truncation: bool,
+    recovery: numbers::Recovery,
+    depth: Depth,
+    transforms: containing::Transforms,
+    trace: Option<Trace>,
     #[cfg(feature = "descriptive-deserialize-errors")]
     scope_description: Vec<ScopeDescription>,
 }
 
+/// A value read by [`UperReader::read_decoded`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decoded<T> {
+    pub value: T,
+    /// Whether the encoding ended at an extension boundary, which was read as if no extensions
+    /// were present, see [`UperReader::set_tolerate_truncated_extensions`]
+    pub recovered_truncation: bool,
+}
+
 impl<B: ScopedBitRead> From<B> for UperReader<B> {
     fn from(bits: B) -> Self {
         UperReader {
+            start_position: bits.pos(),
             bits,
             scope: None,
+            validate_trailing_padding: false,
+            tolerate_truncated_extensions: false,
+            recovered_truncation: false,
+            recovery: numbers::Recovery::default(),
+            depth: Depth::default(),
+            transforms: containing::Transforms::default(),
+            trace: None,
             #[cfg(feature = "descriptive-deserialize-errors")]
             scope_description: Vec::new(),
         }
@@ -757,6 +1371,37 @@ impl<B: ScopedBitRead> UperReader<B> {
         self.bits
     }
 
+    /// Records the regions of the values that are read, see [`crate::debug::annotate`]
+    pub(crate) fn with_trace(mut self) -> Self {
+        self.trace = Some(Trace::default());
+        self
+    }
+
+    pub(crate) fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
+
+    #[inline]
+    fn trace_open(&mut self, children: Children) {
+        if let Some(trace) = &mut self.trace {
+            trace.open(children);
+        }
+    }
+
+    #[inline]
+    fn trace_next(&mut self, end: usize) {
+        if let Some(trace) = &mut self.trace {
+            trace.next(end, self.bits.pos());
+        }
+    }
+
+    #[inline]
+    fn trace_close(&mut self) {
+        if let Some(trace) = &mut self.trace {
+            trace.close(self.bits.pos());
+        }
+    }
+
     #[inline]
     fn read_length_determinant(
         &mut self,
@@ -807,11 +1452,122 @@ impl<B: ScopedBitRead> UperReader<B> {
         result
     }
 
+    /// Number of bits until the end of the current (sub-)slice is reached
     #[inline]
     pub fn bits_remaining(&self) -> usize {
         self.bits.remaining()
     }
 
+    /// Number of bits read since this reader has been created
+    #[inline]
+    pub fn consumed_bits(&self) -> usize {
+        self.bits.pos() - self.start_position
+    }
+
+    pub const fn validates_trailing_padding(&self) -> bool {
+        self.validate_trailing_padding
+    }
+
+    /// If set, [`Reader::read`] fails with [`ErrorKind::TrailingBits`] unless the bits remaining
+    /// after the decoded value are only the zero padding of its final octet, which is consumed.
+    /// This detects truncated and concatenated messages.
+    pub fn set_validate_trailing_padding(&mut self, validate: bool) {
+        self.validate_trailing_padding = validate;
+    }
+
+    pub const fn tolerates_truncated_extensions(&self) -> bool {
+        self.tolerate_truncated_extensions
+    }
+
+    /// If set, the end of the encoding exactly at an extension boundary of an extensible
+    /// `SEQUENCE` or `SET` - where its extension bit or its extension additions are expected - is
+    /// read as if no extensions were present, instead of failing with
+    /// [`ErrorKind::EndOfStream`]. Some deployed encoders omit these trailing bits. Whether this
+    /// happened is reported by [`UperReader::read_decoded`].
+    pub fn set_tolerate_truncated_extensions(&mut self, tolerate: bool) {
+        self.tolerate_truncated_extensions = tolerate;
+    }
+
+    /// Like [`Reader::read`], but also reports whether a truncation was tolerated while reading
+    /// the value
+    pub fn read_decoded<T: Readable>(&mut self) -> Result<Decoded<T>, Error> {
+        self.recovered_truncation = false;
+        let value = self.read::<T>()?;
+        Ok(Decoded {
+            value,
+            recovered_truncation: core::mem::take(&mut self.recovered_truncation),
+        })
+    }
+
+    /// Reads a value written by [`UperWriter::write_nested`] at the current bit position, as it
+    /// would be read on its own, also if this is called while another value is read. The bits
+    /// following the value are not validated as trailing padding.
+    pub fn read_nested<T: Readable>(&mut self) -> Result<T, Error> {
+        let validate = core::mem::replace(&mut self.validate_trailing_padding, false);
+        let result = self.scope_stashed(|reader| reader.read::<T>());
+        self.validate_trailing_padding = validate;
+        result
+    }
+
+    /// Whether the encoding ends where the extension bit of an extensible `SEQUENCE` or `SET` is
+    /// expected and that is tolerated, see [`UperReader::set_tolerate_truncated_extensions`]
+    fn recovers_truncated_extension_bit(&mut self) -> bool {
+        let recovers = self.tolerate_truncated_extensions && self.bits.remaining() == 0;
+        self.recovered_truncation |= recovers;
+        recovers
+    }
+
+    /// Whether the encoding ends where the extension additions of the current extensible
+    /// `SEQUENCE` or `SET` are expected and that is tolerated, in which case none are read
+    fn recover_truncated_extension_additions(&mut self) -> Result<(), Error> {
+        if !self.tolerate_truncated_extensions || self.bits.remaining() > 0 {
+            return Ok(());
+        }
+        if let Some(Scope::ExtensibleSequence {
+            name,
+            bit_pos,
+            calls_until_ext_bitfield: 0,
+            ..
+        }) = self.scope
+        {
+            if self.bits.with_read_position_at(bit_pos, |b| b.read_bit())? {
+                self.scope = Some(Scope::ExtensibleSequenceEmpty(name));
+                self.recovered_truncation = true;
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn nested<T, F: FnOnce(&mut Self) -> Result<T, Error>>(&mut self, f: F) -> Result<T, Error> {
+        self.depth
+            .enter()
+            .map_err(ErrorKind::NestingDepthExceedsLimit)?;
+        let result = f(self);
+        self.depth.leave();
+        result
+    }
+
+    /// Consumes the bits until the next octet boundary after the value that started at the given
+    /// position, which must all be zero. ITU-T X.691 | ISO/IEC 8825-2, 11.1: a value with an
+    /// empty encoding is encoded as a single zero octet, which is consumed too.
+    fn read_padding(&mut self, value_start: usize) -> Result<(), Error> {
+        let consumed = self.bits.pos() - value_start;
+        let remaining = self.bits.remaining();
+        let padding = if consumed == 0 {
+            BYTE_LEN
+        } else {
+            (BYTE_LEN - self.bits.pos() % BYTE_LEN) % BYTE_LEN
+        };
+        for _ in 0..padding.min(remaining) {
+            if self.bits.read_bit()? {
+                return Err(ErrorKind::TrailingBits(consumed, remaining).into());
+            }
+        }
+        Ok(())
+    }
+
+    #[doc(hidden)]
     #[inline]
     pub fn scope_pushed<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
         &mut self,
@@ -822,11 +1578,10 @@ impl<B: ScopedBitRead> UperReader<B> {
         let result = f(self);
         if cfg!(debug_assertions) && result.is_ok() {
             let scope = core::mem::replace(&mut self.scope, original);
-            // call to .unwrap() is save because this is supposed to be the original from above
+            // this is supposed to be the scope from above
             debug_assert!(
-                scope.clone().unwrap().exhausted(),
-                "Not exhausted: {:?}",
-                scope.unwrap()
+                scope.as_ref().is_some_and(Scope::exhausted),
+                "Not exhausted: {scope:?}"
             );
         } else {
             self.scope = original;
@@ -834,6 +1589,7 @@ impl<B: ScopedBitRead> UperReader<B> {
         result
     }
 
+    #[doc(hidden)]
     #[inline]
     pub fn scope_stashed<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
         &mut self,
@@ -873,8 +1629,60 @@ impl<B: ScopedBitRead> UperReader<B> {
         result
     }
 
+    /// Reads a value with the given fn like [`Reader::read`] reads a [`Readable`], which checks
+    /// the trailing padding, see [`UperReader::set_validate_trailing_padding`]
+    #[inline]
+    pub fn read_with<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<T, Error> {
+        let value_start = self.bits.pos();
+        #[allow(clippy::let_and_return)]
+        let value = f(self).and_then(|value| {
+            if self.validate_trailing_padding {
+                let consumed = self.bits.pos() - value_start;
+                let remaining = self.bits.remaining();
+                self.read_padding(value_start)?;
+                if self.bits.remaining() > 0 {
+                    return Err(ErrorKind::TrailingBits(consumed, remaining).into());
+                }
+            }
+            Ok(value)
+        });
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        let value = value.map_err(|mut e| {
+            e.0.description = core::mem::take(&mut self.scope_description);
+            e
+        });
+        value
+    }
+
+    /// Reads an `OPTIONAL` value with the given fn like [`Reader::read_opt`] does
+    #[doc(hidden)]
+    #[inline]
+    pub fn read_opt_with<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<Option<T>, Error> {
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        self.scope_description.push(ScopeDescription::optional());
+
+        // as opt-field this must and will return some value
+        if self
+            .read_bit_field_entry(true)?
+            .ok_or(ErrorKind::OptFlagsExhausted)?
+        {
+            self.with_buffer(|w| w.scope_stashed(f)).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    #[doc(hidden)]
     #[inline]
     pub fn read_bit_field_entry(&mut self, is_opt: bool) -> Result<Option<bool>, Error> {
+        let field_start = self.bits.pos();
+        self.recover_truncated_extension_additions()?;
         #[allow(clippy::let_and_return)]
         let result = if let Some(scope) = &mut self.scope {
             scope.read_from_field(
@@ -893,25 +1701,197 @@ impl<B: ScopedBitRead> UperReader<B> {
         self.scope_description
             .push(ScopeDescription::read_bit_field_entry(is_opt, &result));
 
-        result
+        if self.scope.is_some() {
+            // each field of a SEQUENCE or SET starts with this call
+            self.trace_next(field_start);
+        }
+
+        result
+    }
+
+    /// Decodes values of `T` from back-to-back PDUs until all bits are consumed. Each PDU is
+    /// expected to start on an octet boundary, so the zero padding after each value is consumed
+    /// (see [`UperReader::set_validate_trailing_padding`]). The iteration ends after the first
+    /// error, because the start of the next PDU is unknown then.
+    pub fn iter<T: Readable>(&mut self) -> UperIter<'_, T, B> {
+        UperIter {
+            reader: self,
+            failed: false,
+            _marker: PhantomData,
+        }
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn with_buffer<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<T, Error> {
+        if self
+            .scope
+            .as_ref()
+            .map(Scope::encode_as_open_type_field)
+            .unwrap_or(false)
+        {
+            let len = self.read_length_determinant(None, None)?;
+            self.read_whole_sub_slice(len as usize, f)
+        } else {
+            f(self)
+        }
+    }
+}
+
+impl<'a> UperReader<Bits<'a>> {
+    /// Reads an `OCTET STRING` like [`Reader::read_octet_string`], but borrows the octets from
+    /// the decoded slice - if they start at an octet boundary and are not fragmented, which
+    /// encodings with byte-aligned headers in front of their payload usually ensure. Otherwise,
+    /// the octets are copied into an owned buffer. The generated types own their values, their
+    /// borrowed views - see `RustCodeGenerator::set_borrowed_views` - read with this.
+    ///
+    /// ```rust
+    /// use asn1rs::prelude::*;
+    /// use asn1rs::descriptor::octetstring::NoConstraint;
+    /// use std::borrow::Cow;
+    ///
+    /// let mut writer = UperWriter::default();
+    /// writer.write_octet_string::<NoConstraint>(&[0xCA, 0xFE]).unwrap();
+    /// let bytes = writer.into_bytes_vec();
+    ///
+    /// let mut reader = UperReader::from((&bytes[..], bytes.len() * 8));
+    /// let payload = reader.read_octet_string_borrowed::<NoConstraint>().unwrap();
+    /// assert!(matches!(payload, Cow::Borrowed(&[0xCA, 0xFE])));
+    /// ```
+    pub fn read_octet_string_borrowed<C: octetstring::Constraint>(
+        &mut self,
+    ) -> Result<Cow<'a, [u8]>, Error> {
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        self.scope_description
+            .push(ScopeDescription::octet_string::<C>());
+
+        let _ = self.read_bit_field_entry(false)?;
+        let result = self.with_buffer(|r| {
+            r.bits
+                .read_octetstring_borrowed(C::MIN, C::MAX, C::EXTENSIBLE)
+        });
+
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        self.scope_description.push(ScopeDescription::Result(
+            result
+                .as_ref()
+                .map(|s| {
+                    s.iter()
+                        .map(|v| format!("{v:02x}"))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .map_err(|e| e.clone()),
+        ));
+
+        result
+    }
+
+    /// Reads an `UTF8String` like [`Reader::read_utf8string`], but borrows the characters from
+    /// the decoded slice under the same conditions as [`UperReader::read_octet_string_borrowed`]
+    pub fn read_utf8string_borrowed<C: utf8string::Constraint>(
+        &mut self,
+    ) -> Result<Cow<'a, str>, Error> {
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        self.scope_description
+            .push(ScopeDescription::utf8string::<C>());
+
+        let _ = self.read_bit_field_entry(false)?;
+        let result = self.with_buffer(|r| {
+            // ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 30.3
+            // For 'known-multiplier character string types' there is no min/max in the encoding
+            match r.bits.read_octetstring_borrowed(None, None, false)? {
+                Cow::Borrowed(octets) => match core::str::from_utf8(octets) {
+                    Ok(str) => Ok(Cow::Borrowed(str)),
+                    // report the same error as the owning read
+                    Err(_) => String::from_utf8(octets.to_vec())
+                        .map(Cow::Owned)
+                        .map_err(|e| ErrorKind::FromUtf8Error(e).into()),
+                },
+                Cow::Owned(octets) => String::from_utf8(octets)
+                    .map(Cow::Owned)
+                    .map_err(|e| ErrorKind::FromUtf8Error(e).into()),
+            }
+        });
+
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        self.scope_description.push(ScopeDescription::Result(
+            result
+                .as_ref()
+                .map(ToString::to_string)
+                .map_err(|e| e.clone()),
+        ));
+
+        result
+    }
+}
+
+impl<B: ScopedBitRead> PositionedReader for UperReader<B> {
+    type Mark = ReaderMark;
+
+    #[inline]
+    fn bit_position(&self) -> usize {
+        self.bits.pos()
+    }
+
+    #[inline]
+    fn remaining_bits(&self) -> usize {
+        self.bits.remaining()
+    }
+
+    #[inline]
+    fn peek_bit(&mut self) -> Result<bool, Error> {
+        let position = self.bits.pos();
+        let result = self.bits.read_bit();
+        self.bits.set_pos(position);
+        result
+    }
+
+    #[inline]
+    fn mark(&self) -> ReaderMark {
+        ReaderMark {
+            position: self.bits.pos(),
+            scope: self.scope.clone(),
+            #[cfg(feature = "descriptive-deserialize-errors")]
+            scope_description: self.scope_description.len(),
+        }
+    }
+
+    #[inline]
+    fn rollback(&mut self, mark: ReaderMark) {
+        self.bits.set_pos(mark.position);
+        self.scope = mark.scope;
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        self.scope_description.truncate(mark.scope_description);
+    }
+}
+
+impl<B: ScopedBitRead> containing::RegisterTransform for UperReader<B> {
+    fn transforms_mut(&mut self) -> &mut containing::Transforms {
+        &mut self.transforms
+    }
+}
+
+impl<B: ScopedBitRead> DepthLimit for UperReader<B> {
+    fn depth(&self) -> &Depth {
+        &self.depth
+    }
+
+    fn depth_mut(&mut self) -> &mut Depth {
+        &mut self.depth
+    }
+}
+
+impl<B: ScopedBitRead> numbers::DecodePolicyReader for UperReader<B> {
+    fn recovery(&self) -> &numbers::Recovery {
+        &self.recovery
     }
 
-    #[inline]
-    pub fn with_buffer<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
-        &mut self,
-        f: F,
-    ) -> Result<T, Error> {
-        if self
-            .scope
-            .as_ref()
-            .map(Scope::encode_as_open_type_field)
-            .unwrap_or(false)
-        {
-            let len = self.read_length_determinant(None, None)?;
-            self.read_whole_sub_slice(len as usize, f)
-        } else {
-            f(self)
-        }
+    fn recovery_mut(&mut self) -> &mut numbers::Recovery {
+        &mut self.recovery
     }
 }
 
@@ -923,16 +1903,19 @@ impl<B: ScopedBitRead> Reader for UperReader<B> {
     where
         Self: Sized,
     {
-        #[allow(clippy::let_and_return)]
-        let value = T::read(self);
-        #[cfg(feature = "descriptive-deserialize-errors")]
-        let value = value.map_err(|mut e| {
-            e.0.description = core::mem::take(&mut self.scope_description);
-            e
-        });
+        #[cfg(feature = "stats")]
+        let value_start = self.bits.pos();
+        let value = self.read_with(T::read);
+        #[cfg(feature = "stats")]
+        crate::stats::record_decoded::<T>(Some(value_start), self.bits_read(), value.is_ok());
         value
     }
 
+    #[inline]
+    fn bits_read(&self) -> Option<usize> {
+        Some(self.bits.pos())
+    }
+
     #[inline]
     fn read_sequence<
         C: sequence::Constraint,
@@ -947,45 +1930,58 @@ impl<B: ScopedBitRead> Reader for UperReader<B> {
             .push(ScopeDescription::sequence::<C>());
 
         let _ = self.read_bit_field_entry(false);
+        self.trace_open(Children::Fields(C::FIELD_NAMES));
         #[allow(clippy::let_and_return)]
-        let result = self.with_buffer(|r| {
-            let extension_after = if let Some(extension_after) = C::EXTENDED_AFTER_FIELD {
-                let bit_pos = r.bits.pos();
-                if r.bits.read_bit()? {
-                    Some((extension_after, bit_pos))
+        let result = self.nested(|r| {
+            r.with_buffer(|r| {
+                let extension_after = if let Some(extension_after) = C::EXTENDED_AFTER_FIELD {
+                    let bit_pos = r.bits.pos();
+                    if r.recovers_truncated_extension_bit() {
+                        None
+                    } else if r.bits.read_bit()? {
+                        Some((extension_after, bit_pos))
+                    } else {
+                        None
+                    }
                 } else {
                     None
+                };
+
+                // In UPER the values for all OPTIONAL flags are written before any field
+                // value is written. This remembers their position, so a later call of `read_opt`
+                // can retrieve them from the buffer
+                if r.bits.remaining() < C::STD_OPTIONAL_FIELDS as usize {
+                    return Err(ErrorKind::EndOfStream.into());
                 }
-            } else {
-                None
-            };
 
-            // In UPER the values for all OPTIONAL flags are written before any field
-            // value is written. This remembers their position, so a later call of `read_opt`
-            // can retrieve them from the buffer
-            if r.bits.remaining() < C::STD_OPTIONAL_FIELDS as usize {
-                return Err(ErrorKind::EndOfStream.into());
-            }
+                if extension_after.is_none() && C::STD_OPTIONAL_FIELDS <= 64 {
+                    let scope =
+                        Scope::read_presence_bitmap(&mut r.bits, C::STD_OPTIONAL_FIELDS as u8)?;
+                    return r.scope_pushed(scope, f);
+                }
 
-            let range = r.bits.pos()..r.bits.pos() + C::STD_OPTIONAL_FIELDS as usize;
-            r.bits.set_pos(range.end); // skip optional
-
-            if let Some((extension_after, bit_pos)) = extension_after {
-                r.scope_pushed(
-                    Scope::ExtensibleSequence {
-                        name: C::NAME,
-                        bit_pos,
-                        opt_bit_field: Some(range),
-                        calls_until_ext_bitfield: (extension_after + 1) as usize,
-                        number_of_ext_fields: (C::FIELD_COUNT - (extension_after + 1)) as usize,
-                    },
-                    f,
-                )
-            } else {
-                r.scope_pushed(Scope::OptBitField(range), f)
-            }
+                let range = r.bits.pos()..r.bits.pos() + C::STD_OPTIONAL_FIELDS as usize;
+                r.bits.set_pos(range.end); // skip optional
+
+                if let Some((extension_after, bit_pos)) = extension_after {
+                    r.scope_pushed(
+                        Scope::ExtensibleSequence {
+                            name: C::NAME,
+                            bit_pos,
+                            opt_bit_field: Some(range),
+                            calls_until_ext_bitfield: (extension_after + 1) as usize,
+                            number_of_ext_fields: (C::FIELD_COUNT - (extension_after + 1)) as usize,
+                        },
+                        f,
+                    )
+                } else {
+                    r.scope_pushed(Scope::OptBitField(range), f)
+                }
+            })
         });
 
+        self.trace_close();
+
         #[cfg(feature = "descriptive-deserialize-errors")]
         self.scope_description.push(ScopeDescription::End(C::NAME));
 
@@ -1002,29 +1998,34 @@ impl<B: ScopedBitRead> Reader for UperReader<B> {
 
         let _ = self.read_bit_field_entry(false)?;
         #[allow(clippy::let_and_return)]
-        self.with_buffer(|r| {
-            let len = if C::EXTENSIBLE {
-                let extensible = r.bits.read_bit()?;
-                if extensible {
-                    r.read_length_determinant(None, None)?
+        self.nested(|r| {
+            r.with_buffer(|r| {
+                let len = if C::EXTENSIBLE {
+                    let extensible = r.bits.read_bit()?;
+                    if extensible {
+                        r.read_length_determinant(None, None)?
+                    } else {
+                        r.read_length_determinant(C::MIN, C::MAX)?
+                    }
                 } else {
                     r.read_length_determinant(C::MIN, C::MAX)?
-                }
-            } else {
-                r.read_length_determinant(C::MIN, C::MAX)?
-            };
+                };
 
-            if len > 0 {
-                r.scope_stashed(|r| {
-                    let mut vec = Vec::with_capacity(len as usize);
-                    for _ in 0..len {
-                        vec.push(T::read_value(r)?);
-                    }
-                    Ok(vec)
-                })
-            } else {
-                Ok(Vec::new())
-            }
+                if len > 0 {
+                    r.scope_stashed(|r| {
+                        let mut vec = Vec::with_capacity(len as usize);
+                        r.trace_open(Children::Elements);
+                        for _ in 0..len {
+                            r.trace_next(r.bits.pos());
+                            vec.push(T::read_value(r)?);
+                        }
+                        r.trace_close();
+                        Ok(vec)
+                    })
+                } else {
+                    Ok(Vec::new())
+                }
+            })
         })
     }
 
@@ -1084,25 +2085,35 @@ impl<B: ScopedBitRead> Reader for UperReader<B> {
 
         let _ = self.read_bit_field_entry(false)?;
         #[allow(clippy::let_and_return)]
-        let result = self.scope_stashed(|r| {
-            let index = r.read_choice_index(C::STD_VARIANT_COUNT, C::EXTENSIBLE)?;
-            let result = if index >= C::STD_VARIANT_COUNT {
-                let length = r.read_length_determinant(None, None)?;
-                r.read_whole_sub_slice(length as usize, |r| Ok((index, C::read_content(index, r)?)))
-            } else {
-                Ok((index, C::read_content(index, r)?))
-            }
-            .and_then(|(index, content)| {
-                content.ok_or_else(|| ErrorKind::InvalidChoiceIndex(index, C::VARIANT_COUNT).into())
-            });
-            #[cfg(feature = "descriptive-deserialize-errors")]
-            r.scope_description.push(ScopeDescription::Result(
+        let result = self.nested(|r| {
+            r.scope_stashed(|r| {
+                let index = r.read_choice_index(C::STD_VARIANT_COUNT, C::EXTENSIBLE)?;
+                r.trace_open(Children::alternative::<C>(index));
+                let result = if index >= C::STD_VARIANT_COUNT {
+                    let length = r.read_length_determinant(None, None)?;
+                    r.trace_next(r.bits.pos());
+                    r.read_whole_sub_slice(length as usize, |r| {
+                        Ok((index, C::read_content(index, r)?))
+                    })
+                } else {
+                    r.trace_next(r.bits.pos());
+                    Ok((index, C::read_content(index, r)?))
+                };
+                r.trace_close();
+                let result = result.and_then(|(index, content)| {
+                    content.ok_or_else(|| {
+                        ErrorKind::InvalidChoiceIndex(index, C::VARIANT_COUNT).into()
+                    })
+                });
+                #[cfg(feature = "descriptive-deserialize-errors")]
+                r.scope_description.push(ScopeDescription::Result(
+                    result
+                        .as_ref()
+                        .map(|_| index.to_string())
+                        .map_err(Error::clone),
+                ));
                 result
-                    .as_ref()
-                    .map(|_| index.to_string())
-                    .map_err(Error::clone),
-            ));
-            result
+            })
         });
 
         #[cfg(feature = "descriptive-deserialize-errors")]
@@ -1115,16 +2126,7 @@ impl<B: ScopedBitRead> Reader for UperReader<B> {
     fn read_opt<T: ReadableType>(
         &mut self,
     ) -> Result<Option<<T as ReadableType>::Type>, Self::Error> {
-        #[cfg(feature = "descriptive-deserialize-errors")]
-        self.scope_description.push(ScopeDescription::optional());
-
-        // unwrap: as opt-field this must and will return some value
-        if self.read_bit_field_entry(true)?.unwrap() {
-            self.with_buffer(|w| w.scope_stashed(T::read_value))
-                .map(Some)
-        } else {
-            Ok(None)
-        }
+        self.read_opt_with(T::read_value)
     }
 
     #[inline]
@@ -1135,11 +2137,14 @@ impl<B: ScopedBitRead> Reader for UperReader<B> {
         self.scope_description
             .push(ScopeDescription::default_type());
 
-        // unwrap: as opt-field this must and will return some value
-        if self.read_bit_field_entry(true)?.unwrap() {
+        // as opt-field this must and will return some value
+        if self
+            .read_bit_field_entry(true)?
+            .ok_or(ErrorKind::OptFlagsExhausted)?
+        {
             self.scope_stashed(T::read_value)
         } else {
-            Ok(C::DEFAULT_VALUE.to_owned())
+            Ok(C::default_value().to_owned())
         }
     }
 
@@ -1163,10 +2168,20 @@ impl<B: ScopedBitRead> Reader for UperReader<B> {
             let result = if unconstrained {
                 r.bits.read_unconstrained_whole_number()
             } else {
-                r.bits.read_constrained_whole_number(
-                    const_unwrap_or!(C::MIN, 0),
-                    const_unwrap_or!(C::MAX, i64::MAX),
-                )
+                let min = const_unwrap_or!(C::MIN, 0);
+                let max = const_unwrap_or!(C::MAX, i64::MAX);
+                // a range that is not a power of two leaves encodings for values beyond max
+                r.bits
+                    .read_constrained_whole_number(min, max)
+                    .and_then(|value| {
+                        let in_range = if value <= max {
+                            Ok(())
+                        } else {
+                            Err(Error::value_not_in_range(value, min, max))
+                        };
+                        r.recovery.apply::<T, C, _>(value, in_range).map(T::to_i64)
+                    })
+                    .map_err(|e| e.with_field_name(C::DEFINITION_NAME, C::FIELD_NAME))
             };
 
             #[cfg(feature = "descriptive-deserialize-errors")]
@@ -1299,6 +2314,65 @@ impl<B: ScopedBitRead> Reader for UperReader<B> {
         result
     }
 
+    #[inline]
+    fn read_bmp_string<C: bmpstring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        self.scope_description
+            .push(ScopeDescription::bmp_string::<C>());
+
+        let _ = self.read_bit_field_entry(false)?;
+        #[allow(clippy::let_and_return)]
+        let result = self.with_buffer(|r| {
+            let len = if C::EXTENSIBLE && r.bits.read_bit()? {
+                r.read_length_determinant(None, None)?
+            } else {
+                r.read_length_determinant(C::MIN, C::MAX)?
+            };
+            r.bits.ensure_remaining_bits(len.saturating_mul(16))?;
+
+            let mut string = String::with_capacity(len as usize);
+            for index in 0..len as usize {
+                let mut buffer = [0u8; 2];
+                r.bits.read_bits(&mut buffer)?;
+                // surrogates are not characters of the Basic Multilingual Plane
+                let char = char::from_u32(u32::from(u16::from_be_bytes(buffer))).ok_or(
+                    ErrorKind::InvalidString(Charset::Bmp, char::REPLACEMENT_CHARACTER, index),
+                )?;
+                string.push(char);
+            }
+            Ok(string)
+        });
+
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        self.scope_description
+            .push(ScopeDescription::Result(result.clone()));
+
+        result
+    }
+
+    #[inline]
+    fn read_teletex_string<C: teletexstring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        self.scope_description
+            .push(ScopeDescription::teletex_string::<C>());
+
+        let _ = self.read_bit_field_entry(false)?;
+        #[allow(clippy::let_and_return)]
+        let result = self.with_buffer(|r| {
+            // ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 30.6
+            // Not a 'known-multiplier character string type', so there is no min/max in the
+            // encoding of the octets
+            let octets = r.bits.read_octetstring(None, None, false)?;
+            Ok(octets.into_iter().map(char::from).collect())
+        });
+
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        self.scope_description
+            .push(ScopeDescription::Result(result.clone()));
+
+        result
+    }
+
     #[inline]
     fn read_visible_string<C: visiblestring::Constraint>(&mut self) -> Result<String, Self::Error> {
         #[cfg(feature = "descriptive-deserialize-errors")]
@@ -1355,6 +2429,29 @@ impl<B: ScopedBitRead> Reader for UperReader<B> {
         result
     }
 
+    #[inline]
+    fn read_containing<C: octetstring::Constraint, X: containing::Transform, T: ReadableType>(
+        &mut self,
+    ) -> Result<T::Type, Self::Error> {
+        let octets = self.read_octet_string::<C>()?;
+        let content = self
+            .transforms
+            .decode::<X>(octets)
+            .map_err(|e| ErrorKind::ContentTransformFailed(e.to_string()))?;
+        let mut reader = UperReader::from(Bits::from(&content[..]));
+        reader.depth = self.depth;
+        reader.transforms = self.transforms.clone();
+        T::read_value(&mut reader)
+    }
+
+    #[inline]
+    fn read_substituted<S, X: substituted::Substitute<S, T::Type>, T: ReadableType>(
+        &mut self,
+    ) -> Result<S, Self::Error> {
+        let value = T::read_value(self)?;
+        Ok(X::from_asn(value).map_err(|e| ErrorKind::SubstitutionFailed(e.to_string()))?)
+    }
+
     #[inline]
     fn read_bit_string<C: bitstring::Constraint>(&mut self) -> Result<(Vec<u8>, u64), Self::Error> {
         #[cfg(feature = "descriptive-deserialize-errors")]
@@ -1407,10 +2504,145 @@ impl<B: ScopedBitRead> Reader for UperReader<B> {
 
     #[inline]
     fn read_null<C: null::Constraint>(&mut self) -> Result<Null, Self::Error> {
+        if self.scope.is_some() {
+            // in zero bits, but still a field of the SEQUENCE or SET
+            self.trace_next(self.bits.pos());
+        }
         Ok(Null)
     }
+
+    #[inline]
+    fn read_real<T: real::Float, C: real::Constraint>(&mut self) -> Result<T, Self::Error> {
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        self.scope_description.push(ScopeDescription::real::<C>());
+
+        let _ = self.read_bit_field_entry(false)?;
+        let result = self
+            .with_buffer(|r| r.bits.read_octetstring(None, None, false))
+            .and_then(|contents| {
+                crate::protocol::basic::real::decode(&contents)
+                    .map(T::from_f64)
+                    .map_err(|reason| ErrorKind::InvalidReal(reason).into())
+            });
+
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        self.scope_description.push(ScopeDescription::Result(
+            result
+                .as_ref()
+                .map(|v| v.to_f64().to_string())
+                .map_err(|e| e.clone()),
+        ));
+
+        result
+    }
+
+    #[inline]
+    fn read_object_identifier<C: objectidentifier::Constraint>(
+        &mut self,
+    ) -> Result<Oid, Self::Error> {
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        self.scope_description
+            .push(ScopeDescription::object_identifier::<C>());
+
+        let _ = self.read_bit_field_entry(false)?;
+        let result = self
+            .with_buffer(|r| r.bits.read_octetstring(None, None, false))
+            .and_then(|contents| {
+                crate::protocol::basic::oid::decode(&contents)
+                    .map(Oid::from)
+                    .map_err(|reason| ErrorKind::InvalidObjectIdentifier(reason).into())
+            });
+
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        self.scope_description.push(ScopeDescription::Result(
+            result
+                .as_ref()
+                .map(ToString::to_string)
+                .map_err(|e| e.clone()),
+        ));
+
+        result
+    }
+
+    #[inline]
+    fn read_relative_oid<C: relativeoid::Constraint>(&mut self) -> Result<Oid, Self::Error> {
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        self.scope_description
+            .push(ScopeDescription::relative_oid::<C>());
+
+        let _ = self.read_bit_field_entry(false)?;
+        let result = self
+            .with_buffer(|r| r.bits.read_octetstring(None, None, false))
+            .and_then(|contents| {
+                crate::protocol::basic::oid::decode_relative(&contents)
+                    .map(Oid::from)
+                    .map_err(|reason| ErrorKind::InvalidObjectIdentifier(reason).into())
+            });
+
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        self.scope_description.push(ScopeDescription::Result(
+            result
+                .as_ref()
+                .map(ToString::to_string)
+                .map_err(|e| e.clone()),
+        ));
+
+        result
+    }
+
+    #[inline]
+    fn read_oid_iri<C: oidiri::Constraint>(&mut self) -> Result<String, Self::Error> {
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        self.scope_description
+            .push(ScopeDescription::oid_iri::<C>());
+
+        let _ = self.read_bit_field_entry(false)?;
+        let result = self
+            .with_buffer(|r| r.bits.read_octetstring(None, None, false))
+            .and_then(|octets| {
+                String::from_utf8(octets).map_err(|e| ErrorKind::FromUtf8Error(e).into())
+            })
+            .and_then(|iri| match crate::protocol::basic::oid::check_iri(&iri) {
+                Ok(()) => Ok(iri),
+                Err(reason) => Err(ErrorKind::InvalidObjectIdentifier(reason).into()),
+            });
+
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        self.scope_description
+            .push(ScopeDescription::Result(result.clone()));
+
+        result
+    }
+}
+
+/// Iterator over the values of back-to-back PDUs, see [`UperReader::iter`]
+pub struct UperIter<'r, T: Readable, B: ScopedBitRead> {
+    reader: &'r mut UperReader<B>,
+    failed: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Readable, B: ScopedBitRead> Iterator for UperIter<'_, T, B> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.reader.bits.remaining() == 0 {
+            return None;
+        }
+        let validate = core::mem::replace(&mut self.reader.validate_trailing_padding, false);
+        let value_start = self.reader.bits.pos();
+        let result = self
+            .reader
+            .read::<T>()
+            .and_then(|value| self.reader.read_padding(value_start).map(|_| value));
+        self.reader.validate_trailing_padding = validate;
+        self.failed = result.is_err();
+        Some(result)
+    }
 }
 
+impl<T: Readable, B: ScopedBitRead> core::iter::FusedIterator for UperIter<'_, T, B> {}
+
 pub trait UperDecodable<'a, B: ScopedBitRead> {
     fn decode_from_uper(bits: B) -> Result<Self, Error>
     where
@@ -1499,6 +2731,18 @@ pub enum ScopeDescription {
         max: Option<u64>,
         extensible: bool,
     },
+    BmpString {
+        tag: asn1rs_model::asn::Tag,
+        min: Option<u64>,
+        max: Option<u64>,
+        extensible: bool,
+    },
+    TeletexString {
+        tag: asn1rs_model::asn::Tag,
+        min: Option<u64>,
+        max: Option<u64>,
+        extensible: bool,
+    },
     OctetString {
         tag: asn1rs_model::asn::Tag,
         min: Option<u64>,
@@ -1514,6 +2758,18 @@ pub enum ScopeDescription {
     Boolean {
         tag: asn1rs_model::asn::Tag,
     },
+    Real {
+        tag: asn1rs_model::asn::Tag,
+    },
+    ObjectIdentifier {
+        tag: asn1rs_model::asn::Tag,
+    },
+    RelativeOid {
+        tag: asn1rs_model::asn::Tag,
+    },
+    OidIri {
+        tag: asn1rs_model::asn::Tag,
+    },
     Result(Result<String, Error>),
     BitsLengthDeterminant {
         lower_bound: Option<u64>,
@@ -1668,6 +2924,26 @@ mod scope_description_impl {
             }
         }
 
+        #[inline]
+        pub fn bmp_string<C: bmpstring::Constraint>() -> Self {
+            Self::BmpString {
+                tag: C::TAG,
+                min: C::MIN,
+                max: C::MAX,
+                extensible: C::EXTENSIBLE,
+            }
+        }
+
+        #[inline]
+        pub fn teletex_string<C: teletexstring::Constraint>() -> Self {
+            Self::TeletexString {
+                tag: C::TAG,
+                min: C::MIN,
+                max: C::MAX,
+                extensible: C::EXTENSIBLE,
+            }
+        }
+
         #[inline]
         pub fn octet_string<C: octetstring::Constraint>() -> Self {
             Self::OctetString {
@@ -1693,6 +2969,26 @@ mod scope_description_impl {
             Self::Boolean { tag: C::TAG }
         }
 
+        #[inline]
+        pub fn real<C: real::Constraint>() -> Self {
+            Self::Real { tag: C::TAG }
+        }
+
+        #[inline]
+        pub fn object_identifier<C: objectidentifier::Constraint>() -> Self {
+            Self::ObjectIdentifier { tag: C::TAG }
+        }
+
+        #[inline]
+        pub fn relative_oid<C: relativeoid::Constraint>() -> Self {
+            Self::RelativeOid { tag: C::TAG }
+        }
+
+        #[inline]
+        pub fn oid_iri<C: oidiri::Constraint>() -> Self {
+            Self::OidIri { tag: C::TAG }
+        }
+
         #[inline]
         pub fn bits_length_determinant(
             lower_bound: Option<u64>,
@@ -0,0 +1,582 @@
+//! Transcoding of UPER encoded values into any `serde` data format - without the generated types.
+//!
+//! The [`UperTranscoder`] decodes the value of a type of a (resolved) ASN.1 [`Model`] while it is
+//! being serialized: each primitive is read from the encoding right before it is handed to the
+//! [`Serializer`] and no intermediate structs are built. This allows gateway services to forward
+//! UPER messages as JSON, CBOR or MessagePack - in the same manner as `serde_transcode` does for
+//! a `Deserializer`.
+//!
+//! ```rust
+//! use asn1rs::model::parse::Tokenizer;
+//! use asn1rs::model::Model;
+//! use asn1rs::rw::UperTranscoder;
+//!
+//! let model = Model::try_from(Tokenizer.parse(
+//!     r"Gateway DEFINITIONS AUTOMATIC TAGS ::=
+//!     BEGIN
+//!     Message ::= SEQUENCE {
+//!         id    INTEGER (0..255),
+//!         valid BOOLEAN
+//!     }
+//!     END",
+//! ))
+//! .unwrap()
+//! .try_resolve()
+//! .unwrap();
+//!
+//! let transcoder = UperTranscoder::new(&model, "Message", &[0x2A, 0x80]).unwrap();
+//! assert_eq!(
+//!     r#"{"id":42,"valid":true}"#,
+//!     serde_json::to_string(&transcoder).unwrap()
+//! );
+//! ```
+//!
+//! The values are mapped as follows:
+//!  - `SEQUENCE` and `SET` to a map with the names of the components as keys, absent `OPTIONAL`
+//!    components are serialized as `None`, absent components with a `DEFAULT` as default value
+//!  - `SEQUENCE OF` and `SET OF` to a sequence
+//!  - `CHOICE` to a map with a single entry, the name of the variant as key
+//!  - `ENUMERATED` to the name of the variant
+//!  - `INTEGER` to `i64`, `OCTET STRING` to bytes and `BIT STRING` to a tuple of the bytes and
+//!    the number of bits
+//!  - `NULL` to unit
+//!
+//! Values that are nested deeper than [`UperTranscoder::with_max_depth`] fail with
+//! [`ErrorKind::NestingDepthExceedsLimit`], instead of overflowing the stack on crafted input
+//! for recursive types.
+
+use crate::model::asn::{Asn, Charset, ComponentTypeList, Range, Size, Type};
+use crate::model::resolve::Resolved;
+use crate::model::{Definition, Field, LiteralValue, Model};
+use crate::protocol::per::err::{Error, ErrorKind};
+use crate::protocol::per::unaligned::{BitRead, ScopedBitRead, BYTE_LEN};
+use crate::protocol::per::PackedRead;
+use crate::rw::depth::Depth;
+use crate::rw::Bits;
+use serde::ser::{SerializeMap, SerializeSeq, SerializeTuple};
+use serde::{Serialize, Serializer};
+use std::cell::RefCell;
+
+/// Serializes the UPER encoded value of an ASN.1 type of the given [`Model`], decoding it on the
+/// fly. Decoding errors are reported as custom errors of the [`Serializer`].
+#[derive(Debug, Clone, Copy)]
+pub struct UperTranscoder<'a> {
+    model: &'a Model<Asn>,
+    r#type: &'a Type,
+    bytes: &'a [u8],
+    bit_len: usize,
+    depth: Depth,
+}
+
+impl<'a> UperTranscoder<'a> {
+    /// Returns `None` if the model has no definition with the given name
+    pub fn new(model: &'a Model<Asn>, name: &str, bytes: &'a [u8]) -> Option<Self> {
+        Some(Self {
+            model,
+            r#type: definition(model, name)?,
+            bytes,
+            bit_len: bytes.len() * BYTE_LEN,
+            depth: Depth::default(),
+        })
+    }
+
+    /// Limits the encoding to the given number of bits, the default is the length of the bytes
+    pub fn with_bit_len(mut self, bit_len: usize) -> Self {
+        self.bit_len = bit_len.min(self.bytes.len() * BYTE_LEN);
+        self
+    }
+
+    /// Limits the nesting depth like [`DepthLimit::set_max_depth`](crate::rw::DepthLimit::set_max_depth)
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.depth.max = max_depth;
+        self
+    }
+}
+
+impl Serialize for UperTranscoder<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bits = RefCell::new(Bits::from((self.bytes, self.bit_len)));
+        serialize_type(self.model, self.r#type, &bits, self.depth, serializer)
+    }
+}
+
+fn definition<'a>(model: &'a Model<Asn>, name: &str) -> Option<&'a Type> {
+    model
+        .definitions
+        .iter()
+        .find(|Definition(n, _)| n == name)
+        .map(|Definition(_, asn)| &asn.r#type)
+}
+
+/// A value that is decoded once it is serialized
+struct Value<'a, 'b, 'c> {
+    model: &'a Model<Asn>,
+    r#type: &'a Type,
+    bits: &'b RefCell<Bits<'c>>,
+    depth: Depth,
+}
+
+impl Serialize for Value<'_, '_, '_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_type(self.model, self.r#type, self.bits, self.depth, serializer)
+    }
+}
+
+struct Literal<'a>(&'a LiteralValue);
+
+impl Serialize for Literal<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            LiteralValue::Boolean(value) => serializer.serialize_bool(*value),
+            LiteralValue::String(value) => serializer.serialize_str(value),
+            LiteralValue::Integer(value) => serializer.serialize_i64(*value),
+            LiteralValue::OctetString(value) => serializer.serialize_bytes(value),
+            LiteralValue::BitString(value, bit_len) => {
+                (Bytes(value), bit_len).serialize(serializer)
+            }
+            LiteralValue::EnumeratedVariant(_, variant) => serializer.serialize_str(variant),
+            LiteralValue::Sequence(components) => serializer.collect_map(
+                components
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), Literal(value))),
+            ),
+            LiteralValue::SequenceOf(values) => serializer.collect_seq(values.iter().map(Literal)),
+            LiteralValue::Choice(name, value) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(name, &Literal(value))?;
+                map.end()
+            }
+            LiteralValue::Reference(name) => serializer.serialize_str(name),
+        }
+    }
+}
+
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+fn custom<S: Serializer>(error: Error) -> S::Error {
+    serde::ser::Error::custom(error)
+}
+
+fn serialize_type<S: Serializer>(
+    model: &Model<Asn>,
+    r#type: &Type,
+    bits: &RefCell<Bits>,
+    mut depth: Depth,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if let Type::Sequence(_)
+    | Type::Set(_)
+    | Type::SequenceOf(..)
+    | Type::SetOf(..)
+    | Type::Choice(_) = r#type
+    {
+        depth
+            .enter()
+            .map_err(|max| custom::<S>(ErrorKind::NestingDepthExceedsLimit(max).into()))?;
+    }
+    match r#type {
+        Type::Boolean => {
+            let value = bits.borrow_mut().read_boolean().map_err(custom::<S>)?;
+            serializer.serialize_bool(value)
+        }
+        Type::Integer(integer) => {
+            let value =
+                read_integer(&mut bits.borrow_mut(), &integer.range).map_err(custom::<S>)?;
+            serializer.serialize_i64(value)
+        }
+        Type::String(size, charset) => {
+            let value = read_string(&mut bits.borrow_mut(), size, *charset).map_err(custom::<S>)?;
+            serializer.serialize_str(&value)
+        }
+        Type::OctetString(size) => {
+            let value = read_octet_string(&mut bits.borrow_mut(), size).map_err(custom::<S>)?;
+            serializer.serialize_bytes(&value)
+        }
+        Type::Containing(inner, encoded_by) => {
+            if let Some(encoded_by) = encoded_by {
+                return Err(custom::<S>(
+                    ErrorKind::UnsupportedOperation(format!(
+                        "Transcoding content that is encoded by {encoded_by}"
+                    ))
+                    .into(),
+                ));
+            }
+            let content =
+                read_octet_string(&mut bits.borrow_mut(), &Size::Any).map_err(custom::<S>)?;
+            let content = RefCell::new(Bits::from(&content[..]));
+            serialize_type(model, inner, &content, depth, serializer)
+        }
+        Type::BitString(bit_string) => {
+            let size = &bit_string.size;
+            let (bytes, bit_len) = bits
+                .borrow_mut()
+                .read_bitstring(
+                    size.min().map(|min| *min as u64),
+                    size.max().map(|max| *max as u64),
+                    size.extensible(),
+                )
+                .map_err(custom::<S>)?;
+            let mut tuple = serializer.serialize_tuple(2)?;
+            tuple.serialize_element(&Bytes(&bytes))?;
+            tuple.serialize_element(&bit_len)?;
+            tuple.end()
+        }
+        Type::Null => serializer.serialize_unit(),
+        Type::Real(_) => {
+            let contents =
+                read_octet_string(&mut bits.borrow_mut(), &Size::Any).map_err(custom::<S>)?;
+            let value = crate::protocol::basic::real::decode(&contents)
+                .map_err(|reason| custom::<S>(ErrorKind::InvalidReal(reason).into()))?;
+            serializer.serialize_f64(value)
+        }
+        Type::ObjectIdentifier => {
+            let contents =
+                read_octet_string(&mut bits.borrow_mut(), &Size::Any).map_err(custom::<S>)?;
+            let arcs = crate::protocol::basic::oid::decode(&contents)
+                .map_err(|reason| custom::<S>(ErrorKind::InvalidObjectIdentifier(reason).into()))?;
+            serializer.collect_str(&crate::descriptor::Oid::from(arcs))
+        }
+        Type::RelativeOid => {
+            let contents =
+                read_octet_string(&mut bits.borrow_mut(), &Size::Any).map_err(custom::<S>)?;
+            let arcs = crate::protocol::basic::oid::decode_relative(&contents)
+                .map_err(|reason| custom::<S>(ErrorKind::InvalidObjectIdentifier(reason).into()))?;
+            serializer.collect_str(&crate::descriptor::Oid::from(arcs))
+        }
+        Type::OidIri => {
+            let octets =
+                read_octet_string(&mut bits.borrow_mut(), &Size::Any).map_err(custom::<S>)?;
+            let iri = String::from_utf8(octets)
+                .map_err(|e| custom::<S>(ErrorKind::FromUtf8Error(e).into()))?;
+            serializer.serialize_str(&iri)
+        }
+        Type::Optional(inner) | Type::Default(inner, _) => {
+            let present = bits.borrow_mut().read_bit().map_err(custom::<S>)?;
+            match r#type {
+                Type::Default(_, default) if !present => Literal(default).serialize(serializer),
+                _ if !present => serializer.serialize_none(),
+                _ => serializer.serialize_some(&Value {
+                    model,
+                    r#type: inner,
+                    bits,
+                    depth,
+                }),
+            }
+        }
+        Type::Sequence(components) | Type::Set(components) => {
+            serialize_components(model, components, bits, depth, serializer)
+        }
+        Type::SequenceOf(inner, size) | Type::SetOf(inner, size) => {
+            let len = {
+                let mut bits = bits.borrow_mut();
+                if size.extensible() && bits.read_bit().map_err(custom::<S>)? {
+                    bits.read_length_determinant(None, None)
+                } else {
+                    bits.read_length_determinant(
+                        size.min().map(|min| *min as u64),
+                        size.max().map(|max| *max as u64),
+                    )
+                }
+                .map_err(custom::<S>)?
+            };
+            let mut sequence = serializer.serialize_seq(Some(len as usize))?;
+            for _ in 0..len {
+                sequence.serialize_element(&Value {
+                    model,
+                    r#type: inner,
+                    bits,
+                    depth,
+                })?;
+            }
+            sequence.end()
+        }
+        Type::Enumerated(enumerated) => {
+            let std_variants = enumerated
+                .extension_after_index()
+                .map(|index| index + 1)
+                .unwrap_or_else(|| enumerated.len());
+            let index = bits
+                .borrow_mut()
+                .read_enumeration_index(std_variants as u64, enumerated.is_extensible())
+                .map_err(custom::<S>)?;
+            let variant = enumerated.variants().nth(index as usize).ok_or_else(|| {
+                custom::<S>(ErrorKind::InvalidChoiceIndex(index, enumerated.len() as u64).into())
+            })?;
+            serializer.serialize_str(variant.name())
+        }
+        Type::Choice(choice) => {
+            let std_variants = choice
+                .extension_after_index()
+                .map(|index| index + 1)
+                .unwrap_or_else(|| choice.len());
+            let (index, end) = {
+                let mut bits = bits.borrow_mut();
+                let index = bits
+                    .read_choice_index(std_variants as u64, choice.is_extensible())
+                    .map_err(custom::<S>)?;
+                if index >= std_variants as u64 {
+                    let length = bits
+                        .read_length_determinant(None, None)
+                        .map_err(custom::<S>)?;
+                    (index, Some(bits.pos() + length as usize * BYTE_LEN))
+                } else {
+                    (index, None)
+                }
+            };
+            let variant = choice.variants().nth(index as usize).ok_or_else(|| {
+                custom::<S>(ErrorKind::InvalidChoiceIndex(index, choice.len() as u64).into())
+            })?;
+            let value = Value {
+                model,
+                r#type: variant.r#type(),
+                bits,
+                depth,
+            };
+            let mut map = serializer.serialize_map(Some(1))?;
+            match end {
+                Some(end) => {
+                    within_open_type(bits, end, || map.serialize_entry(variant.name(), &value))?
+                }
+                None => map.serialize_entry(variant.name(), &value)?,
+            }
+            map.end()
+        }
+        Type::TypeReference(name, _) => {
+            let r#type = definition(model, name).ok_or_else(|| {
+                serde::ser::Error::custom(format!("Unknown type reference {name}"))
+            })?;
+            serialize_type(model, r#type, bits, depth, serializer)
+        }
+        Type::Substituted(inner, _) => serialize_type(model, inner, bits, depth, serializer),
+    }
+}
+
+/// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 19
+fn serialize_components<S: Serializer>(
+    model: &Model<Asn>,
+    components: &ComponentTypeList<Resolved>,
+    bits: &RefCell<Bits>,
+    depth: Depth,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let fields = &components.fields;
+    let root_len = components
+        .extension_after
+        .map(|index| index + 1)
+        .unwrap_or(fields.len())
+        .min(fields.len());
+
+    let (has_extensions, presence) = {
+        let mut bits = bits.borrow_mut();
+        let has_extensions =
+            components.extension_after.is_some() && bits.read_bit().map_err(custom::<S>)?;
+        let mut presence = Vec::with_capacity(root_len);
+        for field in &fields[..root_len] {
+            presence.push(if is_optional(field) {
+                Some(bits.read_bit().map_err(custom::<S>)?)
+            } else {
+                None
+            });
+        }
+        (has_extensions, presence)
+    };
+
+    let mut map = serializer.serialize_map(Some(fields.len()))?;
+    for (field, present) in fields[..root_len].iter().zip(presence) {
+        match (present, &field.role.default) {
+            (Some(false), Some(default)) => map.serialize_entry(&field.name, &Literal(default))?,
+            (Some(false), None) => map.serialize_entry(&field.name, &None::<()>)?,
+            (_, _) => map.serialize_entry(
+                &field.name,
+                &Value {
+                    model,
+                    r#type: without_optional(&field.role.r#type),
+                    bits,
+                    depth,
+                },
+            )?,
+        }
+    }
+
+    let extensions = &fields[root_len..];
+    let mut presence = vec![false; extensions.len()];
+    let mut unknown = 0;
+    if has_extensions {
+        let mut bits = bits.borrow_mut();
+        let count = bits.read_normally_small_length().map_err(custom::<S>)? + 1;
+        for index in 0..count as usize {
+            let present = bits.read_bit().map_err(custom::<S>)?;
+            match presence.get_mut(index) {
+                Some(entry) => *entry = present,
+                None if present => unknown += 1,
+                None => {}
+            }
+        }
+    }
+
+    for (field, present) in extensions.iter().zip(presence) {
+        let value = Value {
+            model,
+            r#type: without_optional(&field.role.r#type),
+            bits,
+            depth,
+        };
+        match (present, &field.role.default) {
+            // like the generated code, values with a DEFAULT are not wrapped in an open type
+            (true, Some(_)) => map.serialize_entry(&field.name, &value)?,
+            (true, None) => {
+                let end = {
+                    let mut bits = bits.borrow_mut();
+                    let length = bits
+                        .read_length_determinant(None, None)
+                        .map_err(custom::<S>)?;
+                    bits.pos() + length as usize * BYTE_LEN
+                };
+                within_open_type(bits, end, || {
+                    map.serialize_entry(&field.name, &Some(&value))
+                })?;
+            }
+            (false, Some(default)) => map.serialize_entry(&field.name, &Literal(default))?,
+            (false, None) => map.serialize_entry(&field.name, &None::<()>)?,
+        }
+    }
+
+    // skip the extensions that are unknown to the model
+    let mut bits = bits.borrow_mut();
+    for _ in 0..unknown {
+        let length = bits
+            .read_length_determinant(None, None)
+            .map_err(custom::<S>)?;
+        bits.ensure_remaining_bits(length * BYTE_LEN as u64)
+            .map_err(custom::<S>)?;
+        let position = bits.pos() + length as usize * BYTE_LEN;
+        bits.set_pos(position);
+    }
+    drop(bits);
+
+    map.end()
+}
+
+fn is_optional(field: &Field<Asn>) -> bool {
+    field.role.default.is_some() || matches!(field.role.r#type, Type::Optional(_))
+}
+
+fn without_optional(r#type: &Type) -> &Type {
+    match r#type {
+        Type::Optional(inner) => inner,
+        r#type => r#type,
+    }
+}
+
+/// Limits the encoding to the open type field that ends at the given position while calling `f`
+/// and continues after the end of it afterwards.
+fn within_open_type<T, E: serde::ser::Error, F: FnOnce() -> Result<T, E>>(
+    bits: &RefCell<Bits>,
+    end: usize,
+    f: F,
+) -> Result<T, E> {
+    let original = {
+        let mut bits = bits.borrow_mut();
+        if end > bits.len() {
+            return Err(E::custom(Error::from(ErrorKind::EndOfStream)));
+        }
+        let original = bits.len();
+        bits.set_len(end);
+        original
+    };
+    let result = f();
+    let mut bits = bits.borrow_mut();
+    bits.set_len(original);
+    bits.set_pos(end);
+    result
+}
+
+/// Mirrors the mapping of INTEGER constraints onto the Rust types of the generated code, so that
+/// the transcoder reads the same bits as the generated types do.
+fn read_integer(bits: &mut Bits, range: &Range<Option<i64>>) -> Result<i64, Error> {
+    let (min, max, extensible) = (*range.min(), *range.max(), range.extensible());
+    let unconstrained = matches!(
+        (min, max),
+        (None, None) | (Some(0), None) | (Some(0), Some(i64::MAX)) | (None, Some(i64::MAX))
+    );
+    let (min, max) = if unconstrained {
+        (None, None)
+    } else if extensible && min.unwrap_or_default() >= 0 && max.unwrap_or_default() >= 0 {
+        (min, max)
+    } else if extensible {
+        (Some(min.unwrap_or(i64::MIN)), Some(max.unwrap_or(i64::MAX)))
+    } else {
+        (Some(min.unwrap_or_default()), Some(max.unwrap_or(i64::MAX)))
+    };
+
+    let unconstrained = if extensible {
+        bits.read_bit()?
+    } else {
+        min.is_none() && max.is_none()
+    };
+
+    if unconstrained {
+        bits.read_unconstrained_whole_number()
+    } else {
+        bits.read_constrained_whole_number(min.unwrap_or(0), max.unwrap_or(i64::MAX))
+    }
+}
+
+fn read_octet_string(bits: &mut Bits, size: &Size) -> Result<Vec<u8>, Error> {
+    bits.read_octetstring(
+        size.min().map(|min| *min as u64),
+        size.max().map(|max| *max as u64),
+        size.extensible(),
+    )
+}
+
+fn read_string(bits: &mut Bits, size: &Size, charset: Charset) -> Result<String, Error> {
+    let buffer = if charset == Charset::Utf8 {
+        // ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 30.3
+        bits.read_octetstring(None, None, false)?
+    } else if charset == Charset::Teletex {
+        // ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 30.6
+        let octets = bits.read_octetstring(None, None, false)?;
+        return Ok(octets.into_iter().map(char::from).collect());
+    } else {
+        let len = if size.extensible() && bits.read_bit()? {
+            bits.read_length_determinant(None, None)?
+        } else {
+            bits.read_length_determinant(
+                size.min().map(|min| *min as u64),
+                size.max().map(|max| *max as u64),
+            )?
+        };
+
+        if charset == Charset::Bmp {
+            bits.ensure_remaining_bits(len.saturating_mul(16))?;
+            let mut octets = vec![0u8; len as usize * 2];
+            bits.read_bits(&mut octets)?;
+            return Charset::Bmp.decode(&octets).ok_or_else(|| {
+                ErrorKind::InvalidString(Charset::Bmp, char::REPLACEMENT_CHARACTER, 0).into()
+            });
+        }
+
+        let mut buffer = vec![0u8; len as usize];
+        for byte in buffer.iter_mut() {
+            if charset == Charset::Numeric {
+                bits.read_bits_with_offset(core::slice::from_mut(byte), 4)?;
+                *byte = match *byte {
+                    0 => b' ',
+                    c => b'0' - 1 + c,
+                };
+            } else {
+                bits.read_bits_with_offset(core::slice::from_mut(byte), 1)?;
+            }
+        }
+        buffer
+    };
+
+    String::from_utf8(buffer).map_err(|e| ErrorKind::FromUtf8Error(e).into())
+}
@@ -0,0 +1,178 @@
+use crate::descriptor::Readable;
+use crate::protocol::per::unaligned::BYTE_LEN;
+use crate::protocol::per::ErrorKind;
+use crate::rw::{PositionedReader, UperReader};
+use crate::Error;
+use std::io::Read;
+
+/// The octets read from the source at once, unless more are buffered already
+const CHUNK_LEN: usize = 8 * 1024;
+
+/// The default limit of the octets of a PDU of a [`UperStreamReader`]
+pub const DEFAULT_MAX_PDU_LEN: usize = 8 * 1024 * 1024;
+
+/// Decodes back-to-back UPER encoded PDUs - each padded to whole octets - from a
+/// [`std::io::Read`], such as a file or a socket, without reading the whole input first.
+///
+/// The octets of decoded PDUs are dropped from the buffer, it holds the octets of the PDU that is
+/// currently decoded and those read from the source after it. If a PDU is not completely
+/// buffered yet, decoding it starts over once the source provided the octets that the failed
+/// attempt lacked - such as the octets announced by a length determinant or the header of a
+/// fragment of an `OCTET STRING` of more than 16K octets. So the attempts to decode a PDU are
+/// bounded by the number of its length determinants, regardless of how few octets each read of
+/// the source provides.
+///
+/// A PDU that is longer than [`UperStreamReader::max_pdu_len`] octets - default:
+/// [`DEFAULT_MAX_PDU_LEN`] - fails with [`ErrorKind::PduExceedsLimit`], so that a source that
+/// never completes a PDU cannot make the buffer grow without bounds.
+///
+/// If the source fails - for example with [`std::io::ErrorKind::WouldBlock`] - the buffered
+/// octets are kept, so that calling [`UperStreamReader::read`] again resumes decoding the PDU.
+///
+/// ```rust
+/// use asn1rs::prelude::*;
+///
+/// #[asn(sequence)]
+/// #[derive(Debug, Default, PartialEq)]
+/// pub struct Reading {
+///     #[asn(integer(0..1023))]
+///     sensor: u16,
+///     #[asn(octet_string)]
+///     samples: Vec<u8>,
+/// }
+///
+/// let mut writer = UperWriter::default();
+/// for sensor in 0..3 {
+///     writer.write(&Reading { sensor, samples: vec![0x42; 20_000] }).unwrap();
+///     writer.align_to_octet().unwrap();
+/// }
+/// let file = std::io::Cursor::new(writer.into_bytes_vec());
+///
+/// let mut reader = UperStreamReader::new(file);
+/// while let Some(reading) = reader.read::<Reading>().unwrap() {
+///     assert_eq!(20_000, reading.samples.len());
+/// }
+/// ```
+pub struct UperStreamReader<R: Read> {
+    source: R,
+    /// Holds the read octets up to `filled`, the octets after it are not initialized again for
+    /// each read of the source
+    buffer: Vec<u8>,
+    filled: usize,
+    /// The position in the buffer the next PDU starts at
+    start: usize,
+    /// The octets of the next PDU that need to be buffered before decoding it is attempted again
+    pending: usize,
+    end_of_source: bool,
+    max_pdu_len: usize,
+}
+
+impl<R: Read> UperStreamReader<R> {
+    pub fn new(source: R) -> Self {
+        Self {
+            source,
+            buffer: Vec::new(),
+            filled: 0,
+            start: 0,
+            pending: 0,
+            end_of_source: false,
+            max_pdu_len: DEFAULT_MAX_PDU_LEN,
+        }
+    }
+
+    /// Limits the octets of a PDU, see [`UperStreamReader::max_pdu_len`]
+    pub fn with_max_pdu_len(mut self, max_pdu_len: usize) -> Self {
+        self.max_pdu_len = max_pdu_len;
+        self
+    }
+
+    /// The octets a PDU may span at most, before reading it fails with
+    /// [`ErrorKind::PduExceedsLimit`]
+    pub fn max_pdu_len(&self) -> usize {
+        self.max_pdu_len
+    }
+
+    /// Reads the next PDU, `None` if the source ends before it. Fails with
+    /// [`Kind::EndOfData`](crate::error::Kind::EndOfData) if the source ends within a PDU.
+    pub fn read<T: Readable>(&mut self) -> Result<Option<T>, Error> {
+        loop {
+            let buffered = self.filled - self.start;
+            if buffered > 0 && (buffered >= self.pending || self.end_of_source) {
+                let bytes = &self.buffer[self.start..self.filled];
+                let mut reader = UperReader::from((bytes, bytes.len() * BYTE_LEN));
+                let result = reader.iter::<T>().next();
+                match result {
+                    Some(Ok(value)) => {
+                        self.start += reader.bit_position().div_ceil(BYTE_LEN);
+                        self.pending = 0;
+                        return Ok(Some(value));
+                    }
+                    Some(Err(e)) if self.end_of_source || !Self::needs_more_octets(&e) => {
+                        return Err(e.into())
+                    }
+                    Some(Err(e)) => {
+                        self.pending = (buffered * BYTE_LEN)
+                            .saturating_add(e.missing_bits().unwrap_or(1))
+                            .div_ceil(BYTE_LEN);
+                    }
+                    None => self.pending = buffered + 1,
+                }
+            } else if buffered == 0 && self.end_of_source {
+                return Ok(None);
+            }
+            self.fill()?;
+        }
+    }
+
+    /// The octets read from the source, that are not part of a decoded PDU yet
+    pub fn buffered(&self) -> &[u8] {
+        &self.buffer[self.start..self.filled]
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.source
+    }
+
+    /// The source without the [`UperStreamReader::buffered`] octets
+    pub fn into_inner(self) -> R {
+        self.source
+    }
+
+    fn needs_more_octets(error: &crate::protocol::per::Error) -> bool {
+        matches!(
+            error.kind(),
+            ErrorKind::EndOfStream | ErrorKind::InsufficientDataInSourceBuffer(_)
+        )
+    }
+
+    /// Reads the next octets from the source into the buffer, after dropping the octets of the
+    /// decoded PDUs. The buffer does not grow beyond the larger of the PDU limit and a chunk.
+    fn fill(&mut self) -> Result<(), Error> {
+        self.buffer.copy_within(self.start..self.filled, 0);
+        self.filled -= self.start;
+        self.start = 0;
+
+        // the buffered octets are not a complete PDU
+        let len = self.filled;
+        if len >= self.max_pdu_len || self.pending > self.max_pdu_len {
+            return Err(
+                crate::protocol::per::Error::from(ErrorKind::PduExceedsLimit(self.max_pdu_len))
+                    .into(),
+            );
+        }
+        let capacity = self.max_pdu_len.max(CHUNK_LEN);
+        let end = (len + len.max(CHUNK_LEN).max(self.pending.saturating_sub(len))).min(capacity);
+        if self.buffer.len() < end {
+            self.buffer.resize(end, 0);
+        }
+        let result = loop {
+            match self.source.read(&mut self.buffer[len..end]) {
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                result => break result,
+            }
+        };
+        self.filled = len + *result.as_ref().unwrap_or(&0);
+        self.end_of_source = matches!(result, Ok(0));
+        result.map(drop).map_err(Error::from)
+    }
+}
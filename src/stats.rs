@@ -0,0 +1,188 @@
+//! Statistics about the values that are encoded and decoded, collected per type if the `stats`
+//! feature is enabled. Only the values that are passed to [`Writer::write`] and
+//! [`Reader::read`] are counted - the values nested in them are part of their encoding.
+//!
+//! ```rust
+//! use asn1rs::prelude::*;
+//!
+//! #[asn(sequence)]
+//! #[derive(Debug, Default, PartialEq)]
+//! pub struct Heartbeat {
+//!     #[asn(integer(0..255))]
+//!     sequence: u8,
+//! }
+//!
+//! let mut writer = UperWriter::default();
+//! writer.write(&Heartbeat { sequence: 42 }).unwrap();
+//!
+//! let stats = asn1rs::stats::of::<Heartbeat>();
+//! assert_eq!(1, stats.encoded.values);
+//! assert_eq!(1, stats.encoded.bytes);
+//! assert_eq!(0, stats.decoded.values);
+//! ```
+//!
+//! [`Writer::write`]: crate::descriptor::Writer::write
+//! [`Reader::read`]: crate::descriptor::Reader::read
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::sync::{Mutex, PoisonError};
+
+static REGISTRY: Mutex<BTreeMap<&'static str, TypeStats>> = Mutex::new(BTreeMap::new());
+
+/// The encoded and decoded values of a type
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TypeStats {
+    pub encoded: Counts,
+    pub decoded: Counts,
+}
+
+/// The values of a type that were encoded or decoded
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Counts {
+    /// The values that were encoded or decoded successfully
+    pub values: u64,
+    /// The values that failed to be encoded or decoded
+    pub errors: u64,
+    /// The size of the encodings of the successful values. The encodings of a reader or writer
+    /// that does not keep track of its position are counted as values only.
+    pub bytes: u64,
+    /// The distribution of the sizes that make up [`Counts::bytes`]
+    pub sizes: SizeHistogram,
+}
+
+impl Counts {
+    fn record(&mut self, bytes: Option<usize>, success: bool) {
+        if !success {
+            self.errors += 1;
+            return;
+        }
+        self.values += 1;
+        if let Some(bytes) = bytes {
+            self.bytes += bytes as u64;
+            self.sizes.record(bytes);
+        }
+    }
+}
+
+/// The number of encodings by their size in bytes: the first bucket counts the empty
+/// encodings, the bucket `n` those of `2^(n-1)..2^n` bytes and the last bucket also all larger
+/// ones
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SizeHistogram([u64; SizeHistogram::BUCKETS]);
+
+impl SizeHistogram {
+    pub const BUCKETS: usize = 17;
+
+    pub fn record(&mut self, bytes: usize) {
+        let bucket = (usize::BITS - bytes.leading_zeros()) as usize;
+        self.0[bucket.min(Self::BUCKETS - 1)] += 1;
+    }
+
+    /// The sizes in bytes that are counted in the bucket
+    pub fn range(bucket: usize) -> Range<usize> {
+        match bucket {
+            0 => 0..1,
+            b if b + 1 >= Self::BUCKETS => 1 << (Self::BUCKETS - 2)..usize::MAX,
+            b => 1 << (b - 1)..1 << b,
+        }
+    }
+
+    /// The number of encodings in each bucket, together with the sizes it counts
+    pub fn buckets(&self) -> impl Iterator<Item = (Range<usize>, u64)> + '_ {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(bucket, count)| (Self::range(bucket), *count))
+    }
+
+    /// The number of encodings in all buckets
+    pub fn count(&self) -> u64 {
+        self.0.iter().sum()
+    }
+}
+
+/// The statistics of the type, keyed by [`core::any::type_name`]
+pub fn of<T: ?Sized>() -> TypeStats {
+    get(core::any::type_name::<T>()).unwrap_or_default()
+}
+
+/// The statistics of the type with the given name, see [`core::any::type_name`]
+pub fn get(type_name: &str) -> Option<TypeStats> {
+    registry().get(type_name).cloned()
+}
+
+/// The statistics of all types that were encoded or decoded so far
+pub fn snapshot() -> BTreeMap<&'static str, TypeStats> {
+    registry().clone()
+}
+
+/// Discards the statistics of all types
+pub fn reset() {
+    registry().clear();
+}
+
+pub(crate) fn record_encoded<T: ?Sized>(start: Option<usize>, end: Option<usize>, success: bool) {
+    registry()
+        .entry(core::any::type_name::<T>())
+        .or_default()
+        .encoded
+        .record(bytes_between(start, end), success);
+}
+
+pub(crate) fn record_decoded<T: ?Sized>(start: Option<usize>, end: Option<usize>, success: bool) {
+    registry()
+        .entry(core::any::type_name::<T>())
+        .or_default()
+        .decoded
+        .record(bytes_between(start, end), success);
+}
+
+fn bytes_between(start_bits: Option<usize>, end_bits: Option<usize>) -> Option<usize> {
+    Some(end_bits?.checked_sub(start_bits?)?.div_ceil(8))
+}
+
+fn registry() -> std::sync::MutexGuard<'static, BTreeMap<&'static str, TypeStats>> {
+    // the statistics stay consistent even if a thread panicked while holding the lock
+    REGISTRY.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_buckets() {
+        let mut histogram = SizeHistogram::default();
+        for bytes in [0, 1, 2, 3, 4, 1000, 40_000, usize::MAX] {
+            histogram.record(bytes);
+        }
+        let buckets = histogram
+            .buckets()
+            .filter(|(_range, count)| *count > 0)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (0..1, 1),
+                (1..2, 1),
+                (2..4, 2),
+                (4..8, 1),
+                (512..1024, 1),
+                (32768..usize::MAX, 2),
+            ],
+            buckets
+        );
+        assert_eq!(8, histogram.count());
+        assert!((0..SizeHistogram::BUCKETS)
+            .all(|bucket| SizeHistogram::range(bucket).contains(&(1 << bucket >> 1))));
+    }
+
+    #[test]
+    fn test_bytes_between() {
+        assert_eq!(Some(0), bytes_between(Some(3), Some(3)));
+        assert_eq!(Some(1), bytes_between(Some(3), Some(4)));
+        assert_eq!(Some(2), bytes_between(Some(0), Some(9)));
+        assert_eq!(None, bytes_between(None, Some(9)));
+        assert_eq!(None, bytes_between(Some(9), Some(3)));
+    }
+}
@@ -0,0 +1,111 @@
+use asn1rs::debug::annotate;
+use asn1rs::prelude::*;
+
+asn_to_rust!(
+    r"Annotate DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Vehicle ::= SEQUENCE {
+        speed INTEGER (0..255),
+        plate IA5String OPTIONAL,
+        passengers SEQUENCE OF Person,
+        occupant Occupant,
+        ...,
+        trailer BOOLEAN OPTIONAL
+    }
+
+    Person ::= SEQUENCE {
+        name UTF8String,
+        seat INTEGER (1..9) OPTIONAL
+    }
+
+    Occupant ::= CHOICE {
+        person Person,
+        unknown NULL
+    }
+
+    END"
+);
+
+fn vehicle() -> Vehicle {
+    Vehicle {
+        speed: 42,
+        plate: None,
+        passengers: vec![
+            Person {
+                name: "Jane".to_string(),
+                seat: Some(1),
+            },
+            Person {
+                name: "John".to_string(),
+                seat: None,
+            },
+        ],
+        occupant: Occupant::Person(Person {
+            name: "Joe".to_string(),
+            seat: None,
+        }),
+        trailer: Some(true),
+    }
+}
+
+fn encode(value: &impl Writable) -> Vec<u8> {
+    let mut writer = UperWriter::default();
+    writer.write(value).unwrap();
+    writer.into_bytes_vec()
+}
+
+#[test]
+fn test_regions_are_labeled_with_paths() {
+    let annotation = annotate::<Vehicle>(&encode(&vehicle())).unwrap();
+    assert_eq!(
+        [
+            ("", 0..162),
+            // after the extension bit and the presence bit of plate
+            ("speed", 2..10),
+            ("passengers", 10..104),
+            ("passengers[0]", 18..63),
+            ("passengers[0].name", 19..59),
+            ("passengers[0].seat", 59..63),
+            ("passengers[1]", 63..104),
+            ("passengers[1].name", 64..104),
+            ("occupant", 104..138),
+            ("occupant.Person", 105..138),
+            ("occupant.Person.name", 106..138),
+            // after the bit field of the extension additions, including its length prefix
+            ("trailer", 146..162),
+        ]
+        .map(|(path, bits)| (path.to_string(), bits))
+        .to_vec(),
+        annotation
+            .regions()
+            .iter()
+            .map(|region| (region.path.to_string(), region.bits.clone()))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Some(2..10),
+        annotation.region("speed").map(|r| r.bits.clone())
+    );
+    assert_eq!(None, annotation.region("plate"));
+}
+
+#[test]
+fn test_dump_has_a_line_per_value() {
+    let annotation = annotate::<Vehicle>(&encode(&vehicle())).unwrap();
+    let dump = annotation.to_string();
+    let lines = dump.lines().collect::<Vec<_>>();
+    assert_eq!(1 + annotation.regions().len(), lines.len());
+    assert!(lines[0].ends_with("value"));
+    assert!(lines[1].trim_end().ends_with(" Vehicle"));
+    assert!(lines[2].starts_with("     2..10"));
+    assert!(lines[2].contains(" 8a 80 "));
+    assert!(lines[2].contains(" 00101010 "));
+    assert!(lines[2].ends_with("  speed"));
+    assert!(lines[4].ends_with("      passengers[0]"));
+}
+
+#[test]
+fn test_invalid_encoding_is_an_error() {
+    assert!(annotate::<Vehicle>(&[0x00]).is_err());
+}
@@ -0,0 +1,61 @@
+mod test_utils;
+
+use test_utils::*;
+
+#[asn_verify(
+    schema = r"Verify DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Vehicle ::= SEQUENCE {
+        speed INTEGER (0..255),
+        name  UTF8String OPTIONAL,
+        kind  Kind
+    }
+
+    Kind ::= ENUMERATED { car, truck }
+
+    END",
+    type = "Vehicle"
+)]
+#[derive(Debug, Default, PartialEq)]
+pub struct Vehicle {
+    pub speed: u8,
+    pub name: std::option::Option<String>,
+    pub kind: Kind,
+}
+
+#[asn_verify(
+    schema = r"Verify DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+    Kind ::= ENUMERATED { car, truck }
+    END",
+    type = "Kind"
+)]
+#[asn(enumerated)]
+#[derive(Debug, Default, PartialEq, Copy, Clone)]
+pub enum Kind {
+    #[default]
+    Car,
+    Truck,
+}
+
+#[asn_verify(schema = "tests/asn_verify.asn1", type = "Position")]
+#[derive(Debug, Default, PartialEq)]
+pub struct Position {
+    pub x: u16,
+    pub y: u16,
+}
+
+#[test]
+fn test_verified_types_are_left_untouched() {
+    let vehicle = Vehicle {
+        speed: 100,
+        name: None,
+        kind: Kind::Truck,
+    };
+    assert_eq!(Kind::Truck, vehicle.kind);
+    assert_eq!(Position { x: 1, y: 2 }, Position { x: 1, y: 2 });
+
+    let (bits, bytes) = serialize_uper(&Kind::Truck);
+    assert_eq!(Kind::Truck, deserialize_uper::<Kind>(&bytes, bits));
+}
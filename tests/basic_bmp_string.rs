@@ -0,0 +1,133 @@
+mod test_utils;
+
+use test_utils::*;
+
+asn_to_rust!(
+    r"BasicBmpString DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Unconstrained ::= SEQUENCE {
+        abc BMPString
+    }
+
+    BasicConstrained ::= SEQUENCE {
+        abc BMPString (SIZE(2))
+    }
+
+    BasicConstrainedSmall ::= SEQUENCE {
+        abc BMPString (SIZE(1..4))
+    }
+
+    END"
+);
+
+#[test]
+fn detect_only_invalid_character() {
+    let mut writer = UperWriter::default();
+    let result = Unconstrained {
+        abc: "A€\u{1F600}".to_string(),
+    }
+    .write(&mut writer);
+    assert_eq!(
+        Err(asn1rs::protocol::per::ErrorKind::InvalidString(
+            asn1rs::model::asn::Charset::Bmp,
+            '\u{1F600}',
+            2
+        )
+        .into()),
+        result
+    )
+}
+
+#[test]
+fn test_unconstrained() {
+    // 16 bits for each character
+    serialize_and_deserialize_uper(
+        8 * 5,
+        &[0x02, 0x00, 0x41, 0x20, 0xAC],
+        &Unconstrained {
+            abc: "A€".to_string(),
+        },
+    );
+}
+
+#[test]
+fn test_fixed_size() {
+    serialize_and_deserialize_uper(
+        8 * 4,
+        &[0x00, 0x48, 0x00, 0x69],
+        &BasicConstrained {
+            abc: "Hi".to_string(),
+        },
+    );
+}
+
+#[test]
+fn test_small() {
+    serialize_and_deserialize_uper(
+        8 * 4 + 2,
+        &[0x40, 0x12, 0x00, 0x1A, 0x40],
+        &BasicConstrainedSmall {
+            abc: "Hi".to_string(),
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "SizeNotInRange(5, 1, 4)")]
+fn test_too_large() {
+    serialize_and_deserialize_uper(
+        0,
+        &[],
+        &BasicConstrainedSmall {
+            abc: "Hello".to_string(),
+        },
+    );
+}
+
+#[test]
+fn test_surrogate_is_rejected() {
+    let mut reader = UperReader::from((&[0x01, 0xD8, 0x00][..], 24));
+    assert!(matches!(
+        reader.read::<Unconstrained>().map_err(|e| e.kind().clone()),
+        Err(asn1rs::protocol::per::ErrorKind::InvalidString(
+            asn1rs::model::asn::Charset::Bmp,
+            _,
+            0
+        ))
+    ));
+}
+
+#[test]
+fn test_ber_utf16() {
+    use asn1rs::prelude::basic::BER;
+
+    let value = Unconstrained {
+        abc: "A€".to_string(),
+    };
+    let mut writer = BER::writer();
+    writer.write(&value).unwrap();
+    let bytes = writer.into_bytes_vec();
+    assert_eq!(
+        &[0x30, 0x06, 0x80, 0x04, 0x00, 0x41, 0x20, 0xAC],
+        &bytes[..]
+    );
+    assert_eq!(value, BER::reader(&bytes).read().unwrap());
+}
+
+#[cfg(feature = "oer")]
+#[test]
+fn test_oer_fixed_size_has_no_length() {
+    let value = BasicConstrained {
+        abc: "Hi".to_string(),
+    };
+    let mut writer = CoerWriter::default();
+    writer.write(&value).unwrap();
+    assert_eq!(&[0x00, 0x48, 0x00, 0x69], writer.byte_content());
+    assert_eq!(
+        value,
+        CoerReader::from(writer.byte_content())
+            .read::<BasicConstrained>()
+            .unwrap()
+    );
+}
@@ -0,0 +1,248 @@
+mod test_utils;
+use test_utils::*;
+
+asn_to_rust!(
+    r"BasicObjectIdentifier DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    MyOid ::= OBJECT IDENTIFIER
+
+    AlgorithmIdentifier ::= SEQUENCE {
+        algorithm OBJECT IDENTIFIER,
+        parameters OBJECT IDENTIFIER OPTIONAL
+    }
+
+    Attributes ::= SEQUENCE OF MyOid
+
+    MyRelativeOid ::= RELATIVE-OID
+
+    MyOidIri ::= OID-IRI
+
+    Registration ::= SEQUENCE {
+        base OBJECT IDENTIFIER,
+        local RELATIVE-OID,
+        iri OID-IRI OPTIONAL
+    }
+
+    END"
+);
+
+fn rsa_encryption() -> Oid {
+    Oid::new([1, 2, 840, 113549, 1, 1, 1])
+}
+
+#[test]
+fn test_types() {
+    let _: Oid = MyOid(rsa_encryption()).0;
+    let _: Option<Oid> = AlgorithmIdentifier::default().parameters;
+}
+
+#[test]
+fn test_uper_value() {
+    serialize_and_deserialize_uper(
+        80,
+        &[0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x01],
+        &MyOid(rsa_encryption()),
+    );
+}
+
+fn ec_public_key() -> AlgorithmIdentifier {
+    AlgorithmIdentifier {
+        algorithm: Oid::new([1, 2, 840, 10045, 2, 1]),
+        parameters: Some(Oid::new([1, 2, 840, 10045, 3, 1, 7])),
+    }
+}
+
+#[test]
+fn test_uper_sequence() {
+    serialize_and_deserialize_uper(
+        1 + 8 * 8 + 8 * 9,
+        &[
+            0x83, 0x95, 0x43, 0x24, 0x67, 0x1E, 0x81, 0x00, 0x84, 0x15, 0x43, 0x24, 0x67, 0x1E,
+            0x81, 0x80, 0x83, 0x80,
+        ],
+        &ec_public_key(),
+    );
+}
+
+#[test]
+fn test_uper_sequence_of() {
+    serialize_and_deserialize_uper(
+        8 * 9,
+        &[0x02, 0x03, 0x55, 0x04, 0x03, 0x03, 0x88, 0x37, 0x01],
+        &Attributes(vec![
+            MyOid(Oid::new([2, 5, 4, 3])),
+            MyOid(Oid::new([2, 999, 1])),
+        ]),
+    );
+}
+
+#[test]
+fn test_uper_invalid_value_is_rejected() {
+    let mut writer = UperWriter::default();
+    let error = writer.write(&MyOid(Oid::new([3, 1]))).unwrap_err();
+    assert_eq!(
+        &per::ErrorKind::InvalidObjectIdentifier(
+            "the first arc of an object identifier must be 0, 1 or 2"
+        ),
+        error.kind()
+    );
+
+    let mut reader = UperReader::from((&[0x02, 0x2A, 0x86][..], 24));
+    assert!(matches!(
+        reader.read::<MyOid>().unwrap_err().kind(),
+        per::ErrorKind::InvalidObjectIdentifier(_)
+    ));
+}
+
+#[test]
+fn test_ber_sequence() {
+    use asn1rs::prelude::basic::BER;
+
+    let mut writer = BER::writer();
+    writer.write(&ec_public_key()).unwrap();
+    let bytes = writer.into_bytes_vec();
+    assert_eq!(
+        &[
+            0x30, 0x13, 0x80, 0x07, 0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x02, 0x01, 0x81, 0x08, 0x2A,
+            0x86, 0x48, 0xCE, 0x3D, 0x03, 0x01, 0x07,
+        ],
+        &bytes[..]
+    );
+    assert_eq!(ec_public_key(), BER::reader(&bytes).read().unwrap());
+}
+
+fn registration() -> Registration {
+    Registration {
+        base: Oid::new([1, 2, 840, 113549]),
+        local: Oid::new([1, 1, 1]),
+        iri: Some("/ISO/Member-Body".to_string()),
+    }
+}
+
+#[test]
+fn test_uper_relative_oid() {
+    // ITU-T X.690, 8.20.5
+    serialize_and_deserialize_uper(
+        40,
+        &[0x04, 0xC2, 0x7B, 0x03, 0x02],
+        &MyRelativeOid(Oid::new([8571, 3, 2])),
+    );
+    assert_eq!(
+        rsa_encryption(),
+        Oid::new([1, 2, 840, 113549]).join(&Oid::new([1, 1, 1]))
+    );
+}
+
+#[test]
+fn test_uper_oid_iri() {
+    serialize_and_deserialize_uper(
+        40,
+        &[0x04, b'/', b'I', b'S', b'O'],
+        &MyOidIri("/ISO".to_string()),
+    );
+
+    let mut writer = UperWriter::default();
+    for invalid in ["", "ISO", "/ISO/", "/ISO//Member-Body"] {
+        assert!(matches!(
+            writer
+                .write(&MyOidIri(invalid.to_string()))
+                .unwrap_err()
+                .kind(),
+            per::ErrorKind::InvalidObjectIdentifier(_)
+        ));
+    }
+}
+
+#[test]
+fn test_ber_registration() {
+    use asn1rs::prelude::basic::BER;
+
+    let mut writer = BER::writer();
+    writer.write(&registration()).unwrap();
+    let bytes = writer.into_bytes_vec();
+    let mut expected = vec![
+        0x30, 0x1F, // SEQUENCE
+        0x80, 0x06, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, // base
+        0x81, 0x03, 0x01, 0x01, 0x01, // local
+        0x82, 0x10, // iri
+    ];
+    expected.extend_from_slice(b"/ISO/Member-Body");
+    assert_eq!(expected, bytes);
+    assert_eq!(registration(), BER::reader(&bytes).read().unwrap());
+}
+
+#[cfg(feature = "oer")]
+#[test]
+fn test_oer_registration() {
+    let mut writer = CoerWriter::default();
+    writer.write(&registration()).unwrap();
+    let bytes = writer.into_bytes_vec();
+    assert_eq!(registration(), CoerReader::from(&bytes[..]).read().unwrap());
+}
+
+#[cfg(feature = "cbor")]
+#[test]
+fn test_cbor_relative_oid_and_iri() {
+    let mut writer = CborWriter::default();
+    writer.write(&registration()).unwrap();
+    let bytes = writer.into_bytes_vec();
+    let mut expected = vec![
+        0x83, // array of three
+        0xD8, 0x6F, 0x46, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, // tag 111
+        0xD8, 0x6E, 0x43, 0x01, 0x01, 0x01, // tag 110
+        0x70, // text string of 16 bytes
+    ];
+    expected.extend_from_slice(b"/ISO/Member-Body");
+    assert_eq!(expected, bytes);
+    assert_eq!(registration(), CborReader::from(&bytes[..]).read().unwrap());
+}
+
+#[cfg(feature = "oer")]
+#[test]
+fn test_oer_sequence() {
+    let mut writer = CoerWriter::default();
+    writer.write(&ec_public_key()).unwrap();
+    let bytes = writer.into_bytes_vec();
+    assert_eq!(
+        &[
+            0x80, 0x07, 0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x02, 0x01, 0x08, 0x2A, 0x86, 0x48, 0xCE,
+            0x3D, 0x03, 0x01, 0x07,
+        ],
+        &bytes[..]
+    );
+    assert_eq!(
+        ec_public_key(),
+        CoerReader::from(&bytes[..]).read().unwrap()
+    );
+}
+
+#[cfg(feature = "cbor")]
+#[test]
+fn test_cbor_sequence() {
+    let mut writer = CborWriter::default();
+    writer.write(&ec_public_key()).unwrap();
+    let bytes = writer.into_bytes_vec();
+    assert_eq!(
+        &[
+            0x82, // array of two
+            0xD8, 0x6F, 0x47, 0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x02, 0x01, // tag 111
+            0xD8, 0x6F, 0x48, 0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x03, 0x01, 0x07, // tag 111
+        ],
+        &bytes[..]
+    );
+    assert_eq!(
+        ec_public_key(),
+        CborReader::from(&bytes[..]).read().unwrap()
+    );
+}
+
+#[cfg(feature = "protobuf")]
+#[test]
+fn test_protobuf_dotted_notation() {
+    let mut bytes = vec![0x0A, 17];
+    bytes.extend_from_slice(b"1.2.840.10045.2.1");
+    bytes.extend_from_slice(&[0x12, 19]);
+    bytes.extend_from_slice(b"1.2.840.10045.3.1.7");
+    serialize_and_deserialize_protobuf(&bytes, &ec_public_key());
+}
@@ -0,0 +1,156 @@
+mod test_utils;
+use test_utils::*;
+
+asn_to_rust!(
+    r"BasicReal DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    MyReal ::= REAL
+
+    Measurement ::= SEQUENCE {
+        value REAL,
+        ratio REAL (WITH COMPONENTS { mantissa (-16777215..16777215), base (2), exponent (-125..128) }) OPTIONAL
+    }
+
+    Reading ::= CHOICE {
+        none NULL,
+        value MyReal
+    }
+
+    END"
+);
+
+#[test]
+fn test_types() {
+    let _: f64 = MyReal(1.0).0;
+    let _: Option<f32> = Measurement::default().ratio;
+}
+
+#[test]
+fn test_uper_sequence() {
+    serialize_and_deserialize_uper(
+        65,
+        &[0x81, 0xC0, 0x00, 0x00, 0x81, 0xC0, 0x7F, 0x80, 0x80],
+        &Measurement {
+            value: 1.0,
+            ratio: Some(0.5),
+        },
+    );
+}
+
+#[test]
+fn test_uper_special_values() {
+    // zero has empty contents octets
+    serialize_and_deserialize_uper(8, &[0x00], &MyReal(0.0));
+    serialize_and_deserialize_uper(16, &[0x01, 0x43], &MyReal(-0.0));
+    serialize_and_deserialize_uper(16, &[0x01, 0x40], &MyReal(f64::INFINITY));
+    serialize_and_deserialize_uper(16, &[0x01, 0x41], &MyReal(f64::NEG_INFINITY));
+
+    let (bits, bytes) = serialize_uper(&MyReal(f64::NAN));
+    assert_eq!((16, vec![0x01, 0x42]), (bits, bytes.clone()));
+    assert!(deserialize_uper::<MyReal>(&bytes, bits).0.is_nan());
+}
+
+#[test]
+fn test_uper_choice() {
+    // -6 = -3 * 2^1
+    serialize_and_deserialize_uper(
+        1 + 8 * 4,
+        &[0x81, 0xE0, 0x00, 0x81, 0x80],
+        &Reading::Value(MyReal(-6.0)),
+    );
+}
+
+#[test]
+fn test_uper_round_trip() {
+    for value in [
+        0.1,
+        -1e-300,
+        core::f64::consts::PI,
+        f64::MAX,
+        f64::MIN_POSITIVE,
+    ] {
+        let (bits, bytes) = serialize_uper(&MyReal(value));
+        assert_eq!(MyReal(value), deserialize_uper(&bytes, bits));
+    }
+}
+
+#[test]
+fn test_uper_decimal_encoding_is_accepted() {
+    // ISO 6093 NR3 form
+    let mut bytes = vec![0x06, 0x03];
+    bytes.extend_from_slice(b"25E-1");
+    assert_eq!(MyReal(2.5), deserialize_uper(&bytes, bytes.len() * 8));
+}
+
+#[test]
+fn test_ber_round_trip() {
+    use asn1rs::prelude::basic::BER;
+
+    let measurement = Measurement {
+        value: -6.0,
+        ratio: Some(0.5),
+    };
+    let mut writer = BER::writer();
+    writer.write(&measurement).unwrap();
+    let bytes = writer.into_bytes_vec();
+    assert_eq!(
+        &[0x30, 0x0A, 0x80, 0x03, 0xC0, 0x01, 0x03, 0x81, 0x03, 0x80, 0xFF, 0x01],
+        &bytes[..]
+    );
+    assert_eq!(measurement, BER::reader(&bytes).read().unwrap());
+}
+
+#[cfg(feature = "oer")]
+#[test]
+fn test_oer_round_trip() {
+    let measurement = Measurement {
+        value: 1.0,
+        ratio: Some(0.5),
+    };
+    let mut writer = CoerWriter::default();
+    writer.write(&measurement).unwrap();
+    let bytes = writer.into_bytes_vec();
+    // the single precision value is an IEEE 754 binary32
+    assert_eq!(
+        &[0x80, 0x03, 0x80, 0x00, 0x01, 0x3F, 0x00, 0x00, 0x00],
+        &bytes[..]
+    );
+    assert_eq!(measurement, CoerReader::from(&bytes[..]).read().unwrap());
+}
+
+#[cfg(feature = "cbor")]
+#[test]
+fn test_cbor_round_trip() {
+    let measurement = Measurement {
+        value: 0.1,
+        ratio: Some(1.5),
+    };
+    let mut writer = CborWriter::default();
+    writer.write(&measurement).unwrap();
+    let bytes = writer.into_bytes_vec();
+    assert_eq!(
+        &[
+            0x82, // array of two
+            0xFB, 0x3F, 0xB9, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9A, // 0.1 as binary64
+            0xF9, 0x3E, 0x00, // 1.5 as binary16
+        ],
+        &bytes[..]
+    );
+    assert_eq!(measurement, CborReader::from(&bytes[..]).read().unwrap());
+}
+
+#[cfg(feature = "protobuf")]
+#[test]
+fn test_protobuf_double_and_float() {
+    serialize_and_deserialize_protobuf(
+        &[
+            0x09, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xF0, 0x3F, // 1: double 1.0
+            0x15, 0x00, 0x00, 0x00, 0x3F, // 2: float 0.5
+        ],
+        &Measurement {
+            value: 1.0,
+            ratio: Some(0.5),
+        },
+    );
+}
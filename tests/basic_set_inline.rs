@@ -0,0 +1,87 @@
+mod test_utils;
+
+use test_utils::*;
+
+asn_to_rust!(
+    r"BasicSetInline DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+        Basic ::= SET {
+            abc [APPLICATION 7] UTF8String,
+            def INTEGER
+        }
+
+        Outer ::= SEQUENCE {
+            inner SET {
+                abc [APPLICATION 7] UTF8String,
+                def INTEGER
+            },
+            list SEQUENCE OF SET {
+                abc [APPLICATION 7] UTF8String,
+                def INTEGER
+            },
+            nested SET {
+                flag [APPLICATION 9] BOOLEAN,
+                set SET {
+                    abc [APPLICATION 7] UTF8String,
+                    def INTEGER
+                }
+            }
+        }
+
+    END"
+);
+
+fn inner() -> OuterInner {
+    OuterInner {
+        abc: "hello world".to_string(),
+        def: 778,
+    }
+}
+
+#[test]
+fn test_inline_set_is_sorted_like_a_definition() {
+    let basic = Basic {
+        abc: "hello world".to_string(),
+        def: 778,
+    };
+    assert_eq!(serialize_uper(&basic), serialize_uper(&inner()));
+    serialize_and_deserialize_uper(
+        8 * 15,
+        &[
+            // serialization order def -> abc
+            0x02, 0x03, 0x0A, 0x0B, 0x68, 0x65, 0x6C, 0x6C, 0x6F, 0x20, 0x77, 0x6F, 0x72, 0x6C, 0x64,
+        ],
+        &inner(),
+    );
+}
+
+#[test]
+fn test_nested_inline_set() {
+    // serialization order set (UNIVERSAL 17) -> flag (APPLICATION 9)
+    let (bits, mut bytes) = serialize_uper(&inner());
+    bytes.push(0x80);
+    serialize_and_deserialize_uper(
+        bits + 1,
+        &bytes,
+        &OuterNested {
+            flag: true,
+            set: inner(),
+        },
+    );
+}
+
+#[test]
+fn test_outer() {
+    let outer = Outer {
+        inner: inner(),
+        list: vec![inner()],
+        nested: OuterNested {
+            flag: false,
+            set: inner(),
+        },
+    };
+    let (bits, bytes) = serialize_uper(&outer);
+    assert_eq!(8 * 15 * 3 + 8 + 1, bits);
+    assert_eq!(outer, deserialize_uper(&bytes, bits));
+}
@@ -0,0 +1,86 @@
+mod test_utils;
+
+use test_utils::*;
+
+asn_to_rust!(
+    r"BasicTeletexString DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Unconstrained ::= SEQUENCE {
+        abc TeletexString
+    }
+
+    BasicConstrainedSmall ::= SEQUENCE {
+        abc T61String (SIZE(4..6))
+    }
+
+    END"
+);
+
+#[test]
+fn detect_only_invalid_character() {
+    let mut writer = UperWriter::default();
+    let result = Unconstrained {
+        abc: "café\u{0140}".to_string(),
+    }
+    .write(&mut writer);
+    assert_eq!(
+        Err(asn1rs::protocol::per::ErrorKind::InvalidString(
+            asn1rs::model::asn::Charset::Teletex,
+            '\u{0140}',
+            4
+        )
+        .into()),
+        result
+    )
+}
+
+#[test]
+fn test_unconstrained() {
+    // an octet for each character
+    serialize_and_deserialize_uper(
+        8 * 3,
+        &[0x02, 0x41, 0xE9],
+        &Unconstrained {
+            abc: "Aé".to_string(),
+        },
+    );
+}
+
+#[test]
+fn test_size_is_not_per_visible() {
+    // not a known-multiplier character string type, so the length is not constrained
+    serialize_and_deserialize_uper(
+        8 * 5,
+        &[0x04, 0x63, 0x61, 0x66, 0xE9],
+        &BasicConstrainedSmall {
+            abc: "café".to_string(),
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "SizeNotInRange(8, 4, 6)")]
+fn test_too_large() {
+    serialize_and_deserialize_uper(
+        0,
+        &[],
+        &BasicConstrainedSmall {
+            abc: "12345678".to_string(),
+        },
+    );
+}
+
+#[test]
+fn test_ber_single_octets() {
+    use asn1rs::prelude::basic::BER;
+
+    let value = Unconstrained {
+        abc: "Aé".to_string(),
+    };
+    let mut writer = BER::writer();
+    writer.write(&value).unwrap();
+    let bytes = writer.into_bytes_vec();
+    assert_eq!(&[0x30, 0x04, 0x80, 0x02, 0x41, 0xE9], &bytes[..]);
+    assert_eq!(value, BER::reader(&bytes).read().unwrap());
+}
@@ -138,3 +138,54 @@ fn test_extensible_extended() {
         },
     );
 }
+
+#[test]
+fn invalid_character_error_names_character_index_and_charset() {
+    let mut writer = UperWriter::default();
+    let error = Unconstrained {
+        abc: "Grün".to_string(),
+    }
+    .write(&mut writer)
+    .unwrap_err();
+    assert_eq!(
+        "Invalid character for a VisibleString at index 2: 'ü' (U+00FC)",
+        error.kind().to_string()
+    );
+}
+
+#[test]
+fn substitute_invalid_characters() {
+    let mut writer = UperWriter::default();
+    writer.set_substitute(Some('?'));
+    Unconstrained {
+        abc: "Grün\u{7F}".to_string(),
+    }
+    .write(&mut writer)
+    .unwrap();
+
+    assert_eq!(
+        Unconstrained {
+            abc: "Gr?n?".to_string(),
+        },
+        deserialize_uper::<Unconstrained>(writer.byte_content(), writer.bit_len())
+    );
+}
+
+#[test]
+fn substitute_that_is_not_valid_itself() {
+    let mut writer = UperWriter::default();
+    writer.set_substitute(Some('\u{7F}'));
+    let result = Unconstrained {
+        abc: "Grün".to_string(),
+    }
+    .write(&mut writer);
+    assert_eq!(
+        Err(asn1rs::protocol::per::ErrorKind::InvalidString(
+            asn1rs::model::asn::Charset::Visible,
+            'ü',
+            2
+        )
+        .into()),
+        result
+    )
+}
@@ -0,0 +1,274 @@
+mod test_utils;
+
+use asn1rs::prelude::basic::{ErrorKind, BER};
+use asn1rs::rw::DEFAULT_MAX_DEPTH;
+use test_utils::codec::*;
+use test_utils::*;
+
+asn_to_rust!(
+    r"BerTagged DEFINITIONS ::=
+    BEGIN
+
+    Point ::= SEQUENCE {
+        x     [APPLICATION 1] INTEGER,
+        y     INTEGER (-100..100),
+        label UTF8String OPTIONAL,
+        note  IA5String OPTIONAL
+    }
+
+    END"
+);
+
+struct Ber;
+
+impl Codec for Ber {
+    type Error = asn1rs::prelude::basic::Error;
+
+    fn encode<T: Writable>(value: &T, max_depth: usize) -> Result<Vec<u8>, Self::Error> {
+        let mut writer = BER::writer();
+        writer.set_max_depth(max_depth);
+        writer.write(value)?;
+        Ok(writer.into_bytes_vec())
+    }
+
+    fn decode<T: Readable>(bytes: &[u8], max_depth: usize) -> Result<T, Self::Error> {
+        let mut reader = BER::reader(bytes);
+        reader.set_max_depth(max_depth);
+        let value = reader.read::<T>()?;
+        assert_eq!(0, reader.bytes_remaining());
+        Ok(value)
+    }
+}
+
+/// Writes with the [`DerWriter`], which is read by the [`BerReader`] as well
+struct Der;
+
+impl Codec for Der {
+    type Error = asn1rs::prelude::basic::Error;
+
+    fn encode<T: Writable>(value: &T, max_depth: usize) -> Result<Vec<u8>, Self::Error> {
+        let mut writer = DerWriter::default();
+        writer.set_max_depth(max_depth);
+        writer.write(value)?;
+        Ok(writer.into_bytes_vec())
+    }
+
+    fn decode<T: Readable>(bytes: &[u8], max_depth: usize) -> Result<T, Self::Error> {
+        Ber::decode(bytes, max_depth)
+    }
+}
+
+#[test]
+fn test_context_specific_tags() {
+    assert_eq!(
+        &[
+            0x30, 0x28, // SEQUENCE
+            0x80, 0x01, 0x2A, // [0] 42
+            0x81, 0x01, 0xF9, // [1] -7
+            0x82, 0x07, b'g', b'a', b't', b'e', b'w', b'a', b'y', //
+            0x83, 0x02, b'D', b'E', //
+            // absent payload and priority that equals its DEFAULT value
+            0x86, 0x01, 0x01, // [6] response
+            0x87, 0x02, 0x04, 0xA0, // [7] 4 bits
+            0xA8, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x01, 0x2C, // [8] {1, 300}
+            0xA9, 0x03, 0x81, 0x01, 0xFF, // [9] explicitly tagged CHOICE of [1] BOOLEAN
+        ][..],
+        &serialize::<Ber>(&message())[..]
+    );
+}
+
+#[test]
+fn test_assigned_and_universal_tags() {
+    let point = Point {
+        x: 1,
+        y: -1,
+        label: None,
+        note: Some("n".to_string()),
+    };
+    let bytes = serialize::<Ber>(&point);
+    assert_eq!(
+        &[0x30, 0x09, 0x41, 0x01, 0x01, 0x02, 0x01, 0xFF, 0x16, 0x01, b'n'][..],
+        &bytes[..]
+    );
+    assert_eq!(point, deserialize::<Ber, Point>(&bytes).unwrap());
+}
+
+#[test]
+fn test_values_out_of_range_are_rejected() {
+    // id of 300 instead of 42
+    let mut bytes = serialize::<Ber>(&message());
+    bytes.splice(3..5, [0x02, 0x01, 0x2C]);
+    bytes[1] += 1;
+    assert!(matches!(
+        deserialize::<Ber, Message>(&bytes).unwrap_err().kind(),
+        ErrorKind::ValueNotInRange { value: 300, .. }
+    ));
+}
+
+#[test]
+fn test_extensible_sequence_skips_unknown_components() {
+    let mut bytes = serialize::<Ber>(&message());
+    bytes.extend_from_slice(&[0x8B, 0x01, b'x']);
+    bytes[1] += 3;
+    assert_eq!(message(), deserialize::<Ber, Message>(&bytes).unwrap());
+}
+
+#[test]
+fn test_not_extensible_sequence_rejects_trailing_content() {
+    let bytes = [
+        0x30, 0x0B, 0x41, 0x01, 0x01, 0x02, 0x01, 0xFF, 0x16, 0x01, b'n', 0x05, 0x00,
+    ];
+    assert!(matches!(
+        deserialize::<Ber, Point>(&bytes).unwrap_err().kind(),
+        ErrorKind::TrailingContent(2)
+    ));
+}
+
+#[test]
+fn test_constructed_octet_string() {
+    let expected = Message {
+        payload: Some(vec![1, 2, 3]),
+        ..message()
+    };
+    let mut bytes = serialize::<Ber>(&message());
+    // [4] as two primitive segments, after the code
+    bytes.splice(
+        21..21,
+        [0xA4, 0x07, 0x04, 0x02, 0x01, 0x02, 0x04, 0x01, 0x03],
+    );
+    bytes[1] += 9;
+    assert_eq!(expected, deserialize::<Ber, Message>(&bytes).unwrap());
+}
+
+#[test]
+fn test_indefinite_length_is_not_supported() {
+    let bytes = [0x30, 0x80, 0x41, 0x01, 0x01, 0x02, 0x01, 0xFF, 0x00, 0x00];
+    assert!(matches!(
+        deserialize::<Ber, Point>(&bytes).unwrap_err().kind(),
+        ErrorKind::UnsupportedOperation("indefinite length")
+    ));
+}
+
+fn deserialize_ber_indefinite<T: Readable>(
+    bytes: &[u8],
+) -> Result<T, asn1rs::prelude::basic::Error> {
+    let mut reader = BER::reader(bytes);
+    reader.set_accept_indefinite_length(true);
+    let value = reader.read::<T>()?;
+    assert_eq!(0, reader.bytes_remaining());
+    Ok(value)
+}
+
+#[test]
+fn test_indefinite_length_if_accepted() {
+    let bytes = [0x30, 0x80, 0x41, 0x01, 0x01, 0x02, 0x01, 0xFF, 0x00, 0x00];
+    assert_eq!(
+        Point {
+            x: 1,
+            y: -1,
+            label: None,
+            note: None,
+        },
+        deserialize_ber_indefinite::<Point>(&bytes).unwrap()
+    );
+}
+
+#[test]
+fn test_nested_indefinite_length_if_accepted() {
+    let definite = serialize::<Ber>(&message());
+    assert!(definite[1] < 0x80);
+
+    // the outer SEQUENCE and the [4] payload as constructed string of indefinite length
+    let mut bytes = vec![0x30, 0x80];
+    bytes.extend_from_slice(&definite[2..21]);
+    bytes.extend_from_slice(&[0xA4, 0x80, 0x04, 0x02, 0x01, 0x02, 0x00, 0x00]);
+    bytes.extend_from_slice(&definite[21..]);
+    bytes.extend_from_slice(&[0x00, 0x00]);
+
+    let expected = Message {
+        payload: Some(vec![1, 2]),
+        ..message()
+    };
+    assert_eq!(
+        expected,
+        deserialize_ber_indefinite::<Message>(&bytes).unwrap()
+    );
+    // always written with definite lengths
+    assert_eq!(serialize::<Ber>(&expected)[1], definite[1] + 4);
+}
+
+#[test]
+fn test_indefinite_length_of_primitive_is_rejected() {
+    let bytes = [0x30, 0x80, 0x41, 0x80, 0x01, 0x00, 0x00, 0x00, 0x00];
+    assert!(matches!(
+        deserialize_ber_indefinite::<Point>(&bytes)
+            .unwrap_err()
+            .kind(),
+        ErrorKind::InvalidEncoding(_)
+    ));
+}
+
+/// The encoding of a [`Tree`] that is `levels` deep, crafted without the writer
+fn crafted_deep(levels: usize) -> Vec<u8> {
+    // built back to front, so that each length is known when its header is written
+    fn header(reversed: &mut Vec<u8>, tag: u8) {
+        let len = reversed.len();
+        if len < 0x80 {
+            reversed.push(len as u8);
+        } else {
+            let octets = len.to_be_bytes();
+            let skip = octets.iter().take_while(|octet| **octet == 0).count();
+            reversed.extend(octets[skip..].iter().rev());
+            reversed.push(0x80 | (octets.len() - skip) as u8);
+        }
+        reversed.push(tag);
+    }
+    let mut reversed = Vec::new();
+    for _ in 0..levels {
+        header(&mut reversed, 0xA1);
+        reversed.extend([0x00, 0x01, 0x80]);
+        header(&mut reversed, 0x30);
+    }
+    reversed.reverse();
+    reversed
+}
+
+#[test]
+fn test_crafted_deep_tree_matches_the_writer() {
+    let tree = deep(3);
+    assert_eq!(serialize::<Ber>(&tree), crafted_deep(3));
+    assert_eq!(tree, deserialize::<Ber, Tree>(&crafted_deep(3)).unwrap());
+}
+
+#[test]
+fn test_reading_crafted_deep_input_fails_without_overflowing_the_stack() {
+    let bytes = crafted_deep(100_000);
+    assert!(matches!(
+        deserialize::<Ber, Tree>(&bytes).unwrap_err().kind(),
+        ErrorKind::NestingDepthExceedsLimit(DEFAULT_MAX_DEPTH)
+    ));
+}
+
+#[test]
+fn test_round_trip() {
+    assert_round_trip::<Ber>();
+    assert_round_trip::<Der>();
+}
+
+#[test]
+fn test_constraints_are_checked() {
+    assert_constraints_are_checked::<Ber>();
+    assert_constraints_are_checked::<Der>();
+}
+
+#[test]
+fn test_truncated_input_is_rejected() {
+    assert_truncated_input_is_rejected::<Ber>();
+    assert_truncated_input_is_rejected::<Der>();
+}
+
+#[test]
+fn test_nesting_depth_is_limited() {
+    assert_nesting_depth_is_limited::<Ber>();
+    assert_nesting_depth_is_limited::<Der>();
+}
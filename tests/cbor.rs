@@ -0,0 +1,175 @@
+#![cfg(feature = "cbor")]
+
+mod test_utils;
+
+use asn1rs::protocol::cbor::ErrorKind;
+use asn1rs::rw::DEFAULT_MAX_DEPTH;
+use test_utils::codec::*;
+use test_utils::*;
+
+asn_to_rust!(
+    r"CborMapping DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Header ::= SEQUENCE {
+        id INTEGER (0..255)
+    }
+
+    END"
+);
+
+struct Cbor;
+
+impl Codec for Cbor {
+    type Error = asn1rs::protocol::cbor::Error;
+
+    fn encode<T: Writable>(value: &T, max_depth: usize) -> Result<Vec<u8>, Self::Error> {
+        let mut writer = CborWriter::default();
+        writer.set_max_depth(max_depth);
+        writer.write(value)?;
+        Ok(writer.into_bytes_vec())
+    }
+
+    fn decode<T: Readable>(bytes: &[u8], max_depth: usize) -> Result<T, Self::Error> {
+        let mut reader = CborReader::from(bytes);
+        reader.set_max_depth(max_depth);
+        let value = reader.read::<T>()?;
+        assert_eq!(0, reader.bytes_remaining());
+        Ok(value)
+    }
+}
+
+#[test]
+fn test_deterministic_bytes() {
+    assert_eq!(
+        &[
+            0x8B, // array of 11 fields
+            0x18, 0x2A, // 42
+            0x26, // -7
+            0x67, b'g', b'a', b't', b'e', b'w', b'a', b'y', //
+            0x62, b'D', b'E', //
+            0xF7, // absent payload
+            0xF7, // priority equals its DEFAULT value
+            0x01, // response
+            0x82, 0x41, 0xA0, 0x04, // bit string
+            0x82, 0x01, 0x19, 0x01, 0x2C, // [1, 300]
+            0xA1, 0x01, 0xF5, // {1: true}
+            0xF7, // absent trailer
+        ][..],
+        &serialize::<Cbor>(&message())[..]
+    );
+}
+
+#[test]
+fn test_values_out_of_range_are_rejected() {
+    // id of 300 instead of 42
+    let mut bytes = serialize::<Cbor>(&message());
+    bytes.splice(1..3, [0x19, 0x01, 0x2C]);
+    assert_eq!(
+        &ErrorKind::ValueNotInRange {
+            value: 300,
+            min: 0,
+            max: 255,
+            definition: Some("Message"),
+            field: Some("id"),
+        },
+        deserialize::<Cbor, Message>(&bytes).unwrap_err().kind()
+    );
+}
+
+#[test]
+fn test_non_deterministic_input_is_rejected() {
+    let mut bytes = serialize::<Cbor>(&message());
+    // 42 in a two byte argument instead of a one byte argument
+    bytes.splice(1..3, [0x19, 0x00, 0x2A]);
+    assert_eq!(
+        &ErrorKind::NotDeterministic(0x19),
+        deserialize::<Cbor, Message>(&bytes).unwrap_err().kind()
+    );
+}
+
+#[test]
+fn test_extensible_sequence_with_fewer_or_more_items() {
+    let bytes = serialize::<Cbor>(&message());
+
+    // written by a peer that does not know the trailer
+    let mut fewer = bytes[..bytes.len() - 1].to_vec();
+    fewer[0] = 0x8A;
+    assert_eq!(message(), deserialize::<Cbor, Message>(&fewer).unwrap());
+
+    // written by a peer that knows an additional extension field
+    let mut more = bytes.clone();
+    more[0] = 0x8C;
+    more.extend_from_slice(&[0x82, 0x61, b'x', 0x80]);
+    assert_eq!(message(), deserialize::<Cbor, Message>(&more).unwrap());
+
+    // a missing root field is not accepted
+    let mut missing = bytes[..1].to_vec();
+    missing[0] = 0x80;
+    assert_eq!(
+        &ErrorKind::ItemsExhausted,
+        deserialize::<Cbor, Message>(&missing).unwrap_err().kind()
+    );
+}
+
+#[test]
+fn test_not_extensible_sequence_with_more_items() {
+    assert_eq!(
+        Header { id: 7 },
+        deserialize::<Cbor, _>(&[0x81, 0x07]).unwrap()
+    );
+    assert_eq!(
+        &ErrorKind::UnexpectedItemCount {
+            expected: 1,
+            got: 2
+        },
+        deserialize::<Cbor, Header>(&[0x82, 0x07, 0x07])
+            .unwrap_err()
+            .kind()
+    );
+}
+
+/// The encoding of a [`Tree`] that is `levels` deep: an array of the value and an array of one
+/// element on each level, but the last
+fn crafted_deep(levels: usize) -> Vec<u8> {
+    let mut bytes = [0x82, 0x00, 0x81].repeat(levels - 1);
+    bytes.extend_from_slice(&[0x82, 0x00, 0x80]);
+    bytes
+}
+
+#[test]
+fn test_crafted_deep_tree_matches_the_writer() {
+    let tree = deep(3);
+    assert_eq!(serialize::<Cbor>(&tree), crafted_deep(3));
+    assert_eq!(tree, deserialize::<Cbor, Tree>(&crafted_deep(3)).unwrap());
+}
+
+#[test]
+fn test_reading_crafted_deep_input_fails_without_overflowing_the_stack() {
+    assert_eq!(
+        &ErrorKind::NestingDepthExceedsLimit(DEFAULT_MAX_DEPTH),
+        deserialize::<Cbor, Tree>(&crafted_deep(100_000))
+            .unwrap_err()
+            .kind()
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    assert_round_trip::<Cbor>();
+}
+
+#[test]
+fn test_constraints_are_checked() {
+    assert_constraints_are_checked::<Cbor>();
+}
+
+#[test]
+fn test_truncated_input_is_rejected() {
+    assert_truncated_input_is_rejected::<Cbor>();
+}
+
+#[test]
+fn test_nesting_depth_is_limited() {
+    assert_nesting_depth_is_limited::<Cbor>();
+}
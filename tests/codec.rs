@@ -0,0 +1,94 @@
+#![cfg(feature = "async")]
+
+mod test_utils;
+
+use asn1rs::codec::UperCodec;
+use asn1rs::error::Kind;
+use bytes::BytesMut;
+use test_utils::*;
+use tokio_util::codec::{Decoder, Encoder, LengthDelimitedCodec};
+
+asn_to_rust!(
+    r"Codec DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Message ::= SEQUENCE {
+        id      INTEGER (0..255),
+        payload OCTET STRING
+    }
+
+    END"
+);
+
+fn message(id: u8, len: usize) -> Message {
+    Message {
+        id,
+        payload: vec![id; len],
+    }
+}
+
+#[test]
+fn test_frames_are_decoded_once_complete() {
+    let mut codec = UperCodec::<Message>::new();
+    let mut encoded = BytesMut::new();
+    codec.encode(message(1, 3), &mut encoded).unwrap();
+    codec.encode(&message(2, 20_000), &mut encoded).unwrap();
+
+    let mut received = BytesMut::new();
+    let mut decoded = Vec::new();
+    for chunk in encoded.chunks(1000) {
+        received.extend_from_slice(chunk);
+        while let Some(message) = codec.decode(&mut received).unwrap() {
+            decoded.push(message);
+        }
+    }
+    assert_eq!(vec![message(1, 3), message(2, 20_000)], decoded);
+    assert!(received.is_empty());
+}
+
+#[test]
+fn test_frame_is_the_uper_encoding() {
+    let mut codec = UperCodec::<Message>::new();
+    let mut encoded = BytesMut::new();
+    codec.encode(message(7, 2), &mut encoded).unwrap();
+    let (_bits, bytes) = serialize_uper(&message(7, 2));
+    assert_eq!(&(bytes.len() as u32).to_be_bytes()[..], &encoded[..4]);
+    assert_eq!(&bytes[..], &encoded[4..]);
+}
+
+#[test]
+fn test_custom_framing() {
+    let mut codec = UperCodec::<Message>::from(
+        LengthDelimitedCodec::builder()
+            .length_field_type::<u16>()
+            .little_endian()
+            .new_codec(),
+    );
+    let mut encoded = BytesMut::new();
+    codec.encode(message(7, 2), &mut encoded).unwrap();
+    assert_eq!(&[4, 0, 7, 2, 7, 7][..], &encoded[..]);
+    assert_eq!(Some(message(7, 2)), codec.decode(&mut encoded).unwrap());
+}
+
+#[test]
+fn test_frame_length_is_limited() {
+    let mut codec = UperCodec::<Message>::new();
+    codec.set_max_frame_length(100);
+    assert_eq!(100, codec.max_frame_length());
+
+    let mut encoded = BytesMut::new();
+    let error = codec.encode(message(1, 200), &mut encoded).unwrap_err();
+    assert_eq!(Kind::Io, error.kind());
+
+    let mut encoded = BytesMut::from(&[0, 0, 1, 0][..]);
+    let error = codec.decode(&mut encoded).unwrap_err();
+    assert_eq!(Kind::Io, error.kind());
+}
+
+#[test]
+fn test_truncated_encoding_in_frame() {
+    let mut codec = UperCodec::<Message>::new();
+    let mut encoded = BytesMut::from(&[0, 0, 0, 2, 1, 5][..]);
+    let error = codec.decode(&mut encoded).unwrap_err();
+    assert_eq!(Kind::EndOfData, error.kind());
+}
@@ -0,0 +1,115 @@
+mod test_utils;
+use test_utils::*;
+
+asn_to_rust!(
+    r"DecodePolicy DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Reading ::= SEQUENCE {
+        level INTEGER (0..5),
+        count INTEGER (0..200)
+    }
+
+    END"
+);
+
+/// level = 7, count = 3
+const UPER: &[u8] = &[0b1110_0000, 0b0110_0000];
+
+fn recovered(raw: i64, min: i64, max: i64, value: i64) -> RecoveredValue {
+    RecoveredValue {
+        definition: Some("Reading"),
+        field: Some("level"),
+        raw,
+        min,
+        max,
+        value,
+    }
+}
+
+#[test]
+fn test_uper_strict() {
+    let mut reader = UperReader::from((UPER, 11));
+    assert_eq!(DecodePolicy::Strict, reader.decode_policy());
+    assert_eq!(
+        &per::ErrorKind::ValueNotInRange {
+            value: 7,
+            min: 0,
+            max: 5,
+            definition: Some("Reading"),
+            field: Some("level"),
+        },
+        reader.read::<Reading>().unwrap_err().kind()
+    );
+}
+
+#[test]
+fn test_uper_clamp() {
+    let mut reader = UperReader::from((UPER, 11));
+    reader.set_decode_policy(DecodePolicy::Clamp);
+    assert_eq!(
+        Reading { level: 5, count: 3 },
+        reader.read::<Reading>().unwrap()
+    );
+    assert_eq!(&[recovered(7, 0, 5, 5)], reader.recovered_values());
+}
+
+#[test]
+fn test_uper_lenient() {
+    let mut reader = UperReader::from((UPER, 11));
+    reader.set_decode_policy(DecodePolicy::Lenient);
+    assert_eq!(
+        Reading { level: 7, count: 3 },
+        reader.read::<Reading>().unwrap()
+    );
+    assert_eq!(&[recovered(7, 0, 5, 7)], reader.recovered_values());
+}
+
+#[test]
+fn test_ber_lenient_clamps_what_the_type_cannot_represent() {
+    use asn1rs::prelude::basic::BER;
+
+    // level = 300, count = 3
+    let bytes = [0x30, 0x07, 0x80, 0x02, 0x01, 0x2C, 0x81, 0x01, 0x03];
+    assert!(BER::reader(&bytes).read::<Reading>().is_err());
+
+    let mut reader = BER::reader(&bytes);
+    reader.set_decode_policy(DecodePolicy::Lenient);
+    assert_eq!(
+        Reading { level: 5, count: 3 },
+        reader.read::<Reading>().unwrap()
+    );
+    assert_eq!(&[recovered(300, 0, 5, 5)], reader.recovered_values());
+}
+
+#[cfg(feature = "oer")]
+#[test]
+fn test_oer_clamp() {
+    // level = 7, count = 3
+    let bytes = [0x07, 0x03];
+    assert!(CoerReader::from(&bytes[..]).read::<Reading>().is_err());
+
+    let mut reader = CoerReader::from(&bytes[..]);
+    reader.set_decode_policy(DecodePolicy::Clamp);
+    assert_eq!(
+        Reading { level: 5, count: 3 },
+        reader.read::<Reading>().unwrap()
+    );
+    assert_eq!(&[recovered(7, 0, 5, 5)], reader.recovered_values());
+}
+
+#[cfg(feature = "cbor")]
+#[test]
+fn test_cbor_lenient() {
+    // [7, 3]
+    let bytes = [0x82, 0x07, 0x03];
+    assert!(CborReader::from(&bytes[..]).read::<Reading>().is_err());
+
+    let mut reader = CborReader::from(&bytes[..]);
+    reader.set_decode_policy(DecodePolicy::Lenient);
+    assert_eq!(
+        Reading { level: 7, count: 3 },
+        reader.read::<Reading>().unwrap()
+    );
+    assert_eq!(&[recovered(7, 0, 5, 7)], reader.recovered_values());
+}
@@ -0,0 +1,53 @@
+mod test_utils;
+
+use test_utils::*;
+
+asn_to_rust!(
+    r"DedupInline DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Cell ::= SEQUENCE {
+        interval CHOICE {
+            fdd SEQUENCE { period ENUMERATED { ms10, ms20 }, offset INTEGER (0..7) },
+            tdd NULL
+        }
+    }
+
+    Neighbour ::= SEQUENCE {
+        id       INTEGER (0..255),
+        interval CHOICE {
+            fdd SEQUENCE { period ENUMERATED { ms10, ms20 }, offset INTEGER (0..7) },
+            tdd NULL
+        }
+    }
+
+    END"
+);
+
+#[test]
+fn test_identical_inline_types_are_shared() {
+    let interval = CellInterval::Fdd(CellIntervalFdd {
+        period: CellIntervalFddPeriod::Ms20,
+        offset: 5,
+    });
+    let cell = Cell {
+        interval: interval.clone(),
+    };
+    let neighbour = Neighbour { id: 7, interval };
+    assert_eq!(cell.interval, neighbour.interval);
+}
+
+#[test]
+fn test_shared_inline_types_encode_as_before() {
+    // choice index, period, offset
+    serialize_and_deserialize_uper(
+        1 + 1 + 3,
+        &[0x68],
+        &Cell {
+            interval: CellInterval::Fdd(CellIntervalFdd {
+                period: CellIntervalFddPeriod::Ms20,
+                offset: 5,
+            }),
+        },
+    );
+}
@@ -80,3 +80,14 @@ pub fn test_seq_with_non_default_value_11() {
         },
     );
 }
+
+#[test]
+pub fn test_default_uses_asn_default_values() {
+    assert_eq!(
+        MyCleverSeq {
+            secret_flag: true,
+            flag_secret: false,
+        },
+        MyCleverSeq::default()
+    );
+}
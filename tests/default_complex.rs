@@ -0,0 +1,105 @@
+mod test_utils;
+
+use test_utils::*;
+
+asn_to_rust!(
+    r#"DefaultComplex DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Mode ::= ENUMERATED { passive, active }
+
+    defaultMode Mode ::= active
+
+    Limits ::= SEQUENCE {
+        min   INTEGER (0..255),
+        max   INTEGER (0..255),
+        label UTF8String OPTIONAL
+    }
+
+    Target ::= CHOICE {
+        host UTF8String,
+        port INTEGER (0..65535)
+    }
+
+    Settings ::= SEQUENCE {
+        mode    Mode DEFAULT defaultMode,
+        limits  Limits DEFAULT { min 1, max 10 },
+        named   Limits DEFAULT { min 0, max 255, label "all" },
+        target  Target DEFAULT port : 80,
+        retries SEQUENCE OF INTEGER (0..7) DEFAULT { 1, 2, 4 },
+        tags    SEQUENCE OF UTF8String DEFAULT { }
+    }
+
+    END"#
+);
+
+fn settings() -> Settings {
+    Settings {
+        mode: Mode::Active,
+        limits: Limits {
+            min: 1,
+            max: 10,
+            label: None,
+        },
+        named: Limits {
+            min: 0,
+            max: 255,
+            label: Some("all".to_string()),
+        },
+        target: Target::Port(80),
+        retries: vec![1, 2, 4],
+        tags: Vec::new(),
+    }
+}
+
+#[test]
+fn test_default_values() {
+    assert_eq!(settings(), Settings::default());
+    assert_eq!(
+        Limits {
+            min: 1,
+            max: 10,
+            label: None
+        },
+        SETTINGS_LIMITS_DEFAULT
+    );
+    assert_eq!(vec![1, 2, 4], *SETTINGS_RETRIES_DEFAULT);
+}
+
+#[test]
+fn test_default_values_are_absent() {
+    // only the presence bits of the six fields
+    serialize_and_deserialize_uper(6, &[0x00], &settings());
+}
+
+#[test]
+fn test_non_default_values_are_present() {
+    let mut value = settings();
+    value.mode = Mode::Passive;
+    value.limits.max = 11;
+    value.target = Target::Host(String::new());
+    // presence bits, mode, limits (optional bit, min, max), target (choice index, length)
+    serialize_and_deserialize_uper(
+        6 + 1 + 1 + 8 + 8 + 1 + 8,
+        &[0xD0, 0x01, 0x0B, 0x00, 0x00],
+        &value,
+    );
+
+    let mut value = settings();
+    value.named.label = None;
+    value.retries = vec![1, 2];
+    value.tags = vec!["a".to_string()];
+    // presence bits, named (optional bit, min, max), retries (length, 2 elements), tags
+    // (length, length of the element, element)
+    serialize_and_deserialize_uper(
+        6 + 1 + 8 + 8 + 8 + 3 + 3 + 8 + 8 + 8,
+        &[0x2C, 0x01, 0xFE, 0x04, 0x50, 0x08, 0x0B, 0x08],
+        &value,
+    );
+}
+
+#[test]
+fn test_absent_values_are_decoded_as_default() {
+    let mut reader = UperReader::from((&[0x00][..], 6));
+    assert_eq!(settings(), reader.read::<Settings>().unwrap());
+}
@@ -85,3 +85,18 @@ pub fn test_ref_with_non_default_value_1500() {
 pub fn test_ref_with_default_value() {
     serialize_and_deserialize_uper(8 * 0 + 1, &[0x00], &MyCleverSeqRef { secret_code: -1337 });
 }
+
+#[test]
+pub fn test_default_uses_asn_default_values() {
+    assert_eq!(MyCleverSeq { secret_code: 1337 }, MyCleverSeq::default());
+    assert_eq!(
+        MyCleverSeqRef { secret_code: -1337 },
+        MyCleverSeqRef::default()
+    );
+    assert_eq!(
+        MyCleverSeqWrapped {
+            secret_code: MyWrappedInteger(1337)
+        },
+        MyCleverSeqWrapped::default()
+    );
+}
@@ -11,6 +11,9 @@ asn_to_rust!(
     }
     
     defaultMessage UTF8String ::= "hey hee ha"
+
+    quotedMessage UTF8String ::= "say ""hi"" \o/
+        twice"
     
     MyCleverSeqRef ::= SEQUENCE {
         secret-message UTF8String DEFAULT defaultMessage
@@ -81,3 +84,14 @@ pub fn test_ref_with_default_value() {
         },
     );
 }
+
+#[test]
+pub fn test_default_uses_asn_default_values() {
+    assert_eq!("hey hee ha", MyCleverSeq::default().secret_message);
+    assert_eq!("hey hee ha", MyCleverSeqRef::default().secret_message);
+}
+
+#[test]
+pub fn test_escaped_value_reference() {
+    assert_eq!(r#"say "hi" \o/twice"#, QUOTED_MESSAGE);
+}
@@ -0,0 +1,76 @@
+mod test_utils;
+
+use asn1rs::prelude::basic::BER;
+use test_utils::*;
+
+asn_to_rust!(
+    r"DerCanonical DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Canonical ::= SEQUENCE {
+        values SET OF INTEGER (0..1000),
+        flags  BIT STRING,
+        set    Unordered
+    }
+
+    Unordered ::= SET {
+        b [2] BOOLEAN,
+        a [1] INTEGER (0..255)
+    }
+
+    END"
+);
+
+fn canonical() -> Canonical {
+    Canonical {
+        values: vec![300, 1, 5],
+        flags: BitVec::from_bytes(vec![0b1011_1111], 3),
+        set: Unordered { b: true, a: 7 },
+    }
+}
+
+#[test]
+fn test_der_encoding() {
+    assert_eq!(
+        &[
+            0x30, 0x18, // SEQUENCE
+            0xA0, 0x0A, // [0] SET OF, sorted by the encoding of the elements
+            0x02, 0x01, 0x01, // 1
+            0x02, 0x01, 0x05, // 5
+            0x02, 0x02, 0x01, 0x2C, // 300
+            0x81, 0x02, 0x05, 0xA0, // [1] 3 bits with zeroed unused bits
+            0xA2, 0x06, // [2] SET, components in the order of their tags
+            0x81, 0x01, 0x07, // [1] 7
+            0x82, 0x01, 0xFF, // [2] TRUE
+        ][..],
+        &canonical().write_der().unwrap()[..]
+    );
+}
+
+#[test]
+fn test_der_writer_is_equal_to_write_der() {
+    let mut writer = DerWriter::default();
+    writer.write(&canonical()).unwrap();
+    assert_eq!(canonical().write_der().unwrap(), writer.into_bytes_vec());
+}
+
+#[test]
+fn test_ber_keeps_the_order_of_set_of() {
+    let mut writer = BER::writer();
+    writer.write(&canonical()).unwrap();
+    let bytes = writer.into_bytes_vec();
+    assert_eq!(
+        &[0x02, 0x02, 0x01, 0x2C, 0x02, 0x01, 0x01, 0x02, 0x01, 0x05][..],
+        &bytes[4..14]
+    );
+    assert_ne!(canonical().write_der().unwrap(), bytes);
+}
+
+#[test]
+fn test_der_encoding_is_readable_as_ber() {
+    let bytes = canonical().write_der().unwrap();
+    let value = BER::reader(&bytes).read::<Canonical>().unwrap();
+    assert_eq!(vec![1, 5, 300], value.values);
+    assert_eq!(canonical().set, value.set);
+    assert_eq!(&[0b1010_0000][..], value.flags.as_byte_slice());
+}
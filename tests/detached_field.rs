@@ -0,0 +1,100 @@
+mod test_utils;
+
+use test_utils::*;
+
+asn_to_rust!(
+    r"Detached DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Signed ::= SEQUENCE {
+        tbs-data  ToBeSigned,
+        signature OCTET STRING
+    }
+
+    ToBeSigned ::= SEQUENCE {
+        id      INTEGER (0..255),
+        note    UTF8String OPTIONAL,
+        payload Payload
+    }
+
+    Payload ::= CHOICE {
+        raw    OCTET STRING,
+        number INTEGER (0..65535)
+    }
+
+    END"
+);
+
+fn signed() -> Signed {
+    Signed {
+        tbs_data: ToBeSigned {
+            id: 42,
+            note: Some("hi".to_string()),
+            payload: Payload::Number(1337),
+        },
+        signature: vec![0xCA, 0xFE],
+    }
+}
+
+#[test]
+fn test_der_field_is_equal_to_the_embedded_encoding() {
+    let signed = signed();
+    let mut writer = DerWriter::default();
+    signed
+        .write_field("tbs_data", &mut writer)
+        .unwrap()
+        .unwrap();
+    let tbs_data = writer.into_bytes_vec();
+
+    assert_eq!(
+        &[
+            0xA0, 0x0D, // [0] ToBeSigned
+            0x80, 0x01, 0x2A, // [0] 42
+            0x81, 0x02, b'h', b'i', // [1] "hi"
+            0xA2, 0x04, // [2] Payload
+            0x81, 0x02, 0x05, 0x39, // [1] 1337
+        ][..],
+        &tbs_data[..]
+    );
+
+    let encoded = signed.write_der().unwrap();
+    assert_eq!(&tbs_data[..], &encoded[2..2 + tbs_data.len()]);
+}
+
+#[test]
+fn test_uper_field_is_equal_to_the_embedded_encoding() {
+    let signed = signed();
+    let mut writer = UperWriter::default();
+    signed
+        .write_field("tbs_data", &mut writer)
+        .unwrap()
+        .unwrap();
+    let bits = writer.bit_len();
+    let tbs_data = writer.into_bytes_vec();
+
+    let mut writer = UperWriter::default();
+    writer.write(&signed).unwrap();
+    let encoded = writer.into_bytes_vec();
+
+    // the first field of a SEQUENCE without OPTIONAL fields starts at the first bit
+    assert_eq!(&tbs_data[..bits / 8], &encoded[..bits / 8]);
+    let mask = !(0xFF_u8 >> (bits % 8));
+    assert_eq!(tbs_data[bits / 8] & mask, encoded[bits / 8] & mask);
+}
+
+#[test]
+fn test_alternative_is_written_only_if_set() {
+    let payload = Payload::Number(1337);
+    let mut writer = DerWriter::default();
+    assert!(payload.write_field("Raw", &mut writer).is_none());
+    payload.write_field("Number", &mut writer).unwrap().unwrap();
+    assert_eq!(&[0x81, 0x02, 0x05, 0x39], &writer.into_bytes_vec()[..]);
+}
+
+#[test]
+fn test_unknown_field() {
+    let mut writer = DerWriter::default();
+    assert!(signed().write_field("tbs-data", &mut writer).is_none());
+    assert!(signed().write_field("missing", &mut writer).is_none());
+    assert!(writer.into_bytes_vec().is_empty());
+}
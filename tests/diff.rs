@@ -0,0 +1,101 @@
+use asn1rs::prelude::*;
+
+asn_to_rust!(
+    r"Diff DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Vehicle ::= SEQUENCE {
+        speed INTEGER (0..255),
+        vin IA5String,
+        passengers SEQUENCE OF Person,
+        occupant Occupant
+    }
+
+    Person ::= SEQUENCE {
+        name UTF8String,
+        role Role,
+        seat INTEGER (1..9) OPTIONAL
+    }
+
+    Role ::= ENUMERATED {
+        driver,
+        passenger
+    }
+
+    Occupant ::= CHOICE {
+        person Person,
+        unknown NULL
+    }
+
+    END"
+);
+
+fn vehicle() -> Vehicle {
+    Vehicle {
+        speed: 42,
+        vin: "WVWZZZ1JZXW000001".to_string(),
+        passengers: vec![Person {
+            name: "Jane".to_string(),
+            role: Role::Driver,
+            seat: Some(1),
+        }],
+        occupant: Occupant::Unknown(Null),
+    }
+}
+
+#[test]
+fn test_equal_values_have_no_changes() {
+    assert!(vehicle().diff(&vehicle()).is_empty());
+}
+
+#[test]
+fn test_changed_fields() {
+    let mut other = vehicle();
+    other.speed = 50;
+    other.passengers[0].role = Role::Passenger;
+    other.passengers[0].seat = None;
+
+    let changes = vehicle().diff(&other);
+    assert_eq!(
+        vec![
+            "speed: 42 -> 50",
+            "passengers[0].role: Driver -> Passenger",
+            "passengers[0].seat: 1 -> absent",
+        ],
+        changes.iter().map(ToString::to_string).collect::<Vec<_>>()
+    );
+    assert_eq!(Some(FieldValue::Integer(42)), changes[0].old);
+    assert_eq!(Some(FieldValue::Integer(50)), changes[0].new);
+}
+
+#[test]
+fn test_paths_of_only_one_value() {
+    let mut other = vehicle();
+    other.passengers.push(Person {
+        name: "John".to_string(),
+        role: Role::Passenger,
+        seat: None,
+    });
+    other.occupant = Occupant::Person(Person {
+        name: "Jim".to_string(),
+        role: Role::Driver,
+        seat: Some(2),
+    });
+
+    assert_eq!(
+        vec![
+            "occupant.Unknown: NULL -> -",
+            "passengers[1].name: - -> \"John\"",
+            "passengers[1].role: - -> Passenger",
+            "passengers[1].seat: - -> absent",
+            "occupant.Person.name: - -> \"Jim\"",
+            "occupant.Person.role: - -> Driver",
+            "occupant.Person.seat: - -> 2",
+        ],
+        vehicle()
+            .diff(&other)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    );
+}
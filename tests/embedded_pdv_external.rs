@@ -0,0 +1,72 @@
+mod test_utils;
+use test_utils::*;
+
+asn_to_rust!(
+    r"EmbeddedPdvExternal DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Envelope ::= SEQUENCE {
+        content EMBEDDED PDV,
+        legacy EXTERNAL OPTIONAL
+    }
+
+    END"
+);
+
+fn envelope() -> Envelope {
+    Envelope {
+        content: EmbeddedPdv {
+            identification: EmbeddedPdvIdentification::Syntax(Oid::new([1, 2, 3])),
+            data_value: vec![0xC0, 0xFF, 0xEE],
+        },
+        legacy: Some(External {
+            identification: ExternalIdentification::PresentationContextId(5),
+            data_value_descriptor: Some("legacy".to_string()),
+            data_value: vec![0x01],
+        }),
+    }
+}
+
+#[test]
+fn test_associated_types() {
+    let _: Oid = EmbeddedPdvIdentificationSyntaxes::default().abstract_;
+    let _: u64 = EmbeddedPdvIdentificationContextNegotiation::default().presentation_context_id;
+    let _: EmbeddedPdvIdentification = EmbeddedPdvIdentification::Fixed(Null);
+    let _: Option<String> = External::default().data_value_descriptor;
+    // identical to the inline type of EMBEDDED PDV, which is therefore shared
+    let _: ExternalIdentification =
+        ExternalIdentification::ContextNegotiation(EmbeddedPdvIdentificationContextNegotiation {
+            presentation_context_id: 1,
+            transfer_syntax: Oid::new([2, 1, 1]),
+        });
+}
+
+#[test]
+fn test_uper() {
+    serialize_and_deserialize_uper(
+        151,
+        &[
+            0x90, 0x22, 0xA0, 0x30, 0x3C, 0x0F, 0xFE, 0xEA, 0x02, 0x0A, 0x0C, 0xD8, 0xCA, 0xCE,
+            0xC2, 0xC6, 0xF2, 0x02, 0x02,
+        ],
+        &envelope(),
+    );
+}
+
+#[test]
+fn test_ber_universal_tags() {
+    use asn1rs::prelude::basic::BER;
+
+    let mut writer = BER::writer();
+    writer.write(&envelope()).unwrap();
+    let bytes = writer.into_bytes_vec();
+    assert_eq!(envelope(), BER::reader(&bytes).read().unwrap());
+
+    // the tags of the fields replace the universal tags, which only show on their own
+    let mut writer = BER::writer();
+    writer.write(&envelope().content).unwrap();
+    assert_eq!(0x2B, writer.into_bytes_vec()[0]);
+    let mut writer = BER::writer();
+    writer.write(&envelope().legacy.unwrap()).unwrap();
+    assert_eq!(0x28, writer.into_bytes_vec()[0]);
+}
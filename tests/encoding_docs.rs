@@ -0,0 +1,59 @@
+mod test_utils;
+
+use asn1rs::model::asn::{Range, Size};
+use asn1rs::model::layout::uper_layout;
+use asn1rs::model::rust::RustType;
+use test_utils::*;
+
+asn_to_rust!(
+    encoding_docs = true,
+    r"EncodingDocs DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Percent ::= INTEGER (0..100)
+
+    Code ::= NumericString (SIZE(4))
+
+    Reading ::= SEQUENCE {
+        percent Percent,
+        code    Code,
+        ...,
+        note    UTF8String OPTIONAL
+    }
+
+    Report ::= CHOICE {
+        reading Reading,
+        alarm   BOOLEAN
+    }
+
+    END"
+);
+
+#[test]
+fn test_layouts_match_the_encodings() {
+    assert_eq!(
+        "7-bit constrained integer, offset 0..=100",
+        uper_layout(&RustType::U8(Range::inclusive(0, 100)))
+    );
+    assert_eq!(7, serialize_uper(&Percent(100)).0);
+
+    assert_eq!(
+        "NumericString of 4-bit characters of fixed size 4, without length determinant",
+        uper_layout(&RustType::String(
+            Size::Fix(4, false),
+            asn1rs::model::asn::Charset::Numeric
+        ))
+    );
+    assert_eq!(4 * 4, serialize_uper(&Code("1234".to_string())).0);
+}
+
+#[test]
+fn test_documented_types_round_trip() {
+    let report = Report::Reading(Reading {
+        percent: Percent(42),
+        code: Code("0815".to_string()),
+        note: Some("full".to_string()),
+    });
+    let (bits, bytes) = serialize_uper(&report);
+    assert_eq!(report, deserialize_uper(&bytes, bits));
+}
@@ -0,0 +1,83 @@
+mod test_utils;
+
+use asn1rs::error::{CodecError, Kind};
+use asn1rs::prelude::basic::BER;
+use std::error::Error as _;
+use test_utils::*;
+
+asn_to_rust!(
+    r"UnifiedErrors DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Reading ::= SEQUENCE {
+        sensor UTF8String,
+        value  INTEGER (0..1000)
+    }
+
+    END"
+);
+
+fn reading() -> Reading {
+    Reading {
+        sensor: "temperature".to_string(),
+        value: 210,
+    }
+}
+
+fn decode_uper(bytes: &[u8]) -> Result<Reading, asn1rs::Error> {
+    Ok(UperReader::from((bytes, bytes.len() * 8)).read::<Reading>()?)
+}
+
+fn decode_ber(bytes: &[u8]) -> Result<Reading, asn1rs::Error> {
+    Ok(BER::reader(bytes).read::<Reading>()?)
+}
+
+fn encode_both(reading: &Reading) -> Result<(Vec<u8>, Vec<u8>), asn1rs::Error> {
+    let mut uper = UperWriter::default();
+    uper.write(reading)?;
+    let mut ber = BER::writer();
+    ber.write(reading)?;
+    Ok((uper.into_bytes_vec(), ber.into_bytes_vec()))
+}
+
+#[test]
+fn test_same_kind_for_different_codecs() {
+    let (uper, ber) = encode_both(&reading()).unwrap();
+    assert_eq!(reading(), decode_uper(&uper).unwrap());
+    assert_eq!(reading(), decode_ber(&ber).unwrap());
+
+    let uper = decode_uper(&uper[..3]).unwrap_err();
+    let ber = decode_ber(&ber[..3]).unwrap_err();
+    assert_eq!(Kind::EndOfData, uper.kind());
+    assert_eq!(Kind::EndOfData, ber.kind());
+    assert_eq!(uper.code(), ber.code());
+}
+
+#[test]
+fn test_codec_details_are_accessible() {
+    let error = encode_both(&Reading {
+        value: 1001,
+        ..reading()
+    })
+    .unwrap_err();
+    assert_eq!(Kind::ConstraintViolation, error.kind());
+    assert_eq!(4, error.code());
+    assert!(error.to_string().starts_with(
+        "[E0004] The value 1001 of Reading.value is not within the inclusive range of 0 and 1000"
+    ));
+
+    match error.codec() {
+        CodecError::Uper(e) => assert_eq!(
+            &asn1rs::prelude::per::ErrorKind::ValueNotInRange {
+                value: 1001,
+                min: 0,
+                max: 1000,
+                definition: Some("Reading"),
+                field: Some("value"),
+            },
+            e.kind()
+        ),
+        e => panic!("Unexpected codec error {e:?}"),
+    }
+    assert!(error.source().is_some());
+}
@@ -0,0 +1,38 @@
+mod test_utils;
+
+use test_utils::*;
+
+asn_to_rust!(
+    r"ExtensibilityImplied DEFINITIONS AUTOMATIC TAGS EXTENSIBILITY IMPLIED ::=
+    BEGIN
+
+    Point ::= SEQUENCE {
+        x INTEGER (0..255)
+    }
+
+    Direction ::= ENUMERATED { north, south }
+
+    Shape ::= CHOICE {
+        point Point,
+        direction Direction
+    }
+
+    END"
+);
+
+#[test]
+fn test_sequence_has_extension_bit() {
+    // extension bit (not set) followed by the root component
+    serialize_and_deserialize_uper(9, &[0x02, 0x80], &Point { x: 5 });
+}
+
+#[test]
+fn test_enumerated_has_extension_bit() {
+    serialize_and_deserialize_uper(2, &[0x40], &Direction::South);
+}
+
+#[test]
+fn test_choice_has_extension_bit() {
+    // choice extension bit, index, enumerated extension bit, enumerated index
+    serialize_and_deserialize_uper(4, &[0x50], &Shape::Direction(Direction::South));
+}
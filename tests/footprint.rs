@@ -0,0 +1,111 @@
+mod test_utils;
+
+use asn1rs::footprint::{table, Footprint};
+use test_utils::*;
+
+mod budget {
+    use asn1rs::prelude::*;
+
+    asn_to_rust!(
+        footprint = true,
+        r#"Budget DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+
+        Reading ::= SEQUENCE {
+            sensor  INTEGER (0..1023),
+            name    IA5String (SIZE(1..8)),
+            samples SEQUENCE (SIZE(0..4)) OF INTEGER (-128..127),
+            ...,
+            unit    UTF8String (SIZE(1..2)) OPTIONAL
+        }
+
+        Report ::= CHOICE {
+            reading Reading,
+            alarm   BOOLEAN,
+            ...
+        }
+
+        Log ::= SEQUENCE OF Report
+
+        END"#
+    );
+}
+
+use budget::*;
+
+fn footprint(asn_name: &str) -> Footprint {
+    *budget::footprint()
+        .iter()
+        .find(|footprint| footprint.asn_name == asn_name)
+        .unwrap()
+}
+
+fn assert_within(footprint: Footprint, bits: usize) {
+    assert!(footprint.min_bits <= bits as u64, "{footprint:?}: {bits}");
+    assert!(
+        footprint.max_bits.is_none_or(|max| bits as u64 <= max),
+        "{footprint:?}: {bits}"
+    );
+}
+
+#[test]
+fn test_sizes_of_the_types() {
+    let reading = footprint("Reading");
+    assert_eq!("Reading", reading.rust_name);
+    assert_eq!(std::mem::size_of::<Reading>(), reading.size);
+    assert_eq!(
+        Some(8 + 4 * std::mem::size_of::<i8>() + 2 * 4),
+        reading.heap
+    );
+    assert_eq!(Some(reading.heap.unwrap()), footprint("Report").heap);
+    assert_eq!(None, footprint("Log").heap);
+}
+
+#[test]
+fn test_encodings_within_the_bounds() {
+    let smallest = Reading {
+        sensor: 0,
+        name: "a".to_string(),
+        samples: Vec::new(),
+        unit: None,
+    };
+    let largest = Reading {
+        sensor: 1023,
+        name: "abcdefgh".to_string(),
+        samples: vec![-128, 127, 0, 1],
+        unit: Some("\u{1F321}\u{1F321}".to_string()),
+    };
+
+    let reading = footprint("Reading");
+    assert_eq!(serialize_uper(&smallest).0 as u64, reading.min_bits);
+    assert_eq!(Some(serialize_uper(&largest).0 as u64), reading.max_bits);
+
+    let report = footprint("Report");
+    assert_within(report, serialize_uper(&Report::Alarm(true)).0);
+    assert_within(report, serialize_uper(&Report::Reading(largest.clone())).0);
+    assert_eq!(
+        Some(serialize_uper(&Report::Reading(largest.clone())).0 as u64),
+        report.max_bits
+    );
+
+    let log = footprint("Log");
+    assert_eq!(None, log.max_bits);
+    assert_within(
+        log,
+        serialize_uper(&Log(vec![Report::Reading(largest); 3])).0,
+    );
+}
+
+#[test]
+fn test_table() {
+    let table = table(budget::footprint());
+    let mut lines = table.lines();
+    assert_eq!(
+        Some("| Type | Size | Heap | Min bits | Max bits |"),
+        lines.next()
+    );
+    assert_eq!(
+        Some(format!("| Log | {} | - | 8 | - |", std::mem::size_of::<Log>()).as_str()),
+        lines.nth(3)
+    );
+}
@@ -0,0 +1,89 @@
+use asn1rs::golden::{Goldens, MismatchKind};
+use asn1rs::prelude::*;
+
+asn_to_rust!(
+    r"Golden DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Vehicle ::= SEQUENCE {
+        speed      INTEGER (0..255),
+        passengers SEQUENCE (SIZE(0..3)) OF UTF8String,
+        parked     BOOLEAN
+    }
+
+    END"
+);
+
+fn vehicle() -> Vehicle {
+    Vehicle {
+        speed: 42,
+        passengers: vec!["A".to_string()],
+        parked: true,
+    }
+}
+
+#[test]
+fn test_matching_goldens() {
+    Goldens::default()
+        .add("empty", Vehicle::default(), "0x00 0x00")
+        .add("one passenger", vehicle(), "2a 40 50 60")
+        .assert();
+    asn1rs::assert_uper_eq!(vehicle(), "0x2A4050 60");
+}
+
+#[test]
+fn test_mismatch_names_bit_and_field() {
+    let mismatches = Goldens::default()
+        .add("empty", Vehicle::default(), "00 00")
+        .add("one passenger", vehicle(), "2a 40 58 60")
+        .check()
+        .unwrap_err();
+    assert_eq!(1, mismatches.len());
+    assert_eq!("one passenger", mismatches[0].name);
+    assert_eq!(
+        MismatchKind::Bytes {
+            actual: vec![0x2a, 0x40, 0x50, 0x60],
+            bit: 20,
+            path: Some("passengers[0]".to_string()),
+        },
+        mismatches[0].kind
+    );
+    assert_eq!(
+        "Golden encoding \"one passenger\" of golden::Vehicle differs at bit 20 (byte 2) in `passengers[0]`
+  expected (  4 bytes): 2a       40       58       60
+                        00101010 01000000 01011000 01100000
+    actual (  4 bytes): 2a       40       50       60
+                        00101010 01000000 01010000 01100000
+                                              ^
+",
+        mismatches[0].to_string()
+    );
+}
+
+#[test]
+fn test_goldens_of_bytes() {
+    let goldens = Goldens::default()
+        .add_bytes("empty", Vehicle::default(), vec![0x00, 0x00])
+        .add_bytes("one passenger", vehicle(), vec![0x2a, 0x40, 0x50, 0x60]);
+    assert_eq!(Ok(()), goldens.check());
+    assert_eq!(
+        vec!["empty", "one passenger"],
+        goldens
+            .goldens()
+            .iter()
+            .map(|golden| golden.name.as_str())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+#[should_panic(expected = "UPER encoding of golden::Vehicle differs at bit 7 (byte 0) in `speed`")]
+fn test_assert_uper_eq_panics_on_mismatch() {
+    asn1rs::assert_uper_eq!(vehicle(), "0x2B 0x40 0x50 0x60");
+}
+
+#[test]
+#[should_panic(expected = "Invalid hex bytes: \"0x2\"")]
+fn test_assert_uper_eq_panics_on_invalid_hex() {
+    asn1rs::assert_uper_eq!(vehicle(), "0x2");
+}
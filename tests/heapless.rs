@@ -0,0 +1,129 @@
+#![cfg(feature = "heapless")]
+
+mod test_utils;
+
+use asn1rs::descriptor::substituted::Substitute;
+use test_utils::*;
+
+mod plain {
+    use asn1rs::prelude::*;
+
+    asn_to_rust!(
+        r#"Heapless DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+
+        Frame ::= SEQUENCE {
+            name     UTF8String (SIZE(1..4)),
+            code     IA5String (SIZE(2)),
+            payload  OCTET STRING (SIZE(0..8)),
+            tags     SEQUENCE (SIZE(0..3)) OF VisibleString (SIZE(1..5)),
+            comment  UTF8String OPTIONAL,
+            history  SEQUENCE (SIZE(0..2, ...)) OF INTEGER (0..255),
+            label    IA5String (SIZE(1..8)) DEFAULT "none"
+        }
+
+        END"#
+    );
+}
+
+mod fixed {
+    use asn1rs::prelude::*;
+
+    asn_to_rust!(
+        heapless = true,
+        r#"Heapless DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+
+        Frame ::= SEQUENCE {
+            name     UTF8String (SIZE(1..4)),
+            code     IA5String (SIZE(2)),
+            payload  OCTET STRING (SIZE(0..8)),
+            tags     SEQUENCE (SIZE(0..3)) OF VisibleString (SIZE(1..5)),
+            comment  UTF8String OPTIONAL,
+            history  SEQUENCE (SIZE(0..2, ...)) OF INTEGER (0..255),
+            label    IA5String (SIZE(1..8)) DEFAULT "none"
+        }
+
+        END"#
+    );
+}
+
+fn plain_frame() -> plain::Frame {
+    plain::Frame {
+        name: "Grüß".to_string(),
+        code: "DE".to_string(),
+        payload: vec![0xCA, 0xFE],
+        tags: vec!["a".to_string(), "bcdef".to_string()],
+        comment: Some("unbounded".to_string()),
+        history: vec![1, 2],
+        label: "none".to_string(),
+    }
+}
+
+fn fixed_frame() -> fixed::Frame {
+    fixed::Frame {
+        name: heapless::String::try_from("Grüß").unwrap(),
+        code: heapless::String::try_from("DE").unwrap(),
+        payload: heapless::Vec::from_slice(&[0xCA, 0xFE]).unwrap(),
+        tags: heapless::Vec::from_slice(&[
+            heapless::String::try_from("a").unwrap(),
+            heapless::String::try_from("bcdef").unwrap(),
+        ])
+        .unwrap(),
+        comment: Some("unbounded".to_string()),
+        history: vec![1, 2],
+        label: "none".to_string(),
+    }
+}
+
+#[test]
+fn test_capacities_of_the_fields() {
+    let frame = fixed::Frame::default();
+    let name: &heapless::String<16> = &frame.name;
+    let code: &heapless::String<2> = &frame.code;
+    let payload: &heapless::Vec<u8, 8> = &frame.payload;
+    let tags: &heapless::Vec<heapless::String<5>, 3> = &frame.tags;
+    let comment: &Option<String> = &frame.comment;
+    let history: &Vec<u8> = &frame.history;
+    let label: &String = &frame.label;
+    assert!(name.is_empty() && code.is_empty() && payload.is_empty() && tags.is_empty());
+    assert!(comment.is_none() && history.is_empty() && label == "none");
+}
+
+#[test]
+fn test_encoded_like_the_allocating_types() {
+    assert_eq!(
+        serialize_uper(&plain_frame()),
+        serialize_uper(&fixed_frame())
+    );
+    serialize_and_deserialize_uper(
+        241,
+        &[
+            0x81, 0x91, 0xDC, 0xB0, 0xEF, 0x30, 0xE7, 0xE2, 0x45, 0x2C, 0xAF, 0xE8, 0x61, 0x98,
+            0xB1, 0xE4, 0xCB, 0x98, 0x25, 0xD5, 0xB9, 0x89, 0xBD, 0xD5, 0xB9, 0x91, 0x95, 0x91,
+            0x00, 0x81, 0x00,
+        ],
+        &fixed_frame(),
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    let (bits, bytes) = serialize_uper(&plain_frame());
+    assert_eq!(
+        fixed_frame(),
+        deserialize_uper::<fixed::Frame>(&bytes, bits)
+    );
+}
+
+#[test]
+fn test_exceeding_the_capacity_is_an_error() {
+    type Name = heapless::String<4>;
+    type Payload = heapless::Vec<u8, 2>;
+    assert!(<Name as Substitute<Name, String>>::from_asn("Grüß".to_string()).is_err());
+    assert!(<Payload as Substitute<Payload, Vec<u8>>>::from_asn(vec![1, 2, 3]).is_err());
+    assert_eq!(
+        Ok(Payload::from_slice(&[1, 2]).unwrap()),
+        <Payload as Substitute<Payload, Vec<u8>>>::from_asn(vec![1, 2])
+    );
+}
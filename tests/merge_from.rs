@@ -0,0 +1,93 @@
+mod test_utils;
+use test_utils::*;
+
+asn_to_rust!(
+    r"MergeFrom DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Config ::= SEQUENCE {
+        revision INTEGER (0..65535),
+        name UTF8String OPTIONAL,
+        limits Limits OPTIONAL,
+        peers SEQUENCE OF UTF8String,
+        retries INTEGER (0..7) DEFAULT 3
+    }
+
+    Limits ::= SEQUENCE {
+        rate INTEGER (0..1000) OPTIONAL,
+        burst INTEGER (0..1000) OPTIONAL
+    }
+
+    END"
+);
+
+fn base() -> Config {
+    Config {
+        revision: 1,
+        name: Some("base".to_string()),
+        limits: Some(Limits {
+            rate: Some(100),
+            burst: Some(10),
+        }),
+        peers: vec!["a".to_string(), "b".to_string()],
+        retries: 3,
+    }
+}
+
+#[test]
+fn test_absent_optional_fields_are_kept() {
+    let mut config = base();
+    config.merge_from(&Config {
+        revision: 2,
+        name: None,
+        limits: None,
+        peers: vec!["c".to_string()],
+        retries: 5,
+    });
+    assert_eq!(
+        Config {
+            revision: 2,
+            name: Some("base".to_string()),
+            limits: base().limits,
+            peers: vec!["c".to_string()],
+            retries: 5,
+        },
+        config
+    );
+}
+
+#[test]
+fn test_present_optional_fields_overwrite() {
+    let mut config = base();
+    let delta = Config {
+        revision: 2,
+        name: Some("delta".to_string()),
+        limits: Some(Limits {
+            rate: None,
+            burst: Some(20),
+        }),
+        peers: Vec::new(),
+        retries: 3,
+    };
+    config.merge_from(&delta);
+    assert_eq!(delta, config);
+}
+
+#[test]
+fn test_nested_merge() {
+    let mut limits = Limits {
+        rate: Some(100),
+        burst: None,
+    };
+    limits.merge_from(&Limits {
+        rate: None,
+        burst: Some(20),
+    });
+    assert_eq!(
+        Limits {
+            rate: Some(100),
+            burst: Some(20),
+        },
+        limits
+    );
+}
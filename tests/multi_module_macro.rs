@@ -0,0 +1,55 @@
+mod test_utils;
+
+use test_utils::*;
+
+asn_to_rust!(
+    r"Common DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    max-name-len INTEGER ::= 16
+
+    Identifier ::= INTEGER (0..65535)
+
+    Name ::= UTF8String (SIZE(1..max-name-len))
+
+    END",
+    r"Vehicle DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    IMPORTS
+        Identifier, Name, max-name-len FROM Common;
+
+    Vehicle ::= SEQUENCE {
+        id    Identifier,
+        name  Name,
+        alias IA5String (SIZE(0..max-name-len))
+    }
+
+    END",
+);
+
+use vehicle::Vehicle;
+
+#[test]
+fn test_modules_are_emitted_as_siblings() {
+    let value = Vehicle {
+        id: common::Identifier(1337),
+        name: common::Name("truck".to_string()),
+        alias: "t".to_string(),
+    };
+
+    let (bits, bytes) = serialize_uper(&value);
+    assert_eq!(value, deserialize_uper::<Vehicle>(&bytes, bits));
+    assert_eq!(16, common::MAX_NAME_LEN);
+}
+
+#[test]
+fn test_imported_value_reference_is_resolved() {
+    let mut writer = UperWriter::default();
+    let result = writer.write(&Vehicle {
+        id: common::Identifier(1),
+        name: common::Name("name".to_string()),
+        alias: "a".repeat(common::MAX_NAME_LEN as usize + 1),
+    });
+    assert!(result.is_err(), "alias exceeds the imported max-name-len");
+}
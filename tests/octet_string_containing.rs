@@ -0,0 +1,175 @@
+mod test_utils;
+
+use asn1rs::descriptor::containing::{Transform, TransformError};
+use test_utils::*;
+
+asn_to_rust!(
+    r"ContainingDef DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Inner ::= SEQUENCE {
+        number INTEGER (0..255),
+        flag BOOLEAN
+    }
+
+    Envelope ::= SEQUENCE {
+        content OCTET STRING (CONTAINING Inner)
+    }
+
+    XorEnvelope ::= SEQUENCE {
+        content OCTET STRING (CONTAINING Inner ENCODED BY xor-all)
+    }
+
+    KeyedEnvelope ::= SEQUENCE {
+        content OCTET STRING (CONTAINING Inner ENCODED BY xor-with)
+    }
+
+    WrappedNumber ::= OCTET STRING (CONTAINING INTEGER (0..65535))
+
+    WrappedLabel ::= OCTET STRING (CONTAINING VisibleString)
+
+    END"
+);
+
+/// Flips all bits, a stand-in for real compression or encryption
+pub struct XorAll;
+
+impl Transform for XorAll {
+    fn encode(&self, content: Vec<u8>) -> Result<Vec<u8>, TransformError> {
+        Ok(content.into_iter().map(|b| b ^ 0xFF).collect())
+    }
+
+    fn decode(&self, octets: Vec<u8>) -> Result<Vec<u8>, TransformError> {
+        if octets.is_empty() {
+            Err(TransformError::new("nothing to decode"))
+        } else {
+            Ok(octets.into_iter().map(|b| b ^ 0xFF).collect())
+        }
+    }
+
+    fn unregistered() -> Option<Self> {
+        Some(XorAll)
+    }
+}
+
+/// Xors all bytes with a key that is only known at runtime, a stand-in for encryption
+pub struct XorWith(u8);
+
+impl Transform for XorWith {
+    fn encode(&self, content: Vec<u8>) -> Result<Vec<u8>, TransformError> {
+        Ok(content.into_iter().map(|b| b ^ self.0).collect())
+    }
+
+    fn decode(&self, octets: Vec<u8>) -> Result<Vec<u8>, TransformError> {
+        self.encode(octets)
+    }
+}
+
+fn keyed_envelope() -> KeyedEnvelope {
+    KeyedEnvelope {
+        content: Inner {
+            number: 5,
+            flag: true,
+        },
+    }
+}
+
+#[test]
+fn test_containing_uper() {
+    serialize_and_deserialize_uper(
+        24,
+        &[0x02, 0x05, 0x80],
+        &Envelope {
+            content: Inner {
+                number: 5,
+                flag: true,
+            },
+        },
+    );
+}
+
+#[test]
+fn test_containing_encoded_by_uper() {
+    serialize_and_deserialize_uper(
+        24,
+        &[0x02, 0xFA, 0x7F],
+        &XorEnvelope {
+            content: Inner {
+                number: 5,
+                flag: true,
+            },
+        },
+    );
+}
+
+#[test]
+fn test_containing_transform_error_uper() {
+    let mut reader = UperReader::from((&[0x00][..], 8));
+    let error = reader.read::<XorEnvelope>().unwrap_err();
+    assert_eq!(
+        &asn1rs::protocol::per::ErrorKind::ContentTransformFailed("nothing to decode".to_string()),
+        error.kind()
+    );
+}
+
+#[test]
+fn test_containing_registered_transform_uper() {
+    let mut writer = UperWriter::default().with_transform(XorWith(0x0F));
+    writer.write(&keyed_envelope()).unwrap();
+    assert_eq!(&[0x02, 0x0A, 0x8F], writer.byte_content());
+
+    let mut reader = UperReader::from((writer.byte_content(), writer.bit_len()));
+    reader.register_transform(XorWith(0x0F));
+    assert_eq!(keyed_envelope(), reader.read::<KeyedEnvelope>().unwrap());
+}
+
+#[test]
+fn test_containing_registered_transform_ber() {
+    let mut writer = BerWriter::default().with_transform(XorWith(0xF0));
+    writer.write(&keyed_envelope()).unwrap();
+
+    let mut reader = BerReader::from(writer.byte_content()).with_transform(XorWith(0xF0));
+    assert_eq!(keyed_envelope(), reader.read::<KeyedEnvelope>().unwrap());
+}
+
+#[test]
+fn test_containing_unregistered_transform_uper() {
+    let mut writer = UperWriter::default();
+    let error = writer.write(&keyed_envelope()).unwrap_err();
+    assert_eq!(
+        &asn1rs::protocol::per::ErrorKind::ContentTransformFailed(
+            "no instance of octet_string_containing::XorWith is registered".to_string()
+        ),
+        error.kind()
+    );
+}
+
+#[test]
+fn test_containing_definition_uper() {
+    serialize_and_deserialize_uper(24, &[0x02, 0x12, 0x34], &WrappedNumber(0x1234));
+}
+
+#[test]
+fn test_containing_uses_the_substitute_of_the_writer() {
+    let mut writer = UperWriter::default();
+    writer.set_substitute(Some('?'));
+    writer.write(&WrappedLabel("Grün".to_string())).unwrap();
+    assert_eq!(
+        WrappedLabel("Gr?n".to_string()),
+        deserialize_uper(writer.byte_content(), writer.bit_len())
+    );
+}
+
+#[test]
+#[cfg(feature = "protobuf")]
+fn test_containing_encoded_by_protobuf() {
+    serialize_and_deserialize_protobuf(
+        &[0x0A, 0x04, 0xF7, 0xFA, 0xEF, 0xFE],
+        &XorEnvelope {
+            content: Inner {
+                number: 5,
+                flag: true,
+            },
+        },
+    );
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "bytes")]
+
+mod test_utils;
+
+use bytes::Bytes;
+use test_utils::*;
+
+mod plain {
+    use asn1rs::prelude::*;
+
+    asn_to_rust!(
+        r"OctetStringType DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+
+        Packet ::= SEQUENCE {
+            id        OCTET STRING (SIZE(4)),
+            payload   OCTET STRING,
+            signature OCTET STRING OPTIONAL,
+            chunks    SEQUENCE OF OCTET STRING (SIZE(1..8))
+        }
+
+        END"
+    );
+}
+
+asn_to_rust!(
+    octet_string_type = "Bytes",
+    r"OctetStringType DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Packet ::= SEQUENCE {
+        id        OCTET STRING (SIZE(4)),
+        payload   OCTET STRING,
+        signature OCTET STRING OPTIONAL,
+        chunks    SEQUENCE OF OCTET STRING (SIZE(1..8))
+    }
+
+    END"
+);
+
+fn packet() -> Packet {
+    Packet {
+        id: Bytes::from_static(&[1, 2, 3, 4]),
+        payload: Bytes::from(vec![0xAB; 300]),
+        signature: Some(Bytes::from_static(b"signed")),
+        chunks: vec![Bytes::from_static(b"a"), Bytes::from_static(b"bcdefgh")],
+    }
+}
+
+#[test]
+fn test_bytes_are_encoded_like_vec_u8() {
+    let plain = plain::Packet {
+        id: vec![1, 2, 3, 4],
+        payload: vec![0xAB; 300],
+        signature: Some(b"signed".to_vec()),
+        chunks: vec![b"a".to_vec(), b"bcdefgh".to_vec()],
+    };
+    assert_eq!(serialize_uper(&plain), serialize_uper(&packet()));
+}
+
+#[test]
+fn test_decoded_payload_is_sliced_without_copy() {
+    let (bits, bytes) = serialize_uper(&packet());
+    let decoded = deserialize_uper::<Packet>(&bytes, bits);
+    assert_eq!(packet(), decoded);
+
+    let head = decoded.payload.slice(..10);
+    assert_eq!(decoded.payload.as_ptr(), head.as_ptr());
+    assert_eq!(decoded.payload.as_ptr(), decoded.clone().payload.as_ptr());
+}
+
+#[test]
+fn test_size_constraints_still_apply() {
+    let mut writer = UperWriter::default();
+    let packet = Packet {
+        id: Bytes::from_static(&[1, 2, 3]),
+        ..packet()
+    };
+    assert!(writer.write(&packet).is_err());
+}
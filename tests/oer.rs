@@ -0,0 +1,244 @@
+#![cfg(feature = "oer")]
+
+mod test_utils;
+
+use asn1rs::protocol::oer::ErrorKind;
+use asn1rs::rw::DEFAULT_MAX_DEPTH;
+use test_utils::codec::*;
+use test_utils::*;
+
+asn_to_rust!(
+    r"OerMapping DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Version1 ::= SEQUENCE {
+        id INTEGER (0..255),
+        ...
+    }
+
+    Version2 ::= SEQUENCE {
+        id INTEGER (0..255),
+        ...,
+        note UTF8String,
+        flag BOOLEAN
+    }
+
+    Outer ::= SEQUENCE {
+        inner Inner OPTIONAL,
+        last  BOOLEAN OPTIONAL
+    }
+
+    Inner ::= SEQUENCE {
+        a INTEGER (0..70000) OPTIONAL,
+        b BOOLEAN
+    }
+
+    END"
+);
+
+struct Coer;
+
+impl Codec for Coer {
+    type Error = asn1rs::protocol::oer::Error;
+
+    fn encode<T: Writable>(value: &T, max_depth: usize) -> Result<Vec<u8>, Self::Error> {
+        let mut writer = CoerWriter::default();
+        writer.set_max_depth(max_depth);
+        writer.write(value)?;
+        Ok(writer.into_bytes_vec())
+    }
+
+    fn decode<T: Readable>(bytes: &[u8], max_depth: usize) -> Result<T, Self::Error> {
+        let mut reader = CoerReader::from(bytes);
+        reader.set_max_depth(max_depth);
+        let value = reader.read::<T>()?;
+        assert_eq!(0, reader.bytes_remaining());
+        Ok(value)
+    }
+}
+
+#[test]
+fn test_canonical_bytes() {
+    assert_eq!(
+        &[
+            0x00, // preamble: no extension, no payload, priority equals its DEFAULT value
+            0x2A, // 42 in one octet
+            0xF9, // -7 in one signed octet
+            0x07, b'g', b'a', b't', b'e', b'w', b'a', b'y', //
+            0x02, b'D', b'E', //
+            0x01, // response
+            0xA0, // fixed size bit string
+            0x01, 0x02, // quantity of 2
+            0x01, 0x01, // 1
+            0x02, 0x01, 0x2C, // 300
+            0x81, 0xFF, // [1] TRUE
+        ][..],
+        &serialize::<Coer>(&message())[..]
+    );
+}
+
+#[test]
+fn test_canonical_bytes_with_extension() {
+    let message = Message {
+        payload: Some(vec![0xAB]),
+        priority: 5,
+        trailer: Some("x".to_string()),
+        ..message()
+    };
+    let bytes = serialize::<Coer>(&message);
+    assert_eq!(
+        &[
+            0xE0, // preamble: extension, payload and priority are present
+            0x2A, 0xF9, //
+            0x07, b'g', b'a', b't', b'e', b'w', b'a', b'y', //
+            0x02, b'D', b'E', //
+            0x01, 0xAB, // payload
+            0x05, // priority
+            0x01, 0xA0, 0x01, 0x02, 0x01, 0x01, 0x02, 0x01, 0x2C, 0x81, 0xFF, //
+            0x02, 0x07, 0x80, // extension presence bitmap of one addition
+            0x02, 0x01, b'x', // trailer as open type
+        ][..],
+        &bytes[..]
+    );
+    assert_eq!(message, deserialize::<Coer, Message>(&bytes).unwrap());
+}
+
+#[test]
+fn test_round_trip() {
+    assert_round_trip::<Coer>();
+
+    for outer in [
+        Outer {
+            inner: None,
+            last: None,
+        },
+        Outer {
+            inner: Some(Inner { a: None, b: true }),
+            last: Some(false),
+        },
+        Outer {
+            inner: Some(Inner {
+                a: Some(70000),
+                b: false,
+            }),
+            last: None,
+        },
+    ] {
+        let bytes = serialize::<Coer>(&outer);
+        assert_eq!(outer, deserialize::<Coer, Outer>(&bytes).unwrap());
+    }
+}
+
+#[test]
+fn test_extension_alternative_is_open_type() {
+    let bytes = serialize::<Coer>(&Message {
+        body: Body::Note("x".to_string()),
+        ..message()
+    });
+    assert_eq!(&[0x82, 0x02, 0x01, b'x'], &bytes[bytes.len() - 4..]);
+}
+
+#[test]
+fn test_unknown_extension_additions_are_skipped() {
+    let bytes = serialize::<Coer>(&Version2 {
+        id: 7,
+        note: Some("new".to_string()),
+        flag: Some(true),
+    });
+    assert_eq!(
+        &[
+            0x80, 0x07, // preamble and id
+            0x02, 0x06, 0xC0, // two present additions
+            0x04, 0x03, b'n', b'e', b'w', // note
+            0x01, 0xFF, // flag
+        ][..],
+        &bytes[..]
+    );
+    assert_eq!(Version1 { id: 7 }, deserialize::<Coer, _>(&bytes).unwrap());
+
+    let bytes = serialize::<Coer>(&Version1 { id: 7 });
+    assert_eq!(&[0x00, 0x07], &bytes[..]);
+    assert_eq!(
+        Version2 {
+            id: 7,
+            note: None,
+            flag: None
+        },
+        deserialize::<Coer, _>(&bytes).unwrap()
+    );
+}
+
+#[test]
+fn test_non_canonical_input_is_accepted() {
+    let mut bytes = serialize::<Coer>(&message());
+    // the length of the name in the long form
+    bytes.splice(3..4, [0x81, 0x07]);
+    // any non-zero octet is TRUE
+    *bytes.last_mut().unwrap() = 0x01;
+    assert_eq!(message(), deserialize::<Coer, Message>(&bytes).unwrap());
+}
+
+#[test]
+fn test_invalid_input_is_rejected() {
+    let bytes = serialize::<Coer>(&message());
+    assert_eq!(
+        &ErrorKind::EndOfStream,
+        deserialize::<Coer, Message>(&bytes[..bytes.len() - 1])
+            .unwrap_err()
+            .kind()
+    );
+
+    // an extension alternative with the unknown tag [5]
+    let mut unknown = bytes[..bytes.len() - 2].to_vec();
+    unknown.extend_from_slice(&[0x85, 0x01, 0xFF]);
+    assert!(matches!(
+        deserialize::<Coer, Message>(&unknown).unwrap_err().kind(),
+        ErrorKind::UnexpectedTag {
+            got: asn1rs::model::asn::Tag::ContextSpecific(5),
+            ..
+        }
+    ));
+
+    let error = asn1rs::Error::from(deserialize::<Coer, Message>(&bytes[..1]).unwrap_err());
+    assert_eq!(asn1rs::error::Kind::EndOfData, error.kind());
+}
+
+/// The encoding of a [`Tree`] that is `levels` deep: the value and a quantity of one element
+/// on each level, but the last
+fn crafted_deep(levels: usize) -> Vec<u8> {
+    let mut bytes = [0x00, 0x01, 0x01].repeat(levels - 1);
+    bytes.extend_from_slice(&[0x00, 0x01, 0x00]);
+    bytes
+}
+
+#[test]
+fn test_crafted_deep_tree_matches_the_writer() {
+    let tree = deep(3);
+    assert_eq!(serialize::<Coer>(&tree), crafted_deep(3));
+    assert_eq!(tree, deserialize::<Coer, Tree>(&crafted_deep(3)).unwrap());
+}
+
+#[test]
+fn test_reading_crafted_deep_input_fails_without_overflowing_the_stack() {
+    assert_eq!(
+        &ErrorKind::NestingDepthExceedsLimit(DEFAULT_MAX_DEPTH),
+        deserialize::<Coer, Tree>(&crafted_deep(100_001))
+            .unwrap_err()
+            .kind()
+    );
+}
+
+#[test]
+fn test_constraints_are_checked() {
+    assert_constraints_are_checked::<Coer>();
+}
+
+#[test]
+fn test_truncated_input_is_rejected() {
+    assert_truncated_input_is_rejected::<Coer>();
+}
+
+#[test]
+fn test_nesting_depth_is_limited() {
+    assert_nesting_depth_is_limited::<Coer>();
+}
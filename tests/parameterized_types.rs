@@ -0,0 +1,42 @@
+mod test_utils;
+
+use test_utils::*;
+
+asn_to_rust!(
+    r"ParameterizedTypes DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Container {Type} ::= SEQUENCE {
+        id   INTEGER (0..255),
+        item Type
+    }
+
+    Bounded {Type, INTEGER:max} ::= SEQUENCE (SIZE(1..max)) OF Type
+
+    Message ::= SEQUENCE {
+        flag   Container {BOOLEAN},
+        values Bounded {Value, 4}
+    }
+
+    Value ::= INTEGER (0..7)
+
+    END"
+);
+
+#[test]
+fn test_instances_are_generated() {
+    let message = Message {
+        flag: ContainerBoolean { id: 1, item: true },
+        values: BoundedValue4(vec![Value(1), Value(7)]),
+    };
+    // id, item, length - 1 of the values (2 bits), values (3 bits each)
+    serialize_and_deserialize_uper(17, &[0x01, 0xA7, 0x80], &message);
+}
+
+#[test]
+fn test_arguments_constrain_instances() {
+    let mut writer = UperWriter::default();
+    assert!(writer
+        .write(&BoundedValue4((0..5).map(Value).collect()))
+        .is_err());
+}
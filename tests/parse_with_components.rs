@@ -23,6 +23,14 @@ asn_to_rust!(
         ...,
         def(VarB)
     })
+
+    Profile ::= SEQUENCE {
+        abc UTF8String OPTIONAL,
+        choice CHOICE {
+            a BOOLEAN,
+            b NULL
+        } (WITH COMPONENTS { ..., b ABSENT })
+    } (WITH COMPONENTS { abc PRESENT, choice })
     
     END"
 );
@@ -55,3 +63,13 @@ pub fn with_components_must_be_transparent() {
 
     assert_eq!(writer1.into_bytes_vec(), writer2.into_bytes_vec());
 }
+
+#[test]
+pub fn inline_with_components_are_transparent() {
+    let profile = Profile {
+        abc: Some("abc".to_string()),
+        choice: ProfileChoice::A(true),
+    };
+    let (bits, bytes) = serialize_uper(&profile);
+    assert_eq!(profile, deserialize_uper::<Profile>(&bytes, bits));
+}
@@ -0,0 +1,110 @@
+mod test_utils;
+
+use asn1rs::model::asn::InvisibleConstraintKind;
+use asn1rs::model::parse::Tokenizer;
+use asn1rs::model::Model;
+use test_utils::*;
+
+asn_to_rust!(
+    r#"PerVisibility DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Reading ::= SEQUENCE {
+        level  INTEGER (0..7, ..., 8..15),
+        label  IA5String (PATTERN "[a-z]+"),
+        note   UTF8String (FROM ("a".."z")),
+        unit   UTF8String (SIZE(1..4))
+    }
+
+    END"#
+);
+
+#[test]
+fn test_extension_additions_do_not_change_the_encoding() {
+    // the value is within the root, so it is encoded within the root range 0..7
+    let reading = Reading {
+        level: 5,
+        label: String::new(),
+        note: String::new(),
+        unit: "m".to_string(),
+    };
+    serialize_and_deserialize_uper(36, &[0x50, 0x00, 0x00, 0x16, 0xD0], &reading);
+
+    // values outside of the root are encoded as unconstrained extension values
+    let mut writer = UperWriter::default();
+    writer
+        .write(&Reading {
+            level: 12,
+            ..reading.clone()
+        })
+        .unwrap();
+    assert_eq!(
+        Reading {
+            level: 12,
+            ..reading
+        },
+        UperReader::from((writer.byte_content(), writer.bit_len()))
+            .read::<Reading>()
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_pattern_and_permitted_alphabet_are_not_checked() {
+    let reading = Reading {
+        level: 0,
+        label: "NOT LOWERCASE".to_string(),
+        note: "0123".to_string(),
+        unit: "m".to_string(),
+    };
+    let mut writer = UperWriter::default();
+    assert!(writer.write(&reading).is_ok());
+}
+
+#[test]
+fn test_size_of_utf8string_is_still_checked() {
+    let reading = Reading {
+        level: 0,
+        label: String::new(),
+        note: String::new(),
+        unit: "too long".to_string(),
+    };
+    let mut writer = UperWriter::default();
+    assert!(writer.write(&reading).is_err());
+}
+
+#[test]
+fn test_invisible_constraints_are_reported() {
+    let model = Model::try_from(Tokenizer.parse(
+        r#"PerVisibility DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+
+        Reading ::= SEQUENCE {
+            level  INTEGER (0..7, ..., 8..15),
+            label  IA5String (PATTERN "[a-z]+"),
+            note   UTF8String (FROM ("a".."z")),
+            unit   UTF8String (SIZE(1..4))
+        }
+
+        END"#,
+    ))
+    .unwrap();
+
+    assert_eq!(
+        vec![
+            InvisibleConstraintKind::ExtensionAdditions,
+            InvisibleConstraintKind::Pattern,
+            InvisibleConstraintKind::PermittedAlphabet,
+            InvisibleConstraintKind::Utf8StringSize,
+        ],
+        model
+            .invisible_constraints
+            .iter()
+            .map(|c| c.kind)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        "line 5, column 40 in Reading: Extension additions of constraints are not PER-visible and are ignored",
+        model.invisible_constraints[0].to_string()
+    );
+}
@@ -0,0 +1,64 @@
+//! Pins the items of `asn1rs::prelude`, so that removing or moving one of them is noticed
+
+#![allow(unused_imports)]
+
+use asn1rs::prelude::RegisterTransform;
+use asn1rs::prelude::{asn, asn_to_rust};
+use asn1rs::prelude::{basic, per};
+use asn1rs::prelude::{BasicReader, BasicWriter, PrintlnWriter};
+use asn1rs::prelude::{BitVec, BitVecConst, Null, Oid, RedactedDebug};
+use asn1rs::prelude::{
+    Bits, Decoded, ReaderMark, UperDecodable, UperIter, UperReader, UperWriter, WriterMark,
+};
+use asn1rs::prelude::{DecodePolicy, DecodePolicyReader, DepthLimit, RecoveredValue};
+use asn1rs::prelude::{FieldChange, FieldValue, Path, Segment, Visitable, Visitor};
+use asn1rs::prelude::{Landmark, Mutant, Mutation, UperMutator};
+use asn1rs::prelude::{PositionedReader, PositionedWriter};
+use asn1rs::prelude::{
+    Readable, ReadableType, Reader, Writable, WritableField, WritableType, Writer,
+};
+
+#[cfg(feature = "descriptive-deserialize-errors")]
+use asn1rs::prelude::ScopeDescription;
+#[cfg(feature = "serde")]
+use asn1rs::prelude::UperTranscoder;
+#[cfg(feature = "cbor")]
+use asn1rs::prelude::{cbor, CborReader, CborWriter};
+#[cfg(feature = "oer")]
+use asn1rs::prelude::{oer, CoerReader, CoerWriter};
+#[cfg(feature = "protobuf")]
+use asn1rs::prelude::{protobuf, ProtobufEq, ProtobufJsonWriter, ProtobufReader, ProtobufWriter};
+
+/// Compiles only if both types are the same
+fn same<T>(value: T) -> T {
+    value
+}
+
+#[test]
+fn test_readers_and_writers() {
+    fn reader(bits: UperReader<Bits>) -> asn1rs::rw::UperReader<asn1rs::rw::Bits> {
+        bits
+    }
+    let _ = reader;
+    let _: fn(UperWriter) -> asn1rs::rw::UperWriter = same;
+    let _: fn(BasicWriter<Vec<u8>>) -> asn1rs::rw::BasicWriter<Vec<u8>> = same;
+    let _: fn(PrintlnWriter) -> asn1rs::rw::PrintlnWriter = same;
+}
+
+#[test]
+fn test_error_types() {
+    let _: fn(per::Error) -> asn1rs::protocol::per::Error = same;
+    let _: fn(per::ErrorKind) -> asn1rs::protocol::per::ErrorKind = same;
+    let _: fn(basic::Error) -> asn1rs::protocol::basic::Error = same;
+}
+
+#[test]
+fn test_descriptor_types() {
+    let _: fn(BitVec) -> asn1rs::descriptor::BitVec = same;
+    let _: fn(Null) -> asn1rs::descriptor::Null = same;
+    let _: fn(Oid) -> asn1rs::descriptor::Oid = same;
+    let _: fn(Path) -> asn1rs::descriptor::visit::Path = same;
+    let _: fn(Segment) -> asn1rs::descriptor::visit::Segment = same;
+    let _: fn(FieldChange) -> asn1rs::descriptor::diff::FieldChange = same;
+    let _: fn(FieldValue) -> asn1rs::descriptor::diff::FieldValue = same;
+}
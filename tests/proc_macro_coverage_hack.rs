@@ -5,6 +5,7 @@ use std::panic::AssertUnwindSafe;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::{env, fs, panic};
+use syn::parse::Parser;
 use syn::Attribute;
 use syn::ItemEnum;
 use syn::ItemStruct;
@@ -60,8 +61,87 @@ pub fn emulate_macro_expansion_fallible(mut file: fs::File) {
     }
 
     fn asn_to_rust_fn2(input: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
-        let input = syn::parse2::<syn::LitStr>(input).unwrap();
-        let result = asn1rs_model::proc_macro::asn_to_rust(&input.value());
+        let mut substitutions = asn1rs_model::asn::Substitutions::default();
+        let mut heapless = false;
+        let mut footprint = false;
+        let mut encoding_docs = false;
+        let mut borrowed_views = false;
+        let mut self_test = true;
+        let mut self_test_name = None;
+        let inputs = (|input: syn::parse::ParseStream| {
+            let mut string_type = None;
+            let mut octet_string_type = None;
+            while input.peek(syn::Ident) {
+                let key = input.parse::<syn::Ident>()?;
+                input.parse::<syn::Token![=]>()?;
+                if key == "self_test" {
+                    if input.peek(syn::LitBool) {
+                        self_test = input.parse::<syn::LitBool>()?.value();
+                    } else {
+                        self_test_name = Some(input.parse::<syn::LitStr>()?.value());
+                    }
+                    input.parse::<syn::Token![,]>()?;
+                    continue;
+                }
+                if key == "heapless"
+                    || key == "footprint"
+                    || key == "encoding_docs"
+                    || key == "borrowed_views"
+                {
+                    let value = input.parse::<syn::LitBool>()?.value();
+                    if key == "heapless" {
+                        heapless = value;
+                    } else if key == "footprint" {
+                        footprint = value;
+                    } else if key == "borrowed_views" {
+                        borrowed_views = value;
+                    } else {
+                        encoding_docs = value;
+                    }
+                    input.parse::<syn::Token![,]>()?;
+                    continue;
+                }
+                let config = input.parse::<syn::LitStr>()?;
+                input.parse::<syn::Token![,]>()?;
+                if key == "string_type" {
+                    string_type = asn1rs_model::asn::StringType::from_name(&config.value());
+                } else if key == "octet_string_type" {
+                    octet_string_type =
+                        asn1rs_model::asn::OctetStringType::from_name(&config.value());
+                } else {
+                    substitutions =
+                        asn1rs_model::asn::Substitutions::parse(&config.value()).unwrap();
+                }
+            }
+            if let Some(string_type) = string_type {
+                substitutions.set_string_type(string_type);
+            }
+            if let Some(octet_string_type) = octet_string_type {
+                substitutions.set_octet_string_type(octet_string_type);
+            }
+            syn::punctuated::Punctuated::<syn::LitStr, syn::Token![,]>::parse_terminated(input)
+        })
+        .parse2(input)
+        .unwrap()
+        .into_iter()
+        .map(|l| l.value())
+        .collect::<Vec<_>>();
+        let adjust = |generator: &mut asn1rs_model::generate::rust::RustCodeGenerator| {
+            generator.set_heapless_types(heapless);
+            generator.set_footprint(footprint);
+            generator.set_encoding_docs(encoding_docs);
+            generator.set_borrowed_views(borrowed_views);
+            generator.set_self_test(self_test);
+            if let Some(name) = &self_test_name {
+                generator.set_self_test_name(name.clone());
+            }
+        };
+        let result = if let [input] = &inputs[..] {
+            asn1rs_model::proc_macro::asn_to_rust_substituted(input, &substitutions, adjust)
+        } else {
+            let inputs = inputs.iter().map(String::as_str).collect::<Vec<_>>();
+            asn1rs_model::proc_macro::asn_to_rust_modules(&inputs, &substitutions, adjust)
+        };
         TokenStream::from_str(&result).unwrap()
     }
 
@@ -0,0 +1,107 @@
+#![cfg(feature = "protobuf")]
+
+mod test_utils;
+
+use test_utils::codec::message;
+use test_utils::*;
+
+asn_to_rust!(
+    r"ProtoJson DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Named ::= SEQUENCE {
+        big-number    INTEGER,
+        display-name  UTF8String,
+        kind          Kind,
+        body          Body
+    }
+
+    Kind ::= ENUMERATED { request, response-kind }
+
+    Body ::= CHOICE {
+        empty     NULL,
+        some-flag BOOLEAN,
+        note      UTF8String
+    }
+
+    Wrapper ::= SEQUENCE OF Named
+
+    Values ::= CHOICE {
+        none    NULL,
+        numbers SEQUENCE OF INTEGER (0..255)
+    }
+
+    END"
+);
+
+fn serialize_json(value: &impl Writable) -> String {
+    let mut writer = ProtobufJsonWriter::default();
+    writer.write(value).unwrap();
+    writer.into_string()
+}
+
+#[test]
+fn test_canonical_json() {
+    assert_eq!(
+        concat!(
+            r#"{"id":42,"offset":-7,"name":"gateway","code":"DE","priority":3,"#,
+            r#""kind":"KIND_RESPONSE","flags":"oAAAAAAAAAAE","items":["1","300"],"#,
+            r#""body":{"flag":true}}"#
+        ),
+        serialize_json(&message())
+    );
+}
+
+#[test]
+fn test_names_are_camel_case() {
+    assert_eq!(
+        concat!(
+            r#"{"bigNumber":"1099511627776","displayName":"gate \"way\"","#,
+            r#""kind":"KIND_RESPONSE_KIND","body":{"someFlag":false}}"#
+        ),
+        serialize_json(&Named {
+            big_number: 1 << 40,
+            display_name: "gate \"way\"".to_string(),
+            kind: Kind::ResponseKind,
+            body: Body::SomeFlag(false),
+        })
+    );
+}
+
+#[test]
+fn test_default_values_are_omitted() {
+    assert_eq!(
+        r#"{"body":{"empty":""}}"#,
+        serialize_json(&Named {
+            big_number: 0,
+            display_name: String::new(),
+            kind: Kind::Request,
+            body: Body::Empty(Null),
+        })
+    );
+}
+
+#[test]
+fn test_repeated_messages() {
+    assert_eq!(
+        r#"{"value":[{"bigNumber":"1","body":{"note":"a"}}]}"#,
+        serialize_json(&Wrapper(vec![Named {
+            big_number: 1,
+            display_name: String::new(),
+            kind: Kind::Request,
+            body: Body::Note("a".to_string()),
+        }]))
+    );
+}
+
+#[test]
+fn test_repeated_alternative_is_a_message() {
+    assert_eq!(
+        r#"{"numbers":{"value":[1,2]}}"#,
+        serialize_json(&Values::Numbers(vec![1, 2]))
+    );
+    assert_eq!(
+        r#"{"numbers":{}}"#,
+        serialize_json(&Values::Numbers(Vec::new()))
+    );
+}
@@ -29,6 +29,17 @@ asn_to_rust!(
         B BOOLEAN,
         C UTF8String
     }
+
+    ProtobufListChoice ::= CHOICE {
+        empty NULL,
+        items SEQUENCE OF INTEGER (0..255),
+        flag  BOOLEAN
+    }
+
+    ProtobufListChoiceHolder ::= SEQUENCE {
+        body  ProtobufListChoice,
+        after INTEGER (0..255)
+    }
         
 
     END"
@@ -74,7 +85,7 @@ fn test_choice_ext_a() {
         &[8, 0, 18, 2, 8, 42, 24, 149, 6],
         &ProtobufChoiceExt {
             lone_bool: false,
-            some_choice: ProtobufChoiceExtSomeChoice::A(42),
+            some_choice: ProtobufChoiceSomeChoice::A(42),
             lone_int: 789_u64,
         },
     )
@@ -87,7 +98,7 @@ fn test_choice_ext_a_shortened() {
         deserialize_protobuf::<ProtobufChoiceExt>(&[18, 2, 8, 42, 24, 149, 6],),
         ProtobufChoiceExt {
             lone_bool: false,
-            some_choice: ProtobufChoiceExtSomeChoice::A(42),
+            some_choice: ProtobufChoiceSomeChoice::A(42),
             lone_int: 789_u64,
         },
     );
@@ -100,7 +111,7 @@ fn test_choice_ext_b() {
         &[8, 0, 18, 2, 16, 1, 24, 149, 6],
         &ProtobufChoiceExt {
             lone_bool: false,
-            some_choice: ProtobufChoiceExtSomeChoice::B(true),
+            some_choice: ProtobufChoiceSomeChoice::B(true),
             lone_int: 789_u64,
         },
     )
@@ -113,7 +124,7 @@ fn test_choice_ext_b_shortened() {
         deserialize_protobuf::<ProtobufChoiceExt>(&[18, 2, 16, 1, 24, 149, 6],),
         ProtobufChoiceExt {
             lone_bool: false,
-            some_choice: ProtobufChoiceExtSomeChoice::B(true),
+            some_choice: ProtobufChoiceSomeChoice::B(true),
             lone_int: 789_u64,
         }
     );
@@ -139,3 +150,45 @@ fn test_choice_outer_c() {
         &ProtobufOuterChoice::C("outer space".into()),
     )
 }
+
+#[test]
+#[cfg(feature = "protobuf")]
+fn test_choice_null_alternative_is_written() {
+    serialize_and_deserialize_protobuf(&[10, 0], &ProtobufListChoice::Empty(Null))
+}
+
+#[test]
+#[cfg(feature = "protobuf")]
+fn test_choice_sequence_of_alternative() {
+    serialize_and_deserialize_protobuf(&[18, 4, 8, 1, 8, 2], &ProtobufListChoice::Items(vec![1, 2]))
+}
+
+#[test]
+#[cfg(feature = "protobuf")]
+fn test_choice_empty_sequence_of_alternative() {
+    serialize_and_deserialize_protobuf(&[18, 0], &ProtobufListChoice::Items(Vec::new()))
+}
+
+#[test]
+#[cfg(feature = "protobuf")]
+fn test_choice_sequence_of_alternative_in_sequence() {
+    serialize_and_deserialize_protobuf(
+        &[10, 6, 18, 4, 8, 1, 8, 2, 16, 3],
+        &ProtobufListChoiceHolder {
+            body: ProtobufListChoice::Items(vec![1, 2]),
+            after: 3,
+        },
+    )
+}
+
+#[test]
+#[cfg(feature = "protobuf")]
+fn test_choice_null_alternative_in_sequence() {
+    serialize_and_deserialize_protobuf(
+        &[10, 2, 10, 0, 16, 3],
+        &ProtobufListChoiceHolder {
+            body: ProtobufListChoice::Empty(Null),
+            after: 3,
+        },
+    )
+}
@@ -73,7 +73,7 @@ fn test_enumeration_ext_b() {
         &[8, 0, 16, 1, 24, 217, 2],
         &ProtobufEnumExt {
             lone_bool: false,
-            some_enum: ProtobufEnumExtSomeEnum::B,
+            some_enum: ProtobufEnumSomeEnum::B,
             lone_int: 345_u64,
         },
     )
@@ -31,3 +31,27 @@ fn test_numbers() {
         },
     )
 }
+
+#[test]
+#[cfg(feature = "protobuf")]
+fn test_numbers_multi_byte_varints() {
+    for value in [
+        ProtobufNumbers {
+            should_be_sint32: i32::MIN,
+            should_be_sint64_1: -2147483649_i64,
+            should_be_sint64_2: 2147483648_i64,
+            should_be_uint32: u32::MAX,
+            should_be_uint64: 4294967296_u64,
+        },
+        ProtobufNumbers {
+            should_be_sint32: i32::MAX,
+            should_be_sint64_1: i64::from(i32::MAX),
+            should_be_sint64_2: i64::from(i32::MIN),
+            should_be_uint32: 128,
+            should_be_uint64: 16384,
+        },
+    ] {
+        let bytes = serialize_protobuf(&value);
+        assert_eq!(value, deserialize_protobuf::<ProtobufNumbers>(&bytes));
+    }
+}
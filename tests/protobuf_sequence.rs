@@ -37,7 +37,7 @@ fn test_sequence_ext() {
         &[8, 0, 18, 3, 8, 185, 10, 26, 3, 101, 120, 116],
         &ProtobufSequenceExt {
             lone_bool: false,
-            inner: ProtobufSequenceExtInner { magic_number: 1337 },
+            inner: ProtobufSequenceInner { magic_number: 1337 },
             another_string: "ext".into(),
         },
     )
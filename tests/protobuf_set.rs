@@ -37,7 +37,7 @@ fn test_set_ext() {
         &[8, 0, 18, 3, 8, 185, 10, 26, 3, 101, 120, 116],
         &ProtobufSetExt {
             lone_bool: false,
-            inner: ProtobufSetExtInner { magic_number: 1337 },
+            inner: ProtobufSetInner { magic_number: 1337 },
             another_string: "ext".into(),
         },
     )
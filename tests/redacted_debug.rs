@@ -0,0 +1,96 @@
+use asn1rs::prelude::*;
+
+asn_to_rust!(
+    r"RedactedDebug DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Vehicle ::= SEQUENCE {
+        speed INTEGER (0..255),
+        vin IA5String, -- @sensitive
+        -- @sensitive
+        position Position OPTIONAL,
+        passengers SEQUENCE OF Person
+    }
+
+    Position ::= SEQUENCE {
+        lat INTEGER,
+        lon INTEGER
+    }
+
+    Person ::= SEQUENCE {
+        name UTF8String, -- @sensitive
+        role Role
+    }
+
+    Role ::= ENUMERATED {
+        driver,
+        passenger
+    }
+
+    Occupant ::= CHOICE {
+        person Person,
+        -- @sensitive
+        nickname UTF8String,
+        unknown NULL
+    }
+
+    END"
+);
+
+fn vehicle() -> Vehicle {
+    Vehicle {
+        speed: 42,
+        vin: "WVWZZZ1JZXW000001".to_string(),
+        position: Some(Position { lat: 48, lon: 9 }),
+        passengers: vec![Person {
+            name: "Jane Doe".to_string(),
+            role: Role::Driver,
+        }],
+    }
+}
+
+#[test]
+fn test_sensitive_fields_are_masked() {
+    assert_eq!(
+        "Vehicle { speed: 42, vin: <redacted>, position: <redacted>, passengers: [Person { name: <redacted>, role: Driver }] }",
+        format!("{:?}", vehicle().redacted_debug())
+    );
+}
+
+#[test]
+fn test_debug_is_not_affected() {
+    assert!(format!("{:?}", vehicle()).contains("WVWZZZ1JZXW000001"));
+}
+
+#[test]
+fn test_choice_is_masked() {
+    assert_eq!(
+        "Person(Person { name: <redacted>, role: Passenger })",
+        format!(
+            "{:?}",
+            Occupant::Person(Person {
+                name: "John Doe".to_string(),
+                role: Role::Passenger,
+            })
+            .redacted_debug()
+        )
+    );
+    assert_eq!(
+        "Unknown(Null)",
+        format!("{:?}", Occupant::Unknown(Null).redacted_debug())
+    );
+    assert_eq!(
+        "Nickname(<redacted>)",
+        format!(
+            "{:?}",
+            Occupant::Nickname("Jo".to_string()).redacted_debug()
+        )
+    );
+}
+
+#[test]
+fn test_pretty_print() {
+    let pretty = format!("{:#?}", vehicle().redacted_debug());
+    assert!(pretty.contains("vin: <redacted>,"));
+    assert!(!pretty.contains("Jane"));
+}
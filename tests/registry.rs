@@ -0,0 +1,86 @@
+use asn1rs::prelude::*;
+use asn1rs::registry::{find, Codec};
+
+// only the modules of several inputs get a definitions() fn, as several single modules might be
+// inlined into the same scope
+asn_to_rust!(
+    r"Common DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Identifier ::= INTEGER (0..255)
+
+    END",
+    r"Messages DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    IMPORTS Identifier FROM Common;
+
+    Ping-Request ::= SEQUENCE {
+        id   Identifier,
+        mode ENUMERATED { fast, slow }
+    }
+
+    Ping-Response ::= SEQUENCE {
+        id INTEGER (0..255)
+    }
+
+    END",
+);
+
+use messages::*;
+
+fn encode_uper<T: Writable>(value: &T) -> Vec<u8> {
+    let mut writer = UperWriter::default();
+    writer.write(value).unwrap();
+    writer.into_bytes_vec()
+}
+
+#[test]
+fn test_definitions_are_listed_by_asn_name() {
+    assert_eq!(
+        vec![
+            ("Ping-Request", "PingRequest"),
+            ("Ping-Response", "PingResponse")
+        ],
+        messages::definitions()
+            .iter()
+            .map(|definition| (definition.asn_name, definition.rust_name))
+            .collect::<Vec<_>>()
+    );
+    assert!(find(messages::definitions(), "Ping-Response")
+        .unwrap()
+        .is::<PingResponse>());
+    assert!(find(messages::definitions(), "PingResponse").is_none());
+}
+
+#[test]
+fn test_decode_by_asn_name() {
+    let request = PingRequest {
+        id: common::Identifier(42),
+        mode: PingRequestMode::Slow,
+    };
+    let bytes = encode_uper(&request);
+
+    let decoded = find(messages::definitions(), "Ping-Request")
+        .unwrap()
+        .decode(Codec::Uper, &bytes)
+        .unwrap();
+    assert_eq!(Some(&request), decoded.downcast_ref::<PingRequest>());
+    assert!(decoded.downcast_ref::<PingResponse>().is_none());
+}
+
+#[test]
+fn test_definitions_of_each_module() {
+    assert_eq!(1, common::definitions().len());
+    assert!(find(common::definitions(), "Identifier")
+        .unwrap()
+        .is::<common::Identifier>());
+}
+
+#[test]
+fn test_decode_error() {
+    assert!(find(messages::definitions(), "Ping-Request")
+        .unwrap()
+        .decode(Codec::Ber, &[0x30])
+        .is_err());
+}
@@ -68,3 +68,8 @@ pub fn the_selection_ghi() {
         },
     );
 }
+
+#[test]
+pub fn test_default_uses_asn_default_value() {
+    assert_eq!(Basic::Abc, Container::default().the_selection);
+}
@@ -0,0 +1,171 @@
+mod test_utils;
+
+use asn1rs::descriptor::selftest::{round_trip, Representative, SelfTestErrorKind, HUGE_SIZE};
+use test_utils::*;
+
+asn_to_rust!(
+    r"Common DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Identifier ::= INTEGER (0..65535)
+
+    Level ::= INTEGER (-5..5, ...)
+
+    Kind ::= ENUMERATED { car, truck, ..., bike }
+
+    END",
+    r"Fleet DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    IMPORTS
+        Identifier, Level, Kind FROM Common;
+
+    Vehicle ::= SEQUENCE {
+        id       Identifier,
+        kind     Kind DEFAULT car,
+        type     UTF8String (SIZE(1..4, ...)),
+        code     NumericString (SIZE(3)),
+        plate    PrintableString OPTIONAL,
+        level    Level,
+        lights   BIT STRING (SIZE(0..12)),
+        payload  OCTET STRING,
+        weight   REAL,
+        active   BOOLEAN,
+        ...,
+        model    OBJECT IDENTIFIER OPTIONAL
+    }
+
+    Tree ::= CHOICE {
+        leaf     NULL,
+        node     SEQUENCE (SIZE(0..2)) OF Tree,
+        ...,
+        label    BMPString
+    }
+
+    Garage ::= SET {
+        spots    SET SIZE(1..3) OF Identifier,
+        vehicles SEQUENCE OF Vehicle
+    }
+
+    Empty ::= SEQUENCE { }
+
+    END",
+);
+
+#[test]
+fn test_modules_pass_their_self_test() {
+    common::self_test().unwrap();
+    fleet::self_test().unwrap();
+}
+
+mod single {
+    use super::*;
+
+    asn_to_rust!(
+        self_test = "single_self_test",
+        r"Single DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+
+        Point ::= SEQUENCE { x INTEGER (0..255), y INTEGER (0..255) }
+
+        END"
+    );
+}
+
+mod untested {
+    use super::*;
+
+    asn_to_rust!(
+        self_test = false,
+        r"Untested DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+
+        self-test ::= BOOLEAN
+
+        END"
+    );
+}
+
+#[test]
+fn test_single_module_passes_its_renamed_self_test() {
+    single::single_self_test().unwrap();
+    assert!(untested::SelfTest(true).0);
+}
+
+#[test]
+fn test_representatives_cover_the_bounds() {
+    assert_eq!(
+        vec![0, 65535],
+        common::Identifier::representatives(4)
+            .into_iter()
+            .map(|identifier| identifier.0)
+            .collect::<Vec<_>>()
+    );
+    // the values after the bounds of the extensible range
+    assert_eq!(
+        vec![-5, 5, 6, -6],
+        common::Level::representatives(4)
+            .into_iter()
+            .map(|level| level.0)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![common::Kind::Car, common::Kind::Truck, common::Kind::Bike],
+        common::Kind::representatives(4)
+    );
+}
+
+#[test]
+fn test_representatives_cover_sizes_and_extensions() {
+    let vehicles = fleet::Vehicle::representatives(4);
+    assert!(vehicles.iter().any(|vehicle| vehicle.plate.is_none()));
+    assert!(vehicles.iter().any(|vehicle| vehicle.model.is_some()));
+    // the fields with fewer representative values than the level wrap around
+    assert_eq!(
+        vec![1, 4, 5, 1],
+        vehicles
+            .iter()
+            .map(|vehicle| vehicle.type_.chars().count())
+            .collect::<Vec<_>>()
+    );
+    // an unbounded size is represented by a huge one
+    assert!(vehicles
+        .iter()
+        .any(|vehicle| vehicle.payload.len() == HUGE_SIZE as usize));
+
+    let trees = fleet::Tree::representatives(4);
+    assert!(trees
+        .iter()
+        .any(|tree| matches!(tree, fleet::Tree::Label(_))));
+    assert!(trees
+        .iter()
+        .any(|tree| matches!(tree, fleet::Tree::Node(nodes) if nodes.len() == 2)));
+    assert!(fleet::Tree::representatives(0).is_empty());
+
+    assert_eq!(vec![fleet::Empty {}], fleet::Empty::representatives(1));
+}
+
+#[asn(sequence)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reading {
+    #[asn(integer(0..10))]
+    value: u8,
+}
+
+impl Representative for Reading {
+    fn representatives(_depth: usize) -> Vec<Self> {
+        vec![Self { value: 10 }, Self { value: 11 }]
+    }
+}
+
+#[test]
+fn test_invalid_representative_is_reported() {
+    let error = round_trip::<Reading>("Reading").unwrap_err();
+    assert_eq!("Reading", error.type_name);
+    assert_eq!("UPER", error.codec);
+    assert_eq!("Reading { value: 11 }", error.value);
+    assert!(matches!(error.kind, SelfTestErrorKind::Encode(_)));
+    assert!(error
+        .to_string()
+        .starts_with("UPER of Reading failed to encode Reading { value: 11 }: "));
+}
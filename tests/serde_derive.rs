@@ -0,0 +1,114 @@
+//! The types are written like `RustCodeGenerator::set_derive_serde(true)` generates them
+
+#![cfg(feature = "serde")]
+
+use asn1rs::prelude::*;
+
+#[asn(sequence, extensible_after(id))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Report {
+    #[asn(integer(0..255))]
+    pub id: u8,
+    #[asn(optional(utf8string))]
+    pub note: Option<String>,
+    #[serde(default = "Report::retries_serde_default")]
+    #[asn(default(integer(0..9), 3))]
+    pub retries: u8,
+}
+
+impl Report {
+    fn retries_serde_default() -> u8 {
+        3
+    }
+}
+
+#[asn(sequence)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Values {
+    #[asn(bit_string())]
+    pub flags: BitVec,
+    #[asn(null)]
+    pub nothing: Null,
+    #[asn(object_identifier)]
+    pub kind: Oid,
+    #[asn(complex(State, tag(UNIVERSAL(10))))]
+    pub state: State,
+    #[asn(complex(Event, tag(4)))]
+    pub event: Event,
+}
+
+#[asn(enumerated, extensible_after(Stopped))]
+#[derive(Debug, Clone, PartialEq, Copy, serde::Serialize, serde::Deserialize)]
+pub enum State {
+    Running,
+    Stopped,
+    Paused,
+}
+
+#[asn(choice, extensible_after(Started))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum Event {
+    #[asn(integer(0..255))]
+    Started(u8),
+    #[asn(complex(Report, tag(UNIVERSAL(16))))]
+    Reported(Report),
+}
+
+fn values() -> Values {
+    Values {
+        flags: BitVec::from_bytes(vec![0b1010_0000], 3),
+        nothing: Null,
+        kind: Oid::new([1, 2, 840]),
+        state: State::Paused,
+        event: Event::Reported(Report {
+            id: 7,
+            note: Some("late".to_string()),
+            retries: 1,
+        }),
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    let json = serde_json::to_string(&values()).unwrap();
+    assert_eq!(
+        concat!(
+            r#"{"flags":[[160],3],"nothing":null,"kind":[1,2,840],"state":"Paused","#,
+            r#""event":{"Reported":{"id":7,"note":"late","retries":1}}}"#
+        ),
+        json
+    );
+    assert_eq!(values(), serde_json::from_str::<Values>(&json).unwrap());
+}
+
+#[test]
+fn test_missing_extension_additions_take_their_default() {
+    assert_eq!(
+        Report {
+            id: 9,
+            note: None,
+            retries: 3,
+        },
+        serde_json::from_str::<Report>(r#"{"id":9}"#).unwrap()
+    );
+}
+
+#[test]
+fn test_unused_bits_are_zeroed() {
+    assert_eq!(
+        BitVec::from_bytes(vec![0b1010_0000], 3),
+        serde_json::from_str::<BitVec>("[[175],3]").unwrap()
+    );
+}
+
+#[test]
+fn test_uper_after_serde_round_trip() {
+    let mut writer = UperWriter::default();
+    writer.write(&values()).unwrap();
+    let json = serde_json::to_string(&values()).unwrap();
+    let mut other = UperWriter::default();
+    other
+        .write(&serde_json::from_str::<Values>(&json).unwrap())
+        .unwrap();
+    assert_eq!(writer.byte_content(), other.byte_content());
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "stats")]
+
+mod test_utils;
+use asn1rs::stats;
+use test_utils::*;
+
+asn_to_rust!(
+    r"Stats DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Ping ::= SEQUENCE {
+        id INTEGER (0..255)
+    }
+
+    Report ::= SEQUENCE {
+        text UTF8String
+    }
+
+    Reading ::= SEQUENCE {
+        level INTEGER (0..5)
+    }
+
+    END"
+);
+
+#[test]
+fn test_uper_counts_values_and_bytes() {
+    let (bits, bytes) = serialize_uper(&Ping { id: 7 });
+    assert_eq!(8, bits);
+    let _ = deserialize_uper::<Ping>(&bytes, bits);
+    let _ = deserialize_uper::<Ping>(&bytes, bits);
+
+    let stats = stats::of::<Ping>();
+    assert_eq!(
+        (1, 0, 1),
+        (
+            stats.encoded.values,
+            stats.encoded.errors,
+            stats.encoded.bytes
+        )
+    );
+    assert_eq!(
+        (2, 0, 2),
+        (
+            stats.decoded.values,
+            stats.decoded.errors,
+            stats.decoded.bytes
+        )
+    );
+    assert_eq!(Some(stats), stats::get(core::any::type_name::<Ping>()));
+}
+
+#[test]
+fn test_ber_size_histogram() {
+    use asn1rs::prelude::basic::BER;
+
+    let mut writer = BER::writer();
+    writer
+        .write(&Report {
+            text: "ok".to_string(),
+        })
+        .unwrap();
+    writer
+        .write(&Report {
+            text: "x".repeat(100),
+        })
+        .unwrap();
+
+    let stats = stats::of::<Report>().encoded;
+    assert_eq!(2, stats.values);
+    assert_eq!(6 + 104, stats.bytes);
+    assert_eq!(
+        vec![(4..8, 1), (64..128, 1)],
+        stats
+            .sizes
+            .buckets()
+            .filter(|(_range, count)| *count > 0)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_errors_are_counted() {
+    let mut writer = UperWriter::default();
+    assert!(writer.write(&Reading { level: 9 }).is_err());
+    let mut reader = UperReader::from((&[0b1110_0000][..], 3));
+    assert!(reader.read::<Reading>().is_err());
+
+    let stats = stats::of::<Reading>();
+    assert_eq!(
+        (0, 1, 0),
+        (
+            stats.encoded.values,
+            stats.encoded.errors,
+            stats.encoded.bytes
+        )
+    );
+    assert_eq!(
+        (0, 1, 0),
+        (
+            stats.decoded.values,
+            stats.decoded.errors,
+            stats.decoded.bytes
+        )
+    );
+    assert_eq!(0, stats.encoded.sizes.count());
+    assert!(stats::snapshot().contains_key(core::any::type_name::<Reading>()));
+}
@@ -0,0 +1,175 @@
+mod test_utils;
+
+use std::sync::Arc;
+use test_utils::*;
+
+const MODULE: &str = r#"StringType DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Person ::= SEQUENCE {
+        name     UTF8String,
+        code     IA5String (SIZE(2..4)),
+        nickname PrintableString OPTIONAL,
+        aliases  SEQUENCE OF VisibleString,
+        label    UTF8String DEFAULT "none"
+    }
+
+    END"#;
+
+mod plain {
+    use asn1rs::prelude::*;
+
+    asn_to_rust!(
+        r#"StringType DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+
+        Person ::= SEQUENCE {
+            name     UTF8String,
+            code     IA5String (SIZE(2..4)),
+            nickname PrintableString OPTIONAL,
+            aliases  SEQUENCE OF VisibleString,
+            label    UTF8String DEFAULT "none"
+        }
+
+        END"#
+    );
+}
+
+mod arc {
+    use asn1rs::prelude::*;
+
+    asn_to_rust!(
+        string_type = "Arc<str>",
+        r#"StringType DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+
+        Person ::= SEQUENCE {
+            name     UTF8String,
+            code     IA5String (SIZE(2..4)),
+            nickname PrintableString OPTIONAL,
+            aliases  SEQUENCE OF VisibleString,
+            label    UTF8String DEFAULT "none"
+        }
+
+        END"#
+    );
+}
+
+mod boxed {
+    use asn1rs::prelude::*;
+
+    asn_to_rust!(
+        string_type = "Box<str>",
+        r#"StringType DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+
+        Person ::= SEQUENCE {
+            name     UTF8String,
+            code     IA5String (SIZE(2..4)),
+            nickname PrintableString OPTIONAL,
+            aliases  SEQUENCE OF VisibleString,
+            label    UTF8String DEFAULT "none"
+        }
+
+        END"#
+    );
+}
+
+fn plain_person() -> plain::Person {
+    plain::Person {
+        name: "Ada Lovelace".to_string(),
+        code: "AL".to_string(),
+        nickname: Some("Ada".to_string()),
+        aliases: vec!["Augusta".to_string(), "Countess".to_string()],
+        label: "none".to_string(),
+    }
+}
+
+#[test]
+fn test_arc_str_fields() {
+    let person = arc::Person {
+        name: Arc::from("Ada Lovelace"),
+        code: Arc::from("AL"),
+        nickname: Some(Arc::from("Ada")),
+        aliases: vec![Arc::from("Augusta"), Arc::from("Countess")],
+        label: "none".to_string(),
+    };
+    assert_eq!(serialize_uper(&plain_person()), serialize_uper(&person));
+
+    let (bits, bytes) = serialize_uper(&person);
+    let decoded = deserialize_uper::<arc::Person>(&bytes, bits);
+    assert_eq!(person, decoded);
+
+    let shared = decoded.clone();
+    assert!(Arc::ptr_eq(&decoded.name, &shared.name));
+}
+
+#[test]
+fn test_box_str_fields() {
+    let person = boxed::Person {
+        name: "Ada Lovelace".into(),
+        code: "AL".into(),
+        nickname: Some("Ada".into()),
+        aliases: vec!["Augusta".into(), "Countess".into()],
+        label: "none".to_string(),
+    };
+    assert_eq!(serialize_uper(&plain_person()), serialize_uper(&person));
+    let (bits, bytes) = serialize_uper(&person);
+    assert_eq!(person, deserialize_uper::<boxed::Person>(&bytes, bits));
+}
+
+#[test]
+fn test_size_constraints_still_apply() {
+    let mut writer = UperWriter::default();
+    let person = arc::Person {
+        code: Arc::from("TOO LONG"),
+        ..Default::default()
+    };
+    assert!(writer.write(&person).is_err());
+}
+
+#[cfg(feature = "smol_str")]
+mod smol {
+    use asn1rs::prelude::*;
+
+    asn_to_rust!(
+        string_type = "SmolStr",
+        r#"StringType DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+
+        Person ::= SEQUENCE {
+            name     UTF8String,
+            code     IA5String (SIZE(2..4)),
+            nickname PrintableString OPTIONAL,
+            aliases  SEQUENCE OF VisibleString,
+            label    UTF8String DEFAULT "none"
+        }
+
+        END"#
+    );
+}
+
+#[cfg(feature = "smol_str")]
+#[test]
+fn test_smol_str_fields() {
+    let person = smol::Person {
+        name: "Ada Lovelace".into(),
+        code: "AL".into(),
+        nickname: Some("Ada".into()),
+        aliases: vec!["Augusta".into(), "Countess".into()],
+        label: "none".to_string(),
+    };
+    assert_eq!(serialize_uper(&plain_person()), serialize_uper(&person));
+    let (bits, bytes) = serialize_uper(&person);
+    assert_eq!(person, deserialize_uper::<smol::Person>(&bytes, bits));
+}
+
+#[test]
+fn test_converter_generates_string_type() {
+    let mut converter = asn1rs::converter::Converter::default();
+    converter.load_str(MODULE).unwrap();
+    converter.set_string_type(asn1rs::model::asn::StringType::ArcStr);
+    let files = converter.to_rust_string(|_| {}).unwrap();
+    assert!(files[0].1.contains("pub name: ::std::sync::Arc<str>,"));
+    assert!(files[0].1.contains("pub label: String,"));
+}
@@ -0,0 +1,33 @@
+mod test_utils;
+
+use test_utils::*;
+
+asn_to_rust!(
+    r"StructNew DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Message ::= SEQUENCE {
+        id       INTEGER (0..255),
+        comment  UTF8String OPTIONAL,
+        priority INTEGER (0..7) DEFAULT 3,
+        name     UTF8String,
+        ...,
+        trailer  BOOLEAN
+    }
+
+    END"
+);
+
+#[test]
+fn test_new_takes_mandatory_fields_in_order() {
+    assert_eq!(
+        Message {
+            id: 42,
+            comment: None,
+            priority: 3,
+            name: "hello".to_string(),
+            trailer: None,
+        },
+        Message::new(42, "hello".to_string())
+    );
+}
@@ -0,0 +1,103 @@
+use asn1rs::prelude::*;
+
+asn_to_rust!(
+    r#"StructuredValues DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Mode ::= ENUMERATED { fast, safe }
+
+    Config ::= SEQUENCE {
+        timeout INTEGER (0..255),
+        retries INTEGER (0..10) DEFAULT 3,
+        mode    Mode,
+        name    UTF8String OPTIONAL,
+        ...,
+        verbose BOOLEAN
+    }
+
+    Server ::= SEQUENCE {
+        host IA5String,
+        port INTEGER (0..65535)
+    }
+
+    Target ::= CHOICE {
+        remote Server,
+        retry  INTEGER (0..10)
+    }
+
+    Cluster ::= SEQUENCE {
+        primary Server,
+        backups SEQUENCE OF Server
+    }
+
+    defaultConfig Config ::= { timeout 5, mode fast, verbose TRUE }
+
+    defaultPort INTEGER ::= 8080
+
+    defaultServer Server ::= { host "localhost", port defaultPort }
+
+    defaultTarget Target ::= remote : defaultServer
+
+    retryTarget Target ::= retry : 3
+
+    cluster Cluster ::= {
+        primary { host "a", port 1 },
+        backups { { host "b", port 2 }, { host "c", port 3 } }
+    }
+
+    END"#
+);
+
+// a value that does not allocate is a const
+const CONFIG: Config = DEFAULT_CONFIG;
+const RETRY: Target = RETRY_TARGET;
+
+#[test]
+fn test_sequence_value_with_absent_components() {
+    assert_eq!(
+        Config {
+            timeout: 5,
+            retries: 3,
+            mode: Mode::Fast,
+            name: None,
+            verbose: Some(true),
+        },
+        CONFIG
+    );
+}
+
+#[test]
+fn test_value_referring_to_other_values() {
+    let server = Server {
+        host: "localhost".to_string(),
+        port: 8080,
+    };
+    assert_eq!(server, *DEFAULT_SERVER);
+    assert_eq!(Target::Remote(server), *DEFAULT_TARGET);
+    assert_eq!(Target::Retry(3), RETRY);
+}
+
+#[test]
+fn test_nested_values() {
+    let server = |host: &str, port| Server {
+        host: host.to_string(),
+        port,
+    };
+    assert_eq!(
+        Cluster {
+            primary: server("a", 1),
+            backups: vec![server("b", 2), server("c", 3)],
+        },
+        *CLUSTER
+    );
+}
+
+// the only value of a SEQUENCE is its prototype, which is const if it does not allocate
+const PROTOTYPE: Config = Config::prototype();
+
+#[test]
+fn test_prototype() {
+    assert_eq!(CONFIG, PROTOTYPE);
+    assert_eq!(*DEFAULT_SERVER, Server::prototype());
+    assert_eq!(*CLUSTER, Cluster::prototype());
+}
@@ -0,0 +1,136 @@
+mod test_utils;
+
+use asn1rs::descriptor::substituted::{Substitute, SubstituteError};
+use asn1rs::protocol::per::ErrorKind;
+use test_utils::*;
+
+asn_to_rust!(
+    substitutions = r"
+        -- arrays of the standard library, converted by a local type
+        OCTET STRING (SIZE(16)) => [u8; 16] via crate::IdBytes
+        INTEGER (0..4294967295) => crate::Timestamp
+        INTEGER (0..127)        => crate::Percent",
+    r"Substitution DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Identifier ::= OCTET STRING (SIZE(16))
+
+    Event ::= SEQUENCE {
+        id       OCTET STRING (SIZE(16)),
+        parents  SEQUENCE OF OCTET STRING (SIZE(16)),
+        time     INTEGER (0..4294967295),
+        deadline INTEGER (0..4294967295) OPTIONAL,
+        progress INTEGER (0..127)
+    }
+
+    END"
+);
+
+mod plain {
+    use asn1rs::prelude::*;
+
+    asn_to_rust!(
+        r"Substitution DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+
+        Event ::= SEQUENCE {
+            id       OCTET STRING (SIZE(16)),
+            parents  SEQUENCE OF OCTET STRING (SIZE(16)),
+            time     INTEGER (0..4294967295),
+            deadline INTEGER (0..4294967295) OPTIONAL,
+            progress INTEGER (0..127)
+        }
+
+        END"
+    );
+}
+
+pub struct IdBytes;
+
+impl Substitute<[u8; 16], Vec<u8>> for IdBytes {
+    fn to_asn(value: &[u8; 16]) -> Vec<u8> {
+        value.to_vec()
+    }
+
+    fn from_asn(value: Vec<u8>) -> Result<[u8; 16], SubstituteError> {
+        <[u8; 16]>::try_from(value).map_err(|v| SubstituteError::new(format!("{v:?}")))
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Timestamp(std::time::Duration);
+
+impl Substitute<Timestamp, u32> for Timestamp {
+    fn to_asn(value: &Timestamp) -> u32 {
+        value.0.as_secs() as u32
+    }
+
+    fn from_asn(value: u32) -> Result<Timestamp, SubstituteError> {
+        Ok(Timestamp(std::time::Duration::from_secs(u64::from(value))))
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Percent(u8);
+
+impl Substitute<Percent, u8> for Percent {
+    fn to_asn(value: &Percent) -> u8 {
+        value.0
+    }
+
+    fn from_asn(value: u8) -> Result<Percent, SubstituteError> {
+        if value <= 100 {
+            Ok(Percent(value))
+        } else {
+            Err(SubstituteError::new(format!("{value} exceeds 100%")))
+        }
+    }
+}
+
+fn event() -> Event {
+    Event {
+        id: [0x11; 16],
+        parents: vec![[0x22; 16], [0x33; 16]],
+        time: Timestamp(std::time::Duration::from_secs(1_700_000_000)),
+        deadline: None,
+        progress: Percent(42),
+    }
+}
+
+fn plain_event() -> plain::Event {
+    plain::Event {
+        id: vec![0x11; 16],
+        parents: vec![vec![0x22; 16], vec![0x33; 16]],
+        time: 1_700_000_000,
+        deadline: None,
+        progress: 42,
+    }
+}
+
+#[test]
+fn test_substitutes_are_used_for_all_occurrences() {
+    let identifier: [u8; 16] = Identifier([0x44; 16]).0;
+    assert_eq!([0x44; 16], identifier);
+
+    let event = event();
+    let (bits, bytes) = serialize_uper(&event);
+    assert_eq!(event, deserialize_uper::<Event>(&bytes, bits));
+}
+
+#[test]
+fn test_substitutes_are_encoded_like_the_substituted_type() {
+    assert_eq!(serialize_uper(&plain_event()), serialize_uper(&event()));
+}
+
+#[test]
+fn test_failed_conversion_is_reported() {
+    let (bits, bytes) = serialize_uper(&plain::Event {
+        progress: 120,
+        ..plain_event()
+    });
+    let mut reader = UperReader::from((&bytes[..], bits));
+    assert_eq!(
+        &ErrorKind::SubstitutionFailed("120 exceeds 100%".to_string()),
+        reader.read::<Event>().unwrap_err().kind()
+    );
+}
@@ -0,0 +1,162 @@
+//! The schema, values and assertions that every codec is tested with, the byte-level assertions
+//! of each codec stay in its own test file.
+
+use super::*;
+use asn1rs::error;
+use asn1rs::rw::DEFAULT_MAX_DEPTH;
+use std::fmt::Debug;
+
+asn_to_rust!(
+    r"CodecFixture DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Message ::= SEQUENCE {
+        id       INTEGER (0..255),
+        offset   INTEGER (-100..100),
+        name     UTF8String (SIZE(1..8)),
+        code     IA5String,
+        payload  OCTET STRING OPTIONAL,
+        priority INTEGER (0..7) DEFAULT 3,
+        kind     Kind,
+        flags    BIT STRING (SIZE(4)),
+        items    SEQUENCE OF INTEGER,
+        body     Body,
+        ...,
+        trailer  UTF8String
+    }
+
+    Kind ::= ENUMERATED { request, response, ... }
+
+    Body ::= CHOICE {
+        empty NULL,
+        flag  BOOLEAN,
+        ...,
+        note  UTF8String
+    }
+
+    Tree ::= SEQUENCE {
+        value    INTEGER (0..255),
+        children SEQUENCE OF Tree
+    }
+
+    END"
+);
+
+/// Writes and reads values with the writer and reader of a codec, limited to the given depth
+pub trait Codec {
+    type Error: Into<asn1rs::Error> + Debug;
+
+    fn encode<T: Writable>(value: &T, max_depth: usize) -> Result<Vec<u8>, Self::Error>;
+
+    /// Fails if not all of the bytes are read
+    fn decode<T: Readable>(bytes: &[u8], max_depth: usize) -> Result<T, Self::Error>;
+}
+
+pub fn serialize<C: Codec>(value: &impl Writable) -> Vec<u8> {
+    C::encode(value, DEFAULT_MAX_DEPTH).unwrap()
+}
+
+pub fn deserialize<C: Codec, T: Readable>(bytes: &[u8]) -> Result<T, C::Error> {
+    C::decode(bytes, DEFAULT_MAX_DEPTH)
+}
+
+pub fn message() -> Message {
+    Message {
+        id: 42,
+        offset: -7,
+        name: "gateway".to_string(),
+        code: "DE".to_string(),
+        payload: None,
+        priority: 3,
+        kind: Kind::Response,
+        flags: BitVec::from_bytes(vec![0b1010_0000], 4),
+        items: vec![1, 300],
+        body: Body::Flag(true),
+        trailer: None,
+    }
+}
+
+/// A tree with one node of value zero per level, `levels` deep
+pub fn deep(levels: usize) -> Tree {
+    (1..levels).fold(Tree::new(0, Vec::new()), |child, _| {
+        Tree::new(0, vec![child])
+    })
+}
+
+/// Drops the tree level by level, as the recursive drop of very deep trees overflows the stack
+pub fn dismantle(mut tree: Tree) {
+    while let Some(child) = tree.children.pop() {
+        tree = child;
+    }
+}
+
+fn kind<C: Codec>(error: C::Error) -> error::Kind {
+    error.into().kind()
+}
+
+pub fn assert_round_trip<C: Codec>() {
+    for message in [
+        message(),
+        Message {
+            payload: Some(vec![1, 2, 3]),
+            priority: 0,
+            body: Body::Empty(Null),
+            trailer: Some("end".to_string()),
+            ..message()
+        },
+        Message {
+            body: Body::Note("extension alternative".to_string()),
+            items: Vec::new(),
+            ..message()
+        },
+    ] {
+        let bytes = serialize::<C>(&message);
+        assert_eq!(message, deserialize::<C, Message>(&bytes).unwrap());
+    }
+}
+
+pub fn assert_constraints_are_checked<C: Codec>() {
+    for message in [
+        Message {
+            offset: 101,
+            ..message()
+        },
+        Message {
+            name: "too long for it".to_string(),
+            ..message()
+        },
+    ] {
+        let error = C::encode(&message, DEFAULT_MAX_DEPTH).unwrap_err();
+        assert_eq!(error::Kind::ConstraintViolation, kind::<C>(error));
+    }
+}
+
+pub fn assert_truncated_input_is_rejected<C: Codec>() {
+    let bytes = serialize::<C>(&Message {
+        payload: Some(vec![1, 2, 3]),
+        trailer: Some("end".to_string()),
+        ..message()
+    });
+    for len in 0..bytes.len() {
+        assert!(deserialize::<C, Message>(&bytes[..len]).is_err());
+    }
+}
+
+pub fn assert_nesting_depth_is_limited<C: Codec>() {
+    // each level is a SEQUENCE and a SEQUENCE OF
+    let bytes = C::encode(&deep(5), 10).unwrap();
+    assert_eq!(deep(5), C::decode::<Tree>(&bytes, 10).unwrap());
+    let error = C::encode(&deep(6), 10).unwrap_err();
+    assert_eq!(error::Kind::LimitExceeded, kind::<C>(error));
+
+    let bytes = C::encode(&deep(DEFAULT_MAX_DEPTH), 2 * DEFAULT_MAX_DEPTH).unwrap();
+    let error = deserialize::<C, Tree>(&bytes).unwrap_err();
+    assert_eq!(error::Kind::LimitExceeded, kind::<C>(error));
+    let tree = C::decode::<Tree>(&bytes, 2 * DEFAULT_MAX_DEPTH).unwrap();
+    assert_eq!(deep(DEFAULT_MAX_DEPTH), tree);
+
+    let tree = deep(20_000);
+    let error = C::encode(&tree, DEFAULT_MAX_DEPTH).unwrap_err();
+    assert_eq!(error::Kind::LimitExceeded, kind::<C>(error));
+    dismantle(tree);
+}
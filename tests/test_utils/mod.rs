@@ -1,5 +1,7 @@
 #![allow(unused)]
 
+pub mod codec;
+
 use asn1rs::prelude::basic::DER;
 pub use asn1rs::prelude::*;
 
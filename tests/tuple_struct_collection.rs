@@ -0,0 +1,39 @@
+use asn1rs::prelude::*;
+
+asn_to_rust!(
+    r"TupleStructCollection DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Ones ::= SEQUENCE SIZE(0..10) OF INTEGER (0..255)
+    Names ::= SET OF UTF8String
+
+    END"
+);
+
+#[test]
+fn test_into_iterator() {
+    let mut ones = Ones(vec![1, 2, 3]);
+    assert_eq!(6, (&ones).into_iter().copied().sum::<u8>());
+    for one in &mut ones {
+        *one *= 2;
+    }
+    assert_eq!(vec![2, 4, 6], ones.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_from_iterator_and_extend() {
+    let mut names = ["a", "b"]
+        .into_iter()
+        .map(str::to_string)
+        .collect::<Names>();
+    names.extend(["c".to_string()]);
+    assert_eq!(Names(vec!["a".into(), "b".into(), "c".into()]), names);
+}
+
+#[test]
+fn test_index() {
+    let mut ones = (1..=4).collect::<Ones>();
+    ones[0] = 10;
+    assert_eq!(10, ones[0]);
+    assert_eq!(&[2, 3][..], &ones[1..3]);
+}
@@ -0,0 +1,44 @@
+use asn1rs::prelude::*;
+
+asn_to_rust!(
+    r"TupleStructFrom DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Speed ::= INTEGER (0..255)
+    Name ::= UTF8String (SIZE(1..32))
+    Code ::= IA5String
+    Payload ::= OCTET STRING
+    Valid ::= BOOLEAN
+    Names ::= SEQUENCE OF Name
+
+    END"
+);
+
+fn parse_speed(text: &str) -> Result<Speed, std::num::ParseIntError> {
+    Ok(text.parse::<u8>()?.into())
+}
+
+#[test]
+fn test_from_inner() {
+    assert_eq!(Speed(42), Speed::from(42));
+    assert_eq!(Ok(Speed(42)), parse_speed("42"));
+    assert_eq!(Valid(true), true.into());
+    assert_eq!(
+        Names(vec![Name("a".to_string())]),
+        vec![Name::from("a")].into()
+    );
+}
+
+#[test]
+fn test_into_inner() {
+    assert_eq!(42_u8, Speed(42).into());
+    assert_eq!("abc".to_string(), String::from(Name::from("abc")));
+    assert_eq!(vec![1_u8, 2], Vec::<u8>::from(Payload(vec![1, 2])));
+}
+
+#[test]
+fn test_from_borrowed() {
+    assert_eq!(Name("abc".to_string()), Name::from("abc"));
+    assert_eq!(Code("X1".to_string()), "X1".into());
+    assert_eq!(Payload(vec![1, 2]), Payload::from(&[1_u8, 2][..]));
+}
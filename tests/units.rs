@@ -0,0 +1,71 @@
+mod test_utils;
+
+use asn1rs::descriptor::numbers::Constraint;
+use test_utils::*;
+
+asn_to_rust!(
+    r"BasicUnits DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Position ::= SEQUENCE {
+        lat INTEGER (-900000000..900000001), -- @unit 0,1 microdegree
+        -- @unit m
+        alt INTEGER (-1000..8000) OPTIONAL,
+        valid BOOLEAN
+    }
+
+    END"
+);
+
+#[asn(sequence)]
+#[derive(Default, Debug, Clone, PartialEq, Hash)]
+pub struct Speed {
+    #[asn(integer(0..16383), unit("0.01 m/s"))]
+    pub value: u16,
+}
+
+#[test]
+fn test_unit_constants() {
+    assert_eq!("0,1 microdegree", Position::LAT_UNIT);
+    assert_eq!("m", Position::ALT_UNIT);
+}
+
+#[test]
+fn test_unit_in_descriptor() {
+    assert_eq!(
+        Some("0,1 microdegree"),
+        ___asn1rs_PositionFieldLatConstraint::UNIT
+    );
+    assert_eq!(Some("m"), ___asn1rs_PositionFieldAltConstraint::UNIT);
+    assert_eq!(Some("0.01 m/s"), ___asn1rs_SpeedFieldValueConstraint::UNIT);
+}
+
+#[test]
+fn test_physical_value() {
+    assert_eq!(
+        Some((4.5, "m/s")),
+        ___asn1rs_SpeedFieldValueConstraint::physical(450_u16)
+    );
+    let (lat, unit) = ___asn1rs_PositionFieldLatConstraint::physical(523_456_789_i32).unwrap();
+    assert!((lat - 52_345_678.9).abs() < 1e-6);
+    assert_eq!("microdegree", unit);
+}
+
+#[test]
+fn test_scaled_value_helpers() {
+    let position = Position {
+        lat: 523_456_789,
+        alt: Some(42),
+        valid: true,
+    };
+    assert!((position.lat_microdegree() - 52_345_678.9).abs() < 1e-6);
+    assert_eq!(Some(42.0), position.alt_m());
+    assert_eq!(
+        None,
+        Position {
+            alt: None,
+            ..position
+        }
+        .alt_m()
+    );
+}
@@ -0,0 +1,192 @@
+mod test_utils;
+
+use asn1rs::descriptor::{boolean, octetstring, utf8string};
+use asn1rs::protocol::per::ErrorKind;
+use std::borrow::Cow;
+use test_utils::*;
+
+asn_to_rust!(
+    borrowed_views = true,
+    r"UperBorrowed DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Message ::= SEQUENCE {
+        payload OCTET STRING,
+        text    UTF8String
+    }
+
+    Flagged ::= SEQUENCE {
+        flag    BOOLEAN,
+        payload OCTET STRING
+    }
+
+    Record ::= SEQUENCE {
+        id      INTEGER (0..255),
+        type    UTF8String,
+        note    UTF8String OPTIONAL,
+        ...,
+        blob    OCTET STRING OPTIONAL
+    }
+
+    Label ::= UTF8String
+
+    Count ::= SEQUENCE {
+        value   INTEGER
+    }
+
+    END"
+);
+
+#[test]
+fn test_aligned_strings_are_borrowed() {
+    let message = Message {
+        payload: vec![0xCA, 0xFE, 0xBA, 0xBE],
+        text: "Grüße".to_string(),
+    };
+    let (bits, bytes) = serialize_uper(&message);
+    let mut reader = UperReader::from((&bytes[..], bits));
+
+    let payload = reader
+        .read_octet_string_borrowed::<octetstring::NoConstraint>()
+        .unwrap();
+    let text = reader
+        .read_utf8string_borrowed::<utf8string::NoConstraint>()
+        .unwrap();
+
+    assert!(matches!(payload, Cow::Borrowed(_)));
+    assert!(matches!(text, Cow::Borrowed(_)));
+    assert_eq!(&message.payload[..], &payload[..]);
+    assert_eq!(message.text, text);
+    assert_eq!(0, reader.bits_remaining());
+}
+
+#[test]
+fn test_unaligned_strings_are_copied() {
+    let flagged = Flagged {
+        flag: true,
+        payload: vec![0xCA, 0xFE],
+    };
+    let (bits, bytes) = serialize_uper(&flagged);
+    let mut reader = UperReader::from((&bytes[..], bits));
+
+    assert!(reader.read_boolean::<boolean::NoConstraint>().unwrap());
+    let payload = reader
+        .read_octet_string_borrowed::<octetstring::NoConstraint>()
+        .unwrap();
+
+    assert_eq!(Cow::<[u8]>::Owned(flagged.payload), payload);
+    assert_eq!(0, reader.bits_remaining());
+}
+
+#[test]
+fn test_fragmented_strings_are_copied() {
+    let message = Message {
+        payload: vec![0x42; 16384],
+        text: String::new(),
+    };
+    let (bits, bytes) = serialize_uper(&message);
+    let mut reader = UperReader::from((&bytes[..], bits));
+
+    let payload = reader
+        .read_octet_string_borrowed::<octetstring::NoConstraint>()
+        .unwrap();
+    let text = reader
+        .read_utf8string_borrowed::<utf8string::NoConstraint>()
+        .unwrap();
+
+    assert_eq!(Cow::<[u8]>::Owned(message.payload), payload);
+    assert_eq!(Cow::Borrowed(""), text);
+    assert_eq!(0, reader.bits_remaining());
+}
+
+#[test]
+fn test_invalid_utf8_is_rejected() {
+    let bytes = [0x02, 0xC3, 0x28];
+    let mut reader = UperReader::from((&bytes[..], bytes.len() * 8));
+    assert!(matches!(
+        reader
+            .read_utf8string_borrowed::<utf8string::NoConstraint>()
+            .unwrap_err()
+            .kind(),
+        ErrorKind::FromUtf8Error(_)
+    ));
+}
+
+#[test]
+fn test_generated_views_borrow_their_strings() {
+    let message = Message {
+        payload: vec![0xCA, 0xFE, 0xBA, 0xBE],
+        text: "Grüße".to_string(),
+    };
+    let (bits, bytes) = serialize_uper(&message);
+    let mut reader = UperReader::from((&bytes[..], bits));
+
+    let view = MessageRef::read_uper(&mut reader).unwrap();
+    assert!(matches!(view.payload, Cow::Borrowed(_)));
+    assert!(matches!(view.text, Cow::Borrowed(_)));
+    assert_eq!(view, message);
+    assert_eq!(message, view);
+    assert_eq!(0, reader.bits_remaining());
+}
+
+#[test]
+fn test_generated_views_read_optional_and_extended_fields() {
+    for record in [
+        Record {
+            id: 7,
+            type_: "kind".to_string(),
+            note: Some("a note".to_string()),
+            blob: Some(vec![0x01, 0x02, 0x03]),
+        },
+        Record {
+            id: 8,
+            type_: String::new(),
+            note: None,
+            blob: None,
+        },
+    ] {
+        let (bits, bytes) = serialize_uper(&record);
+        let mut reader = UperReader::from((&bytes[..], bits));
+        let view = RecordRef::read_uper(&mut reader).unwrap();
+        assert_eq!(record.id, view.id);
+        assert_eq!(view, record);
+        assert_eq!(0, reader.bits_remaining());
+    }
+}
+
+#[test]
+fn test_generated_views_of_wrapping_types() {
+    let label = Label("Grüße".to_string());
+    let (bits, bytes) = serialize_uper(&label);
+    let mut reader = UperReader::from((&bytes[..], bits));
+
+    let view = LabelRef::read_uper(&mut reader).unwrap();
+    assert!(matches!(view.0, Cow::Borrowed("Grüße")));
+    assert_eq!(label, view);
+}
+
+#[test]
+fn test_generated_views_differ_from_other_values() {
+    let message = Message {
+        payload: vec![0xCA, 0xFE],
+        text: "text".to_string(),
+    };
+    let view = MessageRef {
+        payload: Cow::Borrowed(&[0xCA, 0xFE]),
+        text: Cow::Borrowed("other"),
+    };
+    assert_ne!(view, message);
+    assert_ne!(message, view);
+}
+
+#[test]
+fn test_generated_views_validate_the_trailing_padding() {
+    let (bits, mut bytes) = serialize_uper(&Label("x".to_string()));
+    bytes.push(0x00);
+    let mut reader = UperReader::from((&bytes[..], bits + 8));
+    reader.set_validate_trailing_padding(true);
+    assert!(matches!(
+        LabelRef::read_uper(&mut reader).unwrap_err().kind(),
+        ErrorKind::TrailingBits(..)
+    ));
+}
@@ -0,0 +1,91 @@
+mod test_utils;
+
+use asn1rs::protocol::per::ErrorKind;
+use test_utils::*;
+
+asn_to_rust!(
+    r"UperFragments DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Header ::= SEQUENCE {
+        version INTEGER (0..7),
+        urgent  BOOLEAN
+    }
+
+    Body ::= SEQUENCE {
+        id      INTEGER (0..1023),
+        payload OCTET STRING (SIZE(0..4))
+    }
+
+    END"
+);
+
+fn header() -> Header {
+    Header {
+        version: 5,
+        urgent: true,
+    }
+}
+
+fn body() -> Body {
+    Body {
+        id: 1000,
+        payload: vec![0xCA, 0xFE],
+    }
+}
+
+fn fragment<T: Writable>(value: &T) -> (Vec<u8>, usize) {
+    let mut writer = UperWriter::default();
+    writer.write(value).unwrap();
+    writer.into_bytes_with_bit_len()
+}
+
+#[test]
+fn test_exact_bit_len() {
+    assert_eq!((vec![0b1011_0000], 4), fragment(&header()));
+    let (bytes, bit_len) = fragment(&body());
+    assert_eq!(serialize_uper(&body()), (bit_len, bytes));
+}
+
+#[test]
+fn test_fragments_are_packed_without_padding() {
+    let mut packed = UperWriter::default();
+    for (bytes, bit_len) in [fragment(&header()), fragment(&body())] {
+        packed.append_bits(&bytes, bit_len).unwrap();
+    }
+    let (bytes, bit_len) = packed.into_bytes_with_bit_len();
+
+    let mut written = UperWriter::default();
+    written.write(&header()).unwrap();
+    written.write(&body()).unwrap();
+    assert_eq!(written.into_bytes_with_bit_len(), (bytes.clone(), bit_len));
+
+    let mut reader = UperReader::from((&bytes[..], bit_len));
+    assert_eq!(header(), reader.read::<Header>().unwrap());
+    assert_eq!(body(), reader.read::<Body>().unwrap());
+    assert_eq!(0, reader.bits_remaining());
+}
+
+#[test]
+fn test_aligned_fragments() {
+    let mut packed = UperWriter::default();
+    let (bytes, bit_len) = fragment(&header());
+    packed.append_bits(&bytes, bit_len).unwrap();
+    assert_eq!(4, packed.align_to_octet().unwrap());
+    assert_eq!(0, packed.align_to_octet().unwrap());
+    packed.write(&header()).unwrap();
+    assert_eq!(
+        (vec![0b1011_0000, 0b1011_0000], 12),
+        packed.into_bytes_with_bit_len()
+    );
+}
+
+#[test]
+fn test_append_beyond_the_bytes() {
+    let mut writer = UperWriter::default();
+    assert!(matches!(
+        writer.append_bits(&[0xFF], 9).unwrap_err().kind(),
+        ErrorKind::InsufficientDataInSourceBuffer(_)
+    ));
+    assert_eq!((Vec::new(), 0), writer.into_bytes_with_bit_len());
+}
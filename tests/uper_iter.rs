@@ -0,0 +1,85 @@
+mod test_utils;
+
+use asn1rs::protocol::per::ErrorKind;
+use test_utils::*;
+
+asn_to_rust!(
+    r"UperIter DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Message ::= SEQUENCE {
+        id   INTEGER (0..255),
+        flag BOOLEAN
+    }
+
+    Empty ::= NULL
+
+    END"
+);
+
+fn concatenated(messages: &[Message]) -> Vec<u8> {
+    messages
+        .iter()
+        .flat_map(|message| serialize_uper(message).1)
+        .collect()
+}
+
+#[test]
+fn test_iter_decodes_all_messages() {
+    let messages = vec![
+        Message { id: 1, flag: true },
+        Message { id: 2, flag: false },
+        Message {
+            id: 255,
+            flag: true,
+        },
+    ];
+    let bytes = concatenated(&messages);
+    assert_eq!(6, bytes.len());
+
+    let mut reader = UperReader::from((&bytes[..], bytes.len() * 8));
+    assert_eq!(
+        messages,
+        reader
+            .iter::<Message>()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    );
+    assert_eq!(48, reader.consumed_bits());
+}
+
+#[test]
+fn test_iter_of_empty_buffer_is_empty() {
+    let mut reader = UperReader::from((&[][..], 0));
+    assert_eq!(0, reader.iter::<Message>().count());
+}
+
+#[test]
+fn test_iter_of_empty_encodings() {
+    let mut reader = UperReader::from((&[0x00, 0x00][..], 16));
+    assert_eq!(2, reader.iter::<Empty>().filter(Result::is_ok).count());
+}
+
+#[test]
+fn test_iter_stops_after_the_first_error() {
+    // the padding of the second message is not zero
+    let bytes = [0x01, 0x80, 0x02, 0x01, 0x03, 0x00];
+    let mut reader = UperReader::from((&bytes[..], bytes.len() * 8));
+    let mut iter = reader.iter::<Message>();
+    assert_eq!(Some(Ok(Message { id: 1, flag: true })), iter.next());
+    assert_eq!(
+        &ErrorKind::TrailingBits(9, 23),
+        iter.next().unwrap().unwrap_err().kind()
+    );
+    assert_eq!(None, iter.next());
+}
+
+#[test]
+fn test_iter_reports_truncated_message() {
+    let bytes = [0x01, 0x80, 0x02];
+    let mut reader = UperReader::from((&bytes[..], bytes.len() * 8));
+    let results = reader.iter::<Message>().collect::<Vec<_>>();
+    assert_eq!(2, results.len());
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+}
@@ -0,0 +1,91 @@
+mod test_utils;
+
+use asn1rs::descriptor::{boolean, octetstring};
+use test_utils::*;
+
+asn_to_rust!(
+    r"UperLengthPrefixed DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Extensible ::= SEQUENCE {
+        flag    BOOLEAN,
+        ...,
+        content OCTET STRING OPTIONAL
+    }
+
+    END"
+);
+
+/// Writes `prefix_bits` single bits and `len` octets as open type, once with the backpatching
+/// helper and once with a temporary writer, as the helper is supposed to replace it
+fn check_against_temporary_writer(prefix_bits: usize, len: usize) {
+    let content = (0..len).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+    let write_content = |w: &mut UperWriter| {
+        for byte in &content {
+            for bit in 0..8 {
+                w.write_boolean::<boolean::NoConstraint>(byte & (0x80 >> bit) != 0)?;
+            }
+        }
+        Ok(())
+    };
+
+    let mut patched = UperWriter::default();
+    let mut expected = UperWriter::default();
+
+    for i in 0..prefix_bits {
+        patched
+            .write_boolean::<boolean::NoConstraint>(i % 2 == 0)
+            .unwrap();
+        expected
+            .write_boolean::<boolean::NoConstraint>(i % 2 == 0)
+            .unwrap();
+    }
+
+    patched.write_length_prefixed(write_content).unwrap();
+
+    let mut temporary = UperWriter::default();
+    write_content(&mut temporary).unwrap();
+    let octets = temporary.into_bytes_vec();
+    expected
+        .write_octet_string::<octetstring::NoConstraint>(&octets)
+        .unwrap();
+
+    assert_eq!(expected.bit_len(), patched.bit_len(), "len={len}");
+    assert_eq!(expected.byte_content(), patched.byte_content(), "len={len}");
+}
+
+#[test]
+fn test_short_form() {
+    for prefix_bits in 0..8 {
+        check_against_temporary_writer(prefix_bits, 0);
+        check_against_temporary_writer(prefix_bits, 1);
+        check_against_temporary_writer(prefix_bits, 127);
+    }
+}
+
+#[test]
+fn test_long_form_moves_content() {
+    for prefix_bits in 0..8 {
+        check_against_temporary_writer(prefix_bits, 128);
+        check_against_temporary_writer(prefix_bits, 1000);
+        check_against_temporary_writer(prefix_bits, 16_383);
+    }
+}
+
+#[test]
+fn test_fragmented() {
+    check_against_temporary_writer(3, 16_384);
+    check_against_temporary_writer(5, 40_000);
+}
+
+#[test]
+fn test_extension_field_roundtrip() {
+    for len in [0, 10, 127, 128, 16_380, 16_381] {
+        let value = Extensible {
+            flag: true,
+            content: Some(vec![0x42; len]),
+        };
+        let (bits, bytes) = serialize_uper(&value);
+        assert_eq!(value, deserialize_uper::<Extensible>(&bytes, bits));
+    }
+}
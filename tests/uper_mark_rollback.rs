@@ -0,0 +1,170 @@
+mod test_utils;
+
+use test_utils::*;
+
+asn_to_rust!(
+    r"UperMarkRollback DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Header ::= SEQUENCE {
+        version INTEGER (0..15),
+        kind    ENUMERATED { ping, pong }
+    }
+
+    Ping ::= SEQUENCE {
+        header  Header,
+        payload UTF8String
+    }
+
+    Pong ::= SEQUENCE {
+        header  Header,
+        latency INTEGER (0..65535)
+    }
+
+    END"
+);
+
+#[derive(Debug, PartialEq)]
+enum Message {
+    Ping(Ping),
+    Pong(Pong),
+}
+
+/// Reads the message that the kind in the common header announces, from any reader that can
+/// look ahead
+fn read_message<R: PositionedReader>(reader: &mut R) -> Result<Message, R::Error> {
+    match reader.lookahead(|r| r.read::<Header>())?.kind {
+        HeaderKind::Ping => reader.read::<Ping>().map(Message::Ping),
+        HeaderKind::Pong => reader.read::<Pong>().map(Message::Pong),
+    }
+}
+
+/// Writes the value only if it fits into the given number of bits, with any writer that can
+/// roll back
+fn write_within<W: PositionedWriter, T: Writable>(
+    writer: &mut W,
+    value: &T,
+    max_bits: usize,
+) -> Result<bool, W::Error> {
+    let start = writer.bit_position();
+    let mark = writer.mark();
+    writer.write(value)?;
+    if writer.bit_position() - start > max_bits {
+        writer.rollback(mark);
+        Ok(false)
+    } else {
+        Ok(true)
+    }
+}
+
+fn ping() -> Ping {
+    Ping {
+        header: Header {
+            version: 3,
+            kind: HeaderKind::Ping,
+        },
+        payload: "hello".to_string(),
+    }
+}
+
+#[test]
+fn test_reader_positions() {
+    let (bits, bytes) = serialize_uper(&ping());
+    let mut reader = UperReader::from((&bytes[..], bits));
+    assert_eq!(0, reader.bit_position());
+    assert_eq!(bits, reader.remaining_bits());
+
+    // version 3 as 4 bits: 0011
+    assert!(!reader.peek_bit().unwrap());
+    assert_eq!(0, reader.bit_position());
+
+    let header = reader.read::<Header>().unwrap();
+    assert_eq!(ping().header, header);
+    assert_eq!(5, reader.bit_position());
+    assert_eq!(bits - 5, reader.remaining_bits());
+}
+
+#[test]
+fn test_reader_lookahead_dispatches_on_header() {
+    let (bits, bytes) = serialize_uper(&ping());
+    let mut reader = UperReader::from((&bytes[..], bits));
+
+    let header = reader.lookahead(|r| r.read::<Header>()).unwrap();
+    assert_eq!(0, reader.bit_position());
+    assert_eq!(HeaderKind::Ping, header.kind);
+    assert_eq!(ping(), reader.read::<Ping>().unwrap());
+    assert_eq!(0, reader.bits_remaining());
+}
+
+#[test]
+fn test_reader_rollback_reads_again() {
+    let (bits, bytes) = serialize_uper(&ping());
+    let mut reader = UperReader::from((&bytes[..], bits));
+
+    let mark = reader.mark();
+    assert!(reader
+        .read::<Pong>()
+        .is_ok_and(|pong| pong.header == ping().header));
+    assert_ne!(0, reader.bit_position());
+    reader.rollback(mark.clone());
+    assert_eq!(mark.bit_position(), reader.bit_position());
+    assert_eq!(ping(), reader.read::<Ping>().unwrap());
+}
+
+#[test]
+fn test_writer_rollback_discards_content() {
+    let pong = Header {
+        version: 15,
+        kind: HeaderKind::Pong,
+    };
+
+    let mut writer = UperWriter::default();
+    writer.write(&ping().header).unwrap();
+    assert_eq!(5, writer.bit_position());
+
+    let mark = writer.mark();
+    writer.write(&ping()).unwrap();
+    writer.rollback(mark);
+    assert_eq!(5, writer.bit_position());
+    writer.write(&pong).unwrap();
+
+    let mut expected = UperWriter::default();
+    expected.write(&ping().header).unwrap();
+    expected.write(&pong).unwrap();
+
+    assert_eq!(expected.bit_len(), writer.bit_len());
+    assert_eq!(expected.byte_content(), writer.byte_content());
+}
+
+#[test]
+fn test_generic_reader_dispatches_on_header() {
+    let pong = Pong {
+        header: Header {
+            version: 1,
+            kind: HeaderKind::Pong,
+        },
+        latency: 42,
+    };
+    for message in [Message::Ping(ping()), Message::Pong(pong)] {
+        let (bits, bytes) = match &message {
+            Message::Ping(ping) => serialize_uper(ping),
+            Message::Pong(pong) => serialize_uper(pong),
+        };
+        let mut reader = UperReader::from((&bytes[..], bits));
+        assert_eq!(message, read_message(&mut reader).unwrap());
+        assert_eq!(0, reader.remaining_bits());
+    }
+}
+
+#[test]
+fn test_generic_writer_rolls_back() {
+    let mut writer = UperWriter::default();
+    assert!(write_within(&mut writer, &ping().header, 5).unwrap());
+    assert!(!write_within(&mut writer, &ping(), 8).unwrap());
+    assert_eq!(5, writer.bit_position());
+    assert!(write_within(&mut writer, &ping(), 64).unwrap());
+    assert_eq!(
+        ping().header,
+        deserialize_uper::<Header>(writer.byte_content(), 5)
+    );
+}
@@ -0,0 +1,161 @@
+mod test_utils;
+
+use test_utils::*;
+
+asn_to_rust!(
+    r"UperMutation DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Message ::= SEQUENCE {
+        id      INTEGER (0..255),
+        payload OCTET STRING,
+        ...,
+        trailer OCTET STRING OPTIONAL
+    }
+
+    Items ::= SEQUENCE (SIZE(1..4)) OF INTEGER (0..7)
+
+    END"
+);
+
+fn message() -> Message {
+    Message {
+        id: 42,
+        payload: vec![1, 2, 3],
+        trailer: None,
+    }
+}
+
+fn read_bits(bytes: &[u8], bits: std::ops::Range<usize>) -> u64 {
+    bits.fold(0, |value, bit| {
+        (value << 1) | u64::from(bytes[bit / 8] & (0x80 >> (bit % 8)) != 0)
+    })
+}
+
+#[test]
+fn test_landmarks_of_extensible_sequence() {
+    let mutator = UperMutator::new(&message()).unwrap();
+    assert_eq!(
+        serialize_uper(&message()),
+        (mutator.bit_len(), mutator.bytes().to_vec())
+    );
+    assert_eq!(
+        &[
+            Landmark::ExtensionBit(0),
+            Landmark::LengthDeterminant {
+                bits: 9..17,
+                constrained: false,
+            },
+        ],
+        mutator.landmarks()
+    );
+}
+
+#[test]
+fn test_landmarks_within_extension_fields() {
+    let message = Message {
+        trailer: Some(vec![0xAB; 5]),
+        ..message()
+    };
+    let mutator = UperMutator::new(&message).unwrap();
+    let lengths = mutator
+        .landmarks()
+        .iter()
+        .filter_map(|landmark| match landmark {
+            Landmark::LengthDeterminant { bits, .. } => {
+                Some(read_bits(mutator.bytes(), bits.clone()))
+            }
+            Landmark::ExtensionBit(_) => None,
+        })
+        .collect::<Vec<_>>();
+
+    // payload, open type field of the trailer and the trailer itself
+    assert_eq!(vec![3, 6, 5], lengths);
+}
+
+#[test]
+fn test_landmarks_of_sequence_of() {
+    let mutator = UperMutator::new(&Items(vec![1, 2, 3])).unwrap();
+    assert_eq!(
+        &[Landmark::LengthDeterminant {
+            bits: 0..2,
+            constrained: true,
+        }],
+        mutator.landmarks()
+    );
+}
+
+#[test]
+fn test_mutants_are_rejected() {
+    let mutator = UperMutator::new(&message()).unwrap();
+    let mut labels = Vec::new();
+
+    for mutant in mutator.corpus() {
+        let mut reader = UperReader::from((&mutant.bytes[..], mutant.bit_len));
+        let result = reader.read::<Message>();
+        if !matches!(mutant.mutation, Mutation::FlipExtensionBit(_)) {
+            assert!(result.is_err(), "{} was accepted", mutant.mutation);
+        }
+        labels.push(mutant.mutation.to_string());
+    }
+
+    assert_eq!(
+        vec![
+            "flip-extension-bit@0",
+            "overflow-length-determinant@9..17",
+            "truncate@0",
+            "truncate@8",
+            "truncate@9",
+            "truncate@16",
+            "truncate@24",
+            "truncate@32",
+            "truncate@40",
+        ],
+        labels
+    );
+}
+
+#[test]
+fn test_overflow_keeps_unconstrained_form() {
+    let mutator = UperMutator::new(&message()).unwrap();
+    let mutant = mutator.mutate(Mutation::OverflowLengthDeterminant {
+        bits: 9..17,
+        constrained: false,
+    });
+    assert_eq!(127, read_bits(&mutant.bytes, 9..17));
+    assert_eq!(mutator.bit_len(), mutant.bit_len);
+}
+
+#[test]
+fn test_truncate_clears_trailing_bits() {
+    let mutator = UperMutator::new(&message()).unwrap();
+    let mutant = mutator.mutate(Mutation::Truncate(12));
+    assert_eq!(12, mutant.bit_len);
+    assert_eq!(2, mutant.bytes.len());
+    assert_eq!(0, mutant.bytes[1] & 0x0F);
+}
+
+#[test]
+fn test_bit_len_beyond_the_bytes_is_limited() {
+    assert_eq!((Vec::new(), 0), Mutation::FlipExtensionBit(0).apply(&[], 8));
+    assert_eq!(
+        (vec![0xFF], 8),
+        Mutation::OverflowLengthDeterminant {
+            bits: 0..16,
+            constrained: true,
+        }
+        .apply(&[0x00], 16)
+    );
+    assert_eq!((vec![0x12], 8), Mutation::Truncate(12).apply(&[0x12], 16));
+}
+
+#[test]
+fn test_random_is_reproducible() {
+    let mutator = UperMutator::new(&message()).unwrap();
+    let first = mutator.random(1337, 32);
+    assert_eq!(32, first.len());
+    assert_eq!(first, mutator.random(1337, 32));
+    assert!(first
+        .iter()
+        .all(|mutant| mutant.bit_len <= mutator.bit_len()));
+}
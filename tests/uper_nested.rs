@@ -0,0 +1,124 @@
+mod test_utils;
+
+use asn1rs::descriptor::common;
+use asn1rs::descriptor::numbers;
+use asn1rs::model::asn::Tag;
+use asn1rs::protocol::per::Error;
+use test_utils::*;
+
+asn_to_rust!(
+    r"UperNested DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Header ::= SEQUENCE {
+        version INTEGER (0..7),
+        urgent  BOOLEAN
+    }
+
+    Body ::= SEQUENCE {
+        id      INTEGER (0..1023),
+        payload OCTET STRING (SIZE(0..4)),
+        ...
+    }
+
+    END"
+);
+
+struct Count;
+impl common::Constraint for Count {
+    const TAG: Tag = Tag::DEFAULT_INTEGER;
+}
+impl numbers::Constraint<u8> for Count {
+    const MIN: Option<i64> = Some(0);
+    const MAX: Option<i64> = Some(7);
+    const MIN_T: Option<u8> = Some(0);
+    const MAX_T: Option<u8> = Some(7);
+}
+
+/// A custom container: a header followed by a 3-bit count of bodies, not an ASN.1 type itself
+#[derive(Debug, PartialEq)]
+struct Envelope {
+    header: Header,
+    bodies: Vec<Body>,
+}
+
+impl Envelope {
+    fn write(&self, writer: &mut UperWriter) -> Result<usize, Error> {
+        let mut bits = writer.write_nested(&self.header)?;
+        writer.write_number::<u8, Count>(self.bodies.len() as u8)?;
+        bits += 3;
+        for body in &self.bodies {
+            bits += writer.write_nested(body)?;
+        }
+        Ok(bits)
+    }
+
+    fn read(reader: &mut UperReader<Bits>) -> Result<Self, Error> {
+        let header = reader.read_nested::<Header>()?;
+        let count = reader.read_number::<u8, Count>()?;
+        let bodies = (0..count)
+            .map(|_| reader.read_nested::<Body>())
+            .collect::<Result<_, _>>()?;
+        Ok(Self { header, bodies })
+    }
+}
+
+fn envelope() -> Envelope {
+    Envelope {
+        header: Header {
+            version: 5,
+            urgent: true,
+        },
+        bodies: vec![
+            Body {
+                id: 1000,
+                payload: vec![0xCA, 0xFE],
+            },
+            Body {
+                id: 7,
+                payload: Vec::new(),
+            },
+        ],
+    }
+}
+
+#[test]
+fn test_nested_values_are_not_aligned() {
+    let envelope = envelope();
+    let mut writer = UperWriter::default();
+    let bits = envelope.write(&mut writer).unwrap();
+
+    let parts = serialize_uper(&envelope.header).0
+        + 3
+        + envelope
+            .bodies
+            .iter()
+            .map(|body| serialize_uper(body).0)
+            .sum::<usize>();
+    assert_eq!(parts, bits);
+    assert_eq!(bits, writer.bit_len());
+}
+
+#[test]
+fn test_nested_values_are_read_back() {
+    let envelope = envelope();
+    let mut writer = UperWriter::default();
+    envelope.write(&mut writer).unwrap();
+
+    let mut reader = writer.as_reader();
+    assert_eq!(envelope, Envelope::read(&mut reader).unwrap());
+    assert_eq!(0, reader.bits_remaining());
+}
+
+#[test]
+fn test_nested_values_are_read_back_with_padding_validation() {
+    let envelope = envelope();
+    let mut writer = UperWriter::default();
+    envelope.write(&mut writer).unwrap();
+
+    let mut reader = writer.as_reader();
+    reader.set_validate_trailing_padding(true);
+    assert_eq!(envelope, Envelope::read(&mut reader).unwrap());
+    assert!(reader.validates_trailing_padding());
+    assert_eq!(0, reader.bits_remaining());
+}
@@ -0,0 +1,93 @@
+mod test_utils;
+
+use asn1rs::protocol::per::ErrorKind;
+use asn1rs::rw::DEFAULT_MAX_DEPTH;
+use test_utils::*;
+
+asn_to_rust!(
+    r"UperNestingDepth DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Tree ::= SEQUENCE {
+        value    INTEGER (0..255),
+        children SEQUENCE OF Tree
+    }
+
+    END"
+);
+
+/// A tree with one node per level, `levels` deep
+fn deep(levels: usize) -> Tree {
+    (1..levels).fold(Tree::new(0, Vec::new()), |child, level| {
+        Tree::new((level % 256) as u8, vec![child])
+    })
+}
+
+/// Drops the tree level by level, as the recursive drop of very deep trees overflows the stack
+fn dismantle(mut tree: Tree) {
+    while let Some(child) = tree.children.pop() {
+        tree = child;
+    }
+}
+
+fn encoded(tree: &Tree) -> (Vec<u8>, usize) {
+    let mut writer = UperWriter::default();
+    writer.set_max_depth(usize::MAX);
+    writer.write(tree).unwrap();
+    let bits = writer.bit_len();
+    (writer.into_bytes_vec(), bits)
+}
+
+#[test]
+fn test_shallow_values_are_not_limited() {
+    let tree = deep(DEFAULT_MAX_DEPTH / 2);
+    let (bytes, bits) = encoded(&tree);
+
+    let mut writer = UperWriter::default();
+    writer.write(&tree).unwrap();
+    assert_eq!(&bytes[..], writer.byte_content());
+
+    let mut reader = UperReader::from((&bytes[..], bits));
+    assert_eq!(tree, reader.read::<Tree>().unwrap());
+}
+
+#[test]
+fn test_writing_too_deep_values_fails() {
+    let mut writer = UperWriter::default();
+    writer.set_max_depth(10);
+    // each level is a SEQUENCE and a SEQUENCE OF
+    assert!(writer.write(&deep(5)).is_ok());
+
+    let mut writer = UperWriter::default();
+    writer.set_max_depth(10);
+    assert_eq!(
+        &ErrorKind::NestingDepthExceedsLimit(10),
+        writer.write(&deep(6)).unwrap_err().kind()
+    );
+}
+
+#[test]
+fn test_reading_too_deep_values_fails() {
+    let (bytes, bits) = encoded(&deep(DEFAULT_MAX_DEPTH));
+
+    let mut reader = UperReader::from((&bytes[..], bits));
+    assert_eq!(
+        &ErrorKind::NestingDepthExceedsLimit(DEFAULT_MAX_DEPTH),
+        reader.read::<Tree>().unwrap_err().kind()
+    );
+
+    let mut reader = UperReader::from((&bytes[..], bits));
+    reader.set_max_depth(2 * DEFAULT_MAX_DEPTH);
+    assert_eq!(deep(DEFAULT_MAX_DEPTH), reader.read::<Tree>().unwrap());
+}
+
+#[test]
+fn test_writing_very_deep_values_fails_without_overflowing_the_stack() {
+    let tree = deep(20_000);
+    let mut writer = UperWriter::default();
+    assert_eq!(
+        &ErrorKind::NestingDepthExceedsLimit(DEFAULT_MAX_DEPTH),
+        writer.write(&tree).unwrap_err().kind()
+    );
+    dismantle(tree);
+}
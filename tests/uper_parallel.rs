@@ -0,0 +1,111 @@
+#![cfg(feature = "rayon")]
+
+mod test_utils;
+
+use asn1rs::descriptor::sequenceof;
+use test_utils::*;
+
+asn_to_rust!(
+    r"UperParallel DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Record ::= SEQUENCE {
+        id      INTEGER (0..4095),
+        flag    BOOLEAN,
+        comment UTF8String OPTIONAL
+    }
+
+    Records ::= SEQUENCE OF Record
+
+    Label ::= SEQUENCE {
+        text VisibleString
+    }
+
+    Labels ::= SEQUENCE OF Label
+
+    END"
+);
+
+fn records(count: usize) -> Vec<Record> {
+    (0..count)
+        .map(|i| Record {
+            id: (i % 4096) as u16,
+            flag: i % 3 == 0,
+            comment: (i % 5 == 0).then(|| format!("record {i}")),
+        })
+        .collect()
+}
+
+#[test]
+fn test_parallel_encoding_equals_sequential_encoding() {
+    for count in [0, 1, 7, 1000, 10_000] {
+        let records = records(count);
+        let (bits, bytes) = serialize_uper(&Records(records.clone()));
+
+        let mut writer = UperWriter::default();
+        writer
+            .write_sequence_of_par::<sequenceof::NoConstraint, _>(&records)
+            .unwrap();
+
+        assert_eq!(bits, writer.bit_len(), "{count} records");
+        assert_eq!(bytes, writer.into_bytes_vec(), "{count} records");
+    }
+}
+
+#[test]
+fn test_parallel_encoding_is_readable() {
+    let records = records(2000);
+    let mut writer = UperWriter::default();
+    writer
+        .write_sequence_of_par::<sequenceof::NoConstraint, _>(&records)
+        .unwrap();
+    assert_eq!(records, writer.as_reader().read::<Records>().unwrap().0);
+}
+
+#[test]
+fn test_parallel_encoding_records_landmarks_sequentially() {
+    let records = records(100);
+    let mut expected = UperWriter::with_landmarks();
+    expected.write(&Records(records.clone())).unwrap();
+
+    let mut writer = UperWriter::with_landmarks();
+    writer
+        .write_sequence_of_par::<sequenceof::NoConstraint, _>(&records)
+        .unwrap();
+
+    assert_eq!(expected.byte_content(), writer.byte_content());
+    assert_eq!(expected.landmarks(), writer.landmarks());
+}
+
+#[test]
+fn test_parallel_encoding_uses_the_substitute_of_the_writer() {
+    let labels = vec![
+        Label {
+            text: "Grün".to_string()
+        };
+        100
+    ];
+    let mut writer = UperWriter::default();
+    writer.set_substitute(Some('?'));
+    writer
+        .write_sequence_of_par::<sequenceof::NoConstraint, _>(&labels)
+        .unwrap();
+    assert!(writer
+        .as_reader()
+        .read::<Labels>()
+        .unwrap()
+        .0
+        .iter()
+        .all(|label| label.text == "Gr?n"));
+}
+
+#[test]
+fn test_parallel_encoding_counts_the_nesting_depth() {
+    // the SEQUENCE OF and each SEQUENCE element
+    for (max_depth, ok) in [(1, false), (2, true)] {
+        let mut writer = UperWriter::default();
+        writer.set_max_depth(max_depth);
+        let result = writer.write_sequence_of_par::<sequenceof::NoConstraint, _>(&records(10));
+        assert_eq!(ok, result.is_ok(), "max depth {max_depth}");
+    }
+}
@@ -0,0 +1,136 @@
+mod test_utils;
+
+use test_utils::*;
+
+asn_to_rust!(
+    r"UperPresenceBitmap DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Inner ::= SEQUENCE {
+        a BOOLEAN OPTIONAL,
+        b INTEGER (0..7) DEFAULT 3
+    }
+
+    Outer ::= SEQUENCE {
+        first  Inner OPTIONAL,
+        flag   BOOLEAN,
+        second Inner,
+        third  UTF8String OPTIONAL
+    }
+
+    Wide ::= SEQUENCE {
+        f0 BOOLEAN OPTIONAL,
+        f1 BOOLEAN OPTIONAL,
+        f2 BOOLEAN OPTIONAL,
+        f3 BOOLEAN OPTIONAL,
+        f4 BOOLEAN OPTIONAL,
+        f5 BOOLEAN OPTIONAL,
+        f6 BOOLEAN OPTIONAL,
+        f7 BOOLEAN OPTIONAL,
+        f8 BOOLEAN OPTIONAL,
+        f9 BOOLEAN OPTIONAL,
+        f10 BOOLEAN OPTIONAL,
+        f11 BOOLEAN OPTIONAL,
+        f12 BOOLEAN OPTIONAL,
+        f13 BOOLEAN OPTIONAL,
+        f14 BOOLEAN OPTIONAL,
+        f15 BOOLEAN OPTIONAL,
+        f16 BOOLEAN OPTIONAL,
+        f17 BOOLEAN OPTIONAL,
+        f18 BOOLEAN OPTIONAL,
+        f19 BOOLEAN OPTIONAL,
+        f20 BOOLEAN OPTIONAL,
+        f21 BOOLEAN OPTIONAL,
+        f22 BOOLEAN OPTIONAL,
+        f23 BOOLEAN OPTIONAL,
+        f24 BOOLEAN OPTIONAL,
+        f25 BOOLEAN OPTIONAL,
+        f26 BOOLEAN OPTIONAL,
+        f27 BOOLEAN OPTIONAL,
+        f28 BOOLEAN OPTIONAL,
+        f29 BOOLEAN OPTIONAL,
+        f30 BOOLEAN OPTIONAL,
+        f31 BOOLEAN OPTIONAL,
+        f32 BOOLEAN OPTIONAL,
+        f33 BOOLEAN OPTIONAL,
+        f34 BOOLEAN OPTIONAL,
+        f35 BOOLEAN OPTIONAL,
+        f36 BOOLEAN OPTIONAL,
+        f37 BOOLEAN OPTIONAL,
+        f38 BOOLEAN OPTIONAL,
+        f39 BOOLEAN OPTIONAL,
+        f40 BOOLEAN OPTIONAL,
+        f41 BOOLEAN OPTIONAL,
+        f42 BOOLEAN OPTIONAL,
+        f43 BOOLEAN OPTIONAL,
+        f44 BOOLEAN OPTIONAL,
+        f45 BOOLEAN OPTIONAL,
+        f46 BOOLEAN OPTIONAL,
+        f47 BOOLEAN OPTIONAL,
+        f48 BOOLEAN OPTIONAL,
+        f49 BOOLEAN OPTIONAL,
+        f50 BOOLEAN OPTIONAL,
+        f51 BOOLEAN OPTIONAL,
+        f52 BOOLEAN OPTIONAL,
+        f53 BOOLEAN OPTIONAL,
+        f54 BOOLEAN OPTIONAL,
+        f55 BOOLEAN OPTIONAL,
+        f56 BOOLEAN OPTIONAL,
+        f57 BOOLEAN OPTIONAL,
+        f58 BOOLEAN OPTIONAL,
+        f59 BOOLEAN OPTIONAL,
+        f60 BOOLEAN OPTIONAL,
+        f61 BOOLEAN OPTIONAL,
+        f62 BOOLEAN OPTIONAL,
+        f63 BOOLEAN OPTIONAL,
+        f64 BOOLEAN OPTIONAL
+    }
+
+    END"
+);
+
+fn round_trip<T: Readable + Writable + std::fmt::Debug + PartialEq>(value: &T) {
+    let (bits, bytes) = serialize_uper(value);
+    assert_eq!(value, &deserialize_uper::<T>(&bytes, bits));
+}
+
+#[test]
+fn test_presence_of_nested_sequences() {
+    let inner = |a, b| Inner { a, b };
+    for first in [None, Some(inner(None, 3)), Some(inner(Some(true), 5))] {
+        for third in [None, Some("x".to_string())] {
+            round_trip(&Outer {
+                first: first.clone(),
+                flag: true,
+                second: inner(Some(false), 7),
+                third,
+            });
+        }
+    }
+}
+
+#[test]
+fn test_presence_bits_are_read_in_order() {
+    // presence of first and third, flag, presence of a and b, a and the empty length of third
+    let (bits, bytes) = serialize_uper(&Outer {
+        first: None,
+        flag: true,
+        second: Inner {
+            a: Some(true),
+            b: 3,
+        },
+        third: Some(String::new()),
+    });
+    assert_eq!(&[0b0111_0100, 0b0000_0000], &bytes[..]);
+    assert_eq!(2 + 1 + 2 + 1 + 8, bits);
+}
+
+#[test]
+fn test_more_than_64_optional_fields() {
+    round_trip(&Wide {
+        f0: Some(true),
+        f63: Some(false),
+        f64: Some(true),
+        ..Default::default()
+    });
+}
@@ -0,0 +1,184 @@
+#![cfg(feature = "serde")]
+
+mod test_utils;
+
+use asn1rs::model::asn::Asn;
+use asn1rs::model::parse::Tokenizer;
+use asn1rs::model::Model;
+use test_utils::*;
+
+asn_to_rust!(
+    r"UperSerde DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Message ::= SEQUENCE {
+        id       INTEGER (0..255),
+        offset   INTEGER (-100..100),
+        name     UTF8String,
+        code     IA5String (SIZE(2)),
+        payload  OCTET STRING OPTIONAL,
+        priority INTEGER (0..7) DEFAULT 3,
+        kind     Kind,
+        flags    BIT STRING (SIZE(4)),
+        items    SEQUENCE OF INTEGER,
+        body     Body,
+        ...,
+        trailer  UTF8String
+    }
+
+    Kind ::= ENUMERATED { request, response, ... }
+
+    Body ::= CHOICE {
+        empty NULL,
+        text  UTF8String,
+        ...,
+        flag  BOOLEAN
+    }
+
+    END"
+);
+
+const SCHEMA: &str = r"UperSerde DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Message ::= SEQUENCE {
+        id       INTEGER (0..255),
+        offset   INTEGER (-100..100),
+        name     UTF8String,
+        code     IA5String (SIZE(2)),
+        payload  OCTET STRING OPTIONAL,
+        priority INTEGER (0..7) DEFAULT 3,
+        kind     Kind,
+        flags    BIT STRING (SIZE(4)),
+        items    SEQUENCE OF INTEGER,
+        body     Body,
+        ...,
+        trailer  UTF8String
+    }
+
+    Kind ::= ENUMERATED { request, response, ... }
+
+    Body ::= CHOICE {
+        empty NULL,
+        text  UTF8String,
+        ...,
+        flag  BOOLEAN
+    }
+
+    END";
+
+fn model() -> Model<Asn> {
+    Model::try_from(Tokenizer.parse(SCHEMA))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+}
+
+fn message() -> Message {
+    Message {
+        id: 42,
+        offset: -7,
+        name: "gateway".to_string(),
+        code: "DE".to_string(),
+        payload: None,
+        priority: 3,
+        kind: Kind::Response,
+        flags: BitVec::from_bytes(vec![0b1010_0000], 4),
+        items: vec![1, 300],
+        body: Body::Text("hello".to_string()),
+        trailer: None,
+    }
+}
+
+fn transcode(value: &impl Writable, name: &str) -> String {
+    let model = model();
+    let (bit_len, bytes) = serialize_uper(value);
+    let transcoder = UperTranscoder::new(&model, name, &bytes)
+        .unwrap()
+        .with_bit_len(bit_len);
+    serde_json::to_string(&transcoder).unwrap()
+}
+
+#[test]
+fn test_sequence_to_json() {
+    assert_eq!(
+        r#"{"id":42,"offset":-7,"name":"gateway","code":"DE","payload":null,"priority":3,"kind":"response","flags":[[160],4],"items":[1,300],"body":{"text":"hello"},"trailer":null}"#,
+        transcode(&message(), "Message")
+    );
+}
+
+#[test]
+fn test_present_optional_default_and_extension() {
+    let message = Message {
+        payload: Some(vec![1, 2]),
+        priority: 6,
+        body: Body::Flag(true),
+        trailer: Some("end".to_string()),
+        ..message()
+    };
+    assert_eq!(
+        r#"{"id":42,"offset":-7,"name":"gateway","code":"DE","payload":[1,2],"priority":6,"kind":"response","flags":[[160],4],"items":[1,300],"body":{"flag":true},"trailer":"end"}"#,
+        transcode(&message, "Message")
+    );
+}
+
+#[test]
+fn test_referenced_types_on_their_own() {
+    assert_eq!(r#""request""#, transcode(&Kind::Request, "Kind"));
+    assert_eq!(r#"{"empty":null}"#, transcode(&Body::Empty(Null), "Body"));
+}
+
+#[test]
+fn test_unknown_definition_and_decode_errors() {
+    let model = model();
+    assert!(UperTranscoder::new(&model, "Unknown", &[]).is_none());
+
+    let (bit_len, bytes) = serialize_uper(&message());
+    let truncated = UperTranscoder::new(&model, "Message", &bytes)
+        .unwrap()
+        .with_bit_len(bit_len / 2);
+    assert!(serde_json::to_string(&truncated).is_err());
+}
+
+const TREE_SCHEMA: &str = r"UperSerdeTree DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Tree ::= SEQUENCE {
+        value    INTEGER (0..255),
+        children SEQUENCE OF Tree
+    }
+
+    END";
+
+/// The encoding of a `Tree` that is `levels` deep: the value and a length of one element on
+/// each level, but the last
+fn crafted_deep_tree(levels: usize) -> Vec<u8> {
+    let mut bytes = [0x00, 0x01].repeat(levels - 1);
+    bytes.extend_from_slice(&[0x00, 0x00]);
+    bytes
+}
+
+#[test]
+fn test_nesting_depth_is_limited() {
+    let model = Model::try_from(Tokenizer.parse(TREE_SCHEMA))
+        .unwrap()
+        .try_resolve()
+        .unwrap();
+
+    let bytes = crafted_deep_tree(2);
+    let transcoder = UperTranscoder::new(&model, "Tree", &bytes).unwrap();
+    assert_eq!(
+        r#"{"value":0,"children":[{"value":0,"children":[]}]}"#,
+        serde_json::to_string(&transcoder).unwrap()
+    );
+    // each level is a SEQUENCE and a SEQUENCE OF
+    assert!(serde_json::to_string(&transcoder.with_max_depth(4)).is_ok());
+    assert!(serde_json::to_string(&transcoder.with_max_depth(3)).is_err());
+
+    let bytes = crafted_deep_tree(100_000);
+    let transcoder = UperTranscoder::new(&model, "Tree", &bytes).unwrap();
+    let error = serde_json::to_string(&transcoder).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("nested deeper than the limit of 128 levels"));
+}
@@ -0,0 +1,87 @@
+mod test_utils;
+
+use test_utils::*;
+
+asn_to_rust!(
+    r"UperSizeHint DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Kind ::= ENUMERATED { a, b, c, ... }
+
+    Value ::= CHOICE {
+        number  INTEGER,
+        text    UTF8String,
+        ratio   REAL,
+        ...,
+        flag    BOOLEAN
+    }
+
+    Record ::= SEQUENCE {
+        id      INTEGER (0..65535),
+        kind    Kind,
+        name    IA5String (SIZE(1..32)),
+        values  SEQUENCE OF Value,
+        payload OCTET STRING,
+        bits    BIT STRING,
+        oid     OBJECT IDENTIFIER OPTIONAL,
+        ...,
+        note    UTF8String OPTIONAL
+    }
+
+    END"
+);
+
+fn record(payload_len: usize) -> Record {
+    Record {
+        id: 65535,
+        kind: Kind::C,
+        name: "record".to_string(),
+        values: vec![
+            Value::Number(u64::MAX),
+            Value::Text("Grüße".to_string()),
+            Value::Ratio(core::f64::consts::PI),
+            Value::Flag(true),
+        ],
+        payload: vec![0x42; payload_len],
+        bits: BitVec::from_bytes(vec![0xCA, 0xFE], 13),
+        oid: Some(Oid::new(vec![1, 3, 6, 1, 4, 1, u64::MAX])),
+        note: Some("extended".to_string()),
+    }
+}
+
+fn assert_hint_covers(record: &Record) {
+    let (bits, _bytes) = serialize_uper(record);
+    let hint = UperSizeHint::of(record);
+    assert!(bits <= hint.bits(), "{bits} > {}", hint.bits());
+    assert_eq!(hint.bits().div_ceil(8), hint.bytes());
+}
+
+#[test]
+fn test_hint_covers_the_encoding() {
+    assert_hint_covers(&record(0));
+    assert_hint_covers(&record(300));
+}
+
+#[test]
+fn test_hint_covers_fragmented_encodings() {
+    assert_hint_covers(&record(16 * 1024));
+    assert_hint_covers(&record(70 * 1024 + 3));
+}
+
+#[test]
+fn test_hint_is_close_for_large_payloads() {
+    let record = record(64 * 1024);
+    let (bits, _bytes) = serialize_uper(&record);
+    assert!(UperSizeHint::of(&record).bits() - bits < 1024);
+}
+
+#[test]
+fn test_writer_encodes_as_before() {
+    let record = record(1024);
+    let mut writer = UperWriter::default();
+    writer.write(&record).unwrap();
+    writer.write(&record).unwrap();
+    let (bits, bytes) = serialize_uper(&record);
+    assert_eq!(2 * bits, writer.bit_len());
+    assert_eq!(record, deserialize_uper(&bytes, bits));
+}
@@ -0,0 +1,185 @@
+mod test_utils;
+
+use asn1rs::error::{CodecError, Kind};
+use asn1rs::protocol::per::ErrorKind;
+use std::cell::Cell;
+use std::io::{Cursor, Read};
+use test_utils::*;
+
+asn_to_rust!(
+    r"UperStream DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Chunk ::= SEQUENCE {
+        id      INTEGER (0..65535),
+        name    UTF8String,
+        payload OCTET STRING,
+        ...
+    }
+
+    Flag ::= BOOLEAN
+
+    Blob ::= OCTET STRING
+
+    END"
+);
+
+fn chunks() -> Vec<Chunk> {
+    [0, 3, 16 * 1024, 70_000, 12]
+        .into_iter()
+        .enumerate()
+        .map(|(id, len)| Chunk {
+            id: id as u16,
+            name: format!("chunk-{id}"),
+            payload: (0..len).map(|i| i as u8).collect(),
+        })
+        .collect()
+}
+
+fn encoded<T: Writable>(values: &[T]) -> Vec<u8> {
+    let mut writer = UperWriter::default();
+    for value in values {
+        writer.write(value).unwrap();
+        writer.align_to_octet().unwrap();
+    }
+    writer.into_bytes_vec()
+}
+
+/// Provides at most the given number of octets per read and fails every other read with
+/// `WouldBlock`, like a non-blocking socket
+struct Trickle {
+    bytes: Cursor<Vec<u8>>,
+    per_read: usize,
+    blocked: bool,
+}
+
+impl Read for Trickle {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.blocked = !self.blocked;
+        if self.blocked {
+            return Err(std::io::ErrorKind::WouldBlock.into());
+        }
+        let len = buf.len().min(self.per_read);
+        self.bytes.read(&mut buf[..len])
+    }
+}
+
+#[test]
+fn test_reads_all_pdus() {
+    let chunks = chunks();
+    let mut reader = UperStreamReader::new(Cursor::new(encoded(&chunks)));
+    for chunk in &chunks {
+        assert_eq!(Some(chunk), reader.read::<Chunk>().unwrap().as_ref());
+    }
+    assert_eq!(None, reader.read::<Chunk>().unwrap());
+    assert!(reader.buffered().is_empty());
+}
+
+#[test]
+fn test_resumes_after_would_block() {
+    let chunks = chunks();
+    let mut reader = UperStreamReader::new(Trickle {
+        bytes: Cursor::new(encoded(&chunks)),
+        per_read: 4093,
+        blocked: false,
+    });
+    let mut read = Vec::new();
+    loop {
+        match reader.read::<Chunk>() {
+            Ok(Some(chunk)) => read.push(chunk),
+            Ok(None) => break,
+            Err(e) => assert_eq!(Kind::Io, e.kind()),
+        }
+    }
+    assert_eq!(chunks, read);
+}
+
+thread_local! {
+    static ATTEMPTS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A [`Blob`] that counts the attempts to decode it
+#[derive(Debug, PartialEq)]
+struct CountedBlob(Blob);
+
+impl Readable for CountedBlob {
+    fn read<R: Reader>(reader: &mut R) -> Result<Self, R::Error> {
+        ATTEMPTS.with(|attempts| attempts.set(attempts.get() + 1));
+        Blob::read(reader).map(CountedBlob)
+    }
+}
+
+#[test]
+fn test_attempts_are_bounded_by_the_length_determinants() {
+    // three fragments of 64K octets and the final length determinant
+    let blob = Blob((0..200_000).map(|i| i as u8).collect());
+    let mut reader = UperStreamReader::new(Trickle {
+        bytes: Cursor::new(encoded(std::slice::from_ref(&blob))),
+        per_read: 7,
+        blocked: false,
+    });
+    let read = loop {
+        match reader.read::<CountedBlob>() {
+            Ok(read) => break read,
+            Err(e) => assert_eq!(Kind::Io, e.kind()),
+        }
+    };
+    assert_eq!(Some(CountedBlob(blob)), read);
+    assert!(
+        ATTEMPTS.with(Cell::get) <= 6,
+        "{} attempts",
+        ATTEMPTS.with(Cell::get)
+    );
+}
+
+#[test]
+fn test_pdus_shorter_than_an_octet() {
+    let flags = [Flag(true), Flag(false), Flag(true)];
+    let mut reader = UperStreamReader::new(Cursor::new(encoded(&flags)));
+    for flag in &flags {
+        assert_eq!(Some(flag), reader.read::<Flag>().unwrap().as_ref());
+    }
+    assert_eq!(None, reader.read::<Flag>().unwrap());
+}
+
+#[test]
+fn test_truncated_pdu_is_end_of_data() {
+    let mut bytes = encoded(&chunks()[..3]);
+    bytes.truncate(bytes.len() - 100);
+    let mut reader = UperStreamReader::new(Cursor::new(bytes));
+    assert!(reader.read::<Chunk>().unwrap().is_some());
+    assert!(reader.read::<Chunk>().unwrap().is_some());
+    assert_eq!(Kind::EndOfData, reader.read::<Chunk>().unwrap_err().kind());
+}
+
+#[test]
+fn test_pdu_longer_than_the_limit() {
+    let chunks = chunks();
+    let mut reader = UperStreamReader::new(Cursor::new(encoded(&chunks))).with_max_pdu_len(1024);
+    assert_eq!(1024, reader.max_pdu_len());
+    assert_eq!(Some(&chunks[0]), reader.read::<Chunk>().unwrap().as_ref());
+    assert_eq!(Some(&chunks[1]), reader.read::<Chunk>().unwrap().as_ref());
+    assert_eq!(
+        Kind::LimitExceeded,
+        reader.read::<Chunk>().unwrap_err().kind()
+    );
+}
+
+#[test]
+fn test_never_ending_pdu_is_not_buffered_without_bounds() {
+    // every 0xC4 is the length determinant of another fragment of 64K octets of 0xC4
+    let mut reader = UperStreamReader::new(std::io::repeat(0xC4)).with_max_pdu_len(300_000);
+    let error = reader.read::<Blob>().unwrap_err();
+    assert_eq!(Kind::LimitExceeded, error.kind());
+    assert!(matches!(
+        error.codec(),
+        CodecError::Uper(e) if e.kind() == &ErrorKind::PduExceedsLimit(300_000)
+    ));
+    assert!(reader.buffered().len() <= 300_000);
+}
+
+#[test]
+fn test_empty_source() {
+    let mut reader = UperStreamReader::new(std::io::empty());
+    assert_eq!(None, reader.read::<Chunk>().unwrap());
+}
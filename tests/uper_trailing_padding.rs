@@ -0,0 +1,77 @@
+mod test_utils;
+
+use asn1rs::protocol::per::ErrorKind;
+use test_utils::*;
+
+asn_to_rust!(
+    r"UperTrailingPadding DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Message ::= SEQUENCE {
+        id   INTEGER (0..255),
+        flag BOOLEAN
+    }
+
+    Empty ::= NULL
+
+    END"
+);
+
+fn validating_reader(bytes: &[u8]) -> UperReader<Bits<'_>> {
+    let mut reader = UperReader::from((bytes, bytes.len() * 8));
+    reader.set_validate_trailing_padding(true);
+    reader
+}
+
+#[test]
+fn test_zero_padding_in_final_octet_is_consumed() {
+    let mut reader = validating_reader(&[0x2A, 0x80]);
+    assert_eq!(
+        Message { id: 42, flag: true },
+        reader.read::<Message>().unwrap()
+    );
+    assert_eq!(16, reader.consumed_bits());
+    assert_eq!(0, reader.bits_remaining());
+}
+
+#[test]
+fn test_set_bits_in_padding_are_rejected() {
+    let mut reader = validating_reader(&[0x2A, 0x81]);
+    assert_eq!(
+        &ErrorKind::TrailingBits(9, 7),
+        reader.read::<Message>().unwrap_err().kind()
+    );
+}
+
+#[test]
+fn test_concatenated_messages_are_rejected() {
+    let mut reader = validating_reader(&[0x2A, 0x80, 0x2A, 0x80]);
+    assert_eq!(
+        &ErrorKind::TrailingBits(9, 23),
+        reader.read::<Message>().unwrap_err().kind()
+    );
+}
+
+#[test]
+fn test_truncated_message_is_rejected() {
+    let mut reader = validating_reader(&[0x2A]);
+    assert!(reader.read::<Message>().is_err());
+}
+
+#[test]
+fn test_empty_encoding_is_a_single_zero_octet() {
+    let mut reader = validating_reader(&[0x00]);
+    assert_eq!(Empty(Null), reader.read::<Empty>().unwrap());
+    assert_eq!(8, reader.consumed_bits());
+}
+
+#[test]
+fn test_trailing_bits_are_ignored_by_default() {
+    let mut reader = UperReader::from((&[0x2A, 0x81, 0xFF][..], 24));
+    assert!(!reader.validates_trailing_padding());
+    assert_eq!(
+        Message { id: 42, flag: true },
+        reader.read::<Message>().unwrap()
+    );
+    assert_eq!(9, reader.consumed_bits());
+}
@@ -0,0 +1,103 @@
+mod test_utils;
+use test_utils::*;
+
+asn_to_rust!(
+    r"UperTruncatedExtensions DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Header ::= SEQUENCE {
+        id INTEGER (0..255),
+        ...,
+        extra BOOLEAN OPTIONAL
+    }
+
+    Trailer ::= SEQUENCE {
+        version INTEGER (1..1),
+        ...,
+        note BOOLEAN OPTIONAL
+    }
+
+    Message ::= SEQUENCE {
+        header Header,
+        trailer Trailer
+    }
+
+    END"
+);
+
+fn message() -> Message {
+    Message {
+        header: Header { id: 5, extra: None },
+        trailer: Trailer {
+            version: 1,
+            note: None,
+        },
+    }
+}
+
+fn tolerant_reader(bytes: &[u8], bit_len: usize) -> UperReader<Bits<'_>> {
+    let mut reader = UperReader::from((bytes, bit_len));
+    reader.set_tolerate_truncated_extensions(true);
+    reader
+}
+
+#[test]
+fn test_complete_encoding_is_not_recovered() {
+    // extension bit, id, extension bit
+    let mut reader = tolerant_reader(&[0x02, 0x80], 10);
+    assert_eq!(
+        Decoded {
+            value: message(),
+            recovered_truncation: false,
+        },
+        reader.read_decoded::<Message>().unwrap()
+    );
+}
+
+#[test]
+fn test_missing_extension_bit() {
+    // extension bit, id
+    let mut reader = UperReader::from((&[0x02, 0x80][..], 9));
+    assert_eq!(
+        &per::ErrorKind::EndOfStream,
+        reader.read::<Message>().unwrap_err().kind()
+    );
+
+    let mut reader = tolerant_reader(&[0x02, 0x80], 9);
+    assert_eq!(
+        Decoded {
+            value: message(),
+            recovered_truncation: true,
+        },
+        reader.read_decoded::<Message>().unwrap()
+    );
+}
+
+#[test]
+fn test_missing_extension_additions() {
+    // extension bit (set), id
+    let mut reader = UperReader::from((&[0x82, 0x80][..], 9));
+    assert_eq!(
+        &per::ErrorKind::EndOfStream,
+        reader.read::<Header>().unwrap_err().kind()
+    );
+
+    let mut reader = tolerant_reader(&[0x82, 0x80], 9);
+    assert_eq!(
+        Decoded {
+            value: message().header,
+            recovered_truncation: true,
+        },
+        reader.read_decoded::<Header>().unwrap()
+    );
+}
+
+#[test]
+fn test_truncation_elsewhere_still_fails() {
+    // extension bit, half of the id
+    let mut reader = tolerant_reader(&[0x02], 5);
+    assert!(matches!(
+        reader.read_decoded::<Header>().unwrap_err().kind(),
+        per::ErrorKind::InsufficientDataInSourceBuffer { .. }
+    ));
+}
@@ -0,0 +1,73 @@
+mod test_utils;
+
+use asn1rs::prelude::basic::DER;
+use test_utils::*;
+
+asn_to_rust!(
+    r"UperUntrustedInput DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Huge ::= SEQUENCE {
+        flag    BOOLEAN,
+        content OCTET STRING (SIZE(0..9223372036854775807))
+    }
+
+    Message ::= SEQUENCE {
+        id      INTEGER (0..255),
+        offset  INTEGER (-5..MAX),
+        payload OCTET STRING,
+        bits    BIT STRING,
+        text    UTF8String,
+        items   SEQUENCE (SIZE(1..4)) OF INTEGER (0..7),
+        ...,
+        trailer IA5String OPTIONAL
+    }
+
+    END"
+);
+
+fn message() -> Message {
+    Message {
+        id: 42,
+        offset: 1337,
+        payload: vec![1, 2, 3],
+        bits: BitVec::from_bytes(vec![0xAB, 0xC0], 10),
+        text: "untrusted".to_string(),
+        items: vec![1, 2, 3],
+        trailer: Some("extended".to_string()),
+    }
+}
+
+#[test]
+fn test_length_determinant_beyond_input_is_rejected_before_allocating() {
+    // flag, then a 63-bit length of (almost) i64::MAX followed by nothing
+    let mut reader = UperReader::from((&[0x7F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF][..], 64));
+    assert!(reader.read::<Huge>().is_err());
+}
+
+#[test]
+fn test_reading_beyond_the_last_byte_fails() {
+    let mut reader = UperReader::from((&[0xFF][..], 8));
+    assert!(reader.read::<Huge>().is_err());
+}
+
+#[test]
+fn test_mutated_encodings_do_not_panic() {
+    let mutator = UperMutator::new(&message()).unwrap();
+    for mutant in mutator.corpus().chain(mutator.random(0xC0FFEE, 1024)) {
+        let result = std::panic::catch_unwind(|| {
+            let mut reader = UperReader::from((&mutant.bytes[..], mutant.bit_len));
+            let _ = reader.read::<Message>();
+        });
+        assert!(result.is_ok(), "{} caused a panic", mutant.mutation);
+    }
+}
+
+#[test]
+fn test_unsupported_der_operations_fail_instead_of_panicking() {
+    let mut writer = DER::writer(Vec::new());
+    assert!(writer.write(&message()).is_err());
+
+    let mut reader = DER::reader(&[0x30, 0x00][..]);
+    assert!(reader.read::<Message>().is_err());
+}
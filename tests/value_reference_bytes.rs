@@ -0,0 +1,58 @@
+mod test_utils;
+
+use test_utils::*;
+
+asn_to_rust!(
+    r"ValueReferenceBytes DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    magic-bytes OCTET STRING ::= 'CAFE'H
+    magic-flags BIT STRING ::= 'A711'H
+    short-flags BIT STRING ::= '101'B
+    nibble-flags BIT STRING ::= 'A7C'H
+
+    Frame ::= SEQUENCE {
+        header OCTET STRING,
+        flags  BIT STRING
+    }
+
+    END"
+);
+
+#[test]
+fn test_octet_string_constant_is_a_byte_array() {
+    const BYTES: [u8; 2] = MAGIC_BYTES;
+    assert_eq!([0xCA, 0xFE], BYTES);
+
+    let frame = Frame {
+        header: MAGIC_BYTES.to_vec(),
+        flags: MAGIC_FLAGS.to_bit_vec(),
+    };
+    assert!(matches!(&frame, Frame { header, .. } if *header == MAGIC_BYTES));
+}
+
+#[test]
+fn test_bit_string_constant_is_a_const_bit_vec() {
+    const FLAGS: BitVecConst = MAGIC_FLAGS;
+    assert_eq!(16, FLAGS.bit_len());
+    assert_eq!(&[0xA7, 0x11], FLAGS.as_byte_slice());
+    assert!(FLAGS.is_bit_set(0));
+    assert!(!FLAGS.is_bit_set(1));
+    assert!(!FLAGS.is_bit_set(16));
+
+    let flags = BitVec::from_bytes(vec![0xA7, 0x11], 16);
+    assert_eq!(flags, MAGIC_FLAGS);
+    assert_eq!(MAGIC_FLAGS, flags);
+    assert_eq!(flags, BitVec::from(MAGIC_FLAGS));
+    assert_ne!(BitVec::from_bytes(vec![0xA7, 0x10], 16), MAGIC_FLAGS);
+}
+
+#[test]
+fn test_bit_string_constant_has_the_bits_of_the_literal() {
+    assert_eq!(3, SHORT_FLAGS.bit_len());
+    assert_eq!(&[0xA0], SHORT_FLAGS.as_byte_slice());
+    assert_eq!(BitVec::from_bytes(vec![0b1010_0000], 3), SHORT_FLAGS);
+
+    assert_eq!(12, NIBBLE_FLAGS.bit_len());
+    assert_eq!(&[0xA7, 0xC0], NIBBLE_FLAGS.as_byte_slice());
+}
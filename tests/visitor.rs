@@ -0,0 +1,202 @@
+use asn1rs::prelude::*;
+
+asn_to_rust!(
+    r"Visitor DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Vehicle ::= SEQUENCE {
+        speed INTEGER (0..255),
+        vin IA5String,
+        position Position OPTIONAL,
+        passengers SEQUENCE OF Person,
+        lights BIT STRING (SIZE(4)),
+        payload OCTET STRING,
+        occupant Occupant,
+        checks SEQUENCE OF NULL
+    }
+
+    Position ::= SEQUENCE {
+        lat INTEGER,
+        lon INTEGER
+    }
+
+    Person ::= SEQUENCE {
+        name UTF8String,
+        role Role,
+        seat INTEGER (1..9) OPTIONAL
+    }
+
+    Role ::= ENUMERATED {
+        driver,
+        passenger
+    }
+
+    Occupant ::= CHOICE {
+        person Person,
+        unknown NULL
+    }
+
+    END"
+);
+
+fn vehicle() -> Vehicle {
+    Vehicle {
+        speed: 42,
+        vin: "WVWZZZ1JZXW000001".to_string(),
+        position: None,
+        passengers: vec![
+            Person {
+                name: "Jane".to_string(),
+                role: Role::Driver,
+                seat: Some(1),
+            },
+            Person {
+                name: "John".to_string(),
+                role: Role::Passenger,
+                seat: None,
+            },
+        ],
+        lights: BitVec::from_bytes(vec![0b1010_0000], 4),
+        payload: vec![0xC0, 0xFF, 0xEE],
+        occupant: Occupant::Unknown(Null),
+        checks: vec![Null, Null],
+    }
+}
+
+/// Records every callback as a line
+#[derive(Default)]
+struct Recorder(Vec<String>);
+
+impl Visitor for Recorder {
+    fn enter(&mut self, path: &Path, definition: &'static str) {
+        self.0.push(format!("enter {definition} at '{path}'"));
+    }
+
+    fn leave(&mut self, path: &Path, definition: &'static str) {
+        self.0.push(format!("leave {definition} at '{path}'"));
+    }
+
+    fn visit_boolean(&mut self, path: &Path, value: bool) {
+        self.0.push(format!("{path}: {value}"));
+    }
+
+    fn visit_integer(&mut self, path: &Path, value: i128) {
+        self.0.push(format!("{path}: {value}"));
+    }
+
+    fn visit_string(&mut self, path: &Path, value: &str) {
+        self.0.push(format!("{path}: {value:?}"));
+    }
+
+    fn visit_octet_string(&mut self, path: &Path, value: &[u8]) {
+        self.0.push(format!("{path}: {value:02x?}"));
+    }
+
+    fn visit_bit_string(&mut self, path: &Path, value: &BitVec) {
+        self.0.push(format!("{path}: {} bits", value.bit_len()));
+    }
+
+    fn visit_enumerated(&mut self, path: &Path, variant: &'static str) {
+        self.0.push(format!("{path}: {variant}"));
+    }
+
+    fn visit_null(&mut self, path: &Path) {
+        self.0.push(format!("{path}: NULL"));
+    }
+
+    fn visit_absent(&mut self, path: &Path) {
+        self.0.push(format!("{path}: absent"));
+    }
+}
+
+#[test]
+fn test_all_values_are_visited_with_their_path() {
+    let mut recorder = Recorder::default();
+    vehicle().accept(&mut recorder);
+    assert_eq!(
+        vec![
+            "enter Vehicle at ''",
+            "speed: 42",
+            "vin: \"WVWZZZ1JZXW000001\"",
+            "position: absent",
+            "enter Person at 'passengers[0]'",
+            "passengers[0].name: \"Jane\"",
+            "enter Role at 'passengers[0].role'",
+            "passengers[0].role: Driver",
+            "leave Role at 'passengers[0].role'",
+            "passengers[0].seat: 1",
+            "leave Person at 'passengers[0]'",
+            "enter Person at 'passengers[1]'",
+            "passengers[1].name: \"John\"",
+            "enter Role at 'passengers[1].role'",
+            "passengers[1].role: Passenger",
+            "leave Role at 'passengers[1].role'",
+            "passengers[1].seat: absent",
+            "leave Person at 'passengers[1]'",
+            "lights: 4 bits",
+            "payload: [c0, ff, ee]",
+            "enter Occupant at 'occupant'",
+            "occupant.Unknown: NULL",
+            "leave Occupant at 'occupant'",
+            "checks[0]: NULL",
+            "checks[1]: NULL",
+            "leave Vehicle at ''",
+        ],
+        recorder.0
+    );
+}
+
+#[test]
+fn test_field_extraction() {
+    /// Collects the values of all fields with the given name, regardless of where they are
+    struct Names(Vec<String>);
+
+    impl Visitor for Names {
+        fn visit_string(&mut self, path: &Path, value: &str) {
+            if path.segments().last() == Some(&Segment::Field("name")) {
+                self.0.push(value.to_string());
+            }
+        }
+    }
+
+    let mut names = Names(Vec::new());
+    vehicle().accept(&mut names);
+    assert_eq!(vec!["Jane", "John"], names.0);
+
+    let mut names = Names(Vec::new());
+    Occupant::Person(vehicle().passengers.remove(1)).accept(&mut names);
+    assert_eq!(vec!["John"], names.0);
+}
+
+#[test]
+fn test_statistics() {
+    #[derive(Default)]
+    struct Statistics {
+        definitions: usize,
+        integers: usize,
+        absent: usize,
+        deepest: usize,
+    }
+
+    impl Visitor for Statistics {
+        fn enter(&mut self, path: &Path, _definition: &'static str) {
+            self.definitions += 1;
+            self.deepest = self.deepest.max(path.segments().len());
+        }
+
+        fn visit_integer(&mut self, _path: &Path, _value: i128) {
+            self.integers += 1;
+        }
+
+        fn visit_absent(&mut self, _path: &Path) {
+            self.absent += 1;
+        }
+    }
+
+    let mut statistics = Statistics::default();
+    vehicle().accept(&mut statistics);
+    assert_eq!(6, statistics.definitions);
+    assert_eq!(2, statistics.integers);
+    assert_eq!(2, statistics.absent);
+    assert_eq!(3, statistics.deepest);
+}